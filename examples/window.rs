@@ -319,7 +319,7 @@ impl ApplicationHandler<UserEvent> for Application {
         };
 
         match event {
-            WindowEvent::Resized(size) => {
+            WindowEvent::Resized { size, .. } => {
                 window.resize(size);
             },
             WindowEvent::Focused(focused) => {
@@ -384,6 +384,9 @@ impl ApplicationHandler<UserEvent> for Application {
                     self.handle_action(event_loop, window_id, action);
                 }
             },
+            WindowEvent::CursorEnteredFrom(Some(from)) => {
+                info!("Cursor entered Window={window_id:?} from Window={from:?}");
+            },
             WindowEvent::CursorLeft { .. } => {
                 info!("Cursor left Window={window_id:?}");
                 window.cursor_left();
@@ -403,12 +406,20 @@ impl ApplicationHandler<UserEvent> for Application {
             },
             WindowEvent::Ime(event) => match event {
                 Ime::Enabled => info!("IME enabled for Window={window_id:?}"),
-                Ime::Preedit(text, caret_pos) => {
-                    info!("Preedit: {}, with caret at {:?}", text, caret_pos);
+                Ime::Preedit(text, caret_pos, spans) => {
+                    info!(
+                        "Preedit: {}, with caret at {:?}, styled as {:?}",
+                        text, caret_pos, spans
+                    );
                 },
                 Ime::Commit(text) => {
                     info!("Committed: {}", text);
                 },
+                Ime::DeleteSurrounding { before_bytes, after_bytes } => {
+                    info!(
+                        "Delete {before_bytes} bytes before and {after_bytes} after the selection"
+                    );
+                },
                 Ime::Disabled => info!("IME disabled for Window={window_id:?}"),
             },
             WindowEvent::PinchGesture { delta, .. } => {
@@ -437,15 +448,51 @@ impl ApplicationHandler<UserEvent> for Application {
             WindowEvent::DoubleTapGesture { .. } => {
                 info!("Smart zoom");
             },
+            WindowEvent::SystemGestureDeferred { .. } => {
+                info!("Swiped from an edge with deferred system gestures");
+            },
+            WindowEvent::KeyboardVisibilityChanged { visible, occluded_rect } => {
+                info!("On-screen keyboard visible: {visible}, covering {occluded_rect:?}");
+            },
+            WindowEvent::VisibilityChanged(visibility) => {
+                info!("Window={window_id:?} visibility changed to {visibility:?}");
+            },
+            WindowEvent::MouseClick { button, click_count, .. } if click_count > 1 => {
+                info!("Clicked {button:?} {click_count} times");
+            },
+            WindowEvent::PanEnded { velocity, .. } => {
+                info!("Pan ended with a velocity of {velocity:?}");
+            },
+            WindowEvent::LongPress { position, .. } => {
+                info!("Long press at {position:?}");
+            },
+            WindowEvent::ContextMenuRequested { position, kind } => {
+                info!("Context menu requested at {position:?} with {kind:?}");
+            },
+            WindowEvent::DroppedData(data) => {
+                info!("Dropped data of types {:?}", data.types().collect::<Vec<_>>());
+            },
             WindowEvent::TouchpadPressure { .. }
+            | WindowEvent::TouchBar(_)
             | WindowEvent::HoveredFileCancelled
             | WindowEvent::KeyboardInput { .. }
             | WindowEvent::CursorEntered { .. }
+            | WindowEvent::CursorEnteredFrom(None)
             | WindowEvent::AxisMotion { .. }
             | WindowEvent::DroppedFile(_)
             | WindowEvent::HoveredFile(_)
             | WindowEvent::Destroyed
             | WindowEvent::Touch(_)
+            | WindowEvent::GraphicsContextLost
+            | WindowEvent::GraphicsContextRestored
+            | WindowEvent::TransitionStarted(_)
+            | WindowEvent::TransitionEnded(_)
+            | WindowEvent::PrintRequested(_)
+            | WindowEvent::ShareCompleted(_)
+            | WindowEvent::MoveResizeBegan
+            | WindowEvent::MoveResizeEnded
+            | WindowEvent::TilingStateChanged(_)
+            | WindowEvent::MouseClick { .. }
             | WindowEvent::Moved(_) => (),
         }
     }
@@ -595,7 +642,9 @@ impl WindowState {
     /// Toggle window decorations.
     fn toggle_decorations(&self) {
         let decorated = self.window.is_decorated();
-        self.window.set_decorations(!decorated);
+        if let Err(err) = self.window.set_decorations(!decorated) {
+            error!("Error toggling decorations: {err}");
+        }
     }
 
     /// Toggle window resizable state.
@@ -0,0 +1,318 @@
+//! Running a single instance of the application, see [`SingleInstance`].
+//!
+//! Desktop applications are usually expected to open a document or a URL in the window that is
+//! already there, instead of starting a second copy of themselves. The first instance to
+//! [`claim`] the application id becomes the primary one, every instance started afterwards
+//! forwards its command line to it and exits. The primary instance receives those as
+//! [`ApplicationHandler::activation_request`].
+//!
+//! ```no_run
+//! use winit::app::SingleInstance;
+//! use winit::event_loop::EventLoop;
+//!
+//! // Exits the process if the application is running already.
+//! let mut instance = SingleInstance::claim("org.example.Editor").unwrap();
+//!
+//! let event_loop = EventLoop::new().unwrap();
+//! instance.listen(&event_loop);
+//! // Stays the primary instance until `instance` is dropped.
+//! ```
+//!
+//! [`claim`]: SingleInstance::claim
+//! [`ApplicationHandler::activation_request`]: crate::application::ApplicationHandler::activation_request
+
+use std::ffi::{OsStr, OsString};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::{env, fmt, process, thread};
+
+use crate::event::Url;
+use crate::event_loop::EventLoop;
+use crate::window::ActivationToken;
+
+#[cfg(any(x11_platform, wayland_platform, macos_platform))]
+#[path = "app/unix.rs"]
+mod ipc;
+#[cfg(windows_platform)]
+#[path = "app/windows.rs"]
+mod ipc;
+#[cfg(not(any(x11_platform, wayland_platform, macos_platform, windows_platform)))]
+#[path = "app/unsupported.rs"]
+mod ipc;
+
+/// The schemes set with [`EventLoopBuilder::with_url_schemes()`].
+///
+/// [`EventLoopBuilder::with_url_schemes()`]: crate::event_loop::EventLoopBuilder::with_url_schemes
+pub(crate) static URL_SCHEMES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// Set with [`EventLoopBuilder::with_file_arguments()`].
+///
+/// [`EventLoopBuilder::with_file_arguments()`]: crate::event_loop::EventLoopBuilder::with_file_arguments
+pub(crate) static FILE_ARGUMENTS: AtomicBool = AtomicBool::new(false);
+
+/// The outcome of claiming the application id on a platform.
+#[allow(dead_code)] // Not every variant is used on every platform
+enum Claim {
+    /// No other instance is running, the listener receives the requests of later ones.
+    Primary(ipc::Listener),
+    /// Another instance is running, and the stream is connected to it.
+    Secondary(ipc::Stream),
+    /// The platform only ever runs a single instance of an application.
+    Unsupported,
+}
+
+/// The primary instance of the application, see the [module-level docs](self).
+///
+/// Dropping it gives up being the primary instance.
+#[must_use = "dropping the instance gives up being the primary instance"]
+pub struct SingleInstance {
+    listener: Option<Arc<ipc::Listener>>,
+    /// Tells the thread receiving the requests to stop.
+    closed: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl SingleInstance {
+    /// Becomes the primary instance of the application identified by `app_id`, or forwards the
+    /// command line to the primary instance and exits the process.
+    ///
+    /// The forwarded request contains the arguments of this process without the program name,
+    /// and the activation token this process was started with. This should be called early, before
+    /// creating the event loop or any window, so that a forwarding instance doesn't flash on the
+    /// screen.
+    ///
+    /// The `app_id` should be unique to the application, like a reverse domain name, and must not
+    /// contain path separators. It is scoped to the current user.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Linux / macOS:** The instances talk through a Unix domain socket in `$XDG_RUNTIME_DIR`,
+    ///   or if that isn't set, in a directory of the temporary directory that only the current user
+    ///   can access. Fails with [`io::ErrorKind::PermissionDenied`] if that directory exists and is
+    ///   accessible by other users.
+    /// - **Windows:** The instances talk through a named pipe. The forwarding instance allows the
+    ///   primary one to take the focus from it.
+    /// - **Android / iOS / Orbital / Web:** The system only runs a single instance, this always
+    ///   becomes the primary instance and requests are never delivered.
+    pub fn claim(app_id: &str) -> io::Result<Self> {
+        let _span = tracing::debug_span!("winit::SingleInstance::claim", app_id).entered();
+
+        if app_id.is_empty() || app_id.contains(['/', '\\', '\0']) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid application id"));
+        }
+
+        let listener = match ipc::claim(app_id)? {
+            Claim::Primary(listener) => Some(Arc::new(listener)),
+            Claim::Secondary(mut stream) => {
+                let request = Request {
+                    args: env::args_os().skip(1).collect(),
+                    token: ["XDG_ACTIVATION_TOKEN", "DESKTOP_STARTUP_ID"]
+                        .into_iter()
+                        .find_map(|var| env::var(var).ok()),
+                    working_dir: env::current_dir()?,
+                };
+                stream.write_all(&request.encode())?;
+                stream.flush()?;
+                drop(stream);
+                process::exit(0)
+            },
+            Claim::Unsupported => None,
+        };
+        Ok(Self { listener, closed: Arc::new(AtomicBool::new(false)), thread: None })
+    }
+
+    /// Starts delivering the requests of later instances to the event loop, as
+    /// [`ApplicationHandler::activation_request`].
+    ///
+    /// Requests are received on a background thread, which wakes up the event loop for each of
+    /// them. The application stays the primary instance until this is dropped, requests that
+    /// arrive once the event loop was dropped are discarded. Does nothing if this is listening
+    /// already.
+    ///
+    /// [`ApplicationHandler::activation_request`]: crate::application::ApplicationHandler::activation_request
+    pub fn listen<T: Send + 'static>(&mut self, event_loop: &EventLoop<T>) {
+        let _span = tracing::debug_span!("winit::SingleInstance::listen").entered();
+
+        let Some(listener) = self.listener.clone() else {
+            return;
+        };
+        if self.thread.is_some() {
+            return;
+        }
+        let closed = self.closed.clone();
+        let proxy = event_loop.create_proxy();
+        let thread = thread::Builder::new()
+            .name(String::from("winit single instance"))
+            .spawn(move || loop {
+                let mut stream = match listener.accept() {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        tracing::error!("failed to wait for activation requests: {err}");
+                        break;
+                    },
+                };
+                if closed.load(Ordering::Acquire) {
+                    break;
+                }
+                let mut message = Vec::new();
+                if let Err(err) = stream.read_to_end(&mut message) {
+                    tracing::warn!("failed to receive an activation request: {err}");
+                    continue;
+                }
+                let Some(request) = Request::decode(&message) else {
+                    tracing::warn!("received a malformed activation request");
+                    continue;
+                };
+                let files = files(&request.args, &request.working_dir);
+                let activation_token = request.token.map(ActivationToken::_new);
+                if proxy.send_activation_request(request.args, activation_token, files).is_err() {
+                    // The event loop is gone, so nothing receives the requests anymore.
+                    break;
+                }
+            })
+            .expect("failed to spawn the single instance thread");
+        self.thread = Some(thread);
+    }
+}
+
+impl Drop for SingleInstance {
+    fn drop(&mut self) {
+        let (Some(listener), Some(thread)) = (&self.listener, self.thread.take()) else {
+            return;
+        };
+        self.closed.store(true, Ordering::Release);
+        // Connect to ourselves, so that the thread stops waiting for the next instance.
+        match listener.wake() {
+            Ok(()) => {
+                let _ = thread.join();
+            },
+            Err(err) => tracing::warn!("failed to stop receiving activation requests: {err}"),
+        }
+    }
+}
+
+impl fmt::Debug for SingleInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SingleInstance").finish_non_exhaustive()
+    }
+}
+
+/// The URLs with a scheme registered for the application among command line arguments.
+pub(crate) fn urls<S: AsRef<OsStr>>(args: &[S]) -> Vec<Url> {
+    let schemes = URL_SCHEMES.lock().unwrap();
+    args.iter()
+        .filter_map(|arg| Url::parse(arg.as_ref().to_str()?))
+        .filter(|url| schemes.iter().any(|scheme| scheme.eq_ignore_ascii_case(url.scheme())))
+        .collect()
+}
+
+/// The existing files among command line arguments, which is how the system passes the documents
+/// to open to applications, if the application opted into it.
+pub(crate) fn files<S: AsRef<OsStr>>(args: &[S], working_dir: &Path) -> Vec<PathBuf> {
+    if !FILE_ARGUMENTS.load(Ordering::Relaxed) {
+        return Vec::new();
+    }
+    args.iter()
+        .map(AsRef::as_ref)
+        .filter(|arg| {
+            let text = arg.to_string_lossy();
+            !text.starts_with('-') && Url::parse(&text).is_none()
+        })
+        .map(|arg| working_dir.join(arg))
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// The command line of another instance.
+#[derive(Debug, PartialEq, Eq)]
+struct Request {
+    args: Vec<OsString>,
+    token: Option<String>,
+    /// The base of relative paths among the arguments.
+    working_dir: PathBuf,
+}
+
+impl Request {
+    /// Every field starts with its length in bytes, as a little endian `u32`. The token comes
+    /// first, empty if there is none, then the working directory. Arguments and paths are encoded
+    /// the way the platform stores them, so that they don't need to be valid Unicode.
+    fn encode(&self) -> Vec<u8> {
+        let mut message = Vec::new();
+        let token = self.token.as_deref().unwrap_or_default().as_bytes().to_vec();
+        let fields = [token, ipc::encode_os_str(self.working_dir.as_os_str())]
+            .into_iter()
+            .chain(self.args.iter().map(|arg| ipc::encode_os_str(arg)));
+        for field in fields {
+            message.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            message.extend_from_slice(&field);
+        }
+        message
+    }
+
+    fn decode(mut message: &[u8]) -> Option<Self> {
+        let mut fields = Vec::new();
+        while !message.is_empty() {
+            let len = u32::from_le_bytes(message.get(..4)?.try_into().unwrap()) as usize;
+            let end = len.checked_add(4)?;
+            fields.push(message.get(4..end)?.to_vec());
+            message = &message[end..];
+        }
+
+        let mut fields = fields.into_iter();
+        let token = String::from_utf8(fields.next()?).ok()?;
+        let working_dir = PathBuf::from(ipc::decode_os_str(fields.next()?)?);
+        let args = fields.map(ipc::decode_os_str).collect::<Option<_>>()?;
+        Some(Self { args, token: Some(token).filter(|token| !token.is_empty()), working_dir })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn message() {
+        let request = Request {
+            args: vec![OsString::from("--new-window"), OsString::new(), OsString::from("añb.txt")],
+            token: Some(String::from("token")),
+            working_dir: PathBuf::from("/home/user"),
+        };
+        assert_eq!(Request::decode(&request.encode()), Some(request));
+
+        let request = Request { args: Vec::new(), token: None, working_dir: PathBuf::from("/") };
+        assert_eq!(Request::decode(&request.encode()), Some(request));
+
+        assert_eq!(Request::decode(b""), None);
+        assert_eq!(Request::decode(b"\0\0\0\0"), None);
+        assert_eq!(Request::decode(b"\0\0\0\0\x01\0\0\0/\x03\0\0\0ar"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn message_with_invalid_unicode() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let arg = OsStr::from_bytes(b"\xff.txt").to_owned();
+        let request = Request { args: vec![arg], token: None, working_dir: PathBuf::from("/tmp") };
+        assert_eq!(Request::decode(&request.encode()), Some(request));
+    }
+
+    #[test]
+    fn urls_in_args() {
+        *URL_SCHEMES.lock().unwrap() = vec![String::from("myapp")];
+        let args = ["localhost:8080", "MyApp://open", "other:path", "myapp:"];
+        assert_eq!(urls(&args), [Url::parse("MyApp://open").unwrap()]);
+    }
+
+    #[test]
+    fn files_in_args() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let args = ["Cargo.toml", "--manifest", "missing.txt", "myapp://Cargo.toml"];
+        FILE_ARGUMENTS.store(true, Ordering::Relaxed);
+        assert_eq!(files(&args, dir), [dir.join("Cargo.toml")]);
+        FILE_ARGUMENTS.store(false, Ordering::Relaxed);
+        assert_eq!(files(&args, dir), Vec::<PathBuf>::new());
+    }
+}
@@ -0,0 +1,99 @@
+//! Single instances talking through a Unix domain socket.
+
+use std::ffi::{OsStr, OsString};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::{env, fs, io};
+
+use super::Claim;
+
+/// How often to retry when another instance claims the socket at the same time.
+const ATTEMPTS: usize = 3;
+
+pub(super) struct Listener {
+    listener: UnixListener,
+    path: PathBuf,
+}
+
+impl Listener {
+    pub fn accept(&self) -> io::Result<UnixStream> {
+        self.listener.accept().map(|(stream, _)| stream)
+    }
+
+    /// Makes a pending [`accept`](Self::accept) return.
+    pub fn wake(&self) -> io::Result<()> {
+        UnixStream::connect(&self.path).map(drop)
+    }
+}
+
+impl Drop for Listener {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+pub(super) type Stream = UnixStream;
+
+pub(super) fn encode_os_str(arg: &OsStr) -> Vec<u8> {
+    arg.as_bytes().to_vec()
+}
+
+pub(super) fn decode_os_str(bytes: Vec<u8>) -> Option<OsString> {
+    Some(OsString::from_vec(bytes))
+}
+
+/// The directory of the sockets, which only the current user can access.
+fn socket_dir() -> io::Result<PathBuf> {
+    if let Some(dir) = env::var_os("XDG_RUNTIME_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+
+    // The temporary directory is shared between users on Linux, unlike the runtime directory.
+    // Another user could create the directory first to pose as the primary instance, so only use
+    // it if it is private.
+    let uid = unsafe { libc::getuid() };
+    let dir = env::temp_dir().join(format!("winit-{uid}"));
+    match fs::DirBuilder::new().mode(0o700).create(&dir) {
+        Ok(()) => (),
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => (),
+        Err(err) => return Err(err),
+    }
+    let metadata = fs::symlink_metadata(&dir)?;
+    if !metadata.is_dir() || metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{} is accessible by other users", dir.display()),
+        ));
+    }
+    Ok(dir)
+}
+
+pub(super) fn claim(app_id: &str) -> io::Result<Claim> {
+    let path = socket_dir()?.join(format!("{app_id}.instance"));
+    for _ in 0..ATTEMPTS {
+        match UnixStream::connect(&path) {
+            Ok(stream) => return Ok(Claim::Secondary(stream)),
+            // The socket of an instance that didn't exit cleanly.
+            Err(err) if err.kind() == io::ErrorKind::ConnectionRefused => {
+                let _ = fs::remove_file(&path);
+            },
+            Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+            Err(err) => return Err(err),
+        }
+
+        match UnixListener::bind(&path) {
+            Ok(listener) => {
+                let listener = Listener { listener, path };
+                fs::set_permissions(&listener.path, fs::Permissions::from_mode(0o600))?;
+                return Ok(Claim::Primary(listener));
+            },
+            // Another instance was faster, connect to it instead.
+            Err(err) if err.kind() == io::ErrorKind::AddrInUse => (),
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(io::Error::new(io::ErrorKind::AddrInUse, "the instance socket is claimed repeatedly"))
+}
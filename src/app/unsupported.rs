@@ -0,0 +1,33 @@
+//! Platforms that only run a single instance of an application on their own.
+
+use std::ffi::{OsStr, OsString};
+use std::io;
+
+use super::Claim;
+
+pub(super) enum Listener {}
+
+impl Listener {
+    pub fn accept(&self) -> io::Result<io::Empty> {
+        match *self {}
+    }
+
+    pub fn wake(&self) -> io::Result<()> {
+        match *self {}
+    }
+}
+
+pub(super) type Stream = io::Sink;
+
+// Requests are never sent on these platforms.
+pub(super) fn encode_os_str(arg: &OsStr) -> Vec<u8> {
+    arg.to_string_lossy().into_owned().into_bytes()
+}
+
+pub(super) fn decode_os_str(bytes: Vec<u8>) -> Option<OsString> {
+    String::from_utf8(bytes).ok().map(OsString::from)
+}
+
+pub(super) fn claim(_app_id: &str) -> io::Result<Claim> {
+    Ok(Claim::Unsupported)
+}
@@ -0,0 +1,130 @@
+//! Single instances talking through a named pipe.
+
+use std::ffi::{OsStr, OsString};
+use std::fs::{File, OpenOptions};
+use std::os::windows::ffi::{OsStrExt, OsStringExt};
+use std::os::windows::io::{AsRawHandle, FromRawHandle, RawHandle};
+use std::sync::Mutex;
+use std::{env, io, iter, mem, ptr};
+
+use windows_sys::Win32::Foundation::{
+    ERROR_ACCESS_DENIED, ERROR_PIPE_BUSY, ERROR_PIPE_CONNECTED, HANDLE, INVALID_HANDLE_VALUE,
+};
+use windows_sys::Win32::Storage::FileSystem::{FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_INBOUND};
+use windows_sys::Win32::System::Pipes::{
+    ConnectNamedPipe, CreateNamedPipeW, WaitNamedPipeW, NMPWAIT_USE_DEFAULT_WAIT,
+    PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE, PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{AllowSetForegroundWindow, ASFW_ANY};
+
+use super::Claim;
+
+/// How often to retry when the primary instance is busy or exits at the same time.
+const ATTEMPTS: usize = 3;
+const BUFFER_SIZE: u32 = 4096;
+
+pub(super) struct Listener {
+    path: String,
+    name: Vec<u16>,
+    /// The instance of the pipe that the next client connects to.
+    pipe: Mutex<File>,
+}
+
+impl Listener {
+    pub fn accept(&self) -> io::Result<File> {
+        let mut pipe = self.pipe.lock().unwrap();
+        if unsafe { ConnectNamedPipe(pipe.as_raw_handle() as HANDLE, ptr::null_mut()) } == 0 {
+            let err = io::Error::last_os_error();
+            // The client connected before waiting for it.
+            if err.raw_os_error() != Some(ERROR_PIPE_CONNECTED as i32) {
+                return Err(err);
+            }
+        }
+
+        // Create the next instance right away, otherwise the pipe would disappear while the
+        // client is handled, and a new instance would become the primary one.
+        let next = create_pipe(&self.name, false)?;
+        Ok(mem::replace(&mut *pipe, next))
+    }
+
+    /// Makes a pending [`accept`](Self::accept) return.
+    pub fn wake(&self) -> io::Result<()> {
+        OpenOptions::new().write(true).open(&self.path).map(drop)
+    }
+}
+
+pub(super) type Stream = File;
+
+/// The UTF-16 code units in little endian, which keeps unpaired surrogates.
+pub(super) fn encode_os_str(arg: &OsStr) -> Vec<u8> {
+    arg.encode_wide().flat_map(u16::to_le_bytes).collect()
+}
+
+pub(super) fn decode_os_str(bytes: Vec<u8>) -> Option<OsString> {
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let wide: Vec<u16> =
+        bytes.chunks_exact(2).map(|unit| u16::from_le_bytes([unit[0], unit[1]])).collect();
+    Some(OsString::from_wide(&wide))
+}
+
+fn create_pipe(name: &[u16], first: bool) -> io::Result<File> {
+    let mut open_mode = PIPE_ACCESS_INBOUND;
+    if first {
+        open_mode |= FILE_FLAG_FIRST_PIPE_INSTANCE;
+    }
+    let handle = unsafe {
+        CreateNamedPipeW(
+            name.as_ptr(),
+            open_mode,
+            PIPE_TYPE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+            PIPE_UNLIMITED_INSTANCES,
+            0,
+            BUFFER_SIZE,
+            0,
+            ptr::null(),
+        )
+    };
+    if handle == INVALID_HANDLE_VALUE {
+        return Err(io::Error::last_os_error());
+    }
+
+    // SAFETY: The handle was just created and is owned by the file from now on.
+    Ok(unsafe { File::from_raw_handle(handle as RawHandle) })
+}
+
+pub(super) fn claim(app_id: &str) -> io::Result<Claim> {
+    // Pipes are shared between all users of the system.
+    let user = env::var("USERNAME").unwrap_or_default();
+    let name = format!(r"\\.\pipe\{app_id}-{user}");
+    let wide_name: Vec<u16> = OsStr::new(&name).encode_wide().chain(iter::once(0)).collect();
+
+    for _ in 0..ATTEMPTS {
+        match create_pipe(&wide_name, true) {
+            Ok(pipe) => {
+                let pipe = Mutex::new(pipe);
+                return Ok(Claim::Primary(Listener { path: name, name: wide_name, pipe }));
+            },
+            Err(err) if err.raw_os_error() == Some(ERROR_ACCESS_DENIED as i32) => (),
+            Err(err) => return Err(err),
+        }
+
+        match OpenOptions::new().write(true).open(&name) {
+            Ok(stream) => {
+                // Only the process that was started last may take the focus, hand that over to
+                // the primary instance.
+                unsafe { AllowSetForegroundWindow(ASFW_ANY) };
+                return Ok(Claim::Secondary(stream));
+            },
+            Err(err) if err.raw_os_error() == Some(ERROR_PIPE_BUSY as i32) => unsafe {
+                WaitNamedPipeW(wide_name.as_ptr(), NMPWAIT_USE_DEFAULT_WAIT);
+            },
+            // The primary instance exited in the meantime.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => (),
+            Err(err) => return Err(err),
+        }
+    }
+
+    Err(io::Error::new(io::ErrorKind::AddrInUse, "the instance pipe is claimed repeatedly"))
+}
@@ -1,8 +1,22 @@
 //! End user application handling.
 
-use crate::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
-use crate::event_loop::ActiveEventLoop;
-use crate::window::WindowId;
+use std::ffi::OsString;
+use std::path::PathBuf;
+#[cfg(not(web_platform))]
+use std::time::Instant;
+
+#[cfg(web_platform)]
+use web_time::Instant;
+
+use crate::dpi::PhysicalPosition;
+use crate::event::{
+    CloseResponse, DeviceEvent, DeviceId, DropOperation, PopState, Signal, StartCause, Url,
+    WindowEvent,
+};
+use crate::event_loop::{ActiveEventLoop, Priority};
+use crate::locale::LanguageTag;
+use crate::notification::{NotificationEvent, NotificationId};
+use crate::window::{ActivationToken, WindowId};
 
 /// The handler of the application events.
 pub trait ApplicationHandler<T: 'static = ()> {
@@ -16,6 +30,24 @@ pub trait ApplicationHandler<T: 'static = ()> {
         let _ = (event_loop, cause);
     }
 
+    /// Emitted once when the application finished launching, before it is [`resumed`] for the
+    /// first time.
+    ///
+    /// This is the place for one-time setup that doesn't need a render surface, like loading
+    /// settings or connecting to services, which would otherwise have to be guarded against
+    /// repeated [`resumed`] events. It follows [`new_events`] with [`StartCause::Init`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Emitted from `application:didFinishLaunchingWithOptions:`.
+    /// - **macOS:** Emitted from `applicationDidFinishLaunching:`.
+    ///
+    /// [`resumed`]: Self::resumed
+    /// [`new_events`]: Self::new_events
+    fn init(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
     /// Emitted when the application has been resumed.
     ///
     /// For consistency, all platforms emit a `Resumed` event even if they don't themselves have a
@@ -89,6 +121,20 @@ pub trait ApplicationHandler<T: 'static = ()> {
         let _ = (event_loop, event);
     }
 
+    /// Emitted when the application has been woken up by [`EventLoopProxy::wake_up_with`].
+    ///
+    /// Wake ups of the same [`Priority`] are coalesced, so this is called at most once per
+    /// priority for all the calls to [`EventLoopProxy::wake_up_with`] since the last time. Wake
+    /// ups with [`Priority::High`] and [`Priority::Normal`] are delivered as soon as the event
+    /// loop sees them, in that order, while those with [`Priority::Low`] are held back until all
+    /// pending events were handled, right before [`about_to_wait`].
+    ///
+    /// [`EventLoopProxy::wake_up_with`]: crate::event_loop::EventLoopProxy::wake_up_with
+    /// [`about_to_wait`]: Self::about_to_wait
+    fn proxy_wake_up(&mut self, event_loop: &ActiveEventLoop, priority: Priority) {
+        let _ = (event_loop, priority);
+    }
+
     /// Emitted when the OS sends an event to a winit window.
     fn window_event(
         &mut self,
@@ -107,6 +153,56 @@ pub trait ApplicationHandler<T: 'static = ()> {
         let _ = (event_loop, device_id, event);
     }
 
+    /// Emitted when the rendering surface of the window became available, i.e. when its
+    /// [raw window handle] can be used to create a graphics surface, e.g. a [`wgpu::Surface`].
+    ///
+    /// It is always followed by [`surface_destroyed`] once the surface goes away, after which it
+    /// might be emitted again.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Emitted after [`resumed`], when the [`SurfaceView`] was created.
+    /// - **Web:** Emitted once the canvas is attached to the document, and when its WebGL context
+    ///   was restored after being lost.
+    /// - **iOS / macOS / Orbital / Wayland / Windows / X11:** The surface lives as long as the
+    ///   window, so this is emitted once after the window was created, before any other event of
+    ///   the window.
+    /// - **macOS / Windows:** Also emitted when a window hidden with [`Window::hide_to_tray()`] is
+    ///   shown again.
+    ///
+    /// [`Window::hide_to_tray()`]: crate::window::Window::hide_to_tray
+    /// [raw window handle]: https://docs.rs/raw-window-handle
+    /// [`wgpu::Surface`]: https://docs.rs/wgpu/latest/wgpu/struct.Surface.html
+    /// [`surface_destroyed`]: Self::surface_destroyed
+    /// [`resumed`]: Self::resumed
+    /// [`SurfaceView`]: https://developer.android.com/reference/android/view/SurfaceView
+    fn surface_created(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        let _ = (event_loop, window_id);
+    }
+
+    /// Emitted when the rendering surface of the window is about to go away.
+    ///
+    /// Graphics surfaces created from the window must be dropped before returning, until the
+    /// next [`surface_created`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Emitted before [`suspended`], when the [`SurfaceView`] is destroyed.
+    /// - **Web:** Emitted when the canvas is detached from the document, or when its WebGL context
+    ///   was lost. The browser is asked to restore the context.
+    /// - **iOS / macOS / Orbital / Wayland / Windows / X11:** Emitted right before
+    ///   [`WindowEvent::Destroyed`].
+    /// - **macOS / Windows:** Also emitted when the window is hidden with
+    ///   [`Window::hide_to_tray()`].
+    ///
+    /// [`Window::hide_to_tray()`]: crate::window::Window::hide_to_tray
+    /// [`surface_created`]: Self::surface_created
+    /// [`suspended`]: Self::suspended
+    /// [`SurfaceView`]: https://developer.android.com/reference/android/view/SurfaceView
+    fn surface_destroyed(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        let _ = (event_loop, window_id);
+    }
+
     /// Emitted when the event loop is about to block and wait for new events.
     ///
     /// Most applications shouldn't need to hook into this event since there is no real relationship
@@ -122,6 +218,29 @@ pub trait ApplicationHandler<T: 'static = ()> {
         let _ = event_loop;
     }
 
+    /// Emitted when the event loop would otherwise sleep, with the time until which the
+    /// application can do low priority work without delaying the next events.
+    ///
+    /// This is a good place for incremental work such as garbage collection or loading assets,
+    /// which should be split into small steps that stop once `deadline` is reached.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The deadline is the one given by [`requestIdleCallback()`], and this is only
+    ///   emitted if the browser supports it.
+    /// - **Others:** Emitted right after [`about_to_wait`], unless the [`ControlFlow`] is [`Poll`].
+    ///   The deadline is the [`WaitUntil`] time, capped to 50ms from now, the same maximum idle
+    ///   period the Web uses.
+    ///
+    /// [`requestIdleCallback()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback
+    /// [`about_to_wait`]: Self::about_to_wait
+    /// [`ControlFlow`]: crate::event_loop::ControlFlow
+    /// [`Poll`]: crate::event_loop::ControlFlow::Poll
+    /// [`WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+    fn idle(&mut self, event_loop: &ActiveEventLoop, deadline: Instant) {
+        let _ = (event_loop, deadline);
+    }
+
     /// Emitted when the application has been suspended.
     ///
     /// # Portability
@@ -187,6 +306,88 @@ pub trait ApplicationHandler<T: 'static = ()> {
         let _ = event_loop;
     }
 
+    /// Emitted when the whole application is asked to close by the system or the user, as opposed
+    /// to a single window with [`WindowEvent::CloseRequested`].
+    ///
+    /// The close proceeds once this returns, unless it is cancelled with
+    /// [`CloseResponse::cancel`], e.g. to ask the user to save unsaved changes first. Calling
+    /// [`ActiveEventLoop::exit_with`] instead delays the close until the cleanup completed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted from `applicationShouldTerminate:`, e.g. when quitting the application
+    ///   from the Dock or with `Cmd+Q`.
+    /// - **Web:** Emitted from the [`beforeunload`] event, e.g. when closing the tab. Cancelling it
+    ///   makes the browser ask the user for confirmation, which browsers only do if the user has
+    ///   interacted with the page. Firefox doesn't put pages that listen to `beforeunload` in the
+    ///   [`bfcache`].
+    /// - **Wayland / X11:** Emitted when the user logs out or shuts down, if the session manager
+    ///   speaks the X Session Management Protocol, like the ones of GNOME and KDE Plasma, and
+    ///   `libSM.so.6` is installed. The session manager waits for the application until this
+    ///   returns, so [`ActiveEventLoop::exit_with`] doesn't delay the end of the session.
+    /// - **Windows:** Emitted from `WM_QUERYENDSESSION` when the user logs off or shuts down.
+    ///   Windows may end the session regardless after showing the user which applications block it.
+    /// - **Android / iOS / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::CloseRequested`]: crate::event::WindowEvent::CloseRequested
+    /// [`beforeunload`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/beforeunload_event
+    /// [`bfcache`]: https://web.dev/bfcache/
+    fn close_requested_by_os(&mut self, event_loop: &ActiveEventLoop, respond: CloseResponse) {
+        let _ = (event_loop, respond);
+    }
+
+    /// Emitted when the user interacted with a notification shown with
+    /// [`ActiveEventLoop::show_notification`].
+    ///
+    /// `id` is the [`NotificationHandle::id`] of the notification.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Clicking a notification activates the application first. Notifications shown by
+    ///   a previous run of the application aren't reported.
+    /// - **Windows:** [`NotificationEvent::Dismissed`] is also emitted when the notification times
+    ///   out and moves to the notification center.
+    ///
+    /// [`NotificationHandle::id`]: crate::notification::NotificationHandle::id
+    fn notification_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        id: NotificationId,
+        event: NotificationEvent,
+    ) {
+        let _ = (event_loop, id, event);
+    }
+
+    /// Emitted when the system is about to terminate the application, the last chance to save
+    /// its state.
+    ///
+    /// Unlike [`close_requested_by_os`], this can't be cancelled, and the event loop exits
+    /// afterwards. As the process might end as soon as this returns, teardown must be done before
+    /// returning.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / macOS:** Emitted from `applicationWillTerminate:`, followed by [`exiting`].
+    /// - **Wayland / X11:** Emitted when the process receives `SIGTERM`, followed by [`exiting`].
+    ///   The signal is blocked on the thread of the event loop, and threads spawned from it
+    ///   afterwards, and read with `signalfd`. Processes with threads started before can still be
+    ///   killed by it. It is left alone if the application handles or blocks it itself. Only
+    ///   supported on Linux. Also emitted when the session manager ends the session, see
+    ///   [`close_requested_by_os`].
+    /// - **Web:** Emitted from the [`pagehide`] event when the page is unloaded instead of being
+    ///   stored in the [`bfcache`], followed by [`exiting`].
+    /// - **Windows:** Emitted from `WM_ENDSESSION` when the user logs off or shuts down, once no
+    ///   application cancelled it. The process is terminated once this returns.
+    /// - **Android / Orbital:** Unsupported.
+    ///
+    /// [`close_requested_by_os`]: Self::close_requested_by_os
+    /// [`exiting`]: Self::exiting
+    /// [`pagehide`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/pagehide_event
+    /// [`bfcache`]: https://web.dev/bfcache/
+    fn terminating(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
     /// Emitted when the event loop is being shut down.
     ///
     /// This is irreversible - if this method is called, it is guaranteed that the event loop
@@ -222,6 +423,347 @@ pub trait ApplicationHandler<T: 'static = ()> {
     fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
         let _ = event_loop;
     }
+
+    /// Emitted when the session of the user was locked, e.g. by the user or after some inactivity.
+    ///
+    /// Applications may want to pause, mute their audio or hide sensitive content until
+    /// [`ApplicationHandler::session_unlocked`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted from the `com.apple.screenIsLocked` distributed notification.
+    /// - **Windows:** Emitted from `WM_WTSSESSION_CHANGE` with `WTS_SESSION_LOCK`.
+    /// - **Android / iOS / Orbital / Wayland / Web / X11:** Unsupported.
+    fn session_locked(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the session locked with [`ApplicationHandler::session_locked`] was unlocked.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted from the `com.apple.screenIsUnlocked` distributed notification.
+    /// - **Windows:** Emitted from `WM_WTSSESSION_CHANGE` with `WTS_SESSION_UNLOCK`.
+    /// - **Android / iOS / Orbital / Wayland / Web / X11:** Unsupported.
+    fn session_unlocked(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the displays went to sleep.
+    ///
+    /// Nothing rendered is shown until [`ApplicationHandler::display_wake`], so applications may
+    /// want to stop rendering and pause.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted from `NSWorkspaceScreensDidSleepNotification`.
+    /// - **Windows:** Emitted when the console display is turned off, which is reported through
+    ///   `GUID_CONSOLE_DISPLAY_STATE`.
+    /// - **Web:** Emitted from the [`freeze`] event, which browsers send before freezing a hidden
+    ///   page, e.g. when the device sleeps but also when the tab has been in the background for a
+    ///   while. This is the last chance to run any code until [`ApplicationHandler::display_wake`].
+    /// - **Android / iOS / Orbital / Wayland / X11:** Unsupported.
+    ///
+    /// [`freeze`]: https://developer.mozilla.org/en-US/docs/Web/API/Document/freeze_event
+    fn display_sleep(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the displays woke up after [`ApplicationHandler::display_sleep`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted from `NSWorkspaceScreensDidWakeNotification`.
+    /// - **Windows:** Emitted when the console display is turned on again, also if it is dimmed.
+    /// - **Web:** Emitted from the [`resume`] event, when a frozen page is resumed.
+    /// - **Android / iOS / Orbital / Wayland / X11:** Unsupported.
+    ///
+    /// [`resume`]: https://developer.mozilla.org/en-US/docs/Web/API/Document/resume_event
+    fn display_wake(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the language preferred by the user changed, see
+    /// [`ActiveEventLoop::locale()`].
+    ///
+    /// This allows switching translations without restarting the application.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Emitted when the configuration of the activity changed.
+    /// - **macOS:** Emitted from `NSCurrentLocaleDidChangeNotification`.
+    /// - **Web:** Emitted from the [`languagechange`] event.
+    /// - **Windows:** Emitted from `WM_SETTINGCHANGE` for the `intl` settings.
+    /// - **iOS / Orbital / Wayland / X11:** Unsupported.
+    ///
+    /// [`languagechange`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/languagechange_event
+    fn locale_changed(&mut self, event_loop: &ActiveEventLoop, locale: LanguageTag) {
+        let _ = (event_loop, locale);
+    }
+
+    /// Emitted when the work area of a monitor changed, e.g. because the taskbar or the dock was
+    /// moved or resized, see [`MonitorHandle::work_area()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted when the screen parameters changed.
+    /// - **Web:** Emitted from the [`change`] event of the screen, see
+    ///   `ActiveEventLoopExtWebSys::work_area()`.
+    /// - **Windows:** Emitted from `WM_SETTINGCHANGE` for `SPI_SETWORKAREA`.
+    /// - **X11:** Emitted when `_NET_WORKAREA` changed.
+    /// - **Android / iOS / Orbital / Wayland:** Unsupported.
+    ///
+    /// [`MonitorHandle::work_area()`]: crate::monitor::MonitorHandle::work_area
+    /// [`change`]: https://developer.mozilla.org/en-US/docs/Web/API/Screen/change_event
+    fn work_area_changed(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the user navigated between the history entries of the application, e.g. with
+    /// the back and forward buttons of the browser.
+    ///
+    /// The application is expected to show the content for [`PopState::url`]. Entries are added
+    /// with `ActiveEventLoopExtWebSys::push_state()`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted from the [`popstate`] event.
+    /// - **Android / iOS / macOS / Orbital / Wayland / Windows / X11:** Unsupported.
+    ///
+    /// [`popstate`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/popstate_event
+    fn navigation(&mut self, event_loop: &ActiveEventLoop, pop_state: PopState) {
+        let _ = (event_loop, pop_state);
+    }
+
+    /// Emitted when the color profile of a monitor changed, see [`MonitorHandle::icc_profile()`].
+    ///
+    /// Applications that soft-proof or color manage their content should fetch the profiles of
+    /// the monitors again.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted from `NSScreenColorSpaceDidChangeNotification`.
+    /// - **Windows:** Emitted when the display settings changed. Changing the profile in the color
+    ///   management settings isn't reported.
+    /// - **X11:** Emitted when an `_ICC_PROFILE` property of the root window changed.
+    /// - **Android / iOS / Orbital / Wayland / Web:** Unsupported.
+    ///
+    /// [`MonitorHandle::icc_profile()`]: crate::monitor::MonitorHandle::icc_profile
+    fn color_profile_changed(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when another instance of the application was started, see [`SingleInstance`].
+    ///
+    /// `args` are the command line arguments of the other instance, without the program name.
+    /// Relative paths among them are relative to the working directory of the other instance. The
+    /// application usually focuses its window with the `activation_token` as
+    /// [`FocusReason::Token`], or [`FocusReason::Programmatic`] without one. URLs and files among
+    /// the arguments are emitted with [`url_opened`] and [`files_opened`] afterwards.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / Orbital / Web:** Unsupported.
+    ///
+    /// [`SingleInstance`]: crate::app::SingleInstance
+    /// [`url_opened`]: Self::url_opened
+    /// [`files_opened`]: Self::files_opened
+    /// [`FocusReason::Token`]: crate::window::FocusReason::Token
+    /// [`FocusReason::Programmatic`]: crate::window::FocusReason::Programmatic
+    fn activation_request(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        args: Vec<OsString>,
+        activation_token: Option<ActivationToken>,
+    ) {
+        let _ = (event_loop, args, activation_token);
+    }
+
+    /// Emitted when the application was asked to open a URL, usually with a custom scheme
+    /// registered for the application, e.g. the redirect at the end of an OAuth flow.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted from `application:openURLs:`. Schemes are registered with
+    ///   `CFBundleURLTypes` in the `Info.plist` of the bundle.
+    /// - **iOS:** Emitted from `application:openURL:options:`. Schemes are registered like on
+    ///   macOS.
+    /// - **Windows / Wayland / X11:** The system starts the application with the URL as an
+    ///   argument, schemes are registered in the registry or with a `.desktop` file, and with
+    ///   [`EventLoopBuilder::with_url_schemes()`]. URLs with these schemes among the arguments of
+    ///   the process are emitted after [`StartCause::Init`], and the ones forwarded by later
+    ///   instances after [`ApplicationHandler::activation_request`].
+    /// - **Web:** Emitted for the target URL of launches through the [`launchQueue`] of installed
+    ///   web applications, e.g. for the `protocol_handlers` of the manifest.
+    /// - **Android / Orbital:** Unsupported.
+    ///
+    /// [`launchQueue`]: https://developer.mozilla.org/en-US/docs/Web/API/Launch_Handler_API
+    /// [`EventLoopBuilder::with_url_schemes()`]: crate::event_loop::EventLoopBuilder::with_url_schemes
+    fn url_opened(&mut self, event_loop: &ActiveEventLoop, url: Url) {
+        let _ = (event_loop, url);
+    }
+
+    /// Emitted when the application was asked to open documents, e.g. when the user double
+    /// clicked files associated with the application, or chose it with "Open With".
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted from `application:openURLs:` for file URLs. Document types are
+    ///   registered with `CFBundleDocumentTypes` in the `Info.plist` of the bundle.
+    /// - **Windows / Wayland / X11:** The system starts the application with the paths as
+    ///   arguments. With [`EventLoopBuilder::with_file_arguments()`], existing files among the
+    ///   arguments of the process are emitted after [`StartCause::Init`], and the ones forwarded by
+    ///   later instances after [`ApplicationHandler::activation_request`], so that documents open
+    ///   in the running application.
+    /// - **Web:** Emitted for the files of launches through the [`launchQueue`] of installed web
+    ///   applications, with the `file_handlers` of the manifest. Browsers don't expose paths, so
+    ///   these are the names of the files, see `ActiveEventLoopExtWebSys::opened_file_handles()`
+    ///   for their contents.
+    /// - **Android / iOS / Orbital:** Unsupported.
+    ///
+    /// [`launchQueue`]: https://developer.mozilla.org/en-US/docs/Web/API/Launch_Handler_API
+    /// [`EventLoopBuilder::with_file_arguments()`]: crate::event_loop::EventLoopBuilder::with_file_arguments
+    fn files_opened(&mut self, event_loop: &ActiveEventLoop, paths: Vec<PathBuf>) {
+        let _ = (event_loop, paths);
+    }
+
+    /// Emitted while data is dragged over a window, whenever the cursor moves, to choose what
+    /// dropping it at the position would do.
+    ///
+    /// The returned operation is shown to the user with the cursor, and the data can't be dropped
+    /// where [`DropOperation::Reject`] is returned. The position is relative to the top-left
+    /// corner of the surface. By default, [`DropOperation::Copy`] is returned everywhere.
+    ///
+    /// This is only emitted for drags the window accepts, see
+    /// [`Window::set_drop_negotiation()`]. The drag ends with
+    /// [`WindowEvent::HoveredFileCancelled`] or with the drop.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / Windows / Web:** Operations the source doesn't allow reject the drop.
+    /// - **Wayland:** The operation is applied once this returns, so the compositor may show the
+    ///   previous operation until then. [`DropOperation::Link`] is shown as
+    ///   [`DropOperation::Copy`].
+    /// - **Android / iOS / Orbital:** Unsupported.
+    ///
+    /// [`Window::set_drop_negotiation()`]: crate::window::Window::set_drop_negotiation
+    /// [`WindowEvent::HoveredFileCancelled`]: crate::event::WindowEvent::HoveredFileCancelled
+    fn drag_hover(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        position: PhysicalPosition<f64>,
+    ) -> DropOperation {
+        let _ = (event_loop, window_id, position);
+        DropOperation::Copy
+    }
+
+    /// Emitted when the user clicked the icon of a window hidden with
+    /// [`Window::hide_to_tray()`], once the window is shown again.
+    ///
+    /// It follows [`surface_created`], as the surface was destroyed while the window was hidden.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / Orbital / Wayland / Web / X11:** Unsupported.
+    ///
+    /// [`Window::hide_to_tray()`]: crate::window::Window::hide_to_tray
+    /// [`surface_created`]: Self::surface_created
+    fn tray_activated(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        let _ = (event_loop, window_id);
+    }
+
+    /// Emitted when the process received one of the signals requested with
+    /// `EventLoopBuilderExtUnix::with_signals()`.
+    ///
+    /// As the signal is received by the event loop, the application can shut down as usual with
+    /// [`ActiveEventLoop::exit()`], e.g. on [`Signal::Interrupt`], instead of being killed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / Orbital / Web / Windows:** Unsupported.
+    fn signal(&mut self, event_loop: &ActiveEventLoop, signal: Signal) {
+        let _ = (event_loop, signal);
+    }
+
+    /// Emitted when a file descriptor registered with `ActiveEventLoopExtUnix::register_fd()` is
+    /// ready for the requested operations, with the token it was registered with.
+    ///
+    /// The event is emitted on each iteration of the event loop while the file descriptor stays
+    /// ready, so the application should read or write until it would block.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / Orbital / Web / Windows:** Unsupported.
+    fn fd_ready(&mut self, event_loop: &ActiveEventLoop, token: u64) {
+        let _ = (event_loop, token);
+    }
+
+    /// Emitted when a handle registered with `ActiveEventLoopExtWindows::register_wait_handle()`
+    /// is signaled, with the token it was registered with.
+    ///
+    /// The event is emitted on each iteration of the event loop while the handle stays signaled,
+    /// e.g. for a manual-reset event until it's reset.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Wayland / Web / X11:** Unsupported.
+    fn handle_signaled(&mut self, event_loop: &ActiveEventLoop, token: u64) {
+        let _ = (event_loop, token);
+    }
+
+    /// Emitted when monitors were connected or disconnected, or their arrangement changed, e.g.
+    /// when mirroring was turned on or off.
+    ///
+    /// This is the time to look for [`ActiveEventLoop::presentation_displays()`] again, e.g. to
+    /// move the output of a slideshow to a projector that was just connected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted when the screen parameters changed.
+    /// - **iOS:** Emitted from `UIScreenDidConnectNotification` and
+    ///   `UIScreenDidDisconnectNotification`, e.g. for AirPlay.
+    /// - **Windows:** Emitted from `WM_DISPLAYCHANGE`.
+    /// - **Wayland:** Emitted when outputs are added or removed.
+    /// - **X11:** Emitted when the RandR configuration of the monitors changed.
+    /// - **Android / Orbital / Web:** Unsupported.
+    ///
+    /// [`ActiveEventLoop::presentation_displays()`]: crate::event_loop::ActiveEventLoop::presentation_displays
+    fn monitors_changed(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the audio of the application was interrupted, e.g. by a phone call or an
+    /// alarm.
+    ///
+    /// The audio of the application is silenced by the system, so games should pause their audio
+    /// and usually the game itself, until [`audio_resumed()`][Self::audio_resumed] is emitted.
+    /// How the audio mixes with other applications is set with an [`AudioSessionCategory`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Emitted from `AVAudioSessionInterruptionNotification`.
+    /// - **Android:** Emitted when the activity is paused, once a window with an
+    ///   [`AudioSessionCategory`] was created, as the audio focus can only be observed from Java.
+    /// - **macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    ///
+    /// [`AudioSessionCategory`]: crate::window::AudioSessionCategory
+    fn audio_interrupted(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
+
+    /// Emitted when the interruption of the audio reported with
+    /// [`audio_interrupted()`][Self::audio_interrupted] ended, and the application can play audio
+    /// again.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** The audio session is activated again before this is emitted.
+    /// - **Android:** Emitted when the activity is resumed.
+    /// - **macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    fn audio_resumed(&mut self, event_loop: &ActiveEventLoop) {
+        let _ = event_loop;
+    }
 }
 
 impl<A: ?Sized + ApplicationHandler<T>, T: 'static> ApplicationHandler<T> for &mut A {
@@ -230,6 +772,11 @@ impl<A: ?Sized + ApplicationHandler<T>, T: 'static> ApplicationHandler<T> for &m
         (**self).new_events(event_loop, cause);
     }
 
+    #[inline]
+    fn init(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).init(event_loop);
+    }
+
     #[inline]
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         (**self).resumed(event_loop);
@@ -240,6 +787,11 @@ impl<A: ?Sized + ApplicationHandler<T>, T: 'static> ApplicationHandler<T> for &m
         (**self).user_event(event_loop, event);
     }
 
+    #[inline]
+    fn proxy_wake_up(&mut self, event_loop: &ActiveEventLoop, priority: Priority) {
+        (**self).proxy_wake_up(event_loop, priority);
+    }
+
     #[inline]
     fn window_event(
         &mut self,
@@ -260,16 +812,51 @@ impl<A: ?Sized + ApplicationHandler<T>, T: 'static> ApplicationHandler<T> for &m
         (**self).device_event(event_loop, device_id, event);
     }
 
+    #[inline]
+    fn surface_created(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        (**self).surface_created(event_loop, window_id);
+    }
+
+    #[inline]
+    fn surface_destroyed(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        (**self).surface_destroyed(event_loop, window_id);
+    }
+
     #[inline]
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         (**self).about_to_wait(event_loop);
     }
 
+    #[inline]
+    fn idle(&mut self, event_loop: &ActiveEventLoop, deadline: Instant) {
+        (**self).idle(event_loop, deadline);
+    }
+
+    #[inline]
+    fn close_requested_by_os(&mut self, event_loop: &ActiveEventLoop, respond: CloseResponse) {
+        (**self).close_requested_by_os(event_loop, respond);
+    }
+
+    #[inline]
+    fn notification_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        id: NotificationId,
+        event: NotificationEvent,
+    ) {
+        (**self).notification_event(event_loop, id, event);
+    }
+
     #[inline]
     fn suspended(&mut self, event_loop: &ActiveEventLoop) {
         (**self).suspended(event_loop);
     }
 
+    #[inline]
+    fn terminating(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).terminating(event_loop);
+    }
+
     #[inline]
     fn exiting(&mut self, event_loop: &ActiveEventLoop) {
         (**self).exiting(event_loop);
@@ -279,6 +866,111 @@ impl<A: ?Sized + ApplicationHandler<T>, T: 'static> ApplicationHandler<T> for &m
     fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
         (**self).memory_warning(event_loop);
     }
+
+    #[inline]
+    fn session_locked(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).session_locked(event_loop);
+    }
+
+    #[inline]
+    fn session_unlocked(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).session_unlocked(event_loop);
+    }
+
+    #[inline]
+    fn display_sleep(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).display_sleep(event_loop);
+    }
+
+    #[inline]
+    fn display_wake(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).display_wake(event_loop);
+    }
+
+    #[inline]
+    fn locale_changed(&mut self, event_loop: &ActiveEventLoop, locale: LanguageTag) {
+        (**self).locale_changed(event_loop, locale);
+    }
+
+    #[inline]
+    fn work_area_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).work_area_changed(event_loop);
+    }
+
+    #[inline]
+    fn navigation(&mut self, event_loop: &ActiveEventLoop, pop_state: PopState) {
+        (**self).navigation(event_loop, pop_state);
+    }
+
+    #[inline]
+    fn color_profile_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).color_profile_changed(event_loop);
+    }
+
+    #[inline]
+    fn activation_request(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        args: Vec<OsString>,
+        activation_token: Option<ActivationToken>,
+    ) {
+        (**self).activation_request(event_loop, args, activation_token);
+    }
+
+    #[inline]
+    fn url_opened(&mut self, event_loop: &ActiveEventLoop, url: Url) {
+        (**self).url_opened(event_loop, url);
+    }
+
+    #[inline]
+    fn files_opened(&mut self, event_loop: &ActiveEventLoop, paths: Vec<PathBuf>) {
+        (**self).files_opened(event_loop, paths);
+    }
+
+    #[inline]
+    fn drag_hover(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        position: PhysicalPosition<f64>,
+    ) -> DropOperation {
+        (**self).drag_hover(event_loop, window_id, position)
+    }
+
+    #[inline]
+    fn tray_activated(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        (**self).tray_activated(event_loop, window_id);
+    }
+
+    #[inline]
+    fn signal(&mut self, event_loop: &ActiveEventLoop, signal: Signal) {
+        (**self).signal(event_loop, signal);
+    }
+
+    #[inline]
+    fn fd_ready(&mut self, event_loop: &ActiveEventLoop, token: u64) {
+        (**self).fd_ready(event_loop, token);
+    }
+
+    #[inline]
+    fn handle_signaled(&mut self, event_loop: &ActiveEventLoop, token: u64) {
+        (**self).handle_signaled(event_loop, token);
+    }
+
+    #[inline]
+    fn monitors_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).monitors_changed(event_loop);
+    }
+
+    #[inline]
+    fn audio_interrupted(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).audio_interrupted(event_loop);
+    }
+
+    #[inline]
+    fn audio_resumed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).audio_resumed(event_loop);
+    }
 }
 
 impl<A: ?Sized + ApplicationHandler<T>, T: 'static> ApplicationHandler<T> for Box<A> {
@@ -287,6 +979,11 @@ impl<A: ?Sized + ApplicationHandler<T>, T: 'static> ApplicationHandler<T> for Bo
         (**self).new_events(event_loop, cause);
     }
 
+    #[inline]
+    fn init(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).init(event_loop);
+    }
+
     #[inline]
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
         (**self).resumed(event_loop);
@@ -297,6 +994,11 @@ impl<A: ?Sized + ApplicationHandler<T>, T: 'static> ApplicationHandler<T> for Bo
         (**self).user_event(event_loop, event);
     }
 
+    #[inline]
+    fn proxy_wake_up(&mut self, event_loop: &ActiveEventLoop, priority: Priority) {
+        (**self).proxy_wake_up(event_loop, priority);
+    }
+
     #[inline]
     fn window_event(
         &mut self,
@@ -317,16 +1019,51 @@ impl<A: ?Sized + ApplicationHandler<T>, T: 'static> ApplicationHandler<T> for Bo
         (**self).device_event(event_loop, device_id, event);
     }
 
+    #[inline]
+    fn surface_created(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        (**self).surface_created(event_loop, window_id);
+    }
+
+    #[inline]
+    fn surface_destroyed(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        (**self).surface_destroyed(event_loop, window_id);
+    }
+
     #[inline]
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
         (**self).about_to_wait(event_loop);
     }
 
+    #[inline]
+    fn idle(&mut self, event_loop: &ActiveEventLoop, deadline: Instant) {
+        (**self).idle(event_loop, deadline);
+    }
+
+    #[inline]
+    fn close_requested_by_os(&mut self, event_loop: &ActiveEventLoop, respond: CloseResponse) {
+        (**self).close_requested_by_os(event_loop, respond);
+    }
+
+    #[inline]
+    fn notification_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        id: NotificationId,
+        event: NotificationEvent,
+    ) {
+        (**self).notification_event(event_loop, id, event);
+    }
+
     #[inline]
     fn suspended(&mut self, event_loop: &ActiveEventLoop) {
         (**self).suspended(event_loop);
     }
 
+    #[inline]
+    fn terminating(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).terminating(event_loop);
+    }
+
     #[inline]
     fn exiting(&mut self, event_loop: &ActiveEventLoop) {
         (**self).exiting(event_loop);
@@ -336,4 +1073,109 @@ impl<A: ?Sized + ApplicationHandler<T>, T: 'static> ApplicationHandler<T> for Bo
     fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
         (**self).memory_warning(event_loop);
     }
+
+    #[inline]
+    fn session_locked(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).session_locked(event_loop);
+    }
+
+    #[inline]
+    fn session_unlocked(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).session_unlocked(event_loop);
+    }
+
+    #[inline]
+    fn display_sleep(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).display_sleep(event_loop);
+    }
+
+    #[inline]
+    fn display_wake(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).display_wake(event_loop);
+    }
+
+    #[inline]
+    fn locale_changed(&mut self, event_loop: &ActiveEventLoop, locale: LanguageTag) {
+        (**self).locale_changed(event_loop, locale);
+    }
+
+    #[inline]
+    fn work_area_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).work_area_changed(event_loop);
+    }
+
+    #[inline]
+    fn navigation(&mut self, event_loop: &ActiveEventLoop, pop_state: PopState) {
+        (**self).navigation(event_loop, pop_state);
+    }
+
+    #[inline]
+    fn color_profile_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).color_profile_changed(event_loop);
+    }
+
+    #[inline]
+    fn activation_request(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        args: Vec<OsString>,
+        activation_token: Option<ActivationToken>,
+    ) {
+        (**self).activation_request(event_loop, args, activation_token);
+    }
+
+    #[inline]
+    fn url_opened(&mut self, event_loop: &ActiveEventLoop, url: Url) {
+        (**self).url_opened(event_loop, url);
+    }
+
+    #[inline]
+    fn files_opened(&mut self, event_loop: &ActiveEventLoop, paths: Vec<PathBuf>) {
+        (**self).files_opened(event_loop, paths);
+    }
+
+    #[inline]
+    fn drag_hover(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        position: PhysicalPosition<f64>,
+    ) -> DropOperation {
+        (**self).drag_hover(event_loop, window_id, position)
+    }
+
+    #[inline]
+    fn tray_activated(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        (**self).tray_activated(event_loop, window_id);
+    }
+
+    #[inline]
+    fn signal(&mut self, event_loop: &ActiveEventLoop, signal: Signal) {
+        (**self).signal(event_loop, signal);
+    }
+
+    #[inline]
+    fn fd_ready(&mut self, event_loop: &ActiveEventLoop, token: u64) {
+        (**self).fd_ready(event_loop, token);
+    }
+
+    #[inline]
+    fn handle_signaled(&mut self, event_loop: &ActiveEventLoop, token: u64) {
+        (**self).handle_signaled(event_loop, token);
+    }
+
+    #[inline]
+    fn monitors_changed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).monitors_changed(event_loop);
+    }
+
+    #[inline]
+    fn audio_interrupted(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).audio_interrupted(event_loop);
+    }
+
+    #[inline]
+    fn audio_resumed(&mut self, event_loop: &ActiveEventLoop) {
+        (**self).audio_resumed(event_loop);
+    }
 }
@@ -0,0 +1,108 @@
+//! Deferred window commands, see [`Window::flush()`].
+//!
+//! Setters of windows called from an event handler don't call into the backend right away, as
+//! the backend may be in the middle of delivering the event, or of applying another change which
+//! the event reports. Instead the commands are queued, and applied once the handler returns, or on
+//! [`Window::flush()`]. Events delivered while applying a command can queue more commands, which
+//! are applied after the current one, so the backend is never re-entered from a handler.
+//!
+//! [`Window::flush()`]: crate::window::Window::flush
+
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+
+type Command = Box<dyn FnOnce()>;
+
+// Handlers run on the thread of the event loop, setters called on other threads are applied right
+// away as before.
+thread_local! {
+    /// How many handlers are running on this thread.
+    static DISPATCHING: Cell<usize> = const { Cell::new(0) };
+    /// Whether queued commands are being applied on this thread.
+    static APPLYING: Cell<bool> = const { Cell::new(false) };
+    static COMMANDS: RefCell<VecDeque<Command>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// Resets a flag of the thread, also when unwinding.
+struct Reset<F: FnMut()>(F);
+
+impl<F: FnMut()> Drop for Reset<F> {
+    fn drop(&mut self) {
+        (self.0)()
+    }
+}
+
+/// Whether commands issued on this thread now have to be queued.
+pub(crate) fn is_deferring() -> bool {
+    DISPATCHING.with(Cell::get) > 0 || APPLYING.with(Cell::get)
+}
+
+/// Queue a command, to be applied after the commands queued before it.
+pub(crate) fn push(command: Command) {
+    COMMANDS.with(|commands| commands.borrow_mut().push_back(command));
+}
+
+/// Run an event handler, and apply the commands it queued unless it is nested in another handler
+/// or in applying a command.
+pub(crate) fn dispatch<R>(f: impl FnOnce() -> R) -> R {
+    DISPATCHING.with(|depth| depth.set(depth.get() + 1));
+    let result = {
+        let _reset = Reset(|| DISPATCHING.with(|depth| depth.set(depth.get() - 1)));
+        f()
+    };
+
+    if DISPATCHING.with(Cell::get) == 0 {
+        flush();
+    }
+    result
+}
+
+/// Apply the queued commands in order, unless they are being applied already, in which case the
+/// outer call applies them once the current command returns.
+pub(crate) fn flush() {
+    if APPLYING.with(|applying| applying.replace(true)) {
+        return;
+    }
+    let _reset = Reset(|| APPLYING.with(|applying| applying.set(false)));
+
+    while let Some(command) = COMMANDS.with(|commands| commands.borrow_mut().pop_front()) {
+        command();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+
+    fn command(log: &Rc<RefCell<Vec<&'static str>>>, name: &'static str) -> Command {
+        let log = Rc::clone(log);
+        Box::new(move || log.borrow_mut().push(name))
+    }
+
+    #[test]
+    fn deferred_commands() {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        assert!(!is_deferring());
+        dispatch(|| {
+            assert!(is_deferring());
+            push(command(&log, "first"));
+            // A nested handler leaves the commands to the outer one.
+            dispatch(|| push(command(&log, "nested")));
+            assert!(log.borrow().is_empty());
+
+            // A command reporting an event queues the commands of the handler after itself.
+            let log = Rc::clone(&log);
+            push(Box::new(move || {
+                log.borrow_mut().push("second");
+                dispatch(|| push(command(&log, "reported")));
+                log.borrow_mut().push("second done");
+            }));
+        });
+
+        assert_eq!(*log.borrow(), ["first", "nested", "second", "second done", "reported"]);
+        assert!(!is_deferring());
+    }
+}
@@ -7,7 +7,8 @@ use cursor_icon::CursorIcon;
 
 use crate::platform_impl::{PlatformCustomCursor, PlatformCustomCursorSource};
 
-/// The maximum width and height for a cursor when using [`CustomCursor::from_rgba`].
+/// The maximum width and height for a cursor when using [`CustomCursor::from_rgba`] or
+/// [`CursorImage::from_rgba`].
 pub const MAX_CURSOR_SIZE: u16 = 2048;
 
 const PIXEL_SIZE: usize = 4;
@@ -102,6 +103,46 @@ impl CustomCursor {
             )?,
         })
     }
+
+    /// Creates a new cursor from several images, each drawn for a different scale factor.
+    ///
+    /// The image that fits the scale factor of the window best is used, and replaced when the
+    /// window moves to a monitor with a different scale factor. This keeps the cursor sharp and
+    /// at the same size on setups with monitors of different DPI.
+    ///
+    /// See [`CursorImage::with_scale_factor()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** All images are added to the cursor, the system picks the one to show.
+    /// - **Web:** All images are added to the cursor with CSS `image-set()`, the browser picks the
+    ///   one to show.
+    /// - **Wayland / Windows / X11:** The image with the smallest scale factor that is at least as
+    ///   large as the one of the window is shown, or the largest image.
+    /// - **Android / iOS / Orbital:** Unsupported.
+    pub fn from_rgba_multi(
+        images: impl IntoIterator<Item = CursorImage>,
+    ) -> Result<CustomCursorSource, BadImage> {
+        let mut images: Vec<_> = images.into_iter().collect();
+        let _span =
+            tracing::debug_span!("winit::Cursor::from_rgba_multi", count = images.len()).entered();
+
+        if images.is_empty() {
+            return Err(BadImage::Empty);
+        }
+
+        if let Some(image) = images
+            .iter()
+            .find(|image| !image.scale_factor.is_normal() || image.scale_factor.is_sign_negative())
+        {
+            return Err(BadImage::InvalidScaleFactor { scale_factor: image.scale_factor });
+        }
+
+        images.sort_by(|a, b| a.scale_factor.total_cmp(&b.scale_factor));
+        images.dedup_by(|a, b| a.scale_factor == b.scale_factor);
+
+        Ok(CustomCursorSource { inner: PlatformCustomCursorSource::from_rgba_multi(images) })
+    }
 }
 
 /// Source for [`CustomCursor`].
@@ -112,7 +153,10 @@ pub struct CustomCursorSource {
     pub(crate) inner: PlatformCustomCursorSource,
 }
 
-/// An error produced when using [`CustomCursor::from_rgba`] with invalid arguments.
+/// An error produced when using [`CustomCursor::from_rgba`] or [`Window::present_rgba`] with
+/// invalid arguments.
+///
+/// [`Window::present_rgba`]: crate::window::Window::present_rgba
 #[derive(Debug, Clone)]
 pub enum BadImage {
     /// Produced when the image dimensions are larger than [`MAX_CURSOR_SIZE`]. This doesn't
@@ -127,6 +171,12 @@ pub enum BadImage {
     DimensionsVsPixelCount { width: u16, height: u16, width_x_height: u64, pixel_count: u64 },
     /// Produced when the hotspot is outside the image bounds
     HotspotOutOfBounds { width: u16, height: u16, hotspot_x: u16, hotspot_y: u16 },
+    /// Produced when [`CustomCursor::from_rgba_multi`] received no images.
+    Empty,
+    /// Produced when the scale factor of an image isn't a positive finite number.
+    InvalidScaleFactor { scale_factor: f64 },
+    /// Produced when the length of a buffer isn't `4 * width * height`.
+    BufferSize { width: u32, height: u32, byte_count: usize },
 }
 
 impl fmt::Display for BadImage {
@@ -155,6 +205,16 @@ impl fmt::Display for BadImage {
                 "The specified hotspot ({hotspot_x:?}, {hotspot_y:?}) is outside the image bounds \
                  ({width:?}x{height:?}).",
             ),
+            BadImage::Empty => write!(f, "No images were supplied for the cursor."),
+            BadImage::InvalidScaleFactor { scale_factor } => write!(
+                f,
+                "The specified scale factor ({scale_factor:?}) isn't a positive finite number.",
+            ),
+            BadImage::BufferSize { width, height, byte_count } => write!(
+                f,
+                "The length of the buffer ({byte_count:?}) doesn't match the specified dimensions \
+                 ({width:?}x{height:?}) with 4 bytes per pixel.",
+            ),
         }
     }
 }
@@ -163,9 +223,11 @@ impl Error for BadImage {}
 
 /// Platforms export this directly as `PlatformCustomCursorSource` if they need to only work with
 /// images.
+///
+/// Holds at least one image, sorted by scale factor.
 #[allow(dead_code)]
 #[derive(Debug)]
-pub(crate) struct OnlyCursorImageSource(pub(crate) CursorImage);
+pub(crate) struct OnlyCursorImageSource(pub(crate) Vec<CursorImage>);
 
 #[allow(dead_code)]
 impl OnlyCursorImageSource {
@@ -176,14 +238,21 @@ impl OnlyCursorImageSource {
         hotspot_x: u16,
         hotspot_y: u16,
     ) -> Result<Self, BadImage> {
-        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y).map(Self)
+        CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y)
+            .map(|image| Self(vec![image]))
+    }
+
+    pub(crate) fn from_rgba_multi(images: Vec<CursorImage>) -> Self {
+        Self(images)
     }
 }
 
 /// Platforms export this directly as `PlatformCustomCursor` if they don't implement caching.
+///
+/// Holds at least one image, sorted by scale factor.
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
-pub(crate) struct OnlyCursorImage(pub(crate) Arc<CursorImage>);
+pub(crate) struct OnlyCursorImage(pub(crate) Arc<[CursorImage]>);
 
 impl Hash for OnlyCursorImage {
     fn hash<H: Hasher>(&self, state: &mut H) {
@@ -199,24 +268,33 @@ impl PartialEq for OnlyCursorImage {
 
 impl Eq for OnlyCursorImage {}
 
-#[derive(Debug)]
+/// An image of a custom cursor drawn for a given scale factor, see
+/// [`CustomCursor::from_rgba_multi`].
+#[derive(Debug, Clone)]
 #[allow(dead_code)]
-pub(crate) struct CursorImage {
+pub struct CursorImage {
     pub(crate) rgba: Vec<u8>,
     pub(crate) width: u16,
     pub(crate) height: u16,
     pub(crate) hotspot_x: u16,
     pub(crate) hotspot_y: u16,
+    pub(crate) scale_factor: f64,
 }
 
 impl CursorImage {
-    pub(crate) fn from_rgba(
-        rgba: Vec<u8>,
+    /// Creates a new image from an rgba buffer, drawn for a scale factor of `1.0`.
+    ///
+    /// The alpha channel is assumed to be **not** premultiplied. The size and the hotspot are in
+    /// physical pixels of the image.
+    pub fn from_rgba(
+        rgba: impl Into<Vec<u8>>,
         width: u16,
         height: u16,
         hotspot_x: u16,
         hotspot_y: u16,
     ) -> Result<Self, BadImage> {
+        let rgba = rgba.into();
+
         if width > MAX_CURSOR_SIZE || height > MAX_CURSOR_SIZE {
             return Err(BadImage::TooLarge { width, height });
         }
@@ -240,8 +318,35 @@ impl CursorImage {
             return Err(BadImage::HotspotOutOfBounds { width, height, hotspot_x, hotspot_y });
         }
 
-        Ok(CursorImage { rgba, width, height, hotspot_x, hotspot_y })
+        Ok(CursorImage { rgba, width, height, hotspot_x, hotspot_y, scale_factor: 1.0 })
+    }
+
+    /// Sets the scale factor this image was drawn for, e.g. `2.0` for an image twice as large as
+    /// the one for a scale factor of `1.0`.
+    pub fn with_scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = scale_factor;
+        self
     }
+
+    /// Returns the scale factor this image was drawn for.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+}
+
+/// Pick the item to use at `scale_factor` out of items sorted by their scale factor: the first
+/// one drawn for at least `scale_factor`, or the last one.
+#[allow(dead_code)]
+pub(crate) fn for_scale_factor<T>(
+    items: &[T],
+    scale_factor: f64,
+    item_scale_factor: impl Fn(&T) -> f64,
+) -> &T {
+    items
+        .iter()
+        .find(|item| item_scale_factor(item) >= scale_factor)
+        .or(items.last())
+        .expect("no cursor images")
 }
 
 // Platforms that don't support cursors will export this as `PlatformCustomCursor`.
@@ -260,4 +365,23 @@ impl NoCustomCursor {
         CursorImage::from_rgba(rgba, width, height, hotspot_x, hotspot_y)?;
         Ok(Self)
     }
+
+    pub(crate) fn from_rgba_multi(_images: Vec<CursorImage>) -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buffer_size_message() {
+        let error = BadImage::BufferSize { width: 2, height: 3, byte_count: 20 };
+        assert_eq!(
+            error.to_string(),
+            "The length of the buffer (20) doesn't match the specified dimensions (2x3) with 4 \
+             bytes per pixel."
+        );
+    }
 }
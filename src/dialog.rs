@@ -0,0 +1,179 @@
+//! The [`MessageDialog`] struct and associated types.
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+/// A message box shown with
+/// [`ActiveEventLoop::show_message_dialog()`](crate::event_loop::ActiveEventLoop::show_message_dialog).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageDialog {
+    pub title: String,
+    pub message: String,
+    pub level: MessageLevel,
+    pub buttons: MessageButtons,
+}
+
+impl MessageDialog {
+    /// Sets the title of the dialog.
+    #[inline]
+    pub fn with_title<T: Into<String>>(mut self, title: T) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the text shown in the dialog.
+    #[inline]
+    pub fn with_message<T: Into<String>>(mut self, message: T) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Sets how severe the message is, which decides the icon of the dialog.
+    ///
+    /// The default is [`MessageLevel::Info`].
+    #[inline]
+    pub fn with_level(mut self, level: MessageLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the buttons of the dialog.
+    ///
+    /// The default is [`MessageButtons::Ok`].
+    #[inline]
+    pub fn with_buttons(mut self, buttons: MessageButtons) -> Self {
+        self.buttons = buttons;
+        self
+    }
+}
+
+/// How severe the message of a [`MessageDialog`] is.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageLevel {
+    #[default]
+    Info,
+    Warning,
+    Error,
+}
+
+/// The buttons of a [`MessageDialog`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessageButtons {
+    #[default]
+    Ok,
+    OkCancel,
+    YesNo,
+    YesNoCancel,
+}
+
+impl MessageButtons {
+    /// Returns the buttons, in the order they are shown on Windows.
+    pub const fn buttons(self) -> &'static [DialogButton] {
+        match self {
+            Self::Ok => &[DialogButton::Ok],
+            Self::OkCancel => &[DialogButton::Ok, DialogButton::Cancel],
+            Self::YesNo => &[DialogButton::Yes, DialogButton::No],
+            Self::YesNoCancel => &[DialogButton::Yes, DialogButton::No, DialogButton::Cancel],
+        }
+    }
+
+    /// Returns the button reported when the dialog is closed without pressing a button, e.g.
+    /// with the Escape key.
+    pub const fn dismiss_button(self) -> DialogButton {
+        match self {
+            Self::Ok => DialogButton::Ok,
+            Self::YesNo => DialogButton::No,
+            Self::OkCancel | Self::YesNoCancel => DialogButton::Cancel,
+        }
+    }
+}
+
+/// A button of a [`MessageDialog`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DialogButton {
+    Ok,
+    Cancel,
+    Yes,
+    No,
+}
+
+impl DialogButton {
+    /// The English label of the button, used where the system doesn't provide one.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) const fn label(self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Cancel => "Cancel",
+            Self::Yes => "Yes",
+            Self::No => "No",
+        }
+    }
+}
+
+struct Shared<T> {
+    result: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// Resolves to the answer of the user once a dialog is closed.
+///
+/// The dialog doesn't block the event loop, so the future has to be polled by an executor that
+/// doesn't block it either. Dropping the future doesn't close the dialog.
+pub struct DialogFuture<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+impl<T> fmt::Debug for DialogFuture<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DialogFuture").finish_non_exhaustive()
+    }
+}
+
+impl<T> Future for DialogFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+/// Resolves a [`DialogFuture`] from the backend.
+///
+/// If it is dropped without sending, e.g. because the dialog couldn't be shown, the future
+/// resolves to the fallback.
+pub(crate) struct DialogSender<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+    fallback: Option<T>,
+}
+
+impl<T> DialogSender<T> {
+    #[allow(dead_code)] // Not used on every platform
+    pub fn send(mut self, result: T) {
+        self.fallback = Some(result);
+    }
+}
+
+impl<T> Drop for DialogSender<T> {
+    fn drop(&mut self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.result = self.fallback.take();
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// Create a [`DialogFuture`] and the sender resolving it.
+pub(crate) fn channel<T>(fallback: T) -> (DialogSender<T>, DialogFuture<T>) {
+    let shared = Arc::new(Mutex::new(Shared { result: None, waker: None }));
+    (DialogSender { shared: shared.clone(), fallback: Some(fallback) }, DialogFuture { shared })
+}
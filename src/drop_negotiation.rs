@@ -0,0 +1,57 @@
+//! The types of dropped data windows accept, see [`Window::set_drop_negotiation()`].
+//!
+//! [`Window::set_drop_negotiation()`]: crate::window::Window::set_drop_negotiation
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::event::DataTransfer;
+use crate::window::{DropNegotiation, WindowId};
+
+// Windows can be used from any thread, and drags are handled on the thread of the event loop.
+static NEGOTIATIONS: Mutex<Option<HashMap<WindowId, Arc<DropNegotiation>>>> = Mutex::new(None);
+
+/// Replace the negotiation of a window, `None` restoring the default.
+pub(crate) fn set(window_id: WindowId, negotiation: Option<Arc<DropNegotiation>>) {
+    let mut negotiations = NEGOTIATIONS.lock().unwrap();
+    let negotiations = negotiations.get_or_insert_with(HashMap::new);
+    match negotiation {
+        Some(negotiation) => negotiations.insert(window_id, negotiation),
+        None => negotiations.remove(&window_id),
+    };
+}
+
+/// Choose which of the offered types a window accepts, in order of preference.
+pub(crate) fn negotiate(window_id: WindowId, offered: &[String]) -> Vec<String> {
+    let negotiation = NEGOTIATIONS
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|negotiations| negotiations.get(&window_id).cloned());
+    let accepted = match negotiation {
+        // Called without the lock, so the callback can change the negotiation.
+        Some(negotiation) => negotiation(offered),
+        None => DataTransfer::DEFAULT_TYPES.iter().map(|&mime_type| mime_type.to_owned()).collect(),
+    };
+    // Types that weren't offered can't be received.
+    accepted.into_iter().filter(|mime_type| offered.contains(mime_type)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiated_types() {
+        let window_id = WindowId::from(u64::MAX - 3);
+        let offered = ["text/uri-list", "text/html", "text/plain"].map(String::from);
+
+        assert_eq!(negotiate(window_id, &offered), ["text/plain", "text/uri-list"]);
+
+        set(window_id, Some(Arc::new(|offered: &[String]| vec![offered[1].clone(), "x".into()])));
+        assert_eq!(negotiate(window_id, &offered), ["text/html"]);
+
+        set(window_id, None);
+        assert_eq!(negotiate(window_id, &offered[1..2]), Vec::<String>::new());
+    }
+}
@@ -1,5 +1,7 @@
-use std::{error, fmt};
+use std::{error, fmt, io};
 
+use crate::cursor::BadImage;
+use crate::event_loop::Backend;
 use crate::platform_impl;
 
 // TODO: Rename
@@ -14,6 +16,27 @@ pub enum ExternalError {
     Os(OsError),
 }
 
+/// An error that may be generated when requesting a change of a [`Window`].
+///
+/// [`Window`]: crate::window::Window
+#[derive(Debug)]
+pub enum RequestError {
+    /// The operation is not supported by the backend, see
+    /// [`Window::capabilities()`](crate::window::Window::capabilities).
+    NotSupported {
+        /// The backend the window belongs to.
+        platform: Backend,
+        /// Why the backend doesn't support the operation.
+        reason: &'static str,
+    },
+    /// The operation was ignored.
+    Ignored,
+    /// The OS cannot perform the operation.
+    Os(OsError),
+    /// The pixels passed to the operation don't match their size.
+    BadImage(BadImage),
+}
+
 /// The error type for when the requested operation is not supported by the backend.
 #[derive(Clone)]
 pub struct NotSupportedError {
@@ -39,6 +62,19 @@ pub enum EventLoopError {
     RecreationAttempt,
     /// Application has exit with an error status.
     ExitFailure(i32),
+    /// The new process of the application couldn't be spawned, see
+    /// [`ActiveEventLoop::request_restart()`](crate::event_loop::ActiveEventLoop::request_restart).
+    Restart(io::Error),
+    /// None of the backends passed to
+    /// [`EventLoopBuilder::with_backend_preference()`](crate::event_loop::EventLoopBuilder::with_backend_preference)
+    /// could be initialized.
+    Backend(BackendError),
+}
+
+/// The error type for when none of the preferred backends could be initialized.
+#[derive(Debug, Clone)]
+pub struct BackendError {
+    failures: Vec<(Backend, String)>,
 }
 
 impl From<OsError> for EventLoopError {
@@ -47,6 +83,21 @@ impl From<OsError> for EventLoopError {
     }
 }
 
+impl RequestError {
+    /// Convert an error of the backend, which only reports that the operation isn't supported.
+    pub(crate) fn from_external(
+        error: ExternalError,
+        platform: Backend,
+        reason: &'static str,
+    ) -> Self {
+        match error {
+            ExternalError::NotSupported(_) => Self::NotSupported { platform, reason },
+            ExternalError::Ignored => Self::Ignored,
+            ExternalError::Os(error) => Self::Os(error),
+        }
+    }
+}
+
 impl NotSupportedError {
     #[inline]
     #[allow(dead_code)]
@@ -55,6 +106,18 @@ impl NotSupportedError {
     }
 }
 
+impl BackendError {
+    pub(crate) fn new(failures: Vec<(Backend, String)>) -> BackendError {
+        BackendError { failures }
+    }
+
+    /// Returns each backend that was tried, in order of preference, with the reason it couldn't
+    /// be initialized.
+    pub fn failures(&self) -> &[(Backend, String)] {
+        &self.failures
+    }
+}
+
 impl OsError {
     #[allow(dead_code)]
     pub(crate) fn new(line: u32, file: &'static str, error: platform_impl::OsError) -> OsError {
@@ -85,6 +148,19 @@ impl fmt::Display for ExternalError {
     }
 }
 
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            RequestError::NotSupported { platform, reason } => {
+                write!(f, "the requested operation is not supported on {platform}: {reason}")
+            },
+            RequestError::Ignored => write!(f, "Operation was ignored"),
+            RequestError::Os(e) => e.fmt(f),
+            RequestError::BadImage(e) => e.fmt(f),
+        }
+    }
+}
+
 impl fmt::Debug for NotSupportedError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
         f.debug_struct("NotSupportedError").finish()
@@ -104,14 +180,28 @@ impl fmt::Display for EventLoopError {
             EventLoopError::NotSupported(e) => e.fmt(f),
             EventLoopError::Os(e) => e.fmt(f),
             EventLoopError::ExitFailure(status) => write!(f, "Exit Failure: {status}"),
+            EventLoopError::Restart(e) => write!(f, "Restart Failure: {e}"),
+            EventLoopError::Backend(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for BackendError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        f.write_str("no backend could be initialized")?;
+        for (backend, reason) in &self.failures {
+            write!(f, "; {backend}: {reason}")?;
         }
+        Ok(())
     }
 }
 
 impl error::Error for OsError {}
 impl error::Error for ExternalError {}
+impl error::Error for RequestError {}
 impl error::Error for NotSupportedError {}
 impl error::Error for EventLoopError {}
+impl error::Error for BackendError {}
 
 #[cfg(test)]
 #[allow(clippy::redundant_clone)]
@@ -127,5 +217,10 @@ mod tests {
             ExternalError::NotSupported(NotSupportedError::new()),
             ExternalError::NotSupported(NotSupportedError::new())
         );
+        let request_error =
+            RequestError::NotSupported { platform: Backend::Wayland, reason: "not possible" };
+        let _ = format!("{:?}, {}", request_error, request_error);
+        let backend_error = BackendError::new(vec![(Backend::Wayland, "no display".to_owned())]);
+        let _ = format!("{:?}, {}", backend_error, backend_error.clone());
     }
 }
@@ -34,8 +34,12 @@
 //!
 //! [`EventLoop::run_app(...)`]: crate::event_loop::EventLoop::run_app
 //! [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+use std::ffi::OsString;
+use std::fmt;
 use std::path::PathBuf;
-use std::sync::{Mutex, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, Weak};
+use std::time::Duration;
 #[cfg(not(web_platform))]
 use std::time::Instant;
 
@@ -49,10 +53,12 @@ use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::error::ExternalError;
 use crate::event_loop::AsyncRequestSerial;
 use crate::keyboard::{self, ModifiersKeyState, ModifiersKeys, ModifiersState};
+use crate::locale::LanguageTag;
+use crate::notification::{NotificationEvent, NotificationId};
 use crate::platform_impl;
 #[cfg(doc)]
 use crate::window::Window;
-use crate::window::{ActivationToken, Theme, WindowId};
+use crate::window::{ActivationToken, Region, Theme, TilingState, WindowId, WindowVisibility};
 
 /// Describes a generic event.
 ///
@@ -79,6 +85,16 @@ pub enum Event<T: 'static> {
     /// [`ApplicationHandler::user_event`]: crate::application::ApplicationHandler::user_event
     UserEvent(T),
 
+    /// See [`ApplicationHandler::surface_created`] for details.
+    ///
+    /// [`ApplicationHandler::surface_created`]: crate::application::ApplicationHandler::surface_created
+    SurfaceCreated(WindowId),
+
+    /// See [`ApplicationHandler::surface_destroyed`] for details.
+    ///
+    /// [`ApplicationHandler::surface_destroyed`]: crate::application::ApplicationHandler::surface_destroyed
+    SurfaceDestroyed(WindowId),
+
     /// See [`ApplicationHandler::suspended`] for details.
     ///
     /// [`ApplicationHandler::suspended`]: crate::application::ApplicationHandler::suspended
@@ -94,6 +110,26 @@ pub enum Event<T: 'static> {
     /// [`ApplicationHandler::about_to_wait`]: crate::application::ApplicationHandler::about_to_wait
     AboutToWait,
 
+    /// See [`ApplicationHandler::idle`] for details.
+    ///
+    /// [`ApplicationHandler::idle`]: crate::application::ApplicationHandler::idle
+    Idle { deadline: Instant },
+
+    /// See [`ApplicationHandler::close_requested_by_os`] for details.
+    ///
+    /// [`ApplicationHandler::close_requested_by_os`]: crate::application::ApplicationHandler::close_requested_by_os
+    CloseRequestedByOS { respond: CloseResponse },
+
+    /// See [`ApplicationHandler::notification_event`] for details.
+    ///
+    /// [`ApplicationHandler::notification_event`]: crate::application::ApplicationHandler::notification_event
+    Notification { id: NotificationId, event: NotificationEvent },
+
+    /// See [`ApplicationHandler::terminating`] for details.
+    ///
+    /// [`ApplicationHandler::terminating`]: crate::application::ApplicationHandler::terminating
+    Terminating,
+
     /// See [`ApplicationHandler::exiting`] for details.
     ///
     /// [`ApplicationHandler::exiting`]: crate::application::ApplicationHandler::exiting
@@ -103,6 +139,101 @@ pub enum Event<T: 'static> {
     ///
     /// [`ApplicationHandler::memory_warning`]: crate::application::ApplicationHandler::memory_warning
     MemoryWarning,
+
+    /// See [`ApplicationHandler::session_locked`] for details.
+    ///
+    /// [`ApplicationHandler::session_locked`]: crate::application::ApplicationHandler::session_locked
+    SessionLocked,
+
+    /// See [`ApplicationHandler::session_unlocked`] for details.
+    ///
+    /// [`ApplicationHandler::session_unlocked`]: crate::application::ApplicationHandler::session_unlocked
+    SessionUnlocked,
+
+    /// See [`ApplicationHandler::display_sleep`] for details.
+    ///
+    /// [`ApplicationHandler::display_sleep`]: crate::application::ApplicationHandler::display_sleep
+    DisplaySleep,
+
+    /// See [`ApplicationHandler::display_wake`] for details.
+    ///
+    /// [`ApplicationHandler::display_wake`]: crate::application::ApplicationHandler::display_wake
+    DisplayWake,
+
+    /// See [`ApplicationHandler::locale_changed`] for details.
+    ///
+    /// [`ApplicationHandler::locale_changed`]: crate::application::ApplicationHandler::locale_changed
+    LocaleChanged(LanguageTag),
+
+    /// See [`ApplicationHandler::work_area_changed`] for details.
+    ///
+    /// [`ApplicationHandler::work_area_changed`]: crate::application::ApplicationHandler::work_area_changed
+    WorkAreaChanged,
+
+    /// See [`ApplicationHandler::navigation`] for details.
+    ///
+    /// [`ApplicationHandler::navigation`]: crate::application::ApplicationHandler::navigation
+    Navigation(PopState),
+
+    /// See [`ApplicationHandler::color_profile_changed`] for details.
+    ///
+    /// [`ApplicationHandler::color_profile_changed`]: crate::application::ApplicationHandler::color_profile_changed
+    ColorProfileChanged,
+
+    /// See [`ApplicationHandler::activation_request`] for details.
+    ///
+    /// [`ApplicationHandler::activation_request`]: crate::application::ApplicationHandler::activation_request
+    ActivationRequest { args: Vec<OsString>, activation_token: Option<ActivationToken> },
+
+    /// See [`ApplicationHandler::url_opened`] for details.
+    ///
+    /// [`ApplicationHandler::url_opened`]: crate::application::ApplicationHandler::url_opened
+    UrlOpened(Url),
+
+    /// See [`ApplicationHandler::files_opened`] for details.
+    ///
+    /// [`ApplicationHandler::files_opened`]: crate::application::ApplicationHandler::files_opened
+    FilesOpened(Vec<PathBuf>),
+
+    /// See [`ApplicationHandler::drag_hover`] for details.
+    ///
+    /// [`ApplicationHandler::drag_hover`]: crate::application::ApplicationHandler::drag_hover
+    DragHover { window_id: WindowId, position: PhysicalPosition<f64>, respond: DropResponse },
+
+    /// See [`ApplicationHandler::tray_activated`] for details.
+    ///
+    /// [`ApplicationHandler::tray_activated`]: crate::application::ApplicationHandler::tray_activated
+    TrayActivated(WindowId),
+
+    /// See [`ApplicationHandler::signal`] for details.
+    ///
+    /// [`ApplicationHandler::signal`]: crate::application::ApplicationHandler::signal
+    Signal(Signal),
+
+    /// See [`ApplicationHandler::fd_ready`] for details.
+    ///
+    /// [`ApplicationHandler::fd_ready`]: crate::application::ApplicationHandler::fd_ready
+    FdReady(u64),
+
+    /// See [`ApplicationHandler::handle_signaled`] for details.
+    ///
+    /// [`ApplicationHandler::handle_signaled`]: crate::application::ApplicationHandler::handle_signaled
+    HandleSignaled(u64),
+
+    /// See [`ApplicationHandler::monitors_changed`] for details.
+    ///
+    /// [`ApplicationHandler::monitors_changed`]: crate::application::ApplicationHandler::monitors_changed
+    MonitorsChanged,
+
+    /// See [`ApplicationHandler::audio_interrupted`] for details.
+    ///
+    /// [`ApplicationHandler::audio_interrupted`]: crate::application::ApplicationHandler::audio_interrupted
+    AudioInterrupted,
+
+    /// See [`ApplicationHandler::audio_resumed`] for details.
+    ///
+    /// [`ApplicationHandler::audio_resumed`]: crate::application::ApplicationHandler::audio_resumed
+    AudioResumed,
 }
 
 impl<T> Event<T> {
@@ -113,12 +244,41 @@ impl<T> Event<T> {
             UserEvent(_) => Err(self),
             WindowEvent { window_id, event } => Ok(WindowEvent { window_id, event }),
             DeviceEvent { device_id, event } => Ok(DeviceEvent { device_id, event }),
+            SurfaceCreated(window_id) => Ok(SurfaceCreated(window_id)),
+            SurfaceDestroyed(window_id) => Ok(SurfaceDestroyed(window_id)),
             NewEvents(cause) => Ok(NewEvents(cause)),
             AboutToWait => Ok(AboutToWait),
+            Idle { deadline } => Ok(Idle { deadline }),
+            CloseRequestedByOS { respond } => Ok(CloseRequestedByOS { respond }),
+            Notification { id, event } => Ok(Notification { id, event }),
+            Terminating => Ok(Terminating),
             LoopExiting => Ok(LoopExiting),
             Suspended => Ok(Suspended),
             Resumed => Ok(Resumed),
             MemoryWarning => Ok(MemoryWarning),
+            SessionLocked => Ok(SessionLocked),
+            SessionUnlocked => Ok(SessionUnlocked),
+            DisplaySleep => Ok(DisplaySleep),
+            DisplayWake => Ok(DisplayWake),
+            LocaleChanged(locale) => Ok(LocaleChanged(locale)),
+            WorkAreaChanged => Ok(WorkAreaChanged),
+            Navigation(pop_state) => Ok(Navigation(pop_state)),
+            ColorProfileChanged => Ok(ColorProfileChanged),
+            ActivationRequest { args, activation_token } => {
+                Ok(ActivationRequest { args, activation_token })
+            },
+            UrlOpened(url) => Ok(UrlOpened(url)),
+            FilesOpened(paths) => Ok(FilesOpened(paths)),
+            DragHover { window_id, position, respond } => {
+                Ok(DragHover { window_id, position, respond })
+            },
+            TrayActivated(window_id) => Ok(TrayActivated(window_id)),
+            Signal(signal) => Ok(Signal(signal)),
+            FdReady(token) => Ok(FdReady(token)),
+            HandleSignaled(token) => Ok(HandleSignaled(token)),
+            MonitorsChanged => Ok(MonitorsChanged),
+            AudioInterrupted => Ok(AudioInterrupted),
+            AudioResumed => Ok(AudioResumed),
         }
     }
 }
@@ -158,15 +318,60 @@ pub enum WindowEvent {
     ActivationTokenDone { serial: AsyncRequestSerial, token: ActivationToken },
 
     /// The size of the window has changed. Contains the client area's new dimensions.
-    Resized(PhysicalSize<u32>),
+    Resized {
+        size: PhysicalSize<u32>,
+        /// Present when the window was created with
+        /// [`WindowAttributes::with_synchronized_resize()`] and the resize is synchronized with
+        /// the compositor, see [`ResizeFrame`] for details.
+        ///
+        /// [`WindowAttributes::with_synchronized_resize()`]: crate::window::WindowAttributes::with_synchronized_resize
+        frame: Option<ResizeFrame>,
+    },
 
     /// The position of the window has changed. Contains the window's new position.
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland:** Unsupported.
+    /// - **Web:** Emitted when the canvas moves relative to the viewport, e.g. when the page is
+    ///   scrolled or its layout changes. Moving the browser window itself isn't reported.
+    /// - **iOS / Android / Wayland:** Unsupported.
     Moved(PhysicalPosition<i32>),
 
+    /// The user started interactively moving or resizing the window, e.g. by dragging its title
+    /// bar or borders.
+    ///
+    /// The [`Moved`] and [`Resized`] events that follow are part of the drag until
+    /// [`MoveResizeEnded`] is emitted, so expensive work like re-rendering at every size or
+    /// physics can be throttled in between.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The event loop keeps being iterated from a timer during the drag, see
+    ///   [`ControlFlow`].
+    /// - **macOS / Wayland:** Only emitted for resizes.
+    /// - **X11 / Web / iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`Moved`]: Self::Moved
+    /// [`Resized`]: Self::Resized
+    /// [`MoveResizeEnded`]: Self::MoveResizeEnded
+    /// [`ControlFlow`]: crate::event_loop::ControlFlow
+    MoveResizeBegan,
+
+    /// The interactive move or resize started by [`MoveResizeBegan`] ended.
+    ///
+    /// [`MoveResizeBegan`]: Self::MoveResizeBegan
+    MoveResizeEnded,
+
+    /// The window was snapped, maximized, made fullscreen or restored, see
+    /// [`Window::tiling_state`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`Window::tiling_state`]: crate::window::Window::tiling_state
+    TilingStateChanged(TilingState),
+
     /// The window has been requested to close.
     CloseRequested,
 
@@ -191,6 +396,27 @@ pub enum WindowEvent {
     /// hovered.
     HoveredFileCancelled,
 
+    /// Data has been dropped into the window, in the types the window accepted.
+    ///
+    /// The types are negotiated when the drag enters the window, see
+    /// [`Window::set_drop_negotiation()`]. By default, text and URIs are accepted. Dropped files
+    /// are accepted regardless, and are still reported with [`DroppedFile`][Self::DroppedFile]
+    /// before this event, which contains their URIs if [`DataTransfer::URI_LIST`] was accepted.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Drags are only delivered if they contain files, text, URLs, HTML, RTF, images
+    ///   or PDFs. Types without a MIME type are named by their uniform type identifier, like
+    ///   `com.apple.webarchive`.
+    /// - **Windows:** Formats registered by name are named by it, like `HTML Format`.
+    /// - **Wayland:** Files aren't accepted regardless, nor reported with `DroppedFile`, only with
+    ///   their URIs.
+    /// - **Web:** Only the types available as strings are offered, files are not read.
+    /// - **Android / iOS / Orbital:** Unsupported.
+    ///
+    /// [`Window::set_drop_negotiation()`]: crate::window::Window::set_drop_negotiation
+    DroppedData(DataTransfer),
+
     /// The window gained or lost focus.
     ///
     /// The parameter is true if the window has gained focus, and false if it has lost focus.
@@ -230,6 +456,26 @@ pub enum WindowEvent {
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     Ime(Ime),
 
+    /// The on-screen keyboard was shown, hidden, or moved.
+    ///
+    /// `occluded_rect` is the part of the window the keyboard covers, relative to the top-left
+    /// corner of its surface, so the focused text field can be scrolled above the keyboard. It's
+    /// `None` when the keyboard is hidden, or doesn't cover the window, like a floating keyboard
+    /// beside it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** The keyboard is assumed to be shown when the content rectangle of the
+    ///   activity shrinks by more than 15% of the window height, as the insets of the keyboard
+    ///   can't be queried without JNI. With `GameActivity`, this follows the animation of the
+    ///   keyboard.
+    /// - **iOS:** Emitted before the keyboard is animated, with the frame it ends at.
+    /// - **Web:** From the VirtualKeyboard API while the page sets
+    ///   `navigator.virtualKeyboard.overlaysContent`, otherwise the keyboard is assumed to be
+    ///   shown when it shrinks the visual viewport by more than 15%.
+    /// - **macOS / Orbital / Wayland / Windows / X11:** Unsupported.
+    KeyboardVisibilityChanged { visible: bool, occluded_rect: Option<Region> },
+
     /// The cursor has moved on the window.
     ///
     /// ## Platform-specific
@@ -251,15 +497,31 @@ pub enum WindowEvent {
 
     /// The cursor has entered the window.
     ///
+    /// The cursor is only in one window at a time, so the window it was in before always receives
+    /// [`CursorLeft`] first. This also holds while a mouse button is held and the window the drag
+    /// started in keeps receiving all mouse input, so dragging between the windows of the
+    /// application can be followed. [`CursorEnteredFrom`] is emitted right after this event.
+    ///
     /// ## Platform-specific
     ///
     /// - **Web:** Doesn't take into account CSS [`border`], [`padding`], or [`transform`].
+    /// - **Wayland:** The position of the cursor outside the window receiving the mouse input is
+    ///   unknown, so while a button is held, other windows are only entered once it is released.
     ///
+    /// [`CursorLeft`]: Self::CursorLeft
+    /// [`CursorEnteredFrom`]: Self::CursorEnteredFrom
     /// [`border`]: https://developer.mozilla.org/en-US/docs/Web/CSS/border
     /// [`padding`]: https://developer.mozilla.org/en-US/docs/Web/CSS/padding
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     CursorEntered { device_id: DeviceId },
 
+    /// Emitted right after [`CursorEntered`], with the window the cursor came from if it moved
+    /// directly from another window of the application, e.g. while dragging something between
+    /// them.
+    ///
+    /// [`CursorEntered`]: Self::CursorEntered
+    CursorEnteredFrom(Option<WindowId>),
+
     /// The cursor has left the window.
     ///
     /// ## Platform-specific
@@ -277,6 +539,64 @@ pub enum WindowEvent {
     /// An mouse button press has been received.
     MouseInput { device_id: DeviceId, state: ElementState, button: MouseButton },
 
+    /// A mouse button was clicked, emitted right after the [`MouseInput`] that pressed it.
+    ///
+    /// `click_count` is `2` for a double click, `3` for a triple click and so on. Presses of the
+    /// same button count as one click sequence as long as each follows the previous one within
+    /// the double click interval of the system, and the cursor stays within a few pixels of the
+    /// first click.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `GetDoubleClickTime` and `SM_CXDOUBLECLK` / `SM_CYDOUBLECLK`.
+    /// - **macOS:** Uses `NSEvent.doubleClickInterval`.
+    /// - **X11:** Uses the `Net/DoubleClickTime` and `Net/DoubleClickDistance` XSETTINGS, which
+    ///   mirror the desktop settings.
+    /// - **Web:** Uses the click count in `detail` of the `pointerdown` event where the browser
+    ///   reports it, and a fixed interval of 500 ms and a distance of 4 pixels otherwise.
+    /// - **Android / iOS / Orbital / Wayland:** Uses a fixed interval of 500 ms and a distance of
+    ///   4 pixels.
+    ///
+    /// [`MouseInput`]: Self::MouseInput
+    MouseClick {
+        device_id: DeviceId,
+        button: MouseButton,
+        click_count: u32,
+        /// The position of the cursor, see [`CursorMoved`](Self::CursorMoved).
+        position: PhysicalPosition<f64>,
+    },
+
+    /// A finger stayed on the window without moving, see [`Window::set_long_press()`].
+    ///
+    /// This is emitted while the finger is still down, in addition to its [`Touch`] events.
+    /// `id` and `position` are the ones of the [`Touch`] that started the press.
+    ///
+    /// [`Window::set_long_press()`]: crate::window::Window::set_long_press
+    /// [`Touch`]: Self::Touch
+    LongPress { device_id: DeviceId, id: u64, position: PhysicalPosition<f64> },
+
+    /// The user asked for a context menu, e.g. with a right click, a long press or the menu key.
+    ///
+    /// This is emitted in addition to the [`MouseInput`] and [`Touch`] events of the gesture, so
+    /// the application can show its own menu without having to recognize all the ways a context
+    /// menu can be requested on the platform. `position` is relative to the window, for
+    /// [`ContextMenuKind::Keyboard`] it is usually the position of the focused element or the
+    /// top-left corner of the window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted on `contextmenu`. Browsers without `PointerEvent`s for `contextmenu`
+    ///   report long presses as [`ContextMenuKind::Keyboard`]. Whether the menu of the browser is
+    ///   shown too is controlled by [`WindowExtWebSys::set_prevent_context_menu()`].
+    /// - **Windows:** Emitted on `WM_CONTEXTMENU`, the position is the top-left corner of the
+    ///   window for [`ContextMenuKind::Keyboard`].
+    /// - **Android / iOS / macOS / Orbital / Wayland / X11:** Unsupported.
+    ///
+    /// [`MouseInput`]: Self::MouseInput
+    /// [`Touch`]: Self::Touch
+    /// [`WindowExtWebSys::set_prevent_context_menu()`]: crate::platform::web::WindowExtWebSys::set_prevent_context_menu
+    ContextMenuRequested { position: PhysicalPosition<f64>, kind: ContextMenuKind },
+
     /// Two-finger pinch gesture, often used for magnification.
     ///
     /// ## Platform-specific
@@ -306,6 +626,27 @@ pub enum WindowEvent {
         phase: TouchPhase,
     },
 
+    /// A pan or scroll gesture ended, with the velocity it ended with.
+    ///
+    /// `velocity` is in physical pixels per second, in the direction of the positions of the
+    /// [`Touch`] or the deltas of the [`MouseWheel`] events. Scrolling views can continue moving
+    /// with it to implement inertia that matches the gesture. Zero if the fingers rested
+    /// before being lifted.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / Web / Windows:** Emitted after the [`Touch`] that lifted a finger which
+    ///   moved, estimated from its recent positions. On **iOS**, also emitted after a
+    ///   [`PanGesture`] ended, from the velocity reported by the recognizer.
+    /// - **macOS:** Emitted after a [`MouseWheel`] with [`TouchPhase::Ended`] from a trackpad,
+    ///   before the system starts its own momentum scrolling.
+    /// - **Orbital / Wayland / X11:** Unsupported.
+    ///
+    /// [`Touch`]: Self::Touch
+    /// [`PanGesture`]: Self::PanGesture
+    /// [`MouseWheel`]: Self::MouseWheel
+    PanEnded { device_id: DeviceId, velocity: PhysicalPosition<f64> },
+
     /// Double tap gesture.
     ///
     /// On a Mac, smart magnification is triggered by a double tap with two fingers
@@ -342,6 +683,20 @@ pub enum WindowEvent {
         phase: TouchPhase,
     },
 
+    /// The user swiped from a screen edge for which system gestures are deferred, so the system
+    /// gesture, like going to the home screen, needs another swipe.
+    ///
+    /// Fullscreen games can pause, or show a hint that another swipe leaves the game.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** Emitted for the edges set with
+    ///   [`WindowExtIOS::set_preferred_screen_edges_deferring_system_gestures()`].
+    /// - **Android / macOS / Orbital / Wayland / Web / Windows / X11:** Unsupported.
+    ///
+    /// [`WindowExtIOS::set_preferred_screen_edges_deferring_system_gestures()`]: crate::platform::ios::WindowExtIOS::set_preferred_screen_edges_deferring_system_gestures
+    SystemGestureDeferred { device_id: DeviceId },
+
     /// Touchpad pressure event.
     ///
     /// At the moment, only supported on Apple forcetouch-capable macbooks.
@@ -349,6 +704,13 @@ pub enum WindowEvent {
     /// touchpad is being pressed) and stage (integer representing the click level).
     TouchpadPressure { device_id: DeviceId, pressure: f32, stage: i64 },
 
+    /// An item of the window's Touch Bar was used.
+    ///
+    /// ## Platform-specific
+    ///
+    /// Only supported on macOS, with items set by `WindowExtMacOS::set_touch_bar`.
+    TouchBar(TouchBarEvent),
+
     /// Motion on some analog axis. May report data redundant to other, more specific events.
     AxisMotion { device_id: DeviceId, axis: AxisId, value: f64 },
 
@@ -423,6 +785,92 @@ pub enum WindowEvent {
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     Occluded(bool),
 
+    /// The visibility of the window changed, see [`Window::visibility`].
+    ///
+    /// This is emitted in addition to [`Focused`] and [`Occluded`], after them.
+    ///
+    /// [`Focused`]: Self::Focused
+    /// [`Occluded`]: Self::Occluded
+    VisibilityChanged(WindowVisibility),
+
+    /// The graphics context or device rendering to the window was lost.
+    ///
+    /// Everything allocated through it is gone and nothing drawn to the window will be presented
+    /// anymore. Drop the GPU resources and either wait for [`GraphicsContextRestored`] or create
+    /// a new device.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted on `webglcontextlost`, or when a `GPUDevice` registered with
+    ///   [`WindowExtWebSys::watch_gpu_device()`] is lost for any reason other than being
+    ///   destroyed.
+    /// - **Android / iOS / macOS / Orbital / Wayland / Windows / X11:** Unsupported.
+    ///
+    /// [`GraphicsContextRestored`]: Self::GraphicsContextRestored
+    /// [`WindowExtWebSys::watch_gpu_device()`]: crate::platform::web::WindowExtWebSys::watch_gpu_device
+    GraphicsContextLost,
+
+    /// The graphics context lost with [`GraphicsContextLost`] can be used again.
+    ///
+    /// GPU resources have to be recreated before rendering.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted on `webglcontextrestored`. A lost `GPUDevice` is never restored, a new
+    ///   one has to be requested instead.
+    /// - **Android / iOS / macOS / Orbital / Wayland / Windows / X11:** Unsupported.
+    ///
+    /// [`GraphicsContextLost`]: Self::GraphicsContextLost
+    GraphicsContextRestored,
+
+    /// The system started animating a change of the window state.
+    ///
+    /// The surface can be resized or shown scaled during the animation, so renderers may want to
+    /// pause or lower their quality until the matching [`TransitionEnded`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Emitted when entering and exiting fullscreen, and when minimizing.
+    /// - **Windows:** Emitted when minimizing and restoring through the title bar, the system menu
+    ///   or the taskbar, but not through [`Window::set_minimized`].
+    /// - **Web:** Emitted when entering and exiting fullscreen. If the browser changes the
+    ///   fullscreen state by itself, e.g. when pressing Escape, it is only emitted together with
+    ///   [`TransitionEnded`] once the change is done.
+    /// - **Android / iOS / Orbital / Wayland / X11:** Unsupported.
+    ///
+    /// [`TransitionEnded`]: Self::TransitionEnded
+    /// [`Window::set_minimized`]: crate::window::Window::set_minimized
+    TransitionStarted(Transition),
+
+    /// The animation reported with [`TransitionStarted`] ended, also if the change failed.
+    ///
+    /// [`TransitionStarted`]: Self::TransitionStarted
+    TransitionEnded(Transition),
+
+    /// The page containing the window is about to be printed, or was printed.
+    ///
+    /// On [`PrintPhase::Started`], the contents of the window are captured for printing right
+    /// after the event is handled, so this is the time to draw a frame at a higher resolution,
+    /// without waiting for [`RedrawRequested`]. Restore the regular rendering on
+    /// [`PrintPhase::Finished`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Emitted on `beforeprint` and `afterprint`, for every window, whether the page is
+    ///   printed through [`WindowExtWebSys::print()`] or the browser. The resolution of the canvas
+    ///   can be increased by changing its `width` and `height` attributes, without changing its
+    ///   size on the page.
+    /// - **Android / iOS / macOS / Orbital / Wayland / Windows / X11:** Unsupported.
+    ///
+    /// [`RedrawRequested`]: Self::RedrawRequested
+    /// [`WindowExtWebSys::print()`]: crate::platform::web::WindowExtWebSys::print
+    PrintRequested(PrintPhase),
+
+    /// The share sheet shown by [`Window::share()`] was closed.
+    ///
+    /// [`Window::share()`]: crate::window::Window::share
+    ShareCompleted(ShareOutcome),
+
     /// Emitted when a window should be redrawn.
     ///
     /// This gets triggered in two scenarios:
@@ -458,6 +906,57 @@ impl DeviceId {
         #[allow(unused_unsafe)]
         DeviceId(unsafe { platform_impl::DeviceId::dummy() })
     }
+
+    /// Returns the seat the device belongs to.
+    ///
+    /// Every input event carries the id of the device it came from, so this tells which seat
+    /// the event belongs to. Applications serving several users at once can use it to route the
+    /// input of each seat separately.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The devices of a seat are merged into one pointer, one keyboard and one touch
+    ///   device, which share a `DeviceId`.
+    /// - **X11:** Floating devices, which aren't attached to a master device, have no seat.
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Unsupported, always `None`.
+    pub fn seat_id(&self) -> Option<SeatId> {
+        self.0.seat_id()
+    }
+}
+
+/// Identifier of a seat, see [`ActiveEventLoop::seats()`].
+///
+/// The id of a seat may be reused after the seat was removed.
+///
+/// [`ActiveEventLoop::seats()`]: crate::event_loop::ActiveEventLoop::seats
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SeatId(pub(crate) u64);
+
+/// A group of input devices with its own pointer and keyboard focus, usually used by one person.
+///
+/// On Wayland, these are the seats of the compositor. On X11, these are the master devices
+/// created with the Multi-Pointer X extension, with the core pointer and keyboard as the first.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Seat {
+    id: SeatId,
+    name: String,
+}
+
+impl Seat {
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn new(id: SeatId, name: String) -> Self {
+        Self { id, name }
+    }
+
+    /// The identifier of the seat, which is also returned by [`DeviceId::seat_id()`].
+    pub fn id(&self) -> SeatId {
+        self.id
+    }
+
+    /// The name of the seat, e.g. `seat0`, which is empty if the platform doesn't provide one.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 /// Represents raw hardware events that are not associated with any particular window.
@@ -658,6 +1157,11 @@ pub struct KeyEvent {
 }
 
 /// Describes keyboard modifiers event.
+///
+/// ## Platform-specific
+///
+/// - **Wayland / Web:** The side of a modifier key is tracked from key presses, so it is
+///   [`ModifiersKeyState::Unknown`] for keys which were already held when the window gained focus.
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Modifiers {
     pub(crate) state: ModifiersState,
@@ -745,12 +1249,14 @@ impl From<ModifiersState> for Modifiers {
 ///
 /// ```ignore
 /// // Press "`" key
-/// Ime::Preedit("`", Some((0, 0)))
+/// Ime::Preedit("`", Some((0, 0)), ..)
 /// // Press "E" key
-/// Ime::Preedit("", None) // Synthetic event generated by winit to clear preedit.
+/// Ime::Preedit("", None, ..) // Synthetic event generated by winit to clear preedit.
 /// Ime::Commit("é")
 /// ```
 ///
+/// The styling of the preedit text is left out here.
+///
 /// Additionally, certain input devices are configured to display a candidate box that allow the
 /// user to select the desired character interactively. (To properly position this box, you must use
 /// [`Window::set_ime_cursor_area`].)
@@ -760,15 +1266,15 @@ impl From<ModifiersState> for Modifiers {
 ///
 /// ```ignore
 /// // Press "A" key
-/// Ime::Preedit("a", Some((1, 1)))
+/// Ime::Preedit("a", Some((1, 1)), ..)
 /// // Press "B" key
-/// Ime::Preedit("a b", Some((3, 3)))
+/// Ime::Preedit("a b", Some((3, 3)), ..)
 /// // Press left arrow key
-/// Ime::Preedit("a b", Some((1, 1)))
+/// Ime::Preedit("a b", Some((1, 1)), ..)
 /// // Press space key
-/// Ime::Preedit("啊b", Some((3, 3)))
+/// Ime::Preedit("啊b", Some((3, 3)), ..)
 /// // Press space key
-/// Ime::Preedit("", None) // Synthetic event generated by winit to clear preedit.
+/// Ime::Preedit("", None, ..) // Synthetic event generated by winit to clear preedit.
 /// Ime::Commit("啊不")
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -783,18 +1289,46 @@ pub enum Ime {
 
     /// Notifies when a new composing text should be set at the cursor position.
     ///
-    /// The value represents a pair of the preedit string and the cursor begin position and end
-    /// position. When it's `None`, the cursor should be hidden. When `String` is an empty string
-    /// this indicates that preedit was cleared.
+    /// The value represents the preedit string, the cursor begin position and end position, and
+    /// how the text should be styled. When the cursor is `None`, it should be hidden. When
+    /// `String` is an empty string this indicates that preedit was cleared.
+    ///
+    /// The cursor position and the [`PreeditSpan`]s are byte-wise indexed. The spans are sorted
+    /// and don't overlap. When they are empty, the platform doesn't report styling, and the whole
+    /// text is usually underlined.
     ///
-    /// The cursor position is byte-wise indexed.
-    Preedit(String, Option<(usize, usize)>),
+    /// ## Platform-specific
+    ///
+    /// - **Orbital / Wayland / Web:** The spans are always empty.
+    Preedit(String, Option<(usize, usize)>, Vec<PreeditSpan>),
 
     /// Notifies when text should be inserted into the editor widget.
     ///
     /// Right before this event winit will send empty [`Self::Preedit`] event.
     Commit(String),
 
+    /// Notifies when text around the cursor should be deleted, see
+    /// [`Window::set_ime_surrounding_text`].
+    ///
+    /// The lengths are in bytes of the surrounding text, before the start and after the end of the
+    /// selection, which is replaced by the next [`Preedit`][Self::Preedit] or
+    /// [`Commit`][Self::Commit] event instead. This event is sent after clearing the preedit, and
+    /// before the next commit.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Sent when the IME deleted text of the `InputConnection`, which has the
+    ///   surrounding text.
+    /// - **iOS / Web / X11 / Orbital:** Unsupported.
+    ///
+    /// [`Window::set_ime_surrounding_text`]: crate::window::Window::set_ime_surrounding_text
+    DeleteSurrounding {
+        /// The bytes to delete before the selection.
+        before_bytes: usize,
+        /// The bytes to delete after the selection.
+        after_bytes: usize,
+    },
+
     /// Notifies when the IME was disabled.
     ///
     /// After receiving this event you won't get any more [`Preedit`][Self::Preedit] or
@@ -804,6 +1338,132 @@ pub enum Ime {
     Disabled,
 }
 
+/// The style of a range of the preedit text, see [`Ime::Preedit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PreeditSpan {
+    /// The byte-wise start of the range.
+    pub start: usize,
+    /// The byte-wise end of the range, exclusive.
+    pub end: usize,
+    /// How the range is underlined.
+    pub underline: PreeditUnderline,
+    /// Whether the range is highlighted, which IMEs use for the clause being converted.
+    pub highlighted: bool,
+}
+
+impl PreeditSpan {
+    /// Merges the styles of consecutive characters into spans, leaving out unstyled characters.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn from_chars(
+        text: &str,
+        styles: impl IntoIterator<Item = (PreeditUnderline, bool)>,
+    ) -> Vec<Self> {
+        let mut spans: Vec<Self> = Vec::new();
+        for ((start, char), (underline, highlighted)) in text.char_indices().zip(styles) {
+            if underline == PreeditUnderline::None && !highlighted {
+                continue;
+            }
+
+            let end = start + char.len_utf8();
+            match spans.last_mut() {
+                Some(span)
+                    if span.end == start
+                        && span.underline == underline
+                        && span.highlighted == highlighted =>
+                {
+                    span.end = end
+                },
+                _ => spans.push(Self { start, end, underline, highlighted }),
+            }
+        }
+        spans
+    }
+}
+
+/// How a [`PreeditSpan`] is underlined.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PreeditUnderline {
+    /// No underline.
+    #[default]
+    None,
+    /// A dotted underline, used for text which wasn't converted yet.
+    Dotted,
+    /// A thin underline.
+    Thin,
+    /// A thick underline, used for the clause being converted.
+    Thick,
+}
+
+/// A change of the window state animated by the system.
+///
+/// See [`WindowEvent::TransitionStarted`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Transition {
+    /// The window enters fullscreen.
+    EnterFullscreen,
+    /// The window exits fullscreen.
+    ExitFullscreen,
+    /// The window is minimized.
+    Minimize,
+    /// The window is restored from being minimized.
+    Restore,
+}
+
+/// The phase of printing a window.
+///
+/// See [`WindowEvent::PrintRequested`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PrintPhase {
+    /// The window is about to be captured for printing.
+    Started,
+    /// Printing is done or was cancelled.
+    Finished,
+}
+
+/// How sharing data ended.
+///
+/// See [`WindowEvent::ShareCompleted`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ShareOutcome {
+    /// The data was handed to the application or service chosen by the user.
+    Shared,
+    /// The user closed the share sheet without sharing.
+    Cancelled,
+    /// The data couldn't be shared.
+    Failed,
+}
+
+/// Input on an item of the Touch Bar, identified by the `id` of the item.
+///
+/// See [`WindowEvent::TouchBar`].
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TouchBarEvent {
+    /// A button was pressed.
+    ButtonPressed { id: u64 },
+    /// A slider was moved, `value` being between its minimum and maximum.
+    SliderChanged { id: u64, value: f64 },
+}
+
+/// How a context menu was requested.
+///
+/// See [`WindowEvent::ContextMenuRequested`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ContextMenuKind {
+    /// A click with the secondary mouse button.
+    Mouse,
+    /// A long press with a finger or a pen.
+    Touch,
+    /// The menu key, or a shortcut like <kbd>Shift</kbd>+<kbd>F10</kbd>.
+    Keyboard,
+}
+
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -934,10 +1594,27 @@ impl ElementState {
 
 /// Describes a button of a mouse controller.
 ///
+/// Buttons are numbered like the [`button`] property of pointer events on Web, counting from `0`
+/// for [`Left`], then [`Middle`], [`Right`], [`Back`] and [`Forward`]. Any further button is
+/// reported as [`Other`] with its number, which starts at `5` unless noted below.
+///
 /// ## Platform-specific
 ///
-/// **macOS:** `Back` and `Forward` might not work with all hardware.
-/// **Orbital:** `Back` and `Forward` are unsupported due to orbital not supporting them.
+/// - **macOS:** `Back` and `Forward` might not work with all hardware.
+/// - **Wayland / X11:** `BTN_SIDE` and `BTN_BACK` are reported as `Back`, `BTN_EXTRA` and
+///   `BTN_FORWARD` as `Forward`. Other buttons are numbered by their evdev code, counting from
+///   `BTN_LEFT`, so the first `Other` button is `BTN_TASK` as `Other(7)`. On X11, buttons 4 to 7
+///   are the scroll wheel and reported as [`WindowEvent::MouseWheel`] instead.
+/// - **Windows:** Only `XBUTTON1` and `XBUTTON2` exist, reported as `Back` and `Forward`.
+/// - **Orbital:** `Back` and `Forward` are unsupported due to orbital not supporting them.
+///
+/// [`button`]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/button
+/// [`Left`]: Self::Left
+/// [`Middle`]: Self::Middle
+/// [`Right`]: Self::Right
+/// [`Back`]: Self::Back
+/// [`Forward`]: Self::Forward
+/// [`Other`]: Self::Other
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum MouseButton {
@@ -1009,6 +1686,312 @@ impl PartialEq for InnerSizeWriter {
     }
 }
 
+/// Guard of a synchronized resize, see [`WindowEvent::Resized`].
+///
+/// While any clone of it is alive, winit holds off completing the resize, so that the window
+/// isn't shown at the new size before its content is. Drop it once a frame with the new size
+/// was presented, e.g. at the end of the next [`WindowEvent::RedrawRequested`].
+///
+/// Dropping it right away is the same as not synchronizing the resize. Holding on to it for too
+/// long only stalls the resize up to a platform-specific timeout.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** The window procedure of `WM_SIZE` synchronously emits
+///   [`WindowEvent::RedrawRequested`] and then waits for the guard to be dropped, so that the
+///   system doesn't repaint the window before the content was resized.
+/// - **macOS:** During a live resize, [`WindowEvent::RedrawRequested`] is emitted synchronously and
+///   the view waits for the guard to be dropped before the window server shows the new frame.
+/// - **Wayland:** The compositor already only shows the new size once a buffer of that size is
+///   committed. Further configures are coalesced into a single [`WindowEvent::Resized`] emitted
+///   once the guard was dropped, so the application never falls behind the interactive resize.
+#[derive(Debug, Clone)]
+pub struct ResizeFrame {
+    token: Arc<ResizeFrameToken>,
+}
+
+impl ResizeFrame {
+    /// Create a guard and the handle to wait for it to be dropped.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn new(
+        on_presented: Option<Box<dyn Fn() + Send + Sync>>,
+    ) -> (Self, ResizeFrameWaiter) {
+        let presented = Arc::new((Mutex::new(false), Condvar::new()));
+        let token = ResizeFrameToken { presented: presented.clone(), on_presented };
+        (Self { token: Arc::new(token) }, ResizeFrameWaiter { presented })
+    }
+}
+
+impl PartialEq for ResizeFrame {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.token, &other.token)
+    }
+}
+
+struct ResizeFrameToken {
+    presented: Arc<(Mutex<bool>, Condvar)>,
+    on_presented: Option<Box<dyn Fn() + Send + Sync>>,
+}
+
+impl fmt::Debug for ResizeFrameToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResizeFrameToken").finish_non_exhaustive()
+    }
+}
+
+impl Drop for ResizeFrameToken {
+    fn drop(&mut self) {
+        let (presented, condvar) = &*self.presented;
+        *presented.lock().unwrap() = true;
+        condvar.notify_all();
+        if let Some(on_presented) = &self.on_presented {
+            on_presented();
+        }
+    }
+}
+
+/// Waits for all clones of a [`ResizeFrame`] to be dropped.
+#[derive(Debug)]
+pub(crate) struct ResizeFrameWaiter {
+    presented: Arc<(Mutex<bool>, Condvar)>,
+}
+
+#[allow(dead_code)] // Not used on every platform
+impl ResizeFrameWaiter {
+    pub(crate) fn is_presented(&self) -> bool {
+        *self.presented.0.lock().unwrap()
+    }
+
+    /// Returns whether the frame was presented before the timeout.
+    pub(crate) fn wait(&self, timeout: Duration) -> bool {
+        let (presented, condvar) = &*self.presented;
+        let guard = presented.lock().unwrap();
+        let (guard, _) =
+            condvar.wait_timeout_while(guard, timeout, |presented| !*presented).unwrap();
+        *guard
+    }
+}
+
+/// Handle to respond to [`ApplicationHandler::close_requested_by_os`].
+///
+/// [`ApplicationHandler::close_requested_by_os`]: crate::application::ApplicationHandler::close_requested_by_os
+#[derive(Debug, Clone)]
+pub struct CloseResponse {
+    pub(crate) cancelled: Weak<AtomicBool>,
+}
+
+impl CloseResponse {
+    #[allow(dead_code)]
+    pub(crate) fn new(cancelled: Weak<AtomicBool>) -> Self {
+        Self { cancelled }
+    }
+
+    /// Cancel the close, keeping the application running.
+    ///
+    /// This only has an effect while the event is being handled.
+    pub fn cancel(&self) -> Result<(), ExternalError> {
+        if let Some(cancelled) = self.cancelled.upgrade() {
+            cancelled.store(true, Ordering::Relaxed);
+            Ok(())
+        } else {
+            Err(ExternalError::Ignored)
+        }
+    }
+}
+
+impl PartialEq for CloseResponse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cancelled.as_ptr() == other.cancelled.as_ptr()
+    }
+}
+
+/// What dropping dragged data would do, see [`ApplicationHandler::drag_hover`].
+///
+/// [`ApplicationHandler::drag_hover`]: crate::application::ApplicationHandler::drag_hover
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DropOperation {
+    /// The data is copied into the application.
+    #[default]
+    Copy,
+    /// The data is moved into the application, the source removing it.
+    Move,
+    /// The application links to the data.
+    Link,
+    /// The data can't be dropped.
+    Reject,
+}
+
+/// Handle to respond to [`Event::DragHover`], with the operation returned by
+/// [`ApplicationHandler::drag_hover`].
+///
+/// [`ApplicationHandler::drag_hover`]: crate::application::ApplicationHandler::drag_hover
+#[derive(Debug, Clone)]
+pub struct DropResponse {
+    pub(crate) operation: Weak<Mutex<DropOperation>>,
+}
+
+impl DropResponse {
+    #[allow(dead_code)]
+    pub(crate) fn new(operation: Weak<Mutex<DropOperation>>) -> Self {
+        Self { operation }
+    }
+
+    /// Set what dropping the data at the position would do.
+    ///
+    /// This only has an effect while the event is being handled.
+    pub fn respond(&self, operation: DropOperation) -> Result<(), ExternalError> {
+        if let Some(current) = self.operation.upgrade() {
+            *current.lock().unwrap() = operation;
+            Ok(())
+        } else {
+            Err(ExternalError::Ignored)
+        }
+    }
+}
+
+impl PartialEq for DropResponse {
+    fn eq(&self, other: &Self) -> bool {
+        self.operation.as_ptr() == other.operation.as_ptr()
+    }
+}
+
+/// The history entry navigated to, see [`ApplicationHandler::navigation`].
+///
+/// [`ApplicationHandler::navigation`]: crate::application::ApplicationHandler::navigation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct PopState {
+    /// The absolute URL of the entry.
+    pub url: String,
+}
+
+/// A URL the application was asked to open, see [`ApplicationHandler::url_opened`].
+///
+/// [`ApplicationHandler::url_opened`]: crate::application::ApplicationHandler::url_opened
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Url(String);
+
+impl Url {
+    /// Returns `None` unless the text starts with a scheme followed by a colon, like
+    /// `myapp:path` or `myapp://host/path`.
+    ///
+    /// Single letter schemes aren't accepted, to tell URLs apart from Windows paths.
+    pub fn parse(text: &str) -> Option<Self> {
+        let (scheme, rest) = text.split_once(':')?;
+        let mut chars = scheme.chars();
+        let valid = chars.next().is_some_and(|char| char.is_ascii_alphabetic())
+            && chars.all(|char| char.is_ascii_alphanumeric() || matches!(char, '+' | '-' | '.'));
+        (valid && scheme.len() > 1 && !rest.is_empty()).then(|| Self(text.to_owned()))
+    }
+
+    /// The scheme of the URL, e.g. `myapp` for `myapp://host/path`.
+    pub fn scheme(&self) -> &str {
+        self.0.split_once(':').map_or("", |(scheme, _)| scheme)
+    }
+
+    /// The URL as text, as it was passed to the application.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Url {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// A Unix signal received by the process, see [`ApplicationHandler::signal`].
+///
+/// [`ApplicationHandler::signal`]: crate::application::ApplicationHandler::signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Signal {
+    /// `SIGINT`, sent when the user presses `Ctrl+C` in the terminal.
+    Interrupt,
+    /// `SIGTERM`, asking the process to terminate, e.g. sent by `kill` or the service manager.
+    Terminate,
+    /// `SIGHUP`, sent when the terminal is closed.
+    Hangup,
+    /// `SIGQUIT`, sent when the user presses `Ctrl+\` in the terminal.
+    Quit,
+    /// `SIGUSR1`, with a meaning defined by the application.
+    User1,
+    /// `SIGUSR2`, with a meaning defined by the application.
+    User2,
+}
+
+/// Data dropped into a window, see [`WindowEvent::DroppedData`].
+///
+/// The data is available in each of the accepted types that the source provided, identified by
+/// their MIME type.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DataTransfer {
+    items: Vec<(String, Vec<u8>)>,
+}
+
+impl DataTransfer {
+    /// UTF-8 encoded text.
+    pub const TEXT: &'static str = "text/plain;charset=utf-8";
+    /// A list of URIs, one per line, as specified by RFC 2483.
+    pub const URI_LIST: &'static str = "text/uri-list";
+
+    /// The types accepted by default, in order of preference.
+    pub(crate) const DEFAULT_TYPES: [&'static str; 3] = [Self::TEXT, "text/plain", Self::URI_LIST];
+
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the data of a type, replacing the data it already had.
+    pub(crate) fn insert(&mut self, mime_type: impl Into<String>, data: Vec<u8>) {
+        let mime_type = mime_type.into();
+        match self.items.iter_mut().find(|(existing, _)| *existing == mime_type) {
+            Some((_, existing)) => *existing = data,
+            None => self.items.push((mime_type, data)),
+        }
+    }
+
+    /// Returns the types the data is available in.
+    pub fn types(&self) -> impl Iterator<Item = &str> {
+        self.items.iter().map(|(mime_type, _)| mime_type.as_str())
+    }
+
+    /// Returns the data of a type, if it is available in it.
+    pub fn get(&self, mime_type: &str) -> Option<&[u8]> {
+        self.items.iter().find(|(existing, _)| existing == mime_type).map(|(_, data)| &data[..])
+    }
+
+    /// Returns the dropped text, from [`TEXT`][Self::TEXT] or `text/plain`.
+    ///
+    /// Invalid UTF-8 is replaced with [`U+FFFD`][char::REPLACEMENT_CHARACTER].
+    pub fn text(&self) -> Option<String> {
+        let data = self.get(Self::TEXT).or_else(|| self.get("text/plain"))?;
+        Some(String::from_utf8_lossy(data).into_owned())
+    }
+
+    /// Returns the dropped URIs, from [`URI_LIST`][Self::URI_LIST].
+    ///
+    /// Comments and lines that aren't URIs are skipped, the URIs are not percent-decoded.
+    pub fn uris(&self) -> Vec<Url> {
+        let Some(data) = self.get(Self::URI_LIST) else {
+            return Vec::new();
+        };
+        String::from_utf8_lossy(data)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(Url::parse)
+            .collect()
+    }
+
+    /// Returns `true` if no data is available.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::dpi::PhysicalPosition;
@@ -1033,9 +2016,41 @@ mod tests {
                 x(UserEvent(()));
                 x(NewEvents(event::StartCause::Init));
                 x(AboutToWait);
+                x(Idle { deadline: super::Instant::now() });
+                x(CloseRequestedByOS { respond: event::CloseResponse::new(Default::default()) });
+                x(Notification {
+                    id: crate::notification::NotificationId::next(),
+                    event: crate::notification::NotificationEvent::Clicked,
+                });
+                x(Terminating);
                 x(LoopExiting);
+                x(SessionLocked);
+                x(SessionUnlocked);
+                x(DisplaySleep);
+                x(DisplayWake);
+                x(LocaleChanged(crate::locale::LanguageTag::parse("en-US").unwrap()));
+                x(WorkAreaChanged);
+                x(Navigation(event::PopState { url: String::from("https://example.com/") }));
+                x(ColorProfileChanged);
+                x(ActivationRequest { args: vec!["x.txt".into()], activation_token: None });
+                x(UrlOpened(event::Url::parse("myapp://x").unwrap()));
+                x(FilesOpened(vec!["x.txt".into()]));
+                x(TrayActivated(wid));
+                x(Signal(event::Signal::Interrupt));
+                x(FdReady(0));
+                x(HandleSignaled(0));
+                x(MonitorsChanged);
+                x(AudioInterrupted);
+                x(AudioResumed);
+                x(DragHover {
+                    window_id: wid,
+                    position: (0, 0).into(),
+                    respond: event::DropResponse::new(Default::default()),
+                });
                 x(Suspended);
                 x(Resumed);
+                x(SurfaceCreated(wid));
+                x(SurfaceDestroyed(wid));
 
                 // Window events.
                 let with_window_event = |wev| x(WindowEvent { window_id: wid, event: wev });
@@ -1044,14 +2059,19 @@ mod tests {
                 with_window_event(Destroyed);
                 with_window_event(Focused(true));
                 with_window_event(Moved((0, 0).into()));
-                with_window_event(Resized((0, 0).into()));
+                with_window_event(MoveResizeBegan);
+                with_window_event(MoveResizeEnded);
+                with_window_event(TilingStateChanged(crate::window::TilingState::Floating));
+                with_window_event(Resized { size: (0, 0).into(), frame: None });
                 with_window_event(DroppedFile("x.txt".into()));
                 with_window_event(HoveredFile("x.txt".into()));
                 with_window_event(HoveredFileCancelled);
+                with_window_event(DroppedData(event::DataTransfer::new()));
                 with_window_event(Ime(Enabled));
                 with_window_event(CursorMoved { device_id: did, position: (0, 0).into() });
                 with_window_event(ModifiersChanged(event::Modifiers::default()));
                 with_window_event(CursorEntered { device_id: did });
+                with_window_event(CursorEnteredFrom(None));
                 with_window_event(CursorLeft { device_id: did });
                 with_window_event(MouseWheel {
                     device_id: did,
@@ -1063,12 +2083,26 @@ mod tests {
                     state: event::ElementState::Pressed,
                     button: event::MouseButton::Other(0),
                 });
+                with_window_event(MouseClick {
+                    device_id: did,
+                    button: event::MouseButton::Left,
+                    click_count: 2,
+                    position: (0, 0).into(),
+                });
                 with_window_event(PinchGesture {
                     device_id: did,
                     delta: 0.0,
                     phase: event::TouchPhase::Started,
                 });
                 with_window_event(DoubleTapGesture { device_id: did });
+                with_window_event(SystemGestureDeferred { device_id: did });
+                with_window_event(KeyboardVisibilityChanged {
+                    visible: true,
+                    occluded_rect: Some(crate::window::Region::new(
+                        PhysicalPosition::new(0, 100),
+                        crate::dpi::PhysicalSize::new(100, 50),
+                    )),
+                });
                 with_window_event(RotationGesture {
                     device_id: did,
                     delta: 0.0,
@@ -1079,7 +2113,14 @@ mod tests {
                     delta: PhysicalPosition::<f32>::new(0.0, 0.0),
                     phase: event::TouchPhase::Started,
                 });
+                with_window_event(LongPress { device_id: did, id: 0, position: (0.0, 0.0).into() });
+                with_window_event(ContextMenuRequested {
+                    position: (0.0, 0.0).into(),
+                    kind: event::ContextMenuKind::Mouse,
+                });
+                with_window_event(PanEnded { device_id: did, velocity: (0.0, 0.0).into() });
                 with_window_event(TouchpadPressure { device_id: did, pressure: 0.0, stage: 0 });
+                with_window_event(TouchBar(event::TouchBarEvent::ButtonPressed { id: 0 }));
                 with_window_event(AxisMotion { device_id: did, axis: 0, value: 0.0 });
                 with_window_event(Touch(event::Touch {
                     device_id: did,
@@ -1090,6 +2131,13 @@ mod tests {
                 }));
                 with_window_event(ThemeChanged(crate::window::Theme::Light));
                 with_window_event(Occluded(true));
+                with_window_event(VisibilityChanged(crate::window::WindowVisibility::Visible));
+                with_window_event(GraphicsContextLost);
+                with_window_event(GraphicsContextRestored);
+                with_window_event(TransitionStarted(event::Transition::EnterFullscreen));
+                with_window_event(TransitionEnded(event::Transition::EnterFullscreen));
+                with_window_event(PrintRequested(event::PrintPhase::Started));
+                with_window_event(ShareCompleted(event::ShareOutcome::Shared));
             }
 
             #[allow(deprecated)]
@@ -1150,6 +2198,59 @@ mod tests {
         assert_eq!(force3.normalized(), 2.0);
     }
 
+    #[test]
+    fn preedit_spans_from_chars() {
+        use event::PreeditUnderline::{Dotted, None, Thick};
+
+        let spans = event::PreeditSpan::from_chars("啊b cd", [
+            (Thick, true),
+            (Thick, true),
+            (None, false),
+            (Dotted, false),
+            (Dotted, false),
+        ]);
+        assert_eq!(spans, [
+            event::PreeditSpan { start: 0, end: 4, underline: Thick, highlighted: true },
+            event::PreeditSpan { start: 5, end: 7, underline: Dotted, highlighted: false },
+        ]);
+    }
+
+    #[test]
+    fn url() {
+        let url = event::Url::parse("my-app+x.y://host/path?a=b").unwrap();
+        assert_eq!(url.scheme(), "my-app+x.y");
+        assert_eq!(url.as_str(), "my-app+x.y://host/path?a=b");
+        assert_eq!(event::Url::parse("mailto:a@example.com").unwrap().scheme(), "mailto");
+
+        for text in ["", "path/file.txt", "C:\\file.txt", "1x://host", "my app://host", "myapp:"] {
+            assert_eq!(event::Url::parse(text), None, "{text}");
+        }
+    }
+
+    #[test]
+    fn data_transfer() {
+        let mut data = event::DataTransfer::new();
+        assert!(data.is_empty());
+        assert_eq!(data.text(), None);
+
+        data.insert("text/plain", b"latin \xe9".to_vec());
+        assert_eq!(data.text().as_deref(), Some("latin \u{fffd}"));
+        data.insert(event::DataTransfer::TEXT, "añb".into());
+        assert_eq!(data.text().as_deref(), Some("añb"));
+
+        let uris = "# comment\r\nfile:///tmp/a%20b.txt\r\nhttps://example.com/\r\n";
+        data.insert(event::DataTransfer::URI_LIST, uris.into());
+        data.insert("text/plain", Vec::new());
+        assert_eq!(data.types().collect::<Vec<_>>(), [
+            "text/plain",
+            event::DataTransfer::TEXT,
+            event::DataTransfer::URI_LIST
+        ]);
+        let uris: Vec<_> = data.uris().iter().map(|uri| uri.as_str().to_owned()).collect();
+        assert_eq!(uris, ["file:///tmp/a%20b.txt", "https://example.com/"]);
+        assert_eq!(data.get("image/png"), None);
+    }
+
     #[allow(clippy::clone_on_copy)]
     #[test]
     fn ensure_attrs_do_not_panic() {
@@ -7,11 +7,17 @@
 //!
 //! See the root-level documentation for information on how to create and use an event loop to
 //! handle events.
+use std::ffi::OsString;
+use std::future::Future;
 use std::marker::PhantomData;
 #[cfg(any(x11_platform, wayland_platform))]
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::{error, fmt};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::Waker;
+use std::{env, error, fmt, io};
 
 #[cfg(not(web_platform))]
 use std::time::{Duration, Instant};
@@ -19,10 +25,44 @@ use std::time::{Duration, Instant};
 use web_time::{Duration, Instant};
 
 use crate::application::ApplicationHandler;
-use crate::error::{EventLoopError, OsError};
-use crate::monitor::MonitorHandle;
-use crate::platform_impl;
-use crate::window::{CustomCursor, CustomCursorSource, Window, WindowAttributes};
+use crate::dialog::{self, DialogButton, DialogFuture, MessageDialog};
+use crate::dpi::PhysicalPosition;
+use crate::error::{EventLoopError, ExternalError, OsError, RequestError};
+use crate::event::{
+    CloseResponse, DeviceEvent, DeviceId, DropOperation, PopState, Seat, Signal, StartCause, Url,
+    WindowEvent,
+};
+use crate::locale::LanguageTag;
+use crate::monitor::{MonitorHandle, VideoModeHandle};
+use crate::notification::{
+    NotificationAttributes, NotificationEvent, NotificationHandle, NotificationId,
+};
+use crate::session::SessionInfo;
+use crate::window::{
+    ActivationToken, CustomCursor, CustomCursorSource, Window, WindowAttributes, WindowId,
+};
+use crate::{app, command_queue, frame_budget, platform_impl, redraw_thread};
+
+// The state these modules keep between events is thread local, as only one event loop can exist,
+// and it is only accessible from its own thread. The exception are the long press gestures of the
+// windows, which are global, as windows can be used from any thread.
+mod click;
+mod crossing;
+pub(crate) mod exit;
+mod filter;
+mod fling;
+pub(crate) mod long_press;
+mod pending_window;
+#[cfg(feature = "trace")]
+mod stats;
+#[cfg(not(any(android_platform, web_platform)))]
+mod surface;
+mod unfocused;
+mod video_mode;
+
+pub use self::filter::EventFilter;
+#[cfg(feature = "trace")]
+pub use self::stats::EventLoopStats;
 
 /// Provides a way to retrieve events from the system and from the windows that were registered to
 /// the events loop.
@@ -40,7 +80,11 @@ use crate::window::{CustomCursor, CustomCursorSource, Window, WindowAttributes};
 ///
 /// [`Window`]: crate::window::Window
 pub struct EventLoop<T: 'static> {
-    pub(crate) event_loop: platform_impl::EventLoop<T>,
+    // Dropped first, to restore the monitors before the connection to the display is closed.
+    pub(crate) _video_modes: video_mode::Guard,
+    pub(crate) event_loop: platform_impl::EventLoop<ProxyMessage<T>>,
+    // Proxies only hold a weak reference, to know whether the event loop is still alive.
+    pub(crate) wake_ups: Arc<WakeUps>,
     pub(crate) _marker: PhantomData<*mut ()>, // Not Send nor Sync
 }
 
@@ -62,11 +106,17 @@ pub struct ActiveEventLoop {
 #[derive(Default)]
 pub struct EventLoopBuilder<T: 'static> {
     pub(crate) platform_specific: platform_impl::PlatformSpecificEventLoopAttributes,
+    backend_preference: Vec<Backend>,
+    dpi_policy: DpiPolicy,
+    url_schemes: Vec<String>,
+    file_arguments: bool,
     _p: PhantomData<T>,
 }
 
 static EVENT_LOOP_CREATED: AtomicBool = AtomicBool::new(false);
 
+static DPI_POLICY: Mutex<DpiPolicy> = Mutex::new(DpiPolicy::System);
+
 impl EventLoopBuilder<()> {
     /// Start building a new event loop.
     #[inline]
@@ -96,11 +146,12 @@ impl<T> EventLoopBuilder<T> {
     /// ## Platform-specific
     ///
     /// - **Wayland/X11:** to prevent running under `Wayland` or `X11` unset `WAYLAND_DISPLAY` or
-    ///   `DISPLAY` respectively when building the event loop.
+    ///   `DISPLAY` respectively when building the event loop, or use [`with_backend_preference()`].
     /// - **Android:** must be configured with an `AndroidApp` from `android_main()` by calling
     ///   [`.with_android_app(app)`] before calling `.build()`, otherwise it'll panic.
     ///
     /// [`platform`]: crate::platform
+    /// [`with_backend_preference()`]: Self::with_backend_preference
     #[cfg_attr(
         android_platform,
         doc = "[`.with_android_app(app)`]: \
@@ -118,14 +169,124 @@ impl<T> EventLoopBuilder<T> {
             return Err(EventLoopError::RecreationAttempt);
         }
 
+        #[cfg(feature = "trace")]
+        stats::reset();
+
+        *DPI_POLICY.lock().unwrap() = self.dpi_policy;
+        app::URL_SCHEMES.lock().unwrap().clone_from(&self.url_schemes);
+        app::FILE_ARGUMENTS.store(self.file_arguments, Ordering::Relaxed);
+
+        #[cfg(any(x11_platform, wayland_platform))]
+        self.platform_specific.backend_preference.clone_from(&self.backend_preference);
+        #[cfg(not(any(x11_platform, wayland_platform)))]
+        if !self.backend_preference.is_empty() && !self.backend_preference.contains(&NATIVE_BACKEND)
+        {
+            let failures = self
+                .backend_preference
+                .iter()
+                .map(|&backend| (backend, "not available on this platform".to_owned()))
+                .collect();
+            return Err(EventLoopError::Backend(crate::error::BackendError::new(failures)));
+        }
+
         // Certain platforms accept a mutable reference in their API.
         #[allow(clippy::unnecessary_mut_passed)]
         Ok(EventLoop {
+            _video_modes: video_mode::Guard::default(),
             event_loop: platform_impl::EventLoop::new(&mut self.platform_specific)?,
+            wake_ups: Arc::new(WakeUps::default()),
             _marker: PhantomData,
         })
     }
 
+    /// Sets the backends to try when building the event loop, in order of preference.
+    ///
+    /// The first backend that can be initialized is used, which can be queried with
+    /// [`ActiveEventLoop::backend()`]. If none of them can, [`build()`] returns
+    /// [`EventLoopError::Backend`] with the reason each of them failed. This allows forcing or
+    /// forbidding backends at runtime, e.g. based on a command line argument.
+    ///
+    /// By default, or if the list is empty, the backend is chosen automatically.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** Without a preference, Wayland is used if `WAYLAND_DISPLAY` or
+    ///   `WAYLAND_SOCKET` is set, and X11 otherwise. `EventLoopBuilderExtX11::with_x11()` and
+    ///   `EventLoopBuilderExtWayland::with_wayland()` take precedence over this.
+    /// - **Other platforms:** Only have a single backend, which has to be part of the list.
+    ///
+    /// [`build()`]: Self::build
+    #[inline]
+    pub fn with_backend_preference(&mut self, backends: &[Backend]) -> &mut Self {
+        self.backend_preference = backends.to_vec();
+        self
+    }
+
+    /// Sets how the scale factor of windows and monitors is determined.
+    ///
+    /// This allows e.g. pixel-art games and terminal emulators to only ever see integer scale
+    /// factors, or to work in physical pixels. The policy applies to everything reporting a scale
+    /// factor, including [`WindowEvent::ScaleFactorChanged`].
+    ///
+    /// The default is [`DpiPolicy::System`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Non-integer scale factors require the compositor to support fractional
+    ///   scaling, otherwise they are rounded.
+    /// - **Web:** The policy is applied to `devicePixelRatio`, which includes the page zoom of the
+    ///   browser, so the page zoom keeps working with [`DpiPolicy::Round`] but is ignored by
+    ///   [`DpiPolicy::Force`] and [`DpiPolicy::Physical`]. The pinch zoom never affects the scale
+    ///   factor.
+    /// - **iOS / macOS / Android / Orbital:** Unsupported, the scale factor of the system is always
+    ///   used.
+    #[inline]
+    pub fn with_dpi_policy(&mut self, policy: DpiPolicy) -> &mut Self {
+        self.dpi_policy = policy;
+        self
+    }
+
+    /// Sets the URL schemes registered for the application, like `myapp` for `myapp://path`.
+    ///
+    /// Command line arguments are only emitted as [`ApplicationHandler::url_opened()`] if they are
+    /// URLs with one of these schemes, compared case-insensitively, so that arguments like
+    /// `localhost:8080` aren't taken for URLs. By default, no arguments are.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / Wayland / X11:** Applies to the arguments of the process, and the ones
+    ///   forwarded by later instances, see [`SingleInstance`].
+    /// - **Android / iOS / macOS / Orbital / Web:** Unused, the system delivers the URLs itself.
+    ///
+    /// [`SingleInstance`]: crate::app::SingleInstance
+    #[inline]
+    pub fn with_url_schemes(&mut self, schemes: &[&str]) -> &mut Self {
+        self.url_schemes = schemes.iter().map(|&scheme| scheme.to_owned()).collect();
+        self
+    }
+
+    /// Sets whether the existing files among the command line arguments are emitted as
+    /// [`ApplicationHandler::files_opened()`], for applications that the system starts with the
+    /// documents to open as arguments.
+    ///
+    /// Arguments starting with `-` and URLs are skipped, but the values of options aren't, so
+    /// `--config settings.toml` would open `settings.toml`. Only enable this if no other argument
+    /// of the application is a path. Disabled by default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / Wayland / X11:** Applies to the arguments of the process, and the ones
+    ///   forwarded by later instances, see [`SingleInstance`].
+    /// - **Android / iOS / macOS / Orbital / Web:** Unused, the system delivers the documents
+    ///   itself.
+    ///
+    /// [`SingleInstance`]: crate::app::SingleInstance
+    #[inline]
+    pub fn with_file_arguments(&mut self, enabled: bool) -> &mut Self {
+        self.file_arguments = enabled;
+        self
+    }
+
     #[cfg(web_platform)]
     pub(crate) fn allow_event_loop_recreation() {
         EVENT_LOOP_CREATED.store(false, Ordering::Relaxed);
@@ -150,7 +311,15 @@ impl fmt::Debug for ActiveEventLoop {
 ///
 /// Defaults to [`Wait`].
 ///
+/// ## Platform-specific
+///
+/// - **Windows:** The modal loops of the system, which run while a window is moved or resized or
+///   one of its menus is open, don't return to the event loop. It is instead iterated from a timer
+///   there, so [`Poll`] and [`WaitUntil`] wake it at most every 10 milliseconds.
+///
 /// [`Wait`]: Self::Wait
+/// [`Poll`]: Self::Poll
+/// [`WaitUntil`]: Self::WaitUntil
 /// [`Event::AboutToWait`]: crate::event::Event::AboutToWait
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum ControlFlow {
@@ -187,6 +356,46 @@ impl ControlFlow {
             None => Self::Wait,
         }
     }
+
+    /// Moves the deadline of [`WaitUntil`] earlier by the average frame time reported with
+    /// [`Window::report_frame_time()`], so that the application is done drawing by the deadline.
+    ///
+    /// [`WaitUntil`]: Self::WaitUntil
+    /// [`Window::report_frame_time()`]: crate::window::Window::report_frame_time
+    fn with_frame_budget(self) -> Self {
+        match (self, frame_budget::frame_time()) {
+            (Self::WaitUntil(deadline), Some(frame_time)) => {
+                Self::WaitUntil(deadline.checked_sub(frame_time).unwrap_or(deadline))
+            },
+            (control_flow, _) => control_flow,
+        }
+    }
+}
+
+/// Set through [`ActiveEventLoop::set_unfocused_policy()`].
+///
+/// Indicates how the event loop should behave under [`ControlFlow::Poll`] while none of the
+/// application's windows has focus.
+///
+/// Defaults to [`Normal`].
+///
+/// [`Normal`]: Self::Normal
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum UnfocusedPolicy {
+    /// Keep polling as usual.
+    #[default]
+    Normal,
+
+    /// Start at most the given number of iterations per second, as if [`ControlFlow::WaitUntil`]
+    /// was set to the start of the current iteration plus the frame time.
+    ///
+    /// A rate of `0` is the same as [`Pause`].
+    ///
+    /// [`Pause`]: Self::Pause
+    ThrottleTo(u32),
+
+    /// Behave as if [`ControlFlow::Wait`] was set, only waking up for new events.
+    Pause,
 }
 
 impl EventLoop<()> {
@@ -213,7 +422,14 @@ impl<T> EventLoop<T> {
     /// Start building a new event loop, with the given type as the user event
     /// type.
     pub fn with_user_event() -> EventLoopBuilder<T> {
-        EventLoopBuilder { platform_specific: Default::default(), _p: PhantomData }
+        EventLoopBuilder {
+            platform_specific: Default::default(),
+            backend_preference: Vec::new(),
+            dpi_policy: DpiPolicy::System,
+            url_schemes: Vec::new(),
+            file_arguments: false,
+            _p: PhantomData,
+        }
     }
 
     /// Run the application with the event loop on the calling thread.
@@ -247,13 +463,17 @@ impl<T> EventLoop<T> {
     #[inline]
     #[cfg(not(all(web_platform, target_feature = "exception-handling")))]
     pub fn run_app<A: ApplicationHandler<T>>(self, app: &mut A) -> Result<(), EventLoopError> {
-        self.event_loop.run_app(app)
+        let mut handler = WakeUpHandler::new(app, self.create_proxy());
+        exit::finish_run(self.event_loop.run_app(&mut handler))
     }
 
     /// Creates an [`EventLoopProxy`] that can be used to dispatch user events
     /// to the main event loop, possibly from another thread.
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy { event_loop_proxy: self.event_loop.create_proxy() }
+        EventLoopProxy {
+            event_loop_proxy: self.event_loop.create_proxy(),
+            wake_ups: Arc::downgrade(&self.wake_ups),
+        }
     }
 
     /// Gets a persistent reference to the underlying platform display.
@@ -279,8 +499,49 @@ impl<T> EventLoop<T> {
     }
 
     /// Sets the [`ControlFlow`].
+    ///
+    /// While frame times are reported with [`Window::report_frame_time()`], the deadline of
+    /// [`ControlFlow::WaitUntil`] is moved earlier by the average frame time.
+    ///
+    /// [`Window::report_frame_time()`]: crate::window::Window::report_frame_time
     pub fn set_control_flow(&self, control_flow: ControlFlow) {
-        self.event_loop.window_target().p.set_control_flow(control_flow)
+        self.event_loop.window_target().p.set_control_flow(control_flow.with_frame_budget())
+    }
+
+    /// Sets the accuracy with which [`ControlFlow::WaitUntil`] should wake up.
+    ///
+    /// See [`ActiveEventLoop::set_timer_resolution()`] for details.
+    pub fn set_timer_resolution(&self, resolution: Duration) {
+        self.event_loop.window_target().p.set_timer_resolution(resolution)
+    }
+
+    /// Sets how [`ControlFlow::Poll`] behaves while no window is focused.
+    ///
+    /// See [`ActiveEventLoop::set_unfocused_policy()`] for details.
+    pub fn set_unfocused_policy(&self, policy: UnfocusedPolicy) {
+        unfocused::set_policy(policy)
+    }
+
+    /// Sets the [`EventFilter`] that sees window and device events before they are dispatched to
+    /// the [`ApplicationHandler`], replacing the previous one.
+    ///
+    /// The filter runs after winit's own bookkeeping, so it can't affect e.g.
+    /// [`ActiveEventLoop::create_window_async()`] or [`ActiveEventLoop::set_unfocused_policy()`].
+    pub fn set_event_filter(&self, filter: impl EventFilter) {
+        filter::set(Some(Box::new(filter)))
+    }
+
+    /// Removes the [`EventFilter`] set with [`EventLoop::set_event_filter()`].
+    pub fn remove_event_filter(&self) {
+        filter::set(None)
+    }
+
+    /// Statistics about the events dispatched so far.
+    ///
+    /// See [`ActiveEventLoop::stats()`] for details.
+    #[cfg(feature = "trace")]
+    pub fn stats(&self) -> EventLoopStats {
+        stats::current()
     }
 
     /// Create a window.
@@ -298,7 +559,9 @@ impl<T> EventLoop<T> {
 
         let window =
             platform_impl::Window::new(&self.event_loop.window_target().p, window_attributes)?;
-        Ok(Window { window })
+        #[cfg(not(any(android_platform, web_platform)))]
+        surface::created(WindowId(window.id()));
+        Ok(Window::new(window))
     }
 
     /// Create custom cursor.
@@ -368,7 +631,37 @@ impl ActiveEventLoop {
         .entered();
 
         let window = platform_impl::Window::new(&self.p, window_attributes)?;
-        Ok(Window { window })
+        #[cfg(not(any(android_platform, web_platform)))]
+        surface::created(WindowId(window.id()));
+        Ok(Window::new(window))
+    }
+
+    /// Create the window, and return a future that resolves to it once it is shown.
+    ///
+    /// The window is created right away, like with [`create_window()`], but only handed out once
+    /// it received its first size, at which point [`Window::inner_size()`] and
+    /// [`Window::scale_factor()`] return their actual values and it is safe to create a surface
+    /// for it. Creation errors are returned when the future is first polled.
+    ///
+    /// The future is woken up while the event loop dispatches the events of the window, so it has
+    /// to be polled by an executor that doesn't block the event loop. Events of the window,
+    /// including the first [`WindowEvent::Resized`], are delivered to the application as usual in
+    /// the meantime.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The future resolves once the canvas was inserted into the web page and laid out.
+    ///   Use `wasm_bindgen_futures::spawn_local()` to run it.
+    /// - **Wayland:** The future resolves once the compositor configured the window.
+    ///
+    /// [`create_window()`]: Self::create_window
+    pub fn create_window_async(
+        &self,
+        window_attributes: WindowAttributes,
+    ) -> impl Future<Output = Result<Window, OsError>> + Send + 'static {
+        let _span = tracing::debug_span!("winit::ActiveEventLoop::create_window_async",).entered();
+
+        pending_window::create(self, window_attributes)
     }
 
     /// Create custom cursor.
@@ -378,6 +671,74 @@ impl ActiveEventLoop {
         self.p.create_custom_cursor(custom_cursor)
     }
 
+    /// Show a notification to the user.
+    ///
+    /// Interactions with the notification are reported to
+    /// [`ApplicationHandler::notification_event`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Uses the `UserNotifications` framework, which requires the application to be
+    ///   bundled with a bundle identifier. The user is asked for permission the first time a
+    ///   notification is shown, and notifications are dropped until it was granted.
+    /// - **Web:** Uses the [Notifications API]. The permission has to be granted beforehand, see
+    ///   `ActiveEventLoopExtWebSys::request_permission`.
+    /// - **Windows:** Uses a balloon of a notification area icon, shown as a toast on Windows 10
+    ///   and later. The icon is removed again when the notification is closed or dismissed.
+    /// - **Android / iOS / Orbital / Wayland / X11:** Unsupported.
+    ///
+    /// [Notifications API]: https://developer.mozilla.org/en-US/docs/Web/API/Notifications_API
+    pub fn show_notification(
+        &self,
+        attributes: NotificationAttributes,
+    ) -> Result<NotificationHandle, ExternalError> {
+        let _span = tracing::debug_span!(
+            "winit::ActiveEventLoop::show_notification",
+            attributes = ?attributes
+        )
+        .entered();
+
+        let id = NotificationId::next();
+        let inner = self.p.show_notification(id, attributes)?;
+        Ok(NotificationHandle { id, inner })
+    }
+
+    /// Show a message box to the user, and return a future resolving to the button they pressed.
+    ///
+    /// The dialog doesn't block the event loop: events keep being delivered, and other windows
+    /// keep rendering while it is shown. Closing the dialog without pressing a button reports
+    /// [`MessageButtons::dismiss_button()`].
+    ///
+    /// This is mostly meant for reporting fatal errors, e.g. when no graphics adapter could be
+    /// found, without having to depend on a GUI toolkit.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Uses `NSAlert`, which is shown application modal.
+    /// - **Windows:** Uses a task dialog, or a message box when the application doesn't use version
+    ///   6 of the common controls.
+    /// - **Wayland / X11:** Uses [`zenity`], and returns [`RequestError::NotSupported`] if it isn't
+    ///   installed.
+    /// - **Web:** Shows an overlay on top of the web page, in the style of `window.confirm()`.
+    /// - **Android / iOS / Orbital:** Unsupported.
+    ///
+    /// [`MessageButtons::dismiss_button()`]: crate::dialog::MessageButtons::dismiss_button
+    /// [`zenity`]: https://help.gnome.org/users/zenity/stable/
+    pub fn show_message_dialog(
+        &self,
+        dialog: MessageDialog,
+    ) -> Result<DialogFuture<DialogButton>, RequestError> {
+        let _span =
+            tracing::debug_span!("winit::ActiveEventLoop::show_message_dialog", dialog = ?dialog)
+                .entered();
+
+        let (sender, future) = dialog::channel(dialog.buttons.dismiss_button());
+        self.p.show_message_dialog(dialog, sender).map_err(|error| {
+            RequestError::from_external(error, self.backend(), "message dialogs are unsupported")
+        })?;
+        Ok(future)
+    }
+
     /// Returns the list of all the monitors available on the system.
     #[inline]
     pub fn available_monitors(&self) -> impl Iterator<Item = MonitorHandle> {
@@ -401,6 +762,163 @@ impl ActiveEventLoop {
         self.p.primary_monitor().map(|inner| MonitorHandle { inner })
     }
 
+    /// Returns the monitors suited for the output view of a presentation, like projectors or
+    /// external displays extending the desktop.
+    ///
+    /// These are the monitors other than the [primary monitor][Self::primary_monitor] that
+    /// [aren't mirrored][MonitorHandle::is_mirrored], so a slideshow can show its presenter view
+    /// on the primary monitor and its output view on one of these. Connecting or disconnecting
+    /// them is reported with [`ApplicationHandler::monitors_changed()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS:** External screens, like AirPlay receivers, that don't mirror the main screen.
+    /// - **Wayland:** Includes every monitor, as there is no primary monitor.
+    /// - **Web:** Always returns an empty iterator, as browsers don't expose monitors.
+    ///
+    /// [`ApplicationHandler::monitors_changed()`]: crate::application::ApplicationHandler::monitors_changed
+    #[inline]
+    pub fn presentation_displays(&self) -> impl Iterator<Item = MonitorHandle> {
+        let _span =
+            tracing::debug_span!("winit::ActiveEventLoop::presentation_displays",).entered();
+
+        let primary = self.primary_monitor();
+        self.available_monitors()
+            .filter(move |monitor| Some(monitor) != primary.as_ref() && !monitor.is_mirrored())
+    }
+
+    /// Changes the video mode of a monitor without entering exclusive fullscreen, or restores its
+    /// original mode with `None`.
+    ///
+    /// This is meant for kiosks and similar setups showing a borderless window that covers the
+    /// monitor. The mode must be one of the [`MonitorHandle::video_modes()`] of the monitor.
+    /// Windows are not moved or resized, and the monitor change is reported like any other.
+    ///
+    /// The original modes of all monitors changed this way are restored when the [`EventLoop`]
+    /// is dropped, which also happens when unwinding from a panic.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The system also restores the original mode when the application exits.
+    /// - **Windows:** The change is not stored in the registry, the system also restores the
+    ///   original mode when the process exits.
+    /// - **X11:** The X server keeps the mode if the process is killed or aborts without unwinding.
+    /// - **Android / iOS / Orbital / Wayland / Web:** Always returns
+    ///   [`RequestError::NotSupported`].
+    ///
+    /// [`MonitorHandle::video_modes()`]: crate::monitor::MonitorHandle::video_modes
+    pub fn set_video_mode(
+        &self,
+        monitor: &MonitorHandle,
+        mode: Option<&VideoModeHandle>,
+    ) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!(
+            "winit::ActiveEventLoop::set_video_mode",
+            monitor = ?monitor,
+            mode = ?mode,
+        )
+        .entered();
+
+        let Some(mode) = mode else {
+            video_mode::restore(&monitor.inner);
+            return Ok(());
+        };
+        if mode.monitor() != *monitor {
+            tracing::warn!("the video mode belongs to another monitor");
+            return Err(RequestError::Ignored);
+        }
+
+        let restore = self.p.set_video_mode(&mode.video_mode).map_err(|error| {
+            RequestError::from_external(
+                error,
+                self.backend(),
+                "applications can't change the modes of monitors",
+            )
+        })?;
+        video_mode::changed(monitor.inner.clone(), restore);
+        Ok(())
+    }
+
+    /// Returns the position of the pointer on the desktop, in the coordinate space of
+    /// [`MonitorHandle::position()`].
+    ///
+    /// See [`Window::cursor_position()`] for the position relative to a window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / Orbital / Wayland / Web:** Always returns
+    ///   [`RequestError::NotSupported`], as positions on the desktop aren't exposed.
+    ///
+    /// [`Window::cursor_position()`]: crate::window::Window::cursor_position
+    #[inline]
+    pub fn pointer_position(&self) -> Result<PhysicalPosition<f64>, RequestError> {
+        let _span = tracing::debug_span!("winit::ActiveEventLoop::pointer_position",).entered();
+
+        self.p.pointer_position().map_err(|error| {
+            RequestError::from_external(error, self.backend(), "the pointer position is unknown")
+        })
+    }
+
+    /// Returns the backend the event loop runs on.
+    ///
+    /// See [`EventLoopBuilder::with_backend_preference()`] on how to choose it.
+    #[inline]
+    pub fn backend(&self) -> Backend {
+        #[cfg(any(x11_platform, wayland_platform))]
+        let backend = if self.p.is_wayland() { Backend::Wayland } else { Backend::X11 };
+        #[cfg(not(any(x11_platform, wayland_platform)))]
+        let backend = NATIVE_BACKEND;
+        backend
+    }
+
+    /// Returns the language preferred by the user, e.g. to choose the translation of the
+    /// application.
+    ///
+    /// Changes are reported with [`ApplicationHandler::locale_changed`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Combines the language and the country of the configuration.
+    /// - **iOS / macOS:** Returns the first of `NSLocale.preferredLanguages`.
+    /// - **Wayland / X11:** Reads the `LANGUAGE`, `LC_ALL`, `LC_MESSAGES` and `LANG` environment
+    ///   variables, in that order.
+    /// - **Web:** Returns the first of [`navigator.languages`].
+    /// - **Windows:** Returns the first of the preferred UI languages of the user.
+    /// - **Orbital:** Always returns `None`.
+    ///
+    /// [`navigator.languages`]: https://developer.mozilla.org/en-US/docs/Web/API/Navigator/languages
+    #[inline]
+    pub fn locale(&self) -> Option<LanguageTag> {
+        let _span = tracing::debug_span!("winit::ActiveEventLoop::locale",).entered();
+
+        self.p.locale()
+    }
+
+    /// Returns the seats of the system, see [`Seat`].
+    ///
+    /// Input events can be attributed to a seat with [`DeviceId::seat_id()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Unsupported, always empty.
+    #[inline]
+    pub fn seats(&self) -> impl Iterator<Item = Seat> {
+        let _span = tracing::debug_span!("winit::ActiveEventLoop::seats",).entered();
+
+        self.p.seats().into_iter()
+    }
+
+    /// Returns information about the session the application runs in, e.g. whether it is a
+    /// remote desktop session, and what windows can do in it.
+    ///
+    /// See [`SessionInfo`] for what is detected on each platform.
+    #[inline]
+    pub fn session_info(&self) -> SessionInfo {
+        let _span = tracing::debug_span!("winit::ActiveEventLoop::session_info",).entered();
+
+        self.p.session_info()
+    }
+
     /// Change if or when [`DeviceEvent`]s are captured.
     ///
     /// Since the [`DeviceEvent`] capture can lead to high CPU usage for unfocused windows, winit
@@ -423,8 +941,13 @@ impl ActiveEventLoop {
     }
 
     /// Sets the [`ControlFlow`].
+    ///
+    /// While frame times are reported with [`Window::report_frame_time()`], the deadline of
+    /// [`ControlFlow::WaitUntil`] is moved earlier by the average frame time.
+    ///
+    /// [`Window::report_frame_time()`]: crate::window::Window::report_frame_time
     pub fn set_control_flow(&self, control_flow: ControlFlow) {
-        self.p.set_control_flow(control_flow)
+        self.p.set_control_flow(control_flow.with_frame_budget())
     }
 
     /// Gets the current [`ControlFlow`].
@@ -432,15 +955,164 @@ impl ActiveEventLoop {
         self.p.control_flow()
     }
 
+    /// Sets the accuracy with which [`ControlFlow::WaitUntil`] should wake up.
+    ///
+    /// By default, the event loop relies on the regular system timers, which can fire up to ~15ms
+    /// late on some platforms. Requesting a finer resolution, e.g. 1ms for audio or MIDI driven
+    /// applications, switches to high resolution timers at the cost of higher power usage. A
+    /// resolution of 15ms or more restores the default behavior.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Raises the system timer resolution with `timeBeginPeriod` and waits on a high
+    ///   resolution waitable timer.
+    /// - **macOS:** Opts the process out of timer coalescing and App Nap.
+    /// - **Web:** Runs the timers in a worker, which avoids the clamping and background throttling
+    ///   of timers on the main thread. Falls back to the default timers if the worker can't be
+    ///   created, e.g. when prohibited by the Content Security Policy.
+    /// - **X11 / Wayland:** Timers are already accurate to ~1ms, so this has no effect.
+    /// - **iOS / Android / Orbital:** Unsupported.
+    pub fn set_timer_resolution(&self, resolution: Duration) {
+        self.p.set_timer_resolution(resolution)
+    }
+
+    /// Sets how [`ControlFlow::Poll`] behaves while no window is focused.
+    ///
+    /// Applications rendering continuously usually don't need to do so at full speed while in
+    /// the background. With a policy other than [`UnfocusedPolicy::Normal`], the event loop
+    /// slows down whenever [`ControlFlow::Poll`] is set at the end of an iteration and none of
+    /// the windows received [`WindowEvent::Focused(true)`][WindowEvent::Focused] since losing
+    /// focus. The application keeps observing [`ControlFlow::Poll`] through
+    /// [`control_flow()`][Self::control_flow] at the start of each iteration, and polling resumes
+    /// at full speed as soon as a window gains focus.
+    ///
+    /// Defaults to [`UnfocusedPolicy::Normal`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Browsers already throttle `requestAnimationFrame()` and timers in background
+    ///   tabs, this additionally applies while the page is visible but the canvas isn't focused.
+    pub fn set_unfocused_policy(&self, policy: UnfocusedPolicy) {
+        unfocused::set_policy(policy)
+    }
+
+    /// Statistics about the events dispatched so far, to find out whether and where the
+    /// application is falling behind.
+    ///
+    /// Only available with the `trace` feature, which also instruments the event loop with
+    /// [`tracing`] spans at the `TRACE` level: `winit::dispatch` around each call into the
+    /// [`ApplicationHandler`], `winit::wait` while the event loop sleeps, and spans around the
+    /// system calls waiting for events.
+    ///
+    /// [`tracing`]: https://docs.rs/tracing
+    #[cfg(feature = "trace")]
+    pub fn stats(&self) -> EventLoopStats {
+        stats::current()
+    }
+
     /// This exits the event loop.
     ///
     /// See [`LoopExiting`][crate::event::Event::LoopExiting].
     pub fn exit(&self) {
         let _span = tracing::debug_span!("winit::ActiveEventLoop::exit",).entered();
 
+        exit::set_outcome(None);
+        self.p.exit()
+    }
+
+    /// Exits the event loop with the exit `code` of the application.
+    ///
+    /// [`EventLoop::run_app()`] returns [`EventLoopError::ExitFailure`] with the code unless it
+    /// is `0`, and `PumpStatus::Exit` carries it. Calling [`exit()`] afterwards resets the code
+    /// to `0`.
+    ///
+    /// [`exit()`]: Self::exit
+    pub fn exit_with_code(&self, code: i32) {
+        let _span = tracing::debug_span!("winit::ActiveEventLoop::exit_with_code", code).entered();
+
+        exit::set_outcome(Some(exit::Outcome::Code(code)));
         self.p.exit()
     }
 
+    /// Exits the event loop, and spawns a new process of the application once it exited, e.g.
+    /// to apply settings.
+    ///
+    /// This doesn't use the restart support of the system, the current executable is spawned
+    /// with [`RestartArgs::args()`] when [`EventLoop::run_app()`] returns, which returns
+    /// [`EventLoopError::Restart`] if that failed. The new process inherits the environment and
+    /// working directory, and both processes run at the same time until the current one returns
+    /// from `main`. Calling [`exit()`] afterwards cancels the restart.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Applications in a bundle are spawned with `open -n` instead, so that Launch
+    ///   Services activates them.
+    /// - **Web:** Reloads the page with `location.reload()` once the event loop exited, the
+    ///   arguments are ignored.
+    /// - **iOS / Android:** Always returns [`RequestError::NotSupported`].
+    ///
+    /// [`exit()`]: Self::exit
+    pub fn request_restart(&self, args: RestartArgs) -> Result<(), RequestError> {
+        let _span =
+            tracing::debug_span!("winit::ActiveEventLoop::request_restart", args = ?args).entered();
+
+        if cfg!(any(ios_platform, android_platform)) {
+            return Err(RequestError::NotSupported {
+                platform: self.backend(),
+                reason: "applications can't restart themselves",
+            });
+        }
+        exit::set_outcome(Some(exit::Outcome::Restart(args)));
+        self.p.exit();
+        Ok(())
+    }
+
+    /// Registers the application to be restarted by the system when it crashes or hangs, or
+    /// unregisters it with `None`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `RegisterApplicationRestart()`. The system only restarts applications
+    ///   that ran for at least 60 seconds, and the arguments are limited to 1024 characters.
+    /// - **Android / iOS / macOS / Orbital / Wayland / Web / X11:** Always returns
+    ///   [`RequestError::NotSupported`].
+    pub fn set_restart_after_crash(&self, args: Option<RestartArgs>) -> Result<(), RequestError> {
+        let _span =
+            tracing::debug_span!("winit::ActiveEventLoop::set_restart_after_crash", args = ?args)
+                .entered();
+
+        self.p.set_restart_after_crash(args.as_ref().map(RestartArgs::args)).map_err(|error| {
+            RequestError::from_external(
+                error,
+                self.backend(),
+                "the system doesn't restart crashed applications",
+            )
+        })
+    }
+
+    /// Exits the event loop once the given future completed.
+    ///
+    /// Until then, the event loop keeps running as usual, including redraws, which makes it
+    /// possible to save state or finish network requests while still showing progress. The
+    /// future is polled on the event loop's thread, at the end of each iteration it was woken up
+    /// for. Calling this again replaces the pending future, and calling [`exit()`] drops it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** If the application is asked to terminate, e.g. with `Cmd+Q`, while a future is
+    ///   pending, the termination is delayed until it completes.
+    /// - **Web:** Navigating away from the page while a future is pending asks the user for
+    ///   confirmation through the `beforeunload` event. Browsers only show the prompt if the user
+    ///   has interacted with the page.
+    ///
+    /// [`exit()`]: Self::exit
+    pub fn exit_with(&self, cleanup: impl Future<Output = ()> + 'static) {
+        let _span = tracing::debug_span!("winit::ActiveEventLoop::exit_with",).entered();
+
+        exit::set(Box::pin(cleanup));
+        self.p.set_exit_pending(true);
+    }
+
     /// Returns if the [`EventLoop`] is about to stop.
     ///
     /// See [`exit()`][Self::exit].
@@ -521,12 +1193,13 @@ unsafe impl rwh_05::HasRawDisplayHandle for OwnedDisplayHandle {
 
 /// Used to send custom events to [`EventLoop`].
 pub struct EventLoopProxy<T: 'static> {
-    event_loop_proxy: platform_impl::EventLoopProxy<T>,
+    event_loop_proxy: platform_impl::EventLoopProxy<ProxyMessage<T>>,
+    wake_ups: Weak<WakeUps>,
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
     fn clone(&self) -> Self {
-        Self { event_loop_proxy: self.event_loop_proxy.clone() }
+        Self { event_loop_proxy: self.event_loop_proxy.clone(), wake_ups: self.wake_ups.clone() }
     }
 }
 
@@ -541,7 +1214,487 @@ impl<T: 'static> EventLoopProxy<T> {
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
         let _span = tracing::debug_span!("winit::EventLoopProxy::send_event",).entered();
 
-        self.event_loop_proxy.send_event(event)
+        match self.event_loop_proxy.send_event(ProxyMessage::User(event)) {
+            Err(EventLoopClosed(ProxyMessage::User(event))) => Err(EventLoopClosed(event)),
+            // The platform proxies hand back the message that was sent, so nothing else can be
+            // returned, and there is no event to return.
+            Err(EventLoopClosed(ProxyMessage::WakeUp | ProxyMessage::ActivationRequest { .. }))
+            | Ok(()) => Ok(()),
+        }
+    }
+
+    /// Wake up the [`EventLoop`] from which this proxy was created, to call
+    /// [`ApplicationHandler::proxy_wake_up`] with the given priority.
+    ///
+    /// Unlike [`send_event`], wake ups don't queue up. Calling this any number of times before
+    /// the event loop gets to handle the wake up results in a single call for each priority, and
+    /// the event loop is only signaled once until then. This makes it suitable to notify the
+    /// event loop from a busy background thread, for example to redraw after decoding a frame.
+    ///
+    /// Returns an `Err` if the associated [`EventLoop`] no longer exists.
+    ///
+    /// [`send_event`]: Self::send_event
+    pub fn wake_up_with(&self, priority: Priority) -> Result<(), EventLoopClosed<()>> {
+        let _span =
+            tracing::debug_span!("winit::EventLoopProxy::wake_up_with", ?priority).entered();
+
+        let wake_ups = self.wake_ups.upgrade().ok_or(EventLoopClosed(()))?;
+        if wake_ups.request(priority) {
+            self.event_loop_proxy
+                .send_event(ProxyMessage::WakeUp)
+                .map_err(|_| EventLoopClosed(()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Deliver a request of another instance of the application, see [`SingleInstance`].
+    ///
+    /// [`SingleInstance`]: crate::app::SingleInstance
+    pub(crate) fn send_activation_request(
+        &self,
+        args: Vec<OsString>,
+        activation_token: Option<ActivationToken>,
+        files: Vec<PathBuf>,
+    ) -> Result<(), EventLoopClosed<()>> {
+        self.event_loop_proxy
+            .send_event(ProxyMessage::ActivationRequest { args, activation_token, files })
+            .map_err(|_| EventLoopClosed(()))
+    }
+
+    /// Returns whether the [`EventLoop`] from which this proxy was created still exists.
+    ///
+    /// Once this returns `false`, sending events and waking up the event loop will fail.
+    pub fn is_event_loop_alive(&self) -> bool {
+        self.wake_ups.strong_count() > 0
+    }
+}
+
+/// The priority of a wake up, see [`EventLoopProxy::wake_up_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    /// Delivered once all pending events were handled, right before
+    /// [`ApplicationHandler::about_to_wait`].
+    Low,
+    /// Delivered as soon as the event loop sees the wake up, after [`Priority::High`].
+    Normal,
+    /// Delivered as soon as the event loop sees the wake up, before [`Priority::Normal`].
+    High,
+}
+
+impl Priority {
+    fn flag(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// The message passed through the platform proxies.
+pub(crate) enum ProxyMessage<T> {
+    User(T),
+    WakeUp,
+    ActivationRequest {
+        args: Vec<OsString>,
+        activation_token: Option<ActivationToken>,
+        /// The files among the arguments, resolved off the event loop.
+        files: Vec<PathBuf>,
+    },
+}
+
+/// The wake ups requested through [`EventLoopProxy::wake_up_with`], shared between the proxies
+/// and the event loop.
+#[derive(Debug, Default)]
+pub(crate) struct WakeUps {
+    /// One flag per [`Priority`], plus [`WakeUps::SIGNALED`].
+    pending: AtomicU8,
+}
+
+impl WakeUps {
+    /// Set while a [`ProxyMessage::WakeUp`] is on its way to the event loop.
+    const SIGNALED: u8 = 1 << 7;
+
+    /// Mark a wake up as pending, returning whether the event loop needs to be signaled.
+    fn request(&self, priority: Priority) -> bool {
+        let previous = self.pending.fetch_or(priority.flag() | Self::SIGNALED, Ordering::AcqRel);
+        previous & Self::SIGNALED == 0
+    }
+
+    /// Take the pending wake up with the given priority.
+    fn take(&self, priority: Priority) -> bool {
+        self.pending.fetch_and(!priority.flag(), Ordering::AcqRel) & priority.flag() != 0
+    }
+}
+
+/// Delivers the [`ProxyMessage`]s to the application.
+pub(crate) struct WakeUpHandler<A> {
+    app: A,
+    // Keeps the event loop alive for the proxies while the handler exists, which matters when
+    // the event loop itself is given up to run the handler.
+    wake_ups: Arc<WakeUps>,
+    /// Wakes up the event loop for the future passed to [`ActiveEventLoop::exit_with`].
+    exit_waker: Waker,
+    /// Entered from `about_to_wait` until the next `new_events`.
+    #[cfg(feature = "trace")]
+    wait_span: Option<tracing::span::EnteredSpan>,
+}
+
+impl<A> WakeUpHandler<A> {
+    pub(crate) fn new<T: 'static>(app: A, proxy: EventLoopProxy<T>) -> Self {
+        Self {
+            app,
+            wake_ups: proxy.wake_ups.upgrade().expect("the event loop is alive"),
+            exit_waker: exit::waker(proxy.event_loop_proxy.waker()),
+            #[cfg(feature = "trace")]
+            wait_span: None,
+        }
+    }
+
+    /// Call into the application, instrumented with the `trace` feature.
+    #[inline]
+    fn dispatch(&mut self, event: &'static str, f: impl FnOnce(&mut A)) {
+        #[cfg(feature = "trace")]
+        let span = tracing::trace_span!("winit::dispatch", event, latency = tracing::field::Empty)
+            .entered();
+        #[cfg(feature = "trace")]
+        let start = Instant::now();
+
+        // Setters called by the application are applied once it returns.
+        command_queue::dispatch(|| f(&mut self.app));
+
+        #[cfg(feature = "trace")]
+        {
+            let latency = start.elapsed();
+            span.record("latency", tracing::field::debug(latency));
+            stats::record_dispatch(start, latency);
+        }
+        #[cfg(not(feature = "trace"))]
+        let _ = event;
+    }
+}
+
+impl<A: ApplicationHandler<T>, T: 'static> ApplicationHandler<ProxyMessage<T>>
+    for WakeUpHandler<A>
+{
+    #[inline]
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+        #[cfg(feature = "trace")]
+        self.wait_span.take();
+        long_press::new_events(event_loop);
+        unfocused::new_events(event_loop);
+        self.dispatch("new_events", |app| app.new_events(event_loop, cause));
+        if cause == StartCause::Init {
+            self.dispatch("init", |app| app.init(event_loop));
+        }
+
+        // Applications are started with the URLs and documents they handle as arguments.
+        #[cfg(any(windows_platform, x11_platform, wayland_platform))]
+        if cause == StartCause::Init {
+            let args: Vec<OsString> = env::args_os().skip(1).collect();
+            for url in app::urls(&args) {
+                self.url_opened(event_loop, url);
+            }
+            let files = env::current_dir().map(|dir| app::files(&args, &dir)).unwrap_or_default();
+            if !files.is_empty() {
+                self.files_opened(event_loop, files);
+            }
+        }
+    }
+
+    #[inline]
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("resumed", |app| app.resumed(event_loop));
+    }
+
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: ProxyMessage<T>) {
+        match event {
+            ProxyMessage::User(event) => {
+                self.dispatch("user_event", |app| app.user_event(event_loop, event))
+            },
+            ProxyMessage::WakeUp => {
+                // Clear the signal first, so that a wake up requested from now on signals again.
+                self.wake_ups.pending.fetch_and(!WakeUps::SIGNALED, Ordering::AcqRel);
+                for priority in [Priority::High, Priority::Normal] {
+                    if self.wake_ups.take(priority) {
+                        self.dispatch("proxy_wake_up", |app| {
+                            app.proxy_wake_up(event_loop, priority)
+                        });
+                    }
+                }
+            },
+            ProxyMessage::ActivationRequest { args, activation_token, files } => {
+                let urls = app::urls(&args);
+                self.activation_request(event_loop, args, activation_token);
+                for url in urls {
+                    self.url_opened(event_loop, url);
+                }
+                if !files.is_empty() {
+                    self.files_opened(event_loop, files);
+                }
+            },
+        }
+    }
+
+    #[inline]
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
+        // Emitted before any other event of the window.
+        #[cfg(not(any(android_platform, web_platform)))]
+        {
+            while let Some(window_id) = surface::take_created() {
+                self.surface_created(event_loop, window_id);
+            }
+            if event == WindowEvent::Destroyed {
+                self.dispatch("surface_destroyed", |app| {
+                    app.surface_destroyed(event_loop, window_id)
+                });
+            }
+        }
+        let entered_from = match crossing::window_event(window_id, &event) {
+            crossing::Action::Dispatch => None,
+            crossing::Action::Skip => return,
+            crossing::Action::LeaveFirst(left, device_id) => {
+                self.window_event(event_loop, left, WindowEvent::CursorLeft { device_id });
+                return self.window_event(event_loop, window_id, event);
+            },
+            crossing::Action::Entered(from) => Some(WindowEvent::CursorEnteredFrom(from)),
+        };
+        unfocused::window_event(window_id, &event);
+        pending_window::window_event(window_id, &event);
+        let Some((window_id, event)) = filter::window_event(event_loop, window_id, event) else {
+            return;
+        };
+        if event == WindowEvent::RedrawRequested && redraw_thread::request(window_id) {
+            return;
+        }
+        let resizing = redraw_thread::window_event(window_id, &event);
+        let click = click::window_event(event_loop, window_id, &event);
+        let pan_ended = fling::window_event(window_id, &event);
+        let secondary_click = long_press::window_event(window_id, &event);
+        self.dispatch("window_event", |app| app.window_event(event_loop, window_id, event));
+        drop(resizing);
+        if let Some(event) = click.or(entered_from).or(pan_ended) {
+            self.window_event(event_loop, window_id, event);
+        }
+        for event in secondary_click {
+            self.window_event(event_loop, window_id, event);
+        }
+    }
+
+    #[inline]
+    fn device_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        let Some((device_id, event)) = filter::device_event(event_loop, device_id, event) else {
+            return;
+        };
+        self.dispatch("device_event", |app| app.device_event(event_loop, device_id, event));
+    }
+
+    #[inline]
+    fn surface_created(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        self.dispatch("surface_created", |app| app.surface_created(event_loop, window_id));
+    }
+
+    #[inline]
+    fn surface_destroyed(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        self.dispatch("surface_destroyed", |app| app.surface_destroyed(event_loop, window_id));
+    }
+
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        #[cfg(not(any(android_platform, web_platform)))]
+        while let Some(window_id) = surface::take_created() {
+            self.surface_created(event_loop, window_id);
+        }
+        exit::poll(event_loop, &self.exit_waker);
+        if self.wake_ups.take(Priority::Low) {
+            self.dispatch("proxy_wake_up", |app| app.proxy_wake_up(event_loop, Priority::Low));
+        }
+        for (window_id, event) in long_press::take_detected() {
+            self.window_event(event_loop, window_id, event);
+        }
+        self.dispatch("about_to_wait", |app| app.about_to_wait(event_loop));
+        unfocused::about_to_wait(event_loop);
+        long_press::about_to_wait(event_loop);
+        crossing::about_to_wait();
+
+        // The Web backend reports the idle periods of the browser instead.
+        #[cfg(not(web_platform))]
+        if let Some(deadline) = idle_deadline(event_loop) {
+            self.dispatch("idle", |app| app.idle(event_loop, deadline));
+        }
+
+        #[cfg(feature = "trace")]
+        {
+            self.wait_span = Some(tracing::trace_span!("winit::wait").entered());
+        }
+    }
+
+    #[inline]
+    fn idle(&mut self, event_loop: &ActiveEventLoop, deadline: Instant) {
+        self.dispatch("idle", |app| app.idle(event_loop, deadline));
+    }
+
+    #[inline]
+    fn close_requested_by_os(&mut self, event_loop: &ActiveEventLoop, respond: CloseResponse) {
+        self.dispatch("close_requested_by_os", |app| {
+            app.close_requested_by_os(event_loop, respond)
+        });
+    }
+
+    #[inline]
+    fn notification_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        id: NotificationId,
+        event: NotificationEvent,
+    ) {
+        self.dispatch("notification_event", |app| app.notification_event(event_loop, id, event));
+    }
+
+    #[inline]
+    fn suspended(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("suspended", |app| app.suspended(event_loop));
+    }
+
+    #[inline]
+    fn terminating(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("terminating", |app| app.terminating(event_loop));
+    }
+
+    #[inline]
+    fn exiting(&mut self, event_loop: &ActiveEventLoop) {
+        if exit::is_pending() {
+            exit::clear();
+            event_loop.p.set_exit_pending(false);
+        }
+        self.dispatch("exiting", |app| app.exiting(event_loop));
+        // The page never returns from running the event loop.
+        #[cfg(web_platform)]
+        if exit::take_restart().is_some() {
+            event_loop.p.reload();
+        }
+    }
+
+    #[inline]
+    fn memory_warning(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("memory_warning", |app| app.memory_warning(event_loop));
+    }
+
+    #[inline]
+    fn session_locked(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("session_locked", |app| app.session_locked(event_loop));
+    }
+
+    #[inline]
+    fn session_unlocked(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("session_unlocked", |app| app.session_unlocked(event_loop));
+    }
+
+    #[inline]
+    fn display_sleep(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("display_sleep", |app| app.display_sleep(event_loop));
+    }
+
+    #[inline]
+    fn display_wake(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("display_wake", |app| app.display_wake(event_loop));
+    }
+
+    #[inline]
+    fn locale_changed(&mut self, event_loop: &ActiveEventLoop, locale: LanguageTag) {
+        self.dispatch("locale_changed", |app| app.locale_changed(event_loop, locale));
+    }
+
+    #[inline]
+    fn work_area_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("work_area_changed", |app| app.work_area_changed(event_loop));
+    }
+
+    #[inline]
+    fn navigation(&mut self, event_loop: &ActiveEventLoop, pop_state: PopState) {
+        self.dispatch("navigation", |app| app.navigation(event_loop, pop_state));
+    }
+
+    #[inline]
+    fn color_profile_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("color_profile_changed", |app| app.color_profile_changed(event_loop));
+    }
+
+    #[inline]
+    fn activation_request(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        args: Vec<OsString>,
+        activation_token: Option<ActivationToken>,
+    ) {
+        self.dispatch("activation_request", |app| {
+            app.activation_request(event_loop, args, activation_token)
+        });
+    }
+
+    #[inline]
+    fn url_opened(&mut self, event_loop: &ActiveEventLoop, url: Url) {
+        self.dispatch("url_opened", |app| app.url_opened(event_loop, url));
+    }
+
+    #[inline]
+    fn files_opened(&mut self, event_loop: &ActiveEventLoop, paths: Vec<PathBuf>) {
+        self.dispatch("files_opened", |app| app.files_opened(event_loop, paths));
+    }
+
+    #[inline]
+    fn drag_hover(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        position: PhysicalPosition<f64>,
+    ) -> DropOperation {
+        let mut operation = DropOperation::default();
+        self.dispatch("drag_hover", |app| {
+            operation = app.drag_hover(event_loop, window_id, position)
+        });
+        operation
+    }
+
+    #[inline]
+    fn tray_activated(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        self.dispatch("tray_activated", |app| app.tray_activated(event_loop, window_id));
+    }
+
+    #[inline]
+    fn signal(&mut self, event_loop: &ActiveEventLoop, signal: Signal) {
+        self.dispatch("signal", |app| app.signal(event_loop, signal));
+    }
+
+    #[inline]
+    fn fd_ready(&mut self, event_loop: &ActiveEventLoop, token: u64) {
+        self.dispatch("fd_ready", |app| app.fd_ready(event_loop, token));
+    }
+
+    #[inline]
+    fn handle_signaled(&mut self, event_loop: &ActiveEventLoop, token: u64) {
+        self.dispatch("handle_signaled", |app| app.handle_signaled(event_loop, token));
+    }
+
+    #[inline]
+    fn monitors_changed(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("monitors_changed", |app| app.monitors_changed(event_loop));
+    }
+
+    #[inline]
+    fn audio_interrupted(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("audio_interrupted", |app| app.audio_interrupted(event_loop));
+    }
+
+    #[inline]
+    fn audio_resumed(&mut self, event_loop: &ActiveEventLoop) {
+        self.dispatch("audio_resumed", |app| app.audio_resumed(event_loop));
     }
 }
 
@@ -551,6 +1704,27 @@ impl<T: 'static> fmt::Debug for EventLoopProxy<T> {
     }
 }
 
+/// The longest idle period reported to [`ApplicationHandler::idle`], so that work done in it can't
+/// delay input handling for long. Matches the maximum idle period of `requestIdleCallback()`.
+#[cfg(not(web_platform))]
+const MAX_IDLE_PERIOD: Duration = Duration::from_millis(50);
+
+/// The deadline of the idle period the event loop is about to enter, if it is going to sleep.
+#[cfg(not(web_platform))]
+fn idle_deadline(event_loop: &ActiveEventLoop) -> Option<Instant> {
+    if event_loop.exiting() {
+        return None;
+    }
+
+    let now = Instant::now();
+    let max_deadline = now.checked_add(MAX_IDLE_PERIOD)?;
+    match event_loop.control_flow() {
+        ControlFlow::Poll => None,
+        ControlFlow::Wait => Some(max_deadline),
+        ControlFlow::WaitUntil(deadline) => (deadline > now).then(|| deadline.min(max_deadline)),
+    }
+}
+
 /// The error that is returned when an [`EventLoopProxy`] attempts to wake up an [`EventLoop`] that
 /// no longer exists.
 ///
@@ -566,6 +1740,148 @@ impl<T> fmt::Display for EventLoopClosed<T> {
 
 impl<T: fmt::Debug> error::Error for EventLoopClosed<T> {}
 
+/// A windowing system winit can run on, see [`EventLoopBuilder::with_backend_preference()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// Android.
+    Android,
+    /// AppKit on macOS.
+    AppKit,
+    /// Orbital on Redox OS.
+    Orbital,
+    /// UIKit on iOS.
+    UiKit,
+    /// Wayland, requires the `wayland` feature.
+    Wayland,
+    /// The browser.
+    Web,
+    /// Win32 on Windows.
+    Windows,
+    /// X11, requires the `x11` feature.
+    X11,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Backend::Android => "Android",
+            Backend::AppKit => "AppKit",
+            Backend::Orbital => "Orbital",
+            Backend::UiKit => "UIKit",
+            Backend::Wayland => "Wayland",
+            Backend::Web => "Web",
+            Backend::Windows => "Windows",
+            Backend::X11 => "X11",
+        })
+    }
+}
+
+/// The only backend of platforms which have a single one.
+#[cfg(android_platform)]
+pub(crate) const NATIVE_BACKEND: Backend = Backend::Android;
+#[cfg(macos_platform)]
+pub(crate) const NATIVE_BACKEND: Backend = Backend::AppKit;
+#[cfg(orbital_platform)]
+pub(crate) const NATIVE_BACKEND: Backend = Backend::Orbital;
+#[cfg(ios_platform)]
+pub(crate) const NATIVE_BACKEND: Backend = Backend::UiKit;
+#[cfg(web_platform)]
+pub(crate) const NATIVE_BACKEND: Backend = Backend::Web;
+#[cfg(windows_platform)]
+pub(crate) const NATIVE_BACKEND: Backend = Backend::Windows;
+
+/// How the scale factor is determined, see [`EventLoopBuilder::with_dpi_policy()`].
+#[derive(Debug, Clone, Copy, Default)]
+pub enum DpiPolicy {
+    /// Use the scale factor of the system.
+    #[default]
+    System,
+    /// Use the given scale factor, regardless of the system.
+    Force(f64),
+    /// Work in physical pixels, i.e. always use a scale factor of `1.0`.
+    Physical,
+    /// Pass the scale factor of the system through a function, e.g. [`f64::round`] or
+    /// [`f64::ceil`] for integer scale factors.
+    Round(fn(f64) -> f64),
+}
+
+impl DpiPolicy {
+    /// Applies the policy to the given scale factor of the system.
+    ///
+    /// Results that aren't a valid scale factor, like a factor rounded down to `0.0`, fall back to
+    /// the scale factor of the system.
+    pub fn apply(self, scale_factor: f64) -> f64 {
+        let applied = match self {
+            DpiPolicy::System => scale_factor,
+            DpiPolicy::Force(scale_factor) => scale_factor,
+            DpiPolicy::Physical => 1.0,
+            DpiPolicy::Round(round) => round(scale_factor),
+        };
+
+        if crate::dpi::validate_scale_factor(applied) {
+            applied
+        } else {
+            scale_factor
+        }
+    }
+
+    /// The policy of the event loop.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn current() -> Self {
+        *DPI_POLICY.lock().unwrap()
+    }
+}
+
+/// How to restart the application, see [`ActiveEventLoop::request_restart()`] and
+/// [`ActiveEventLoop::set_restart_after_crash()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestartArgs {
+    args: Option<Vec<OsString>>,
+}
+
+impl RestartArgs {
+    /// Restarts the application with the arguments it was started with.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Passes `args` to the new instance instead of the arguments of the current one.
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<OsString>,
+    {
+        self.args = Some(args.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Returns the arguments passed to the new instance, without the executable.
+    pub fn args(&self) -> Vec<OsString> {
+        self.args.clone().unwrap_or_else(|| env::args_os().skip(1).collect())
+    }
+
+    /// Spawns a new process of the application.
+    fn spawn(&self) -> io::Result<()> {
+        let exe = env::current_exe()?;
+        // Bundled applications are launched through Launch Services like the Finder does, so they
+        // are activated as usual.
+        #[cfg(macos_platform)]
+        if let Some(bundle) = exe
+            .ancestors()
+            .find(|path| path.extension().is_some_and(|extension| extension == "app"))
+        {
+            return Command::new("/usr/bin/open")
+                .arg("-n")
+                .arg(bundle)
+                .arg("--args")
+                .args(self.args())
+                .spawn()
+                .map(drop);
+        }
+        Command::new(exe).args(self.args()).spawn().map(drop)
+    }
+}
+
 /// Control when device events are captured.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 pub enum DeviceEvents {
@@ -0,0 +1,182 @@
+//! Counting clicks for [`WindowEvent::MouseClick`].
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use super::{ActiveEventLoop, Duration, Instant};
+use crate::dpi::PhysicalPosition;
+use crate::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+use crate::window::WindowId;
+
+/// Used where the system doesn't tell the double click interval.
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+/// Used where the system doesn't tell how far the cursor may move between clicks, in pixels.
+const DEFAULT_DISTANCE: f64 = 4.0;
+
+struct Click {
+    window_id: WindowId,
+    button: MouseButton,
+    time: Instant,
+    /// Where the first click of the sequence happened.
+    position: PhysicalPosition<f64>,
+    count: u32,
+}
+
+#[derive(Default)]
+struct State {
+    cursor_positions: HashMap<WindowId, PhysicalPosition<f64>>,
+    last_click: Option<Click>,
+}
+
+thread_local! {
+    static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+}
+
+impl State {
+    /// Count a press of `button` at `position`, which continues the sequence of the last click if
+    /// it's in the same window, with the same button, within `interval` and `distance`.
+    ///
+    /// The count reported by the system, if any, takes precedence.
+    #[allow(clippy::too_many_arguments)]
+    fn press(
+        &mut self,
+        window_id: WindowId,
+        button: MouseButton,
+        position: PhysicalPosition<f64>,
+        time: Instant,
+        interval: Duration,
+        distance: f64,
+        reported: Option<u32>,
+    ) -> u32 {
+        let continues = self.last_click.as_ref().is_some_and(|last| {
+            last.window_id == window_id
+                && last.button == button
+                && time.duration_since(last.time) <= interval
+                && (position.x - last.position.x).abs() <= distance
+                && (position.y - last.position.y).abs() <= distance
+        });
+
+        let last = match &mut self.last_click {
+            Some(last) if continues => {
+                last.time = time;
+                last.count += 1;
+                last
+            },
+            last_click => last_click.insert(Click { window_id, button, time, position, count: 1 }),
+        };
+        if let Some(count) = reported {
+            last.count = count;
+        }
+        last.count
+    }
+}
+
+fn with_state<R>(f: impl FnOnce(&mut State) -> R) -> R {
+    STATE.with(|state| f(state.borrow_mut().get_or_insert_with(State::default)))
+}
+
+/// Keep track of the cursor, and return the [`WindowEvent::MouseClick`] to emit after a press.
+pub(super) fn window_event(
+    event_loop: &ActiveEventLoop,
+    window_id: WindowId,
+    event: &WindowEvent,
+) -> Option<WindowEvent> {
+    match *event {
+        WindowEvent::CursorMoved { position, .. } => {
+            with_state(|state| state.cursor_positions.insert(window_id, position));
+            None
+        },
+        WindowEvent::Destroyed => {
+            with_state(|state| state.cursor_positions.remove(&window_id));
+            None
+        },
+        WindowEvent::MouseInput { device_id, state: ElementState::Pressed, button } => {
+            Some(click(event_loop, window_id, device_id, button))
+        },
+        _ => None,
+    }
+}
+
+fn click(
+    event_loop: &ActiveEventLoop,
+    window_id: WindowId,
+    device_id: DeviceId,
+    button: MouseButton,
+) -> WindowEvent {
+    let (interval, distance) = event_loop
+        .p
+        .double_click_settings()
+        .map_or((DEFAULT_INTERVAL, DEFAULT_DISTANCE), |(interval, distance)| {
+            (interval, distance as f64)
+        });
+    #[cfg(web_platform)]
+    let reported = event_loop.p.take_click_count();
+    #[cfg(not(web_platform))]
+    let reported = None;
+    let now = Instant::now();
+
+    with_state(|state| {
+        let position = state.cursor_positions.get(&window_id).copied().unwrap_or_default();
+        let click_count =
+            state.press(window_id, button, position, now, interval, distance, reported);
+        WindowEvent::MouseClick { device_id, button, click_count, position }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(500);
+
+    fn press(
+        state: &mut State,
+        window_id: u64,
+        button: MouseButton,
+        x: f64,
+        time: Instant,
+        reported: Option<u32>,
+    ) -> u32 {
+        let position = PhysicalPosition::new(x, 0.0);
+        state.press(WindowId::from(window_id), button, position, time, INTERVAL, 4.0, reported)
+    }
+
+    #[test]
+    fn consecutive_clicks() {
+        let mut state = State::default();
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+
+        assert_eq!(press(&mut state, 1, MouseButton::Left, 0.0, start, None), 1);
+        assert_eq!(press(&mut state, 1, MouseButton::Left, 3.0, start + ms(400), None), 2);
+        // The interval counts from the previous click.
+        assert_eq!(press(&mut state, 1, MouseButton::Left, 4.0, start + ms(800), None), 3);
+        assert_eq!(press(&mut state, 1, MouseButton::Left, 4.0, start + ms(1400), None), 1);
+    }
+
+    #[test]
+    fn interrupted_clicks() {
+        let mut state = State::default();
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+
+        assert_eq!(press(&mut state, 1, MouseButton::Left, 0.0, start, None), 1);
+        // The distance counts from the first click.
+        assert_eq!(press(&mut state, 1, MouseButton::Left, 3.0, start + ms(100), None), 2);
+        assert_eq!(press(&mut state, 1, MouseButton::Left, 6.0, start + ms(200), None), 1);
+        assert_eq!(press(&mut state, 1, MouseButton::Right, 6.0, start + ms(300), None), 1);
+        assert_eq!(press(&mut state, 2, MouseButton::Right, 6.0, start + ms(400), None), 1);
+        assert_eq!(press(&mut state, 2, MouseButton::Right, 6.0, start + ms(500), None), 2);
+    }
+
+    #[test]
+    fn reported_count() {
+        let mut state = State::default();
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+
+        assert_eq!(press(&mut state, 1, MouseButton::Left, 0.0, start, Some(2)), 2);
+        assert_eq!(press(&mut state, 1, MouseButton::Left, 0.0, start + ms(100), None), 3);
+        assert_eq!(press(&mut state, 1, MouseButton::Left, 0.0, start + ms(200), Some(1)), 1);
+    }
+}
@@ -0,0 +1,156 @@
+//! Pairing [`WindowEvent::CursorEntered`] and [`WindowEvent::CursorLeft`] across windows, and
+//! emitting [`WindowEvent::CursorEnteredFrom`].
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+use crate::event::{DeviceId, WindowEvent};
+use crate::window::WindowId;
+
+#[derive(Default)]
+struct Pointer {
+    /// The window the cursor is in.
+    hovered: Option<WindowId>,
+    /// The window the cursor left last.
+    left: Option<WindowId>,
+    /// The windows that were left and not entered since, whose further leave events are
+    /// duplicates.
+    outside: HashSet<WindowId>,
+    /// Whether the cursor left [`Self::left`] during the current iteration of the event loop, so
+    /// that entering another window now means moving between the two.
+    left_recently: bool,
+}
+
+thread_local! {
+    static STATE: RefCell<Option<HashMap<DeviceId, Pointer>>> = const { RefCell::new(None) };
+}
+
+fn with_state<R>(f: impl FnOnce(&mut HashMap<DeviceId, Pointer>) -> R) -> R {
+    STATE.with(|state| f(state.borrow_mut().get_or_insert_with(HashMap::new)))
+}
+
+/// What to do with a window event.
+pub(super) enum Action {
+    /// Dispatch the event.
+    Dispatch,
+    /// Drop the event, because it is a duplicate or the cursor already is in another window.
+    Skip,
+    /// The cursor is still in another window, which has to be left first.
+    LeaveFirst(WindowId, DeviceId),
+    /// Dispatch the event, followed by [`WindowEvent::CursorEnteredFrom`].
+    Entered(Option<WindowId>),
+}
+
+/// Keep track of the window the cursor is in.
+pub(super) fn window_event(window_id: WindowId, event: &WindowEvent) -> Action {
+    match *event {
+        WindowEvent::CursorEntered { device_id } => with_state(|state| {
+            let pointer = state.entry(device_id).or_default();
+            match pointer.hovered {
+                Some(hovered) if hovered == window_id => return Action::Skip,
+                Some(hovered) => return Action::LeaveFirst(hovered, device_id),
+                None => (),
+            }
+
+            pointer.hovered = Some(window_id);
+            pointer.outside.remove(&window_id);
+            let from = pointer.left.filter(|&left| pointer.left_recently && left != window_id);
+            pointer.left_recently = false;
+            Action::Entered(from)
+        }),
+        WindowEvent::CursorLeft { device_id } => with_state(|state| {
+            let pointer = state.entry(device_id).or_default();
+            match pointer.hovered {
+                Some(hovered) if hovered == window_id => pointer.hovered = None,
+                Some(_) => return Action::Skip,
+                None if pointer.outside.contains(&window_id) => return Action::Skip,
+                // The window was never entered, e.g. because the cursor was in it on startup.
+                None => (),
+            }
+
+            pointer.left = Some(window_id);
+            pointer.left_recently = true;
+            pointer.outside.insert(window_id);
+            Action::Dispatch
+        }),
+        WindowEvent::Destroyed => {
+            with_state(|state| {
+                for pointer in state.values_mut() {
+                    if pointer.hovered == Some(window_id) {
+                        pointer.hovered = None;
+                    }
+                    if pointer.left == Some(window_id) {
+                        pointer.left = None;
+                    }
+                    pointer.outside.remove(&window_id);
+                }
+            });
+            Action::Dispatch
+        },
+        _ => Action::Dispatch,
+    }
+}
+
+/// The end of an iteration of the event loop, after which entering a window no longer counts as
+/// coming from the window left before.
+pub(super) fn about_to_wait() {
+    with_state(|state| {
+        for pointer in state.values_mut() {
+            pointer.left_recently = false;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entered(window_id: u64) -> Action {
+        let device_id = unsafe { DeviceId::dummy() };
+        window_event(WindowId::from(window_id), &WindowEvent::CursorEntered { device_id })
+    }
+
+    fn left(window_id: u64) -> Action {
+        let device_id = unsafe { DeviceId::dummy() };
+        window_event(WindowId::from(window_id), &WindowEvent::CursorLeft { device_id })
+    }
+
+    #[test]
+    fn duplicates_are_skipped() {
+        assert!(matches!(entered(1), Action::Entered(None)));
+        assert!(matches!(entered(1), Action::Skip));
+        assert!(matches!(left(1), Action::Dispatch));
+        assert!(matches!(left(1), Action::Skip));
+        // The cursor was never in the window.
+        assert!(matches!(left(2), Action::Dispatch));
+    }
+
+    #[test]
+    fn enter_before_leave() {
+        assert!(matches!(entered(1), Action::Entered(None)));
+        let Action::LeaveFirst(window_id, _) = entered(2) else {
+            panic!("the cursor has to leave the first window first");
+        };
+        assert_eq!(window_id, WindowId::from(1));
+        // The leave event that follows the synthesized one.
+        assert!(matches!(left(1), Action::Dispatch));
+        assert!(matches!(left(1), Action::Skip));
+    }
+
+    #[test]
+    fn entered_from() {
+        assert!(matches!(entered(1), Action::Entered(None)));
+        assert!(matches!(left(1), Action::Dispatch));
+        assert!(matches!(entered(2), Action::Entered(Some(id)) if id == WindowId::from(1)));
+
+        // Only within the same iteration of the event loop.
+        assert!(matches!(left(2), Action::Dispatch));
+        about_to_wait();
+        assert!(matches!(entered(1), Action::Entered(None)));
+
+        // Not from a destroyed window.
+        assert!(matches!(left(1), Action::Dispatch));
+        window_event(WindowId::from(1), &WindowEvent::Destroyed);
+        assert!(matches!(entered(2), Action::Entered(None)));
+    }
+}
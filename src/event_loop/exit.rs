@@ -0,0 +1,241 @@
+//! Deferred exits, see [`ActiveEventLoop::exit_with()`], and the outcome of exits, see
+//! [`ActiveEventLoop::exit_with_code()`] and [`ActiveEventLoop::request_restart()`].
+
+use std::cell::RefCell;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+use super::{ActiveEventLoop, RestartArgs};
+use crate::error::EventLoopError;
+#[cfg(any(
+    windows_platform,
+    macos_platform,
+    android_platform,
+    x11_platform,
+    wayland_platform
+))]
+use crate::platform::pump_events::PumpStatus;
+use crate::platform_impl;
+
+type ExitFuture = Pin<Box<dyn Future<Output = ()>>>;
+
+/// What happens once the event loop exited.
+pub(super) enum Outcome {
+    Code(i32),
+    Restart(RestartArgs),
+}
+
+thread_local! {
+    static PENDING_EXIT: RefCell<Option<ExitFuture>> = const { RefCell::new(None) };
+    static OUTCOME: RefCell<Option<Outcome>> = const { RefCell::new(None) };
+}
+
+/// Set what happens once the event loop exited, `None` meaning a normal exit.
+pub(super) fn set_outcome(outcome: Option<Outcome>) {
+    OUTCOME.with(|cell| *cell.borrow_mut() = outcome);
+}
+
+/// Take the restart requested with [`ActiveEventLoop::request_restart()`], if any.
+#[cfg(web_platform)]
+pub(super) fn take_restart() -> Option<RestartArgs> {
+    OUTCOME.with(|cell| {
+        let mut outcome = cell.borrow_mut();
+        match outcome.take() {
+            Some(Outcome::Restart(args)) => Some(args),
+            other => {
+                *outcome = other;
+                None
+            },
+        }
+    })
+}
+
+/// Apply the outcome of the exit to the result of running the event loop.
+pub(crate) fn finish_run(result: Result<(), EventLoopError>) -> Result<(), EventLoopError> {
+    match OUTCOME.with(|cell| cell.borrow_mut().take()) {
+        Some(Outcome::Code(code)) if code != 0 && result.is_ok() => {
+            Err(EventLoopError::ExitFailure(code))
+        },
+        Some(Outcome::Restart(args)) if result.is_ok() => {
+            args.spawn().map_err(EventLoopError::Restart)
+        },
+        _ => result,
+    }
+}
+
+/// Apply the outcome of the exit to the status of pumping the event loop.
+#[cfg(any(windows_platform, macos_platform, android_platform, x11_platform, wayland_platform))]
+pub(crate) fn finish_pump(status: PumpStatus) -> PumpStatus {
+    let PumpStatus::Exit(code) = status else {
+        return status;
+    };
+    match OUTCOME.with(|cell| cell.borrow_mut().take()) {
+        Some(Outcome::Code(requested)) if code == 0 => PumpStatus::Exit(requested),
+        Some(Outcome::Restart(args)) if code == 0 => {
+            if let Err(error) = args.spawn() {
+                tracing::error!("failed to restart the application: {error}");
+                return PumpStatus::Exit(1);
+            }
+            PumpStatus::Exit(0)
+        },
+        _ => PumpStatus::Exit(code),
+    }
+}
+
+/// Replace the pending exit.
+pub(super) fn set(future: ExitFuture) {
+    PENDING_EXIT.with(|exit| *exit.borrow_mut() = Some(future));
+}
+
+/// Whether the event loop is waiting on an exit future.
+pub(super) fn is_pending() -> bool {
+    PENDING_EXIT.with(|exit| exit.borrow().is_some())
+}
+
+/// Drop the pending exit, if any, e.g. when the event loop exits early.
+pub(super) fn clear() {
+    // Dropping the future can run arbitrary code, so it must not happen while borrowed.
+    let future = PENDING_EXIT.with(|exit| exit.borrow_mut().take());
+    drop(future);
+}
+
+/// Poll the pending exit, and exit the event loop once it completed.
+pub(super) fn poll(event_loop: &ActiveEventLoop, waker: &Waker) {
+    if poll_pending(waker) {
+        event_loop.p.set_exit_pending(false);
+        event_loop.exit();
+    }
+}
+
+/// Poll the pending exit, and return whether it completed.
+fn poll_pending(waker: &Waker) -> bool {
+    let Some(mut future) = PENDING_EXIT.with(|exit| exit.borrow_mut().take()) else {
+        return false;
+    };
+
+    match future.as_mut().poll(&mut Context::from_waker(waker)) {
+        Poll::Ready(()) => true,
+        Poll::Pending => {
+            PENDING_EXIT.with(|exit| {
+                // Keep the future unless it was replaced while polling.
+                exit.borrow_mut().get_or_insert(future);
+            });
+            false
+        },
+    }
+}
+
+/// Create the waker passed to exit futures, which wakes up the event loop to poll them again.
+pub(super) fn waker(waker: platform_impl::EventLoopWaker) -> Waker {
+    Waker::from(Arc::new(ExitWaker(waker)))
+}
+
+struct ExitWaker(platform_impl::EventLoopWaker);
+
+impl Wake for ExitWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        // Waking up only makes the event loop run another iteration, in which the pending exit is
+        // polled.
+        self.0.wake();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::future;
+    use std::rc::Rc;
+
+    use super::*;
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    #[test]
+    fn poll_exit() {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        assert!(!poll_pending(&waker));
+
+        // Completes on the second poll.
+        let polls = Rc::new(Cell::new(0));
+        set(Box::pin({
+            let polls = polls.clone();
+            future::poll_fn(move |_| {
+                polls.set(polls.get() + 1);
+                if polls.get() < 2 {
+                    Poll::Pending
+                } else {
+                    Poll::Ready(())
+                }
+            })
+        }));
+        assert!(is_pending());
+        assert!(!poll_pending(&waker));
+        assert!(is_pending());
+        assert!(poll_pending(&waker));
+        assert!(!is_pending());
+        assert_eq!(polls.get(), 2);
+
+        set(Box::pin(future::pending()));
+        clear();
+        assert!(!poll_pending(&waker));
+    }
+
+    #[test]
+    fn replaced_while_polling() {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        set(Box::pin(future::poll_fn(|_| {
+            set(Box::pin(future::ready(())));
+            Poll::Pending
+        })));
+        assert!(!poll_pending(&waker));
+        assert!(poll_pending(&waker));
+    }
+
+    #[test]
+    fn run_outcome() {
+        assert!(finish_run(Ok(())).is_ok());
+
+        set_outcome(Some(Outcome::Code(0)));
+        assert!(finish_run(Ok(())).is_ok());
+
+        set_outcome(Some(Outcome::Code(3)));
+        assert!(matches!(finish_run(Ok(())), Err(EventLoopError::ExitFailure(3))));
+        // The outcome is cleared once it was applied.
+        assert!(finish_run(Ok(())).is_ok());
+
+        // An error of the event loop takes precedence, and still clears the outcome.
+        set_outcome(Some(Outcome::Code(3)));
+        assert!(matches!(
+            finish_run(Err(EventLoopError::ExitFailure(1))),
+            Err(EventLoopError::ExitFailure(1))
+        ));
+        assert!(finish_run(Ok(())).is_ok());
+    }
+
+    #[test]
+    #[cfg(any(windows_platform, macos_platform, android_platform, x11_platform, wayland_platform))]
+    fn pump_outcome() {
+        assert!(matches!(finish_pump(PumpStatus::Exit(0)), PumpStatus::Exit(0)));
+
+        // The outcome is only applied once the event loop exits.
+        set_outcome(Some(Outcome::Code(5)));
+        assert!(matches!(finish_pump(PumpStatus::Continue), PumpStatus::Continue));
+        assert!(matches!(finish_pump(PumpStatus::Exit(0)), PumpStatus::Exit(5)));
+        assert!(matches!(finish_pump(PumpStatus::Exit(0)), PumpStatus::Exit(0)));
+
+        // A failure of the event loop takes precedence.
+        set_outcome(Some(Outcome::Code(5)));
+        assert!(matches!(finish_pump(PumpStatus::Exit(2)), PumpStatus::Exit(2)));
+        assert!(matches!(finish_pump(PumpStatus::Exit(0)), PumpStatus::Exit(0)));
+    }
+}
@@ -0,0 +1,131 @@
+//! Filtering events before they reach the application, see [`EventLoop::set_event_filter()`].
+//!
+//! [`EventLoop::set_event_filter()`]: super::EventLoop::set_event_filter
+
+use std::cell::RefCell;
+
+use super::ActiveEventLoop;
+use crate::event::{DeviceEvent, DeviceId, WindowEvent};
+use crate::window::WindowId;
+
+/// Sees events before they are dispatched to the [`ApplicationHandler`], and may consume, modify
+/// or re-route them.
+///
+/// This allows building layers on top of an application, e.g. a global shortcut handler, input
+/// recording or a debug overlay, without the application having to forward events to them.
+///
+/// Each method receives the event and returns the event to dispatch in its place, or [`None`] to
+/// consume it. By default, every event is passed through unchanged.
+///
+/// See [`EventLoop::set_event_filter()`].
+///
+/// [`ApplicationHandler`]: crate::application::ApplicationHandler
+/// [`EventLoop::set_event_filter()`]: super::EventLoop::set_event_filter
+pub trait EventFilter: 'static {
+    /// Filter an event sent to a window.
+    ///
+    /// Returning a different [`WindowId`] re-routes the event to another window.
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) -> Option<(WindowId, WindowEvent)> {
+        let _ = event_loop;
+        Some((window_id, event))
+    }
+
+    /// Filter an event sent to a device.
+    fn device_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        device_id: DeviceId,
+        event: DeviceEvent,
+    ) -> Option<(DeviceId, DeviceEvent)> {
+        let _ = event_loop;
+        Some((device_id, event))
+    }
+}
+
+thread_local! {
+    static FILTER: RefCell<Option<Box<dyn EventFilter>>> = const { RefCell::new(None) };
+}
+
+/// Replace the filter.
+pub(super) fn set(filter: Option<Box<dyn EventFilter>>) {
+    FILTER.with(|current| *current.borrow_mut() = filter);
+}
+
+/// Pass a window event through the filter.
+pub(super) fn window_event(
+    event_loop: &ActiveEventLoop,
+    window_id: WindowId,
+    event: WindowEvent,
+) -> Option<(WindowId, WindowEvent)> {
+    apply((window_id, event), |filter, (window_id, event)| {
+        filter.window_event(event_loop, window_id, event)
+    })
+}
+
+/// Pass a device event through the filter.
+pub(super) fn device_event(
+    event_loop: &ActiveEventLoop,
+    device_id: DeviceId,
+    event: DeviceEvent,
+) -> Option<(DeviceId, DeviceEvent)> {
+    apply((device_id, event), |filter, (device_id, event)| {
+        filter.device_event(event_loop, device_id, event)
+    })
+}
+
+/// Pass an event through the filter if there is one.
+fn apply<E>(event: E, f: impl FnOnce(&mut dyn EventFilter, E) -> Option<E>) -> Option<E> {
+    FILTER.with(|filter| match filter.borrow_mut().as_mut() {
+        Some(filter) => f(filter.as_mut(), event),
+        None => Some(event),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    use super::*;
+
+    /// Records when it is dropped.
+    struct Filter(Rc<Cell<bool>>);
+
+    impl EventFilter for Filter {}
+
+    impl Drop for Filter {
+        fn drop(&mut self) {
+            self.0.set(true);
+        }
+    }
+
+    #[test]
+    fn pass_through_without_filter() {
+        assert_eq!(apply(1, |_, _| unreachable!("there is no filter")), Some(1));
+    }
+
+    #[test]
+    fn replace_filter() {
+        let calls = Cell::new(0);
+        let dropped = Rc::new(Cell::new(false));
+        set(Some(Box::new(Filter(dropped.clone()))));
+        let consume = |_: &mut dyn EventFilter, _| {
+            calls.set(calls.get() + 1);
+            None::<u32>
+        };
+        assert_eq!(apply(1, consume), None);
+        assert_eq!(apply(2, |_, event| Some(event + 1)), Some(3));
+        assert_eq!(calls.get(), 1);
+
+        // The previous filter is dropped.
+        assert!(!dropped.get());
+        set(None);
+        assert!(dropped.get());
+        assert_eq!(apply(1, |_, _| unreachable!("the filter was removed")), Some(1));
+    }
+}
@@ -0,0 +1,68 @@
+//! Estimating the velocity of fingers for [`WindowEvent::PanEnded`].
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use super::Instant;
+use crate::event::{DeviceId, TouchPhase, WindowEvent};
+use crate::velocity::VelocityTracker;
+use crate::window::WindowId;
+
+struct Finger {
+    start: Instant,
+    tracker: VelocityTracker,
+    moved: bool,
+}
+
+thread_local! {
+    static FINGERS: RefCell<HashMap<(WindowId, DeviceId, u64), Finger>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Keep track of fingers, and return the [`WindowEvent::PanEnded`] to emit when one that moved
+/// is lifted.
+pub(super) fn window_event(window_id: WindowId, event: &WindowEvent) -> Option<WindowEvent> {
+    let touch = match event {
+        WindowEvent::Touch(touch) => touch,
+        WindowEvent::Destroyed => {
+            FINGERS.with(|fingers| {
+                fingers
+                    .borrow_mut()
+                    .retain(|(finger_window_id, ..), _| *finger_window_id != window_id)
+            });
+            return None;
+        },
+        _ => return None,
+    };
+
+    let now = Instant::now();
+    FINGERS.with(|fingers| {
+        let mut fingers = fingers.borrow_mut();
+        let key = (window_id, touch.device_id, touch.id);
+        if touch.phase == TouchPhase::Started {
+            fingers.insert(key, Finger {
+                start: now,
+                tracker: VelocityTracker::new(),
+                moved: false,
+            });
+        }
+        let finger = fingers.get_mut(&key)?;
+        finger.tracker.add(now.duration_since(finger.start).as_secs_f64(), touch.location);
+        finger.moved |= touch.phase == TouchPhase::Moved;
+
+        match touch.phase {
+            TouchPhase::Started | TouchPhase::Moved => None,
+            TouchPhase::Ended => {
+                let finger = fingers.remove(&key)?;
+                finger.moved.then(|| WindowEvent::PanEnded {
+                    device_id: touch.device_id,
+                    velocity: finger.tracker.velocity(),
+                })
+            },
+            TouchPhase::Cancelled => {
+                fingers.remove(&key);
+                None
+            },
+        }
+    })
+}
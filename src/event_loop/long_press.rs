@@ -0,0 +1,242 @@
+//! Detecting long presses for [`WindowEvent::LongPress`], see [`Window::set_long_press()`].
+//!
+//! [`Window::set_long_press()`]: crate::window::Window::set_long_press
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::{ActiveEventLoop, ControlFlow, Instant};
+use crate::dpi::PhysicalPosition;
+use crate::event::{DeviceId, ElementState, MouseButton, TouchPhase, WindowEvent};
+use crate::window::{LongPressGesture, WindowId};
+
+struct Press {
+    window_id: WindowId,
+    device_id: DeviceId,
+    id: u64,
+    start: PhysicalPosition<f64>,
+    deadline: Instant,
+    gesture: LongPressGesture,
+    /// Whether [`WindowEvent::LongPress`] was emitted.
+    detected: bool,
+}
+
+#[derive(Default)]
+struct State {
+    presses: Vec<Press>,
+    /// The control flow of the application, and what it was replaced with at the end of the last
+    /// iteration to wake up for the next press.
+    replaced: Option<(ControlFlow, ControlFlow)>,
+}
+
+// Windows can be used from any thread, so unlike the presses this can't be thread local.
+static GESTURES: Mutex<Option<HashMap<WindowId, LongPressGesture>>> = Mutex::new(None);
+
+thread_local! {
+    static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+}
+
+fn with_state<R>(f: impl FnOnce(&mut State) -> R) -> R {
+    STATE.with(|state| f(state.borrow_mut().get_or_insert_with(State::default)))
+}
+
+fn gesture(window_id: WindowId) -> Option<LongPressGesture> {
+    GESTURES.lock().unwrap().as_ref()?.get(&window_id).copied()
+}
+
+/// Replace the long press detection of a window.
+pub(crate) fn set(window_id: WindowId, gesture: Option<LongPressGesture>) {
+    let mut gestures = GESTURES.lock().unwrap();
+    let gestures = gestures.get_or_insert_with(HashMap::new);
+    match gesture {
+        Some(gesture) => gestures.insert(window_id, gesture),
+        None => gestures.remove(&window_id),
+    };
+}
+
+/// Hand the control flow back to the application if it was replaced.
+pub(super) fn new_events(event_loop: &ActiveEventLoop) {
+    let replaced = with_state(|state| state.replaced.take());
+
+    // Unless the application changed it in the meantime.
+    if let Some((control_flow, replacement)) = replaced {
+        if event_loop.control_flow() == replacement {
+            event_loop.set_control_flow(control_flow);
+        }
+    }
+}
+
+/// Keep track of touches, and return the secondary click to emit when a long press ended.
+pub(super) fn window_event(window_id: WindowId, event: &WindowEvent) -> Vec<WindowEvent> {
+    let touch = match event {
+        WindowEvent::Touch(touch) => touch,
+        WindowEvent::Destroyed => {
+            set(window_id, None);
+            with_state(|state| state.presses.retain(|press| press.window_id != window_id));
+            return Vec::new();
+        },
+        _ => return Vec::new(),
+    };
+
+    if touch.phase == TouchPhase::Started {
+        if let Some(gesture) = gesture(window_id) {
+            with_state(|state| {
+                state.presses.push(Press {
+                    window_id,
+                    device_id: touch.device_id,
+                    id: touch.id,
+                    start: touch.location,
+                    deadline: Instant::now() + gesture.duration,
+                    gesture,
+                    detected: false,
+                })
+            });
+        }
+        return Vec::new();
+    }
+
+    with_state(|state| {
+        let Some(index) = state.presses.iter().position(|press| {
+            press.window_id == window_id
+                && press.device_id == touch.device_id
+                && press.id == touch.id
+        }) else {
+            return Vec::new();
+        };
+
+        let press = &state.presses[index];
+        let distance = (touch.location.x - press.start.x).hypot(touch.location.y - press.start.y);
+        match touch.phase {
+            TouchPhase::Moved if distance <= press.gesture.slop => Vec::new(),
+            TouchPhase::Ended if press.detected && press.gesture.secondary_click => {
+                let device_id = state.presses.swap_remove(index).device_id;
+                [ElementState::Pressed, ElementState::Released]
+                    .into_iter()
+                    .map(|state| WindowEvent::MouseInput {
+                        device_id,
+                        state,
+                        button: MouseButton::Right,
+                    })
+                    .collect()
+            },
+            _ => {
+                state.presses.swap_remove(index);
+                Vec::new()
+            },
+        }
+    })
+}
+
+/// Return the long presses that were detected since the last iteration.
+pub(super) fn take_detected() -> Vec<(WindowId, WindowEvent)> {
+    let now = Instant::now();
+    with_state(|state| {
+        state
+            .presses
+            .iter_mut()
+            .filter(|press| !press.detected && press.deadline <= now)
+            .map(|press| {
+                press.detected = true;
+                (press.window_id, WindowEvent::LongPress {
+                    device_id: press.device_id,
+                    id: press.id,
+                    position: press.start,
+                })
+            })
+            .collect()
+    })
+}
+
+/// Make sure the event loop wakes up in time to detect the next long press.
+pub(super) fn about_to_wait(event_loop: &ActiveEventLoop) {
+    let Some(deadline) = with_state(|state| {
+        state.presses.iter().filter(|press| !press.detected).map(|press| press.deadline).min()
+    }) else {
+        return;
+    };
+
+    let control_flow = event_loop.control_flow();
+    let wakes_up_in_time = match control_flow {
+        ControlFlow::Poll => true,
+        ControlFlow::Wait => false,
+        ControlFlow::WaitUntil(instant) => instant <= deadline,
+    };
+    if !wakes_up_in_time {
+        let replacement = ControlFlow::WaitUntil(deadline);
+        with_state(|state| state.replaced = Some((control_flow, replacement)));
+        event_loop.set_control_flow(replacement);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::event::Touch;
+
+    fn touch(window_id: WindowId, phase: TouchPhase, x: f64) -> Vec<WindowEvent> {
+        let device_id = unsafe { DeviceId::dummy() };
+        let location = PhysicalPosition::new(x, 0.0);
+        let touch = Touch { device_id, phase, location, force: None, id: 0 };
+        window_event(window_id, &WindowEvent::Touch(touch))
+    }
+
+    fn detect(window_id: WindowId, gesture: LongPressGesture) {
+        set(window_id, Some(gesture));
+        touch(window_id, TouchPhase::Started, 0.0);
+        let detected = take_detected();
+        assert_eq!(detected.len(), 1);
+        assert!(matches!(detected[0], (id, WindowEvent::LongPress { .. }) if id == window_id));
+        assert!(take_detected().is_empty());
+    }
+
+    #[test]
+    fn secondary_click() {
+        // The gestures are shared between the threads of the tests.
+        let window_id = WindowId::from(1);
+        let gesture =
+            LongPressGesture { duration: Duration::ZERO, slop: 10.0, secondary_click: true };
+
+        detect(window_id, gesture);
+        let events = touch(window_id, TouchPhase::Ended, 0.0);
+        assert!(matches!(events[..], [
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Right,
+                ..
+            },
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Right,
+                ..
+            },
+        ]));
+
+        detect(window_id, LongPressGesture { secondary_click: false, ..gesture });
+        assert!(touch(window_id, TouchPhase::Ended, 0.0).is_empty());
+    }
+
+    #[test]
+    fn cancelled_press() {
+        let window_id = WindowId::from(2);
+        let gesture = LongPressGesture {
+            duration: Duration::from_secs(3600),
+            slop: 4.0,
+            ..Default::default()
+        };
+        set(window_id, Some(gesture));
+
+        touch(window_id, TouchPhase::Started, 0.0);
+        touch(window_id, TouchPhase::Moved, 3.0);
+        assert_eq!(with_state(|state| state.presses.len()), 1);
+        touch(window_id, TouchPhase::Moved, 5.0);
+        assert_eq!(with_state(|state| state.presses.len()), 0);
+
+        // Without a gesture, presses aren't tracked.
+        set(window_id, None);
+        touch(window_id, TouchPhase::Started, 0.0);
+        assert_eq!(with_state(|state| state.presses.len()), 0);
+    }
+}
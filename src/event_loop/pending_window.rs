@@ -0,0 +1,172 @@
+//! Windows waiting to be shown, see [`ActiveEventLoop::create_window_async()`].
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use super::ActiveEventLoop;
+use crate::error::OsError;
+use crate::event::WindowEvent;
+use crate::window::{Window, WindowAttributes, WindowId};
+
+#[derive(Default)]
+struct Shared {
+    ready: bool,
+    waker: Option<Waker>,
+}
+
+thread_local! {
+    static PENDING: RefCell<HashMap<WindowId, Arc<Mutex<Shared>>>> = RefCell::new(HashMap::new());
+}
+
+/// Resolves to the window once it received its first size.
+pub(super) struct WindowFuture {
+    result: Option<Result<Window, OsError>>,
+    /// The window that is waited for.
+    pending: Option<(WindowId, Arc<Mutex<Shared>>)>,
+}
+
+/// Create the window and wait for it to be shown.
+pub(super) fn create(event_loop: &ActiveEventLoop, attributes: WindowAttributes) -> WindowFuture {
+    let window = match event_loop.create_window(attributes) {
+        Ok(window) => window,
+        Err(error) => return WindowFuture { result: Some(Err(error)), pending: None },
+    };
+
+    let pending = Some((window.id(), register(window.id())));
+    WindowFuture { result: Some(Ok(window)), pending }
+}
+
+fn register(window_id: WindowId) -> Arc<Mutex<Shared>> {
+    let shared = Arc::new(Mutex::new(Shared::default()));
+    PENDING.with(|pending| pending.borrow_mut().insert(window_id, shared.clone()));
+    shared
+}
+
+/// Resolve the future of the window once it received its first size.
+pub(super) fn window_event(window_id: WindowId, event: &WindowEvent) {
+    match event {
+        // Every backend either reports the first size or redraws the window once it is shown.
+        WindowEvent::Resized { .. } | WindowEvent::RedrawRequested => {
+            let Some(shared) = PENDING.with(|pending| pending.borrow_mut().remove(&window_id))
+            else {
+                return;
+            };
+
+            let mut shared = shared.lock().unwrap();
+            shared.ready = true;
+            if let Some(waker) = shared.waker.take() {
+                waker.wake();
+            }
+        },
+        // The future was dropped before the window was shown.
+        WindowEvent::Destroyed => {
+            PENDING.with(|pending| pending.borrow_mut().remove(&window_id));
+        },
+        _ => (),
+    }
+}
+
+impl Future for WindowFuture {
+    type Output = Result<Window, OsError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if let Some((_, shared)) = &self.pending {
+            let mut shared = shared.lock().unwrap();
+            if !shared.ready {
+                shared.waker = Some(cx.waker().clone());
+                return Poll::Pending;
+            }
+        }
+
+        Poll::Ready(self.result.take().expect("`WindowFuture` polled after completion"))
+    }
+}
+
+impl Drop for WindowFuture {
+    fn drop(&mut self) {
+        // The window is dropped with the future if it wasn't shown yet, so stop waiting for it.
+        if let Some((window_id, _)) = &self.pending {
+            let _ = PENDING.try_with(|pending| pending.borrow_mut().remove(window_id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::task::Wake;
+
+    use super::*;
+    use crate::dpi::PhysicalSize;
+
+    #[derive(Default)]
+    struct FlagWaker(AtomicBool);
+
+    impl Wake for FlagWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn is_pending(window_id: WindowId) -> bool {
+        PENDING.with(|pending| pending.borrow().contains_key(&window_id))
+    }
+
+    fn wait_for(window_id: WindowId) -> WindowFuture {
+        WindowFuture { result: None, pending: Some((window_id, register(window_id))) }
+    }
+
+    fn is_ready(future: &WindowFuture) -> bool {
+        future.pending.as_ref().unwrap().1.lock().unwrap().ready
+    }
+
+    #[test]
+    fn resolves_on_first_size_or_redraw() {
+        let events = [
+            WindowEvent::Resized { size: PhysicalSize::new(800, 600), frame: None },
+            WindowEvent::RedrawRequested,
+        ];
+        for (id, event) in events.into_iter().enumerate() {
+            let window_id = WindowId::from(id as u64);
+            let mut future = wait_for(window_id);
+            let flag = Arc::new(FlagWaker::default());
+            let waker = Waker::from(flag.clone());
+            let poll = Pin::new(&mut future).poll(&mut Context::from_waker(&waker));
+            assert!(poll.is_pending());
+
+            window_event(window_id, &event);
+            assert!(is_ready(&future));
+            assert!(flag.0.load(Ordering::Relaxed));
+            assert!(!is_pending(window_id));
+        }
+    }
+
+    #[test]
+    fn ignores_other_events() {
+        let window_id = WindowId::from(0);
+        let future = wait_for(window_id);
+        window_event(window_id, &WindowEvent::Focused(true));
+        window_event(WindowId::from(1), &WindowEvent::RedrawRequested);
+        assert!(!is_ready(&future));
+        assert!(is_pending(window_id));
+    }
+
+    #[test]
+    fn cleared_with_window() {
+        // The window is destroyed.
+        let window_id = WindowId::from(0);
+        let future = wait_for(window_id);
+        window_event(window_id, &WindowEvent::Destroyed);
+        assert!(!is_ready(&future));
+        assert!(!is_pending(window_id));
+
+        // The window is dropped with the future.
+        let window_id = WindowId::from(1);
+        drop(wait_for(window_id));
+        assert!(!is_pending(window_id));
+    }
+}
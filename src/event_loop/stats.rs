@@ -0,0 +1,135 @@
+//! Statistics about the dispatching of events, collected with the `trace` feature.
+
+use std::cell::RefCell;
+
+#[cfg(not(web_platform))]
+use std::time::{Duration, Instant};
+#[cfg(web_platform)]
+use web_time::{Duration, Instant};
+
+/// How long the event rate is measured over.
+const RATE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Statistics about how the event loop dispatches events to the application.
+///
+/// See [`ActiveEventLoop::stats()`][super::ActiveEventLoop::stats].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EventLoopStats {
+    /// The number of events dispatched to the application since the event loop was created.
+    pub events: u64,
+    /// The rate of dispatched events, measured over the last period of at least a second when the
+    /// statistics are read. Events are counted in periods that start with the first event after
+    /// the previous period ended.
+    pub events_per_second: f64,
+    /// The longest time the application took to handle a single event.
+    pub max_dispatch_latency: Duration,
+}
+
+#[derive(Default)]
+struct Collector {
+    events: u64,
+    max_dispatch_latency: Duration,
+    /// When the first event of the current period was dispatched.
+    period_start: Option<Instant>,
+    period_events: u64,
+    /// The rate of the previous period, while the current one is shorter than [`RATE_PERIOD`].
+    previous_rate: f64,
+}
+
+thread_local! {
+    static COLLECTOR: RefCell<Collector> = RefCell::new(Collector::default());
+}
+
+pub(crate) fn reset() {
+    COLLECTOR.with(|collector| *collector.borrow_mut() = Collector::default());
+}
+
+pub(crate) fn current() -> EventLoopStats {
+    current_at(Instant::now())
+}
+
+fn current_at(now: Instant) -> EventLoopStats {
+    COLLECTOR.with(|collector| {
+        let collector = collector.borrow();
+        let period = match collector.period_start {
+            Some(period_start) => now.saturating_duration_since(period_start),
+            None => Duration::ZERO,
+        };
+        let events_per_second = if period >= RATE_PERIOD {
+            collector.period_events as f64 / period.as_secs_f64()
+        } else {
+            collector.previous_rate
+        };
+        EventLoopStats {
+            events: collector.events,
+            events_per_second,
+            max_dispatch_latency: collector.max_dispatch_latency,
+        }
+    })
+}
+
+/// Record an event dispatched at `start`, which took `latency` to handle.
+pub(crate) fn record_dispatch(start: Instant, latency: Duration) {
+    COLLECTOR.with(|collector| {
+        let collector = &mut *collector.borrow_mut();
+        collector.events += 1;
+        collector.max_dispatch_latency = collector.max_dispatch_latency.max(latency);
+
+        let period_start = *collector.period_start.get_or_insert(start);
+        let period = start.saturating_duration_since(period_start);
+        if period >= RATE_PERIOD {
+            collector.previous_rate = collector.period_events as f64 / period.as_secs_f64();
+            collector.period_start = Some(start);
+            collector.period_events = 0;
+        }
+        collector.period_events += 1;
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MS: Duration = Duration::from_millis(1);
+
+    #[test]
+    fn counts_events() {
+        let start = Instant::now();
+        assert_eq!(current_at(start), EventLoopStats::default());
+
+        record_dispatch(start, 3 * MS);
+        record_dispatch(start + MS, 5 * MS);
+        record_dispatch(start + 2 * MS, MS);
+        let stats = current_at(start + 10 * MS);
+        assert_eq!(stats.events, 3);
+        assert_eq!(stats.max_dispatch_latency, 5 * MS);
+
+        reset();
+        assert_eq!(current_at(start + 10 * MS), EventLoopStats::default());
+    }
+
+    #[test]
+    fn rate_when_read() {
+        let start = Instant::now();
+        for i in 0..10 {
+            record_dispatch(start + i * 50 * MS, MS);
+        }
+        // The first period isn't over yet.
+        assert_eq!(current_at(start + 500 * MS).events_per_second, 0.0);
+        assert_eq!(current_at(start + 1000 * MS).events_per_second, 10.0);
+        // The rate drops while no events are dispatched.
+        assert_eq!(current_at(start + 4000 * MS).events_per_second, 2.5);
+    }
+
+    #[test]
+    fn rate_of_previous_period() {
+        let start = Instant::now();
+        for i in 0..20 {
+            record_dispatch(start + i * 50 * MS, MS);
+        }
+        // Starts the next period.
+        record_dispatch(start + 1000 * MS, MS);
+        assert_eq!(current_at(start + 1500 * MS).events_per_second, 20.0);
+        assert_eq!(current_at(start + 3000 * MS).events_per_second, 0.5);
+    }
+}
@@ -0,0 +1,23 @@
+//! Surface events on platforms where the surface lives as long as the window, see
+//! [`ApplicationHandler::surface_created()`].
+//!
+//! [`ApplicationHandler::surface_created()`]: crate::application::ApplicationHandler::surface_created
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+use crate::window::WindowId;
+
+thread_local! {
+    static CREATED: RefCell<VecDeque<WindowId>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// Queue `surface_created` for a new window.
+pub(super) fn created(window_id: WindowId) {
+    CREATED.with(|created| created.borrow_mut().push_back(window_id));
+}
+
+/// Take the next window for which `surface_created` wasn't emitted yet.
+pub(super) fn take_created() -> Option<WindowId> {
+    CREATED.with(|created| created.borrow_mut().pop_front())
+}
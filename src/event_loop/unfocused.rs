@@ -0,0 +1,133 @@
+//! Throttling while no window is focused, see [`ActiveEventLoop::set_unfocused_policy()`].
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use super::{ActiveEventLoop, ControlFlow, Duration, Instant, UnfocusedPolicy};
+use crate::event::WindowEvent;
+use crate::window::WindowId;
+
+#[derive(Default)]
+struct State {
+    policy: UnfocusedPolicy,
+    focused: HashSet<WindowId>,
+    /// When the current iteration of the event loop started.
+    iteration_start: Option<Instant>,
+    /// What [`ControlFlow::Poll`] was replaced with at the end of the last iteration.
+    throttled: Option<ControlFlow>,
+}
+
+impl State {
+    /// What to replace [`ControlFlow::Poll`] with according to the policy, if no window is
+    /// focused.
+    fn throttle(&mut self) -> Option<ControlFlow> {
+        if !self.focused.is_empty() {
+            return None;
+        }
+
+        let control_flow = match self.policy {
+            UnfocusedPolicy::Normal => return None,
+            UnfocusedPolicy::Pause | UnfocusedPolicy::ThrottleTo(0) => ControlFlow::Wait,
+            UnfocusedPolicy::ThrottleTo(hz) => {
+                let start = self.iteration_start.unwrap_or_else(Instant::now);
+                match start.checked_add(Duration::from_secs(1) / hz) {
+                    Some(instant) => ControlFlow::WaitUntil(instant),
+                    None => ControlFlow::Wait,
+                }
+            },
+        };
+        self.throttled = Some(control_flow);
+
+        Some(control_flow)
+    }
+}
+
+thread_local! {
+    static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+}
+
+fn with_state<R>(f: impl FnOnce(&mut State) -> R) -> R {
+    STATE.with(|state| f(state.borrow_mut().get_or_insert_with(State::default)))
+}
+
+/// Replace the policy.
+pub(super) fn set_policy(policy: UnfocusedPolicy) {
+    with_state(|state| state.policy = policy);
+}
+
+/// Start a new iteration, handing [`ControlFlow::Poll`] back to the application if it was
+/// replaced.
+pub(super) fn new_events(event_loop: &ActiveEventLoop) {
+    let throttled = with_state(|state| {
+        state.iteration_start = Some(Instant::now());
+        state.throttled.take()
+    });
+
+    // Unless the application changed it in the meantime.
+    if throttled == Some(event_loop.control_flow()) {
+        event_loop.set_control_flow(ControlFlow::Poll);
+    }
+}
+
+/// Keep track of which windows are focused.
+pub(super) fn window_event(window_id: WindowId, event: &WindowEvent) {
+    match event {
+        WindowEvent::Focused(true) => with_state(|state| {
+            state.focused.insert(window_id);
+        }),
+        WindowEvent::Focused(false) | WindowEvent::Destroyed => with_state(|state| {
+            state.focused.remove(&window_id);
+        }),
+        _ => (),
+    }
+}
+
+/// Replace [`ControlFlow::Poll`] according to the policy if no window is focused.
+pub(super) fn about_to_wait(event_loop: &ActiveEventLoop) {
+    if event_loop.control_flow() != ControlFlow::Poll {
+        return;
+    }
+
+    if let Some(control_flow) = with_state(State::throttle) {
+        event_loop.set_control_flow(control_flow);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn focused(window_id: u64, focused: bool) {
+        window_event(WindowId::from(window_id), &WindowEvent::Focused(focused));
+    }
+
+    #[test]
+    fn throttled_without_focus() {
+        assert_eq!(with_state(State::throttle), None);
+        set_policy(UnfocusedPolicy::Pause);
+        assert_eq!(with_state(State::throttle), Some(ControlFlow::Wait));
+
+        focused(1, true);
+        focused(2, true);
+        assert_eq!(with_state(State::throttle), None);
+        focused(1, false);
+        assert_eq!(with_state(State::throttle), None);
+        window_event(WindowId::from(2), &WindowEvent::Destroyed);
+        assert_eq!(with_state(State::throttle), Some(ControlFlow::Wait));
+        assert_eq!(with_state(|state| state.throttled), Some(ControlFlow::Wait));
+    }
+
+    #[test]
+    fn throttle_rate() {
+        let start = Instant::now();
+        with_state(|state| state.iteration_start = Some(start));
+
+        set_policy(UnfocusedPolicy::ThrottleTo(10));
+        let deadline = start + Duration::from_millis(100);
+        assert_eq!(with_state(State::throttle), Some(ControlFlow::WaitUntil(deadline)));
+        set_policy(UnfocusedPolicy::ThrottleTo(0));
+        assert_eq!(with_state(State::throttle), Some(ControlFlow::Wait));
+        set_policy(UnfocusedPolicy::Normal);
+        assert_eq!(with_state(State::throttle), None);
+    }
+}
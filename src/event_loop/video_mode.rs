@@ -0,0 +1,53 @@
+//! Video modes changed outside of fullscreen, see [`ActiveEventLoop::set_video_mode()`].
+//!
+//! [`ActiveEventLoop::set_video_mode()`]: super::ActiveEventLoop::set_video_mode
+
+use std::cell::RefCell;
+
+use crate::platform_impl;
+
+/// Switches a monitor back to the mode it had before it was changed.
+type Restore = Box<dyn FnOnce()>;
+
+thread_local! {
+    static CHANGED: RefCell<Vec<(platform_impl::MonitorHandle, Restore)>> =
+        const { RefCell::new(Vec::new()) };
+}
+
+/// Remember how to restore a monitor whose mode was changed, unless it was changed before, in
+/// which case the original mode is restored.
+pub(super) fn changed(monitor: platform_impl::MonitorHandle, restore: Restore) {
+    CHANGED.with(|changed| {
+        let mut changed = changed.borrow_mut();
+        if !changed.iter().any(|(changed, _)| *changed == monitor) {
+            changed.push((monitor, restore));
+        }
+    });
+}
+
+/// Restore the original mode of a monitor, if it was changed.
+pub(super) fn restore(monitor: &platform_impl::MonitorHandle) {
+    // Restoring can deliver events, so it must not happen while borrowed.
+    let restore = CHANGED.with(|changed| {
+        let mut changed = changed.borrow_mut();
+        let index = changed.iter().position(|(changed, _)| changed == monitor)?;
+        Some(changed.remove(index).1)
+    });
+    if let Some(restore) = restore {
+        restore();
+    }
+}
+
+/// Restores the original modes of all changed monitors when dropped, which happens with the
+/// [`EventLoop`](super::EventLoop), also when unwinding from a panic.
+#[derive(Default)]
+pub(crate) struct Guard(());
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        let changed = CHANGED.with(|changed| changed.take());
+        for (_, restore) in changed {
+            restore();
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Frame times reported by the application, see [`Window::report_frame_time()`].
+//!
+//! The event loop uses them to leave the application time to draw: [`ControlFlow::WaitUntil`]
+//! wakes up earlier by the average frame time, and on Web, polling yields to drawing while frames
+//! miss their budget.
+//!
+//! [`Window::report_frame_time()`]: crate::window::Window::report_frame_time
+//! [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+#[cfg(not(web_platform))]
+use std::time::{Duration, Instant};
+
+#[cfg(web_platform)]
+use web_time::{Duration, Instant};
+
+use crate::window::WindowId;
+
+/// The weight of a new frame in the average frame time.
+const SMOOTHING: f64 = 0.1;
+/// How long windows that stopped reporting frames are still taken into account.
+const STALE_AFTER: Duration = Duration::from_secs(1);
+/// How often the budget is updated from the refresh rate of the monitor.
+const BUDGET_INTERVAL: Duration = Duration::from_secs(1);
+/// The budget when the refresh rate of the monitor is unknown, of a 60Hz monitor.
+const DEFAULT_BUDGET: Duration = Duration::from_nanos(16_666_667);
+
+/// Statistics of the frame times reported with [`Window::report_frame_time()`], see
+/// [`Window::frame_statistics()`].
+///
+/// [`Window::report_frame_time()`]: crate::window::Window::report_frame_time
+/// [`Window::frame_statistics()`]: crate::window::Window::frame_statistics
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FrameStatistics {
+    /// The time of the last frame.
+    pub last_frame_time: Duration,
+    /// The exponentially smoothed time of the recent frames.
+    pub average_frame_time: Duration,
+    /// The time a frame can take, one refresh interval of the monitor of the window.
+    pub budget: Duration,
+    /// The number of frames reported.
+    pub frames: u64,
+    /// The number of frames that took longer than the budget, and thus missed a refresh.
+    pub janky_frames: u64,
+}
+
+struct Frames {
+    statistics: FrameStatistics,
+    reported: Instant,
+    budget_updated: Instant,
+}
+
+impl Frames {
+    fn is_stale(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.reported) > STALE_AFTER
+    }
+}
+
+// Windows can be used from any thread, so this can't be thread local.
+static FRAMES: Mutex<Option<HashMap<WindowId, Frames>>> = Mutex::new(None);
+
+/// Add the time of a frame of a window, with `budget` queried when it's out of date.
+pub(crate) fn report(
+    window_id: WindowId,
+    frame_time: Duration,
+    budget: impl FnOnce() -> Option<Duration>,
+) {
+    let now = Instant::now();
+    let stale_budget = FRAMES
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|frames| frames.get(&window_id))
+        .map_or(true, |frames| {
+            now.saturating_duration_since(frames.budget_updated) > BUDGET_INTERVAL
+        });
+    // Querying the monitor may wait on the main thread, so it's done without holding the lock.
+    let budget = stale_budget.then(|| budget().unwrap_or(DEFAULT_BUDGET));
+
+    let mut frames = FRAMES.lock().unwrap();
+    let frames = frames.get_or_insert_with(HashMap::new);
+    let frames = frames.entry(window_id).or_insert_with(|| Frames {
+        statistics: FrameStatistics { average_frame_time: frame_time, ..Default::default() },
+        reported: now,
+        budget_updated: now,
+    });
+    if let Some(budget) = budget {
+        frames.statistics.budget = budget;
+        frames.budget_updated = now;
+    }
+
+    let statistics = &mut frames.statistics;
+    let average = statistics.average_frame_time.as_secs_f64();
+    statistics.average_frame_time =
+        Duration::from_secs_f64(average + SMOOTHING * (frame_time.as_secs_f64() - average));
+    statistics.last_frame_time = frame_time;
+    statistics.frames += 1;
+    if frame_time > statistics.budget {
+        statistics.janky_frames += 1;
+    }
+    frames.reported = now;
+}
+
+pub(crate) fn statistics(window_id: WindowId) -> FrameStatistics {
+    let frames = FRAMES.lock().unwrap();
+    frames
+        .as_ref()
+        .and_then(|frames| frames.get(&window_id))
+        .map_or_else(FrameStatistics::default, |frames| frames.statistics)
+}
+
+pub(crate) fn remove(window_id: WindowId) {
+    if let Some(frames) = FRAMES.lock().unwrap().as_mut() {
+        frames.remove(&window_id);
+    }
+}
+
+/// The longest average frame time of the windows that are drawing.
+pub(crate) fn frame_time() -> Option<Duration> {
+    let now = Instant::now();
+    let frames = FRAMES.lock().unwrap();
+    frames
+        .as_ref()?
+        .values()
+        .filter(|frames| !frames.is_stale(now))
+        .map(|frames| frames.statistics.average_frame_time)
+        .max()
+}
+
+/// Whether the average frame time of a window that is drawing exceeds its budget.
+#[allow(dead_code)] // Only used on Web
+pub(crate) fn over_budget() -> bool {
+    let now = Instant::now();
+    let frames = FRAMES.lock().unwrap();
+    frames.as_ref().is_some_and(|frames| {
+        frames.values().any(|frames| {
+            !frames.is_stale(now) && frames.statistics.average_frame_time > frames.statistics.budget
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics_of_reports() {
+        let window_id = WindowId::from(u64::MAX - 3);
+        let budget = Duration::from_millis(10);
+
+        report(window_id, Duration::from_millis(5), || Some(budget));
+        report(window_id, Duration::from_millis(25), || unreachable!());
+        let statistics = statistics(window_id);
+        assert_eq!(statistics.last_frame_time, Duration::from_millis(25));
+        assert_eq!(statistics.budget, budget);
+        assert_eq!(statistics.frames, 2);
+        assert_eq!(statistics.janky_frames, 1);
+        assert!(statistics.average_frame_time > Duration::from_millis(5));
+        assert!(statistics.average_frame_time < Duration::from_millis(10));
+
+        remove(window_id);
+        assert_eq!(super::statistics(window_id), FrameStatistics::default());
+    }
+}
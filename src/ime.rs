@@ -0,0 +1,161 @@
+//! The text around the cursor, see [`Window::set_ime_surrounding_text()`].
+//!
+//! [`Window::set_ime_surrounding_text()`]: crate::window::Window::set_ime_surrounding_text
+
+// Not every platform supports surrounding text, which can come up as dead code warnings.
+#![allow(dead_code)]
+
+use std::ops::Range;
+
+/// The text around the cursor, with the cursor and the anchor of the selection as byte offsets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurroundingText {
+    text: String,
+    cursor: usize,
+    anchor: usize,
+}
+
+impl SurroundingText {
+    /// Returns `None` if the cursor or the anchor isn't at a character boundary of the text.
+    pub fn new(text: String, cursor: usize, anchor: usize) -> Option<Self> {
+        (text.is_char_boundary(cursor) && text.is_char_boundary(anchor)).then_some(Self {
+            text,
+            cursor,
+            anchor,
+        })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn anchor(&self) -> usize {
+        self.anchor
+    }
+
+    /// The selected bytes, empty at the cursor if nothing is selected.
+    pub fn selection(&self) -> Range<usize> {
+        self.cursor.min(self.anchor)..self.cursor.max(self.anchor)
+    }
+
+    /// Shorten the text to at most `max_len` bytes, keeping as much as possible around the
+    /// selection.
+    pub fn truncate(&self, max_len: usize) -> Self {
+        if self.text.len() <= max_len {
+            return self.clone();
+        }
+
+        let selection = self.selection();
+        let start = if selection.len() > max_len {
+            // Keep the end of the selection, that's where the cursor usually is.
+            selection.end - max_len
+        } else {
+            let margin = (max_len - selection.len()) / 2;
+            selection.start.saturating_sub(margin).min(self.text.len() - max_len)
+        };
+        let start = ceil_char_boundary(&self.text, start);
+        let end = floor_char_boundary(&self.text, start + max_len);
+
+        Self {
+            text: self.text[start..end].to_owned(),
+            cursor: self.cursor.clamp(start, end) - start,
+            anchor: self.anchor.clamp(start, end) - start,
+        }
+    }
+
+    /// The offset in UTF-16 code units of a byte offset.
+    pub fn utf16_offset(&self, offset: usize) -> usize {
+        self.text[..floor_char_boundary(&self.text, offset)].encode_utf16().count()
+    }
+
+    /// The byte offset of an offset in UTF-16 code units, clamped to the text.
+    pub fn byte_offset(&self, utf16_offset: usize) -> usize {
+        let mut utf16_index = 0;
+        for (index, char) in self.text.char_indices() {
+            if utf16_index >= utf16_offset {
+                return index;
+            }
+            utf16_index += char.len_utf16();
+        }
+        self.text.len()
+    }
+
+    /// The range of the text that is replaced, and the text it's replaced with, to change the text
+    /// to `new`.
+    pub fn replaced_range<'a>(&self, new: &'a str) -> (Range<usize>, &'a str) {
+        let prefix: usize = self
+            .text
+            .chars()
+            .zip(new.chars())
+            .take_while(|(old, new)| old == new)
+            .map(|(old, _)| old.len_utf8())
+            .sum();
+        let suffix: usize = self.text[prefix..]
+            .chars()
+            .rev()
+            .zip(new[prefix..].chars().rev())
+            .take_while(|(old, new)| old == new)
+            .map(|(old, _)| old.len_utf8())
+            .sum();
+        (prefix..self.text.len() - suffix, &new[prefix..new.len() - suffix])
+    }
+
+    /// The bytes to delete before and after the selection to delete `range` as well.
+    pub fn delete_range(&self, range: Range<usize>) -> (usize, usize) {
+        let selection = self.selection();
+        (
+            selection.start.saturating_sub(range.start.min(selection.start)),
+            range.end.max(selection.end) - selection.end,
+        )
+    }
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
+fn ceil_char_boundary(text: &str, index: usize) -> usize {
+    let mut index = index.min(text.len());
+    while !text.is_char_boundary(index) {
+        index += 1;
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surrounding_text() {
+        assert!(SurroundingText::new("añb".into(), 2, 0).is_none());
+
+        let text = SurroundingText::new("añb😀c".into(), 8, 1).unwrap();
+        assert_eq!(text.selection(), 1..8);
+        assert_eq!(text.utf16_offset(8), 5);
+        assert_eq!(text.byte_offset(5), 8);
+        assert_eq!(text.byte_offset(100), 9);
+        assert_eq!(text.delete_range(0..9), (1, 1));
+        assert_eq!(text.delete_range(3..4), (0, 0));
+        assert_eq!(text.replaced_range("añc😀c"), (3..4, "c"));
+        assert_eq!(text.replaced_range("añbb😀c"), (4..4, "b"));
+        assert_eq!(text.replaced_range("añ"), (3..9, ""));
+
+        let text = SurroundingText::new("0123456789".into(), 5, 5).unwrap();
+        assert_eq!(text.truncate(4), SurroundingText::new("3456".into(), 2, 2).unwrap());
+        let text = SurroundingText::new("0123456789".into(), 9, 9).unwrap();
+        assert_eq!(text.truncate(4), SurroundingText::new("6789".into(), 3, 3).unwrap());
+        let text = SurroundingText::new("0123456789".into(), 8, 1).unwrap();
+        assert_eq!(text.truncate(4), SurroundingText::new("4567".into(), 4, 0).unwrap());
+        let text = SurroundingText::new("0😀".into(), 0, 0).unwrap();
+        assert_eq!(text.truncate(4), SurroundingText::new("0".into(), 0, 0).unwrap());
+    }
+}
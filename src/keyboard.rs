@@ -74,6 +74,10 @@ use bitflags::bitflags;
 use serde::{Deserialize, Serialize};
 pub use smol_str::SmolStr;
 
+pub use self::accelerator::{Accelerator, AcceleratorKey, ParseAcceleratorError};
+
+mod accelerator;
+
 /// Contains the platform-native physical key identifier
 ///
 /// The exact values vary from platform to platform (which is part of why this is a per-platform
@@ -1756,6 +1760,45 @@ bitflags! {
     }
 }
 
+#[allow(dead_code)] // Not used on every platform
+impl ModifiersKeys {
+    /// Returns the flag of a modifier key, which is empty for other keys and when the side of the
+    /// key is unknown.
+    pub(crate) fn from_key(key: &Key, location: KeyLocation) -> Self {
+        let (left, right) = match key {
+            Key::Named(NamedKey::Shift) => (Self::LSHIFT, Self::RSHIFT),
+            Key::Named(NamedKey::Control) => (Self::LCONTROL, Self::RCONTROL),
+            Key::Named(NamedKey::Alt) => (Self::LALT, Self::RALT),
+            Key::Named(NamedKey::Super | NamedKey::Meta) => (Self::LSUPER, Self::RSUPER),
+            _ => return Self::empty(),
+        };
+        match location {
+            KeyLocation::Left => left,
+            KeyLocation::Right => right,
+            _ => Self::empty(),
+        }
+    }
+
+    /// Drop the keys of modifiers which aren't active anymore.
+    ///
+    /// Releases of modifier keys are missed when the window isn't focused anymore, e.g. when
+    /// switching windows with <kbd>Alt</kbd>+<kbd>Tab</kbd>.
+    pub(crate) fn retain_active(self, state: ModifiersState) -> Self {
+        let mut keys = self;
+        for (modifier, sides) in [
+            (ModifiersState::SHIFT, Self::LSHIFT | Self::RSHIFT),
+            (ModifiersState::CONTROL, Self::LCONTROL | Self::RCONTROL),
+            (ModifiersState::ALT, Self::LALT | Self::RALT),
+            (ModifiersState::SUPER, Self::LSUPER | Self::RSUPER),
+        ] {
+            if !state.contains(modifier) {
+                keys.remove(sides);
+            }
+        }
+        keys
+    }
+}
+
 #[cfg(feature = "serde")]
 mod modifiers_serde {
     use super::ModifiersState;
@@ -0,0 +1,584 @@
+//! Parsing and matching keyboard shortcuts, see [`Accelerator`].
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use super::{Key, KeyCode, ModifiersState, NamedKey, PhysicalKey, SmolStr};
+use crate::event::{ElementState, KeyEvent};
+
+/// A keyboard shortcut, e.g. <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>P</kbd>.
+///
+/// Accelerators are usually parsed from strings like `"Ctrl+Shift+P"` or `"CmdOrCtrl+,"`, and
+/// checked against key presses with [`Accelerator::matches()`], which takes care of the
+/// conventions of the platform and the keyboard layout:
+///
+/// - Characters are matched by the key without modifiers, so `"Ctrl+Shift+P"` matches regardless of
+///   whether <kbd>Shift</kbd> turns the character uppercase, and dead keys match the character
+///   printed on them.
+/// - Characters which need <kbd>Shift</kbd> to be typed match with it, e.g. `"Ctrl+?"` matches
+///   <kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>/</kbd> on a US layout.
+/// - Characters typed with <kbd>AltGr</kbd>, which Windows reports as
+///   <kbd>Ctrl</kbd>+<kbd>Alt</kbd>, don't trigger <kbd>Ctrl</kbd>+<kbd>Alt</kbd> shortcuts.
+/// - On layouts without Latin characters, e.g. Cyrillic, ASCII characters are matched by the
+///   position of the key on a US layout, so `"Ctrl+C"` keeps working.
+///
+/// Use [`AcceleratorKey::Physical`] to match a key by its position regardless of the layout.
+///
+/// ## Syntax
+///
+/// Modifiers and the key are separated by `+`, and are case insensitive:
+///
+/// - `Ctrl` / `Control`, `Shift`, `Alt` / `Option`, and `Super` / `Cmd` / `Command` / `Win` /
+///   `Meta`.
+/// - `CmdOrCtrl` / `CommandOrControl` / `Primary` for [`Accelerator::PRIMARY_MODIFIER`].
+/// - A single character, `Plus` for `+`, or the name of a key like `Enter`, `Esc`, `PageUp`, `Left`
+///   or `F5`.
+/// - `KeyA` to `KeyZ` and `Digit0` to `Digit9` for physical keys.
+///
+/// ```
+/// use winit::keyboard::{Accelerator, ModifiersState};
+///
+/// let accelerator: Accelerator = "CmdOrCtrl+Shift+P".parse().unwrap();
+/// assert_eq!(accelerator.modifiers(), Accelerator::PRIMARY_MODIFIER | ModifiersState::SHIFT);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Accelerator {
+    modifiers: ModifiersState,
+    key: AcceleratorKey,
+}
+
+/// The key of an [`Accelerator`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AcceleratorKey {
+    /// Match the key producing this value on the current keyboard layout.
+    Logical(Key),
+    /// Match the key at this position, regardless of the keyboard layout.
+    Physical(KeyCode),
+}
+
+impl From<Key> for AcceleratorKey {
+    fn from(key: Key) -> Self {
+        Self::Logical(key)
+    }
+}
+
+impl From<NamedKey> for AcceleratorKey {
+    fn from(key: NamedKey) -> Self {
+        Self::Logical(Key::Named(key))
+    }
+}
+
+impl From<KeyCode> for AcceleratorKey {
+    fn from(code: KeyCode) -> Self {
+        Self::Physical(code)
+    }
+}
+
+impl Accelerator {
+    /// The modifier used for most shortcuts on the platform: <kbd>Cmd</kbd> on macOS and iOS, and
+    /// <kbd>Ctrl</kbd> everywhere else.
+    #[cfg(any(macos_platform, ios_platform))]
+    pub const PRIMARY_MODIFIER: ModifiersState = ModifiersState::SUPER;
+    /// The modifier used for most shortcuts on the platform: <kbd>Cmd</kbd> on macOS and iOS, and
+    /// <kbd>Ctrl</kbd> everywhere else.
+    #[cfg(not(any(macos_platform, ios_platform)))]
+    pub const PRIMARY_MODIFIER: ModifiersState = ModifiersState::CONTROL;
+
+    /// Create an accelerator from its modifiers and key.
+    ///
+    /// Characters are matched case insensitively.
+    pub fn new(modifiers: ModifiersState, key: impl Into<AcceleratorKey>) -> Self {
+        let key = match key.into() {
+            AcceleratorKey::Logical(Key::Character(character)) => {
+                AcceleratorKey::Logical(Key::Character(SmolStr::new(character.to_lowercase())))
+            },
+            key => key,
+        };
+        Self { modifiers, key }
+    }
+
+    /// Returns the modifiers which have to be held.
+    pub fn modifiers(&self) -> ModifiersState {
+        self.modifiers
+    }
+
+    /// Returns the key which has to be pressed.
+    pub fn key(&self) -> &AcceleratorKey {
+        &self.key
+    }
+
+    /// Returns whether the key press triggers the accelerator.
+    ///
+    /// `modifiers` is the state last reported by [`WindowEvent::ModifiersChanged`]. Key releases
+    /// never match.
+    ///
+    /// [`WindowEvent::ModifiersChanged`]: crate::event::WindowEvent::ModifiersChanged
+    pub fn matches(&self, event: &KeyEvent, modifiers: ModifiersState) -> bool {
+        event.state == ElementState::Pressed
+            && self.matches_key(
+                &key_without_modifiers(event),
+                &event.logical_key,
+                event.physical_key,
+                modifiers,
+            )
+    }
+
+    fn matches_key(
+        &self,
+        base: &Key,
+        logical: &Key,
+        physical: PhysicalKey,
+        modifiers: ModifiersState,
+    ) -> bool {
+        let character = match &self.key {
+            AcceleratorKey::Physical(code) => {
+                return modifiers == self.modifiers && physical == *code
+            },
+            AcceleratorKey::Logical(Key::Character(character)) => character.as_str(),
+            AcceleratorKey::Logical(key) => {
+                return modifiers == self.modifiers && (logical == key || base == key)
+            },
+        };
+
+        let base = lowercase_character(base);
+        let produced = lowercase_character(logical);
+        let Some(base) = base.as_deref() else {
+            return false;
+        };
+
+        // Windows reports AltGr as Ctrl+Alt, which only produces a different character when the
+        // layout assigns one. On macOS, Option is a regular modifier for shortcuts.
+        let alt_gr = !cfg!(macos_platform)
+            && modifiers.contains(ModifiersState::CONTROL | ModifiersState::ALT)
+            && produced.as_deref().is_some_and(|produced| produced != base);
+
+        if !alt_gr && base == character && modifiers == self.modifiers {
+            return true;
+        }
+
+        // The character needs additional modifiers to be typed, which aren't part of the shortcut.
+        if produced.as_deref().is_some_and(|produced| produced != base && produced == character) {
+            let consumed = if alt_gr {
+                ModifiersState::SHIFT | ModifiersState::CONTROL | ModifiersState::ALT
+            } else if cfg!(macos_platform) {
+                ModifiersState::SHIFT | ModifiersState::ALT
+            } else {
+                ModifiersState::SHIFT
+            };
+            if modifiers.contains(self.modifiers) && consumed.contains(modifiers - self.modifiers) {
+                return true;
+            }
+        }
+
+        // Layouts without Latin characters fall back to the positions of a US layout.
+        !base.is_ascii()
+            && modifiers == self.modifiers
+            && us_key_code(character).is_some_and(|code| physical == code)
+    }
+}
+
+/// The key without modifiers, where the platform reports it.
+fn key_without_modifiers(event: &KeyEvent) -> Key {
+    #[cfg(any(windows_platform, macos_platform, x11_platform, wayland_platform, orbital_platform))]
+    {
+        event.platform_specific.key_without_modifiers.clone()
+    }
+    #[cfg(not(any(
+        windows_platform,
+        macos_platform,
+        x11_platform,
+        wayland_platform,
+        orbital_platform
+    )))]
+    {
+        event.logical_key.clone()
+    }
+}
+
+/// Returns the lowercase character of the key, including the character printed on dead keys.
+fn lowercase_character(key: &Key) -> Option<String> {
+    match key {
+        Key::Character(character) => Some(character.to_lowercase()),
+        Key::Dead(Some(character)) => Some(character.to_lowercase().collect()),
+        _ => None,
+    }
+}
+
+const LETTERS: [KeyCode; 26] = [
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+];
+
+const DIGITS: [KeyCode; 10] = [
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// Returns the key producing the character on a US layout without modifiers.
+fn us_key_code(character: &str) -> Option<KeyCode> {
+    let mut chars = character.chars();
+    let (Some(character), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+    Some(match character {
+        'a'..='z' => LETTERS[character as usize - 'a' as usize],
+        '0'..='9' => DIGITS[character as usize - '0' as usize],
+        '`' => KeyCode::Backquote,
+        '-' => KeyCode::Minus,
+        '=' => KeyCode::Equal,
+        '[' => KeyCode::BracketLeft,
+        ']' => KeyCode::BracketRight,
+        '\\' => KeyCode::Backslash,
+        ';' => KeyCode::Semicolon,
+        '\'' => KeyCode::Quote,
+        ',' => KeyCode::Comma,
+        '.' => KeyCode::Period,
+        '/' => KeyCode::Slash,
+        _ => return None,
+    })
+}
+
+/// Names of keys, the first one of each key is used for formatting.
+const NAMED_KEYS: &[(&str, NamedKey)] = &[
+    ("Enter", NamedKey::Enter),
+    ("Return", NamedKey::Enter),
+    ("Tab", NamedKey::Tab),
+    ("Space", NamedKey::Space),
+    ("Backspace", NamedKey::Backspace),
+    ("Delete", NamedKey::Delete),
+    ("Del", NamedKey::Delete),
+    ("Insert", NamedKey::Insert),
+    ("Ins", NamedKey::Insert),
+    ("Esc", NamedKey::Escape),
+    ("Escape", NamedKey::Escape),
+    ("Home", NamedKey::Home),
+    ("End", NamedKey::End),
+    ("PageUp", NamedKey::PageUp),
+    ("PgUp", NamedKey::PageUp),
+    ("PageDown", NamedKey::PageDown),
+    ("PgDn", NamedKey::PageDown),
+    ("Up", NamedKey::ArrowUp),
+    ("ArrowUp", NamedKey::ArrowUp),
+    ("Down", NamedKey::ArrowDown),
+    ("ArrowDown", NamedKey::ArrowDown),
+    ("Left", NamedKey::ArrowLeft),
+    ("ArrowLeft", NamedKey::ArrowLeft),
+    ("Right", NamedKey::ArrowRight),
+    ("ArrowRight", NamedKey::ArrowRight),
+    ("PrintScreen", NamedKey::PrintScreen),
+    ("Pause", NamedKey::Pause),
+    ("ContextMenu", NamedKey::ContextMenu),
+    ("MediaPlayPause", NamedKey::MediaPlayPause),
+    ("MediaStop", NamedKey::MediaStop),
+    ("MediaTrackNext", NamedKey::MediaTrackNext),
+    ("MediaTrackPrevious", NamedKey::MediaTrackPrevious),
+    ("VolumeUp", NamedKey::AudioVolumeUp),
+    ("VolumeDown", NamedKey::AudioVolumeDown),
+    ("VolumeMute", NamedKey::AudioVolumeMute),
+    ("F1", NamedKey::F1),
+    ("F2", NamedKey::F2),
+    ("F3", NamedKey::F3),
+    ("F4", NamedKey::F4),
+    ("F5", NamedKey::F5),
+    ("F6", NamedKey::F6),
+    ("F7", NamedKey::F7),
+    ("F8", NamedKey::F8),
+    ("F9", NamedKey::F9),
+    ("F10", NamedKey::F10),
+    ("F11", NamedKey::F11),
+    ("F12", NamedKey::F12),
+    ("F13", NamedKey::F13),
+    ("F14", NamedKey::F14),
+    ("F15", NamedKey::F15),
+    ("F16", NamedKey::F16),
+    ("F17", NamedKey::F17),
+    ("F18", NamedKey::F18),
+    ("F19", NamedKey::F19),
+    ("F20", NamedKey::F20),
+    ("F21", NamedKey::F21),
+    ("F22", NamedKey::F22),
+    ("F23", NamedKey::F23),
+    ("F24", NamedKey::F24),
+];
+
+fn parse_modifier(name: &str) -> Option<ModifiersState> {
+    const MODIFIERS: &[(&str, ModifiersState)] = &[
+        ("Ctrl", ModifiersState::CONTROL),
+        ("Control", ModifiersState::CONTROL),
+        ("Shift", ModifiersState::SHIFT),
+        ("Alt", ModifiersState::ALT),
+        ("Option", ModifiersState::ALT),
+        ("Super", ModifiersState::SUPER),
+        ("Cmd", ModifiersState::SUPER),
+        ("Command", ModifiersState::SUPER),
+        ("Win", ModifiersState::SUPER),
+        ("Meta", ModifiersState::SUPER),
+        ("CmdOrCtrl", Accelerator::PRIMARY_MODIFIER),
+        ("CommandOrControl", Accelerator::PRIMARY_MODIFIER),
+        ("Primary", Accelerator::PRIMARY_MODIFIER),
+    ];
+    MODIFIERS.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|&(_, modifier)| modifier)
+}
+
+fn parse_key(name: &str) -> Option<AcceleratorKey> {
+    let mut chars = name.chars();
+    if let (Some(character), None) = (chars.next(), chars.next()) {
+        return Some(match character {
+            ' ' => NamedKey::Space.into(),
+            _ => Key::Character(SmolStr::new(name)).into(),
+        });
+    }
+
+    if name.eq_ignore_ascii_case("Plus") {
+        return Some(Key::Character(SmolStr::new("+")).into());
+    }
+
+    if let Some(&(_, key)) = NAMED_KEYS.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)) {
+        return Some(key.into());
+    }
+
+    let physical = |prefix: &str, first: char, codes: &[KeyCode]| {
+        let rest = name.strip_prefix(prefix)?;
+        let mut chars = rest.chars();
+        let (Some(character), None) = (chars.next(), chars.next()) else {
+            return None;
+        };
+        let index = (character.to_ascii_uppercase() as usize).checked_sub(first as usize)?;
+        codes.get(index).map(|&code| AcceleratorKey::Physical(code))
+    };
+    physical("Key", 'A', &LETTERS).or_else(|| physical("Digit", '0', &DIGITS))
+}
+
+impl FromStr for Accelerator {
+    type Err = ParseAcceleratorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseAcceleratorError::Empty);
+        }
+
+        // The key may be `+` itself, as in `Ctrl++`.
+        let (modifier_names, key_name) = match s.strip_suffix("++") {
+            Some(modifier_names) => (modifier_names, "+"),
+            None if s == "+" => ("", "+"),
+            None => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+
+        let mut modifiers = ModifiersState::empty();
+        if !modifier_names.is_empty() {
+            for name in modifier_names.split('+').map(str::trim) {
+                modifiers |= parse_modifier(name)
+                    .ok_or_else(|| ParseAcceleratorError::UnknownModifier(name.to_owned()))?;
+            }
+        }
+
+        let key_name = match key_name {
+            " " => key_name,
+            _ => key_name.trim(),
+        };
+        if key_name.is_empty() {
+            return Err(ParseAcceleratorError::MissingKey);
+        }
+        let key = parse_key(key_name)
+            .ok_or_else(|| ParseAcceleratorError::UnknownKey(key_name.to_owned()))?;
+
+        Ok(Self::new(modifiers, key))
+    }
+}
+
+impl fmt::Display for Accelerator {
+    /// Formats the accelerator with the modifier names of the platform, e.g. `Cmd+Shift+P` on
+    /// macOS and `Ctrl+Shift+P` elsewhere.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (alt, super_key) = if cfg!(any(macos_platform, ios_platform)) {
+            ("Option", "Cmd")
+        } else if cfg!(windows_platform) {
+            ("Alt", "Win")
+        } else {
+            ("Alt", "Super")
+        };
+        for (modifier, name) in [
+            (ModifiersState::CONTROL, "Ctrl"),
+            (ModifiersState::ALT, alt),
+            (ModifiersState::SHIFT, "Shift"),
+            (ModifiersState::SUPER, super_key),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+
+        match &self.key {
+            AcceleratorKey::Logical(Key::Character(character)) if character == "+" => {
+                f.write_str("Plus")
+            },
+            AcceleratorKey::Logical(Key::Character(character)) => {
+                f.write_str(&character.to_uppercase())
+            },
+            AcceleratorKey::Logical(Key::Named(named)) => {
+                match NAMED_KEYS.iter().find(|(_, key)| key == named) {
+                    Some((name, _)) => f.write_str(name),
+                    None => write!(f, "{named:?}"),
+                }
+            },
+            AcceleratorKey::Logical(key) => write!(f, "{key:?}"),
+            AcceleratorKey::Physical(code) => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// An error produced when parsing an [`Accelerator`] failed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseAcceleratorError {
+    /// The string was empty.
+    Empty,
+    /// The string only contained modifiers.
+    MissingKey,
+    /// A modifier wasn't recognized.
+    UnknownModifier(String),
+    /// The key wasn't recognized.
+    UnknownKey(String),
+}
+
+impl fmt::Display for ParseAcceleratorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("the accelerator is empty"),
+            Self::MissingKey => f.write_str("the accelerator has no key"),
+            Self::UnknownModifier(name) => write!(f, "unknown modifier `{name}`"),
+            Self::UnknownKey(name) => write!(f, "unknown key `{name}`"),
+        }
+    }
+}
+
+impl Error for ParseAcceleratorError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn character(character: &str) -> Key {
+        Key::Character(SmolStr::new(character))
+    }
+
+    #[test]
+    fn parse() {
+        let accelerator: Accelerator = "ctrl+Shift+p".parse().unwrap();
+        assert_eq!(accelerator.modifiers(), ModifiersState::CONTROL | ModifiersState::SHIFT);
+        assert_eq!(accelerator.key(), &AcceleratorKey::Logical(character("p")));
+
+        let accelerator: Accelerator = "CmdOrCtrl++".parse().unwrap();
+        assert_eq!(accelerator.modifiers(), Accelerator::PRIMARY_MODIFIER);
+        assert_eq!(accelerator.key(), &AcceleratorKey::Logical(character("+")));
+
+        let accelerator: Accelerator = "Alt + F4".parse().unwrap();
+        assert_eq!(accelerator.key(), &AcceleratorKey::Logical(Key::Named(NamedKey::F4)));
+
+        let accelerator: Accelerator = "Ctrl+KeyZ".parse().unwrap();
+        assert_eq!(accelerator.key(), &AcceleratorKey::Physical(KeyCode::KeyZ));
+
+        assert_eq!("".parse::<Accelerator>(), Err(ParseAcceleratorError::Empty));
+        assert_eq!("Ctrl+".parse::<Accelerator>(), Err(ParseAcceleratorError::MissingKey));
+        assert_eq!(
+            "Hyper+A".parse::<Accelerator>(),
+            Err(ParseAcceleratorError::UnknownModifier("Hyper".to_owned()))
+        );
+        assert_eq!(
+            "Ctrl+Foo".parse::<Accelerator>(),
+            Err(ParseAcceleratorError::UnknownKey("Foo".to_owned()))
+        );
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for accelerator in ["Ctrl+Shift+P", "Alt+Plus", "Ctrl+PageUp", "Shift+F5", "Ctrl+KeyZ"] {
+            let accelerator: Accelerator = accelerator.parse().unwrap();
+            assert_eq!(accelerator.to_string().parse::<Accelerator>().unwrap(), accelerator);
+        }
+    }
+
+    #[test]
+    fn matches_characters() {
+        let ctrl_shift = ModifiersState::CONTROL | ModifiersState::SHIFT;
+        let accelerator: Accelerator = "Ctrl+Shift+P".parse().unwrap();
+        let physical = PhysicalKey::Code(KeyCode::KeyP);
+        assert!(accelerator.matches_key(&character("p"), &character("P"), physical, ctrl_shift));
+        assert!(!accelerator.matches_key(
+            &character("p"),
+            &character("p"),
+            physical,
+            ModifiersState::CONTROL
+        ));
+
+        // Typing `?` needs Shift on a US layout.
+        let accelerator: Accelerator = "Ctrl+?".parse().unwrap();
+        let physical = PhysicalKey::Code(KeyCode::Slash);
+        assert!(accelerator.matches_key(&character("/"), &character("?"), physical, ctrl_shift));
+
+        // Cyrillic layouts fall back to the US positions.
+        let accelerator: Accelerator = "Ctrl+C".parse().unwrap();
+        let physical = PhysicalKey::Code(KeyCode::KeyC);
+        assert!(accelerator.matches_key(
+            &character("с"),
+            &character("с"),
+            physical,
+            ModifiersState::CONTROL
+        ));
+
+        // Dead keys match the character printed on them.
+        let accelerator: Accelerator = "Ctrl+`".parse().unwrap();
+        let physical = PhysicalKey::Code(KeyCode::Backquote);
+        assert!(accelerator.matches_key(
+            &Key::Dead(Some('`')),
+            &Key::Dead(Some('`')),
+            physical,
+            ModifiersState::CONTROL
+        ));
+    }
+
+    #[test]
+    #[cfg(not(macos_platform))]
+    fn alt_gr_is_not_a_shortcut() {
+        // `@` is typed with AltGr+Q on a German layout, which is reported as Ctrl+Alt on Windows.
+        let ctrl_alt = ModifiersState::CONTROL | ModifiersState::ALT;
+        let physical = PhysicalKey::Code(KeyCode::KeyQ);
+        let accelerator: Accelerator = "Ctrl+Alt+Q".parse().unwrap();
+        assert!(!accelerator.matches_key(&character("q"), &character("@"), physical, ctrl_alt));
+        let accelerator: Accelerator = "@".parse().unwrap();
+        assert!(accelerator.matches_key(&character("q"), &character("@"), physical, ctrl_alt));
+    }
+}
@@ -146,6 +146,8 @@
 //! * `rwh_06`: Implement `raw-window-handle v0.6` traits.
 //! * `serde`: Enables serialization/deserialization of certain types with [Serde](https://crates.io/crates/serde).
 //! * `mint`: Enables mint (math interoperability standard types) conversions.
+//! * `trace`: Instruments the event loop with [`tracing`](https://docs.rs/tracing) spans, and
+//!   enables [`ActiveEventLoop::stats()`][event_loop::ActiveEventLoop::stats].
 //!
 //! See the [`platform`] module for documentation on platform-specific cargo
 //! features.
@@ -189,19 +191,32 @@ pub use rwh_06 as raw_window_handle;
 #[doc(inline)]
 pub use dpi;
 
+pub mod app;
 pub mod application;
 #[cfg(any(doc, doctest, test))]
 pub mod changelog;
+mod command_queue;
+pub mod dialog;
+mod drop_negotiation;
 #[macro_use]
 pub mod error;
 mod cursor;
 pub mod event;
 pub mod event_loop;
+mod frame_budget;
 mod icon;
+mod ime;
 pub mod keyboard;
+pub mod locale;
 pub mod monitor;
+pub mod notification;
+mod performance_hint;
+pub mod placement;
 mod platform_impl;
+mod redraw_thread;
+pub mod session;
 mod utils;
+mod velocity;
 pub mod window;
 
 pub mod platform;
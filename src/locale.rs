@@ -0,0 +1,134 @@
+//! The [`LanguageTag`] type, see [`ActiveEventLoop::locale()`].
+//!
+//! [`ActiveEventLoop::locale()`]: crate::event_loop::ActiveEventLoop::locale
+
+use std::fmt;
+
+/// A [BCP 47] language tag like `en-US` or `zh-Hant-TW`, describing the language the user prefers.
+///
+/// Tags are normalized to the common casing, with a lowercase language, a titlecase script and an
+/// uppercase region, so they can be compared directly.
+///
+/// [BCP 47]: https://www.rfc-editor.org/info/bcp47
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LanguageTag(String);
+
+impl LanguageTag {
+    /// Parses a language tag, returning `None` if it isn't well-formed.
+    ///
+    /// Underscores are accepted as separators as well, like in `en_US`.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let mut normalized = String::with_capacity(tag.len());
+        for (index, subtag) in tag.split(['-', '_']).enumerate() {
+            let valid = if index == 0 {
+                (2..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphabetic())
+            } else {
+                (1..=8).contains(&subtag.len()) && subtag.chars().all(|c| c.is_ascii_alphanumeric())
+            };
+            if !valid {
+                return None;
+            }
+
+            if index > 0 {
+                normalized.push('-');
+            }
+            match subtag.len() {
+                // Regions, which follow the language or the script.
+                2 if index == 1 || index == 2 => normalized.push_str(&subtag.to_ascii_uppercase()),
+                // Scripts.
+                4 if index == 1 && subtag.chars().all(|c| c.is_ascii_alphabetic()) => {
+                    normalized.push_str(&subtag[..1].to_ascii_uppercase());
+                    normalized.push_str(&subtag[1..].to_ascii_lowercase());
+                },
+                _ => normalized.push_str(&subtag.to_ascii_lowercase()),
+            }
+        }
+
+        Some(Self(normalized))
+    }
+
+    /// Converts a POSIX locale like `de_DE.UTF-8@euro`, ignoring the `C` and `POSIX` locales.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn from_posix(locale: &str) -> Option<Self> {
+        let locale = locale.split(['.', '@']).next().unwrap_or_default();
+        if locale == "C" || locale == "POSIX" {
+            return None;
+        }
+        Self::parse(locale)
+    }
+
+    /// Returns the tag as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the primary language subtag, e.g. `en` for `en-US`.
+    pub fn language(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+
+    /// Returns whether the language is written from right to left, like Arabic or Hebrew.
+    ///
+    /// An explicit script wins over the language, e.g. `az-Arab` is right-to-left while `az` isn't.
+    pub fn is_right_to_left(&self) -> bool {
+        const SCRIPTS: &[&str] = &["Adlm", "Arab", "Hebr", "Nkoo", "Rohg", "Syrc", "Thaa"];
+        const LANGUAGES: &[&str] =
+            &["ar", "ckb", "dv", "fa", "he", "iw", "ks", "ps", "sd", "ug", "ur", "yi"];
+
+        let script =
+            self.0.split('-').nth(1).filter(|subtag| {
+                subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic())
+            });
+        match script {
+            Some(script) => SCRIPTS.contains(&script),
+            None => LANGUAGES.contains(&self.language()),
+        }
+    }
+}
+
+impl AsRef<str> for LanguageTag {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(LanguageTag::parse("en-US").unwrap().as_str(), "en-US");
+        assert_eq!(LanguageTag::parse("EN_us").unwrap().as_str(), "en-US");
+        assert_eq!(LanguageTag::parse("zh-hant-tw").unwrap().as_str(), "zh-Hant-TW");
+        assert_eq!(LanguageTag::parse("es-419").unwrap().as_str(), "es-419");
+        assert_eq!(LanguageTag::parse("de").unwrap().language(), "de");
+        assert_eq!(LanguageTag::parse(""), None);
+        assert_eq!(LanguageTag::parse("e"), None);
+        assert_eq!(LanguageTag::parse("en-"), None);
+        assert_eq!(LanguageTag::parse("en-US.UTF-8"), None);
+    }
+
+    #[test]
+    fn is_right_to_left() {
+        assert!(LanguageTag::parse("ar-EG").unwrap().is_right_to_left());
+        assert!(LanguageTag::parse("he").unwrap().is_right_to_left());
+        assert!(LanguageTag::parse("az-Arab").unwrap().is_right_to_left());
+        assert!(!LanguageTag::parse("en-US").unwrap().is_right_to_left());
+        assert!(!LanguageTag::parse("ur-Latn").unwrap().is_right_to_left());
+    }
+
+    #[test]
+    fn from_posix() {
+        assert_eq!(LanguageTag::from_posix("de_DE.UTF-8@euro").unwrap().as_str(), "de-DE");
+        assert_eq!(LanguageTag::from_posix("pt_BR").unwrap().as_str(), "pt-BR");
+        assert_eq!(LanguageTag::from_posix("C.UTF-8"), None);
+        assert_eq!(LanguageTag::from_posix("POSIX"), None);
+    }
+}
@@ -5,6 +5,8 @@
 //! methods, which return an iterator of [`MonitorHandle`]:
 //! - [`ActiveEventLoop::available_monitors`][crate::event_loop::ActiveEventLoop::available_monitors].
 //! - [`Window::available_monitors`][crate::window::Window::available_monitors].
+use std::ops::RangeInclusive;
+
 use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::platform_impl;
 
@@ -73,6 +75,24 @@ impl VideoModeHandle {
         self.video_mode.refresh_rate_millihertz()
     }
 
+    /// Returns the range of refresh rates in mHz that frames can be presented at in this video
+    /// mode.
+    ///
+    /// On monitors that [support variable refresh rates][MonitorHandle::supports_variable_refresh],
+    /// this goes from the lowest rate of the monitor up to [`refresh_rate_millihertz`]. On other
+    /// monitors, it only contains [`refresh_rate_millihertz`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The lowest rate is read from the range limits in the EDID of the monitor, the
+    ///   range only contains [`refresh_rate_millihertz`] if the EDID doesn't have them.
+    ///
+    /// [`refresh_rate_millihertz`]: Self::refresh_rate_millihertz
+    #[inline]
+    pub fn refresh_rate_range(&self) -> RangeInclusive<u32> {
+        self.video_mode.refresh_rate_range()
+    }
+
     /// Returns the monitor that this video mode is valid for. Each monitor has
     /// a separate set of valid video modes.
     #[inline]
@@ -138,6 +158,21 @@ impl MonitorHandle {
         self.inner.refresh_rate_millihertz()
     }
 
+    /// Returns whether the monitor supports variable refresh rates, like FreeSync, G-Sync or
+    /// ProMotion, so frames can be presented whenever they are ready within
+    /// [`VideoModeHandle::refresh_rate_range`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Whether the refresh interval of the screen is variable, which requires macOS
+    ///   12.
+    /// - **X11:** The `vrr_capable` property of the output, set by the kernel driver.
+    /// - **Android / iOS / Orbital / Wayland / Web / Windows:** Always returns `false`.
+    #[inline]
+    pub fn supports_variable_refresh(&self) -> bool {
+        self.inner.supports_variable_refresh()
+    }
+
     /// Returns the scale factor of the underlying monitor. To map logical pixels to physical
     /// pixels and vice versa, use [`Window::scale_factor`].
     ///
@@ -155,6 +190,68 @@ impl MonitorHandle {
         self.inner.scale_factor()
     }
 
+    /// Returns the area of the monitor that isn't covered by taskbars, docks or panels, in the
+    /// same coordinates as [`position`][Self::position].
+    ///
+    /// New windows should be placed inside the work area so they aren't hidden, and the work
+    /// area changing is reported with [`ApplicationHandler::work_area_changed`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The visible frame of the screen, without the menu bar and the dock.
+    /// - **X11:** `_NET_WORKAREA` of the current desktop, restricted to the monitor.
+    /// - **Android / iOS / Orbital / Wayland:** Returns the whole monitor.
+    ///
+    /// [`ApplicationHandler::work_area_changed`]: crate::application::ApplicationHandler::work_area_changed
+    #[inline]
+    pub fn work_area(&self) -> Rect {
+        self.inner.work_area()
+    }
+
+    /// Returns whether the monitor shows the same content as another monitor, like a projector
+    /// mirroring a laptop screen.
+    ///
+    /// Mirrored monitors aren't suited for the output view of a presentation, see
+    /// [`ActiveEventLoop::presentation_displays()`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Whether the display is in a mirroring set, as the source or as a mirror.
+    /// - **iOS:** Whether the screen mirrors the main screen, e.g. with AirPlay mirroring.
+    /// - **Windows:** Whether more than one display is attached to the output of the monitor, in
+    ///   clone mode.
+    /// - **X11:** Whether the CRTC drives more than one output, or another CRTC covers the same
+    ///   area.
+    /// - **Android / Orbital / Wayland:** Always returns `false`.
+    ///
+    /// [`ActiveEventLoop::presentation_displays()`]: crate::event_loop::ActiveEventLoop::presentation_displays
+    #[inline]
+    pub fn is_mirrored(&self) -> bool {
+        self.inner.is_mirrored()
+    }
+
+    /// Returns the ICC profile of the monitor, for color managing and soft-proofing content.
+    ///
+    /// Unlike sampling the screen, this doesn't require a permission to capture the screen.
+    /// Changes are reported with [`ApplicationHandler::color_profile_changed`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The ICC data of the color space of the display, from ColorSync.
+    /// - **Windows:** The profile returned by `GetICMProfileW` for the device context of the
+    ///   monitor.
+    /// - **X11:** The `_ICC_PROFILE` property of the root window set by color managers like colord,
+    ///   or `_ICC_PROFILE_n` for the monitor at index `n`.
+    /// - **Wayland:** Always `None`, the color management protocol isn't supported yet.
+    /// - **Web:** Always `None`, browsers don't expose the profile of the screen.
+    /// - **Android / iOS / Orbital:** Always `None`.
+    ///
+    /// [`ApplicationHandler::color_profile_changed`]: crate::application::ApplicationHandler::color_profile_changed
+    #[inline]
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        self.inner.icc_profile()
+    }
+
     /// Returns all fullscreen video modes supported by this monitor.
     ///
     /// ## Platform-specific
@@ -165,3 +262,20 @@ impl MonitorHandle {
         self.inner.video_modes().map(|video_mode| VideoModeHandle { video_mode })
     }
 }
+
+/// A rectangle on the desktop, in physical pixels.
+///
+/// See [`MonitorHandle::work_area`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub position: PhysicalPosition<i32>,
+    pub size: PhysicalSize<u32>,
+}
+
+impl Rect {
+    /// Creates a rectangle from its top-left corner and its size.
+    #[inline]
+    pub fn new(position: PhysicalPosition<i32>, size: PhysicalSize<u32>) -> Self {
+        Self { position, size }
+    }
+}
@@ -0,0 +1,115 @@
+//! The [`NotificationAttributes`] struct and associated types.
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::platform_impl::PlatformNotification;
+
+/// Attributes used when showing a notification with
+/// [`ActiveEventLoop::show_notification()`](crate::event_loop::ActiveEventLoop::show_notification).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotificationAttributes {
+    pub title: String,
+    pub body: String,
+    pub silent: bool,
+}
+
+impl NotificationAttributes {
+    /// Sets the title of the notification.
+    #[inline]
+    pub fn with_title<T: Into<String>>(mut self, title: T) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Sets the text shown below the title.
+    #[inline]
+    pub fn with_body<T: Into<String>>(mut self, body: T) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Sets whether the notification should be shown without playing a sound.
+    ///
+    /// The default is `false`.
+    #[inline]
+    pub fn with_silent(mut self, silent: bool) -> Self {
+        self.silent = silent;
+        self
+    }
+}
+
+/// Identifier of a notification, used to match [`NotificationEvent`]s to the
+/// [`NotificationHandle`] returned when showing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NotificationId(u64);
+
+impl NotificationId {
+    pub(crate) fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) const fn from_raw(id: u64) -> Self {
+        Self(id)
+    }
+
+    /// Returns the raw value of the identifier.
+    pub const fn into_raw(self) -> u64 {
+        self.0
+    }
+}
+
+/// Describes how the user interacted with a notification.
+///
+/// See [`ApplicationHandler::notification_event`] for details.
+///
+/// [`ApplicationHandler::notification_event`]: crate::application::ApplicationHandler::notification_event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NotificationEvent {
+    /// The user clicked the notification.
+    Clicked,
+    /// The notification was dismissed by the user or the system.
+    Dismissed,
+}
+
+/// A notification shown with
+/// [`ActiveEventLoop::show_notification()`](crate::event_loop::ActiveEventLoop::show_notification).
+///
+/// Dropping the handle doesn't close the notification.
+pub struct NotificationHandle {
+    pub(crate) id: NotificationId,
+    pub(crate) inner: PlatformNotification,
+}
+
+impl fmt::Debug for NotificationHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotificationHandle").field("id", &self.id).finish_non_exhaustive()
+    }
+}
+
+impl NotificationHandle {
+    /// Returns the identifier passed along with the [`NotificationEvent`]s of this notification.
+    #[inline]
+    pub fn id(&self) -> NotificationId {
+        self.id
+    }
+
+    /// Removes the notification, without emitting [`NotificationEvent::Dismissed`].
+    #[inline]
+    pub fn close(&self) {
+        self.inner.close()
+    }
+}
+
+/// For platforms which don't support notifications.
+#[allow(dead_code)] // Not used on every platform
+#[derive(Debug)]
+pub(crate) enum NoNotification {}
+
+#[allow(dead_code)] // Not used on every platform
+impl NoNotification {
+    pub fn close(&self) {
+        match *self {}
+    }
+}
@@ -0,0 +1,45 @@
+//! The performance hint of the process, see [`Window::set_performance_hint()`].
+//!
+//! [`Window::set_performance_hint()`]: crate::window::Window::set_performance_hint
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::window::{PerformanceHint, WindowId};
+
+// Windows can be used from any thread, so this can't be thread local.
+static HINTS: Mutex<Option<HashMap<WindowId, PerformanceHint>>> = Mutex::new(None);
+
+fn strongest(hints: &HashMap<WindowId, PerformanceHint>) -> PerformanceHint {
+    hints.values().copied().max().unwrap_or_default()
+}
+
+/// Replace the hint of a window, and return the hint of the process if that changed.
+pub(crate) fn set(window_id: WindowId, hint: PerformanceHint) -> Option<PerformanceHint> {
+    let mut hints = HINTS.lock().unwrap();
+    let hints = hints.get_or_insert_with(HashMap::new);
+    let old = strongest(hints);
+    match hint {
+        PerformanceHint::Default => hints.remove(&window_id),
+        hint => hints.insert(window_id, hint),
+    };
+    let new = strongest(hints);
+    (new != old).then_some(new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strongest_hint() {
+        let first = WindowId::from(u64::MAX - 1);
+        let second = WindowId::from(u64::MAX - 2);
+
+        assert_eq!(set(first, PerformanceHint::Game), Some(PerformanceHint::Game));
+        assert_eq!(set(second, PerformanceHint::Realtime), Some(PerformanceHint::Realtime));
+        assert_eq!(set(first, PerformanceHint::Default), None);
+        assert_eq!(set(second, PerformanceHint::Game), Some(PerformanceHint::Game));
+        assert_eq!(set(second, PerformanceHint::Default), Some(PerformanceHint::Default));
+    }
+}
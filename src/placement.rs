@@ -0,0 +1,201 @@
+//! Placing windows on monitors, see [`Window::center_on()`], [`Window::move_to_monitor()`],
+//! [`Window::cascade_from()`] and [`Window::set_outer_position_on()`].
+//!
+//! Windows are placed inside the [work area] of monitors, so they don't end up under taskbars or
+//! docks, and are kept on the monitor when they don't fit.
+//!
+//! [`Window::center_on()`]: crate::window::Window::center_on
+//! [`Window::move_to_monitor()`]: crate::window::Window::move_to_monitor
+//! [`Window::cascade_from()`]: crate::window::Window::cascade_from
+//! [`Window::set_outer_position_on()`]: crate::window::Window::set_outer_position_on
+//! [work area]: crate::monitor::MonitorHandle::work_area
+
+use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::locale::LanguageTag;
+use crate::monitor::{MonitorHandle, Rect};
+
+/// Which monitor to place a window on, see [`Window::center_on()`].
+///
+/// [`Window::center_on()`]: crate::window::Window::center_on
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum MonitorSelector {
+    /// The monitor the window is currently on.
+    #[default]
+    Current,
+    /// The primary monitor of the system.
+    Primary,
+    /// The given monitor.
+    Monitor(MonitorHandle),
+}
+
+/// Where to place a window inside the work area of a monitor, see [`Window::move_to_monitor()`].
+///
+/// [`Window::move_to_monitor()`]: crate::window::Window::move_to_monitor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Align {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+/// The direction of the layout of the user interface, which decides the corner of the work area
+/// positions are relative to, see [`Window::set_outer_position_on()`].
+///
+/// [`Window::set_outer_position_on()`]: crate::window::Window::set_outer_position_on
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum LayoutDirection {
+    /// Positions are relative to the top-left corner, to the left edge of the window.
+    #[default]
+    LeftToRight,
+    /// Positions are relative to the top-right corner, to the right edge of the window, like in
+    /// Arabic or Hebrew user interfaces.
+    RightToLeft,
+}
+
+impl LayoutDirection {
+    /// Returns the direction in which the language of `locale` is written, see
+    /// [`ActiveEventLoop::locale()`].
+    ///
+    /// [`ActiveEventLoop::locale()`]: crate::event_loop::ActiveEventLoop::locale
+    pub fn of(locale: &LanguageTag) -> Self {
+        if locale.is_right_to_left() {
+            Self::RightToLeft
+        } else {
+            Self::LeftToRight
+        }
+    }
+}
+
+/// The offset between cascaded windows, in logical pixels, which is about the height of a title
+/// bar.
+const CASCADE_OFFSET: f64 = 32.0;
+
+/// Returns the position of a window of `size` aligned inside of `area`.
+///
+/// Windows larger than the area are placed at its top-left corner, so their title bar stays
+/// reachable.
+pub fn align(area: Rect, size: PhysicalSize<u32>, align: Align) -> PhysicalPosition<i32> {
+    let offset = |available: u32, size: u32, start: bool, end: bool| {
+        let free = available.saturating_sub(size);
+        match (start, end) {
+            (true, _) => 0,
+            (_, true) => free,
+            _ => free / 2,
+        }
+    };
+
+    let (left, right) = match align {
+        Align::TopLeft | Align::Left | Align::BottomLeft => (true, false),
+        Align::TopRight | Align::Right | Align::BottomRight => (false, true),
+        Align::Top | Align::Center | Align::Bottom => (false, false),
+    };
+    let (top, bottom) = match align {
+        Align::TopLeft | Align::Top | Align::TopRight => (true, false),
+        Align::BottomLeft | Align::Bottom | Align::BottomRight => (false, true),
+        Align::Left | Align::Center | Align::Right => (false, false),
+    };
+
+    PhysicalPosition::new(
+        area.position.x + offset(area.size.width, size.width, left, right) as i32,
+        area.position.y + offset(area.size.height, size.height, top, bottom) as i32,
+    )
+}
+
+/// Returns the position of a window of `size` cascaded from a window at `from`, which starts over
+/// at the top-left corner of `area` when the window wouldn't fit anymore.
+pub fn cascade(
+    area: Rect,
+    from: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+    scale_factor: f64,
+) -> PhysicalPosition<i32> {
+    let offset = (CASCADE_OFFSET * scale_factor).round() as i32;
+    let fits = |start: i32, area_start: i32, area_size: u32, size: u32| {
+        start >= area_start && start + size as i32 <= area_start + area_size as i32
+    };
+
+    let position = PhysicalPosition::new(from.x + offset, from.y + offset);
+    if fits(position.x, area.position.x, area.size.width, size.width)
+        && fits(position.y, area.position.y, area.size.height, size.height)
+    {
+        position
+    } else {
+        align(area, size, Align::TopLeft)
+    }
+}
+
+/// Returns the position of a window of `size` at `position` from the leading top corner of
+/// `area`, in logical pixels of a monitor with `scale_factor`.
+///
+/// In [`LayoutDirection::RightToLeft`], `position.x` is the distance between the right edges of
+/// the area and the window.
+pub fn relative_to(
+    area: Rect,
+    position: LogicalPosition<f64>,
+    size: PhysicalSize<u32>,
+    scale_factor: f64,
+    direction: LayoutDirection,
+) -> PhysicalPosition<i32> {
+    let offset: PhysicalPosition<i32> = position.to_physical(scale_factor);
+    let x = match direction {
+        LayoutDirection::LeftToRight => area.position.x + offset.x,
+        LayoutDirection::RightToLeft => {
+            area.position.x + area.size.width as i32 - offset.x - size.width as i32
+        },
+    };
+    PhysicalPosition::new(x, area.position.y + offset.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area() -> Rect {
+        Rect::new(PhysicalPosition::new(100, 50), PhysicalSize::new(1000, 800))
+    }
+
+    #[test]
+    fn align() {
+        let size = PhysicalSize::new(200, 100);
+        assert_eq!(super::align(area(), size, Align::TopLeft), PhysicalPosition::new(100, 50));
+        assert_eq!(super::align(area(), size, Align::Center), PhysicalPosition::new(500, 400));
+        assert_eq!(super::align(area(), size, Align::Right), PhysicalPosition::new(900, 400));
+        assert_eq!(super::align(area(), size, Align::BottomRight), PhysicalPosition::new(900, 750));
+
+        // Too large windows are kept at the top-left corner.
+        let size = PhysicalSize::new(2000, 2000);
+        assert_eq!(super::align(area(), size, Align::BottomRight), PhysicalPosition::new(100, 50));
+    }
+
+    #[test]
+    fn cascade() {
+        let size = PhysicalSize::new(200, 100);
+        let from = PhysicalPosition::new(100, 50);
+        assert_eq!(super::cascade(area(), from, size, 2.0), PhysicalPosition::new(164, 114));
+
+        // Starts over when reaching the bottom of the area.
+        let from = PhysicalPosition::new(300, 760);
+        assert_eq!(super::cascade(area(), from, size, 1.0), PhysicalPosition::new(100, 50));
+    }
+
+    #[test]
+    fn relative_to() {
+        let size = PhysicalSize::new(200, 100);
+        let position = LogicalPosition::new(10.0, 20.0);
+        assert_eq!(
+            super::relative_to(area(), position, size, 2.0, LayoutDirection::LeftToRight),
+            PhysicalPosition::new(120, 90)
+        );
+        assert_eq!(
+            super::relative_to(area(), position, size, 2.0, LayoutDirection::RightToLeft),
+            PhysicalPosition::new(880, 90)
+        );
+    }
+}
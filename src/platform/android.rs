@@ -57,6 +57,20 @@
 //!
 //! For more details, refer to these `android-activity` [example applications](https://github.com/rust-mobile/android-activity/tree/main/examples).
 //!
+//! ## Gamepads
+//!
+//! With either base class, gamepads and joysticks are reported with
+//! [`ApplicationHandler::device_event()`]: the sticks, triggers and directional pad as
+//! [`DeviceEvent::Motion`], with an [`Axis`] converted to an integer as the axis, and the buttons
+//! as [`DeviceEvent::Button`], with a [`Keycode`] converted to an integer as the button. The
+//! buttons are still reported as keyboard input too.
+//!
+//! [`ApplicationHandler::device_event()`]: crate::application::ApplicationHandler::device_event
+//! [`DeviceEvent::Motion`]: crate::event::DeviceEvent::Motion
+//! [`DeviceEvent::Button`]: crate::event::DeviceEvent::Button
+//! [`Axis`]: self::activity::input::Axis
+//! [`Keycode`]: self::activity::input::Keycode
+//!
 //! ## Converting from `ndk-glue` to `android-activity`
 //!
 //! If your application is currently based on `NativeActivity` via the `ndk-glue` crate and building
@@ -71,7 +85,7 @@
 //!    event loop (as shown above).
 
 use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
-use crate::window::{Window, WindowAttributes};
+use crate::window::{AudioSessionCategory, Window, WindowAttributes};
 
 use self::activity::{AndroidApp, ConfigurationRef, Rect};
 
@@ -103,9 +117,27 @@ impl WindowExtAndroid for Window {
 impl ActiveEventLoopExtAndroid for ActiveEventLoop {}
 
 /// Additional methods on [`WindowAttributes`] that are specific to Android.
-pub trait WindowAttributesExtAndroid {}
+pub trait WindowAttributesExtAndroid {
+    /// Sets how the audio of the application is used, so that pausing the activity, e.g. for a
+    /// phone call, emits [`ApplicationHandler::audio_interrupted()`], and resuming it
+    /// [`ApplicationHandler::audio_resumed()`].
+    ///
+    /// Android has no audio session, the audio focus is requested by the application with its
+    /// audio API, e.g. with the usage of an AAudio stream. Observing the audio focus needs a Java
+    /// listener, so the category isn't applied otherwise.
+    ///
+    /// [`ApplicationHandler::audio_interrupted()`]: crate::application::ApplicationHandler::audio_interrupted
+    /// [`ApplicationHandler::audio_resumed()`]: crate::application::ApplicationHandler::audio_resumed
+    fn with_audio_session_category(self, category: AudioSessionCategory) -> Self;
+}
 
-impl WindowAttributesExtAndroid for WindowAttributes {}
+impl WindowAttributesExtAndroid for WindowAttributes {
+    #[inline]
+    fn with_audio_session_category(mut self, category: AudioSessionCategory) -> Self {
+        self.platform_specific.audio_session_category = Some(category);
+        self
+    }
+}
 
 pub trait EventLoopBuilderExtAndroid {
     /// Associates the `AndroidApp` that was passed to `android_main()` with the event loop
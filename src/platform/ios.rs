@@ -68,7 +68,7 @@ use std::os::raw::c_void;
 
 use crate::event_loop::EventLoop;
 use crate::monitor::{MonitorHandle, VideoModeHandle};
-use crate::window::{Window, WindowAttributes};
+use crate::window::{AudioSessionCategory, Window, WindowAttributes};
 
 /// Additional methods on [`EventLoop`] that are specific to iOS.
 pub trait EventLoopExtIOS {
@@ -115,6 +115,9 @@ pub trait WindowExtIOS {
     /// This only has an effect on iOS 11.0+.
     fn set_prefers_home_indicator_hidden(&self, hidden: bool);
 
+    /// Returns whether the [`Window`] prefers the home indicator hidden.
+    fn prefers_home_indicator_hidden(&self) -> bool;
+
     /// Sets the screen edges for which the system gestures will take a lower priority than the
     /// application's touch handling.
     ///
@@ -123,9 +126,17 @@ pub trait WindowExtIOS {
     /// and then calls
     /// [`-[UIViewController setNeedsUpdateOfScreenEdgesDeferringSystemGestures]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/2887507-setneedsupdateofscreenedgesdefer?language=objc).
     ///
+    /// Swipes from these edges, which the system then ignores, are reported with
+    /// [`WindowEvent::SystemGestureDeferred`].
+    ///
     /// This only has an effect on iOS 11.0+.
+    ///
+    /// [`WindowEvent::SystemGestureDeferred`]: crate::event::WindowEvent::SystemGestureDeferred
     fn set_preferred_screen_edges_deferring_system_gestures(&self, edges: ScreenEdge);
 
+    /// Returns the screen edges for which the system gestures are deferred.
+    fn preferred_screen_edges_deferring_system_gestures(&self) -> ScreenEdge;
+
     /// Sets whether the [`Window`] prefers the status bar hidden.
     ///
     /// The default is to prefer showing the status bar.
@@ -184,39 +195,47 @@ pub trait WindowExtIOS {
 impl WindowExtIOS for Window {
     #[inline]
     fn set_scale_factor(&self, scale_factor: f64) {
-        self.window.maybe_queue_on_main(move |w| w.set_scale_factor(scale_factor))
+        self.queue_command(move |w| w.set_scale_factor(scale_factor))
     }
 
     #[inline]
     fn set_valid_orientations(&self, valid_orientations: ValidOrientations) {
-        self.window.maybe_queue_on_main(move |w| w.set_valid_orientations(valid_orientations))
+        self.queue_command(move |w| w.set_valid_orientations(valid_orientations))
     }
 
     #[inline]
     fn set_prefers_home_indicator_hidden(&self, hidden: bool) {
-        self.window.maybe_queue_on_main(move |w| w.set_prefers_home_indicator_hidden(hidden))
+        self.queue_command(move |w| w.set_prefers_home_indicator_hidden(hidden))
+    }
+
+    #[inline]
+    fn prefers_home_indicator_hidden(&self) -> bool {
+        self.window.maybe_wait_on_main(|w| w.prefers_home_indicator_hidden())
     }
 
     #[inline]
     fn set_preferred_screen_edges_deferring_system_gestures(&self, edges: ScreenEdge) {
-        self.window.maybe_queue_on_main(move |w| {
-            w.set_preferred_screen_edges_deferring_system_gestures(edges)
-        })
+        self.queue_command(move |w| w.set_preferred_screen_edges_deferring_system_gestures(edges))
+    }
+
+    #[inline]
+    fn preferred_screen_edges_deferring_system_gestures(&self) -> ScreenEdge {
+        self.window.maybe_wait_on_main(|w| w.preferred_screen_edges_deferring_system_gestures())
     }
 
     #[inline]
     fn set_prefers_status_bar_hidden(&self, hidden: bool) {
-        self.window.maybe_queue_on_main(move |w| w.set_prefers_status_bar_hidden(hidden))
+        self.queue_command(move |w| w.set_prefers_status_bar_hidden(hidden))
     }
 
     #[inline]
     fn set_preferred_status_bar_style(&self, status_bar_style: StatusBarStyle) {
-        self.window.maybe_queue_on_main(move |w| w.set_preferred_status_bar_style(status_bar_style))
+        self.queue_command(move |w| w.set_preferred_status_bar_style(status_bar_style))
     }
 
     #[inline]
     fn recognize_pinch_gesture(&self, should_recognize: bool) {
-        self.window.maybe_queue_on_main(move |w| w.recognize_pinch_gesture(should_recognize));
+        self.queue_command(move |w| w.recognize_pinch_gesture(should_recognize));
     }
 
     #[inline]
@@ -226,7 +245,7 @@ impl WindowExtIOS for Window {
         minimum_number_of_touches: u8,
         maximum_number_of_touches: u8,
     ) {
-        self.window.maybe_queue_on_main(move |w| {
+        self.queue_command(move |w| {
             w.recognize_pan_gesture(
                 should_recognize,
                 minimum_number_of_touches,
@@ -237,12 +256,12 @@ impl WindowExtIOS for Window {
 
     #[inline]
     fn recognize_doubletap_gesture(&self, should_recognize: bool) {
-        self.window.maybe_queue_on_main(move |w| w.recognize_doubletap_gesture(should_recognize));
+        self.queue_command(move |w| w.recognize_doubletap_gesture(should_recognize));
     }
 
     #[inline]
     fn recognize_rotation_gesture(&self, should_recognize: bool) {
-        self.window.maybe_queue_on_main(move |w| w.recognize_rotation_gesture(should_recognize));
+        self.queue_command(move |w| w.recognize_rotation_gesture(should_recognize));
     }
 }
 
@@ -299,6 +318,17 @@ pub trait WindowAttributesExtIOS {
     /// This sets the initial value returned by
     /// [`-[UIViewController preferredStatusBarStyle]`](https://developer.apple.com/documentation/uikit/uiviewcontroller/1621416-preferredstatusbarstyle?language=objc),
     fn with_preferred_status_bar_style(self, status_bar_style: StatusBarStyle) -> Self;
+
+    /// Sets the category of the [`AVAudioSession`] of the application, and activates it, when the
+    /// [`Window`] is created.
+    ///
+    /// The default is to leave the audio session alone, which plays audio like
+    /// [`AudioSessionCategory::Ambient`] once the application uses it. Interruptions of the audio
+    /// session are reported with [`ApplicationHandler::audio_interrupted()`] either way.
+    ///
+    /// [`AVAudioSession`]: https://developer.apple.com/documentation/avfaudio/avaudiosession?language=objc
+    /// [`ApplicationHandler::audio_interrupted()`]: crate::application::ApplicationHandler::audio_interrupted
+    fn with_audio_session_category(self, category: AudioSessionCategory) -> Self;
 }
 
 impl WindowAttributesExtIOS for WindowAttributes {
@@ -337,6 +367,12 @@ impl WindowAttributesExtIOS for WindowAttributes {
         self.platform_specific.preferred_status_bar_style = status_bar_style;
         self
     }
+
+    #[inline]
+    fn with_audio_session_category(mut self, category: AudioSessionCategory) -> Self {
+        self.platform_specific.audio_session_category = Some(category);
+        self
+    }
 }
 
 /// Additional methods on [`MonitorHandle`] that are specific to iOS.
@@ -14,11 +14,15 @@
 //! [#2051]: https://github.com/rust-windowing/winit/issues/2051
 //! [#2087]: https://github.com/rust-windowing/winit/issues/2087
 
+use std::fmt;
 use std::os::raw::c_void;
+use std::ptr::NonNull;
+use std::sync::Arc;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::dpi::LogicalPosition;
 use crate::event_loop::{ActiveEventLoop, EventLoopBuilder};
 use crate::monitor::MonitorHandle;
 use crate::window::{Window, WindowAttributes};
@@ -94,6 +98,49 @@ pub trait WindowExtMacOS {
 
     /// Getter for the [`WindowExtMacOS::set_option_as_alt`].
     fn option_as_alt(&self) -> OptionAsAlt;
+
+    /// Change the appearance of the titlebar.
+    ///
+    /// See [`TitlebarStyle`] for the available options.
+    fn set_titlebar_style(&self, style: TitlebarStyle);
+
+    /// Getter for the [`WindowExtMacOS::set_titlebar_style`].
+    fn titlebar_style(&self) -> TitlebarStyle;
+
+    /// Enables secure event input while the window is focused.
+    ///
+    /// This wraps `EnableSecureEventInput`, which hides keystrokes from other applications,
+    /// including event taps used by keyloggers, and limits input methods to Roman ones. It is
+    /// also enabled for [`ImePurpose::Password`], see [`Window::set_ime_purpose`].
+    ///
+    /// The input is only secured while the window is the key window, since it affects the
+    /// whole system.
+    ///
+    /// [`ImePurpose::Password`]: crate::window::ImePurpose::Password
+    /// [`Window::set_ime_purpose`]: crate::window::Window::set_ime_purpose
+    fn set_secure_input(&self, enabled: bool);
+
+    /// Makes the view of the window backed by a `CAMetalLayer` owned by winit, and configures it.
+    ///
+    /// Winit keeps the `contentsScale` and `drawableSize` of the layer in sync with the size of
+    /// the view and the scale factor of the monitor the window is on. Renderers looking up the
+    /// layer of the view, e.g. through `raw-window-metal`, use this layer instead of creating
+    /// their own.
+    fn set_layer_config(&self, config: CAMetalLayerConfig);
+
+    /// Returns a pointer to the `CAMetalLayer` created by [`WindowExtMacOS::set_layer_config`].
+    ///
+    /// The layer lives as long as the window.
+    fn metal_layer(&self) -> Option<*mut c_void>;
+
+    /// Sets the items shown in the Touch Bar while the window is the key window, replacing the
+    /// previous ones.
+    ///
+    /// Using the items is reported with [`WindowEvent::TouchBar`]. An empty [`TouchBarSpec`]
+    /// removes the items, the Touch Bar then showing the controls of the system.
+    ///
+    /// [`WindowEvent::TouchBar`]: crate::event::WindowEvent::TouchBar
+    fn set_touch_bar(&self, spec: TouchBarSpec);
 }
 
 impl WindowExtMacOS for Window {
@@ -114,7 +161,7 @@ impl WindowExtMacOS for Window {
 
     #[inline]
     fn set_has_shadow(&self, has_shadow: bool) {
-        self.window.maybe_queue_on_main(move |w| w.set_has_shadow(has_shadow))
+        self.queue_command(move |w| w.set_has_shadow(has_shadow))
     }
 
     #[inline]
@@ -129,17 +176,17 @@ impl WindowExtMacOS for Window {
 
     #[inline]
     fn select_next_tab(&self) {
-        self.window.maybe_queue_on_main(|w| w.select_next_tab())
+        self.queue_command(|w| w.select_next_tab())
     }
 
     #[inline]
     fn select_previous_tab(&self) {
-        self.window.maybe_queue_on_main(|w| w.select_previous_tab())
+        self.queue_command(|w| w.select_previous_tab())
     }
 
     #[inline]
     fn select_tab_at_index(&self, index: usize) {
-        self.window.maybe_queue_on_main(move |w| w.select_tab_at_index(index))
+        self.queue_command(move |w| w.select_tab_at_index(index))
     }
 
     #[inline]
@@ -154,18 +201,50 @@ impl WindowExtMacOS for Window {
 
     #[inline]
     fn set_document_edited(&self, edited: bool) {
-        self.window.maybe_queue_on_main(move |w| w.set_document_edited(edited))
+        self.queue_command(move |w| w.set_document_edited(edited))
     }
 
     #[inline]
     fn set_option_as_alt(&self, option_as_alt: OptionAsAlt) {
-        self.window.maybe_queue_on_main(move |w| w.set_option_as_alt(option_as_alt))
+        self.queue_command(move |w| w.set_option_as_alt(option_as_alt))
     }
 
     #[inline]
     fn option_as_alt(&self) -> OptionAsAlt {
         self.window.maybe_wait_on_main(|w| w.option_as_alt())
     }
+
+    #[inline]
+    fn set_titlebar_style(&self, style: TitlebarStyle) {
+        self.queue_command(move |w| w.set_titlebar_style(style))
+    }
+
+    #[inline]
+    fn titlebar_style(&self) -> TitlebarStyle {
+        self.window.maybe_wait_on_main(|w| w.titlebar_style())
+    }
+
+    #[inline]
+    fn set_secure_input(&self, enabled: bool) {
+        self.queue_command(move |w| w.set_secure_input(enabled))
+    }
+
+    #[inline]
+    fn set_layer_config(&self, config: CAMetalLayerConfig) {
+        self.queue_command(move |w| w.set_layer_config(config))
+    }
+
+    #[inline]
+    fn metal_layer(&self) -> Option<*mut c_void> {
+        // Pointers aren't `Send`.
+        let layer = self.window.maybe_wait_on_main(|w| w.metal_layer().map(|layer| layer as usize));
+        layer.map(|layer| layer as *mut c_void)
+    }
+
+    #[inline]
+    fn set_touch_bar(&self, spec: TouchBarSpec) {
+        self.queue_command(move |w| w.set_touch_bar(spec))
+    }
 }
 
 /// Corresponds to `NSApplicationActivationPolicy`.
@@ -191,6 +270,9 @@ pub enum ActivationPolicy {
 /// - `with_titlebar_hidden`
 /// - `with_titlebar_buttons_hidden`
 /// - `with_fullsize_content_view`
+/// - `with_toolbar_style`
+/// - `with_titlebar_accessory`
+/// - `with_traffic_light_inset`
 pub trait WindowAttributesExtMacOS {
     /// Enables click-and-drag behavior for the entire window, not just the titlebar.
     fn with_movable_by_window_background(self, movable_by_window_background: bool) -> Self;
@@ -216,6 +298,24 @@ pub trait WindowAttributesExtMacOS {
     ///
     /// See [`WindowExtMacOS::set_option_as_alt`] for details on what this means if set.
     fn with_option_as_alt(self, option_as_alt: OptionAsAlt) -> Self;
+    /// Sets the style of the window's toolbar.
+    ///
+    /// Using anything other than [`ToolbarStyle::Automatic`] installs an empty toolbar if the
+    /// window doesn't have one, since the style only has an effect when a toolbar is present.
+    /// Use [`ToolbarStyle::Unified`] to merge the titlebar and the toolbar into a single bar.
+    ///
+    /// Requires macOS 11.0 or later, ignored otherwise.
+    fn with_toolbar_style(self, toolbar_style: ToolbarStyle) -> Self;
+    /// Adds a custom view to the titlebar.
+    ///
+    /// Can be called multiple times, the accessories are added in order.
+    fn with_titlebar_accessory<A: TitlebarAccessory + 'static>(self, accessory: A) -> Self;
+    /// Moves the close, minimize and zoom buttons, also known as the traffic lights.
+    ///
+    /// The inset is the distance in points from the top left corner of the window to the top
+    /// left corner of the close button. The position is kept across resizes and fullscreen
+    /// transitions.
+    fn with_traffic_light_inset(self, inset: LogicalPosition<f64>) -> Self;
 }
 
 impl WindowAttributesExtMacOS for WindowAttributes {
@@ -284,6 +384,24 @@ impl WindowAttributesExtMacOS for WindowAttributes {
         self.platform_specific.option_as_alt = option_as_alt;
         self
     }
+
+    #[inline]
+    fn with_toolbar_style(mut self, toolbar_style: ToolbarStyle) -> Self {
+        self.platform_specific.toolbar_style = toolbar_style;
+        self
+    }
+
+    #[inline]
+    fn with_titlebar_accessory<A: TitlebarAccessory + 'static>(mut self, accessory: A) -> Self {
+        self.platform_specific.titlebar_accessories.push(Arc::new(accessory));
+        self
+    }
+
+    #[inline]
+    fn with_traffic_light_inset(mut self, inset: LogicalPosition<f64>) -> Self {
+        self.platform_specific.traffic_light_inset = Some(inset);
+        self
+    }
 }
 
 pub trait EventLoopBuilderExtMacOS {
@@ -432,3 +550,162 @@ pub enum OptionAsAlt {
     #[default]
     None,
 }
+
+/// Corresponds to `NSWindowToolbarStyle`.
+///
+/// The default is [`ToolbarStyle::Automatic`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ToolbarStyle {
+    /// Corresponds to `NSWindowToolbarStyleAutomatic`.
+    #[default]
+    Automatic,
+
+    /// Corresponds to `NSWindowToolbarStyleExpanded`.
+    ///
+    /// The toolbar is shown below the titlebar.
+    Expanded,
+
+    /// Corresponds to `NSWindowToolbarStylePreference`.
+    ///
+    /// Like [`ToolbarStyle::Expanded`], but with the items centered, as in preference windows.
+    Preference,
+
+    /// Corresponds to `NSWindowToolbarStyleUnified`.
+    ///
+    /// The titlebar and the toolbar are merged into a single bar.
+    Unified,
+
+    /// Corresponds to `NSWindowToolbarStyleUnifiedCompact`.
+    ///
+    /// Like [`ToolbarStyle::Unified`], but with reduced margins.
+    UnifiedCompact,
+}
+
+/// The appearance of the window's titlebar.
+///
+/// See [`WindowExtMacOS::set_titlebar_style`]. The fields correspond to the
+/// [`WindowAttributesExtMacOS`] methods of the same name.
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct TitlebarStyle {
+    /// See [`WindowAttributesExtMacOS::with_titlebar_transparent`].
+    pub transparent: bool,
+
+    /// See [`WindowAttributesExtMacOS::with_title_hidden`].
+    pub title_hidden: bool,
+
+    /// See [`WindowAttributesExtMacOS::with_fullsize_content_view`].
+    pub fullsize_content_view: bool,
+
+    /// See [`WindowAttributesExtMacOS::with_toolbar_style`].
+    pub toolbar_style: ToolbarStyle,
+
+    /// See [`WindowAttributesExtMacOS::with_traffic_light_inset`].
+    ///
+    /// `None` leaves the buttons where AppKit puts them.
+    pub traffic_light_inset: Option<LogicalPosition<f64>>,
+}
+
+/// The configuration of the `CAMetalLayer` of a window.
+///
+/// See [`WindowExtMacOS::set_layer_config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CAMetalLayerConfig {
+    /// Whether presenting waits for the vertical blank, corresponding to `displaySyncEnabled`.
+    ///
+    /// The default is `true`.
+    pub display_sync: bool,
+
+    /// Whether the layer can show colors brighter than white on displays supporting it,
+    /// corresponding to `wantsExtendedDynamicRangeContent`.
+    ///
+    /// The default is `false`.
+    pub wants_extended_dynamic_range: bool,
+
+    /// The number of drawables in the swap chain, corresponding to `maximumDrawableCount`.
+    ///
+    /// Must be 2 or 3, the default is 3.
+    pub maximum_drawable_count: u8,
+}
+
+impl Default for CAMetalLayerConfig {
+    fn default() -> Self {
+        Self { display_sync: true, wants_extended_dynamic_range: false, maximum_drawable_count: 3 }
+    }
+}
+
+/// Where a [`TitlebarAccessory`] is placed in the titlebar.
+///
+/// Corresponds to the `layoutAttribute` of `NSTitlebarAccessoryViewController`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TitlebarAccessoryPosition {
+    /// The view is placed at the trailing edge of the titlebar.
+    #[default]
+    Trailing,
+
+    /// The view is placed at the leading edge of the titlebar, after the window buttons.
+    Leading,
+
+    /// The view is placed below the titlebar, spanning the whole width of the window.
+    Bottom,
+}
+
+/// A custom view in the window's titlebar.
+///
+/// See [`WindowAttributesExtMacOS::with_titlebar_accessory`].
+///
+/// # Safety
+///
+/// [`TitlebarAccessory::ns_view`] must return a valid pointer to an `NSView` that isn't
+/// installed in another view hierarchy.
+pub unsafe trait TitlebarAccessory: fmt::Debug + Send + Sync {
+    /// Returns a pointer to the `NSView` to put in the titlebar.
+    ///
+    /// This is called once on the main thread when the window is created. The view is retained
+    /// by the window.
+    fn ns_view(&self) -> NonNull<c_void>;
+
+    /// Where the view should be placed.
+    ///
+    /// The default is [`TitlebarAccessoryPosition::Trailing`].
+    fn position(&self) -> TitlebarAccessoryPosition {
+        TitlebarAccessoryPosition::Trailing
+    }
+}
+
+/// The items of a window's Touch Bar.
+///
+/// See [`WindowExtMacOS::set_touch_bar`].
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TouchBarSpec {
+    /// The items, from left to right.
+    pub items: Vec<TouchBarItem>,
+}
+
+impl TouchBarSpec {
+    /// Appends an item to the right of the previous ones.
+    #[inline]
+    pub fn with_item(mut self, item: TouchBarItem) -> Self {
+        self.items.push(item);
+        self
+    }
+}
+
+/// An item of the Touch Bar, see [`TouchBarSpec`].
+///
+/// The `id` is reported with the [`TouchBarEvent`]s of the item.
+///
+/// [`TouchBarEvent`]: crate::event::TouchBarEvent
+#[derive(Debug, Clone, PartialEq)]
+pub enum TouchBarItem {
+    /// A button with a title, reporting [`TouchBarEvent::ButtonPressed`].
+    ///
+    /// [`TouchBarEvent::ButtonPressed`]: crate::event::TouchBarEvent::ButtonPressed
+    Button { id: u64, title: String },
+
+    /// A slider with a label, starting at `value` between `min` and `max`, reporting
+    /// [`TouchBarEvent::SliderChanged`] while it is moved.
+    ///
+    /// [`TouchBarEvent::SliderChanged`]: crate::event::TouchBarEvent::SliderChanged
+    Slider { id: u64, label: String, min: f64, max: f64, value: f64 },
+}
@@ -1,7 +1,9 @@
-use std::time::Duration;
+#[cfg(any(x11_platform, wayland_platform))]
+use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
+use std::time::{Duration, Instant};
 
 use crate::application::ApplicationHandler;
-use crate::event_loop::EventLoop;
+use crate::event_loop::{exit, ControlFlow, EventLoop, WakeUpHandler};
 
 /// Additional methods on [`EventLoop`] for pumping events within an external event loop
 pub trait EventLoopExtPumpEvents {
@@ -119,7 +121,10 @@ impl<T> EventLoopExtPumpEvents for EventLoop<T> {
         timeout: Option<Duration>,
         app: &mut A,
     ) -> PumpStatus {
-        self.event_loop.pump_app_events(timeout, app)
+        let status = self
+            .event_loop
+            .pump_app_events(timeout, &mut WakeUpHandler::new(app, self.create_proxy()));
+        exit::finish_pump(status)
     }
 }
 
@@ -130,3 +135,94 @@ pub enum PumpStatus {
     /// Exit external loop.
     Exit(i32),
 }
+
+/// An [`EventLoop`] together with the application it drives, for letting an external event loop
+/// like [`calloop`], [`mio`] or [`tokio`] drive winit, instead of running winit as the main loop.
+///
+/// The external loop calls [`EventLoopSource::dispatch()`] when the event loop has new events,
+/// and at the latest after [`EventLoopSource::timeout()`], which follows the [`ControlFlow`] of
+/// the application. It's built on [`EventLoopExtPumpEvents::pump_app_events()`], so the same
+/// caveats apply.
+///
+/// On X11 and Wayland, new events are signaled by the file descriptor of the event loop becoming
+/// readable, which can be registered into the external loop:
+///
+/// - **[`calloop`]:** With a `Generic` source of a clone of the file descriptor.
+/// - **[`mio`]:** With a `SourceFd` of the raw file descriptor.
+/// - **[`tokio`]:** By wrapping the source itself in an `AsyncFd`.
+///
+/// Other platforms have no file descriptor to wait on, so the external loop has to dispatch
+/// regularly, e.g. once per frame, to notice new events.
+///
+/// [`calloop`]: https://crates.io/crates/calloop
+/// [`mio`]: https://crates.io/crates/mio
+/// [`tokio`]: https://crates.io/crates/tokio
+/// [`ControlFlow`]: crate::event_loop::ControlFlow
+pub struct EventLoopSource<A, T: 'static = ()> {
+    event_loop: EventLoop<T>,
+    app: A,
+}
+
+impl<A: ApplicationHandler<T>, T: 'static> EventLoopSource<A, T> {
+    /// Creates a source driving `app` with `event_loop`.
+    pub fn new(event_loop: EventLoop<T>, app: A) -> Self {
+        Self { event_loop, app }
+    }
+
+    /// Dispatches the pending events to the application, without blocking.
+    pub fn dispatch(&mut self) -> PumpStatus {
+        self.event_loop.pump_app_events(Some(Duration::ZERO), &mut self.app)
+    }
+
+    /// Returns how long the external loop may wait for new events before calling
+    /// [`EventLoopSource::dispatch()`] again, or `None` if it may wait indefinitely.
+    pub fn timeout(&self) -> Option<Duration> {
+        let event_loop = self.event_loop.event_loop.window_target();
+        if event_loop.exiting() {
+            return Some(Duration::ZERO);
+        }
+        match event_loop.control_flow() {
+            ControlFlow::Poll => Some(Duration::ZERO),
+            ControlFlow::Wait => None,
+            ControlFlow::WaitUntil(deadline) => {
+                Some(deadline.saturating_duration_since(Instant::now()))
+            },
+        }
+    }
+
+    /// Returns the event loop.
+    pub fn event_loop(&self) -> &EventLoop<T> {
+        &self.event_loop
+    }
+
+    /// Returns the application.
+    pub fn app(&self) -> &A {
+        &self.app
+    }
+
+    /// Returns the application mutably.
+    pub fn app_mut(&mut self) -> &mut A {
+        &mut self.app
+    }
+
+    /// Returns the event loop and the application.
+    pub fn into_inner(self) -> (EventLoop<T>, A) {
+        (self.event_loop, self.app)
+    }
+}
+
+#[cfg(any(x11_platform, wayland_platform))]
+impl<A, T> AsFd for EventLoopSource<A, T> {
+    /// Get the `fd` of the event loop, which becomes readable when it has new events.
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.event_loop.as_fd()
+    }
+}
+
+#[cfg(any(x11_platform, wayland_platform))]
+impl<A, T> AsRawFd for EventLoopSource<A, T> {
+    /// Get the raw `fd` of the event loop, which becomes readable when it has new events.
+    fn as_raw_fd(&self) -> RawFd {
+        self.event_loop.as_raw_fd()
+    }
+}
@@ -1,6 +1,6 @@
 use crate::application::ApplicationHandler;
 use crate::error::EventLoopError;
-use crate::event_loop::{ActiveEventLoop, EventLoop};
+use crate::event_loop::{exit, ActiveEventLoop, EventLoop, WakeUpHandler};
 
 #[cfg(doc)]
 use crate::{platform::pump_events::EventLoopExtPumpEvents, window::Window};
@@ -72,7 +72,8 @@ impl<T> EventLoopExtRunOnDemand for EventLoop<T> {
         app: &mut A,
     ) -> Result<(), EventLoopError> {
         self.event_loop.window_target().clear_exit();
-        self.event_loop.run_app_on_demand(app)
+        let mut handler = WakeUpHandler::new(app, self.create_proxy());
+        exit::finish_run(self.event_loop.run_app_on_demand(&mut handler))
     }
 }
 
@@ -0,0 +1,107 @@
+//! # Unix
+//!
+//! Functionality shared by the backends of Unix systems, X11, Wayland and macOS.
+
+use std::os::fd::BorrowedFd;
+
+use crate::error::ExternalError;
+use crate::event::Signal;
+use crate::event_loop::{ActiveEventLoop, EventLoopBuilder};
+
+/// The readiness of a file descriptor to watch with [`ActiveEventLoopExtUnix::register_fd()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interest {
+    /// Whether data can be read without blocking.
+    pub readable: bool,
+    /// Whether data can be written without blocking.
+    pub writable: bool,
+}
+
+impl Interest {
+    /// Watch whether the file descriptor is readable.
+    pub const READ: Self = Self { readable: true, writable: false };
+    /// Watch whether the file descriptor is writable.
+    pub const WRITE: Self = Self { readable: false, writable: true };
+    /// Watch whether the file descriptor is readable or writable.
+    pub const BOTH: Self = Self { readable: true, writable: true };
+}
+
+/// Additional methods on [`ActiveEventLoop`] that are specific to Unix.
+pub trait ActiveEventLoopExtUnix {
+    /// Watch a file descriptor, like a socket, an `inotify` instance or a `timerfd`, emitting
+    /// [`ApplicationHandler::fd_ready()`] with `token` when it's ready for `interest`.
+    ///
+    /// This saves a thread waking up the event loop for each file descriptor. Registering another
+    /// file descriptor with the same token replaces the previous one.
+    ///
+    /// # Safety
+    ///
+    /// The event loop keeps using the file descriptor after this returns, so it must stay open
+    /// until it's unregistered with [`unregister_fd()`][Self::unregister_fd], registered again
+    /// with another file descriptor for the same token, or the event loop exited.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** Watched by the `epoll` or `kqueue` instance of the event loop.
+    /// - **macOS:** Watched by the main run loop with a `CFFileDescriptor`, which doesn't report
+    ///   errors for file descriptors that can't be watched.
+    ///
+    /// [`ApplicationHandler::fd_ready()`]: crate::application::ApplicationHandler::fd_ready
+    unsafe fn register_fd(
+        &self,
+        fd: BorrowedFd<'_>,
+        interest: Interest,
+        token: u64,
+    ) -> Result<(), ExternalError>;
+
+    /// Stop watching the file descriptor registered with `token`, if any.
+    fn unregister_fd(&self, token: u64);
+}
+
+impl ActiveEventLoopExtUnix for ActiveEventLoop {
+    #[inline]
+    unsafe fn register_fd(
+        &self,
+        fd: BorrowedFd<'_>,
+        interest: Interest,
+        token: u64,
+    ) -> Result<(), ExternalError> {
+        self.p.register_fd(fd, interest, token)
+    }
+
+    #[inline]
+    fn unregister_fd(&self, token: u64) {
+        self.p.unregister_fd(token)
+    }
+}
+
+/// Additional methods on [`EventLoopBuilder`] that are specific to Unix.
+pub trait EventLoopBuilderExtUnix {
+    /// Receive these signals with [`ApplicationHandler::signal()`], instead of their default
+    /// action, which usually terminates the process.
+    ///
+    /// The signals are received by the event loop itself, so Ctrl+C can be handled by exiting the
+    /// event loop, without a signal handler on another thread racing the shutdown. Signals for
+    /// which the application installed a handler are left alone. By default, no signals are
+    /// received.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** The signals are blocked on the thread of the event loop, and threads
+    ///   spawned from it afterwards, and read with `signalfd`. Processes with threads started
+    ///   before can still be interrupted by them. Requesting [`Signal::Terminate`] replaces
+    ///   [`ApplicationHandler::terminating()`]. Only supported on Linux.
+    /// - **macOS:** The signals are ignored by the process and read with a `kqueue`.
+    ///
+    /// [`ApplicationHandler::signal()`]: crate::application::ApplicationHandler::signal
+    /// [`ApplicationHandler::terminating()`]: crate::application::ApplicationHandler::terminating
+    fn with_signals(&mut self, signals: &[Signal]) -> &mut Self;
+}
+
+impl<T> EventLoopBuilderExtUnix for EventLoopBuilder<T> {
+    #[inline]
+    fn with_signals(&mut self, signals: &[Signal]) -> &mut Self {
+        self.platform_specific.signals = signals.to_vec();
+        self
+    }
+}
@@ -15,7 +15,7 @@
 //! * `wayland-csd-adwaita-notitle`.
 use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
 use crate::monitor::MonitorHandle;
-use crate::window::{Window, WindowAttributes};
+use crate::window::{Region, Window, WindowAttributes};
 
 pub use crate::window::Theme;
 
@@ -72,9 +72,23 @@ impl<T> EventLoopBuilderExtWayland for EventLoopBuilder<T> {
 }
 
 /// Additional methods on [`Window`] that are specific to Wayland.
-pub trait WindowExtWayland {}
+pub trait WindowExtWayland {
+    /// Limits the cursor grab of [`Window::set_cursor_grab`] to regions of the window, or the
+    /// whole window with `None`.
+    ///
+    /// A locked cursor is only locked once it enters one of the regions, and a confined cursor
+    /// can't leave them. The regions are relative to the top-left corner of the surface, and
+    /// are updated with the next frame of the window.
+    fn set_cursor_grab_regions(&self, regions: Option<&[Region]>);
+}
 
-impl WindowExtWayland for Window {}
+impl WindowExtWayland for Window {
+    #[inline]
+    fn set_cursor_grab_regions(&self, regions: Option<&[Region]>) {
+        let regions = regions.map(<[Region]>::to_vec);
+        self.queue_command(move |w| w.set_cursor_grab_regions(regions))
+    }
+}
 
 /// Additional methods on [`WindowAttributes`] that are specific to Wayland.
 pub trait WindowAttributesExtWayland {
@@ -86,6 +100,16 @@ pub trait WindowAttributesExtWayland {
     /// For details about application ID conventions, see the
     /// [Desktop Entry Spec](https://specifications.freedesktop.org/desktop-entry-spec/desktop-entry-spec-latest.html#desktop-file-id)
     fn with_name(self, general: impl Into<String>, instance: impl Into<String>) -> Self;
+
+    /// Build window with the themed icon of the given name, as in the
+    /// [Icon Theme Spec](https://specifications.freedesktop.org/icon-theme-spec/latest/).
+    ///
+    /// Compositors may show it instead of the pixels set with
+    /// [`WindowAttributes::with_window_icon`], and show it if no such icon is set. It is kept when
+    /// the icon is changed with [`Window::set_window_icon`].
+    ///
+    /// Requires the compositor to support the xdg-toplevel-icon protocol.
+    fn with_icon_name(self, name: impl Into<String>) -> Self;
 }
 
 impl WindowAttributesExtWayland for WindowAttributes {
@@ -95,6 +119,12 @@ impl WindowAttributesExtWayland for WindowAttributes {
             Some(crate::platform_impl::ApplicationName::new(general.into(), instance.into()));
         self
     }
+
+    #[inline]
+    fn with_icon_name(mut self, name: impl Into<String>) -> Self {
+        self.platform_specific.wayland.icon_name = Some(name.into());
+        self
+    }
 }
 
 /// Additional methods on `MonitorHandle` that are specific to Wayland.
@@ -23,7 +23,6 @@
 //! ## CSS properties
 //!
 //! It is recommended **not** to apply certain CSS properties to the canvas:
-//! - [`transform`](https://developer.mozilla.org/en-US/docs/Web/CSS/transform)
 //! - [`border`](https://developer.mozilla.org/en-US/docs/Web/CSS/border)
 //! - [`padding`](https://developer.mozilla.org/en-US/docs/Web/CSS/padding)
 //!
@@ -34,6 +33,12 @@
 //!   [`WindowEvent::Touch`].
 //! - [`Window::set_outer_position()`]
 //!
+//! A CSS [`transform`](https://developer.mozilla.org/en-US/docs/Web/CSS/transform) applied to the
+//! canvas or one of its ancestors doesn't change the size of the canvas, and positions like
+//! [`WindowEvent::CursorMoved`] are reported in the untransformed coordinate space of the canvas.
+//! Use [`Window::coordinate_transform()`] to convert between them and coordinates relative to the
+//! viewport, which also accounts for the page zoom.
+//!
 //! [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
 //! [`Window::(set_)inner_size()`]: crate::window::Window::inner_size
 //! [`WindowEvent::Occluded`]: crate::event::WindowEvent::Occluded
@@ -42,6 +47,7 @@
 //! [`WindowEvent::CursorLeft`]: crate::event::WindowEvent::CursorLeft
 //! [`WindowEvent::Touch`]: crate::event::WindowEvent::Touch
 //! [`Window::set_outer_position()`]: crate::window::Window::set_outer_position
+//! [`Window::coordinate_transform()`]: crate::window::Window::coordinate_transform
 
 use std::error::Error;
 use std::fmt::{self, Display, Formatter};
@@ -51,25 +57,61 @@ use std::task::{Context, Poll};
 use std::time::Duration;
 
 #[cfg(web_platform)]
-use web_sys::HtmlCanvasElement;
+use wasm_bindgen::JsValue;
+#[cfg(web_platform)]
+use web_sys::{HtmlCanvasElement, HtmlElement};
 
 use crate::application::ApplicationHandler;
 use crate::cursor::CustomCursorSource;
-use crate::event_loop::{ActiveEventLoop, EventLoop};
+use crate::error::ExternalError;
+use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy, WakeUpHandler};
+use crate::monitor::Rect;
 #[cfg(web_platform)]
 use crate::platform_impl::CustomCursorFuture as PlatformCustomCursorFuture;
+#[cfg(web_platform)]
+use crate::platform_impl::PermissionFuture as PlatformPermissionFuture;
 use crate::platform_impl::PlatformCustomCursorSource;
+#[cfg(web_platform)]
+use crate::platform_impl::PlatformJsEventSource;
 use crate::window::{CustomCursor, Window, WindowAttributes};
 
 #[cfg(not(web_platform))]
 #[doc(hidden)]
 pub struct HtmlCanvasElement;
 
+#[cfg(not(web_platform))]
+#[doc(hidden)]
+pub struct HtmlElement;
+
+#[cfg(not(web_platform))]
+#[doc(hidden)]
+pub struct JsValue;
+
 pub trait WindowExtWebSys {
     /// Only returns the canvas if called from inside the window context (the
     /// main thread).
     fn canvas(&self) -> Option<HtmlCanvasElement>;
 
+    /// Returns an element positioned exactly over the canvas, to render HTML content like text
+    /// inputs or menus above it. Only returns the element if called from inside the window context
+    /// (the main thread).
+    ///
+    /// The element is created on first use and inserted as the next sibling of the canvas. It is
+    /// kept over the canvas when the canvas is resized, the browser window is resized, or
+    /// fullscreen is entered or exited, and is removed when the [`Window`] is dropped. Other
+    /// changes that move the canvas without resizing it are picked up the next time this is
+    /// called.
+    ///
+    /// The element itself doesn't receive pointer events, so they reach the canvas below it. Set
+    /// `pointer-events: auto` on the elements added to it that should be interactive.
+    ///
+    /// While the canvas is fullscreen, the element is shown as a
+    /// [popover](https://developer.mozilla.org/en-US/docs/Web/API/Popover_API), as nothing else
+    /// can be rendered above a fullscreen element. It is hidden in browsers without support for
+    /// popovers.
+    #[cfg_attr(not(web_platform), doc = "", doc = "[`HtmlElement`]: #only-available-on-wasm")]
+    fn overlay_container(&self) -> Option<HtmlElement>;
+
     /// Returns [`true`] if calling `event.preventDefault()` is enabled.
     ///
     /// See [`Window::set_prevent_default()`] for more details.
@@ -84,6 +126,49 @@ pub trait WindowExtWebSys {
     /// Some events are impossible to prevent. E.g. Firefox allows to access the native browser
     /// context menu with Shift+Rightclick.
     fn set_prevent_default(&self, prevent_default: bool);
+
+    /// Returns [`true`] if the menu of the browser is prevented on `contextmenu`.
+    ///
+    /// See [`set_prevent_context_menu()`](Self::set_prevent_context_menu) for more details.
+    fn prevent_context_menu(&self) -> bool;
+
+    /// Sets whether the menu of the browser should be prevented when a context menu is requested
+    /// on the canvas, independently of [`set_prevent_default()`](Self::set_prevent_default).
+    ///
+    /// [`WindowEvent::ContextMenuRequested`] is emitted either way, so the application can show
+    /// its own menu over the canvas while the menu of the browser stays available on the rest of
+    /// the page.
+    ///
+    /// Follows [`prevent_default()`](Self::prevent_default) until called.
+    ///
+    /// [`WindowEvent::ContextMenuRequested`]: crate::event::WindowEvent::ContextMenuRequested
+    fn set_prevent_context_menu(&self, prevent_context_menu: bool);
+
+    /// Emit [`WindowEvent::GraphicsContextLost`] when `device`, a
+    /// [`GPUDevice`](https://developer.mozilla.org/en-US/docs/Web/API/GPUDevice) rendering to the
+    /// canvas, is lost. Only takes effect if called from inside the window context (the main
+    /// thread).
+    ///
+    /// Only the last device passed is watched. Losing it by calling `GPUDevice.destroy()` isn't
+    /// reported. Unlike WebGL contexts, lost devices are never restored, so no
+    /// [`WindowEvent::GraphicsContextRestored`] follows; request a new device instead and pass it
+    /// here again.
+    ///
+    /// WebGL context loss is always reported and doesn't need this.
+    ///
+    /// [`WindowEvent::GraphicsContextLost`]: crate::event::WindowEvent::GraphicsContextLost
+    /// [`WindowEvent::GraphicsContextRestored`]: crate::event::WindowEvent::GraphicsContextRestored
+    #[cfg_attr(not(web_platform), doc = "", doc = "[`JsValue`]: #only-available-on-wasm")]
+    fn watch_gpu_device(&self, device: &JsValue);
+
+    /// Open the print dialog of the browser for the page containing the canvas.
+    ///
+    /// The dialog is opened after the current event is handled, so the application receives
+    /// [`WindowEvent::PrintRequested`] and can render a frame for printing before the page is
+    /// captured.
+    ///
+    /// [`WindowEvent::PrintRequested`]: crate::event::WindowEvent::PrintRequested
+    fn print(&self);
 }
 
 impl WindowExtWebSys for Window {
@@ -92,6 +177,10 @@ impl WindowExtWebSys for Window {
         self.window.canvas()
     }
 
+    fn overlay_container(&self) -> Option<HtmlElement> {
+        self.window.overlay_container()
+    }
+
     fn prevent_default(&self) -> bool {
         self.window.prevent_default()
     }
@@ -99,6 +188,22 @@ impl WindowExtWebSys for Window {
     fn set_prevent_default(&self, prevent_default: bool) {
         self.window.set_prevent_default(prevent_default)
     }
+
+    fn prevent_context_menu(&self) -> bool {
+        self.window.prevent_context_menu()
+    }
+
+    fn set_prevent_context_menu(&self, prevent_context_menu: bool) {
+        self.window.set_prevent_context_menu(prevent_context_menu)
+    }
+
+    fn watch_gpu_device(&self, device: &JsValue) {
+        self.window.watch_gpu_device(device)
+    }
+
+    fn print(&self) {
+        self.window.print()
+    }
 }
 
 pub trait WindowAttributesExtWebSys {
@@ -119,16 +224,34 @@ pub trait WindowAttributesExtWebSys {
     /// Enabled by default.
     fn with_prevent_default(self, prevent_default: bool) -> Self;
 
+    /// Sets whether the menu of the browser should be prevented when a context menu is requested
+    /// on the canvas.
+    ///
+    /// See [`WindowExtWebSys::set_prevent_context_menu()`] for more details.
+    ///
+    /// Follows [`with_prevent_default()`](Self::with_prevent_default) by default.
+    fn with_prevent_context_menu(self, prevent_context_menu: bool) -> Self;
+
     /// Whether the canvas should be focusable using the tab key. This is necessary to capture
     /// canvas keyboard events.
     ///
     /// Enabled by default.
+    #[deprecated = "use `WindowAttributes::with_focusable()` instead"]
     fn with_focusable(self, focusable: bool) -> Self;
 
     /// On window creation, append the canvas element to the web page if it isn't already.
     ///
     /// Disabled by default.
     fn with_append(self, append: bool) -> Self;
+
+    /// An element of the web page to show until [`Window::notify_first_frame_presented()`] is
+    /// called, e.g. a loading indicator, which is removed from the page afterwards.
+    ///
+    /// Only has an effect together with [`WindowAttributes::with_splash_screen()`].
+    ///
+    /// [`None`] by default.
+    #[cfg_attr(not(web_platform), doc = "", doc = "[`HtmlElement`]: #only-available-on-wasm")]
+    fn with_splash_element(self, element: Option<HtmlElement>) -> Self;
 }
 
 impl WindowAttributesExtWebSys for WindowAttributes {
@@ -142,8 +265,13 @@ impl WindowAttributesExtWebSys for WindowAttributes {
         self
     }
 
+    fn with_prevent_context_menu(mut self, prevent_context_menu: bool) -> Self {
+        self.platform_specific.prevent_context_menu = Some(prevent_context_menu);
+        self
+    }
+
     fn with_focusable(mut self, focusable: bool) -> Self {
-        self.platform_specific.focusable = focusable;
+        self.focusable = focusable;
         self
     }
 
@@ -151,6 +279,11 @@ impl WindowAttributesExtWebSys for WindowAttributes {
         self.platform_specific.append = append;
         self
     }
+
+    fn with_splash_element(mut self, element: Option<HtmlElement>) -> Self {
+        self.platform_specific.set_splash_element(element);
+        self
+    }
 }
 
 /// Additional methods on `EventLoop` that are specific to the web.
@@ -189,7 +322,8 @@ impl<T> EventLoopExtWebSys for EventLoop<T> {
     type UserEvent = T;
 
     fn spawn_app<A: ApplicationHandler<Self::UserEvent> + 'static>(self, app: A) {
-        self.event_loop.spawn_app(app);
+        let handler = WakeUpHandler::new(app, self.create_proxy());
+        self.event_loop.spawn_app(handler);
     }
 }
 
@@ -211,6 +345,89 @@ pub trait ActiveEventLoopExtWebSys {
     /// Async version of [`ActiveEventLoop::create_custom_cursor()`] which waits until the
     /// cursor has completely finished loading.
     fn create_custom_cursor_async(&self, source: CustomCursorSource) -> CustomCursorFuture;
+
+    /// Queries the state of a [`Permission`] without prompting the user.
+    ///
+    /// This allows to check if using a feature would prompt the user, e.g. to ask for it at a
+    /// convenient time with [`request_permission()`](Self::request_permission) instead.
+    ///
+    /// Returns [`PermissionError::Unsupported`] if the browser can't report the state of the
+    /// permission.
+    fn query_permission(&self, permission: Permission) -> PermissionFuture;
+
+    /// Requests a [`Permission`], prompting the user if necessary.
+    ///
+    /// Most browsers only allow prompting the user in response to user input, e.g. while handling
+    /// [`WindowEvent::MouseInput`] or [`WindowEvent::KeyboardInput`].
+    ///
+    /// [`Permission::PointerLock`] and [`Permission::ClipboardWrite`] are granted when used and
+    /// can't be requested ahead of time, so this only queries their state. Requesting
+    /// [`Permission::ClipboardRead`] reads the clipboard, as there is no other way to prompt for
+    /// it.
+    ///
+    /// [`WindowEvent::MouseInput`]: crate::event::WindowEvent::MouseInput
+    /// [`WindowEvent::KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    fn request_permission(&self, permission: Permission) -> PermissionFuture;
+
+    /// Resolves with the new state the next time the state of a [`Permission`] changes, e.g. when
+    /// the user revokes it in the browser settings.
+    ///
+    /// Returns [`PermissionError::Unsupported`] if the browser can't report the state of the
+    /// permission.
+    fn permission_changed(&self, permission: Permission) -> PermissionFuture;
+
+    /// Returns the area of the screen that isn't covered by taskbars or docks, from
+    /// [`screen.availLeft`] and friends, as browsers don't expose monitors.
+    ///
+    /// Changes are reported with [`ApplicationHandler::work_area_changed`] in browsers that fire
+    /// [`change`] events on the screen.
+    ///
+    /// [`screen.availLeft`]: https://developer.mozilla.org/en-US/docs/Web/API/Screen/availLeft
+    /// [`change`]: https://developer.mozilla.org/en-US/docs/Web/API/Screen/change_event
+    fn work_area(&self) -> Rect;
+
+    /// Adds an entry to the session history of the page with [`history.pushState()`], changing
+    /// the URL shown by the browser without reloading the page.
+    ///
+    /// `url` can be relative to the current URL, but must have the same origin. Navigating back
+    /// to an entry is reported with [`ApplicationHandler::navigation`].
+    ///
+    /// [`history.pushState()`]: https://developer.mozilla.org/en-US/docs/Web/API/History/pushState
+    fn push_state(&self, url: &str) -> Result<(), ExternalError>;
+
+    /// Like [`push_state()`](Self::push_state), but replaces the current entry with
+    /// [`history.replaceState()`].
+    ///
+    /// [`history.replaceState()`]: https://developer.mozilla.org/en-US/docs/Web/API/History/replaceState
+    fn replace_state(&self, url: &str) -> Result<(), ExternalError>;
+
+    /// Returns the [`FileSystemFileHandle`]s of the files last emitted with
+    /// [`ApplicationHandler::files_opened`], which only has their names.
+    ///
+    /// [`FileSystemFileHandle`]: https://developer.mozilla.org/en-US/docs/Web/API/FileSystemFileHandle
+    fn opened_file_handles(&self) -> Vec<JsValue>;
+
+    /// Defines a JavaScript function called `name` on the global object, that delivers its
+    /// argument to [`ApplicationHandler::user_event`] through `proxy`, waking up the event loop.
+    ///
+    /// `convert` turns the argument into a user event. An error returned by it, or sending to a
+    /// closed event loop, is thrown by the function. This allows JavaScript code, e.g. the
+    /// `onmessage` handler of a `WebSocket`, to talk to the application without any glue on the
+    /// Rust side:
+    ///
+    /// ```js
+    /// socket.onmessage = (event) => deliverMessage(event.data);
+    /// ```
+    ///
+    /// The function can also be retrieved with [`JsEventSource::function()`]. It is removed from
+    /// the global object when the returned [`JsEventSource`] is dropped. Fails if the global
+    /// object already has a property called `name`.
+    fn register_js_callback<T: 'static>(
+        &self,
+        name: &str,
+        proxy: EventLoopProxy<T>,
+        convert: impl FnMut(JsValue) -> Result<T, JsValue> + 'static,
+    ) -> Result<JsEventSource, ExternalError>;
 }
 
 impl ActiveEventLoopExtWebSys for ActiveEventLoop {
@@ -219,6 +436,50 @@ impl ActiveEventLoopExtWebSys for ActiveEventLoop {
         self.p.create_custom_cursor_async(source)
     }
 
+    #[inline]
+    fn query_permission(&self, permission: Permission) -> PermissionFuture {
+        self.p.query_permission(permission)
+    }
+
+    #[inline]
+    fn request_permission(&self, permission: Permission) -> PermissionFuture {
+        self.p.request_permission(permission)
+    }
+
+    #[inline]
+    fn permission_changed(&self, permission: Permission) -> PermissionFuture {
+        self.p.permission_changed(permission)
+    }
+
+    #[inline]
+    fn work_area(&self) -> Rect {
+        self.p.work_area()
+    }
+
+    #[inline]
+    fn push_state(&self, url: &str) -> Result<(), ExternalError> {
+        self.p.push_state(url)
+    }
+
+    #[inline]
+    fn replace_state(&self, url: &str) -> Result<(), ExternalError> {
+        self.p.replace_state(url)
+    }
+
+    #[inline]
+    fn opened_file_handles(&self) -> Vec<JsValue> {
+        self.p.opened_file_handles()
+    }
+
+    fn register_js_callback<T: 'static>(
+        &self,
+        name: &str,
+        proxy: EventLoopProxy<T>,
+        convert: impl FnMut(JsValue) -> Result<T, JsValue> + 'static,
+    ) -> Result<JsEventSource, ExternalError> {
+        PlatformJsEventSource::new(name, proxy, convert).map(JsEventSource)
+    }
+
     #[inline]
     fn set_poll_strategy(&self, strategy: PollStrategy) {
         self.p.set_poll_strategy(strategy);
@@ -230,6 +491,26 @@ impl ActiveEventLoopExtWebSys for ActiveEventLoop {
     }
 }
 
+/// A JavaScript function delivering user events, see
+/// [`ActiveEventLoopExtWebSys::register_js_callback()`].
+///
+/// The function is removed from the global object when this is dropped.
+#[derive(Debug)]
+pub struct JsEventSource(pub(crate) PlatformJsEventSource);
+
+impl JsEventSource {
+    /// The name of the function on the global object.
+    pub fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    /// The function itself, e.g. to pass it to JavaScript directly instead of through the global
+    /// object.
+    pub fn function(&self) -> &JsValue {
+        self.0.function()
+    }
+}
+
 /// Strategy used for [`ControlFlow::Poll`][crate::event_loop::ControlFlow::Poll].
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum PollStrategy {
@@ -325,6 +606,10 @@ impl Error for BadAnimation {}
 #[cfg(not(web_platform))]
 struct PlatformCustomCursorFuture;
 
+#[cfg(not(web_platform))]
+#[derive(Debug)]
+struct PlatformJsEventSource;
+
 #[derive(Debug)]
 pub struct CustomCursorFuture(pub(crate) PlatformCustomCursorFuture);
 
@@ -354,3 +639,68 @@ impl Display for CustomCursorError {
         }
     }
 }
+
+/// A permission that can be queried with [`ActiveEventLoopExtWebSys::query_permission()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Permission {
+    /// Locking the cursor with [`Window::set_cursor_grab()`].
+    ///
+    /// [`Window::set_cursor_grab()`]: crate::window::Window::set_cursor_grab
+    PointerLock,
+    /// Reading the clipboard.
+    ClipboardRead,
+    /// Writing to the clipboard.
+    ClipboardWrite,
+    /// Showing notifications.
+    Notifications,
+    /// Persisting storage, so it isn't cleared by the browser under storage pressure.
+    PersistentStorage,
+}
+
+/// The state of a [`Permission`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PermissionState {
+    /// The permission was granted.
+    Granted,
+    /// The permission was denied.
+    Denied,
+    /// Using the feature or requesting the permission prompts the user.
+    Prompt,
+}
+
+#[cfg(not(web_platform))]
+struct PlatformPermissionFuture;
+
+/// Future returned by [`ActiveEventLoopExtWebSys::query_permission()`],
+/// [`ActiveEventLoopExtWebSys::request_permission()`] and
+/// [`ActiveEventLoopExtWebSys::permission_changed()`].
+#[derive(Debug)]
+pub struct PermissionFuture(pub(crate) PlatformPermissionFuture);
+
+impl Future for PermissionFuture {
+    type Output = Result<PermissionState, PermissionError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum PermissionError {
+    /// The browser doesn't support querying or requesting the permission.
+    Unsupported,
+    /// Querying or requesting the permission failed, e.g. because it isn't allowed in an
+    /// `<iframe>`.
+    Failed(String),
+}
+
+impl Display for PermissionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "permission not supported by the browser"),
+            Self::Failed(error) => write!(f, "failed to query or request permission: {error}"),
+        }
+    }
+}
+
+impl Error for PermissionError {}
@@ -6,11 +6,12 @@ use std::borrow::Borrow;
 use std::ffi::c_void;
 use std::path::Path;
 
-use crate::dpi::PhysicalSize;
+use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::error::ExternalError;
 use crate::event::DeviceId;
-use crate::event_loop::EventLoopBuilder;
+use crate::event_loop::{ActiveEventLoop, EventLoop, EventLoopBuilder};
 use crate::monitor::MonitorHandle;
-use crate::window::{BadIcon, Icon, Window, WindowAttributes};
+use crate::window::{BadIcon, Icon, ResizeDirection, Theme, Window, WindowAttributes};
 
 /// Window Handle type used by Win32 API
 pub type HWND = isize;
@@ -18,6 +19,8 @@ pub type HWND = isize;
 pub type HMENU = isize;
 /// Monitor Handle type used by Win32 API
 pub type HMONITOR = isize;
+/// Handle type used by Win32 API for kernel objects
+pub type HANDLE = isize;
 
 /// Describes a system-drawn backdrop material of a window.
 ///
@@ -105,6 +108,207 @@ pub enum CornerPreference {
     RoundSmall = 3,
 }
 
+/// The window manager attributes of a window, applied with
+/// [`WindowExtWindows::set_dwm_attributes`].
+///
+/// Fields left as `None` are reset to the system default.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DwmConfig {
+    /// The theme of the title bar, corresponding to `DWMWA_USE_IMMERSIVE_DARK_MODE`.
+    ///
+    /// `None` makes the title bar follow the theme of the window, see [`Window::set_theme`].
+    ///
+    /// Supported starting with Windows 10 Build 19041.
+    pub titlebar_theme: Option<Theme>,
+
+    /// The preferred style of the window corners.
+    ///
+    /// Supported starting with Windows 11 Build 22000.
+    pub corner_preference: Option<CornerPreference>,
+
+    /// The color of the window border.
+    ///
+    /// Supported starting with Windows 11 Build 22000.
+    pub border_color: Option<Color>,
+
+    /// The background color of the title bar.
+    ///
+    /// Supported starting with Windows 11 Build 22000.
+    pub caption_color: Option<Color>,
+
+    /// The color of the window title.
+    ///
+    /// Supported starting with Windows 11 Build 22000.
+    pub text_color: Option<Color>,
+}
+
+/// Describes how a window handles changes of the monitor DPI.
+///
+/// For a detailed explanation, see [`DPI_AWARENESS_CONTEXT docs`].
+///
+/// [`DPI_AWARENESS_CONTEXT docs`]: https://learn.microsoft.com/en-us/windows/win32/hidpi/dpi-awareness-context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DpiAwareness {
+    /// Corresponds to `DPI_AWARENESS_CONTEXT_UNAWARE`.
+    ///
+    /// The window is always rendered at 96 DPI and bitmap-stretched by the system.
+    Unaware,
+
+    /// Corresponds to `DPI_AWARENESS_CONTEXT_SYSTEM_AWARE`.
+    ///
+    /// The window uses the DPI of the primary monitor at the time the process started.
+    System,
+
+    /// Corresponds to `DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE`.
+    ///
+    /// The window is notified when the DPI changes, but the non-client area isn't scaled.
+    PerMonitor,
+
+    /// Corresponds to `DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2`.
+    ///
+    /// Like [`DpiAwareness::PerMonitor`], but the non-client area is scaled and the window size
+    /// is negotiated with the application before the DPI changes. This is what winit asks for,
+    /// and requires Windows 10 Creators Update (1703) or later.
+    PerMonitorV2,
+}
+
+/// Describes which part of a window is at a given point.
+///
+/// Corresponds to the `HT*` values returned from `WM_NCHITTEST`, see
+/// [`WindowExtWindows::set_hit_test_handler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HitTestResult {
+    /// Corresponds to `HTCLIENT`.
+    ///
+    /// The point is in the client area and generates the usual cursor events.
+    Client,
+
+    /// Corresponds to `HTCAPTION`.
+    ///
+    /// Dragging moves the window and double-clicking maximizes it.
+    Caption,
+
+    /// Corresponds to `HTMINBUTTON`.
+    MinimizeButton,
+
+    /// Corresponds to `HTMAXBUTTON`.
+    ///
+    /// On Windows 11, hovering it shows the Snap Layouts flyout.
+    MaximizeButton,
+
+    /// Corresponds to `HTCLOSE`.
+    CloseButton,
+
+    /// Corresponds to `HTLEFT`, `HTTOPRIGHT`, etc.
+    ///
+    /// Dragging resizes the window in the given direction.
+    Resize(ResizeDirection),
+
+    /// Corresponds to `HTTRANSPARENT`.
+    ///
+    /// The point is passed on to the window below it, if it belongs to the same thread.
+    Transparent,
+}
+
+/// A function deciding which part of a window is at a given point.
+///
+/// See [`WindowExtWindows::set_hit_test_handler`].
+pub type HitTestHandler =
+    Box<dyn Fn(PhysicalPosition<i32>) -> Option<HitTestResult> + Send + Sync + 'static>;
+
+/// The result of a message hook, see [`WindowExtWindows::add_message_hook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HookResult {
+    /// Pass the message on to the next hook, and eventually to winit.
+    Continue,
+
+    /// Return the given value from the window procedure.
+    ///
+    /// Hooks with a lower priority and winit itself don't see the message.
+    Handled(isize),
+}
+
+/// Identifies a message hook added with [`WindowExtWindows::add_message_hook`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MessageHookId(pub(crate) u64);
+
+/// Controls whether touch and pen input is also delivered as mouse input, see
+/// [`WindowExtWindows::set_pointer_promotion`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PromotionPolicy {
+    /// Emit mouse events for the mouse messages Windows synthesizes from touch input, in addition
+    /// to [`WindowEvent::Touch`].
+    ///
+    /// The default is `true`.
+    ///
+    /// [`WindowEvent::Touch`]: crate::event::WindowEvent::Touch
+    pub touch_to_mouse: bool,
+
+    /// Emit mouse events for the mouse messages Windows synthesizes from pen input, in addition
+    /// to [`WindowEvent::Touch`].
+    ///
+    /// The default is `true`.
+    ///
+    /// [`WindowEvent::Touch`]: crate::event::WindowEvent::Touch
+    pub pen_to_mouse: bool,
+
+    /// Draw the system's visual feedback for touch and pen contacts, such as the ripple shown
+    /// when tapping.
+    ///
+    /// The default is `true`.
+    pub visual_feedback: bool,
+}
+
+impl Default for PromotionPolicy {
+    fn default() -> Self {
+        Self { touch_to_mouse: true, pen_to_mouse: true, visual_feedback: true }
+    }
+}
+
+/// Configures which raw input devices winit listens to, and how mouse input is delivered.
+///
+/// Raw input is what [`DeviceEvent`]s are generated from. See
+/// [`EventLoopBuilderExtWindows::with_raw_input`].
+///
+/// [`DeviceEvent`]: crate::event::DeviceEvent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawInputConfig {
+    /// Register for raw input from mice.
+    ///
+    /// The default is `true`.
+    pub mouse: bool,
+
+    /// Register for raw input from keyboards.
+    ///
+    /// The default is `true`.
+    pub keyboard: bool,
+
+    /// Read raw mouse input on a dedicated thread.
+    ///
+    /// Mice with high polling rates (4-8kHz) can send more `WM_INPUT` messages than the event loop
+    /// can handle, delaying all other messages. With this enabled, mouse input is read on a
+    /// separate thread and handed to the event loop in batches.
+    ///
+    /// The default is `false`.
+    pub mouse_thread: bool,
+
+    /// Merge consecutive motion of the same mouse into a single [`DeviceEvent::MouseMotion`]
+    /// when it is delivered from the [`mouse_thread`][Self::mouse_thread].
+    ///
+    /// Motion is never merged across button or wheel input, so their relative order is kept.
+    ///
+    /// The default is `true`.
+    ///
+    /// [`DeviceEvent::MouseMotion`]: crate::event::DeviceEvent::MouseMotion
+    pub coalesce_mouse_motion: bool,
+}
+
+impl Default for RawInputConfig {
+    fn default() -> Self {
+        Self { mouse: true, keyboard: true, mouse_thread: false, coalesce_mouse_motion: true }
+    }
+}
+
 /// A wrapper around a [`Window`] that ignores thread-specific window handle limitations.
 ///
 /// See [`WindowBorrowExtWindows::any_thread`] for more information.
@@ -159,6 +363,60 @@ impl<W: Borrow<Window>> rwh_06::HasWindowHandle for AnyThread<W> {
     }
 }
 
+/// Additional methods on [`ActiveEventLoop`] that are specific to Windows.
+pub trait ActiveEventLoopExtWindows {
+    /// Wait on a handle, like an event, a process or a waitable timer, with the messages of the
+    /// event loop, emitting [`ApplicationHandler::handle_signaled()`] with `token` when it's
+    /// signaled.
+    ///
+    /// This saves a thread waking up the event loop for each handle. Registering another handle
+    /// with the same token replaces the previous one. Up to 62 handles can be registered.
+    ///
+    /// The handle must stay open until it's unregistered with
+    /// [`unregister_wait_handle()`][Self::unregister_wait_handle]. Waiting has the same side
+    /// effects as with `WaitForSingleObject`, e.g. it resets auto-reset events.
+    ///
+    /// [`ApplicationHandler::handle_signaled()`]: crate::application::ApplicationHandler::handle_signaled
+    fn register_wait_handle(&self, handle: HANDLE, token: u64) -> Result<(), ExternalError>;
+
+    /// Stop waiting on the handle registered with `token`, if any.
+    fn unregister_wait_handle(&self, token: u64);
+}
+
+impl ActiveEventLoopExtWindows for ActiveEventLoop {
+    #[inline]
+    fn register_wait_handle(&self, handle: HANDLE, token: u64) -> Result<(), ExternalError> {
+        self.p.register_wait_handle(handle, token)
+    }
+
+    #[inline]
+    fn unregister_wait_handle(&self, token: u64) {
+        self.p.unregister_wait_handle(token)
+    }
+}
+
+/// Additional methods on [`EventLoop`] that are specific to Windows.
+pub trait EventLoopExtWindows {
+    /// Wait on a handle before the event loop runs, see
+    /// [`ActiveEventLoopExtWindows::register_wait_handle()`].
+    fn register_wait_handle(&self, handle: HANDLE, token: u64) -> Result<(), ExternalError>;
+
+    /// Stop waiting on the handle registered with `token`, if any.
+    fn unregister_wait_handle(&self, token: u64);
+}
+
+impl<T: 'static> EventLoopExtWindows for EventLoop<T> {
+    #[inline]
+    fn register_wait_handle(&self, handle: HANDLE, token: u64) -> Result<(), ExternalError> {
+        self.event_loop.window_target().p.register_wait_handle(handle, token)
+    }
+
+    #[inline]
+    fn unregister_wait_handle(&self, token: u64) {
+        self.event_loop.window_target().p.unregister_wait_handle(token)
+    }
+}
+
 /// Additional methods on `EventLoop` that are specific to Windows.
 pub trait EventLoopBuilderExtWindows {
     /// Whether to allow the event loop to be created off of the main thread.
@@ -199,6 +457,9 @@ pub trait EventLoopBuilderExtWindows {
     /// A callback to be executed before dispatching a win32 message to the window procedure.
     /// Return true to disable winit's internal message dispatching.
     ///
+    /// To intercept the messages of a single window instead, including those sent to it directly
+    /// rather than posted to the message queue, see [`WindowExtWindows::add_message_hook`].
+    ///
     /// # Example
     ///
     /// ```
@@ -225,6 +486,36 @@ pub trait EventLoopBuilderExtWindows {
     fn with_msg_hook<F>(&mut self, callback: F) -> &mut Self
     where
         F: FnMut(*const c_void) -> bool + 'static;
+
+    /// Which raw input devices to listen to, and how to deliver mouse input.
+    ///
+    /// By default, winit listens to all mice and keyboards on the event loop thread. Devices
+    /// are told apart by the [`DeviceId`] of the [`DeviceEvent`]s, see
+    /// [`DeviceIdExtWindows::persistent_identifier`].
+    ///
+    /// # Example
+    ///
+    /// Only listen to mice, and read them on a separate thread.
+    ///
+    /// ```
+    /// use winit::event_loop::EventLoopBuilder;
+    /// #[cfg(target_os = "windows")]
+    /// use winit::platform::windows::{EventLoopBuilderExtWindows, RawInputConfig};
+    ///
+    /// let mut builder = EventLoopBuilder::new();
+    /// #[cfg(target_os = "windows")]
+    /// builder.with_raw_input(RawInputConfig {
+    ///     keyboard: false,
+    ///     mouse_thread: true,
+    ///     ..Default::default()
+    /// });
+    /// # if false { // We can't test this part
+    /// let event_loop = builder.build();
+    /// # }
+    /// ```
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    fn with_raw_input(&mut self, config: RawInputConfig) -> &mut Self;
 }
 
 impl<T> EventLoopBuilderExtWindows for EventLoopBuilder<T> {
@@ -248,6 +539,12 @@ impl<T> EventLoopBuilderExtWindows for EventLoopBuilder<T> {
         self.platform_specific.msg_hook = Some(Box::new(callback));
         self
     }
+
+    #[inline]
+    fn with_raw_input(&mut self, config: RawInputConfig) -> &mut Self {
+        self.platform_specific.raw_input = config;
+        self
+    }
 }
 
 /// Additional methods on `Window` that are specific to Windows.
@@ -303,6 +600,102 @@ pub trait WindowExtWindows {
     /// Supported starting with Windows 11 Build 22000.
     fn set_corner_preference(&self, preference: CornerPreference);
 
+    /// Sets the title bar theme, corner preference, and border and title bar colors at once.
+    ///
+    /// Unlike the individual setters, the configuration is remembered and applied again whenever
+    /// the theme of the window changes, either through [`Window::set_theme`] or because the
+    /// system theme changed. Prefer this over calling `DwmSetWindowAttribute` on the window
+    /// handle directly, which winit's own theme handling would override.
+    fn set_dwm_attributes(&self, config: DwmConfig);
+
+    /// Returns the DPI awareness the window ended up with.
+    ///
+    /// Winit requests [`DpiAwareness::PerMonitorV2`], but falls back to older modes depending on
+    /// the Windows version, and the application manifest can override it. Only with
+    /// [`DpiAwareness::PerMonitorV2`] is [`WindowEvent::ScaleFactorChanged`] emitted before the
+    /// window is resized by the system when it is dragged between monitors.
+    ///
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    fn dpi_awareness(&self) -> DpiAwareness;
+
+    /// Sets the area of a custom-drawn maximize button, in client area coordinates.
+    ///
+    /// Windows 11 shows the Snap Layouts flyout when hovering the maximize button, but only knows
+    /// where it is for the system-drawn titlebar. Windows that draw their own titlebar can use
+    /// this to report their maximize button, which also makes clicking it toggle maximization.
+    ///
+    /// Like with the system-drawn titlebar, no cursor events are generated while the cursor is
+    /// over the button. Pass `None` to remove it.
+    fn set_caption_button_bounds(&self, maximize_button: Option<(Position, Size)>);
+
+    /// Sets a function deciding which part of the window is at a given point.
+    ///
+    /// The function is called from `WM_NCHITTEST` with the cursor position in physical client
+    /// area coordinates. Returning [`None`] falls back to the default behavior, which includes the
+    /// area set with [`WindowExtWindows::set_caption_button_bounds`].
+    ///
+    /// Clicking a point reported as one of the caption buttons minimizes, maximizes or closes the
+    /// window respectively.
+    ///
+    /// The function is called very often on the event loop thread, so it should return quickly
+    /// and must not block on the event loop. Pass `None` to remove it.
+    fn set_hit_test_handler(&self, handler: Option<HitTestHandler>);
+
+    /// Sets whether touch and pen input is also delivered as mouse input.
+    ///
+    /// By default, Windows synthesizes mouse messages from touch and pen input for applications
+    /// that don't handle it, so the same contact produces both [`WindowEvent::Touch`] and mouse
+    /// events. Disabling the promotion keeps the streams distinct. Mouse input is never promoted
+    /// to [`WindowEvent::Touch`].
+    ///
+    /// Visual feedback requires Windows 8 or later.
+    ///
+    /// [`WindowEvent::Touch`]: crate::event::WindowEvent::Touch
+    fn set_pointer_promotion(&self, policy: PromotionPolicy);
+
+    /// Sets whether fullscreen windows are set up for independent flip presentation.
+    ///
+    /// When enabled, which is the default, borderless and exclusive fullscreen windows get the
+    /// `WS_POPUP` style without any frame, and the client area covers the whole monitor even with
+    /// [`WindowExtWindows::set_undecorated_shadow`]. This lets DWM hand the screen directly to a
+    /// flip model swap chain, skipping composition and the frame of latency it adds.
+    ///
+    /// Disable this for overlays or windows that should keep being composed, e.g. so screen
+    /// recorders that capture the desktop see them.
+    ///
+    /// Exclusive fullscreen through DXGI (`IDXGISwapChain::SetFullscreenState`) is managed by the
+    /// swap chain; use it on a borderless fullscreen window.
+    fn set_fullscreen_optimizations(&self, enabled: bool);
+
+    /// Returns whether the window currently meets the requirements for independent flip.
+    ///
+    /// That is, it is fullscreen with [`WindowExtWindows::set_fullscreen_optimizations`] enabled,
+    /// covers its monitor exactly, and is neither layered nor transparent. DWM still decides on
+    /// every present, e.g. it composes the window while another one overlaps it, which is
+    /// reported per frame by `IDXGISwapChainMedia::GetFrameStatisticsMedia`.
+    fn is_fullscreen_optimized(&self) -> bool;
+
+    /// Adds a hook that sees the messages sent to the window procedure before winit does.
+    ///
+    /// The hook is called with the window handle, the message, and its `WPARAM` and `LPARAM`.
+    /// Hooks with a higher `priority` run first, and hooks with the same priority run in the
+    /// order they were added. Once a hook returns [`HookResult::Handled`], the remaining hooks
+    /// and winit are skipped, so the window can end up in a state winit doesn't know about.
+    ///
+    /// Hooks run on the thread that created the window. They aren't called for `WM_NCDESTROY`,
+    /// which winit needs to clean up the window, and a hook isn't re-entered for messages sent
+    /// while it is running.
+    ///
+    /// Returns an identifier to remove the hook with [`WindowExtWindows::remove_message_hook`].
+    fn add_message_hook<F>(&self, priority: i32, hook: F) -> MessageHookId
+    where
+        F: FnMut(HWND, u32, usize, isize) -> HookResult + Send + 'static;
+
+    /// Removes a hook added with [`WindowExtWindows::add_message_hook`].
+    ///
+    /// Returns `false` if the hook was already removed.
+    fn remove_message_hook(&self, id: MessageHookId) -> bool;
+
     /// Get the raw window handle for this [`Window`] without checking for thread affinity.
     ///
     /// Window handles in Win32 have a property called "thread affinity" that ties them to their
@@ -407,6 +800,54 @@ impl WindowExtWindows for Window {
         self.window.set_corner_preference(preference)
     }
 
+    #[inline]
+    fn set_dwm_attributes(&self, config: DwmConfig) {
+        self.window.set_dwm_attributes(config)
+    }
+
+    #[inline]
+    fn dpi_awareness(&self) -> DpiAwareness {
+        self.window.dpi_awareness()
+    }
+
+    #[inline]
+    fn set_caption_button_bounds(&self, maximize_button: Option<(Position, Size)>) {
+        self.window.set_caption_button_bounds(maximize_button)
+    }
+
+    #[inline]
+    fn set_hit_test_handler(&self, handler: Option<HitTestHandler>) {
+        self.window.set_hit_test_handler(handler)
+    }
+
+    #[inline]
+    fn set_pointer_promotion(&self, policy: PromotionPolicy) {
+        self.window.set_pointer_promotion(policy)
+    }
+
+    #[inline]
+    fn set_fullscreen_optimizations(&self, enabled: bool) {
+        self.window.set_fullscreen_optimizations(enabled)
+    }
+
+    #[inline]
+    fn is_fullscreen_optimized(&self) -> bool {
+        self.window.is_fullscreen_optimized()
+    }
+
+    #[inline]
+    fn add_message_hook<F>(&self, priority: i32, hook: F) -> MessageHookId
+    where
+        F: FnMut(HWND, u32, usize, isize) -> HookResult + Send + 'static,
+    {
+        self.window.add_message_hook(priority, hook)
+    }
+
+    #[inline]
+    fn remove_message_hook(&self, id: MessageHookId) -> bool {
+        self.window.remove_message_hook(id)
+    }
+
     #[cfg(feature = "rwh_06")]
     unsafe fn window_handle_any_thread(
         &self,
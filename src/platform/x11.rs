@@ -53,6 +53,33 @@ pub enum WindowType {
     Normal,
 }
 
+/// Whether the compositor should stop compositing while the window is shown. Maps directly to
+/// [`_NET_WM_BYPASS_COMPOSITOR`](https://specifications.freedesktop.org/wm-spec/wm-spec-1.5.html).
+///
+/// Bypassing the compositor avoids an additional frame of latency for fullscreen windows, but
+/// compositors are free to ignore this hint.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CompositorBypass {
+    /// Let the compositor decide.
+    #[default]
+    NoPreference,
+    /// Ask the compositor to stop compositing, e.g. for games or screen magnifiers.
+    Bypass,
+    /// Ask the compositor to never stop compositing, e.g. for windows that are transparent.
+    Disable,
+}
+
+/// How the visual of a window is picked, see [`WindowAttributesExtX11::with_visual()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VisualSelector {
+    /// A true color visual with the given depth, e.g. 30 for 10 bits per color channel.
+    Depth(u8),
+    /// A true color visual with an alpha channel, for windows that are transparent.
+    Rgba,
+}
+
 /// The first argument in the provided hook will be the pointer to `XDisplay`
 /// and the second one the pointer to [`XErrorEvent`]. The returned `bool` is an
 /// indicator whether the error was handled by the callback.
@@ -139,15 +166,32 @@ impl<T> EventLoopBuilderExtX11 for EventLoopBuilder<T> {
 }
 
 /// Additional methods on [`Window`] that are specific to X11.
-pub trait WindowExtX11 {}
+pub trait WindowExtX11 {
+    /// Sets whether the compositor should stop compositing while the window is shown.
+    ///
+    /// See [`WindowAttributesExtX11::with_bypass_compositor()`].
+    fn set_bypass_compositor(&self, bypass: CompositorBypass);
+}
 
-impl WindowExtX11 for Window {}
+impl WindowExtX11 for Window {
+    #[inline]
+    fn set_bypass_compositor(&self, bypass: CompositorBypass) {
+        self.queue_command(move |w| w.set_bypass_compositor(bypass))
+    }
+}
 
 /// Additional methods on [`WindowAttributes`] that are specific to X11.
 pub trait WindowAttributesExtX11 {
     /// Create this window with a specific X11 visual.
     fn with_x11_visual(self, visual_id: XVisualID) -> Self;
 
+    /// Create this window with a visual matching the selector.
+    ///
+    /// Without it, the visual of transparent windows is a 32-bit one if there is one, and the
+    /// visual of the parent window otherwise. Creating the window fails if no visual matches.
+    /// [`with_x11_visual()`][Self::with_x11_visual] takes precedence.
+    fn with_visual(self, selector: VisualSelector) -> Self;
+
     fn with_x11_screen(self, screen_id: i32) -> Self;
 
     /// Build window with the given `general` and `instance` names.
@@ -161,8 +205,16 @@ pub trait WindowAttributesExtX11 {
     fn with_name(self, general: impl Into<String>, instance: impl Into<String>) -> Self;
 
     /// Build window with override-redirect flag; defaults to false.
+    ///
+    /// Override-redirect windows are not managed by the window manager: they aren't decorated,
+    /// can't be moved or resized by the user and don't receive keyboard focus unless the cursor
+    /// is grabbed. This is suited for menus, tooltips, overlays and drop-down launchers.
     fn with_override_redirect(self, override_redirect: bool) -> Self;
 
+    /// Build window with `_NET_WM_BYPASS_COMPOSITOR` hint; defaults to
+    /// [`CompositorBypass::NoPreference`].
+    fn with_bypass_compositor(self, bypass: CompositorBypass) -> Self;
+
     /// Build window with `_NET_WM_WINDOW_TYPE` hints; defaults to `Normal`.
     fn with_x11_window_type(self, x11_window_type: Vec<WindowType>) -> Self;
 
@@ -204,6 +256,12 @@ impl WindowAttributesExtX11 for WindowAttributes {
         self
     }
 
+    #[inline]
+    fn with_visual(mut self, selector: VisualSelector) -> Self {
+        self.platform_specific.x11.visual = Some(selector);
+        self
+    }
+
     #[inline]
     fn with_x11_screen(mut self, screen_id: i32) -> Self {
         self.platform_specific.x11.screen_id = Some(screen_id);
@@ -223,6 +281,12 @@ impl WindowAttributesExtX11 for WindowAttributes {
         self
     }
 
+    #[inline]
+    fn with_bypass_compositor(mut self, bypass: CompositorBypass) -> Self {
+        self.platform_specific.x11.bypass_compositor = bypass;
+        self
+    }
+
     #[inline]
     fn with_x11_window_type(mut self, x11_window_types: Vec<WindowType>) -> Self {
         self.platform_specific.x11.x11_window_types = x11_window_types;
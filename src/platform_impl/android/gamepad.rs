@@ -0,0 +1,97 @@
+//! Input from gamepads and joysticks, delivered as device events.
+//!
+//! Both `NativeActivity` and `GameActivity` report controllers as motion events from a joystick
+//! source, and their buttons as key events, so this works the same with either backend.
+
+use std::collections::HashMap;
+
+use android_activity::input::{Axis, Keycode, MotionEvent};
+use android_activity::AndroidApp;
+
+use crate::event::{DeviceEvent, ElementState};
+
+/// The axes of gamepads, the sticks, the triggers and the directional pad.
+const AXES: [Axis; 10] = [
+    Axis::X,
+    Axis::Y,
+    Axis::Z,
+    Axis::Rz,
+    Axis::HatX,
+    Axis::HatY,
+    Axis::Ltrigger,
+    Axis::Rtrigger,
+    Axis::Gas,
+    Axis::Brake,
+];
+
+/// Report the axes of gamepads in motion events, `GameActivity` only reports `X` and `Y` by
+/// default.
+pub fn enable_axes(app: &AndroidApp) {
+    for axis in AXES {
+        app.enable_motion_axis(axis);
+    }
+}
+
+pub fn is_button(keycode: Keycode) -> bool {
+    matches!(
+        keycode,
+        Keycode::ButtonA
+            | Keycode::ButtonB
+            | Keycode::ButtonC
+            | Keycode::ButtonX
+            | Keycode::ButtonY
+            | Keycode::ButtonZ
+            | Keycode::ButtonL1
+            | Keycode::ButtonR1
+            | Keycode::ButtonL2
+            | Keycode::ButtonR2
+            | Keycode::ButtonThumbl
+            | Keycode::ButtonThumbr
+            | Keycode::ButtonStart
+            | Keycode::ButtonSelect
+            | Keycode::ButtonMode
+            | Keycode::Button1
+            | Keycode::Button2
+            | Keycode::Button3
+            | Keycode::Button4
+            | Keycode::Button5
+            | Keycode::Button6
+            | Keycode::Button7
+            | Keycode::Button8
+            | Keycode::Button9
+            | Keycode::Button10
+            | Keycode::Button11
+            | Keycode::Button12
+            | Keycode::Button13
+            | Keycode::Button14
+            | Keycode::Button15
+            | Keycode::Button16
+    )
+}
+
+pub fn button(keycode: Keycode, state: ElementState) -> DeviceEvent {
+    DeviceEvent::Button { button: keycode.into(), state }
+}
+
+/// The last values of the axes by device, as each motion event reports every axis.
+#[derive(Debug, Default)]
+pub struct Axes {
+    values: HashMap<(i32, u32), f32>,
+}
+
+impl Axes {
+    /// The motion of the axes that changed since the last motion event of the device.
+    pub fn motion(&mut self, motion_event: &MotionEvent<'_>) -> Vec<DeviceEvent> {
+        let device_id = motion_event.device_id();
+        let pointer = motion_event.pointer_at_index(0);
+        AXES.iter()
+            .filter_map(|&axis| {
+                let value = pointer.axis_value(axis);
+                let axis = u32::from(axis);
+                let previous = self.values.insert((device_id, axis), value);
+                (previous != Some(value))
+                    .then_some(DeviceEvent::Motion { axis, value: value as f64 })
+            })
+            .collect()
+    }
+}
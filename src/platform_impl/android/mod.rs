@@ -4,11 +4,14 @@ use std::cell::Cell;
 use std::collections::VecDeque;
 use std::hash::Hash;
 use std::marker::PhantomData;
+use std::ops::RangeInclusive;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use android_activity::input::{InputEvent, KeyAction, Keycode, MotionAction};
+use android_activity::input::{
+    ImeOptions, InputEvent, InputType, KeyAction, Keycode, MotionAction, TextInputAction,
+};
 use android_activity::{
     AndroidApp, AndroidAppWaker, ConfigurationRef, InputStatus, MainEvent, Rect,
 };
@@ -16,27 +19,43 @@ use tracing::{debug, trace, warn};
 
 use crate::application::ApplicationHandler;
 use crate::cursor::Cursor;
+use crate::dialog::{DialogButton, DialogSender, MessageDialog};
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error;
 use crate::error::EventLoopError;
 use crate::event::{self, Force, InnerSizeWriter, StartCause};
 use crate::event_loop::{self, ControlFlow, DeviceEvents};
+use crate::ime::SurroundingText;
+use crate::locale::LanguageTag;
+use crate::monitor::Rect;
+use crate::notification::{NotificationAttributes, NotificationId};
 use crate::platform::pump_events::PumpStatus;
 use crate::platform_impl::Fullscreen;
+use crate::session::{SessionCapabilities, SessionInfo, SessionKind};
 use crate::window::{
-    self, CursorGrabMode, CustomCursor, CustomCursorSource, ImePurpose, ResizeDirection, Theme,
-    WindowButtons, WindowLevel,
+    self, AudioSessionCategory, Autocapitalization, CapturePolicy, CoordinateTransform,
+    CursorGrabMode, CustomCursor, CustomCursorSource, EnterKeyLabel, FocusReason, FrameRateRange,
+    ImePurpose, KeyboardHints, KeyboardKind, PerformanceHint, Region, ResizeDirection, ShareData,
+    TextServicesConfig, Theme, TilingState, WindowButtons, WindowCapabilities, WindowLevel,
+    WindowVisibility,
 };
 
+mod gamepad;
 mod keycodes;
+mod text_input;
 
 pub(crate) use crate::cursor::{
     NoCustomCursor as PlatformCustomCursor, NoCustomCursor as PlatformCustomCursorSource,
 };
 pub(crate) use crate::icon::NoIcon as PlatformIcon;
+pub(crate) use crate::notification::NoNotification as PlatformNotification;
 
 static HAS_FOCUS: AtomicBool = AtomicBool::new(true);
 
+/// The share of the window height the content rectangle has to shrink by to assume that it's the
+/// on-screen keyboard, rather than the system bars.
+const KEYBOARD_MIN_HEIGHT_PERCENT: i32 = 15;
+
 /// Returns the minimum `Option<Duration>`, taking into account that `None`
 /// equates to an infinite timeout, not a zero timeout (so can't just use
 /// `Option::min`)
@@ -134,6 +153,16 @@ impl RedrawRequester {
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub struct KeyEventExtra {}
 
+/// Combines the language and the country of the configuration.
+fn locale(app: &AndroidApp) -> Option<LanguageTag> {
+    let config = app.config();
+    let language = config.language()?;
+    match config.country() {
+        Some(country) => LanguageTag::parse(&format!("{language}-{country}")),
+        None => LanguageTag::parse(&language),
+    }
+}
+
 pub struct EventLoop<T: 'static> {
     android_app: AndroidApp,
     window_target: event_loop::ActiveEventLoop,
@@ -146,6 +175,11 @@ pub struct EventLoop<T: 'static> {
     cause: StartCause,
     ignore_volume_keys: bool,
     combining_accent: Option<char>,
+    /// The locale last reported with `locale_changed`.
+    locale: Option<LanguageTag>,
+    gamepad_axes: gamepad::Axes,
+    /// The part of the window covered by the on-screen keyboard, as last reported.
+    keyboard_occluded_rect: Option<Region>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -171,6 +205,7 @@ impl<T: 'static> EventLoop<T> {
              Android",
         );
         let redraw_flag = SharedFlag::new();
+        gamepad::enable_axes(android_app);
 
         Ok(Self {
             android_app: android_app.clone(),
@@ -183,6 +218,8 @@ impl<T: 'static> EventLoop<T> {
                         &redraw_flag,
                         android_app.create_waker(),
                     ),
+                    audio_session: Cell::new(false),
+                    text_input: Default::default(),
                 },
                 _marker: PhantomData,
             },
@@ -195,6 +232,9 @@ impl<T: 'static> EventLoop<T> {
             cause: StartCause::Init,
             ignore_volume_keys: attributes.ignore_volume_keys,
             combining_accent: None,
+            locale: locale(android_app),
+            gamepad_axes: Default::default(),
+            keyboard_occluded_rect: None,
         })
     }
 
@@ -217,14 +257,16 @@ impl<T: 'static> EventLoop<T> {
             match event {
                 MainEvent::InitWindow { .. } => {
                     app.resumed(self.window_target());
+                    app.surface_created(self.window_target(), window::WindowId(WindowId));
                 },
                 MainEvent::TerminateWindow { .. } => {
+                    app.surface_destroyed(self.window_target(), window::WindowId(WindowId));
                     app.suspended(self.window_target());
                 },
                 MainEvent::WindowResized { .. } => resized = true,
                 MainEvent::RedrawNeeded { .. } => pending_redraw = true,
-                MainEvent::ContentRectChanged { .. } => {
-                    warn!("TODO: find a way to notify application of content rect change");
+                MainEvent::ContentRectChanged { .. } | MainEvent::InsetsChanged { .. } => {
+                    self.handle_keyboard_visibility(app);
                 },
                 MainEvent::GainedFocus => {
                     HAS_FOCUS.store(true, Ordering::Relaxed);
@@ -256,6 +298,14 @@ impl<T: 'static> EventLoop<T> {
 
                         app.window_event(self.window_target(), window_id, event);
                     }
+
+                    let locale = locale(&self.android_app);
+                    if locale != self.locale {
+                        self.locale = locale.clone();
+                        if let Some(locale) = locale {
+                            app.locale_changed(self.window_target(), locale);
+                        }
+                    }
                 },
                 MainEvent::LowMemory => {
                     app.memory_warning(self.window_target());
@@ -267,6 +317,9 @@ impl<T: 'static> EventLoop<T> {
                 MainEvent::Resume { .. } => {
                     debug!("App Resumed - is running");
                     self.running = true;
+                    if self.window_target.p.audio_session.get() {
+                        app.audio_resumed(self.window_target());
+                    }
                 },
                 MainEvent::SaveState { .. } => {
                     // XXX: how to forward this state to applications?
@@ -276,6 +329,9 @@ impl<T: 'static> EventLoop<T> {
                 MainEvent::Pause => {
                     debug!("App Paused - stopped running");
                     self.running = false;
+                    if self.window_target.p.audio_session.get() {
+                        app.audio_interrupted(self.window_target());
+                    }
                 },
                 MainEvent::Stop => {
                     // XXX: how to forward this state to applications?
@@ -286,10 +342,6 @@ impl<T: 'static> EventLoop<T> {
                     // killed by the OS?
                     warn!("TODO: forward onDestroy notification to application");
                 },
-                MainEvent::InsetsChanged { .. } => {
-                    // XXX: how to forward this state to applications?
-                    warn!("TODO: handle Android InsetsChanged notification");
-                },
                 unknown => {
                     trace!("Unknown MainEvent {unknown:?} (ignored)");
                 },
@@ -302,6 +354,13 @@ impl<T: 'static> EventLoop<T> {
         // a borrow of `self` while iterating
         let android_app = self.android_app.clone();
 
+        // The IME is enabled before its text input is handled.
+        let toggled = self.window_target.p.text_input.lock().unwrap().toggled();
+        if let Some(ime) = toggled {
+            let window_id = window::WindowId(WindowId);
+            app.window_event(self.window_target(), window_id, event::WindowEvent::Ime(ime));
+        }
+
         // Process input events
         match android_app.input_events_iter() {
             Ok(mut input_iter) => loop {
@@ -334,7 +393,7 @@ impl<T: 'static> EventLoop<T> {
                     PhysicalSize::new(0, 0)
                 };
                 let window_id = window::WindowId(WindowId);
-                let event = event::WindowEvent::Resized(size);
+                let event = event::WindowEvent::Resized { size, frame: None };
                 app.window_event(self.window_target(), window_id, event);
             }
 
@@ -353,6 +412,35 @@ impl<T: 'static> EventLoop<T> {
         self.pending_redraw = pending_redraw;
     }
 
+    /// Emit `KeyboardVisibilityChanged` when the part of the window below the content rectangle
+    /// changed, which is where the on-screen keyboard is.
+    fn handle_keyboard_visibility<A: ApplicationHandler<T>>(&mut self, app: &mut A) {
+        let Some(native_window) = self.android_app.native_window() else {
+            return;
+        };
+        let (width, height) = (native_window.width(), native_window.height());
+        let content_bottom = self.android_app.content_rect().bottom;
+        let covered = height - content_bottom;
+
+        let occluded_rect = (covered > height * KEYBOARD_MIN_HEIGHT_PERCENT / 100).then(|| {
+            Region::new(
+                PhysicalPosition::new(0, content_bottom),
+                PhysicalSize::new(width as u32, covered as u32),
+            )
+        });
+        if occluded_rect == self.keyboard_occluded_rect {
+            return;
+        }
+        self.keyboard_occluded_rect = occluded_rect;
+
+        let window_id = window::WindowId(WindowId);
+        let event = event::WindowEvent::KeyboardVisibilityChanged {
+            visible: occluded_rect.is_some(),
+            occluded_rect,
+        };
+        app.window_event(self.window_target(), window_id, event);
+    }
+
     fn handle_input_event<A: ApplicationHandler<T>>(
         &mut self,
         android_app: &AndroidApp,
@@ -361,6 +449,12 @@ impl<T: 'static> EventLoop<T> {
     ) -> InputStatus {
         let mut input_status = InputStatus::Handled;
         match event {
+            InputEvent::MotionEvent(motion_event) if motion_event.source().is_joystick_class() => {
+                let device_id = event::DeviceId(DeviceId(motion_event.device_id()));
+                for event in self.gamepad_axes.motion(motion_event) {
+                    app.device_event(self.window_target(), device_id, event);
+                }
+            },
             InputEvent::MotionEvent(motion_event) => {
                 let window_id = window::WindowId(WindowId);
                 let device_id = event::DeviceId(DeviceId(motion_event.device_id()));
@@ -427,6 +521,12 @@ impl<T: 'static> EventLoop<T> {
                             _ => event::ElementState::Released,
                         };
 
+                        if gamepad::is_button(keycode) && key.repeat_count() == 0 {
+                            let device_id = event::DeviceId(DeviceId(key.device_id()));
+                            let event = gamepad::button(keycode, state);
+                            app.device_event(self.window_target(), device_id, event);
+                        }
+
                         let key_char = keycodes::character_map_and_combine_key(
                             android_app,
                             key,
@@ -452,6 +552,13 @@ impl<T: 'static> EventLoop<T> {
                     },
                 }
             },
+            InputEvent::TextEvent(state) => {
+                let events = self.window_target.p.text_input.lock().unwrap().text_changed(state);
+                let window_id = window::WindowId(WindowId);
+                for ime in events {
+                    app.window_event(self.window_target(), window_id, event::WindowEvent::Ime(ime));
+                }
+            },
             _ => {
                 warn!("Unknown android_activity input event {event:?}")
             },
@@ -543,6 +650,8 @@ impl<T: 'static> EventLoop<T> {
             };
 
         let android_app = self.android_app.clone(); // Don't borrow self as part of poll expression
+        #[cfg(feature = "trace")]
+        let _span = tracing::trace_span!("winit::poll_events", ?timeout).entered();
         android_app.poll_events(timeout, |poll_event| {
             let mut main_event = None;
 
@@ -628,6 +737,20 @@ impl<T> EventLoopProxy<T> {
         self.waker.wake();
         Ok(())
     }
+
+    pub fn waker(&self) -> EventLoopWaker {
+        EventLoopWaker(self.waker.clone())
+    }
+}
+
+/// Wakes up the `EventLoop` from any thread, without a message.
+#[derive(Clone)]
+pub struct EventLoopWaker(AndroidAppWaker);
+
+impl EventLoopWaker {
+    pub fn wake(&self) {
+        self.0.wake();
+    }
 }
 
 pub struct ActiveEventLoop {
@@ -635,6 +758,10 @@ pub struct ActiveEventLoop {
     control_flow: Cell<ControlFlow>,
     exit: Cell<bool>,
     redraw_requester: RedrawRequester,
+    /// Whether a window with an audio session category was created, so that pausing the activity
+    /// interrupts the audio of the application.
+    audio_session: Cell<bool>,
+    text_input: Arc<Mutex<text_input::TextInput>>,
 }
 
 impl ActiveEventLoop {
@@ -642,6 +769,40 @@ impl ActiveEventLoop {
         Some(MonitorHandle::new(self.app.clone()))
     }
 
+    pub fn locale(&self) -> Option<LanguageTag> {
+        locale(&self.app)
+    }
+
+    pub fn seats(&self) -> Vec<event::Seat> {
+        Vec::new()
+    }
+
+    pub fn session_info(&self) -> SessionInfo {
+        SessionInfo::new(SessionKind::Mobile, false, SessionCapabilities::empty())
+    }
+
+    pub fn double_click_settings(&self) -> Option<(Duration, u32)> {
+        None
+    }
+
+    pub fn pointer_position(&self) -> Result<PhysicalPosition<f64>, error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
+    pub fn set_restart_after_crash(
+        &self,
+        _args: Option<Vec<std::ffi::OsString>>,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
+    pub fn set_video_mode(
+        &self,
+        _mode: &VideoModeHandle,
+    ) -> Result<Box<dyn FnOnce()>, error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     pub fn create_custom_cursor(&self, source: CustomCursorSource) -> CustomCursor {
         let _ = source.inner;
         CustomCursor { inner: PlatformCustomCursor }
@@ -678,6 +839,27 @@ impl ActiveEventLoop {
         self.control_flow.get()
     }
 
+    #[inline]
+    pub(crate) fn set_timer_resolution(&self, _resolution: Duration) {}
+
+    pub(crate) fn set_exit_pending(&self, _pending: bool) {}
+
+    pub(crate) fn show_notification(
+        &self,
+        _id: NotificationId,
+        _attributes: NotificationAttributes,
+    ) -> Result<PlatformNotification, error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
+    pub(crate) fn show_message_dialog(
+        &self,
+        _dialog: MessageDialog,
+        _sender: DialogSender<DialogButton>,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     pub(crate) fn exit(&self) {
         self.exit.set(true)
     }
@@ -742,24 +924,41 @@ impl DeviceId {
     pub const fn dummy() -> Self {
         DeviceId(0)
     }
+
+    pub fn seat_id(&self) -> Option<event::SeatId> {
+        None
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
-pub struct PlatformSpecificWindowAttributes;
+pub struct PlatformSpecificWindowAttributes {
+    pub audio_session_category: Option<AudioSessionCategory>,
+}
+
+/// The part of a window that the closures of `Window::maybe_queue_on_main` run with.
+pub(crate) type MainThreadWindow = Window;
 
 pub(crate) struct Window {
     app: AndroidApp,
     redraw_requester: RedrawRequester,
+    text_input: Arc<Mutex<text_input::TextInput>>,
 }
 
 impl Window {
     pub(crate) fn new(
         el: &ActiveEventLoop,
-        _window_attrs: window::WindowAttributes,
+        window_attrs: window::WindowAttributes,
     ) -> Result<Self, error::OsError> {
         // FIXME this ignores requested window attributes
+        if window_attrs.platform_specific.audio_session_category.is_some() {
+            el.audio_session.set(true);
+        }
 
-        Ok(Self { app: el.app.clone(), redraw_requester: el.redraw_requester.clone() })
+        Ok(Self {
+            app: el.app.clone(),
+            redraw_requester: el.redraw_requester.clone(),
+            text_input: el.text_input.clone(),
+        })
     }
 
     pub(crate) fn maybe_queue_on_main(&self, f: impl FnOnce(&Self) + Send + 'static) {
@@ -798,10 +997,16 @@ impl Window {
 
     pub fn pre_present_notify(&self) {}
 
+    pub fn notify_first_frame_presented(&self) {}
+
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, error::NotSupportedError> {
         Err(error::NotSupportedError::new())
     }
 
+    pub fn coordinate_transform(&self) -> CoordinateTransform {
+        CoordinateTransform::from_scale_factor(self.inner_position().ok(), self.scale_factor())
+    }
+
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, error::NotSupportedError> {
         Err(error::NotSupportedError::new())
     }
@@ -876,6 +1081,10 @@ impl Window {
         None
     }
 
+    pub fn capabilities(&self) -> WindowCapabilities {
+        WindowCapabilities::empty()
+    }
+
     pub fn set_decorations(&self, _decorations: bool) {}
 
     pub fn is_decorated(&self) -> bool {
@@ -888,20 +1097,101 @@ impl Window {
 
     pub fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
 
-    pub fn set_ime_allowed(&self, _allowed: bool) {}
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        let state = self.text_input.lock().unwrap().set_allowed(allowed);
+        if let Some(state) = state {
+            self.app.set_text_input_state(state);
+            self.app.show_soft_input(false);
+        } else if !allowed {
+            self.app.hide_soft_input(false);
+        }
+        // Emit `Ime::Enabled` or `Ime::Disabled`.
+        self.app.create_waker().wake();
+    }
 
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    pub fn set_ime_surrounding_text(&self, text: SurroundingText) {
+        let state = self.text_input.lock().unwrap().set_surrounding_text(text);
+        if let Some(state) = state {
+            self.app.set_text_input_state(state);
+        }
+    }
+
+    pub fn set_text_services(&self, _config: TextServicesConfig) {}
+
+    pub fn set_keyboard_hints(&self, hints: KeyboardHints) {
+        let mut input_type = match hints.kind {
+            KeyboardKind::Numeric => InputType::TYPE_CLASS_NUMBER,
+            KeyboardKind::Decimal => {
+                InputType::TYPE_CLASS_NUMBER | InputType::TYPE_NUMBER_FLAG_DECIMAL
+            },
+            KeyboardKind::Phone => InputType::TYPE_CLASS_PHONE,
+            KeyboardKind::Email => {
+                InputType::TYPE_CLASS_TEXT | InputType::TYPE_TEXT_VARIATION_EMAIL_ADDRESS
+            },
+            KeyboardKind::Url => InputType::TYPE_CLASS_TEXT | InputType::TYPE_TEXT_VARIATION_URI,
+            KeyboardKind::Text | KeyboardKind::Search => InputType::TYPE_CLASS_TEXT,
+        };
+        // Capitalization flags only apply to text.
+        if input_type.contains(InputType::TYPE_CLASS_TEXT) {
+            input_type |= match hints.autocap {
+                Some(Autocapitalization::Sentences) => InputType::TYPE_TEXT_FLAG_CAP_SENTENCES,
+                Some(Autocapitalization::Words) => InputType::TYPE_TEXT_FLAG_CAP_WORDS,
+                Some(Autocapitalization::Characters) => InputType::TYPE_TEXT_FLAG_CAP_CHARACTERS,
+                Some(Autocapitalization::None) | None => InputType::empty(),
+            };
+        }
+
+        let action = match hints.enter_label {
+            EnterKeyLabel::Default if hints.kind == KeyboardKind::Search => TextInputAction::Search,
+            EnterKeyLabel::Default => TextInputAction::Unspecified,
+            EnterKeyLabel::Enter => TextInputAction::None,
+            EnterKeyLabel::Done => TextInputAction::Done,
+            EnterKeyLabel::Go => TextInputAction::Go,
+            EnterKeyLabel::Next => TextInputAction::Next,
+            EnterKeyLabel::Previous => TextInputAction::Previous,
+            EnterKeyLabel::Search => TextInputAction::Search,
+            EnterKeyLabel::Send => TextInputAction::Send,
+        };
+
+        self.app.set_ime_editor_info(input_type, action, ImeOptions::IME_FLAG_NO_FULLSCREEN);
+    }
+
     pub fn focus_window(&self) {}
 
+    pub fn focus_window_with(&self, _reason: FocusReason) {}
+
+    pub fn set_focusable(&self, _focusable: bool) {}
+
     pub fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
 
     pub fn set_cursor(&self, _: Cursor) {}
 
+    pub fn set_cursor_for_device(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _cursor: Cursor,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
+    pub fn set_pointer_capture(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _capture: bool,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     pub fn set_cursor_position(&self, _: Position) -> Result<(), error::ExternalError> {
         Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
     }
 
+    pub fn cursor_position(&self) -> Result<PhysicalPosition<f64>, error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     pub fn set_cursor_grab(&self, _: CursorGrabMode) -> Result<(), error::ExternalError> {
         Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
     }
@@ -926,6 +1216,13 @@ impl Window {
         Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
     }
 
+    pub fn set_hittest_regions(
+        &self,
+        _regions: &[window::Region],
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     #[cfg(feature = "rwh_04")]
     pub fn raw_window_handle_rwh_04(&self) -> rwh_04::RawWindowHandle {
         use rwh_04::HasRawWindowHandle;
@@ -1002,10 +1299,36 @@ impl Window {
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn set_capture_policy(&self, _policy: CapturePolicy) {}
+
+    pub fn set_performance_hint(&self, _hint: PerformanceHint) {}
+
+    pub fn set_preferred_frame_rate_range(&self, _range: Option<FrameRateRange>) {}
+
+    pub fn share(&self, _data: ShareData) {}
+
+    pub fn present_rgba(
+        &self,
+        _buffer: &[u8],
+        _size: PhysicalSize<u32>,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
+    pub fn hide_to_tray(&self) {}
+
     pub fn has_focus(&self) -> bool {
         HAS_FOCUS.load(Ordering::Relaxed)
     }
 
+    pub fn visibility(&self) -> WindowVisibility {
+        WindowVisibility::new(true, false, false, self.has_focus())
+    }
+
+    pub fn tiling_state(&self) -> TilingState {
+        TilingState::Floating
+    }
+
     pub fn title(&self) -> String {
         String::new()
     }
@@ -1059,6 +1382,14 @@ impl MonitorHandle {
         (0, 0).into()
     }
 
+    pub fn work_area(&self) -> Rect {
+        Rect::new(self.position(), self.size())
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     pub fn scale_factor(&self) -> f64 {
         self.app.config().density().map(|dpi| dpi as f64 / 160.0).unwrap_or(1.0)
     }
@@ -1068,6 +1399,14 @@ impl MonitorHandle {
         None
     }
 
+    pub fn supports_variable_refresh(&self) -> bool {
+        false
+    }
+
+    pub fn is_mirrored(&self) -> bool {
+        false
+    }
+
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         let size = self.size().into();
         // FIXME this is not the real refresh rate
@@ -1102,6 +1441,10 @@ impl VideoModeHandle {
         self.refresh_rate_millihertz
     }
 
+    pub fn refresh_rate_range(&self) -> RangeInclusive<u32> {
+        self.refresh_rate_millihertz..=self.refresh_rate_millihertz
+    }
+
     pub fn monitor(&self) -> MonitorHandle {
         self.monitor.clone()
     }
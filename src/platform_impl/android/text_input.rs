@@ -0,0 +1,134 @@
+//! Text input from the IME through the `InputConnection` of `GameActivity`.
+//!
+//! The IME edits the whole text state of the `InputConnection`, with the composing text as a region
+//! of it, so the edits are found by comparing the text to the surrounding text of the application
+//! and turned into `Ime` events. The offsets of the text state are in UTF-16 code units, like Java
+//! strings.
+
+use android_activity::input::{TextInputState, TextSpan};
+
+use crate::event::Ime;
+use crate::ime::SurroundingText;
+
+/// The state of the IME, shared by the window and the event loop.
+#[derive(Debug)]
+pub struct TextInput {
+    /// Whether the application allows the IME.
+    allowed: bool,
+    /// Whether `Ime::Enabled` was emitted last, rather than `Ime::Disabled`.
+    enabled: bool,
+    /// Whether a preedit was emitted that wasn't cleared yet.
+    preedit: bool,
+    /// The text of the application around the cursor, without the preedit, as the IME has it.
+    surrounding_text: SurroundingText,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        Self {
+            allowed: false,
+            enabled: false,
+            preedit: false,
+            surrounding_text: SurroundingText::new(String::new(), 0, 0).unwrap(),
+        }
+    }
+}
+
+impl TextInput {
+    /// Allow or disallow the IME, returning the text state to show it with when it's allowed.
+    pub fn set_allowed(&mut self, allowed: bool) -> Option<TextInputState> {
+        if self.allowed == allowed {
+            return None;
+        }
+        self.allowed = allowed;
+        allowed.then(|| text_input_state(&self.surrounding_text))
+    }
+
+    /// `Ime::Enabled` or `Ime::Disabled` when the IME was allowed or disallowed since the last
+    /// call.
+    pub fn toggled(&mut self) -> Option<Ime> {
+        if self.enabled == self.allowed {
+            return None;
+        }
+        self.enabled = self.allowed;
+        self.preedit = false;
+        Some(if self.enabled { Ime::Enabled } else { Ime::Disabled })
+    }
+
+    /// Update the surrounding text, returning the text state to send to the IME if it changed.
+    ///
+    /// The text isn't sent while composing, as that would end the composition.
+    pub fn set_surrounding_text(&mut self, text: SurroundingText) -> Option<TextInputState> {
+        if self.preedit || text == self.surrounding_text {
+            return None;
+        }
+        self.surrounding_text = text;
+        self.enabled.then(|| text_input_state(&self.surrounding_text))
+    }
+
+    /// The `Ime` events that apply the text state edited by the IME to the surrounding text.
+    pub fn text_changed(&mut self, state: &TextInputState) -> Vec<Ime> {
+        let mut events = Vec::new();
+        if !self.enabled {
+            return events;
+        }
+
+        // Offsets at character boundaries of the text, 0 is always one.
+        let text = SurroundingText::new(state.text.clone(), 0, 0).unwrap();
+        let compose = state
+            .compose_region
+            .map(|region| text.byte_offset(region.start)..text.byte_offset(region.end))
+            .filter(|region| region.start < region.end);
+        let cursor = text.byte_offset(state.selection.end);
+        let anchor = text.byte_offset(state.selection.start);
+
+        let mut committed = state.text.clone();
+        let preedit = compose.clone().map(|region| {
+            let preedit = committed[region.clone()].to_owned();
+            committed.replace_range(region.clone(), "");
+            let cursor = cursor.clamp(region.start, region.end) - region.start;
+            (preedit, cursor)
+        });
+        // Offsets in the text without the preedit.
+        let uncomposed = |offset: usize| match &compose {
+            Some(region) if offset >= region.end => offset - region.len(),
+            Some(region) if offset > region.start => region.start,
+            _ => offset,
+        };
+
+        let (replaced, inserted) = self.surrounding_text.replaced_range(&committed);
+        let edited = !replaced.is_empty() || !inserted.is_empty();
+        if self.preedit && (edited || preedit.is_none()) {
+            events.push(Ime::Preedit(String::new(), None, Vec::new()));
+            self.preedit = false;
+        }
+        if !replaced.is_empty() {
+            let (before_bytes, after_bytes) = self.surrounding_text.delete_range(replaced);
+            if before_bytes > 0 || after_bytes > 0 {
+                events.push(Ime::DeleteSurrounding { before_bytes, after_bytes });
+            }
+        }
+        if !inserted.is_empty() {
+            events.push(Ime::Commit(inserted.to_owned()));
+        }
+        if let Some((preedit, cursor)) = preedit {
+            events.push(Ime::Preedit(preedit, Some((cursor, cursor)), Vec::new()));
+            self.preedit = true;
+        }
+
+        self.surrounding_text =
+            SurroundingText::new(committed, uncomposed(cursor), uncomposed(anchor)).unwrap();
+        events
+    }
+}
+
+fn text_input_state(text: &SurroundingText) -> TextInputState {
+    TextInputState {
+        text: text.text().to_owned(),
+        selection: TextSpan {
+            start: text.utf16_offset(text.anchor()),
+            end: text.utf16_offset(text.cursor()),
+        },
+        compose_region: None,
+    }
+}
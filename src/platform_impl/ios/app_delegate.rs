@@ -1,11 +1,22 @@
-use objc2::{declare_class, mutability, ClassType, DeclaredClass};
-use objc2_foundation::{MainThreadMarker, NSObject, NSObjectProtocol};
-use objc2_ui_kit::{UIApplication, UIWindow};
+use objc2::rc::Retained;
+use objc2::runtime::AnyObject;
+use objc2::{declare_class, msg_send, msg_send_id, mutability, sel, ClassType, DeclaredClass};
+use objc2_foundation::{
+    CGPoint, CGRect, CGSize, MainThreadMarker, NSNotification, NSNotificationCenter, NSObject,
+    NSObjectProtocol, NSURL,
+};
+use objc2_ui_kit::{
+    UIApplication, UICoordinateSpace, UIKeyboardFrameEndUserInfoKey,
+    UIKeyboardWillChangeFrameNotification, UIScreenDidConnectNotification,
+    UIScreenDidDisconnectNotification, UIWindow,
+};
 
 use super::app_state::{self, EventWrapper};
+use super::audio;
 use super::window::WinitUIWindow;
-use crate::event::{Event, WindowEvent};
-use crate::window::WindowId as RootWindowId;
+use crate::dpi::{LogicalPosition, LogicalSize};
+use crate::event::{Event, Url, WindowEvent};
+use crate::window::{Region, WindowId as RootWindowId};
 
 declare_class!(
     pub struct AppDelegate;
@@ -22,6 +33,34 @@ declare_class!(
     unsafe impl AppDelegate {
         #[method(application:didFinishLaunchingWithOptions:)]
         fn did_finish_launching(&self, _application: &UIApplication, _: *mut NSObject) -> bool {
+            let center = unsafe { NSNotificationCenter::defaultCenter() };
+            for name in unsafe { [UIScreenDidConnectNotification, UIScreenDidDisconnectNotification] } {
+                unsafe {
+                    center.addObserver_selector_name_object(
+                        self,
+                        sel!(screensDidChange:),
+                        Some(name),
+                        None,
+                    )
+                };
+            }
+            unsafe {
+                center.addObserver_selector_name_object(
+                    self,
+                    sel!(audioSessionInterruption:),
+                    Some(audio::AVAudioSessionInterruptionNotification),
+                    None,
+                )
+            };
+            unsafe {
+                center.addObserver_selector_name_object(
+                    self,
+                    sel!(keyboardWillChangeFrame:),
+                    Some(UIKeyboardWillChangeFrameNotification),
+                    None,
+                )
+            };
+
             app_state::did_finish_launching(MainThreadMarker::new().unwrap());
             true
         }
@@ -50,7 +89,7 @@ declare_class!(
 
         #[method(applicationWillTerminate:)]
         fn will_terminate(&self, application: &UIApplication) {
-            let mut events = Vec::new();
+            let mut events = vec![EventWrapper::StaticEvent(Event::Terminating)];
             #[allow(deprecated)]
             for window in application.windows().iter() {
                 if window.is_kind_of::<WinitUIWindow>() {
@@ -71,14 +110,104 @@ declare_class!(
             app_state::terminated(mtm);
         }
 
+        #[method(application:openURL:options:)]
+        fn open_url(&self, _application: &UIApplication, url: &NSURL, _: *mut NSObject) -> bool {
+            let Some(url) = unsafe { url.absoluteString() }.and_then(|url| Url::parse(&url.to_string()))
+            else {
+                return false;
+            };
+            let mtm = MainThreadMarker::new().unwrap();
+            app_state::handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::UrlOpened(url)));
+            true
+        }
+
         #[method(applicationDidReceiveMemoryWarning:)]
         fn did_receive_memory_warning(&self, _application: &UIApplication) {
             let mtm = MainThreadMarker::new().unwrap();
             app_state::handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::MemoryWarning))
         }
+
+        #[method(screensDidChange:)]
+        fn screens_did_change(&self, _: &NSNotification) {
+            let mtm = MainThreadMarker::new().unwrap();
+            app_state::handle_nonuser_event(mtm, EventWrapper::StaticEvent(Event::MonitorsChanged))
+        }
+
+        #[method(audioSessionInterruption:)]
+        fn audio_session_interruption(&self, notification: &NSNotification) {
+            let event = match audio::interruption_began(notification) {
+                Some(true) => Event::AudioInterrupted,
+                Some(false) => Event::AudioResumed,
+                None => return,
+            };
+            let mtm = MainThreadMarker::new().unwrap();
+            app_state::handle_nonuser_event(mtm, EventWrapper::StaticEvent(event))
+        }
+
+        #[method(keyboardWillChangeFrame:)]
+        fn keyboard_will_change_frame(&self, notification: &NSNotification) {
+            let Some(frame) = keyboard_frame(notification) else {
+                return;
+            };
+            let mtm = MainThreadMarker::new().unwrap();
+            let mut events = Vec::new();
+            #[allow(deprecated)]
+            for window in UIApplication::sharedApplication(mtm).windows().iter() {
+                if window.is_kind_of::<WinitUIWindow>() {
+                    // SAFETY: We just checked that the window is a `winit` window
+                    let window = unsafe {
+                        let ptr: *const UIWindow = window;
+                        let ptr: *const WinitUIWindow = ptr.cast();
+                        &*ptr
+                    };
+                    let screen = window.screen();
+                    // The frame is in the coordinates of the screen, and moved off it when hidden.
+                    let visible = intersection(frame, screen.bounds()).is_some();
+                    let occluded_rect = window
+                        .rootViewController()
+                        .and_then(|view_controller| view_controller.view())
+                        .filter(|_| visible)
+                        .and_then(|view| {
+                            let frame = view
+                                .convertRect_fromCoordinateSpace(frame, &screen.coordinateSpace());
+                            intersection(frame, view.bounds())
+                        })
+                        .map(|frame| {
+                            Region::new(
+                                LogicalPosition::new(frame.origin.x as f64, frame.origin.y as f64),
+                                LogicalSize::new(frame.size.width as f64, frame.size.height as f64),
+                            )
+                        });
+                    events.push(EventWrapper::StaticEvent(Event::WindowEvent {
+                        window_id: RootWindowId(window.id()),
+                        event: WindowEvent::KeyboardVisibilityChanged { visible, occluded_rect },
+                    }));
+                }
+            }
+            app_state::handle_nonuser_events(mtm, events);
+        }
     }
 );
 
+/// The frame the keyboard is animated to, from a `UIKeyboardWillChangeFrameNotification`.
+fn keyboard_frame(notification: &NSNotification) -> Option<CGRect> {
+    let user_info: Option<Retained<AnyObject>> = unsafe { msg_send_id![notification, userInfo] };
+    let frame: Option<Retained<AnyObject>> =
+        unsafe { msg_send_id![&user_info?, objectForKey: UIKeyboardFrameEndUserInfoKey] };
+    Some(unsafe { msg_send![&frame?, CGRectValue] })
+}
+
+fn intersection(a: CGRect, b: CGRect) -> Option<CGRect> {
+    let (min, max) = (a.min(), a.max());
+    let (other_min, other_max) = (b.min(), b.max());
+    let x = min.x.max(other_min.x);
+    let y = min.y.max(other_min.y);
+    let width = max.x.min(other_max.x) - x;
+    let height = max.y.min(other_max.y) - y;
+    (width > 0.0 && height > 0.0)
+        .then(|| CGRect::new(CGPoint::new(x, y), CGSize::new(width, height)))
+}
+
 impl AppDelegate {
     fn send_occluded_event_for_all_windows(&self, application: &UIApplication, occluded: bool) {
         let mut events = Vec::new();
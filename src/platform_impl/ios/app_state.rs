@@ -14,11 +14,11 @@ use core_foundation::runloop::{
     CFRunLoopTimerInvalidate, CFRunLoopTimerRef, CFRunLoopTimerSetNextFireDate,
 };
 use objc2::rc::Retained;
-use objc2::runtime::AnyObject;
+use objc2::runtime::{AnyObject, NSObject};
 use objc2::{msg_send, sel};
 use objc2_foundation::{
-    CGRect, CGSize, MainThreadMarker, NSInteger, NSObjectProtocol, NSOperatingSystemVersion,
-    NSProcessInfo,
+    ns_string, CGRect, CGSize, MainThreadMarker, NSActivityOptions, NSInteger, NSObjectProtocol,
+    NSOperatingSystemVersion, NSProcessInfo,
 };
 use objc2_ui_kit::{UICoordinateSpace, UIView};
 
@@ -26,7 +26,7 @@ use super::window::WinitUIWindow;
 use crate::dpi::PhysicalSize;
 use crate::event::{Event, InnerSizeWriter, StartCause, WindowEvent};
 use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ControlFlow};
-use crate::window::WindowId as RootWindowId;
+use crate::window::{PerformanceHint, WindowId as RootWindowId};
 
 macro_rules! bug {
     ($($msg:tt)*) => {
@@ -138,6 +138,8 @@ pub(crate) struct AppState {
     app_state: Option<AppStateImpl>,
     control_flow: ControlFlow,
     waker: EventLoopWaker,
+    /// Activity for the `PerformanceHint` of the application.
+    performance_activity: Option<Retained<NSObject>>,
 }
 
 impl AppState {
@@ -161,6 +163,7 @@ impl AppState {
                     }),
                     control_flow: ControlFlow::default(),
                     waker,
+                    performance_activity: None,
                 });
             }
             init_guard(&mut guard);
@@ -412,6 +415,27 @@ impl AppState {
     pub(crate) fn control_flow(&self) -> ControlFlow {
         self.control_flow
     }
+
+    pub(crate) fn set_performance_hint(&mut self, hint: PerformanceHint) {
+        let process_info = NSProcessInfo::processInfo();
+        if let Some(old) = self.performance_activity.take() {
+            unsafe { process_info.endActivity(&old) };
+        }
+
+        let options = match hint {
+            PerformanceHint::Default => return,
+            PerformanceHint::Game => {
+                NSActivityOptions::NSActivityUserInitiatedAllowingIdleSystemSleep
+            },
+            PerformanceHint::Realtime => {
+                NSActivityOptions::NSActivityUserInitiatedAllowingIdleSystemSleep
+                    | NSActivityOptions::NSActivityLatencyCritical
+            },
+        };
+        let reason = ns_string!("PerformanceHint of the application");
+        self.performance_activity =
+            Some(unsafe { process_info.beginActivityWithOptions_reason(options, reason) });
+    }
 }
 
 pub(crate) fn set_key_window(mtm: MainThreadMarker, window: &Retained<WinitUIWindow>) {
@@ -0,0 +1,70 @@
+//! Configuring the `AVAudioSession` of the application, and observing its interruptions.
+
+use std::ptr;
+
+use objc2::rc::Retained;
+use objc2::runtime::AnyObject;
+use objc2::{class, msg_send, msg_send_id};
+use objc2_foundation::{NSNotification, NSString, NSUInteger};
+use tracing::warn;
+
+use crate::window::AudioSessionCategory;
+
+#[link(name = "AVFoundation", kind = "framework")]
+extern "C" {
+    static AVAudioSessionCategoryAmbient: &'static NSString;
+    static AVAudioSessionCategoryPlayback: &'static NSString;
+    static AVAudioSessionCategoryRecord: &'static NSString;
+
+    pub(super) static AVAudioSessionInterruptionNotification: &'static NSString;
+    static AVAudioSessionInterruptionTypeKey: &'static NSString;
+}
+
+/// `AVAudioSessionInterruptionTypeBegan`, the interruption type is `Ended` otherwise.
+const INTERRUPTION_TYPE_BEGAN: NSUInteger = 1;
+
+fn shared_instance() -> Retained<AnyObject> {
+    unsafe { msg_send_id![class!(AVAudioSession), sharedInstance] }
+}
+
+fn set_active(session: &AnyObject) -> bool {
+    unsafe { msg_send![session, setActive: true, error: ptr::null_mut::<*mut AnyObject>()] }
+}
+
+/// Set the category of the audio session and activate it.
+pub(super) fn set_category(category: AudioSessionCategory) {
+    let name = unsafe {
+        match category {
+            AudioSessionCategory::Ambient => AVAudioSessionCategoryAmbient,
+            AudioSessionCategory::Playback => AVAudioSessionCategoryPlayback,
+            AudioSessionCategory::Record => AVAudioSessionCategoryRecord,
+        }
+    };
+
+    let session = shared_instance();
+    let set: bool =
+        unsafe { msg_send![&session, setCategory: name, error: ptr::null_mut::<*mut AnyObject>()] };
+    if !set {
+        warn!("failed to set the category of the audio session to {category:?}");
+    } else if !set_active(&session) {
+        warn!("failed to activate the audio session");
+    }
+}
+
+/// Whether an `AVAudioSessionInterruptionNotification` reports that the interruption began, or
+/// otherwise ended, in which case the audio session is activated again.
+pub(super) fn interruption_began(notification: &NSNotification) -> Option<bool> {
+    let user_info: Option<Retained<AnyObject>> = unsafe { msg_send_id![notification, userInfo] };
+    let user_info = user_info?;
+    let kind: Option<Retained<AnyObject>> =
+        unsafe { msg_send_id![&user_info, objectForKey: AVAudioSessionInterruptionTypeKey] };
+    let kind: NSUInteger = unsafe { msg_send![&kind?, unsignedIntegerValue] };
+
+    if kind == INTERRUPTION_TYPE_BEGAN {
+        return Some(true);
+    }
+    if !set_active(&shared_instance()) {
+        warn!("failed to activate the audio session after an interruption");
+    }
+    Some(false)
+}
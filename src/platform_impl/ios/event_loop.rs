@@ -3,6 +3,7 @@ use std::ffi::{c_char, c_int, c_void};
 use std::marker::PhantomData;
 use std::ptr::{self, NonNull};
 use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
 
 use core_foundation::base::{CFIndex, CFRelease};
 use core_foundation::runloop::{
@@ -13,22 +14,27 @@ use core_foundation::runloop::{
 };
 use objc2::rc::Retained;
 use objc2::{msg_send_id, ClassType};
-use objc2_foundation::{MainThreadMarker, NSString};
+use objc2_foundation::{MainThreadMarker, NSLocale, NSString};
 use objc2_ui_kit::{UIApplication, UIApplicationMain, UIDevice, UIScreen, UIUserInterfaceIdiom};
 
 use crate::application::ApplicationHandler;
-use crate::error::EventLoopError;
-use crate::event::Event;
+use crate::dialog::{DialogButton, DialogSender, MessageDialog};
+use crate::dpi::PhysicalPosition;
+use crate::error::{EventLoopError, ExternalError, NotSupportedError};
+use crate::event::{Event, Seat};
 use crate::event_loop::{
     ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents, EventLoopClosed,
 };
+use crate::locale::LanguageTag;
+use crate::notification::{NotificationAttributes, NotificationId};
 use crate::platform::ios::Idiom;
 use crate::platform_impl::platform::app_state::{EventLoopHandler, HandlePendingUserEvents};
+use crate::session::{SessionCapabilities, SessionInfo, SessionKind};
 use crate::window::{CustomCursor, CustomCursorSource};
 
 use super::app_delegate::AppDelegate;
 use super::app_state::AppState;
-use super::{app_state, monitor, MonitorHandle};
+use super::{app_state, monitor, MonitorHandle, PlatformNotification, VideoModeHandle};
 
 #[derive(Debug)]
 pub struct ActiveEventLoop {
@@ -50,6 +56,41 @@ impl ActiveEventLoop {
         Some(MonitorHandle::new(UIScreen::mainScreen(self.mtm)))
     }
 
+    pub fn locale(&self) -> Option<LanguageTag> {
+        let languages = unsafe { NSLocale::preferredLanguages() };
+        languages.iter().find_map(|language| LanguageTag::parse(&language.to_string()))
+    }
+
+    pub fn seats(&self) -> Vec<Seat> {
+        Vec::new()
+    }
+
+    pub fn session_info(&self) -> SessionInfo {
+        SessionInfo::new(SessionKind::Mobile, false, SessionCapabilities::empty())
+    }
+
+    pub fn double_click_settings(&self) -> Option<(Duration, u32)> {
+        None
+    }
+
+    pub fn pointer_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_restart_after_crash(
+        &self,
+        _args: Option<Vec<std::ffi::OsString>>,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_video_mode(
+        &self,
+        _mode: &VideoModeHandle,
+    ) -> Result<Box<dyn FnOnce()>, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
@@ -75,6 +116,27 @@ impl ActiveEventLoop {
         AppState::get_mut(self.mtm).control_flow()
     }
 
+    #[inline]
+    pub(crate) fn set_timer_resolution(&self, _resolution: Duration) {}
+
+    pub(crate) fn set_exit_pending(&self, _pending: bool) {}
+
+    pub(crate) fn show_notification(
+        &self,
+        _id: NotificationId,
+        _attributes: NotificationAttributes,
+    ) -> Result<PlatformNotification, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub(crate) fn show_message_dialog(
+        &self,
+        _dialog: MessageDialog,
+        _sender: DialogSender<DialogButton>,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub(crate) fn exit(&self) {
         // https://developer.apple.com/library/archive/qa/qa1561/_index.html
         // it is not possible to quit an iOS app gracefully and programmatically
@@ -126,11 +188,40 @@ fn map_user_event<T: 'static, A: ApplicationHandler<T>>(
                 app.user_event(window_target, event);
             }
         },
+        Event::SurfaceCreated(window_id) => app.surface_created(window_target, window_id),
+        Event::SurfaceDestroyed(window_id) => app.surface_destroyed(window_target, window_id),
         Event::Suspended => app.suspended(window_target),
         Event::Resumed => app.resumed(window_target),
         Event::AboutToWait => app.about_to_wait(window_target),
+        Event::Idle { deadline } => app.idle(window_target, deadline),
+        Event::CloseRequestedByOS { respond } => app.close_requested_by_os(window_target, respond),
+        Event::Notification { id, event } => app.notification_event(window_target, id, event),
+        Event::Terminating => app.terminating(window_target),
         Event::LoopExiting => app.exiting(window_target),
         Event::MemoryWarning => app.memory_warning(window_target),
+        Event::SessionLocked => app.session_locked(window_target),
+        Event::SessionUnlocked => app.session_unlocked(window_target),
+        Event::DisplaySleep => app.display_sleep(window_target),
+        Event::DisplayWake => app.display_wake(window_target),
+        Event::LocaleChanged(locale) => app.locale_changed(window_target, locale),
+        Event::WorkAreaChanged => app.work_area_changed(window_target),
+        Event::Navigation(pop_state) => app.navigation(window_target, pop_state),
+        Event::ColorProfileChanged => app.color_profile_changed(window_target),
+        Event::ActivationRequest { args, activation_token } => {
+            app.activation_request(window_target, args, activation_token)
+        },
+        Event::UrlOpened(url) => app.url_opened(window_target, url),
+        Event::FilesOpened(paths) => app.files_opened(window_target, paths),
+        Event::DragHover { window_id, position, respond } => {
+            let _ = respond.respond(app.drag_hover(window_target, window_id, position));
+        },
+        Event::TrayActivated(window_id) => app.tray_activated(window_target, window_id),
+        Event::Signal(signal) => app.signal(window_target, signal),
+        Event::FdReady(token) => app.fd_ready(window_target, token),
+        Event::HandleSignaled(token) => app.handle_signaled(window_target, token),
+        Event::MonitorsChanged => app.monitors_changed(window_target),
+        Event::AudioInterrupted => app.audio_interrupted(window_target),
+        Event::AudioResumed => app.audio_resumed(window_target),
     }
 }
 
@@ -302,6 +393,24 @@ impl<T> EventLoopProxy<T> {
         }
         Ok(())
     }
+
+    pub fn waker(&self) -> EventLoopWaker {
+        // With its own run loop source, which stays valid after this proxy is dropped.
+        EventLoopWaker(EventLoopProxy::new(mpsc::channel().0))
+    }
+}
+
+/// Wakes up the `EventLoop` from any thread, without a message.
+#[derive(Clone)]
+pub struct EventLoopWaker(EventLoopProxy<()>);
+
+impl EventLoopWaker {
+    pub fn wake(&self) {
+        unsafe {
+            CFRunLoopSourceSignal(self.0.source);
+            CFRunLoopWakeUp(CFRunLoopGetMain());
+        }
+    }
 }
 
 fn setup_control_flow_observers() {
@@ -3,6 +3,7 @@
 
 mod app_delegate;
 mod app_state;
+mod audio;
 mod event_loop;
 mod monitor;
 mod view;
@@ -11,18 +12,21 @@ mod window;
 
 use std::fmt;
 
-use crate::event::DeviceId as RootDeviceId;
+use crate::event::{DeviceId as RootDeviceId, SeatId};
 
 pub(crate) use self::event_loop::{
-    ActiveEventLoop, EventLoop, EventLoopProxy, OwnedDisplayHandle,
+    ActiveEventLoop, EventLoop, EventLoopProxy, EventLoopWaker, OwnedDisplayHandle,
     PlatformSpecificEventLoopAttributes,
 };
 pub(crate) use self::monitor::{MonitorHandle, VideoModeHandle};
-pub(crate) use self::window::{PlatformSpecificWindowAttributes, Window, WindowId};
+pub(crate) use self::window::{
+    MainThreadWindow, PlatformSpecificWindowAttributes, Window, WindowId,
+};
 pub(crate) use crate::cursor::{
     NoCustomCursor as PlatformCustomCursor, NoCustomCursor as PlatformCustomCursorSource,
 };
 pub(crate) use crate::icon::NoIcon as PlatformIcon;
+pub(crate) use crate::notification::NoNotification as PlatformNotification;
 pub(crate) use crate::platform_impl::Fullscreen;
 
 /// There is no way to detect which device that performed a certain event in
@@ -36,6 +40,10 @@ impl DeviceId {
     pub const unsafe fn dummy() -> Self {
         DeviceId
     }
+
+    pub fn seat_id(&self) -> Option<SeatId> {
+        None
+    }
 }
 
 pub(crate) const DEVICE_ID: RootDeviceId = RootDeviceId(DeviceId);
@@ -1,7 +1,8 @@
 #![allow(clippy::unnecessary_cast)]
 
 use std::collections::{BTreeSet, VecDeque};
-use std::{fmt, hash, ptr};
+use std::ops::RangeInclusive;
+use std::{fmt, hash};
 
 use objc2::mutability::IsRetainable;
 use objc2::rc::Retained;
@@ -10,7 +11,7 @@ use objc2_foundation::{run_on_main, MainThreadBound, MainThreadMarker, NSInteger
 use objc2_ui_kit::{UIScreen, UIScreenMode};
 
 use crate::dpi::{PhysicalPosition, PhysicalSize};
-use crate::monitor::VideoModeHandle as RootVideoModeHandle;
+use crate::monitor::{Rect, VideoModeHandle as RootVideoModeHandle};
 use crate::platform_impl::platform::app_state;
 
 // Workaround for `MainThreadBound` implementing almost no traits
@@ -79,6 +80,10 @@ impl VideoModeHandle {
         self.refresh_rate_millihertz
     }
 
+    pub fn refresh_rate_range(&self) -> RangeInclusive<u32> {
+        self.refresh_rate_millihertz..=self.refresh_rate_millihertz
+    }
+
     pub fn monitor(&self) -> MonitorHandle {
         self.monitor.clone()
     }
@@ -102,13 +107,14 @@ impl Clone for MonitorHandle {
 
 impl hash::Hash for MonitorHandle {
     fn hash<H: hash::Hasher>(&self, state: &mut H) {
-        (self as *const Self).hash(state);
+        self.screen_ptr().hash(state);
     }
 }
 
 impl PartialEq for MonitorHandle {
     fn eq(&self, other: &Self) -> bool {
-        ptr::eq(self, other)
+        // Handles of the same screen are equal, e.g. to find the primary monitor among others.
+        self.screen_ptr() == other.screen_ptr()
     }
 }
 
@@ -123,7 +129,7 @@ impl PartialOrd for MonitorHandle {
 impl Ord for MonitorHandle {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         // TODO: Make a better ordering
-        (self as *const Self).cmp(&(other as *const Self))
+        self.screen_ptr().cmp(&other.screen_ptr())
     }
 }
 
@@ -174,6 +180,14 @@ impl MonitorHandle {
         (bounds.origin.x as f64, bounds.origin.y as f64).into()
     }
 
+    pub fn work_area(&self) -> Rect {
+        Rect::new(self.position(), self.size())
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     pub fn scale_factor(&self) -> f64 {
         self.ui_screen.get_on_main(|ui_screen| ui_screen.nativeScale()) as f64
     }
@@ -182,6 +196,14 @@ impl MonitorHandle {
         Some(self.ui_screen.get_on_main(|ui_screen| refresh_rate_millihertz(ui_screen)))
     }
 
+    pub fn supports_variable_refresh(&self) -> bool {
+        false
+    }
+
+    pub fn is_mirrored(&self) -> bool {
+        self.ui_screen.get_on_main(|ui_screen| ui_screen.mirroredScreen().is_some())
+    }
+
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         run_on_main(|mtm| {
             let ui_screen = self.ui_screen(mtm);
@@ -199,6 +221,12 @@ impl MonitorHandle {
         })
     }
 
+    fn screen_ptr(&self) -> *const UIScreen {
+        // SAFETY: Marker only used to get the pointer
+        let mtm = unsafe { MainThreadMarker::new_unchecked() };
+        Retained::as_ptr(self.ui_screen.get(mtm))
+    }
+
     pub(crate) fn ui_screen(&self, mtm: MainThreadMarker) -> &Retained<UIScreen> {
         self.ui_screen.get(mtm)
     }
@@ -1,29 +1,39 @@
 #![allow(clippy::unnecessary_cast)]
 use std::cell::{Cell, RefCell};
+use std::iter;
 
 use objc2::rc::Retained;
 use objc2::runtime::{NSObjectProtocol, ProtocolObject};
 use objc2::{declare_class, msg_send, msg_send_id, mutability, sel, ClassType, DeclaredClass};
-use objc2_foundation::{CGFloat, CGPoint, CGRect, MainThreadMarker, NSObject, NSSet};
+use objc2_foundation::{
+    CGFloat, CGPoint, CGRect, MainThreadMarker, NSInteger, NSObject, NSRunLoop,
+    NSRunLoopCommonModes, NSSet,
+};
+use objc2_quartz_core::{CADisplayLink, CAFrameRateRange};
 use objc2_ui_kit::{
     UICoordinateSpace, UIEvent, UIForceTouchCapability, UIGestureRecognizer,
     UIGestureRecognizerDelegate, UIGestureRecognizerState, UIPanGestureRecognizer,
-    UIPinchGestureRecognizer, UIResponder, UIRotationGestureRecognizer, UITapGestureRecognizer,
-    UITouch, UITouchPhase, UITouchType, UITraitEnvironment, UIView,
+    UIPinchGestureRecognizer, UIRectEdge, UIResponder, UIRotationGestureRecognizer,
+    UIScreenEdgePanGestureRecognizer, UITapGestureRecognizer, UITouch, UITouchPhase, UITouchType,
+    UITraitEnvironment, UIView,
 };
 
 use super::app_state::{self, EventWrapper};
 use super::window::WinitUIWindow;
 use crate::dpi::PhysicalPosition;
 use crate::event::{Event, Force, Touch, TouchPhase, WindowEvent};
+use crate::platform::ios::ScreenEdge;
 use crate::platform_impl::platform::DEVICE_ID;
-use crate::window::{WindowAttributes, WindowId as RootWindowId};
+use crate::window::{FrameRateRange, WindowAttributes, WindowId as RootWindowId};
 
 pub struct WinitViewState {
     pinch_gesture_recognizer: RefCell<Option<Retained<UIPinchGestureRecognizer>>>,
     doubletap_gesture_recognizer: RefCell<Option<Retained<UITapGestureRecognizer>>>,
     rotation_gesture_recognizer: RefCell<Option<Retained<UIRotationGestureRecognizer>>>,
     pan_gesture_recognizer: RefCell<Option<Retained<UIPanGestureRecognizer>>>,
+    screen_edge_gesture_recognizers: RefCell<Vec<Retained<UIScreenEdgePanGestureRecognizer>>>,
+    /// The display link asking for the frame rates of `Window::set_preferred_frame_rate_range`.
+    display_link: RefCell<Option<Retained<CADisplayLink>>>,
 
     // for iOS delta references the start of the Gesture
     rotation_last_delta: Cell<CGFloat>,
@@ -89,7 +99,7 @@ declare_class!(
                 mtm,
                 EventWrapper::StaticEvent(Event::WindowEvent {
                     window_id: RootWindowId(window.id()),
-                    event: WindowEvent::Resized(size),
+                    event: WindowEvent::Resized { size, frame: None },
                 }),
             );
         }
@@ -140,7 +150,7 @@ declare_class!(
                 .chain(std::iter::once(EventWrapper::StaticEvent(
                     Event::WindowEvent {
                         window_id,
-                        event: WindowEvent::Resized(size.to_physical(scale_factor)),
+                        event: WindowEvent::Resized { size: size.to_physical(scale_factor), frame: None },
                     },
                 ))),
             );
@@ -221,6 +231,29 @@ declare_class!(
             }
         }
 
+        #[method(screenEdgeGesture:)]
+        fn screen_edge_gesture(&self, recognizer: &UIScreenEdgePanGestureRecognizer) {
+            let window = self.window().unwrap();
+
+            if recognizer.state() == UIGestureRecognizerState::Began {
+                let gesture_event = EventWrapper::StaticEvent(Event::WindowEvent {
+                    window_id: RootWindowId(window.id()),
+                    event: WindowEvent::SystemGestureDeferred {
+                        device_id: DEVICE_ID,
+                    },
+                });
+
+                let mtm = MainThreadMarker::new().unwrap();
+                app_state::handle_nonuser_event(mtm, gesture_event);
+            }
+        }
+
+        #[method(displayLinkFired:)]
+        fn display_link_fired(&self, _display_link: &CADisplayLink) {
+            // Nothing to do, waking up the event loop on every frame of the display is enough for
+            // the redraws requested in the meantime to be emitted.
+        }
+
         #[method(rotationGesture:)]
         fn rotation_gesture(&self, recognizer: &UIRotationGestureRecognizer) {
             let window = self.window().unwrap();
@@ -310,9 +343,23 @@ declare_class!(
                     phase,
                 },
             });
+            let pan_ended = (phase == TouchPhase::Ended).then(|| {
+                let velocity = recognizer.velocityInView(Some(self));
+                let scale_factor = self.contentScaleFactor();
+                EventWrapper::StaticEvent(Event::WindowEvent {
+                    window_id: RootWindowId(window.id()),
+                    event: WindowEvent::PanEnded {
+                        device_id: DEVICE_ID,
+                        velocity: PhysicalPosition::new(
+                            velocity.x * scale_factor,
+                            velocity.y * scale_factor,
+                        ),
+                    },
+                })
+            });
 
             let mtm = MainThreadMarker::new().unwrap();
-            app_state::handle_nonuser_event(mtm, gesture_event);
+            app_state::handle_nonuser_events(mtm, iter::once(gesture_event).chain(pan_ended));
         }
     }
 
@@ -337,6 +384,8 @@ impl WinitView {
             doubletap_gesture_recognizer: RefCell::new(None),
             rotation_gesture_recognizer: RefCell::new(None),
             pan_gesture_recognizer: RefCell::new(None),
+            screen_edge_gesture_recognizers: RefCell::new(Vec::new()),
+            display_link: RefCell::new(None),
 
             rotation_last_delta: Cell::new(0.0),
             pinch_last_delta: Cell::new(0.0),
@@ -350,6 +399,10 @@ impl WinitView {
             this.setContentScaleFactor(scale_factor as _);
         }
 
+        this.recognize_screen_edge_gestures(
+            window_attributes.platform_specific.preferred_screen_edges_deferring_system_gestures,
+        );
+
         this
     }
 
@@ -447,6 +500,65 @@ impl WinitView {
         }
     }
 
+    /// Recognize swipes from the edges for which the system gestures are deferred, which the
+    /// system ignores. The touches are still delivered to the view.
+    pub(crate) fn set_preferred_frame_rate_range(&self, range: Option<FrameRateRange>) {
+        if let Some(display_link) = self.ivars().display_link.take() {
+            // The display link retains the view until it's invalidated.
+            unsafe { display_link.invalidate() };
+        }
+        let Some(range) = range else {
+            return;
+        };
+
+        let display_link =
+            unsafe { CADisplayLink::displayLinkWithTarget_selector(self, sel!(displayLinkFired:)) };
+        // `preferredFrameRateRange` is only available since iOS 15.
+        if display_link.respondsToSelector(sel!(setPreferredFrameRateRange:)) {
+            let range = CAFrameRateRange {
+                minimum: range.minimum,
+                maximum: range.maximum,
+                preferred: range.preferred,
+            };
+            unsafe { display_link.setPreferredFrameRateRange(range) };
+        } else {
+            #[allow(deprecated)]
+            unsafe {
+                display_link.setPreferredFramesPerSecond(range.preferred.round() as NSInteger)
+            };
+        }
+        unsafe {
+            display_link.addToRunLoop_forMode(&NSRunLoop::mainRunLoop(), NSRunLoopCommonModes)
+        };
+        self.ivars().display_link.replace(Some(display_link));
+    }
+
+    pub(crate) fn recognize_screen_edge_gestures(&self, edges: ScreenEdge) {
+        let mtm = MainThreadMarker::from(self);
+        for recognizer in self.ivars().screen_edge_gesture_recognizers.take() {
+            self.removeGestureRecognizer(&recognizer);
+        }
+
+        let mut recognizers = Vec::new();
+        for edge in edges.iter() {
+            let recognizer = unsafe {
+                UIScreenEdgePanGestureRecognizer::initWithTarget_action(
+                    mtm.alloc(),
+                    Some(self),
+                    Some(sel!(screenEdgeGesture:)),
+                )
+            };
+            unsafe {
+                recognizer.setEdges(UIRectEdge(edge.bits().into()));
+                recognizer.setCancelsTouchesInView(false);
+            }
+            recognizer.setDelegate(Some(ProtocolObject::from_ref(self)));
+            self.addGestureRecognizer(&recognizer);
+            recognizers.push(recognizer);
+        }
+        self.ivars().screen_edge_gesture_recognizers.replace(recognizers);
+    }
+
     fn handle_touches(&self, touches: &NSSet<UITouch>) {
         let window = self.window().unwrap();
         let mut touch_events = Vec::new();
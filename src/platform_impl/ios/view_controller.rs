@@ -95,6 +95,15 @@ impl WinitViewController {
         }
     }
 
+    pub(crate) fn prefers_home_indicator_hidden(&self) -> bool {
+        self.ivars().prefers_home_indicator_auto_hidden.get()
+    }
+
+    pub(crate) fn preferred_screen_edges(&self) -> ScreenEdge {
+        let edges = self.ivars().preferred_screen_edges_deferring_system_gestures.get();
+        ScreenEdge::from_bits_truncate(edges.0 as u8)
+    }
+
     pub(crate) fn set_preferred_screen_edges_deferring_system_gestures(&self, val: ScreenEdge) {
         let val = {
             assert_eq!(val.bits() & !ScreenEdge::ALL.bits(), 0, "invalid `ScreenEdge`");
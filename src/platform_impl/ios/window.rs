@@ -22,13 +22,16 @@ use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, P
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
 use crate::event::{Event, WindowEvent};
 use crate::icon::Icon;
+use crate::ime::SurroundingText;
 use crate::platform::ios::{ScreenEdge, StatusBarStyle, ValidOrientations};
 use crate::platform_impl::platform::{
-    app_state, monitor, ActiveEventLoop, Fullscreen, MonitorHandle,
+    app_state, audio, monitor, ActiveEventLoop, Fullscreen, MonitorHandle,
 };
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
-    WindowButtons, WindowId as RootWindowId, WindowLevel,
+    AudioSessionCategory, CapturePolicy, CoordinateTransform, CursorGrabMode, FocusReason,
+    FrameRateRange, ImePurpose, KeyboardHints, PerformanceHint, ResizeDirection, ShareData,
+    TextServicesConfig, Theme, TilingState, UserAttentionType, WindowAttributes, WindowButtons,
+    WindowCapabilities, WindowId as RootWindowId, WindowLevel, WindowVisibility,
 };
 
 declare_class!(
@@ -113,6 +116,12 @@ pub struct Inner {
     gl_or_metal_backed: bool,
 }
 
+impl Drop for Inner {
+    fn drop(&mut self) {
+        self.view.set_preferred_frame_rate_range(None);
+    }
+}
+
 impl Inner {
     pub fn set_title(&self, _title: &str) {
         debug!("`Window::set_title` is ignored on iOS")
@@ -155,6 +164,8 @@ impl Inner {
 
     pub fn pre_present_notify(&self) {}
 
+    pub fn notify_first_frame_presented(&self) {}
+
     pub fn inner_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         let safe_area = self.safe_area_screen_space();
         let position =
@@ -163,6 +174,10 @@ impl Inner {
         Ok(position.to_physical(scale_factor))
     }
 
+    pub fn coordinate_transform(&self) -> CoordinateTransform {
+        CoordinateTransform::from_scale_factor(self.inner_position().ok(), self.scale_factor())
+    }
+
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         let screen_frame = self.screen_frame();
         let position =
@@ -252,10 +267,30 @@ impl Inner {
         debug!("`Window::set_cursor` ignored on iOS")
     }
 
+    pub fn set_cursor_for_device(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _cursor: Cursor,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_pointer_capture(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _capture: bool,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub fn set_cursor_position(&self, _position: Position) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    pub fn cursor_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub fn set_cursor_grab(&self, _: CursorGrabMode) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
@@ -279,6 +314,13 @@ impl Inner {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    pub fn set_hittest_regions(
+        &self,
+        _regions: &[crate::window::Region],
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub fn set_minimized(&self, _minimized: bool) {
         warn!("`Window::set_minimized` is ignored on iOS")
     }
@@ -349,6 +391,11 @@ impl Inner {
         }
     }
 
+    #[inline]
+    pub fn capabilities(&self) -> WindowCapabilities {
+        WindowCapabilities::SET_POSITION
+    }
+
     pub fn set_decorations(&self, _decorations: bool) {}
 
     pub fn is_decorated(&self) -> bool {
@@ -375,10 +422,26 @@ impl Inner {
         warn!("`Window::set_ime_allowed` is ignored on iOS")
     }
 
+    pub fn set_ime_surrounding_text(&self, _text: SurroundingText) {
+        warn!("`Window::set_ime_surrounding_text` is ignored on iOS")
+    }
+
+    pub fn set_text_services(&self, _config: TextServicesConfig) {
+        warn!("`Window::set_text_services` is ignored on iOS")
+    }
+
+    pub fn set_keyboard_hints(&self, _hints: KeyboardHints) {
+        warn!("`Window::set_keyboard_hints` is ignored on iOS")
+    }
+
     pub fn focus_window(&self) {
         warn!("`Window::set_focus` is ignored on iOS")
     }
 
+    pub fn focus_window_with(&self, _reason: FocusReason) {}
+
+    pub fn set_focusable(&self, _focusable: bool) {}
+
     pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {
         warn!("`Window::request_user_attention` is ignored on iOS")
     }
@@ -446,10 +509,41 @@ impl Inner {
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn set_capture_policy(&self, _policy: CapturePolicy) {}
+
+    pub fn set_performance_hint(&self, hint: PerformanceHint) {
+        let mtm = MainThreadMarker::new().unwrap();
+        app_state::AppState::get_mut(mtm).set_performance_hint(hint);
+    }
+
+    pub fn set_preferred_frame_rate_range(&self, range: Option<FrameRateRange>) {
+        self.view.set_preferred_frame_rate_range(range);
+    }
+
+    pub fn share(&self, _data: ShareData) {}
+
+    pub fn present_rgba(
+        &self,
+        _buffer: &[u8],
+        _size: PhysicalSize<u32>,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn hide_to_tray(&self) {}
+
     pub fn has_focus(&self) -> bool {
         self.window.isKeyWindow()
     }
 
+    pub fn visibility(&self) -> WindowVisibility {
+        WindowVisibility::new(true, false, false, self.has_focus())
+    }
+
+    pub fn tiling_state(&self) -> TilingState {
+        TilingState::Floating
+    }
+
     #[inline]
     pub fn set_theme(&self, _theme: Option<Theme>) {
         warn!("`Window::set_theme` is ignored on iOS");
@@ -465,6 +559,9 @@ impl Inner {
     }
 }
 
+/// The part of a window that the closures of `Window::maybe_queue_on_main` run with.
+pub(crate) type MainThreadWindow = Inner;
+
 pub struct Window {
     inner: MainThreadBound<Inner>,
 }
@@ -518,6 +615,10 @@ impl Window {
 
         app_state::set_key_window(mtm, &window);
 
+        if let Some(category) = window_attributes.platform_specific.audio_session_category {
+            audio::set_category(category);
+        }
+
         // Like the Windows and macOS backends, we send a `ScaleFactorChanged` and `Resized`
         // event on window creation if the DPI factor != 1.0
         let scale_factor = view.contentScaleFactor();
@@ -542,7 +643,10 @@ impl Window {
                 .chain(std::iter::once(EventWrapper::StaticEvent(
                     Event::WindowEvent {
                         window_id,
-                        event: WindowEvent::Resized(size.to_physical(scale_factor)),
+                        event: WindowEvent::Resized {
+                            size: size.to_physical(scale_factor),
+                            frame: None,
+                        },
                     },
                 ))),
             );
@@ -604,8 +708,17 @@ impl Inner {
         self.view_controller.set_prefers_home_indicator_auto_hidden(hidden);
     }
 
+    pub fn prefers_home_indicator_hidden(&self) -> bool {
+        self.view_controller.prefers_home_indicator_hidden()
+    }
+
     pub fn set_preferred_screen_edges_deferring_system_gestures(&self, edges: ScreenEdge) {
         self.view_controller.set_preferred_screen_edges_deferring_system_gestures(edges);
+        self.view.recognize_screen_edge_gestures(edges);
+    }
+
+    pub fn preferred_screen_edges_deferring_system_gestures(&self) -> ScreenEdge {
+        self.view_controller.preferred_screen_edges()
     }
 
     pub fn set_prefers_status_bar_hidden(&self, hidden: bool) {
@@ -735,4 +848,5 @@ pub struct PlatformSpecificWindowAttributes {
     pub prefers_status_bar_hidden: bool,
     pub preferred_status_bar_style: StatusBarStyle,
     pub preferred_screen_edges_deferring_system_gestures: ScreenEdge,
+    pub audio_session_category: Option<AudioSessionCategory>,
 }
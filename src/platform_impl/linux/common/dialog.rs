@@ -0,0 +1,67 @@
+//! Message dialogs shown with `zenity`, which works on both X11 and Wayland.
+
+use std::process::{Command, Stdio};
+use std::{io, thread};
+
+use crate::dialog::{DialogButton, DialogSender, MessageButtons, MessageDialog, MessageLevel};
+use crate::error::{ExternalError, NotSupportedError};
+use crate::platform_impl::OsError;
+
+pub fn show_message_dialog(
+    dialog: MessageDialog,
+    sender: DialogSender<DialogButton>,
+) -> Result<(), ExternalError> {
+    let mut command = Command::new("zenity");
+    command.args(["--title", &dialog.title, "--text", &dialog.message, "--no-markup"]);
+    match dialog.buttons {
+        MessageButtons::Ok => {
+            command.arg(match dialog.level {
+                MessageLevel::Info => "--info",
+                MessageLevel::Warning => "--warning",
+                MessageLevel::Error => "--error",
+            });
+        },
+        MessageButtons::OkCancel => {
+            command.args(["--question", "--ok-label=OK", "--cancel-label=Cancel"]);
+        },
+        MessageButtons::YesNo => {
+            command.args(["--question", "--ok-label=Yes", "--cancel-label=No"]);
+        },
+        // Closing the dialog reports the cancel label, so "No" has to be the extra button.
+        MessageButtons::YesNoCancel => {
+            command.args([
+                "--question",
+                "--ok-label=Yes",
+                "--cancel-label=Cancel",
+                "--extra-button=No",
+            ]);
+        },
+    }
+
+    let child = command.stdin(Stdio::null()).stdout(Stdio::piped()).spawn().map_err(|error| {
+        if error.kind() == io::ErrorKind::NotFound {
+            ExternalError::NotSupported(NotSupportedError::new())
+        } else {
+            ExternalError::Os(os_error!(OsError::Misc("failed to run `zenity`")))
+        }
+    })?;
+
+    let buttons = dialog.buttons;
+    let result = thread::Builder::new().name("winit message dialog".to_owned()).spawn(move || {
+        let Ok(output) = child.wait_with_output() else {
+            return;
+        };
+        // The first button exits with 0, and the extra button prints its label.
+        let button = match output.status.code() {
+            Some(0) => buttons.buttons()[0],
+            Some(1) if output.stdout.starts_with(b"No") => DialogButton::No,
+            _ => buttons.dismiss_button(),
+        };
+        sender.send(button);
+    });
+    if result.is_err() {
+        return Err(ExternalError::Os(os_error!(OsError::Misc("failed to spawn a thread"))));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! Watching file descriptors of the application in the event loop, which works on both X11 and
+//! Wayland.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, RawFd};
+
+use calloop::generic::Generic;
+use calloop::{LoopHandle, Mode, PostAction, RegistrationToken};
+
+use crate::error::ExternalError;
+use crate::platform::unix::Interest;
+use crate::platform_impl::OsError;
+
+/// A file descriptor borrowed from the application until it's unregistered.
+struct BorrowedRawFd(RawFd);
+
+impl AsFd for BorrowedRawFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // SAFETY: The caller of `ActiveEventLoopExtUnix::register_fd()` keeps the file descriptor
+        // open while it's registered.
+        unsafe { BorrowedFd::borrow_raw(self.0) }
+    }
+}
+
+/// The sources of the file descriptors registered by the application, by token.
+pub struct FdSources<D: 'static> {
+    handle: LoopHandle<'static, D>,
+    sources: RefCell<HashMap<u64, RegistrationToken>>,
+}
+
+impl<D: 'static> FdSources<D> {
+    pub fn new(handle: LoopHandle<'static, D>) -> Self {
+        Self { handle, sources: Default::default() }
+    }
+
+    /// Watch `fd`, calling `callback` with `token` on each dispatch while it's ready.
+    pub fn register(
+        &self,
+        fd: BorrowedFd<'_>,
+        interest: Interest,
+        token: u64,
+        mut callback: impl FnMut(u64, &mut D) + 'static,
+    ) -> Result<(), ExternalError> {
+        self.unregister(token);
+
+        let interest =
+            calloop::Interest { readable: interest.readable, writable: interest.writable };
+        let source = Generic::new(BorrowedRawFd(fd.as_raw_fd()), interest, Mode::Level);
+        let registration = self
+            .handle
+            .insert_source(source, move |_, _, data| {
+                callback(token, data);
+                Ok(PostAction::Continue)
+            })
+            .map_err(|err| {
+                tracing::warn!("failed to register the file descriptor: {}", err.error);
+                ExternalError::Os(os_error!(OsError::Misc(
+                    "failed to register the file descriptor"
+                )))
+            })?;
+        self.sources.borrow_mut().insert(token, registration);
+
+        Ok(())
+    }
+
+    pub fn unregister(&self, token: u64) {
+        if let Some(registration) = self.sources.borrow_mut().remove(&token) {
+            self.handle.remove(registration);
+        }
+    }
+}
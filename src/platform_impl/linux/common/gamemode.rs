@@ -0,0 +1,76 @@
+//! Registering with Feral GameMode through `libgamemode`, which works on both X11 and Wayland.
+
+use std::ffi::{c_char, c_int, c_void, CStr};
+use std::mem;
+use std::sync::Mutex;
+
+use crate::utils::Lazy;
+use crate::window::PerformanceHint;
+
+type RequestFn = unsafe extern "C" fn() -> c_int;
+type ErrorStringFn = unsafe extern "C" fn() -> *const c_char;
+
+struct GameMode {
+    request_start: RequestFn,
+    request_end: RequestFn,
+    error_string: ErrorStringFn,
+}
+
+impl GameMode {
+    fn load() -> Option<Self> {
+        // The library is kept loaded for the rest of the process, like the client header of
+        // GameMode does.
+        let library =
+            unsafe { libc::dlopen(b"libgamemode.so.0\0".as_ptr().cast(), libc::RTLD_NOW) };
+        if library.is_null() {
+            return None;
+        }
+
+        let symbol = |name: &[u8]| {
+            let symbol = unsafe { libc::dlsym(library, name.as_ptr().cast()) };
+            (!symbol.is_null()).then_some(symbol)
+        };
+        let request_start = symbol(b"real_gamemode_request_start\0")?;
+        let request_end = symbol(b"real_gamemode_request_end\0")?;
+        let error_string = symbol(b"real_gamemode_error_string\0")?;
+        unsafe {
+            Some(Self {
+                request_start: mem::transmute::<*mut c_void, RequestFn>(request_start),
+                request_end: mem::transmute::<*mut c_void, RequestFn>(request_end),
+                error_string: mem::transmute::<*mut c_void, ErrorStringFn>(error_string),
+            })
+        }
+    }
+}
+
+static GAMEMODE: Lazy<Option<GameMode>> = Lazy::new(GameMode::load);
+static REGISTERED: Mutex<bool> = Mutex::new(false);
+
+pub fn set_performance_hint(hint: PerformanceHint) {
+    let register = hint != PerformanceHint::Default;
+    let Some(gamemode) = &*GAMEMODE else {
+        if register {
+            tracing::warn!("`libgamemode.so.0` isn't installed, ignoring the performance hint");
+        }
+        return;
+    };
+
+    let mut registered = REGISTERED.lock().unwrap();
+    if *registered == register {
+        return;
+    }
+
+    let result = unsafe {
+        if register {
+            (gamemode.request_start)()
+        } else {
+            (gamemode.request_end)()
+        }
+    };
+    if result < 0 {
+        let error = unsafe { CStr::from_ptr((gamemode.error_string)()) };
+        tracing::warn!("Failed to update the GameMode registration: {}", error.to_string_lossy());
+    } else {
+        *registered = register;
+    }
+}
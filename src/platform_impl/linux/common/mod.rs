@@ -1 +1,7 @@
+pub mod dialog;
+pub mod fd;
+pub mod gamemode;
+pub mod session;
+#[cfg(target_os = "linux")]
+pub mod signal;
 pub mod xkb;
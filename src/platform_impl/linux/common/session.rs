@@ -0,0 +1,456 @@
+//! Taking part in ending the session through the X Session Management Protocol of `libSM`, which
+//! works on both X11 and Wayland.
+//!
+//! The session manager asks every client before the user logs out or shuts down, and clients that
+//! requested to interact with the user may cancel it.
+
+use std::cell::{Cell, RefCell};
+use std::ffi::{c_char, c_int, c_ulong, c_void, CStr};
+use std::os::fd::{AsFd, BorrowedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::{env, mem, ptr};
+
+use calloop::generic::Generic;
+use calloop::{Interest, LoopHandle, Mode, PostAction};
+
+use crate::event::CloseResponse;
+use crate::utils::Lazy;
+
+type SmcConn = *mut c_void;
+type IceConn = *mut c_void;
+type SmPointer = *mut c_void;
+type Bool = c_int;
+
+type SaveYourselfProc = unsafe extern "C" fn(SmcConn, SmPointer, c_int, Bool, c_int, Bool);
+/// The callbacks for dying, completing a save, cancelling the shutdown and interacting.
+type Proc = unsafe extern "C" fn(SmcConn, SmPointer);
+type IoErrorHandler = unsafe extern "C" fn(IceConn);
+
+type OpenConnectionFn = unsafe extern "C" fn(
+    *mut c_char,
+    SmPointer,
+    c_int,
+    c_int,
+    c_ulong,
+    *mut SmcCallbacks,
+    *const c_char,
+    *mut *mut c_char,
+    c_int,
+    *mut c_char,
+) -> SmcConn;
+type CloseConnectionFn = unsafe extern "C" fn(SmcConn, c_int, *mut *mut c_char) -> c_int;
+type SetPropertiesFn = unsafe extern "C" fn(SmcConn, c_int, *mut *mut SmProp);
+type InteractRequestFn = unsafe extern "C" fn(SmcConn, c_int, Proc, SmPointer) -> c_int;
+type DoneFn = unsafe extern "C" fn(SmcConn, Bool);
+type GetIceConnectionFn = unsafe extern "C" fn(SmcConn) -> IceConn;
+type ConnectionNumberFn = unsafe extern "C" fn(IceConn) -> c_int;
+type ProcessMessagesFn = unsafe extern "C" fn(IceConn, *mut c_void, *mut Bool) -> c_int;
+type SetIoErrorHandlerFn = unsafe extern "C" fn(Option<IoErrorHandler>) -> Option<IoErrorHandler>;
+
+const SAVE_YOURSELF_PROC_MASK: c_ulong = 1 << 0;
+const DIE_PROC_MASK: c_ulong = 1 << 1;
+const SAVE_COMPLETE_PROC_MASK: c_ulong = 1 << 2;
+const SHUTDOWN_CANCELLED_PROC_MASK: c_ulong = 1 << 3;
+const INTERACT_STYLE_NONE: c_int = 0;
+const DIALOG_NORMAL: c_int = 1;
+const RESTART_NEVER: u8 = 3;
+const PROCESS_MESSAGES_SUCCESS: c_int = 0;
+
+#[repr(C)]
+struct Callback<F> {
+    callback: F,
+    client_data: SmPointer,
+}
+
+#[repr(C)]
+struct SmcCallbacks {
+    save_yourself: Callback<SaveYourselfProc>,
+    die: Callback<Proc>,
+    save_complete: Callback<Proc>,
+    shutdown_cancelled: Callback<Proc>,
+}
+
+#[repr(C)]
+struct SmPropValue {
+    length: c_int,
+    value: SmPointer,
+}
+
+#[repr(C)]
+struct SmProp {
+    name: *mut c_char,
+    type_: *mut c_char,
+    num_vals: c_int,
+    vals: *mut SmPropValue,
+}
+
+struct LibSm {
+    open_connection: OpenConnectionFn,
+    close_connection: CloseConnectionFn,
+    set_properties: SetPropertiesFn,
+    interact_request: InteractRequestFn,
+    interact_done: DoneFn,
+    save_yourself_done: DoneFn,
+    get_ice_connection: GetIceConnectionFn,
+    connection_number: ConnectionNumberFn,
+    process_messages: ProcessMessagesFn,
+}
+
+impl LibSm {
+    fn load() -> Option<Self> {
+        // The libraries are kept loaded for the rest of the process, as the I/O error handler of
+        // `libICE` is global.
+        let open = |name: &[u8]| {
+            let library = unsafe { libc::dlopen(name.as_ptr().cast(), libc::RTLD_NOW) };
+            (!library.is_null()).then_some(library)
+        };
+        let sm = open(b"libSM.so.6\0")?;
+        let ice = open(b"libICE.so.6\0")?;
+        let symbol = |library, name: &[u8]| {
+            let symbol = unsafe { libc::dlsym(library, name.as_ptr().cast()) };
+            (!symbol.is_null()).then_some(symbol)
+        };
+
+        unsafe {
+            // The default handler exits the process when the session manager goes away.
+            let set_io_error_handler = mem::transmute::<*mut c_void, SetIoErrorHandlerFn>(symbol(
+                ice,
+                b"IceSetIOErrorHandler\0",
+            )?);
+            let previous = set_io_error_handler(None);
+            let default = set_io_error_handler(previous);
+            let address = |handler: Option<IoErrorHandler>| handler.map(|f| f as *const ());
+            if address(previous) == address(default) {
+                set_io_error_handler(Some(ignore_io_error));
+            }
+
+            Some(Self {
+                open_connection: mem::transmute::<*mut c_void, OpenConnectionFn>(symbol(
+                    sm,
+                    b"SmcOpenConnection\0",
+                )?),
+                close_connection: mem::transmute::<*mut c_void, CloseConnectionFn>(symbol(
+                    sm,
+                    b"SmcCloseConnection\0",
+                )?),
+                set_properties: mem::transmute::<*mut c_void, SetPropertiesFn>(symbol(
+                    sm,
+                    b"SmcSetProperties\0",
+                )?),
+                interact_request: mem::transmute::<*mut c_void, InteractRequestFn>(symbol(
+                    sm,
+                    b"SmcInteractRequest\0",
+                )?),
+                interact_done: mem::transmute::<*mut c_void, DoneFn>(symbol(
+                    sm,
+                    b"SmcInteractDone\0",
+                )?),
+                save_yourself_done: mem::transmute::<*mut c_void, DoneFn>(symbol(
+                    sm,
+                    b"SmcSaveYourselfDone\0",
+                )?),
+                get_ice_connection: mem::transmute::<*mut c_void, GetIceConnectionFn>(symbol(
+                    sm,
+                    b"SmcGetIceConnection\0",
+                )?),
+                connection_number: mem::transmute::<*mut c_void, ConnectionNumberFn>(symbol(
+                    ice,
+                    b"IceConnectionNumber\0",
+                )?),
+                process_messages: mem::transmute::<*mut c_void, ProcessMessagesFn>(symbol(
+                    ice,
+                    b"IceProcessMessages\0",
+                )?),
+            })
+        }
+    }
+}
+
+static LIBSM: Lazy<Option<LibSm>> = Lazy::new(LibSm::load);
+
+unsafe extern "C" fn ignore_io_error(_: IceConn) {}
+
+/// A message of the session manager, queued by the callbacks while `libSM` processes messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Message {
+    SaveYourself { shutdown: bool, interact: bool },
+    Interact,
+    Die,
+    ShutdownCancelled,
+}
+
+type Messages = RefCell<Vec<Message>>;
+
+fn push(client_data: SmPointer, message: Message) {
+    // SAFETY: The client data is the message queue of the client, which outlives the connection.
+    let messages = unsafe { &*(client_data as *const Messages) };
+    messages.borrow_mut().push(message);
+}
+
+unsafe extern "C" fn save_yourself(
+    _: SmcConn,
+    client_data: SmPointer,
+    _save_type: c_int,
+    shutdown: Bool,
+    interact_style: c_int,
+    _fast: Bool,
+) {
+    let message = Message::SaveYourself {
+        shutdown: shutdown != 0,
+        interact: interact_style != INTERACT_STYLE_NONE,
+    };
+    push(client_data, message);
+}
+
+unsafe extern "C" fn interact(_: SmcConn, client_data: SmPointer) {
+    push(client_data, Message::Interact);
+}
+
+unsafe extern "C" fn die(_: SmcConn, client_data: SmPointer) {
+    push(client_data, Message::Die);
+}
+
+unsafe extern "C" fn save_complete(_: SmcConn, _: SmPointer) {}
+
+unsafe extern "C" fn shutdown_cancelled(_: SmcConn, client_data: SmPointer) {
+    push(client_data, Message::ShutdownCancelled);
+}
+
+/// Where the client is in saving its state for the session manager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Save {
+    Idle,
+    /// Waiting for the turn to interact with the user.
+    WaitingToInteract,
+    /// The application is asked whether to end the session.
+    Interacting,
+}
+
+struct Client {
+    lib: &'static LibSm,
+    connection: SmcConn,
+    ice_connection: IceConn,
+    save: Cell<Save>,
+    /// Boxed, so that its address stays the same for the callbacks.
+    messages: Box<Messages>,
+}
+
+impl Client {
+    fn open(lib: &'static LibSm) -> Option<Self> {
+        // Not running in a session with a session manager.
+        env::var_os("SESSION_MANAGER")?;
+
+        let messages = Box::<Messages>::default();
+        let client_data = &*messages as *const Messages as SmPointer;
+        let mut callbacks = SmcCallbacks {
+            save_yourself: Callback { callback: save_yourself, client_data },
+            die: Callback { callback: die, client_data },
+            save_complete: Callback { callback: save_complete, client_data },
+            shutdown_cancelled: Callback { callback: shutdown_cancelled, client_data },
+        };
+        let mask = SAVE_YOURSELF_PROC_MASK
+            | DIE_PROC_MASK
+            | SAVE_COMPLETE_PROC_MASK
+            | SHUTDOWN_CANCELLED_PROC_MASK;
+        let mut client_id = ptr::null_mut();
+        let mut error = [0 as c_char; 256];
+        let connection = unsafe {
+            (lib.open_connection)(
+                ptr::null_mut(),
+                ptr::null_mut(),
+                1,
+                0,
+                mask,
+                &mut callbacks,
+                ptr::null(),
+                &mut client_id,
+                error.len() as c_int,
+                error.as_mut_ptr(),
+            )
+        };
+        if connection.is_null() {
+            let error = unsafe { CStr::from_ptr(error.as_ptr()) };
+            tracing::warn!("failed to connect to the session manager: {}", error.to_string_lossy());
+            return None;
+        }
+        unsafe { libc::free(client_id.cast()) };
+
+        let ice_connection = unsafe { (lib.get_ice_connection)(connection) };
+        let client =
+            Self { lib, connection, ice_connection, save: Cell::new(Save::Idle), messages };
+        client.set_properties();
+        Some(client)
+    }
+
+    /// Set the properties the protocol requires, and ask not to be restarted with the session, as
+    /// applications don't restore their state from the session manager.
+    fn set_properties(&self) {
+        let program = env::current_exe().map(PathBuf::into_os_string).unwrap_or_default();
+        let user =
+            env::var_os("USER").unwrap_or_else(|| unsafe { libc::getuid() }.to_string().into());
+        let mut program = program.as_bytes().to_vec();
+        let mut user = user.as_bytes().to_vec();
+        let mut restart_style = [RESTART_NEVER];
+
+        let value = |bytes: &mut [u8]| SmPropValue {
+            length: bytes.len() as c_int,
+            value: bytes.as_mut_ptr().cast(),
+        };
+        let mut values = [value(&mut program), value(&mut user), value(&mut restart_style)];
+        let (program, rest) = values.split_at_mut(1);
+        let (user, restart_style) = rest.split_at_mut(1);
+        let prop = |name: &'static [u8], type_: &'static [u8], vals: &mut [SmPropValue]| SmProp {
+            name: name.as_ptr() as *mut c_char,
+            type_: type_.as_ptr() as *mut c_char,
+            num_vals: vals.len() as c_int,
+            vals: vals.as_mut_ptr(),
+        };
+        let mut props = [
+            prop(b"Program\0", b"ARRAY8\0", program),
+            prop(b"CloneCommand\0", b"LISTofARRAY8\0", program),
+            prop(b"RestartCommand\0", b"LISTofARRAY8\0", program),
+            prop(b"UserID\0", b"ARRAY8\0", user),
+            prop(b"RestartStyleHint\0", b"CARD8\0", restart_style),
+        ];
+        let mut props: Vec<*mut SmProp> =
+            props.iter_mut().map(|prop| prop as *mut SmProp).collect();
+        unsafe {
+            (self.lib.set_properties)(self.connection, props.len() as c_int, props.as_mut_ptr())
+        };
+    }
+
+    fn save_yourself_done(&self) {
+        self.save.set(Save::Idle);
+        unsafe { (self.lib.save_yourself_done)(self.connection, 1) };
+    }
+
+    /// Handle the messages of the session manager, returning `false` once the connection is lost.
+    fn dispatch(self: &Rc<Self>, mut callback: impl FnMut(SessionEvent)) -> bool {
+        let status = unsafe {
+            (self.lib.process_messages)(self.ice_connection, ptr::null_mut(), ptr::null_mut())
+        };
+
+        for message in self.messages.take() {
+            match message {
+                // The application is only asked before the session ends, and needs to be allowed
+                // to interact with the user to cancel it.
+                Message::SaveYourself { shutdown: true, interact: true } => {
+                    let client_data = &*self.messages as *const Messages as SmPointer;
+                    let requested = unsafe {
+                        (self.lib.interact_request)(
+                            self.connection,
+                            DIALOG_NORMAL,
+                            interact,
+                            client_data,
+                        )
+                    };
+                    if requested != 0 {
+                        self.save.set(Save::WaitingToInteract);
+                    } else {
+                        self.save_yourself_done();
+                    }
+                },
+                Message::SaveYourself { .. } => self.save_yourself_done(),
+                Message::Interact => {
+                    self.save.set(Save::Interacting);
+                    let end = EndSession { client: self.clone(), cancel: false };
+                    callback(SessionEvent::EndRequested(end));
+                },
+                Message::Die => callback(SessionEvent::End),
+                Message::ShutdownCancelled => {
+                    if self.save.get() != Save::Idle {
+                        self.save_yourself_done();
+                    }
+                },
+            }
+        }
+
+        status == PROCESS_MESSAGES_SUCCESS
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        if self.save.get() != Save::Idle {
+            self.save_yourself_done();
+        }
+        unsafe { (self.lib.close_connection)(self.connection, 0, ptr::null_mut()) };
+    }
+}
+
+/// The connection as a source of the event loop.
+struct Connection(Rc<Client>);
+
+impl AsFd for Connection {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        // SAFETY: The file descriptor is open until the connection is closed, which happens once
+        // the client is dropped.
+        unsafe { BorrowedFd::borrow_raw((self.0.lib.connection_number)(self.0.ice_connection)) }
+    }
+}
+
+pub enum SessionEvent {
+    /// The user is about to log out or shut down, which the application may cancel.
+    EndRequested(EndSession),
+    /// The session ends, and the application must exit.
+    End,
+}
+
+/// The session manager waits for the application to allow ending the session, until this is
+/// dropped.
+pub struct EndSession {
+    client: Rc<Client>,
+    cancel: bool,
+}
+
+impl EndSession {
+    /// Ask the application whether to end the session.
+    pub fn request_close(mut self, close_requested_by_os: impl FnOnce(CloseResponse)) {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        close_requested_by_os(CloseResponse::new(Arc::downgrade(&cancelled)));
+        self.cancel = cancelled.load(Ordering::Relaxed);
+    }
+}
+
+impl Drop for EndSession {
+    fn drop(&mut self) {
+        // The session manager may have cancelled the shutdown in the meantime.
+        if self.client.save.get() == Save::Interacting {
+            unsafe { (self.client.lib.interact_done)(self.client.connection, self.cancel as Bool) };
+            self.client.save_yourself_done();
+        }
+    }
+}
+
+/// Receive the requests of the session manager with a source of the event loop, calling
+/// `callback` for each of them.
+///
+/// Does nothing outside of a session with a session manager, or if `libSM` isn't installed.
+pub fn insert_source<D: 'static>(
+    handle: &LoopHandle<'static, D>,
+    mut callback: impl FnMut(SessionEvent, &mut D) + 'static,
+) {
+    let Some(lib) = &*LIBSM else {
+        return;
+    };
+    let Some(client) = Client::open(lib) else {
+        return;
+    };
+
+    let source = Generic::new(Connection(Rc::new(client)), Interest::READ, Mode::Level);
+    let result = handle.insert_source(source, move |_, connection, data| {
+        if connection.as_ref().0.dispatch(|event| callback(event, data)) {
+            Ok(PostAction::Continue)
+        } else {
+            tracing::warn!("lost the connection to the session manager");
+            Ok(PostAction::Remove)
+        }
+    });
+    if let Err(err) = result {
+        tracing::warn!("failed to receive the requests of the session manager: {}", err.error);
+    }
+}
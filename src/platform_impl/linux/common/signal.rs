@@ -0,0 +1,152 @@
+//! Receiving signals in the event loop through `signalfd`, which works on both X11 and Wayland.
+
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd};
+use std::{io, mem, ptr};
+
+use calloop::generic::Generic;
+use calloop::{Interest, LoopHandle, Mode, PostAction};
+use libc::{c_int, sigset_t};
+
+use crate::event::Signal;
+
+/// Signals blocked on the thread of the event loop, so that they are read from a file descriptor
+/// instead of interrupting the process.
+///
+/// The signals are unblocked again when dropped.
+struct SignalFd {
+    fd: OwnedFd,
+    blocked: sigset_t,
+}
+
+impl SignalFd {
+    /// Take over the signals which weren't blocked or handled by the application yet, returning
+    /// `None` if there are none.
+    fn new(signals: &[c_int]) -> io::Result<Option<Self>> {
+        let mut current = empty_set();
+        let result = unsafe { libc::pthread_sigmask(libc::SIG_BLOCK, ptr::null(), &mut current) };
+        if result != 0 {
+            return Err(io::Error::from_raw_os_error(result));
+        }
+
+        let mut blocked = empty_set();
+        let mut any = false;
+        for &signal in signals {
+            let mut action: libc::sigaction = unsafe { mem::zeroed() };
+            if unsafe { libc::sigaction(signal, ptr::null(), &mut action) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            if action.sa_sigaction == libc::SIG_DFL
+                && unsafe { libc::sigismember(&current, signal) } == 0
+            {
+                unsafe { libc::sigaddset(&mut blocked, signal) };
+                any = true;
+            }
+        }
+        if !any {
+            return Ok(None);
+        }
+
+        let fd = unsafe { libc::signalfd(-1, &blocked, libc::SFD_NONBLOCK | libc::SFD_CLOEXEC) };
+        if fd < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+        // Threads spawned from now on inherit the mask, so the signals aren't delivered to them.
+        let result = unsafe { libc::pthread_sigmask(libc::SIG_BLOCK, &blocked, ptr::null_mut()) };
+        if result != 0 {
+            return Err(io::Error::from_raw_os_error(result));
+        }
+
+        Ok(Some(Self { fd, blocked }))
+    }
+
+    /// Read the signals received since the last call.
+    fn read(&self) -> Vec<c_int> {
+        let mut signals = Vec::new();
+        loop {
+            let mut info: libc::signalfd_siginfo = unsafe { mem::zeroed() };
+            let size = mem::size_of::<libc::signalfd_siginfo>();
+            let read =
+                unsafe { libc::read(self.fd.as_raw_fd(), ptr::addr_of_mut!(info).cast(), size) };
+            if read != size as isize {
+                return signals;
+            }
+            signals.push(info.ssi_signo as c_int);
+        }
+    }
+}
+
+impl AsFd for SignalFd {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        self.fd.as_fd()
+    }
+}
+
+impl Drop for SignalFd {
+    fn drop(&mut self) {
+        // Signals that arrived in the meantime are delivered with their default disposition.
+        unsafe { libc::pthread_sigmask(libc::SIG_UNBLOCK, &self.blocked, ptr::null_mut()) };
+    }
+}
+
+fn to_raw(signal: Signal) -> c_int {
+    match signal {
+        Signal::Interrupt => libc::SIGINT,
+        Signal::Terminate => libc::SIGTERM,
+        Signal::Hangup => libc::SIGHUP,
+        Signal::Quit => libc::SIGQUIT,
+        Signal::User1 => libc::SIGUSR1,
+        Signal::User2 => libc::SIGUSR2,
+    }
+}
+
+fn from_raw(signal: c_int) -> Option<Signal> {
+    Some(match signal {
+        libc::SIGINT => Signal::Interrupt,
+        libc::SIGTERM => Signal::Terminate,
+        libc::SIGHUP => Signal::Hangup,
+        libc::SIGQUIT => Signal::Quit,
+        libc::SIGUSR1 => Signal::User1,
+        libc::SIGUSR2 => Signal::User2,
+        _ => return None,
+    })
+}
+
+fn empty_set() -> sigset_t {
+    let mut set: sigset_t = unsafe { mem::zeroed() };
+    unsafe { libc::sigemptyset(&mut set) };
+    set
+}
+
+/// Receive `signals` with a source of the event loop, calling `callback` with the signals that
+/// arrived.
+///
+/// Signals for which the application installed a handler or which it blocked are left alone.
+pub fn insert_source<D: 'static>(
+    handle: &LoopHandle<'static, D>,
+    signals: &[Signal],
+    mut callback: impl FnMut(&[Signal], &mut D) + 'static,
+) {
+    let signals: Vec<_> = signals.iter().copied().map(to_raw).collect();
+    let signal_fd = match SignalFd::new(&signals) {
+        Ok(Some(signal_fd)) => signal_fd,
+        Ok(None) => return,
+        Err(err) => {
+            tracing::warn!("failed to receive signals in the event loop: {err}");
+            return;
+        },
+    };
+
+    let source = Generic::new(signal_fd, Interest::READ, Mode::Level);
+    let result = handle.insert_source(source, move |_, signal_fd, data| {
+        let signals: Vec<_> = signal_fd.as_ref().read().into_iter().filter_map(from_raw).collect();
+        if !signals.is_empty() {
+            callback(&signals, data);
+        }
+        Ok(PostAction::Continue)
+    });
+    if let Err(err) = result {
+        tracing::warn!("failed to receive signals in the event loop: {}", err.error);
+    }
+}
@@ -4,6 +4,7 @@
 compile_error!("Please select a feature to build for unix: `x11`, `wayland`");
 
 use std::collections::VecDeque;
+use std::ops::RangeInclusive;
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
 use std::sync::Arc;
 use std::time::Duration;
@@ -12,28 +13,45 @@ use std::{env, fmt};
 use std::{ffi::CStr, mem::MaybeUninit, os::raw::*, sync::Mutex};
 
 use crate::application::ApplicationHandler;
+use crate::ime::SurroundingText;
 use crate::platform::pump_events::PumpStatus;
+use crate::platform::unix::Interest;
 #[cfg(x11_platform)]
 use crate::utils::Lazy;
 use smol_str::SmolStr;
 
 #[cfg(x11_platform)]
 use self::x11::{X11Error, XConnection, XError, XNotSupported};
+use crate::dialog::{DialogButton, DialogSender, MessageDialog};
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
-use crate::error::{EventLoopError, ExternalError, NotSupportedError, OsError as RootOsError};
-use crate::event_loop::{AsyncRequestSerial, ControlFlow, DeviceEvents, EventLoopClosed};
+use crate::error::{
+    BackendError, EventLoopError, ExternalError, NotSupportedError, OsError as RootOsError,
+};
+use crate::event::{Seat, SeatId, Signal};
+use crate::event_loop::{
+    AsyncRequestSerial, Backend as RootBackend, ControlFlow, DeviceEvents, EventLoopClosed,
+};
 use crate::icon::Icon;
 use crate::keyboard::Key;
+use crate::locale::LanguageTag;
+use crate::monitor::Rect;
+use crate::notification::{NotificationAttributes, NotificationId};
 #[cfg(x11_platform)]
-use crate::platform::x11::{WindowType as XWindowType, XlibErrorHook};
+use crate::platform::x11::{
+    CompositorBypass, VisualSelector, WindowType as XWindowType, XlibErrorHook,
+};
+use crate::session::{SessionCapabilities, SessionInfo, SessionKind};
 use crate::window::{
-    ActivationToken, Cursor, CursorGrabMode, CustomCursor, CustomCursorSource, ImePurpose,
-    ResizeDirection, Theme, UserAttentionType, WindowAttributes, WindowButtons, WindowLevel,
+    ActivationToken, CapturePolicy, CoordinateTransform, Cursor, CursorGrabMode, CustomCursor,
+    CustomCursorSource, FocusReason, FrameRateRange, ImePurpose, KeyboardHints, PerformanceHint,
+    Region, ResizeDirection, ShareData, TextServicesConfig, Theme, TilingState, UserAttentionType,
+    WindowAttributes, WindowButtons, WindowCapabilities, WindowLevel, WindowVisibility,
 };
 
 pub(crate) use self::common::xkb::{physicalkey_to_scancode, scancode_to_physicalkey};
 pub(crate) use crate::cursor::OnlyCursorImageSource as PlatformCustomCursorSource;
 pub(crate) use crate::icon::RgbaIcon as PlatformIcon;
+pub(crate) use crate::notification::NoNotification as PlatformNotification;
 pub(crate) use crate::platform_impl::Fullscreen;
 
 pub(crate) mod common;
@@ -50,10 +68,12 @@ pub(crate) enum Backend {
     Wayland,
 }
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) forced_backend: Option<Backend>,
+    pub(crate) backend_preference: Vec<RootBackend>,
     pub(crate) any_thread: bool,
+    pub(crate) signals: Vec<Signal>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -74,15 +94,26 @@ pub struct PlatformSpecificWindowAttributes {
     pub activation_token: Option<ActivationToken>,
     #[cfg(x11_platform)]
     pub x11: X11WindowAttributes,
+    #[cfg(wayland_platform)]
+    pub wayland: WaylandWindowAttributes,
+}
+
+#[derive(Clone, Debug, Default)]
+#[cfg(wayland_platform)]
+pub struct WaylandWindowAttributes {
+    /// The themed icon of the window.
+    pub icon_name: Option<String>,
 }
 
 #[derive(Clone, Debug)]
 #[cfg(x11_platform)]
 pub struct X11WindowAttributes {
     pub visual_id: Option<x11rb::protocol::xproto::Visualid>,
+    pub visual: Option<VisualSelector>,
     pub screen_id: Option<i32>,
     pub base_size: Option<Size>,
     pub override_redirect: bool,
+    pub bypass_compositor: CompositorBypass,
     pub x11_window_types: Vec<XWindowType>,
 
     /// The parent window to embed this window into.
@@ -98,12 +129,16 @@ impl Default for PlatformSpecificWindowAttributes {
             #[cfg(x11_platform)]
             x11: X11WindowAttributes {
                 visual_id: None,
+                visual: None,
                 screen_id: None,
                 base_size: None,
                 override_redirect: false,
+                bypass_compositor: CompositorBypass::NoPreference,
                 x11_window_types: vec![XWindowType::Normal],
                 embed_window: None,
             },
+            #[cfg(wayland_platform)]
+            wayland: WaylandWindowAttributes::default(),
         }
     }
 }
@@ -133,6 +168,9 @@ impl fmt::Display for OsError {
     }
 }
 
+/// The part of a window that the closures of `Window::maybe_queue_on_main` run with.
+pub(crate) type MainThreadWindow = Window;
+
 pub(crate) enum Window {
     #[cfg(x11_platform)]
     X(x11::Window),
@@ -176,6 +214,15 @@ impl DeviceId {
         #[cfg(all(not(wayland_platform), x11_platform))]
         return DeviceId::X(unsafe { x11::DeviceId::dummy() });
     }
+
+    pub fn seat_id(&self) -> Option<SeatId> {
+        match self {
+            #[cfg(x11_platform)]
+            DeviceId::X(id) => id.seat_id(),
+            #[cfg(wayland_platform)]
+            DeviceId::Wayland(id) => id.seat_id(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
@@ -235,11 +282,31 @@ impl MonitorHandle {
         x11_or_wayland!(match self; MonitorHandle(m) => m.position())
     }
 
+    #[inline]
+    pub fn work_area(&self) -> Rect {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.work_area())
+    }
+
+    #[inline]
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.icc_profile())
+    }
+
     #[inline]
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
         x11_or_wayland!(match self; MonitorHandle(m) => m.refresh_rate_millihertz())
     }
 
+    #[inline]
+    pub fn supports_variable_refresh(&self) -> bool {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.supports_variable_refresh())
+    }
+
+    #[inline]
+    pub fn is_mirrored(&self) -> bool {
+        x11_or_wayland!(match self; MonitorHandle(m) => m.is_mirrored())
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         x11_or_wayland!(match self; MonitorHandle(m) => m.scale_factor() as _)
@@ -275,6 +342,11 @@ impl VideoModeHandle {
         x11_or_wayland!(match self; VideoModeHandle(m) => m.refresh_rate_millihertz())
     }
 
+    #[inline]
+    pub fn refresh_rate_range(&self) -> RangeInclusive<u32> {
+        x11_or_wayland!(match self; VideoModeHandle(m) => m.refresh_rate_range())
+    }
+
     #[inline]
     pub fn monitor(&self) -> MonitorHandle {
         x11_or_wayland!(match self; VideoModeHandle(m) => m.monitor(); as MonitorHandle)
@@ -347,6 +419,10 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.inner_position())
     }
 
+    pub fn coordinate_transform(&self) -> CoordinateTransform {
+        CoordinateTransform::from_scale_factor(self.inner_position().ok(), self.scale_factor())
+    }
+
     #[inline]
     pub fn set_outer_position(&self, position: Position) {
         x11_or_wayland!(match self; Window(w) => w.set_outer_position(position))
@@ -417,11 +493,67 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_cursor(cursor))
     }
 
+    #[inline]
+    pub fn cursor_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        x11_or_wayland!(match self; Window(window) => window.cursor_position())
+    }
+
+    #[inline]
+    pub fn set_cursor_for_device(
+        &self,
+        device_id: crate::event::DeviceId,
+        cursor: Cursor,
+    ) -> Result<(), ExternalError> {
+        match (self, device_id.0) {
+            #[cfg(x11_platform)]
+            (Window::X(ref window), DeviceId::X(device_id)) => {
+                window.set_cursor_for_device(device_id, cursor)
+            },
+            #[cfg(wayland_platform)]
+            (Window::Wayland(ref window), DeviceId::Wayland(device_id)) => {
+                window.set_cursor_for_device(device_id, cursor);
+                Ok(())
+            },
+            #[cfg(all(x11_platform, wayland_platform))]
+            _ => Err(ExternalError::NotSupported(NotSupportedError::new())),
+        }
+    }
+
+    #[inline]
+    pub fn set_pointer_capture(
+        &self,
+        device_id: crate::event::DeviceId,
+        capture: bool,
+    ) -> Result<(), ExternalError> {
+        match (self, device_id.0) {
+            #[cfg(x11_platform)]
+            (Window::X(ref window), DeviceId::X(device_id)) => {
+                window.set_pointer_capture(device_id, capture)
+            },
+            // Only implicit grabs while a button is pressed are possible.
+            #[cfg(wayland_platform)]
+            _ => {
+                let _ = capture;
+                Err(ExternalError::NotSupported(NotSupportedError::new()))
+            },
+        }
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         x11_or_wayland!(match self; Window(window) => window.set_cursor_grab(mode))
     }
 
+    #[cfg(wayland_platform)]
+    #[inline]
+    pub fn set_cursor_grab_regions(&self, regions: Option<Vec<Region>>) {
+        match self {
+            #[cfg(x11_platform)]
+            Window::X(_) => (),
+            Window::Wayland(ref window) => window.set_cursor_grab_regions(regions),
+        }
+    }
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         x11_or_wayland!(match self; Window(window) => window.set_cursor_visible(visible))
@@ -447,6 +579,11 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_cursor_hittest(hittest))
     }
 
+    #[inline]
+    pub fn set_hittest_regions(&self, regions: &[Region]) -> Result<(), ExternalError> {
+        x11_or_wayland!(match self; Window(w) => w.set_hittest_regions(regions))
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         x11_or_wayland!(match self; Window(w) => w.scale_factor())
@@ -487,6 +624,21 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_fullscreen(monitor))
     }
 
+    #[inline]
+    pub fn is_wayland(&self) -> bool {
+        match *self {
+            #[cfg(wayland_platform)]
+            Window::Wayland(_) => true,
+            #[cfg(x11_platform)]
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn capabilities(&self) -> WindowCapabilities {
+        x11_or_wayland!(match self; Window(w) => w.capabilities())
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         x11_or_wayland!(match self; Window(w) => w.set_decorations(decorations))
@@ -527,11 +679,36 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.set_ime_purpose(purpose))
     }
 
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, text: SurroundingText) {
+        x11_or_wayland!(match self; Window(w) => w.set_ime_surrounding_text(text))
+    }
+
+    #[inline]
+    pub fn set_text_services(&self, config: TextServicesConfig) {
+        x11_or_wayland!(match self; Window(w) => w.set_text_services(config))
+    }
+
+    #[inline]
+    pub fn set_keyboard_hints(&self, hints: KeyboardHints) {
+        x11_or_wayland!(match self; Window(w) => w.set_keyboard_hints(hints))
+    }
+
     #[inline]
     pub fn focus_window(&self) {
         x11_or_wayland!(match self; Window(w) => w.focus_window())
     }
 
+    #[inline]
+    pub fn focus_window_with(&self, reason: FocusReason) {
+        x11_or_wayland!(match self; Window(w) => w.focus_window_with(reason))
+    }
+
+    #[inline]
+    pub fn set_focusable(&self, focusable: bool) {
+        x11_or_wayland!(match self; Window(w) => w.set_focusable(focusable))
+    }
+
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         x11_or_wayland!(match self; Window(w) => w.request_user_attention(request_type))
     }
@@ -546,6 +723,19 @@ impl Window {
         x11_or_wayland!(match self; Window(w) => w.pre_present_notify())
     }
 
+    #[inline]
+    pub fn notify_first_frame_presented(&self) {}
+
+    #[cfg(x11_platform)]
+    #[inline]
+    pub fn set_bypass_compositor(&self, bypass: CompositorBypass) {
+        match self {
+            Window::X(ref window) => window.set_bypass_compositor(bypass),
+            #[cfg(wayland_platform)]
+            Window::Wayland(_) => (),
+        }
+    }
+
     #[inline]
     pub fn current_monitor(&self) -> Option<MonitorHandle> {
         Some(x11_or_wayland!(match self; Window(w) => w.current_monitor()?; as MonitorHandle))
@@ -616,11 +806,47 @@ impl Window {
         x11_or_wayland!(match self; Window(window) => window.set_content_protected(protected))
     }
 
+    #[inline]
+    pub fn set_capture_policy(&self, policy: CapturePolicy) {
+        x11_or_wayland!(match self; Window(window) => window.set_capture_policy(policy))
+    }
+
+    #[inline]
+    pub fn set_performance_hint(&self, hint: PerformanceHint) {
+        common::gamemode::set_performance_hint(hint)
+    }
+
+    #[inline]
+    pub fn set_preferred_frame_rate_range(&self, _range: Option<FrameRateRange>) {}
+
+    #[inline]
+    pub fn share(&self, _data: ShareData) {}
+
+    pub fn hide_to_tray(&self) {}
+
+    #[inline]
+    pub fn present_rgba(
+        &self,
+        buffer: &[u8],
+        size: PhysicalSize<u32>,
+    ) -> Result<(), ExternalError> {
+        x11_or_wayland!(match self; Window(w) => w.present_rgba(buffer, size))
+    }
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         x11_or_wayland!(match self; Window(window) => window.has_focus())
     }
 
+    #[inline]
+    pub fn visibility(&self) -> WindowVisibility {
+        x11_or_wayland!(match self; Window(window) => window.visibility())
+    }
+
+    pub fn tiling_state(&self) -> TilingState {
+        x11_or_wayland!(match self; Window(window) => window.tiling_state())
+    }
+
     pub fn title(&self) -> String {
         x11_or_wayland!(match self; Window(window) => window.title())
     }
@@ -711,9 +937,32 @@ impl<T: 'static> Clone for EventLoopProxy<T> {
     }
 }
 
+#[derive(Clone)]
+pub enum EventLoopWaker {
+    #[cfg(x11_platform)]
+    X(x11::EventLoopWaker),
+    #[cfg(wayland_platform)]
+    Wayland(wayland::EventLoopWaker),
+}
+
+impl EventLoopWaker {
+    pub fn wake(&self) {
+        x11_or_wayland!(match self; EventLoopWaker(waker) => waker.wake())
+    }
+}
+
 impl<T: 'static> EventLoop<T> {
     pub(crate) fn new(
         attributes: &PlatformSpecificEventLoopAttributes,
+    ) -> Result<Self, EventLoopError> {
+        let mut event_loop = Self::new_backend(attributes)?;
+        x11_or_wayland!(match &mut event_loop; EventLoop(evlp) => evlp.receive_signals(&attributes.signals));
+        x11_or_wayland!(match &mut event_loop; EventLoop(evlp) => evlp.join_session());
+        Ok(event_loop)
+    }
+
+    fn new_backend(
+        attributes: &PlatformSpecificEventLoopAttributes,
     ) -> Result<Self, EventLoopError> {
         if !attributes.any_thread && !is_main_thread() {
             panic!(
@@ -725,6 +974,10 @@ impl<T: 'static> EventLoop<T> {
             );
         }
 
+        if attributes.forced_backend.is_none() && !attributes.backend_preference.is_empty() {
+            return Self::new_preferred(&attributes.backend_preference);
+        }
+
         // NOTE: Wayland first because of X11 could be present under Wayland as well. Empty
         // variables are also treated as not set.
         let backend = match (
@@ -769,6 +1022,46 @@ impl<T: 'static> EventLoop<T> {
         }
     }
 
+    /// Try the backends in order, and return why each failed if none could be initialized.
+    fn new_preferred(preference: &[RootBackend]) -> Result<Self, EventLoopError> {
+        let mut failures = Vec::new();
+        for &backend in preference {
+            let result: Result<Self, EventLoopError> = match backend {
+                #[cfg(wayland_platform)]
+                RootBackend::Wayland => Self::new_wayland_any_thread(),
+                #[cfg(not(wayland_platform))]
+                RootBackend::Wayland => {
+                    failures.push((backend, "enable the `winit/wayland` feature".to_owned()));
+                    continue;
+                },
+                #[cfg(x11_platform)]
+                RootBackend::X11 => match X11_BACKEND.lock().unwrap().as_ref() {
+                    Ok(xconn) => Ok(EventLoop::X(x11::EventLoop::new(xconn.clone()))),
+                    Err(err) => {
+                        failures.push((backend, err.to_string()));
+                        continue;
+                    },
+                },
+                #[cfg(not(x11_platform))]
+                RootBackend::X11 => {
+                    failures.push((backend, "enable the `winit/x11` feature".to_owned()));
+                    continue;
+                },
+                _ => {
+                    failures.push((backend, "not available on this platform".to_owned()));
+                    continue;
+                },
+            };
+
+            match result {
+                Ok(event_loop) => return Ok(event_loop),
+                Err(err) => failures.push((backend, err.to_string())),
+            }
+        }
+
+        Err(EventLoopError::Backend(BackendError::new(failures)))
+    }
+
     #[cfg(wayland_platform)]
     fn new_wayland_any_thread() -> Result<EventLoop<T>, EventLoopError> {
         wayland::EventLoop::new().map(|evlp| EventLoop::Wayland(Box::new(evlp)))
@@ -838,6 +1131,10 @@ impl<T: 'static> EventLoopProxy<T> {
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
         x11_or_wayland!(match self; EventLoopProxy(proxy) => proxy.send_event(event))
     }
+
+    pub fn waker(&self) -> EventLoopWaker {
+        x11_or_wayland!(match self; EventLoopProxy(proxy) => proxy.waker(); as EventLoopWaker)
+    }
 }
 
 pub enum ActiveEventLoop {
@@ -883,11 +1180,91 @@ impl ActiveEventLoop {
         )
     }
 
+    #[inline]
+    pub fn seats(&self) -> Vec<Seat> {
+        x11_or_wayland!(match self; ActiveEventLoop(evlp) => evlp.seats())
+    }
+
+    pub fn locale(&self) -> Option<LanguageTag> {
+        // Like with gettext, `LANGUAGE` is a list of languages in order of preference.
+        ["LANGUAGE", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .into_iter()
+            .filter_map(|name| env::var(name).ok())
+            .find_map(|value| LanguageTag::from_posix(value.split(':').next()?))
+    }
+
+    pub fn session_info(&self) -> SessionInfo {
+        // Set by xrdp for the applications started in its sessions.
+        let xrdp = env::var_os("XRDP_SESSION").is_some();
+        let wayland = env::var_os("WAYLAND_DISPLAY").is_some()
+            || env::var("XDG_SESSION_TYPE").is_ok_and(|session_type| session_type == "wayland");
+
+        match *self {
+            #[cfg(wayland_platform)]
+            ActiveEventLoop::Wayland(_) => SessionInfo::new(
+                if xrdp { SessionKind::Remote } else { SessionKind::Desktop },
+                wayland,
+                SessionCapabilities::GRAB_CURSOR | SessionCapabilities::TRANSPARENCY,
+            ),
+            #[cfg(x11_platform)]
+            ActiveEventLoop::X(ref evlp) => {
+                let remote =
+                    xrdp || env::var("DISPLAY").is_ok_and(|display| is_remote_display(&display));
+                let mut capabilities =
+                    SessionCapabilities::SET_POSITION | SessionCapabilities::GRAB_CURSOR;
+                // Transparency requires a compositing manager.
+                if evlp.x_connection().is_composited() {
+                    capabilities |= SessionCapabilities::TRANSPARENCY;
+                }
+                SessionInfo::new(
+                    if remote { SessionKind::Remote } else { SessionKind::Desktop },
+                    wayland,
+                    capabilities,
+                )
+            },
+        }
+    }
+
+    pub fn double_click_settings(&self) -> Option<(Duration, u32)> {
+        match *self {
+            #[cfg(wayland_platform)]
+            ActiveEventLoop::Wayland(_) => None,
+            #[cfg(x11_platform)]
+            ActiveEventLoop::X(ref evlp) => evlp.x_connection().double_click_settings(),
+        }
+    }
+
     #[inline]
     pub fn listen_device_events(&self, allowed: DeviceEvents) {
         x11_or_wayland!(match self; Self(evlp) => evlp.listen_device_events(allowed))
     }
 
+    #[inline]
+    pub fn pointer_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        x11_or_wayland!(match self; Self(evlp) => evlp.pointer_position())
+    }
+
+    pub fn set_restart_after_crash(
+        &self,
+        _args: Option<Vec<std::ffi::OsString>>,
+    ) -> Result<(), ExternalError> {
+        // Neither X11 nor Wayland sessions restart crashed applications.
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_video_mode(
+        &self,
+        mode: &VideoModeHandle,
+    ) -> Result<Box<dyn FnOnce()>, ExternalError> {
+        match (self, mode) {
+            #[cfg(x11_platform)]
+            (ActiveEventLoop::X(evlp), VideoModeHandle::X(mode)) => evlp.set_video_mode(mode),
+            // Only the compositor controls the modes of outputs on Wayland.
+            #[allow(unreachable_patterns)]
+            _ => Err(ExternalError::NotSupported(NotSupportedError::new())),
+        }
+    }
+
     #[cfg(feature = "rwh_05")]
     #[inline]
     pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
@@ -910,6 +1287,27 @@ impl ActiveEventLoop {
         x11_or_wayland!(match self; Self(evlp) => evlp.control_flow())
     }
 
+    #[inline]
+    pub(crate) fn set_timer_resolution(&self, _resolution: Duration) {}
+
+    pub(crate) fn set_exit_pending(&self, _pending: bool) {}
+
+    pub(crate) fn show_notification(
+        &self,
+        _id: NotificationId,
+        _attributes: NotificationAttributes,
+    ) -> Result<PlatformNotification, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub(crate) fn show_message_dialog(
+        &self,
+        dialog: MessageDialog,
+        sender: DialogSender<DialogButton>,
+    ) -> Result<(), ExternalError> {
+        common::dialog::show_message_dialog(dialog, sender)
+    }
+
     pub(crate) fn clear_exit(&self) {
         x11_or_wayland!(match self; Self(evlp) => evlp.clear_exit())
     }
@@ -918,6 +1316,19 @@ impl ActiveEventLoop {
         x11_or_wayland!(match self; Self(evlp) => evlp.exit())
     }
 
+    pub(crate) fn register_fd(
+        &self,
+        fd: BorrowedFd<'_>,
+        interest: Interest,
+        token: u64,
+    ) -> Result<(), ExternalError> {
+        x11_or_wayland!(match self; Self(evlp) => evlp.register_fd(fd, interest, token))
+    }
+
+    pub(crate) fn unregister_fd(&self, token: u64) {
+        x11_or_wayland!(match self; Self(evlp) => evlp.unregister_fd(token))
+    }
+
     pub(crate) fn exiting(&self) -> bool {
         x11_or_wayland!(match self; Self(evlp) => evlp.exiting())
     }
@@ -1010,6 +1421,16 @@ fn min_timeout(a: Option<Duration>, b: Option<Duration>) -> Option<Duration> {
     a.map_or(b, |a_timeout| b.map_or(Some(a_timeout), |b_timeout| Some(a_timeout.min(b_timeout))))
 }
 
+/// Whether the X11 `DISPLAY` is on another host, e.g. `localhost:10.0` with X11 forwarding over
+/// SSH, as opposed to `:0` or `unix:0`.
+#[cfg(x11_platform)]
+fn is_remote_display(display: &str) -> bool {
+    match display.rsplit_once(':') {
+        Some((host, _)) => !host.is_empty() && host != "unix" && !host.starts_with('/'),
+        None => false,
+    }
+}
+
 #[cfg(target_os = "linux")]
 fn is_main_thread() -> bool {
     rustix::thread::gettid() == rustix::process::getpid()
@@ -16,11 +16,14 @@ use sctk::reexports::client::{globals, Connection, QueueHandle};
 
 use crate::application::ApplicationHandler;
 use crate::cursor::OnlyCursorImage;
-use crate::dpi::LogicalSize;
-use crate::error::{EventLoopError, OsError as RootOsError};
-use crate::event::{Event, InnerSizeWriter, StartCause, WindowEvent};
+use crate::dpi::{LogicalSize, PhysicalPosition};
+use crate::error::{EventLoopError, ExternalError, NotSupportedError, OsError as RootOsError};
+use crate::event::{Event, InnerSizeWriter, ResizeFrame, Signal, StartCause, WindowEvent};
 use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents};
 use crate::platform::pump_events::PumpStatus;
+use crate::platform::unix::Interest;
+use crate::platform_impl::common::fd::FdSources;
+use crate::platform_impl::common::session::{self, SessionEvent};
 use crate::platform_impl::platform::min_timeout;
 use crate::platform_impl::{
     ActiveEventLoop as PlatformActiveEventLoop, OsError, PlatformCustomCursor,
@@ -30,7 +33,7 @@ use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource};
 mod proxy;
 pub mod sink;
 
-pub use proxy::EventLoopProxy;
+pub use proxy::{EventLoopProxy, EventLoopWaker};
 use sink::EventSink;
 
 use super::state::{WindowCompositorUpdate, WinitState};
@@ -94,6 +97,8 @@ impl<T: 'static> EventLoop<T> {
         // NOTE: do a roundtrip after binding the globals to prevent potential
         // races with the server.
         map_err!(event_queue.roundtrip(&mut winit_state), WaylandError::Dispatch)?;
+        // The outputs announced initially aren't changes.
+        winit_state.monitors_changed = false;
 
         // Register Wayland source.
         let wayland_source = WaylandSource::new(connection.clone(), event_queue);
@@ -153,6 +158,7 @@ impl<T: 'static> EventLoop<T> {
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
             state: RefCell::new(winit_state),
+            fd_sources: FdSources::new(event_loop.handle()),
         };
 
         let event_loop = Self {
@@ -327,6 +333,27 @@ impl<T: 'static> EventLoop<T> {
             app.user_event(&self.window_target, user_event);
         }
 
+        for token in self.with_state(|state| mem::take(&mut state.fd_ready)) {
+            app.fd_ready(&self.window_target, token);
+        }
+
+        for signal in self.with_state(|state| mem::take(&mut state.signals)) {
+            app.signal(&self.window_target, signal);
+        }
+
+        if self.with_state(|state| mem::take(&mut state.monitors_changed)) {
+            app.monitors_changed(&self.window_target);
+        }
+
+        if let Some(end) = self.with_state(|state| state.session_end.take()) {
+            end.request_close(|respond| app.close_requested_by_os(&self.window_target, respond));
+        }
+
+        if self.with_state(|state| mem::take(&mut state.terminating)) {
+            app.terminating(&self.window_target);
+            self.window_target.p.exit();
+        }
+
         // Drain the pending compositor updates.
         self.with_state(|state| compositor_updates.append(&mut state.window_compositor_updates));
 
@@ -375,12 +402,39 @@ impl<T: 'static> EventLoop<T> {
             // NOTE: Rescale changed the physical size which winit operates in, thus we should
             // resize.
             if compositor_update.resized || compositor_update.scale_changed {
-                let physical_size = self.with_state(|state| {
+                let event_loop_awakener = match &self.window_target.p {
+                    PlatformActiveEventLoop::Wayland(window_target) => {
+                        window_target.event_loop_awakener.clone()
+                    },
+                    #[cfg(x11_platform)]
+                    PlatformActiveEventLoop::X(_) => unreachable!(),
+                };
+
+                let resize = self.with_state(|state| {
                     let windows = state.windows.get_mut();
-                    let window = windows.get(&window_id).unwrap().lock().unwrap();
+                    let mut window = windows.get(&window_id).unwrap().lock().unwrap();
+
+                    // Coalesce the configures until the application presented the last resize.
+                    if window
+                        .pending_resize_frame
+                        .as_ref()
+                        .is_some_and(|frame| !frame.is_presented())
+                    {
+                        let mut update = WindowCompositorUpdate::new(window_id);
+                        update.resized = true;
+                        state.window_compositor_updates.push(update);
+                        return None;
+                    }
 
                     let scale_factor = window.scale_factor();
                     let size = logical_to_physical_rounded(window.inner_size(), scale_factor);
+                    let frame = window.synchronized_resize.then(|| {
+                        let on_presented = Box::new(move || event_loop_awakener.ping());
+                        let (frame, waiter) = ResizeFrame::new(Some(on_presented));
+                        window.pending_resize_frame = Some(waiter);
+                        frame
+                    });
+                    drop(window);
 
                     // Mark the window as needed a redraw.
                     state
@@ -391,12 +445,14 @@ impl<T: 'static> EventLoop<T> {
                         .redraw_requested
                         .store(true, Ordering::Relaxed);
 
-                    size
+                    Some((size, frame))
                 });
 
-                let window_id = crate::window::WindowId(window_id);
-                let event = WindowEvent::Resized(physical_size);
-                app.window_event(&self.window_target, window_id, event);
+                if let Some((size, frame)) = resize {
+                    let window_id = crate::window::WindowId(window_id);
+                    let event = WindowEvent::Resized { size, frame };
+                    app.window_event(&self.window_target, window_id, event);
+                }
             }
 
             if compositor_update.close_window {
@@ -417,6 +473,10 @@ impl<T: 'static> EventLoop<T> {
                 Event::DeviceEvent { device_id, event } => {
                     app.device_event(&self.window_target, device_id, event)
                 },
+                Event::DragHover { window_id, position, respond } => {
+                    let operation = app.drag_hover(&self.window_target, window_id, position);
+                    let _ = respond.respond(operation);
+                },
                 _ => unreachable!("event which is neither device nor window event."),
             }
         }
@@ -433,10 +493,17 @@ impl<T: 'static> EventLoop<T> {
                 Event::DeviceEvent { device_id, event } => {
                     app.device_event(&self.window_target, device_id, event)
                 },
+                Event::DragHover { window_id, position, respond } => {
+                    let operation = app.drag_hover(&self.window_target, window_id, position);
+                    let _ = respond.respond(operation);
+                },
                 _ => unreachable!("event which is neither device nor window event."),
             }
         }
 
+        // Apply what the application chose for the hovering drags.
+        self.with_state(|state| state.apply_drop_operations());
+
         // Collect the window ids
         self.with_state(|state| {
             window_ids.extend(state.window_requests.get_mut().keys());
@@ -526,10 +593,56 @@ impl<T: 'static> EventLoop<T> {
 
     #[inline]
     pub fn create_proxy(&self) -> EventLoopProxy<T> {
-        EventLoopProxy::new(self.user_events_sender.clone())
+        let event_loop_awakener = match &self.window_target.p {
+            PlatformActiveEventLoop::Wayland(window_target) => {
+                window_target.event_loop_awakener.clone()
+            },
+            #[cfg(x11_platform)]
+            PlatformActiveEventLoop::X(_) => unreachable!(),
+        };
+        EventLoopProxy::new(self.user_events_sender.clone(), event_loop_awakener)
     }
 
     #[inline]
+    /// Receive `signals` in the event loop, and `SIGTERM` to shut down if it isn't requested.
+    pub(crate) fn receive_signals(&mut self, signals: &[Signal]) {
+        #[cfg(target_os = "linux")]
+        {
+            let requested = signals.to_vec();
+            let mut signals = requested.clone();
+            if !signals.contains(&Signal::Terminate) {
+                signals.push(Signal::Terminate);
+            }
+            crate::platform_impl::common::signal::insert_source(
+                &self.event_loop.handle(),
+                &signals,
+                move |received, winit_state: &mut WinitState| {
+                    for &signal in received {
+                        if requested.contains(&signal) {
+                            winit_state.signals.push(signal);
+                        } else {
+                            winit_state.terminating = true;
+                        }
+                    }
+                    winit_state.dispatched_events = true;
+                },
+            );
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = signals;
+    }
+
+    /// Receive the requests of the session manager in the event loop.
+    pub(crate) fn join_session(&mut self) {
+        session::insert_source(&self.event_loop.handle(), |event, winit_state: &mut WinitState| {
+            match event {
+                SessionEvent::EndRequested(end) => winit_state.session_end = Some(end),
+                SessionEvent::End => winit_state.terminating = true,
+            }
+            winit_state.dispatched_events = true;
+        });
+    }
+
     pub fn window_target(&self) -> &RootActiveEventLoop {
         &self.window_target
     }
@@ -545,6 +658,9 @@ impl<T: 'static> EventLoop<T> {
     }
 
     fn loop_dispatch<D: Into<Option<std::time::Duration>>>(&mut self, timeout: D) -> IOResult<()> {
+        #[cfg(feature = "trace")]
+        let _span = tracing::trace_span!("winit::dispatch_sources").entered();
+
         let state = match &mut self.window_target.p {
             PlatformActiveEventLoop::Wayland(window_target) => window_target.state.get_mut(),
             #[cfg(feature = "x11")]
@@ -622,9 +738,28 @@ pub struct ActiveEventLoop {
 
     /// Connection to the wayland server.
     pub connection: Connection,
+
+    /// File descriptors registered by the application.
+    pub fd_sources: FdSources<WinitState>,
 }
 
 impl ActiveEventLoop {
+    pub(crate) fn register_fd(
+        &self,
+        fd: BorrowedFd<'_>,
+        interest: Interest,
+        token: u64,
+    ) -> Result<(), ExternalError> {
+        self.fd_sources.register(fd, interest, token, |token, state| {
+            state.fd_ready.push(token);
+            state.dispatched_events = true;
+        })
+    }
+
+    pub(crate) fn unregister_fd(&self, token: u64) {
+        self.fd_sources.unregister(token)
+    }
+
     pub(crate) fn set_control_flow(&self, control_flow: ControlFlow) {
         self.control_flow.set(control_flow)
     }
@@ -656,6 +791,12 @@ impl ActiveEventLoop {
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    #[inline]
+    pub fn pointer_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        // Wayland doesn't expose positions on the desktop.
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub(crate) fn create_custom_cursor(&self, cursor: CustomCursorSource) -> RootCustomCursor {
         RootCustomCursor {
             inner: PlatformCustomCursor::Wayland(OnlyCursorImage(Arc::from(cursor.inner.0))),
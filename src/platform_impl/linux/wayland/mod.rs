@@ -6,13 +6,15 @@ use std::fmt::Display;
 use std::sync::Arc;
 
 use sctk::reexports::client::globals::{BindError, GlobalError};
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{self, ConnectError, DispatchError, Proxy};
 
 pub(super) use crate::cursor::OnlyCursorImage as CustomCursor;
 use crate::dpi::{LogicalSize, PhysicalSize};
+use crate::event::SeatId;
 pub use crate::platform_impl::platform::{OsError, WindowId};
-pub use event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy};
+pub use event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy, EventLoopWaker};
 pub use output::{MonitorHandle, VideoModeHandle};
 pub use window::Window;
 
@@ -63,13 +65,22 @@ impl From<WaylandError> for OsError {
     }
 }
 
-/// Dummy device id, since Wayland doesn't have device events.
+/// Wayland doesn't expose individual input devices, so the device id stands for the pointer,
+/// keyboard and touch of a seat.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct DeviceId;
+pub struct DeviceId(Option<SeatId>);
 
 impl DeviceId {
+    pub fn new(seat: &WlSeat) -> Self {
+        DeviceId(Some(make_seat_id(seat)))
+    }
+
     pub const unsafe fn dummy() -> Self {
-        DeviceId
+        DeviceId(None)
+    }
+
+    pub fn seat_id(&self) -> Option<SeatId> {
+        self.0
     }
 }
 
@@ -79,6 +90,12 @@ fn make_wid(surface: &WlSurface) -> WindowId {
     WindowId(surface.id().as_ptr() as u64)
 }
 
+/// Get the SeatId out of the seat.
+#[inline]
+fn make_seat_id(seat: &WlSeat) -> SeatId {
+    SeatId(seat.id().protocol_id() as u64)
+}
+
 /// The default routine does floor, but we need round on Wayland.
 fn logical_to_physical_rounded(size: LogicalSize<u32>, scale_factor: f64) -> PhysicalSize<u32> {
     let width = size.width as f64 * scale_factor;
@@ -1,9 +1,13 @@
+use std::ops::RangeInclusive;
+
 use sctk::reexports::client::protocol::wl_output::WlOutput;
 use sctk::reexports::client::Proxy;
 
 use sctk::output::OutputData;
 
 use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::event_loop::DpiPolicy;
+use crate::monitor::Rect;
 use crate::platform_impl::platform::VideoModeHandle as PlatformVideoModeHandle;
 
 use super::event_loop::ActiveEventLoop;
@@ -75,6 +79,30 @@ impl MonitorHandle {
         })
     }
 
+    /// Wayland doesn't expose the panels of the compositor.
+    #[inline]
+    pub fn work_area(&self) -> Rect {
+        Rect::new(self.position(), self.size())
+    }
+
+    /// The color management protocol isn't available in the version of `wayland-protocols` used.
+    #[inline]
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Adaptive sync is negotiated per surface by the compositor, which doesn't expose it.
+    #[inline]
+    pub fn supports_variable_refresh(&self) -> bool {
+        false
+    }
+
+    /// Outputs don't expose whether the compositor mirrors them.
+    #[inline]
+    pub fn is_mirrored(&self) -> bool {
+        false
+    }
+
     #[inline]
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
         let output_data = self.proxy.data::<OutputData>().unwrap();
@@ -84,9 +112,9 @@ impl MonitorHandle {
     }
 
     #[inline]
-    pub fn scale_factor(&self) -> i32 {
+    pub fn scale_factor(&self) -> f64 {
         let output_data = self.proxy.data::<OutputData>().unwrap();
-        output_data.scale_factor()
+        DpiPolicy::current().apply(output_data.scale_factor() as f64)
     }
 
     #[inline]
@@ -157,6 +185,11 @@ impl VideoModeHandle {
         self.refresh_rate_millihertz
     }
 
+    #[inline]
+    pub fn refresh_rate_range(&self) -> RangeInclusive<u32> {
+        self.refresh_rate_millihertz..=self.refresh_rate_millihertz
+    }
+
     pub fn monitor(&self) -> MonitorHandle {
         self.monitor.clone()
     }
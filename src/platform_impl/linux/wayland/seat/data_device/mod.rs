@@ -0,0 +1,276 @@
+//! Drag and drop handling.
+
+use std::cell::RefCell;
+use std::io::{ErrorKind, Read};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+
+use sctk::data_device_manager::data_device::{DataDeviceData, DataDeviceHandler};
+use sctk::data_device_manager::data_offer::{DataOfferHandler, DragOffer};
+use sctk::data_device_manager::data_source::DataSourceHandler;
+use sctk::data_device_manager::WritePipe;
+use sctk::reexports::calloop::PostAction;
+use sctk::reexports::client::protocol::wl_data_device::WlDataDevice;
+use sctk::reexports::client::protocol::wl_data_device_manager::DndAction;
+use sctk::reexports::client::protocol::wl_data_source::WlDataSource;
+use sctk::reexports::client::{Connection, Proxy, QueueHandle};
+
+use crate::dpi::LogicalPosition;
+use crate::event::{DataTransfer, DropOperation, DropResponse, Event, WindowEvent};
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{self, WindowId};
+
+/// A drag hovering a window.
+#[derive(Debug)]
+pub struct Drag {
+    /// The MIME types accepted for the drag.
+    types: Vec<String>,
+    /// What a drop would do, chosen by the application on `DragHover`.
+    operation: Arc<Mutex<DropOperation>>,
+    /// Whether the application was asked for the operation since it was last applied.
+    hovered: bool,
+}
+
+impl Drag {
+    /// Tell the compositor what a drop would do, which it shows to the user.
+    fn apply(&self, offer: &DragOffer) {
+        let operation = *self.operation.lock().unwrap();
+        let action = match operation {
+            _ if self.types.is_empty() => DndAction::empty(),
+            DropOperation::Reject => DndAction::empty(),
+            // Wayland has no action to link the data.
+            DropOperation::Copy | DropOperation::Link => DndAction::Copy,
+            DropOperation::Move => DndAction::Move,
+        };
+        let mime_type = self.types.first().filter(|_| !action.is_empty());
+        offer.accept_mime_type(offer.serial, mime_type.cloned());
+        offer.set_actions(action, action);
+    }
+}
+
+/// The data of a drop being received, from a pipe for each accepted type.
+struct Transfer {
+    offer: DragOffer,
+    window_id: WindowId,
+    /// The number of pipes still being read.
+    pending: usize,
+    data: DataTransfer,
+}
+
+impl Transfer {
+    /// Store the data of a type, and report the drop once all of them were read.
+    fn received(&mut self, state: &mut WinitState, mime_type: String, data: Option<Vec<u8>>) {
+        if let Some(data) = data {
+            self.data.insert(mime_type, data);
+        }
+        self.pending -= 1;
+        if self.pending > 0 {
+            return;
+        }
+
+        self.offer.finish();
+        self.offer.destroy();
+        if !self.data.is_empty() {
+            let data = std::mem::take(&mut self.data);
+            state.events_sink.push_window_event(WindowEvent::DroppedData(data), self.window_id);
+        }
+        state.dispatched_events = true;
+    }
+}
+
+fn drag_offer(data_device: &WlDataDevice) -> Option<(DragOffer, &DataDeviceData)> {
+    let data = data_device.data::<DataDeviceData>()?;
+    Some((data.drag_offer()?, data))
+}
+
+impl WinitState {
+    /// Ask the application what a drop at the position of the drag would do, unless the drag
+    /// isn't accepted.
+    fn drag_hover(&mut self, data_device: &WlDataDevice) {
+        let Some((offer, data)) = drag_offer(data_device) else { return };
+        let Some(drag) = self.seats.get_mut(&data.seat().id()).and_then(|seat| seat.drag.as_mut())
+        else {
+            return;
+        };
+        if drag.types.is_empty() {
+            return;
+        }
+
+        let window_id = wayland::make_wid(&offer.surface);
+        let Some(window) = self.windows.get_mut().get(&window_id) else { return };
+        let scale_factor = window.lock().unwrap().scale_factor();
+        let position = LogicalPosition::new(offer.x, offer.y).to_physical(scale_factor);
+
+        drag.hovered = true;
+        let respond = DropResponse::new(Arc::downgrade(&drag.operation));
+        let window_id = crate::window::WindowId(window_id);
+        self.events_sink.window_events.push(Event::DragHover { window_id, position, respond });
+    }
+
+    /// Apply the operations the application chose for the drags hovering the windows.
+    pub fn apply_drop_operations(&mut self) {
+        for seat_state in self.seats.values_mut() {
+            let (Some(drag), Some(data_device)) = (&mut seat_state.drag, &seat_state.data_device)
+            else {
+                continue;
+            };
+            if !std::mem::take(&mut drag.hovered) {
+                continue;
+            }
+            if let Some((offer, _)) = drag_offer(data_device.inner()) {
+                drag.apply(&offer);
+            }
+        }
+    }
+}
+
+impl DataDeviceHandler for WinitState {
+    fn enter(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: &WlDataDevice) {
+        let Some((offer, data)) = drag_offer(data_device) else { return };
+        let window_id = wayland::make_wid(&offer.surface);
+        let offered = offer.with_mime_types(|mime_types| mime_types.to_vec());
+        let types =
+            crate::drop_negotiation::negotiate(crate::window::WindowId(window_id), &offered);
+
+        // Until the application chooses, the data is copied.
+        let drag = Drag { types, operation: Default::default(), hovered: false };
+        drag.apply(&offer);
+        if let Some(seat_state) = self.seats.get_mut(&data.seat().id()) {
+            seat_state.drag = Some(drag);
+        }
+        self.drag_hover(data_device);
+    }
+
+    fn leave(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: &WlDataDevice) {
+        let Some(data) = data_device.data::<DataDeviceData>() else { return };
+        if let Some(seat_state) = self.seats.get_mut(&data.seat().id()) {
+            seat_state.drag = None;
+        }
+    }
+
+    fn motion(&mut self, _: &Connection, _: &QueueHandle<Self>, data_device: &WlDataDevice) {
+        self.drag_hover(data_device);
+    }
+
+    fn selection(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataDevice) {}
+
+    fn drop_performed(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        data_device: &WlDataDevice,
+    ) {
+        let Some((offer, data)) = drag_offer(data_device) else { return };
+        let drag = match self.seats.get_mut(&data.seat().id()) {
+            Some(seat_state) => seat_state.drag.take(),
+            None => return,
+        };
+        let drop_types = match drag {
+            Some(drag) if *drag.operation.lock().unwrap() != DropOperation::Reject => drag.types,
+            _ => Vec::new(),
+        };
+        if drop_types.is_empty() {
+            offer.destroy();
+            return;
+        }
+
+        let window_id = wayland::make_wid(&offer.surface);
+        let transfer = Rc::new(RefCell::new(Transfer {
+            offer: offer.clone(),
+            window_id,
+            pending: drop_types.len(),
+            data: DataTransfer::new(),
+        }));
+
+        // The sources write the data asynchronously, so the pipes are read from the event loop.
+        for mime_type in drop_types {
+            let transfer = Rc::clone(&transfer);
+            let pipe = match offer.receive(mime_type.clone()) {
+                Ok(pipe) => pipe,
+                Err(err) => {
+                    tracing::warn!("Failed to receive dropped {mime_type}: {err}");
+                    transfer.borrow_mut().received(self, mime_type, None);
+                    continue;
+                },
+            };
+
+            let mut buffer = Vec::new();
+            let mut mime_type = Some(mime_type);
+            let result = self.loop_handle.insert_source(pipe, move |_, file, state| {
+                let mut file: &std::fs::File = file;
+                let mut chunk = [0; 4096];
+                let data = match file.read(&mut chunk) {
+                    Ok(0) => Some(std::mem::take(&mut buffer)),
+                    Ok(len) => {
+                        buffer.extend_from_slice(&chunk[..len]);
+                        return PostAction::Continue;
+                    },
+                    Err(err) if err.kind() == ErrorKind::WouldBlock => return PostAction::Continue,
+                    Err(err) => {
+                        tracing::warn!("Failed to read dropped data: {err}");
+                        None
+                    },
+                };
+
+                let mime_type = mime_type.take().unwrap();
+                transfer.borrow_mut().received(state, mime_type, data);
+                PostAction::Remove
+            });
+            if let Err(err) = result {
+                tracing::warn!("Failed to read dropped data: {}", err.error);
+            }
+        }
+    }
+}
+
+impl DataOfferHandler for WinitState {
+    fn source_actions(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+
+    fn selected_action(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &mut DragOffer,
+        _: DndAction,
+    ) {
+    }
+}
+
+// Winit doesn't offer data, but the data device requires handling sources.
+impl DataSourceHandler for WinitState {
+    fn accept_mime(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+        _: Option<String>,
+    ) {
+    }
+
+    fn send_request(
+        &mut self,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+        _: &WlDataSource,
+        _: String,
+        _: WritePipe,
+    ) {
+    }
+
+    fn cancelled(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource) {}
+
+    fn dnd_dropped(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource) {}
+
+    fn dnd_finished(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource) {}
+
+    fn action(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &WlDataSource, _: DndAction) {}
+}
+
+sctk::delegate_data_device!(WinitState);
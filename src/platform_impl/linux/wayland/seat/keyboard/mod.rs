@@ -14,7 +14,7 @@ use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::{Connection, Dispatch, Proxy, QueueHandle, WEnum};
 
 use crate::event::{ElementState, WindowEvent};
-use crate::keyboard::ModifiersState;
+use crate::keyboard::{ModifiersKeys, ModifiersState};
 
 use crate::platform_impl::common::xkb::Context;
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
@@ -83,10 +83,14 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 // HACK: this is just for GNOME not fixing their ordering issue of modifiers.
                 if std::mem::take(&mut seat_state.modifiers_pending) {
                     state.events_sink.push_window_event(
-                        WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
+                        WindowEvent::ModifiersChanged(seat_state.current_modifiers()),
                         window_id,
                     );
                 }
+
+                if was_unfocused {
+                    state.update_visibility(window_id);
+                }
             },
             WlKeyboardEvent::Leave { surface, .. } => {
                 let window_id = wayland::make_wid(&surface);
@@ -114,6 +118,11 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 // anyway.
                 *data.window_id.lock().unwrap() = None;
 
+                // Modifier keys released in the meantime aren't reported to us, and the
+                // compositor sends the current modifiers when the keyboard enters again.
+                seat_state.modifiers = ModifiersState::empty();
+                seat_state.pressed_mods = ModifiersKeys::empty();
+
                 if !focused {
                     // Notify that no modifiers are being pressed.
                     state.events_sink.push_window_event(
@@ -122,6 +131,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     );
 
                     state.events_sink.push_window_event(WindowEvent::Focused(false), window_id);
+                    state.update_visibility(window_id);
                 }
             },
             WlKeyboardEvent::Key { key, state: WEnum::Value(WlKeyState::Pressed), .. } => {
@@ -224,6 +234,8 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
 
                 xkb_state.update_modifiers(mods_depressed, mods_latched, mods_locked, 0, 0, group);
                 seat_state.modifiers = xkb_state.modifiers().into();
+                seat_state.pressed_mods =
+                    seat_state.pressed_mods.retain_active(seat_state.modifiers);
 
                 // HACK: part of the workaround from `WlKeyboardEvent::Enter`.
                 let window_id = match *data.window_id.lock().unwrap() {
@@ -235,7 +247,7 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 };
 
                 state.events_sink.push_window_event(
-                    WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
+                    WindowEvent::ModifiersChanged(seat_state.current_modifiers()),
                     window_id,
                 );
             },
@@ -362,10 +374,25 @@ fn key_input(
 
     let keyboard_state = seat_state.keyboard_state.as_mut().unwrap();
 
-    let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(DeviceId));
+    let device_id =
+        crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(DeviceId::new(&data.seat)));
     if let Some(mut key_context) = keyboard_state.xkb_context.key_context() {
         let event = key_context.process_key_event(keycode, state, repeat);
+
+        // The compositor only reports which modifiers are active, not the side of their keys.
+        let old_modifiers = seat_state.current_modifiers();
+        seat_state.pressed_mods.set(
+            ModifiersKeys::from_key(&event.logical_key, event.location),
+            state == ElementState::Pressed,
+        );
+        let modifiers = seat_state.current_modifiers();
+
         let event = WindowEvent::KeyboardInput { device_id, event, is_synthetic: false };
         event_sink.push_window_event(event, window_id);
+
+        // Pressing the key of an already active modifier doesn't update the modifiers.
+        if modifiers != old_modifiers {
+            event_sink.push_window_event(WindowEvent::ModifiersChanged(modifiers), window_id);
+        }
     }
 }
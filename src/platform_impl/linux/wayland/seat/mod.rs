@@ -11,18 +11,22 @@ use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 use sctk::reexports::protocols::wp::relative_pointer::zv1::client::zwp_relative_pointer_v1::ZwpRelativePointerV1;
 use sctk::reexports::protocols::wp::text_input::zv3::client::zwp_text_input_v3::ZwpTextInputV3;
 
+use sctk::data_device_manager::data_device::DataDevice;
 use sctk::seat::pointer::{ThemeSpec, ThemedPointer};
 use sctk::seat::{Capability as SeatCapability, SeatHandler, SeatState};
 
-use crate::event::WindowEvent;
-use crate::keyboard::ModifiersState;
+use crate::event::{Modifiers, Seat, WindowEvent};
+use crate::keyboard::{ModifiersKeys, ModifiersState};
 use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::{make_seat_id, ActiveEventLoop};
 
+mod data_device;
 mod keyboard;
 mod pointer;
 mod text_input;
 mod touch;
 
+use data_device::Drag;
 pub use pointer::relative_pointer::RelativePointerState;
 pub use pointer::{PointerConstraintsState, WinitPointerData, WinitPointerDataExt};
 pub use text_input::{TextInputState, ZwpTextInputV3Ext};
@@ -54,14 +58,31 @@ pub struct WinitSeatState {
     /// The current modifiers state on the seat.
     modifiers: ModifiersState,
 
+    /// The sides of the modifier keys held on the seat.
+    pressed_mods: ModifiersKeys,
+
     /// Whether we have pending modifiers.
     modifiers_pending: bool,
+
+    /// The data device bound on the seat, for drag and drop.
+    data_device: Option<DataDevice>,
+
+    /// The drag hovering a window of the seat.
+    drag: Option<Drag>,
 }
 
 impl WinitSeatState {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// The modifiers reported to the focused window.
+    fn current_modifiers(&self) -> Modifiers {
+        Modifiers {
+            state: self.modifiers,
+            pressed_mods: self.pressed_mods.retain_active(self.modifiers),
+        }
+    }
 }
 
 impl SeatHandler for WinitState {
@@ -107,7 +128,7 @@ impl SeatHandler for WinitState {
                     manager.get_relative_pointer(
                         themed_pointer.pointer(),
                         queue_handle,
-                        sctk::globals::GlobalData,
+                        seat.clone(),
                     )
                 });
 
@@ -121,6 +142,15 @@ impl SeatHandler for WinitState {
             _ => (),
         }
 
+        if let Some(manager) = seat_state
+            .data_device
+            .is_none()
+            .then_some(self.data_device_manager_state.as_ref())
+            .flatten()
+        {
+            seat_state.data_device = Some(manager.get_data_device(queue_handle, &seat));
+        }
+
         if let Some(text_input_state) =
             seat_state.text_input.is_none().then_some(self.text_input_state.as_ref()).flatten()
         {
@@ -166,8 +196,7 @@ impl SeatHandler for WinitState {
                     let _ = self.pointer_surfaces.remove(&surface_id);
 
                     // Remove the inner locks/confines before dropping the pointer.
-                    pointer_data.unlock_pointer();
-                    pointer_data.unconfine_pointer();
+                    pointer_data.remove_constraint();
 
                     if pointer.pointer().version() >= 3 {
                         pointer.pointer().release();
@@ -202,6 +231,21 @@ impl SeatHandler for WinitState {
     }
 }
 
+impl ActiveEventLoop {
+    pub fn seats(&self) -> Vec<Seat> {
+        let state = self.state.borrow();
+        state
+            .seat_state
+            .seats()
+            .map(|seat| {
+                let info = state.seat_state.info(&seat);
+                let name = info.and_then(|info| info.name).unwrap_or_default();
+                Seat::new(make_seat_id(&seat), name)
+            })
+            .collect()
+    }
+}
+
 impl WinitState {
     fn on_keyboard_destroy(&mut self, seat: &ObjectId) {
         for (window_id, window) in self.windows.get_mut() {
@@ -210,6 +254,10 @@ impl WinitState {
             window.remove_seat_focus(seat);
             if had_focus != window.has_focus() {
                 self.events_sink.push_window_event(WindowEvent::Focused(false), *window_id);
+                if let Some(visibility) = window.update_visibility() {
+                    self.events_sink
+                        .push_window_event(WindowEvent::VisibilityChanged(visibility), *window_id);
+                }
             }
         }
     }
@@ -6,6 +6,7 @@ use std::time::Duration;
 
 use sctk::reexports::client::delegate_dispatch;
 use sctk::reexports::client::protocol::wl_pointer::WlPointer;
+use sctk::reexports::client::protocol::wl_region::WlRegion;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle, Dispatch};
@@ -43,7 +44,8 @@ impl PointerHandler for WinitState {
         let seat = pointer.winit_data().seat();
         let seat_state = self.seats.get(&seat.id()).unwrap();
 
-        let device_id = crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(DeviceId));
+        let device_id =
+            crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(DeviceId::new(seat)));
 
         for event in events {
             let surface = &event.surface;
@@ -71,6 +73,9 @@ impl PointerHandler for WinitState {
                 PointerEventKind::Enter { .. } | PointerEventKind::Motion { .. }
                     if parent_surface != surface =>
                 {
+                    // The pointer is over the decorations, outside of the window.
+                    pointer.winit_data().inner.lock().unwrap().position = None;
+
                     if let Some(icon) = window.frame_point_moved(
                         seat,
                         surface,
@@ -118,7 +123,10 @@ impl PointerHandler for WinitState {
                     }
 
                     // Set the currently focused surface.
-                    pointer.winit_data().inner.lock().unwrap().surface = Some(window_id);
+                    let mut inner = pointer.winit_data().inner.lock().unwrap();
+                    inner.surface = Some(window_id);
+                    inner.position = Some(event.position.into());
+                    drop(inner);
 
                     self.events_sink.push_window_event(
                         WindowEvent::CursorMoved { device_id, position },
@@ -131,12 +139,17 @@ impl PointerHandler for WinitState {
                     }
 
                     // Remove the active surface.
-                    pointer.winit_data().inner.lock().unwrap().surface = None;
+                    let mut inner = pointer.winit_data().inner.lock().unwrap();
+                    inner.surface = None;
+                    inner.position = None;
+                    drop(inner);
 
                     self.events_sink
                         .push_window_event(WindowEvent::CursorLeft { device_id }, window_id);
                 },
                 PointerEventKind::Motion { .. } => {
+                    pointer.winit_data().inner.lock().unwrap().position =
+                        Some(event.position.into());
                     self.events_sink.push_window_event(
                         WindowEvent::CursorMoved { device_id, position },
                         window_id,
@@ -225,57 +238,85 @@ impl WinitPointerData {
         }
     }
 
+    /// Lock the pointer in place on the surface, within `region` or the whole surface.
+    ///
+    /// The lock persists when the pointer leaves the surface, and the compositor activates it
+    /// again whenever the surface regains pointer focus.
     pub fn lock_pointer(
         &self,
         pointer_constraints: &PointerConstraintsState,
         surface: &WlSurface,
         pointer: &WlPointer,
+        region: Option<&WlRegion>,
         queue_handle: &QueueHandle<WinitState>,
     ) {
         let mut inner = self.inner.lock().unwrap();
-        if inner.locked_pointer.is_none() {
-            inner.locked_pointer = Some(pointer_constraints.lock_pointer(
-                surface,
-                pointer,
-                None,
-                Lifetime::Persistent,
-                queue_handle,
-                GlobalData,
-            ));
+        if let Some((constrained_surface, PointerConstraint::Locked(locked_pointer))) =
+            &inner.constraint
+        {
+            if constrained_surface == surface {
+                locked_pointer.set_region(region);
+                return;
+            }
         }
-    }
 
-    pub fn unlock_pointer(&self) {
-        let mut inner = self.inner.lock().unwrap();
-        if let Some(locked_pointer) = inner.locked_pointer.take() {
-            locked_pointer.destroy();
-        }
+        // A pointer can only be constrained to one surface at a time.
+        inner.remove_constraint();
+        let locked_pointer = pointer_constraints.lock_pointer(
+            surface,
+            pointer,
+            region,
+            Lifetime::Persistent,
+            queue_handle,
+            GlobalData,
+        );
+        inner.constraint = Some((surface.clone(), PointerConstraint::Locked(locked_pointer)));
     }
 
+    /// Confine the pointer to `region` of the surface, or the whole surface.
     pub fn confine_pointer(
         &self,
         pointer_constraints: &PointerConstraintsState,
         surface: &WlSurface,
         pointer: &WlPointer,
+        region: Option<&WlRegion>,
         queue_handle: &QueueHandle<WinitState>,
     ) {
-        self.inner.lock().unwrap().confined_pointer = Some(pointer_constraints.confine_pointer(
+        let mut inner = self.inner.lock().unwrap();
+        if let Some((constrained_surface, PointerConstraint::Confined(confined_pointer))) =
+            &inner.constraint
+        {
+            if constrained_surface == surface {
+                confined_pointer.set_region(region);
+                return;
+            }
+        }
+
+        inner.remove_constraint();
+        let confined_pointer = pointer_constraints.confine_pointer(
             surface,
             pointer,
-            None,
+            region,
             Lifetime::Persistent,
             queue_handle,
             GlobalData,
-        ));
+        );
+        inner.constraint = Some((surface.clone(), PointerConstraint::Confined(confined_pointer)));
     }
 
-    pub fn unconfine_pointer(&self) {
-        let inner = self.inner.lock().unwrap();
-        if let Some(confined_pointer) = inner.confined_pointer.as_ref() {
-            confined_pointer.destroy();
+    /// Release the lock or the confinement of the pointer to the surface, if any.
+    pub fn release_pointer(&self, surface: &WlSurface) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.constraint.as_ref().is_some_and(|(constrained, _)| constrained == surface) {
+            inner.remove_constraint();
         }
     }
 
+    /// Release the lock or the confinement of the pointer, whatever surface it applies to.
+    pub fn remove_constraint(&self) {
+        self.inner.lock().unwrap().remove_constraint();
+    }
+
     /// Seat associated with this pointer.
     pub fn seat(&self) -> &WlSeat {
         self.sctk_data.seat()
@@ -286,6 +327,11 @@ impl WinitPointerData {
         self.inner.lock().unwrap().surface
     }
 
+    /// Position on the active window, in surface-local logical coordinates.
+    pub fn position(&self) -> Option<LogicalPosition<f64>> {
+        self.inner.lock().unwrap().position
+    }
+
     /// Last button serial.
     pub fn latest_button_serial(&self) -> u32 {
         self.sctk_data.latest_button_serial().unwrap_or_default()
@@ -296,10 +342,14 @@ impl WinitPointerData {
         self.sctk_data.latest_enter_serial().unwrap_or_default()
     }
 
-    pub fn set_locked_cursor_position(&self, surface_x: f64, surface_y: f64) {
+    pub fn set_locked_cursor_position(&self, surface: &WlSurface, surface_x: f64, surface_y: f64) {
         let inner = self.inner.lock().unwrap();
-        if let Some(locked_pointer) = inner.locked_pointer.as_ref() {
-            locked_pointer.set_cursor_position_hint(surface_x, surface_y);
+        if let Some((constrained_surface, PointerConstraint::Locked(locked_pointer))) =
+            &inner.constraint
+        {
+            if constrained_surface == surface {
+                locked_pointer.set_cursor_position_hint(surface_x, surface_y);
+            }
         }
     }
 }
@@ -310,13 +360,17 @@ impl PointerDataExt for WinitPointerData {
     }
 }
 
+/// A lock or a confinement of the pointer.
 #[derive(Debug)]
-pub struct WinitPointerDataInner {
-    /// The associated locked pointer.
-    locked_pointer: Option<ZwpLockedPointerV1>,
+enum PointerConstraint {
+    Locked(ZwpLockedPointerV1),
+    Confined(ZwpConfinedPointerV1),
+}
 
-    /// The associated confined pointer.
-    confined_pointer: Option<ZwpConfinedPointerV1>,
+#[derive(Debug)]
+pub struct WinitPointerDataInner {
+    /// The constraint of the pointer, with the surface it applies to.
+    constraint: Option<(WlSurface, PointerConstraint)>,
 
     /// Serial of the last button event.
     latest_button_serial: u32,
@@ -324,19 +378,26 @@ pub struct WinitPointerDataInner {
     /// Currently focused window.
     surface: Option<WindowId>,
 
+    /// Position on the focused window, unless the pointer is over the decorations.
+    position: Option<LogicalPosition<f64>>,
+
     /// Current axis phase.
     phase: TouchPhase,
 }
 
-impl Drop for WinitPointerDataInner {
-    fn drop(&mut self) {
-        if let Some(locked_pointer) = self.locked_pointer.take() {
-            locked_pointer.destroy();
+impl WinitPointerDataInner {
+    fn remove_constraint(&mut self) {
+        match self.constraint.take() {
+            Some((_, PointerConstraint::Locked(locked_pointer))) => locked_pointer.destroy(),
+            Some((_, PointerConstraint::Confined(confined_pointer))) => confined_pointer.destroy(),
+            None => (),
         }
+    }
+}
 
-        if let Some(confined_pointer) = self.confined_pointer.take() {
-            confined_pointer.destroy();
-        }
+impl Drop for WinitPointerDataInner {
+    fn drop(&mut self) {
+        self.remove_constraint();
     }
 }
 
@@ -344,8 +405,8 @@ impl Default for WinitPointerDataInner {
     fn default() -> Self {
         Self {
             surface: None,
-            locked_pointer: None,
-            confined_pointer: None,
+            position: None,
+            constraint: None,
             latest_button_serial: 0,
             phase: TouchPhase::Ended,
         }
@@ -369,7 +430,8 @@ fn wayland_button_to_winit(button: u32) -> MouseButton {
         BTN_MIDDLE => MouseButton::Middle,
         BTN_BACK | BTN_SIDE => MouseButton::Back,
         BTN_FORWARD | BTN_EXTRA => MouseButton::Forward,
-        button => MouseButton::Other(button as u16),
+        // Count from the left button, so that `BTN_TASK` is the 8th button.
+        button => MouseButton::Other(button.saturating_sub(BTN_LEFT) as u16),
     }
 }
 
@@ -473,3 +535,27 @@ delegate_dispatch!(WinitState: [ WpCursorShapeDeviceV1: GlobalData] => SeatState
 delegate_dispatch!(WinitState: [ZwpPointerConstraintsV1: GlobalData] => PointerConstraintsState);
 delegate_dispatch!(WinitState: [ZwpLockedPointerV1: GlobalData] => PointerConstraintsState);
 delegate_dispatch!(WinitState: [ZwpConfinedPointerV1: GlobalData] => PointerConstraintsState);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_buttons_like_x11() {
+        // The evdev codes, which X11 reports as buttons 1 to 3 and 8 to 13.
+        let buttons = [
+            (0x110, MouseButton::Left),
+            (0x111, MouseButton::Right),
+            (0x112, MouseButton::Middle),
+            (0x113, MouseButton::Back),
+            (0x114, MouseButton::Forward),
+            (0x115, MouseButton::Forward),
+            (0x116, MouseButton::Back),
+            (0x117, MouseButton::Other(7)),
+            (0x118, MouseButton::Other(8)),
+        ];
+        for (button, expected) in buttons {
+            assert_eq!(wayland_button_to_winit(button), expected, "button {button:#x}");
+        }
+    }
+}
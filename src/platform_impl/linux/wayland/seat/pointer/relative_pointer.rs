@@ -4,6 +4,7 @@ use std::ops::Deref;
 
 use sctk::reexports::client::globals::{BindError, GlobalList};
 use sctk::reexports::client::{delegate_dispatch, Dispatch};
+use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::{Connection, QueueHandle};
 use sctk::reexports::protocols::wp::relative_pointer::zv1::{
     client::zwp_relative_pointer_manager_v1::ZwpRelativePointerManagerV1,
@@ -14,6 +15,7 @@ use sctk::globals::GlobalData;
 
 use crate::event::DeviceEvent;
 use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::DeviceId;
 
 /// Wrapper around the relative pointer.
 pub struct RelativePointerState {
@@ -51,12 +53,12 @@ impl Dispatch<ZwpRelativePointerManagerV1, GlobalData, WinitState> for RelativeP
     }
 }
 
-impl Dispatch<ZwpRelativePointerV1, GlobalData, WinitState> for RelativePointerState {
+impl Dispatch<ZwpRelativePointerV1, WlSeat, WinitState> for RelativePointerState {
     fn event(
         state: &mut WinitState,
         _proxy: &ZwpRelativePointerV1,
         event: <ZwpRelativePointerV1 as wayland_client::Proxy>::Event,
-        _data: &GlobalData,
+        seat: &WlSeat,
         _conn: &Connection,
         _qhandle: &QueueHandle<WinitState>,
     ) {
@@ -66,18 +68,19 @@ impl Dispatch<ZwpRelativePointerV1, GlobalData, WinitState> for RelativePointerS
             },
             _ => return,
         };
+        let device_id = DeviceId::new(seat);
         state
             .events_sink
-            .push_device_event(DeviceEvent::Motion { axis: 0, value: dx_unaccel }, super::DeviceId);
+            .push_device_event(DeviceEvent::Motion { axis: 0, value: dx_unaccel }, device_id);
         state
             .events_sink
-            .push_device_event(DeviceEvent::Motion { axis: 1, value: dy_unaccel }, super::DeviceId);
+            .push_device_event(DeviceEvent::Motion { axis: 1, value: dy_unaccel }, device_id);
         state.events_sink.push_device_event(
             DeviceEvent::MouseMotion { delta: (dx_unaccel, dy_unaccel) },
-            super::DeviceId,
+            device_id,
         );
     }
 }
 
-delegate_dispatch!(WinitState: [ZwpRelativePointerV1: GlobalData] => RelativePointerState);
+delegate_dispatch!(WinitState: [ZwpRelativePointerV1: WlSeat] => RelativePointerState);
 delegate_dispatch!(WinitState: [ZwpRelativePointerManagerV1: GlobalData] => RelativePointerState);
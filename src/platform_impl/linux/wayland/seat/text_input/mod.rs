@@ -72,8 +72,7 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 };
 
                 if window.ime_allowed() {
-                    text_input.enable();
-                    text_input.set_content_type_by_purpose(window.ime_purpose());
+                    window.enable_text_input(text_input);
                     text_input.commit();
                     state.events_sink.push_window_event(WindowEvent::Ime(Ime::Enabled), window_id);
                 }
@@ -115,6 +114,9 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 text_input_data.pending_preedit = None;
                 text_input_data.pending_commit = text;
             },
+            TextInputEvent::DeleteSurroundingText { before_length, after_length } => {
+                text_input_data.pending_delete = Some((before_length, after_length));
+            },
             TextInputEvent::Done { .. } => {
                 let window_id = match text_input_data.surface.as_ref() {
                     Some(surface) => wayland::make_wid(surface),
@@ -123,10 +125,21 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
 
                 // Clear preedit at the start of `Done`.
                 state.events_sink.push_window_event(
-                    WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                    WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                     window_id,
                 );
 
+                // Send `DeleteSurrounding`, which has to be applied before the commit.
+                if let Some((before_bytes, after_bytes)) = text_input_data.pending_delete.take() {
+                    state.events_sink.push_window_event(
+                        WindowEvent::Ime(Ime::DeleteSurrounding {
+                            before_bytes: before_bytes as usize,
+                            after_bytes: after_bytes as usize,
+                        }),
+                        window_id,
+                    );
+                }
+
                 // Send `Commit`.
                 if let Some(text) = text_input_data.pending_commit.take() {
                     state
@@ -140,14 +153,11 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                         preedit.cursor_begin.map(|b| (b, preedit.cursor_end.unwrap_or(b)));
 
                     state.events_sink.push_window_event(
-                        WindowEvent::Ime(Ime::Preedit(preedit.text, cursor_range)),
+                        WindowEvent::Ime(Ime::Preedit(preedit.text, cursor_range, Vec::new())),
                         window_id,
                     );
                 }
             },
-            TextInputEvent::DeleteSurroundingText { .. } => {
-                // Not handled.
-            },
             _ => {},
         }
     }
@@ -184,6 +194,9 @@ pub struct TextInputDataInner {
 
     /// The preedit to submit on `done`.
     pending_preedit: Option<Preedit>,
+
+    /// The lengths of the surrounding text to delete on `done`.
+    pending_delete: Option<(u32, u32)>,
 }
 
 /// The state of the preedit.
@@ -41,7 +41,7 @@ impl TouchHandler for WinitState {
         self.events_sink.push_window_event(
             WindowEvent::Touch(Touch {
                 device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                    DeviceId,
+                    DeviceId::new(touch.seat()),
                 )),
                 phase: TouchPhase::Started,
                 location: location.to_physical(scale_factor),
@@ -78,7 +78,7 @@ impl TouchHandler for WinitState {
         self.events_sink.push_window_event(
             WindowEvent::Touch(Touch {
                 device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                    DeviceId,
+                    DeviceId::new(touch.seat()),
                 )),
                 phase: TouchPhase::Ended,
                 location: touch_point.location.to_physical(scale_factor),
@@ -117,7 +117,7 @@ impl TouchHandler for WinitState {
         self.events_sink.push_window_event(
             WindowEvent::Touch(Touch {
                 device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                    DeviceId,
+                    DeviceId::new(touch.seat()),
                 )),
                 phase: TouchPhase::Moved,
                 location: touch_point.location.to_physical(scale_factor),
@@ -143,7 +143,7 @@ impl TouchHandler for WinitState {
             self.events_sink.push_window_event(
                 WindowEvent::Touch(Touch {
                     device_id: crate::event::DeviceId(crate::platform_impl::DeviceId::Wayland(
-                        DeviceId,
+                        DeviceId::new(touch.seat()),
                     )),
                     phase: TouchPhase::Cancelled,
                     location,
@@ -12,6 +12,7 @@ use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 
 use sctk::compositor::{CompositorHandler, CompositorState};
+use sctk::data_device_manager::DataDeviceManagerState;
 use sctk::output::{OutputHandler, OutputState};
 use sctk::registry::{ProvidesRegistryState, RegistryState};
 use sctk::seat::pointer::ThemedPointer;
@@ -23,6 +24,9 @@ use sctk::shm::slot::SlotPool;
 use sctk::shm::{Shm, ShmHandler};
 use sctk::subcompositor::SubcompositorState;
 
+use crate::event::{Signal, WindowEvent};
+use crate::event_loop::DpiPolicy;
+use crate::platform_impl::common::session::EndSession;
 use crate::platform_impl::wayland::event_loop::sink::EventSink;
 use crate::platform_impl::wayland::output::MonitorHandle;
 use crate::platform_impl::wayland::seat::{
@@ -33,6 +37,7 @@ use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
 use crate::platform_impl::wayland::types::wp_fractional_scaling::FractionalScalingManager;
 use crate::platform_impl::wayland::types::wp_viewporter::ViewporterState;
 use crate::platform_impl::wayland::types::xdg_activation::XdgActivationState;
+use crate::platform_impl::wayland::types::xdg_toplevel_icon::XdgToplevelIconManager;
 use crate::platform_impl::wayland::window::{WindowRequests, WindowState};
 use crate::platform_impl::wayland::{WaylandError, WindowId};
 use crate::platform_impl::OsError;
@@ -84,6 +89,9 @@ pub struct WinitState {
     /// The state of the text input on the client.
     pub text_input_state: Option<TextInputState>,
 
+    /// The data device manager, for drag and drop.
+    pub data_device_manager_state: Option<DataDeviceManagerState>,
+
     /// Observed monitors.
     pub monitors: Arc<Mutex<Vec<MonitorHandle>>>,
 
@@ -109,15 +117,47 @@ pub struct WinitState {
     /// KWin blur manager.
     pub kwin_blur_manager: Option<KWinBlurManager>,
 
+    /// The manager of toplevel icons.
+    pub xdg_toplevel_icon_manager: Option<XdgToplevelIconManager>,
+
     /// Loop handle to re-register event sources, such as keyboard repeat.
     pub loop_handle: LoopHandle<'static, Self>,
 
     /// Whether we have dispatched events to the user thus we want to
     /// send `AboutToWait` and normally wakeup the user.
     pub dispatched_events: bool,
+
+    /// Whether `SIGTERM` was received since the last iteration, unless it was requested, or the
+    /// session ended.
+    pub terminating: bool,
+
+    /// The session manager asks whether to end the session.
+    pub session_end: Option<EndSession>,
+
+    /// The requested signals received since the last iteration.
+    pub signals: Vec<Signal>,
+
+    /// The tokens of the file descriptors of the application that are ready.
+    pub fd_ready: Vec<u64>,
+
+    /// Whether outputs were added or removed since the last iteration.
+    pub monitors_changed: bool,
 }
 
 impl WinitState {
+    /// Push `VisibilityChanged` if the visibility of the window changed.
+    pub fn update_visibility(&mut self, window_id: WindowId) {
+        let visibility = match self.windows.get_mut().get(&window_id) {
+            Some(window) => window.lock().unwrap().update_visibility(),
+            None => return,
+        };
+
+        if let Some(visibility) = visibility {
+            self.events_sink
+                .push_window_event(WindowEvent::VisibilityChanged(visibility), window_id);
+        }
+    }
+
     pub fn new(
         globals: &GlobalList,
         queue_handle: &QueueHandle<Self>,
@@ -177,9 +217,11 @@ impl WinitState {
             viewporter_state,
             fractional_scaling_manager,
             kwin_blur_manager: KWinBlurManager::new(globals, queue_handle).ok(),
+            xdg_toplevel_icon_manager: XdgToplevelIconManager::new(globals, queue_handle).ok(),
 
             seats,
             text_input_state: TextInputState::new(globals, queue_handle).ok(),
+            data_device_manager_state: DataDeviceManagerState::bind(globals, queue_handle).ok(),
 
             relative_pointer: RelativePointerState::new(globals, queue_handle).ok(),
             pointer_constraints: PointerConstraintsState::new(globals, queue_handle)
@@ -192,6 +234,11 @@ impl WinitState {
             loop_handle,
             // Make it true by default.
             dispatched_events: true,
+            terminating: false,
+            session_end: None,
+            signals: Vec::new(),
+            fd_ready: Vec::new(),
+            monitors_changed: false,
         })
     }
 
@@ -203,6 +250,7 @@ impl WinitState {
     ) {
         // Check if the cursor surface.
         let window_id = super::make_wid(surface);
+        let scale_factor = self.dpi_scale_factor(scale_factor);
 
         if let Some(window) = self.windows.get_mut().get(&window_id) {
             // Don't update the scaling factor, when legacy method is used.
@@ -238,6 +286,17 @@ impl WinitState {
         }
     }
 
+    /// Applies the DPI policy to the scale factor reported by the compositor.
+    pub fn dpi_scale_factor(&self, scale_factor: f64) -> f64 {
+        let scale_factor = DpiPolicy::current().apply(scale_factor);
+        // Without fractional scaling the scale is applied with the integer buffer scale.
+        if self.fractional_scaling_manager.is_none() {
+            scale_factor.round().max(1.)
+        } else {
+            scale_factor
+        }
+    }
+
     pub fn queue_close(updates: &mut Vec<WindowCompositorUpdate>, window_id: WindowId) {
         let pos = if let Some(pos) = updates.iter().position(|update| update.window_id == window_id)
         {
@@ -283,14 +342,36 @@ impl WindowHandler for WinitState {
         };
 
         // Populate the configure to the window.
-        self.window_compositor_updates[pos].resized |= self
+        let mut window_state = self
             .windows
             .get_mut()
             .get_mut(&window_id)
             .expect("got configure for dead window.")
             .lock()
-            .unwrap()
-            .configure(configure, &self.shm, &self.subcompositor_state);
+            .unwrap();
+        let was_resizing = window_state.is_resizing();
+        self.window_compositor_updates[pos].resized |=
+            window_state.configure(configure, &self.shm, &self.subcompositor_state);
+        let visibility = window_state.update_visibility();
+        let tiling_state = window_state.update_tiling_state();
+        let resizing = window_state.is_resizing();
+        drop(window_state);
+
+        if let Some(visibility) = visibility {
+            self.events_sink
+                .push_window_event(WindowEvent::VisibilityChanged(visibility), window_id);
+        }
+
+        if let Some(tiling_state) = tiling_state {
+            self.events_sink
+                .push_window_event(WindowEvent::TilingStateChanged(tiling_state), window_id);
+        }
+
+        if resizing != was_resizing {
+            let event =
+                if resizing { WindowEvent::MoveResizeBegan } else { WindowEvent::MoveResizeEnded };
+            self.events_sink.push_window_event(event, window_id);
+        }
 
         // NOTE: configure demands wl_surface::commit, however winit doesn't commit on behalf of the
         // users, since it can break a lot of things, thus it'll ask users to redraw instead.
@@ -313,6 +394,8 @@ impl OutputHandler for WinitState {
 
     fn new_output(&mut self, _: &Connection, _: &QueueHandle<Self>, output: WlOutput) {
         self.monitors.lock().unwrap().push(MonitorHandle::new(output));
+        self.monitors_changed = true;
+        self.dispatched_events = true;
     }
 
     fn update_output(&mut self, _: &Connection, _: &QueueHandle<Self>, updated: WlOutput) {
@@ -331,6 +414,8 @@ impl OutputHandler for WinitState {
         if let Some(pos) = monitors.iter().position(|output| output == &removed) {
             monitors.remove(pos);
         }
+        self.monitors_changed = true;
+        self.dispatched_events = true;
     }
 }
 
@@ -403,7 +488,7 @@ pub struct WindowCompositorUpdate {
 }
 
 impl WindowCompositorUpdate {
-    fn new(window_id: WindowId) -> Self {
+    pub fn new(window_id: WindowId) -> Self {
         Self { window_id, resized: false, scale_changed: false, close_window: false }
     }
 }
@@ -8,7 +8,8 @@ use crate::cursor::CursorImage;
 #[derive(Debug)]
 pub enum SelectedCursor {
     Named(CursorIcon),
-    Custom(CustomCursor),
+    /// A cursor for each image, sorted by scale factor.
+    Custom(Vec<CustomCursor>),
 }
 
 impl Default for SelectedCursor {
@@ -24,6 +25,7 @@ pub struct CustomCursor {
     pub h: i32,
     pub hotspot_x: i32,
     pub hotspot_y: i32,
+    pub scale_factor: f64,
 }
 
 impl CustomCursor {
@@ -37,16 +39,7 @@ impl CustomCursor {
             )
             .unwrap();
 
-        for (canvas_chunk, rgba) in canvas.chunks_exact_mut(4).zip(image.rgba.chunks_exact(4)) {
-            // Alpha in buffer is premultiplied.
-            let alpha = rgba[3] as f32 / 255.;
-            let r = (rgba[0] as f32 * alpha) as u32;
-            let g = (rgba[1] as f32 * alpha) as u32;
-            let b = (rgba[2] as f32 * alpha) as u32;
-            let color = ((rgba[3] as u32) << 24) + (r << 16) + (g << 8) + b;
-            let array: &mut [u8; 4] = canvas_chunk.try_into().unwrap();
-            *array = color.to_le_bytes();
-        }
+        write_argb8888(canvas, &image.rgba);
 
         CustomCursor {
             buffer,
@@ -54,6 +47,21 @@ impl CustomCursor {
             h: image.height as i32,
             hotspot_x: image.hotspot_x as i32,
             hotspot_y: image.hotspot_y as i32,
+            scale_factor: image.scale_factor,
         }
     }
 }
+
+/// Write RGBA pixels into a buffer of the `Argb8888` format.
+pub(crate) fn write_argb8888(canvas: &mut [u8], rgba: &[u8]) {
+    for (canvas_chunk, rgba) in canvas.chunks_exact_mut(4).zip(rgba.chunks_exact(4)) {
+        // Alpha in buffer is premultiplied.
+        let alpha = rgba[3] as f32 / 255.;
+        let r = (rgba[0] as f32 * alpha) as u32;
+        let g = (rgba[1] as f32 * alpha) as u32;
+        let b = (rgba[2] as f32 * alpha) as u32;
+        let color = ((rgba[3] as u32) << 24) + (r << 16) + (g << 8) + b;
+        let array: &mut [u8; 4] = canvas_chunk.try_into().unwrap();
+        *array = color.to_le_bytes();
+    }
+}
@@ -76,6 +76,7 @@ impl Dispatch<XdgActivationTokenV1, XdgActivationTokenData, WinitState> for XdgA
                     attention_requested.store(false, std::sync::atomic::Ordering::Relaxed);
                 }
             },
+            XdgActivationTokenData::Activate(surface) => global.activate(token, surface),
             XdgActivationTokenData::Obtain((window_id, serial)) => {
                 state.events_sink.push_window_event(
                     crate::event::WindowEvent::ActivationTokenDone {
@@ -95,6 +96,8 @@ impl Dispatch<XdgActivationTokenV1, XdgActivationTokenData, WinitState> for XdgA
 pub enum XdgActivationTokenData {
     /// Request user attention for the given surface.
     Attention((WlSurface, Weak<AtomicBool>)),
+    /// Activate the given surface.
+    Activate(WlSurface),
     /// Get a token to be passed outside of the winit.
     Obtain((WindowId, AsyncRequestSerial)),
 }
@@ -0,0 +1,109 @@
+//! Handling of the xdg-toplevel-icon protocol.
+
+use sctk::reexports::client::globals::{BindError, GlobalList};
+use sctk::reexports::client::{delegate_dispatch, Connection, Dispatch, Proxy, QueueHandle};
+use sctk::reexports::protocols::xdg::shell::client::xdg_toplevel::XdgToplevel;
+
+use sctk::globals::GlobalData;
+use sctk::reexports::client::protocol::wl_shm::Format;
+use sctk::shm::slot::{Buffer, SlotPool};
+
+use crate::platform_impl::wayland::state::WinitState;
+use crate::platform_impl::wayland::types::cursor::write_argb8888;
+use crate::platform_impl::PlatformIcon;
+
+use self::protocol::xdg_toplevel_icon_manager_v1::XdgToplevelIconManagerV1;
+use self::protocol::xdg_toplevel_icon_v1::XdgToplevelIconV1;
+
+/// The bindings of the protocol, which isn't part of the `wayland-protocols` release in use yet.
+#[allow(dead_code, non_camel_case_types, unused_imports, missing_docs, clippy::all)]
+pub mod protocol {
+    use sctk::reexports::client as wayland_client;
+    use sctk::reexports::client::protocol::*;
+    use sctk::reexports::protocols::xdg::shell::client::*;
+
+    pub mod __interfaces {
+        use sctk::reexports::client::protocol::__interfaces::*;
+        use sctk::reexports::protocols::xdg::shell::client::__interfaces::*;
+        wayland_scanner::generate_interfaces!(
+            "src/platform_impl/linux/wayland/protocols/xdg-toplevel-icon-v1.xml"
+        );
+    }
+    use self::__interfaces::*;
+
+    wayland_scanner::generate_client_code!(
+        "src/platform_impl/linux/wayland/protocols/xdg-toplevel-icon-v1.xml"
+    );
+}
+
+/// The toplevel icon manager.
+#[derive(Debug, Clone)]
+pub struct XdgToplevelIconManager {
+    manager: XdgToplevelIconManagerV1,
+}
+
+impl XdgToplevelIconManager {
+    pub fn new(
+        globals: &GlobalList,
+        queue_handle: &QueueHandle<WinitState>,
+    ) -> Result<Self, BindError> {
+        let manager = globals.bind(queue_handle, 1..=1, GlobalData)?;
+        Ok(Self { manager })
+    }
+
+    pub fn create_icon(&self, queue_handle: &QueueHandle<WinitState>) -> XdgToplevelIconV1 {
+        self.manager.create_icon(queue_handle, ())
+    }
+
+    /// Set the icon of the toplevel, or reset it to the default one, applied on the next commit.
+    pub fn set_icon(&self, toplevel: &XdgToplevel, icon: Option<&XdgToplevelIconV1>) {
+        self.manager.set_icon(toplevel, icon)
+    }
+}
+
+/// Allocate the pixels of `icon`, centered in a square as the protocol requires.
+pub(crate) fn icon_buffer(pool: &mut SlotPool, icon: &PlatformIcon) -> Option<Buffer> {
+    let (width, height) = (icon.width as usize, icon.height as usize);
+    let size = width.max(height);
+    let (buffer, canvas) = pool
+        .create_buffer(size as i32, size as i32, 4 * size as i32, Format::Argb8888)
+        .map_err(|err| tracing::warn!("failed to allocate the window icon: {err}"))
+        .ok()?;
+
+    canvas.fill(0);
+    let (left, top) = ((size - width) / 2, (size - height) / 2);
+    for (y, row) in icon.rgba.chunks_exact(4 * width).enumerate() {
+        let start = 4 * ((top + y) * size + left);
+        write_argb8888(&mut canvas[start..start + 4 * width], row);
+    }
+    Some(buffer)
+}
+
+impl Dispatch<XdgToplevelIconManagerV1, GlobalData, WinitState> for XdgToplevelIconManager {
+    fn event(
+        _: &mut WinitState,
+        _: &XdgToplevelIconManagerV1,
+        _: <XdgToplevelIconManagerV1 as Proxy>::Event,
+        _: &GlobalData,
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        // The preferred sizes only matter for scalable icons, which an `Icon` isn't.
+    }
+}
+
+impl Dispatch<XdgToplevelIconV1, (), WinitState> for XdgToplevelIconManager {
+    fn event(
+        _: &mut WinitState,
+        _: &XdgToplevelIconV1,
+        _: <XdgToplevelIconV1 as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<WinitState>,
+    ) {
+        unreachable!("no events defined for xdg_toplevel_icon_v1");
+    }
+}
+
+delegate_dispatch!(WinitState: [XdgToplevelIconManagerV1: GlobalData] => XdgToplevelIconManager);
+delegate_dispatch!(WinitState: [XdgToplevelIconV1: ()] => XdgToplevelIconManager);
@@ -14,23 +14,25 @@ use sctk::shell::WaylandSurface;
 
 use tracing::warn;
 
-use crate::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
 use crate::event::{Ime, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
+use crate::ime::SurroundingText;
 use crate::platform_impl::{
     Fullscreen, MonitorHandle as PlatformMonitorHandle, OsError, PlatformIcon,
 };
 use crate::window::{
-    Cursor, CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowLevel,
+    CapturePolicy, Cursor, CursorGrabMode, FocusReason, ImePurpose, KeyboardHints, ResizeDirection,
+    TextServicesConfig, Theme, TilingState, UserAttentionType, WindowAttributes, WindowButtons,
+    WindowCapabilities, WindowLevel, WindowVisibility,
 };
 
 use super::event_loop::sink::EventSink;
 use super::output::MonitorHandle;
 use super::state::WinitState;
 use super::types::xdg_activation::XdgActivationTokenData;
-use super::{ActiveEventLoop, WaylandError, WindowId};
+use super::{ActiveEventLoop, DeviceId, WaylandError, WindowId};
 
 pub(crate) mod state;
 
@@ -114,11 +116,17 @@ impl Window {
             attributes.preferred_theme,
         );
 
+        // The compositor only reports scale factors other than `1`, so the DPI policy has to be
+        // applied to the initial one.
+        window_state.set_scale_factor(state.dpi_scale_factor(1.));
+
         // Set transparency hint.
         window_state.set_transparent(attributes.transparent);
 
         window_state.set_blur(attributes.blur);
 
+        window_state.synchronized_resize = attributes.synchronized_resize;
+
         // Set the decorations hint.
         window_state.set_decorate(attributes.decorations);
 
@@ -130,6 +138,12 @@ impl Window {
         // Set the window title.
         window_state.set_title(attributes.title);
 
+        // Set the window icon.
+        window_state.icon_name = attributes.platform_specific.wayland.icon_name;
+        if attributes.window_icon.is_some() || window_state.icon_name.is_some() {
+            window_state.set_window_icon(attributes.window_icon.as_ref().map(|icon| &icon.inner));
+        }
+
         // Set the min and max sizes. We must set the hints upon creating a window, so
         // we use the default `1.` scaling...
         let min_size = attributes.min_inner_size.map(|size| size.to_logical(1.));
@@ -290,6 +304,17 @@ impl Window {
         self.window_state.lock().unwrap().request_frame_callback();
     }
 
+    pub fn present_rgba(
+        &self,
+        buffer: &[u8],
+        size: PhysicalSize<u32>,
+    ) -> Result<(), ExternalError> {
+        self.window_state.lock().unwrap().present_rgba(buffer, size)?;
+        // Wake the event loop to flush the commit.
+        self.event_loop_awakener.ping();
+        Ok(())
+    }
+
     #[inline]
     pub fn outer_size(&self) -> PhysicalSize<u32> {
         let window_state = self.window_state.lock().unwrap();
@@ -345,6 +370,15 @@ impl Window {
         self.window_state.lock().unwrap().has_focus()
     }
 
+    #[inline]
+    pub fn visibility(&self) -> WindowVisibility {
+        self.window_state.lock().unwrap().visibility()
+    }
+
+    pub fn tiling_state(&self) -> TilingState {
+        self.window_state.lock().unwrap().tiling_state()
+    }
+
     #[inline]
     pub fn is_minimized(&self) -> Option<bool> {
         // XXX clients don't know whether they are minimized or not.
@@ -397,6 +431,16 @@ impl Window {
         self.window_state.lock().unwrap().set_blur(blur);
     }
 
+    #[inline]
+    pub fn capabilities(&self) -> WindowCapabilities {
+        let mut capabilities = WindowCapabilities::DECORATIONS
+            | WindowCapabilities::CURSOR_GRAB
+            | WindowCapabilities::REDRAW_THREAD
+            | WindowCapabilities::SOFTWARE_PRESENT;
+        capabilities.set(WindowCapabilities::FOCUS, self.xdg_activation.is_some());
+        capabilities
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorate: bool) {
         self.window_state.lock().unwrap().set_decorate(decorate)
@@ -411,7 +455,11 @@ impl Window {
     pub fn set_window_level(&self, _level: WindowLevel) {}
 
     #[inline]
-    pub(crate) fn set_window_icon(&self, _window_icon: Option<PlatformIcon>) {}
+    pub(crate) fn set_window_icon(&self, window_icon: Option<PlatformIcon>) {
+        self.window_state.lock().unwrap().set_window_icon(window_icon.as_ref());
+        // NOTE: Requires commit to be applied.
+        self.request_redraw();
+    }
 
     #[inline]
     pub fn set_minimized(&self, minimized: bool) {
@@ -493,6 +541,13 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_cursor_for_device(&self, device_id: DeviceId, cursor: Cursor) {
+        if let Some(seat_id) = device_id.seat_id() {
+            self.window_state.lock().unwrap().set_cursor_for_seat(seat_id, cursor);
+        }
+    }
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         self.window_state.lock().unwrap().set_cursor_visible(visible);
@@ -545,6 +600,11 @@ impl Window {
         self.window_state.lock().unwrap().set_cursor_grab(mode)
     }
 
+    #[inline]
+    pub fn set_cursor_grab_regions(&self, regions: Option<Vec<crate::window::Region>>) {
+        self.window_state.lock().unwrap().set_cursor_grab_regions(regions)
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, position: Position) -> Result<(), ExternalError> {
         let scale_factor = self.scale_factor();
@@ -557,6 +617,14 @@ impl Window {
             .map(|_| self.request_redraw())
     }
 
+    #[inline]
+    pub fn cursor_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        let window_state = self.window_state.lock().unwrap();
+        // The position is only known while the pointer is over the window.
+        let position = window_state.cursor_position().ok_or(ExternalError::Ignored)?;
+        Ok(position.to_physical(window_state.scale_factor()))
+    }
+
     #[inline]
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         self.window_state.lock().unwrap().drag_window()
@@ -579,15 +647,33 @@ impl Window {
         }
     }
 
+    pub fn set_hittest_regions(
+        &self,
+        regions: &[crate::window::Region],
+    ) -> Result<(), ExternalError> {
+        let input_region = Region::new(&*self.compositor).map_err(|_| {
+            ExternalError::Os(os_error!(OsError::Misc("failed to set input region.")))
+        })?;
+
+        // The input region is in surface local coordinates.
+        let scale_factor = self.scale_factor();
+        for region in regions {
+            let position: LogicalPosition<i32> = region.position.to_logical(scale_factor);
+            let size: LogicalSize<i32> = region.size.to_logical(scale_factor);
+            input_region.add(position.x, position.y, size.width, size.height);
+        }
+
+        self.window.wl_surface().set_input_region(Some(input_region.wl_region()));
+        Ok(())
+    }
+
     #[inline]
     pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
-        let window_state = self.window_state.lock().unwrap();
-        if window_state.ime_allowed() {
-            let scale_factor = window_state.scale_factor();
-            let position = position.to_logical(scale_factor);
-            let size = size.to_logical(scale_factor);
-            window_state.set_ime_cursor_area(position, size);
-        }
+        let mut window_state = self.window_state.lock().unwrap();
+        let scale_factor = window_state.scale_factor();
+        let position = position.to_logical(scale_factor);
+        let size = size.to_logical(scale_factor);
+        window_state.set_ime_cursor_area(position, size);
     }
 
     #[inline]
@@ -601,6 +687,17 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, text: SurroundingText) {
+        self.window_state.lock().unwrap().set_ime_surrounding_text(text);
+    }
+
+    #[inline]
+    pub fn set_text_services(&self, _config: TextServicesConfig) {}
+
+    #[inline]
+    pub fn set_keyboard_hints(&self, _hints: KeyboardHints) {}
+
     #[inline]
     pub fn set_ime_purpose(&self, purpose: ImePurpose) {
         self.window_state.lock().unwrap().set_ime_purpose(purpose);
@@ -609,6 +706,36 @@ impl Window {
     #[inline]
     pub fn focus_window(&self) {}
 
+    #[inline]
+    pub fn focus_window_with(&self, reason: FocusReason) {
+        let Some(xdg_activation) = self.xdg_activation.as_ref() else {
+            return;
+        };
+
+        let input = match reason {
+            FocusReason::Token(token) => {
+                xdg_activation.activate(token._token, self.surface());
+                return;
+            },
+            FocusReason::UserInitiated => self.window_state.lock().unwrap().latest_button_serial(),
+            FocusReason::Programmatic => None,
+        };
+
+        // Without the serial of a user input, the compositor decides on its own whether to
+        // activate the window, and usually requests attention for it instead.
+        let surface = self.surface().clone();
+        let data = XdgActivationTokenData::Activate(surface.clone());
+        let xdg_activation_token = xdg_activation.get_activation_token(&self.queue_handle, data);
+        xdg_activation_token.set_surface(&surface);
+        if let Some((seat, serial)) = input {
+            xdg_activation_token.set_serial(serial, &seat);
+        }
+        xdg_activation_token.commit();
+    }
+
+    #[inline]
+    pub fn set_focusable(&self, _focusable: bool) {}
+
     #[inline]
     pub fn surface(&self) -> &WlSurface {
         self.window.wl_surface()
@@ -690,6 +817,8 @@ impl Window {
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn set_capture_policy(&self, _policy: CapturePolicy) {}
+
     #[inline]
     pub fn title(&self) -> String {
         self.window_state.lock().unwrap().title().to_owned()
@@ -1,15 +1,16 @@
 //! The state of the window, which is shared with the event-loop.
 
+use std::mem;
 use std::num::NonZeroU32;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 
-use ahash::HashSet;
+use ahash::{HashMap, HashSet};
 use tracing::{info, warn};
 
 use sctk::reexports::client::backend::ObjectId;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
-use sctk::reexports::client::protocol::wl_shm::WlShm;
+use sctk::reexports::client::protocol::wl_shm::{Format, WlShm};
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle};
 use sctk::reexports::csd_frame::{
@@ -25,19 +26,25 @@ use sctk::seat::pointer::{PointerDataExt, ThemedPointer};
 use sctk::shell::xdg::window::{DecorationMode, Window, WindowConfigure};
 use sctk::shell::xdg::XdgSurface;
 use sctk::shell::WaylandSurface;
-use sctk::shm::slot::SlotPool;
+use sctk::shm::slot::{Buffer, SlotPool};
 use sctk::shm::Shm;
 use sctk::subcompositor::SubcompositorState;
 use wayland_protocols_plasma::blur::client::org_kde_kwin_blur::OrgKdeKwinBlur;
 
-use crate::cursor::CustomCursor as RootCustomCursor;
+use crate::cursor::{self, CustomCursor as RootCustomCursor};
 use crate::dpi::{LogicalPosition, LogicalSize, PhysicalSize, Size};
 use crate::error::{ExternalError, NotSupportedError};
-use crate::platform_impl::wayland::logical_to_physical_rounded;
+use crate::event::{ResizeFrameWaiter, SeatId};
+use crate::ime::SurroundingText;
 use crate::platform_impl::wayland::types::cursor::{CustomCursor, SelectedCursor};
 use crate::platform_impl::wayland::types::kwin_blur::KWinBlurManager;
-use crate::platform_impl::{PlatformCustomCursor, WindowId};
-use crate::window::{CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme};
+use crate::platform_impl::wayland::types::xdg_toplevel_icon::{self, XdgToplevelIconManager};
+use crate::platform_impl::wayland::{logical_to_physical_rounded, make_seat_id};
+use crate::platform_impl::{PlatformCustomCursor, PlatformIcon, WindowId};
+use crate::window::{
+    CursorGrabMode, CursorIcon, ImePurpose, ResizeDirection, Theme, TiledEdges, TilingState,
+    WindowVisibility,
+};
 
 use crate::platform_impl::wayland::seat::{
     PointerConstraintsState, WinitPointerData, WinitPointerDataExt, ZwpTextInputV3Ext,
@@ -71,6 +78,9 @@ pub struct WindowState {
 
     selected_cursor: SelectedCursor,
 
+    /// The cursors set for the pointers of a single seat, in place of the selected cursor.
+    seat_cursors: HashMap<SeatId, SelectedCursor>,
+
     /// Whether the cursor is visible.
     pub cursor_visible: bool,
 
@@ -94,6 +104,12 @@ pub struct WindowState {
     /// Seats that has keyboard focus on that window.
     seat_focus: HashSet<ObjectId>,
 
+    /// The visibility last reported with `WindowEvent::VisibilityChanged`.
+    last_visibility: WindowVisibility,
+
+    /// The tiling state last reported with `WindowEvent::TilingStateChanged`.
+    last_tiling_state: TilingState,
+
     /// The scale factor of the window.
     scale_factor: f64,
 
@@ -109,6 +125,12 @@ pub struct WindowState {
     /// Whether the IME input is allowed for that window.
     ime_allowed: bool,
 
+    /// The last IME cursor area, which has to be sent again whenever text input is enabled.
+    ime_cursor_area: Option<(LogicalPosition<u32>, LogicalSize<u32>)>,
+
+    /// The last IME surrounding text, which also has to be sent again.
+    ime_surrounding_text: Option<SurroundingText>,
+
     /// The current IME purpose.
     ime_purpose: ImePurpose,
 
@@ -144,12 +166,28 @@ pub struct WindowState {
     fractional_scale: Option<WpFractionalScaleV1>,
     blur: Option<OrgKdeKwinBlur>,
     blur_manager: Option<KWinBlurManager>,
+    icon_manager: Option<XdgToplevelIconManager>,
+
+    /// The themed icon, which compositors may prefer over the pixels of the window icon.
+    pub icon_name: Option<String>,
+
+    /// The pixels of the window icon, which must not change while the icon is set.
+    icon_buffer: Option<Buffer>,
+
+    /// The pixels presented with `present_rgba`, which must not change while attached.
+    presented_buffer: Option<Buffer>,
 
     /// Whether the client side decorations have pending move operations.
     ///
     /// The value is the serial of the event triggered moved.
     has_pending_move: Option<u32>,
 
+    /// Whether `WindowEvent::Resized` carries a `ResizeFrame`.
+    pub synchronized_resize: bool,
+
+    /// The last synchronized resize, which the application might not have presented yet.
+    pub(crate) pending_resize_frame: Option<ResizeFrameWaiter>,
+
     /// The underlying SCTK window.
     pub window: Window,
 
@@ -185,19 +223,28 @@ impl WindowState {
         Self {
             blur: None,
             blur_manager: winit_state.kwin_blur_manager.clone(),
+            icon_manager: winit_state.xdg_toplevel_icon_manager.clone(),
+            icon_name: None,
+            icon_buffer: None,
+            presented_buffer: None,
             compositor,
             connection,
             csd_fails: false,
             cursor_grab_mode: GrabState::new(),
             selected_cursor: Default::default(),
+            seat_cursors: Default::default(),
             cursor_visible: true,
             decorate: true,
             fractional_scale,
             frame: None,
             frame_callback_state: FrameCallbackState::None,
             seat_focus: Default::default(),
+            last_visibility: WindowVisibility::Hidden,
+            last_tiling_state: TilingState::Floating,
             has_pending_move: None,
             ime_allowed: false,
+            ime_cursor_area: None,
+            ime_surrounding_text: None,
             ime_purpose: ImePurpose::Normal,
             last_configure: None,
             max_inner_size: None,
@@ -206,6 +253,8 @@ impl WindowState {
             pointers: Default::default(),
             queue_handle: queue_handle.clone(),
             resizable: true,
+            synchronized_resize: false,
+            pending_resize_frame: None,
             scale_factor: 1.,
             shm: winit_state.shm.wl_shm().clone(),
             custom_cursor_pool: winit_state.custom_cursor_pool.clone(),
@@ -232,6 +281,28 @@ impl WindowState {
         })
     }
 
+    /// Apply closure on the pointers without a cursor of their own seat.
+    fn apply_on_unassigned_pointer<F: Fn(&ThemedPointer<WinitPointerData>)>(&self, callback: F) {
+        self.apply_on_pointer(|pointer, data| {
+            if !self.seat_cursors.contains_key(&make_seat_id(data.seat())) {
+                callback(pointer);
+            }
+        })
+    }
+
+    /// The serial of the latest button press on the window, with the seat it happened on.
+    pub fn latest_button_serial(&self) -> Option<(WlSeat, u32)> {
+        self.pointers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|pointer| {
+                let data = pointer.pointer().winit_data();
+                (data.seat().clone(), data.latest_button_serial())
+            })
+            .filter(|&(_, serial)| serial != 0)
+            .max_by_key(|&(_, serial)| serial)
+    }
+
     /// Get the current state of the frame callback.
     pub fn frame_callback_state(&self) -> FrameCallbackState {
         self.frame_callback_state
@@ -528,12 +599,86 @@ impl WindowState {
         !self.seat_focus.is_empty()
     }
 
+    pub fn visibility(&self) -> WindowVisibility {
+        // Compositors suspend windows that aren't visible, e.g. because they are fully obscured
+        // or on another workspace.
+        let suspended = self
+            .last_configure
+            .as_ref()
+            .is_some_and(|configure| configure.state.contains(XdgWindowState::SUSPENDED));
+        WindowVisibility::new(true, false, suspended, self.has_focus())
+    }
+
+    pub fn tiling_state(&self) -> TilingState {
+        let Some(configure) = self.last_configure.as_ref() else {
+            return TilingState::Floating;
+        };
+        if configure.is_fullscreen() {
+            return TilingState::Fullscreen;
+        }
+        if configure.is_maximized() {
+            return TilingState::Maximized;
+        }
+
+        let mut edges = TiledEdges::empty();
+        edges.set(TiledEdges::LEFT, configure.state.contains(XdgWindowState::TILED_LEFT));
+        edges.set(TiledEdges::RIGHT, configure.state.contains(XdgWindowState::TILED_RIGHT));
+        edges.set(TiledEdges::TOP, configure.state.contains(XdgWindowState::TILED_TOP));
+        edges.set(TiledEdges::BOTTOM, configure.state.contains(XdgWindowState::TILED_BOTTOM));
+        if edges.is_empty() {
+            TilingState::Floating
+        } else {
+            TilingState::Tiled(edges)
+        }
+    }
+
+    /// Returns the tiling state if it changed since the last call.
+    pub fn update_tiling_state(&mut self) -> Option<TilingState> {
+        let tiling_state = self.tiling_state();
+        (mem::replace(&mut self.last_tiling_state, tiling_state) != tiling_state)
+            .then_some(tiling_state)
+    }
+
+    /// Whether the user is interactively resizing the window.
+    pub fn is_resizing(&self) -> bool {
+        self.last_configure
+            .as_ref()
+            .is_some_and(|configure| configure.state.contains(XdgWindowState::RESIZING))
+    }
+
+    /// Returns the visibility if it changed since the last call.
+    pub fn update_visibility(&mut self) -> Option<WindowVisibility> {
+        let visibility = self.visibility();
+        (mem::replace(&mut self.last_visibility, visibility) != visibility).then_some(visibility)
+    }
+
     /// Whether the IME is allowed.
     #[inline]
     pub fn ime_allowed(&self) -> bool {
         self.ime_allowed
     }
 
+    /// Enable text input, restoring the IME state that is reset by enabling it.
+    pub fn enable_text_input(&self, text_input: &ZwpTextInputV3) {
+        text_input.enable();
+        text_input.set_content_type_by_purpose(self.ime_purpose);
+        if let Some((position, size)) = self.ime_cursor_area {
+            text_input.set_cursor_rectangle(
+                position.x as i32,
+                position.y as i32,
+                size.width as i32,
+                size.height as i32,
+            );
+        }
+        if let Some(text) = &self.ime_surrounding_text {
+            text_input.set_surrounding_text(
+                text.text().to_owned(),
+                text.cursor() as i32,
+                text.anchor() as i32,
+            );
+        }
+    }
+
     /// Get the size of the window.
     #[inline]
     pub fn inner_size(&self) -> LogicalSize<u32> {
@@ -609,11 +754,17 @@ impl WindowState {
         if self.cursor_visible {
             match &self.selected_cursor {
                 SelectedCursor::Named(icon) => self.set_cursor(*icon),
-                SelectedCursor::Custom(cursor) => self.apply_custom_cursor(cursor),
+                SelectedCursor::Custom(cursors) => self.apply_custom_cursor(cursors),
             }
         } else {
             self.set_cursor_visible(self.cursor_visible);
         }
+
+        self.apply_on_pointer(|pointer, data| {
+            if let Some(cursor) = self.seat_cursors.get(&make_seat_id(data.seat())) {
+                self.apply_cursor_on_pointer(pointer, cursor);
+            }
+        });
     }
 
     /// Reissue the transparency hint to the compositor.
@@ -695,64 +846,115 @@ impl WindowState {
             return;
         }
 
-        self.apply_on_pointer(|pointer, _| {
-            if pointer.set_cursor(&self.connection, cursor_icon).is_err() {
-                warn!("Failed to set cursor to {:?}", cursor_icon);
+        self.apply_on_unassigned_pointer(|pointer| self.apply_cursor_icon(pointer, cursor_icon))
+    }
+
+    /// Set the cursor of the pointers of a single seat, regardless of the cursor visibility.
+    pub(crate) fn set_cursor_for_seat(&mut self, seat_id: SeatId, cursor: cursor::Cursor) {
+        let cursor = match cursor {
+            cursor::Cursor::Icon(icon) => SelectedCursor::Named(icon),
+            cursor::Cursor::Custom(cursor) => match self.load_custom_cursor(cursor) {
+                Some(cursors) => SelectedCursor::Custom(cursors),
+                None => return,
+            },
+        };
+
+        self.apply_on_pointer(|pointer, data| {
+            if make_seat_id(data.seat()) == seat_id {
+                self.apply_cursor_on_pointer(pointer, &cursor);
             }
-        })
+        });
+
+        self.seat_cursors.insert(seat_id, cursor);
+    }
+
+    fn apply_cursor_on_pointer(
+        &self,
+        pointer: &ThemedPointer<WinitPointerData>,
+        cursor: &SelectedCursor,
+    ) {
+        match cursor {
+            SelectedCursor::Named(icon) => self.apply_cursor_icon(pointer, *icon),
+            SelectedCursor::Custom(cursors) => {
+                self.apply_custom_cursor_on_pointer(pointer, cursors)
+            },
+        }
+    }
+
+    fn apply_cursor_icon(
+        &self,
+        pointer: &ThemedPointer<WinitPointerData>,
+        cursor_icon: CursorIcon,
+    ) {
+        if pointer.set_cursor(&self.connection, cursor_icon).is_err() {
+            warn!("Failed to set cursor to {:?}", cursor_icon);
+        }
     }
 
     /// Set the custom cursor icon.
     pub(crate) fn set_custom_cursor(&mut self, cursor: RootCustomCursor) {
+        let Some(cursors) = self.load_custom_cursor(cursor) else {
+            return;
+        };
+
+        if self.cursor_visible {
+            self.apply_custom_cursor(&cursors);
+        }
+
+        self.selected_cursor = SelectedCursor::Custom(cursors);
+    }
+
+    fn load_custom_cursor(&self, cursor: RootCustomCursor) -> Option<Vec<CustomCursor>> {
         let cursor = match cursor {
             RootCustomCursor { inner: PlatformCustomCursor::Wayland(cursor) } => cursor.0,
             #[cfg(x11_platform)]
             RootCustomCursor { inner: PlatformCustomCursor::X(_) } => {
                 tracing::error!("passed a X11 cursor to Wayland backend");
-                return;
+                return None;
             },
         };
 
-        let cursor = {
-            let mut pool = self.custom_cursor_pool.lock().unwrap();
-            CustomCursor::new(&mut pool, &cursor)
-        };
-
-        if self.cursor_visible {
-            self.apply_custom_cursor(&cursor);
-        }
+        let mut pool = self.custom_cursor_pool.lock().unwrap();
+        Some(cursor.iter().map(|image| CustomCursor::new(&mut pool, image)).collect())
+    }
 
-        self.selected_cursor = SelectedCursor::Custom(cursor);
+    fn apply_custom_cursor(&self, cursors: &[CustomCursor]) {
+        self.apply_on_unassigned_pointer(|pointer| {
+            self.apply_custom_cursor_on_pointer(pointer, cursors)
+        });
     }
 
-    fn apply_custom_cursor(&self, cursor: &CustomCursor) {
-        self.apply_on_pointer(|pointer, _| {
-            let surface = pointer.surface();
+    fn apply_custom_cursor_on_pointer(
+        &self,
+        pointer: &ThemedPointer<WinitPointerData>,
+        cursors: &[CustomCursor],
+    ) {
+        let surface = pointer.surface();
 
-            let scale = surface.data::<SurfaceData>().unwrap().surface_data().scale_factor();
+        let scale = surface.data::<SurfaceData>().unwrap().surface_data().scale_factor();
+        let cursor = cursor::for_scale_factor(cursors, scale as f64, |cursor| cursor.scale_factor);
 
-            surface.set_buffer_scale(scale);
-            surface.attach(Some(cursor.buffer.wl_buffer()), 0, 0);
-            if surface.version() >= 4 {
-                surface.damage_buffer(0, 0, cursor.w, cursor.h);
-            } else {
-                surface.damage(0, 0, cursor.w / scale, cursor.h / scale);
-            }
-            surface.commit();
-
-            let serial = pointer
-                .pointer()
-                .data::<WinitPointerData>()
-                .and_then(|data| data.pointer_data().latest_enter_serial())
-                .unwrap();
-
-            pointer.pointer().set_cursor(
-                serial,
-                Some(surface),
-                cursor.hotspot_x / scale,
-                cursor.hotspot_y / scale,
-            );
-        });
+        surface.set_buffer_scale(scale);
+        surface.attach(Some(cursor.buffer.wl_buffer()), 0, 0);
+        if surface.version() >= 4 {
+            surface.damage_buffer(0, 0, cursor.w, cursor.h);
+        } else {
+            surface.damage(0, 0, cursor.w / scale, cursor.h / scale);
+        }
+        surface.commit();
+
+        let serial = pointer
+            .pointer()
+            .data::<WinitPointerData>()
+            .and_then(|data| data.pointer_data().latest_enter_serial())
+            .unwrap();
+
+        pointer.pointer().set_cursor(
+            serial,
+            Some(surface),
+            cursor.hotspot_x / scale,
+            cursor.hotspot_y / scale,
+        );
     }
 
     /// Set maximum inner window size.
@@ -819,6 +1021,13 @@ impl WindowState {
         self.set_max_inner_size(self.max_inner_size);
     }
 
+    /// Set the regions of the surface the cursor grab is limited to.
+    pub fn set_cursor_grab_regions(&mut self, regions: Option<Vec<crate::window::Region>>) {
+        self.cursor_grab_mode.regions = regions;
+        let mode = self.cursor_grab_mode.current_grab_mode;
+        let _ = self.set_cursor_grab_inner(mode);
+    }
+
     /// Set the grabbing state on the surface.
     fn set_cursor_grab_inner(&mut self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         let pointer_constraints = match self.pointer_constraints.as_ref() {
@@ -827,37 +1036,68 @@ impl WindowState {
             None => return Err(ExternalError::NotSupported(NotSupportedError::new())),
         };
 
-        // Replace the current mode.
-        let old_mode = std::mem::replace(&mut self.cursor_grab_mode.current_grab_mode, mode);
+        self.cursor_grab_mode.current_grab_mode = mode;
 
-        match old_mode {
-            CursorGrabMode::None => (),
-            CursorGrabMode::Confined => self.apply_on_pointer(|_, data| {
-                data.unconfine_pointer();
-            }),
-            CursorGrabMode::Locked => {
-                self.apply_on_pointer(|_, data| data.unlock_pointer());
-            },
+        // Release the pointers that left the window, they are constrained again once they
+        // enter it. The others keep their constraint if the mode didn't change, so the lock
+        // isn't lost in the meantime.
+        let surface = self.window.wl_surface();
+        let current_pointers: Vec<_> = self.pointers.iter().filter_map(Weak::upgrade).collect();
+        for pointer in self.cursor_grab_mode.pointers.drain(..).filter_map(|p| p.upgrade()) {
+            let entered = current_pointers.iter().any(|current| Arc::ptr_eq(current, &pointer));
+            if !entered || mode == CursorGrabMode::None {
+                pointer.pointer().winit_data().release_pointer(surface);
+            }
         }
 
-        let surface = self.window.wl_surface();
-        match mode {
-            CursorGrabMode::Locked => self.apply_on_pointer(|pointer, data| {
-                let pointer = pointer.pointer();
-                data.lock_pointer(pointer_constraints, surface, pointer, &self.queue_handle)
-            }),
-            CursorGrabMode::Confined => self.apply_on_pointer(|pointer, data| {
-                let pointer = pointer.pointer();
-                data.confine_pointer(pointer_constraints, surface, pointer, &self.queue_handle)
-            }),
-            CursorGrabMode::None => {
-                // Current lock/confine was already removed.
-            },
+        let region = match &self.cursor_grab_mode.regions {
+            Some(regions) => Some(self.surface_region(regions)?),
+            None => None,
+        };
+        let region = region.as_ref().map(Region::wl_region);
+        for pointer in &current_pointers {
+            let data = pointer.pointer().winit_data();
+            let wl_pointer = pointer.pointer();
+            match mode {
+                CursorGrabMode::Locked => data.lock_pointer(
+                    pointer_constraints,
+                    surface,
+                    wl_pointer,
+                    region,
+                    &self.queue_handle,
+                ),
+                CursorGrabMode::Confined => data.confine_pointer(
+                    pointer_constraints,
+                    surface,
+                    wl_pointer,
+                    region,
+                    &self.queue_handle,
+                ),
+                CursorGrabMode::None => continue,
+            }
+            self.cursor_grab_mode.pointers.push(Arc::downgrade(pointer));
         }
 
         Ok(())
     }
 
+    /// Create a region of the surface from regions relative to its top-left corner.
+    fn surface_region(&self, regions: &[crate::window::Region]) -> Result<Region, ExternalError> {
+        let region = Region::new(&*self.compositor).map_err(|_| {
+            ExternalError::Os(os_error!(crate::platform_impl::OsError::Misc(
+                "failed to create region."
+            )))
+        })?;
+
+        // Regions are in surface local coordinates.
+        for part in regions {
+            let position: LogicalPosition<i32> = part.position.to_logical(self.scale_factor);
+            let size: LogicalSize<i32> = part.size.to_logical(self.scale_factor);
+            region.add(position.x, position.y, size.width, size.height);
+        }
+        Ok(region)
+    }
+
     pub fn show_window_menu(&self, position: LogicalPosition<u32>) {
         // TODO(kchibisov) handle touch serials.
         self.apply_on_pointer(|_, data| {
@@ -880,13 +1120,22 @@ impl WindowState {
             ))));
         }
 
+        let surface = self.window.wl_surface();
         self.apply_on_pointer(|_, data| {
-            data.set_locked_cursor_position(position.x, position.y);
+            data.set_locked_cursor_position(surface, position.x, position.y);
         });
 
         Ok(())
     }
 
+    /// Position of the pointer over the window, if any.
+    pub fn cursor_position(&self) -> Option<LogicalPosition<f64>> {
+        self.pointers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .find_map(|pointer| pointer.pointer().winit_data().position())
+    }
+
     /// Set the visibility state of the cursor.
     pub fn set_cursor_visible(&mut self, cursor_visible: bool) {
         self.cursor_visible = cursor_visible;
@@ -894,14 +1143,14 @@ impl WindowState {
         if self.cursor_visible {
             match &self.selected_cursor {
                 SelectedCursor::Named(icon) => self.set_cursor(*icon),
-                SelectedCursor::Custom(cursor) => self.apply_custom_cursor(cursor),
+                SelectedCursor::Custom(cursors) => self.apply_custom_cursor(cursors),
             }
         } else {
-            for pointer in self.pointers.iter().filter_map(|pointer| pointer.upgrade()) {
+            self.apply_on_unassigned_pointer(|pointer| {
                 let latest_enter_serial = pointer.pointer().winit_data().latest_enter_serial();
 
                 pointer.pointer().set_cursor(latest_enter_serial, None, 0, 0);
-            }
+            });
         }
     }
 
@@ -950,8 +1199,7 @@ impl WindowState {
         for text_input in &self.text_inputs {
             applied = true;
             if allowed {
-                text_input.enable();
-                text_input.set_content_type_by_purpose(self.ime_purpose);
+                self.enable_text_input(text_input);
             } else {
                 text_input.disable();
             }
@@ -962,7 +1210,12 @@ impl WindowState {
     }
 
     /// Set the IME position.
-    pub fn set_ime_cursor_area(&self, position: LogicalPosition<u32>, size: LogicalSize<u32>) {
+    pub fn set_ime_cursor_area(&mut self, position: LogicalPosition<u32>, size: LogicalSize<u32>) {
+        self.ime_cursor_area = Some((position, size));
+        if !self.ime_allowed {
+            return;
+        }
+
         // FIXME: This won't fly unless user will have a way to request IME window per seat, since
         // the ime windows will be overlapping, but winit doesn't expose API to specify for
         // which seat we're setting IME position.
@@ -974,6 +1227,25 @@ impl WindowState {
         }
     }
 
+    /// Set the IME surrounding text.
+    pub fn set_ime_surrounding_text(&mut self, text: SurroundingText) {
+        // The protocol limits the size of requests.
+        const MAX_LEN: usize = 4000;
+
+        let text = text.truncate(MAX_LEN);
+        if self.ime_allowed {
+            for text_input in &self.text_inputs {
+                text_input.set_surrounding_text(
+                    text.text().to_owned(),
+                    text.cursor() as i32,
+                    text.anchor() as i32,
+                );
+                text_input.commit();
+            }
+        }
+        self.ime_surrounding_text = Some(text);
+    }
+
     /// Set the IME purpose.
     pub fn set_ime_purpose(&mut self, purpose: ImePurpose) {
         self.ime_purpose = purpose;
@@ -984,11 +1256,6 @@ impl WindowState {
         }
     }
 
-    /// Get the IME purpose.
-    pub fn ime_purpose(&self) -> ImePurpose {
-        self.ime_purpose
-    }
-
     /// Set the scale factor for the given window.
     #[inline]
     pub fn set_scale_factor(&mut self, scale_factor: f64) {
@@ -1004,6 +1271,71 @@ impl WindowState {
         }
     }
 
+    /// Set the icon of the toplevel, falling back to the themed icon name.
+    pub(crate) fn set_window_icon(&mut self, icon: Option<&PlatformIcon>) {
+        let Some(icon_manager) = self.icon_manager.as_ref() else {
+            info!("Toplevel icon manager unavailable, unable to set the window icon");
+            return;
+        };
+
+        let buffer = icon.and_then(|icon| {
+            xdg_toplevel_icon::icon_buffer(&mut self.custom_cursor_pool.lock().unwrap(), icon)
+        });
+        if buffer.is_none() && self.icon_name.is_none() {
+            icon_manager.set_icon(self.window.xdg_toplevel(), None);
+        } else {
+            let xdg_icon = icon_manager.create_icon(&self.queue_handle);
+            if let Some(name) = &self.icon_name {
+                xdg_icon.set_name(name.clone());
+            }
+            if let Some(buffer) = &buffer {
+                xdg_icon.add_buffer(buffer.wl_buffer(), 1);
+            }
+            icon_manager.set_icon(self.window.xdg_toplevel(), Some(&xdg_icon));
+            // The toplevel keeps the icon.
+            xdg_icon.destroy();
+        }
+        self.icon_buffer = buffer;
+    }
+
+    /// Attach the RGBA pixels to the surface, in a `wl_shm` buffer.
+    pub fn present_rgba(
+        &mut self,
+        rgba: &[u8],
+        size: PhysicalSize<u32>,
+    ) -> Result<(), ExternalError> {
+        if size.width == 0 || size.height == 0 {
+            return Ok(());
+        }
+
+        let (width, height) = (size.width as i32, size.height as i32);
+        let mut pool = self.custom_cursor_pool.lock().unwrap();
+        let (buffer, canvas) =
+            pool.create_buffer(width, height, 4 * width, Format::Xrgb8888).map_err(|err| {
+                warn!("failed to allocate the buffer to present: {err}");
+                ExternalError::Os(os_error!(crate::platform_impl::OsError::Misc(
+                    "failed to allocate the buffer to present."
+                )))
+            })?;
+        for (pixel, rgba) in canvas.chunks_exact_mut(4).zip(rgba.chunks_exact(4)) {
+            pixel.copy_from_slice(&[rgba[2], rgba[1], rgba[0], 0xff]);
+        }
+        drop(pool);
+
+        let surface = self.window.wl_surface();
+        buffer.attach_to(surface).map_err(|_| {
+            ExternalError::Os(os_error!(crate::platform_impl::OsError::Misc(
+                "failed to attach the buffer to present."
+            )))
+        })?;
+        surface.damage_buffer(0, 0, width, height);
+        surface.commit();
+
+        // The previous buffer is released by the compositor once the new one is attached.
+        self.presented_buffer = Some(buffer);
+        Ok(())
+    }
+
     /// Make window background blurred
     #[inline]
     pub fn set_blur(&mut self, blurred: bool) {
@@ -1094,18 +1426,28 @@ impl Drop for WindowState {
 }
 
 /// The state of the cursor grabs.
-#[derive(Clone, Copy)]
 struct GrabState {
     /// The grab mode requested by the user.
     user_grab_mode: CursorGrabMode,
 
     /// The current grab mode.
     current_grab_mode: CursorGrabMode,
+
+    /// The regions the pointer is locked or confined to, instead of the whole surface.
+    regions: Option<Vec<crate::window::Region>>,
+
+    /// The pointers constrained to the surface, which keep the constraint after leaving it.
+    pointers: Vec<Weak<ThemedPointer<WinitPointerData>>>,
 }
 
 impl GrabState {
     fn new() -> Self {
-        Self { user_grab_mode: CursorGrabMode::None, current_grab_mode: CursorGrabMode::None }
+        Self {
+            user_grab_mode: CursorGrabMode::None,
+            current_grab_mode: CursorGrabMode::None,
+            regions: None,
+            pointers: Vec::new(),
+        }
     }
 }
 
@@ -43,6 +43,7 @@ atom_manager! {
     XIM_SERVERS,
 
     // Assorted ICCCM Atoms
+    _NET_WM_BYPASS_COMPOSITOR,
     _NET_WM_ICON,
     _NET_WM_MOVERESIZE,
     _NET_WM_NAME,
@@ -85,21 +86,35 @@ atom_manager! {
     XdndDrop,
     XdndPosition,
     XdndStatus,
-    XdndActionPrivate,
+    XdndActionCopy,
+    XdndActionMove,
+    XdndActionLink,
     XdndSelection,
     XdndFinished,
     XdndTypeList,
     TextUriList: b"text/uri-list",
     None: b"None",
 
+    // XInput2 Valuator Labels
+    AbsMtPressure: b"Abs MT Pressure",
+
+    // XInput2 device properties
+    LibinputTappingEnabled: b"libinput Tapping Enabled",
+
+    // RandR output properties
+    Edid: b"EDID",
+    VrrCapable: b"vrr_capable",
+
     // Miscellaneous Atoms
     _GTK_THEME_VARIANT,
     _MOTIF_WM_HINTS,
     _NET_ACTIVE_WINDOW,
     _NET_CLIENT_LIST,
+    _NET_CURRENT_DESKTOP,
     _NET_FRAME_EXTENTS,
     _NET_SUPPORTED,
     _NET_SUPPORTING_WM_CHECK,
+    _NET_WORKAREA,
     _XEMBED,
     _XSETTINGS_SETTINGS
 }
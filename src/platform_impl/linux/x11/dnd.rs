@@ -10,6 +10,8 @@ use x11rb::protocol::xproto::{self, ConnectionExt};
 use super::atoms::AtomName::None as DndNone;
 use super::atoms::*;
 use super::{util, CookieResultExt, X11Error, XConnection};
+use crate::event::{DataTransfer, DropOperation};
+use crate::window::WindowId;
 
 #[derive(Debug, Clone, Copy)]
 pub enum DndState {
@@ -45,20 +47,86 @@ pub struct Dnd {
     pub type_list: Option<Vec<xproto::Atom>>,
     // Populated by XdndPosition event handler
     pub source_window: Option<xproto::Window>,
+    // Populated by XdndPosition event handler, what the window would do with a drop
+    pub operation: DropOperation,
     // Populated by SelectionNotify event handler (triggered by XdndPosition event handler)
     pub result: Option<Result<Vec<PathBuf>, DndDataParseError>>,
+    // Populated by XdndEnter event handler, the negotiated MIME types and their targets
+    pub accepted: Vec<(String, xproto::Atom)>,
+    // Populated by XdndDrop event handler, the types left to convert and the data received
+    pub transfer: Option<Transfer>,
+}
+
+/// The data of a drop being received, one accepted type at a time.
+pub struct Transfer {
+    pub source_window: xproto::Window,
+    pub time: xproto::Timestamp,
+    pub pending: Vec<(String, xproto::Atom)>,
+    pub data: DataTransfer,
 }
 
 impl Dnd {
     pub fn new(xconn: Arc<XConnection>) -> Result<Self, X11Error> {
-        Ok(Dnd { xconn, version: None, type_list: None, source_window: None, result: None })
+        Ok(Dnd {
+            xconn,
+            version: None,
+            type_list: None,
+            source_window: None,
+            operation: DropOperation::Copy,
+            result: None,
+            accepted: Vec::new(),
+            transfer: None,
+        })
     }
 
     pub fn reset(&mut self) {
         self.version = None;
         self.type_list = None;
         self.source_window = None;
+        self.operation = DropOperation::Copy;
         self.result = None;
+        self.accepted.clear();
+        self.transfer = None;
+    }
+
+    /// Negotiate the types of the drag with the window, `UTF8_STRING` being offered as
+    /// [`DataTransfer::TEXT`].
+    pub fn negotiate(&mut self, window_id: WindowId) {
+        let atoms = self.xconn.atoms();
+        let type_list = self.type_list.as_deref().unwrap_or_default();
+        let cookies: Vec<_> = type_list
+            .iter()
+            .map(|&atom| (atom, self.xconn.xcb_connection().get_atom_name(atom)))
+            .collect();
+        let mut offered: Vec<(String, xproto::Atom)> = Vec::new();
+        for (atom, cookie) in cookies {
+            let Ok(Ok(reply)) = cookie.map(|cookie| cookie.reply()) else { continue };
+            let name = match String::from_utf8(reply.name) {
+                Ok(name) if name.contains('/') => name,
+                _ if atom == atoms[UTF8_STRING] => DataTransfer::TEXT.to_owned(),
+                _ => continue,
+            };
+            if !offered.iter().any(|(offered, _)| *offered == name) {
+                offered.push((name, atom));
+            }
+        }
+
+        let names: Vec<String> = offered.iter().map(|(name, _)| name.clone()).collect();
+        self.accepted = crate::drop_negotiation::negotiate(window_id, &names)
+            .into_iter()
+            .filter_map(|name| offered.iter().find(|(offered, _)| *offered == name).cloned())
+            .collect();
+    }
+
+    /// Whether the drop is accepted, and the action matching the operation of the window.
+    fn status(&self, state: DndState) -> (u32, xproto::Atom) {
+        let atoms = self.xconn.atoms();
+        match (state, self.operation) {
+            (DndState::Rejected, _) | (_, DropOperation::Reject) => (0, atoms[DndNone]),
+            (DndState::Accepted, DropOperation::Copy) => (1, atoms[XdndActionCopy]),
+            (DndState::Accepted, DropOperation::Move) => (1, atoms[XdndActionMove]),
+            (DndState::Accepted, DropOperation::Link) => (1, atoms[XdndActionLink]),
+        }
     }
 
     pub unsafe fn send_status(
@@ -68,10 +136,7 @@ impl Dnd {
         state: DndState,
     ) -> Result<(), X11Error> {
         let atoms = self.xconn.atoms();
-        let (accepted, action) = match state {
-            DndState::Accepted => (1, atoms[XdndActionPrivate]),
-            DndState::Rejected => (0, atoms[DndNone]),
-        };
+        let (accepted, action) = self.status(state);
         self.xconn
             .send_client_msg(target_window, target_window, atoms[XdndStatus] as _, None, [
                 this_window,
@@ -92,10 +157,7 @@ impl Dnd {
         state: DndState,
     ) -> Result<(), X11Error> {
         let atoms = self.xconn.atoms();
-        let (accepted, action) = match state {
-            DndState::Accepted => (1, atoms[XdndActionPrivate]),
-            DndState::Rejected => (0, atoms[DndNone]),
-        };
+        let (accepted, action) = self.status(state);
         self.xconn
             .send_client_msg(target_window, target_window, atoms[XdndFinished] as _, None, [
                 this_window,
@@ -121,26 +183,32 @@ impl Dnd {
         )
     }
 
-    pub unsafe fn convert_selection(&self, window: xproto::Window, time: xproto::Timestamp) {
+    pub unsafe fn convert_selection(
+        &self,
+        window: xproto::Window,
+        target: xproto::Atom,
+        time: xproto::Timestamp,
+    ) {
         let atoms = self.xconn.atoms();
         self.xconn
             .xcb_connection()
-            .convert_selection(
-                window,
-                atoms[XdndSelection],
-                atoms[TextUriList],
-                atoms[XdndSelection],
-                time,
-            )
+            .convert_selection(window, atoms[XdndSelection], target, atoms[XdndSelection], time)
             .expect_then_ignore_error("Failed to send XdndSelection event")
     }
 
     pub unsafe fn read_data(
         &self,
         window: xproto::Window,
+        target: xproto::Atom,
     ) -> Result<Vec<c_uchar>, util::GetPropertyError> {
         let atoms = self.xconn.atoms();
-        self.xconn.get_property(window, atoms[XdndSelection], atoms[TextUriList])
+        match self.xconn.get_property(window, atoms[XdndSelection], target) {
+            // Sources don't always use the target as the type of the data.
+            Err(util::GetPropertyError::TypeMismatch(actual_type)) if actual_type != 0 => {
+                self.xconn.get_property(window, atoms[XdndSelection], actual_type)
+            },
+            result => result,
+        }
     }
 
     pub fn parse_data(&self, data: &mut [c_uchar]) -> Result<Vec<PathBuf>, DndDataParseError> {
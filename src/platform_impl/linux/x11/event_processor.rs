@@ -1,8 +1,8 @@
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
 use std::os::raw::{c_char, c_int, c_long, c_ulong};
-use std::slice;
 use std::sync::{Arc, Mutex};
+use std::{mem, slice};
 
 use x11_dl::xinput2::{
     self, XIDeviceEvent, XIEnterEvent, XIFocusInEvent, XIFocusOutEvent, XIHierarchyEvent,
@@ -13,7 +13,7 @@ use x11_dl::xlib::{
     XDestroyWindowEvent, XEvent, XExposeEvent, XKeyEvent, XMapEvent, XPropertyEvent,
     XReparentEvent, XSelectionEvent, XVisibilityEvent, XkbAnyEvent, XkbStateRec,
 };
-use x11rb::protocol::xinput;
+use x11rb::protocol::xinput::{self, ConnectionExt as _};
 use x11rb::protocol::xkb::ID as XkbId;
 use x11rb::protocol::xproto::{self, ConnectionExt as _, ModMask};
 use x11rb::x11_utils::{ExtensionInformation, Serialize};
@@ -21,8 +21,9 @@ use xkbcommon_dl::xkb_mod_mask_t;
 
 use crate::dpi::{PhysicalPosition, PhysicalSize};
 use crate::event::{
-    DeviceEvent, ElementState, Event, Ime, InnerSizeWriter, MouseButton, MouseScrollDelta,
-    RawKeyEvent, Touch, TouchPhase, WindowEvent,
+    DataTransfer, DeviceEvent, DeviceId as RootDeviceId, DropOperation, DropResponse, ElementState,
+    Event, Force, Ime, InnerSizeWriter, MouseButton, MouseScrollDelta, RawKeyEvent, Touch,
+    TouchPhase, WindowEvent,
 };
 use crate::event_loop::ActiveEventLoop as RootAEL;
 use crate::keyboard::ModifiersState;
@@ -30,12 +31,14 @@ use crate::platform_impl::common::xkb::{self, XkbState};
 use crate::platform_impl::platform::common::xkb::Context;
 use crate::platform_impl::platform::x11::ime::{ImeEvent, ImeEventReceiver, ImeRequest};
 use crate::platform_impl::platform::x11::ActiveEventLoop;
-use crate::platform_impl::platform::ActiveEventLoop as PlatformActiveEventLoop;
+use crate::platform_impl::platform::{
+    ActiveEventLoop as PlatformActiveEventLoop, DeviceId as PlatformDeviceId,
+};
 use crate::platform_impl::x11::atoms::*;
 use crate::platform_impl::x11::util::cookie::GenericEventCookie;
 use crate::platform_impl::x11::{
-    mkdid, mkwid, util, CookieResultExt, Device, DeviceId, DeviceInfo, Dnd, DndState, ImeReceiver,
-    ScrollOrientation, UnownedWindow, WindowId,
+    mkwid, util, CookieResultExt, Device, DeviceId, DeviceInfo, Dnd, DndState, ImeReceiver,
+    ScrollOrientation, Transfer, UnownedWindow, WindowId,
 };
 
 /// The maximum amount of X modifiers to replay.
@@ -49,7 +52,9 @@ pub struct EventProcessor {
     pub ime_receiver: ImeReceiver,
     pub ime_event_receiver: ImeEventReceiver,
     pub randr_event_offset: u8,
-    pub devices: RefCell<HashMap<DeviceId, Device>>,
+    pub devices: RefCell<HashMap<xinput::DeviceId, Device>>,
+    /// The master pointer of every device, which identifies its seat.
+    pub seats: RefCell<HashMap<xinput::DeviceId, xinput::DeviceId>>,
     pub xi2ext: ExtensionInformation,
     pub xkbext: ExtensionInformation,
     pub target: RootAEL,
@@ -69,6 +74,8 @@ pub struct EventProcessor {
     pub xfiltered_modifiers: VecDeque<c_ulong>,
     pub xmodmap: util::ModifierKeymap,
     pub is_composing: bool,
+    /// The window the pointer is over while it's grabbed by another window, e.g. during a drag.
+    pub drag_target: Cell<Option<xproto::Window>>,
 }
 
 impl EventProcessor {
@@ -104,15 +111,15 @@ impl EventProcessor {
                 ImeEvent::Enabled => WindowEvent::Ime(Ime::Enabled),
                 ImeEvent::Start => {
                     self.is_composing = true;
-                    WindowEvent::Ime(Ime::Preedit("".to_owned(), None))
+                    WindowEvent::Ime(Ime::Preedit("".to_owned(), None, Vec::new()))
                 },
-                ImeEvent::Update(text, position) if self.is_composing => {
-                    WindowEvent::Ime(Ime::Preedit(text, Some((position, position))))
+                ImeEvent::Update(text, position, spans) if self.is_composing => {
+                    WindowEvent::Ime(Ime::Preedit(text, Some((position, position)), spans))
                 },
                 ImeEvent::End => {
                     self.is_composing = false;
                     // Issue empty preedit on `Done`.
-                    WindowEvent::Ime(Ime::Preedit(String::new(), None))
+                    WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new()))
                 },
                 ImeEvent::Disabled => {
                     self.is_composing = false;
@@ -335,13 +342,55 @@ impl EventProcessor {
     pub fn init_device(&self, device: xinput::DeviceId) {
         let window_target = Self::window_target(&self.target);
         let mut devices = self.devices.borrow_mut();
+        let atoms = window_target.xconn.atoms();
         if let Some(info) = DeviceInfo::get(&window_target.xconn, device as _) {
             for info in info.iter() {
-                devices.insert(DeviceId(info.deviceid as _), Device::new(info));
+                // The libinput driver only offers tapping for touchpads, which have no touch class.
+                let touchpad = info._use == xinput2::XISlavePointer
+                    && window_target
+                        .xconn
+                        .xcb_connection()
+                        .xinput_xi_list_properties(info.deviceid as xinput::DeviceId)
+                        .ok()
+                        .and_then(|cookie| cookie.reply().ok())
+                        .is_some_and(|reply| {
+                            reply.properties.contains(&atoms[LibinputTappingEnabled])
+                        });
+                devices
+                    .insert(info.deviceid as _, Device::new(info, atoms[AbsMtPressure], touchpad));
             }
         }
     }
 
+    /// Find the seats of all devices, which change when devices are added, removed, or attached
+    /// to another master.
+    pub fn update_seats(&self) {
+        let window_target = Self::window_target(&self.target);
+        let Some(info) = DeviceInfo::get(&window_target.xconn, super::ALL_DEVICES.into()) else {
+            return;
+        };
+        let attachments: HashMap<_, _> =
+            info.iter().map(|info| (info.deviceid, (info._use, info.attachment))).collect();
+
+        // A master pointer is paired with a master keyboard, and slaves are attached to either.
+        let seat = |device| match *attachments.get(&device)? {
+            (xinput2::XIMasterPointer, _) => Some(device),
+            (xinput2::XIMasterKeyboard | xinput2::XISlavePointer, master) => Some(master),
+            (xinput2::XISlaveKeyboard, master) => Some(attachments.get(&master)?.1),
+            _ => None,
+        };
+        *self.seats.borrow_mut() = attachments
+            .keys()
+            .filter_map(|&device| Some((device as _, seat(device)? as _)))
+            .collect();
+    }
+
+    /// The device id of a device, with its seat.
+    fn mkdid(&self, device: xinput::DeviceId) -> RootDeviceId {
+        let seat = self.seats.borrow().get(&device).copied();
+        RootDeviceId(PlatformDeviceId::X(DeviceId::new(device, seat)))
+    }
+
     pub fn with_window<F, Ret>(&self, window_id: xproto::Window, callback: F) -> Option<Ret>
     where
         F: Fn(&Arc<UnownedWindow>) -> Ret,
@@ -445,6 +494,7 @@ impl EventProcessor {
             } else if let Ok(more_types) = unsafe { self.dnd.get_type_list(source_window) } {
                 self.dnd.type_list = Some(more_types);
             }
+            self.dnd.negotiate(window_id);
             return;
         }
 
@@ -453,20 +503,17 @@ impl EventProcessor {
             // over our window. We emit HoveredFile in response; while the macOS backend
             // does that upon a drag entering, XDND doesn't have access to the actual drop
             // data until this event. For parity with other platforms, we only emit
-            // `HoveredFile` the first time, though if winit's API is later extended to
-            // supply position updates with `HoveredFile` or another event, implementing
-            // that here would be trivial.
+            // `HoveredFile` the first time, while the position is reported with `DragHover`
+            // every time.
 
             let source_window = xev.data.get_long(0) as xproto::Window;
 
-            // Equivalent to `(x << shift) | y`
-            // where `shift = mem::size_of::<c_short>() * 8`
+            // Equivalent to `(x << 16) | y`
             // Note that coordinates are in "desktop space", not "window space"
             // (in X11 parlance, they're root window coordinates)
-            // let packed_coordinates = xev.data.get_long(2);
-            // let shift = mem::size_of::<libc::c_short>() * 8;
-            // let x = packed_coordinates >> shift;
-            // let y = packed_coordinates & !(x << shift);
+            let packed_coordinates = xev.data.get_long(2);
+            let x = (packed_coordinates >> 16) as i16;
+            let y = packed_coordinates as i16;
 
             // By our own state flow, `version` should never be `None` at this point.
             let version = self.dnd.version.unwrap_or(5);
@@ -474,11 +521,13 @@ impl EventProcessor {
             // Action is specified in versions 2 and up, though we don't need it anyway.
             // let action = xev.data.get_long(4);
 
-            let accepted = if let Some(ref type_list) = self.dnd.type_list {
+            // Files are always accepted, other data if the window accepts any of its types.
+            let has_files = if let Some(ref type_list) = self.dnd.type_list {
                 type_list.contains(&atoms[TextUriList])
             } else {
                 false
             };
+            let accepted = has_files || !self.dnd.accepted.is_empty();
 
             if !accepted {
                 unsafe {
@@ -490,8 +539,22 @@ impl EventProcessor {
                 return;
             }
 
+            // Ask the application what a drop at this position would do.
+            let position = wt
+                .xconn
+                .xcb_connection()
+                .translate_coordinates(wt.root, window, x, y)
+                .ok()
+                .and_then(|cookie| cookie.reply().ok())
+                .map(|reply| PhysicalPosition::new(reply.dst_x as f64, reply.dst_y as f64))
+                .unwrap_or_default();
+            let operation = Arc::new(Mutex::new(DropOperation::Copy));
+            let respond = DropResponse::new(Arc::downgrade(&operation));
+            callback(&self.target, Event::DragHover { window_id, position, respond });
+            self.dnd.operation = *operation.lock().unwrap();
+
             self.dnd.source_window = Some(source_window);
-            if self.dnd.result.is_none() {
+            if has_files && self.dnd.result.is_none() {
                 let time = if version >= 1 {
                     xev.data.get_long(3) as xproto::Timestamp
                 } else {
@@ -504,7 +567,7 @@ impl EventProcessor {
 
                 // This results in the `SelectionNotify` event below
                 unsafe {
-                    self.dnd.convert_selection(window, time);
+                    self.dnd.convert_selection(window, atoms[TextUriList], time);
                 }
             }
 
@@ -517,7 +580,9 @@ impl EventProcessor {
         }
 
         if xev.message_type == atoms[XdndDrop] as c_ulong {
-            let (source_window, state) = if let Some(source_window) = self.dnd.source_window {
+            let hovered =
+                self.dnd.source_window.filter(|_| self.dnd.operation != DropOperation::Reject);
+            let (source_window, state) = if let Some(source_window) = hovered {
                 if let Some(Ok(ref path_list)) = self.dnd.result {
                     for path in path_list {
                         let event = Event::WindowEvent {
@@ -527,10 +592,30 @@ impl EventProcessor {
                         callback(&self.target, event);
                     }
                 }
+
+                // The other accepted types are converted one at a time, finishing the drop once
+                // all of them were received, see `selection_notify`.
+                let mut pending = mem::take(&mut self.dnd.accepted);
+                if !pending.is_empty() {
+                    let version = self.dnd.version.unwrap_or(5);
+                    let time = if version >= 1 {
+                        xev.data.get_long(2) as xproto::Timestamp
+                    } else {
+                        x11rb::CURRENT_TIME
+                    };
+                    pending.reverse();
+                    let target = pending.last().unwrap().1;
+                    self.dnd.transfer =
+                        Some(Transfer { source_window, time, pending, data: DataTransfer::new() });
+                    unsafe {
+                        self.dnd.convert_selection(window, target, time);
+                    }
+                    return;
+                }
                 (source_window, DndState::Accepted)
             } else {
                 // `source_window` won't be part of our DND state if we already rejected the drop in
-                // our `XdndPosition` handler.
+                // our `XdndPosition` handler, and the window may have rejected it at the position.
                 let source_window = xev.data.get_long(0) as xproto::Window;
                 (source_window, DndState::Rejected)
             };
@@ -565,13 +650,52 @@ impl EventProcessor {
         // Set the timestamp.
         wt.xconn.set_timestamp(xev.time as xproto::Timestamp);
 
+        if let Some(mut transfer) = self.dnd.transfer.take() {
+            // This is where we receive the accepted types of a drop, the property being `None`
+            // when the source failed to convert it.
+            let expected = transfer.pending.last().map(|&(_, target)| target as c_ulong);
+            if xev.selection != atoms[XdndSelection] as c_ulong || expected != Some(xev.target) {
+                self.dnd.transfer = Some(transfer);
+                return;
+            }
+            let (mime_type, target) = transfer.pending.pop().unwrap();
+            if xev.property == atoms[XdndSelection] as c_ulong {
+                if let Ok(data) = unsafe { self.dnd.read_data(window, target) } {
+                    transfer.data.insert(mime_type, data);
+                }
+            }
+
+            if let Some(&(_, target)) = transfer.pending.last() {
+                unsafe {
+                    self.dnd.convert_selection(window, target, transfer.time);
+                }
+                self.dnd.transfer = Some(transfer);
+                return;
+            }
+
+            if !transfer.data.is_empty() {
+                let event = Event::WindowEvent {
+                    window_id,
+                    event: WindowEvent::DroppedData(transfer.data),
+                };
+                callback(&self.target, event);
+            }
+            unsafe {
+                self.dnd
+                    .send_finished(window, transfer.source_window, DndState::Accepted)
+                    .expect("Failed to send `XdndFinished` message.");
+            }
+            self.dnd.reset();
+            return;
+        }
+
         if xev.property != atoms[XdndSelection] as c_ulong {
             return;
         }
 
         // This is where we receive data from drag and drop
         self.dnd.result = None;
-        if let Ok(mut data) = unsafe { self.dnd.read_data(window) } {
+        if let Ok(mut data) = unsafe { self.dnd.read_data(window, atoms[TextUriList]) } {
             let parse_result = self.dnd.parse_data(&mut data);
             if let Ok(ref path_list) = parse_result {
                 for path in path_list {
@@ -717,6 +841,8 @@ impl EventProcessor {
                 let new_inner_size = *inner_size.lock().unwrap();
                 drop(inner_size);
 
+                window.reload_custom_cursor();
+
                 if new_inner_size != old_inner_size {
                     window.request_inner_size_physical(new_inner_size.width, new_inner_size.height);
                     window.shared_state_lock().dpi_adjusted = Some(new_inner_size.into());
@@ -729,9 +855,8 @@ impl EventProcessor {
 
         // NOTE: Ensure that the lock is dropped before handling the resized and
         // sending the event back to user.
-        let hittest = {
+        {
             let mut shared_state_lock = window.shared_state_lock();
-            let hittest = shared_state_lock.cursor_hittest;
 
             // This is a hack to ensure that the DPI adjusted resize is actually
             // applied on all WMs. KWin doesn't need this, but Xfwm does. The hack
@@ -749,19 +874,14 @@ impl EventProcessor {
                     window.request_inner_size_physical(adjusted_size.0, adjusted_size.1);
                 }
             }
-
-            hittest
-        };
-
-        // Reload hittest.
-        if hittest.unwrap_or(false) {
-            let _ = window.set_cursor_hittest(true);
         }
 
+        window.reload_cursor_hittest();
+
         if resized {
             callback(&self.target, Event::WindowEvent {
                 window_id,
-                event: WindowEvent::Resized(new_inner_size.into()),
+                event: WindowEvent::Resized { size: new_inner_size.into(), frame: None },
             });
         }
     }
@@ -797,6 +917,7 @@ impl EventProcessor {
         let event = Event::WindowEvent { window_id, event: WindowEvent::Focused(focus) };
 
         callback(&self.target, event);
+        self.update_visibility(window, &mut callback);
     }
 
     fn destroy_notify<T: 'static, F>(&self, xev: &XDestroyWindowEvent, mut callback: F)
@@ -834,7 +955,26 @@ impl EventProcessor {
         if atom == xproto::Atom::from(xproto::AtomEnum::RESOURCE_MANAGER)
             || atom == atoms[_XSETTINGS_SETTINGS]
         {
+            if atom == atoms[_XSETTINGS_SETTINGS] {
+                wt.x_connection().invalidate_cached_double_click_settings();
+            }
             self.process_dpi_change(&mut callback);
+        } else if atom == atoms[_NET_WM_STATE] {
+            // Minimizing the window sets `_NET_WM_STATE_HIDDEN`.
+            self.update_visibility(xev.window as xproto::Window, &mut callback);
+            self.update_tiling_state(xev.window as xproto::Window, &mut callback);
+        } else if atom == atoms[_NET_WORKAREA] {
+            // The work areas are stored with the monitors.
+            wt.x_connection().invalidate_cached_monitor_list();
+            callback(&self.target, Event::WorkAreaChanged);
+        } else if xev.window as xproto::Window == wt.root
+            && wt.xconn.available_monitors().is_ok_and(|monitors| {
+                monitors.iter().any(|monitor| monitor.icc_profile_atom == atom)
+            })
+        {
+            // The profiles are stored with the monitors.
+            wt.x_connection().invalidate_cached_monitor_list();
+            callback(&self.target, Event::ColorProfileChanged);
         }
     }
 
@@ -844,15 +984,49 @@ impl EventProcessor {
     {
         let xwindow = xev.window as xproto::Window;
 
+        let occluded = xev.state == xlib::VisibilityFullyObscured;
         let event = Event::WindowEvent {
             window_id: mkwid(xwindow),
-            event: WindowEvent::Occluded(xev.state == xlib::VisibilityFullyObscured),
+            event: WindowEvent::Occluded(occluded),
         };
         callback(&self.target, event);
 
         self.with_window(xwindow, |window| {
+            window.shared_state_lock().occluded = occluded;
             window.visibility_notify();
         });
+        self.update_visibility(xwindow, &mut callback);
+    }
+
+    /// Emit `VisibilityChanged` if the visibility of the window changed.
+    fn update_visibility<T: 'static, F>(&self, window: xproto::Window, callback: &mut F)
+    where
+        F: FnMut(&RootAEL, Event<T>),
+    {
+        if let Some(Some(visibility)) =
+            self.with_window(window, |window| window.update_visibility())
+        {
+            let event = Event::WindowEvent {
+                window_id: mkwid(window),
+                event: WindowEvent::VisibilityChanged(visibility),
+            };
+            callback(&self.target, event);
+        }
+    }
+
+    fn update_tiling_state<T: 'static, F>(&self, window: xproto::Window, callback: &mut F)
+    where
+        F: FnMut(&RootAEL, Event<T>),
+    {
+        if let Some(Some(tiling_state)) =
+            self.with_window(window, |window| window.update_tiling_state())
+        {
+            let event = Event::WindowEvent {
+                window_id: mkwid(window),
+                event: WindowEvent::TilingStateChanged(tiling_state),
+            };
+            callback(&self.target, event);
+        }
     }
 
     fn expose<T: 'static, F>(&self, xev: &XExposeEvent, mut callback: F)
@@ -890,7 +1064,7 @@ impl EventProcessor {
         };
 
         let window_id = mkwid(window);
-        let device_id = mkdid(util::VIRTUAL_CORE_KEYBOARD);
+        let device_id = self.mkdid(util::VIRTUAL_CORE_KEYBOARD);
 
         let keycode = xev.keycode as _;
 
@@ -976,7 +1150,7 @@ impl EventProcessor {
             if !written.is_empty() {
                 let event = Event::WindowEvent {
                     window_id,
-                    event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                    event: WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                 };
                 callback(&self.target, event);
 
@@ -1032,7 +1206,7 @@ impl EventProcessor {
     {
         let wt = Self::window_target(&self.target);
         let window_id = mkwid(event.event as xproto::Window);
-        let device_id = mkdid(event.deviceid as xinput::DeviceId);
+        let device_id = self.mkdid(event.deviceid as xinput::DeviceId);
 
         // Set the timestamp.
         wt.xconn.set_timestamp(event.time as xproto::Timestamp);
@@ -1042,18 +1216,12 @@ impl EventProcessor {
             return;
         }
 
-        let event = match event.detail as u32 {
-            xlib::Button1 => {
-                WindowEvent::MouseInput { device_id, state, button: MouseButton::Left }
-            },
-            xlib::Button2 => {
-                WindowEvent::MouseInput { device_id, state, button: MouseButton::Middle }
-            },
-
-            xlib::Button3 => {
-                WindowEvent::MouseInput { device_id, state, button: MouseButton::Right }
-            },
+        // Releasing the grab sends crossing events for the window under the pointer.
+        if state == ElementState::Released {
+            self.drag_target.set(None);
+        }
 
+        let event = match event.detail as u32 {
             // Suppress emulated scroll wheel clicks, since we handle the real motion events for
             // those. In practice, even clicky scroll wheels appear to be reported by
             // evdev (and XInput2 in turn) as axis motion, so we don't otherwise
@@ -1069,10 +1237,7 @@ impl EventProcessor {
                 },
                 phase: TouchPhase::Moved,
             },
-            8 => WindowEvent::MouseInput { device_id, state, button: MouseButton::Back },
-
-            9 => WindowEvent::MouseInput { device_id, state, button: MouseButton::Forward },
-            x => WindowEvent::MouseInput { device_id, state, button: MouseButton::Other(x as u16) },
+            button => WindowEvent::MouseInput { device_id, state, button: mouse_button(button) },
         };
 
         let event = Event::WindowEvent { window_id, event };
@@ -1088,7 +1253,7 @@ impl EventProcessor {
         // Set the timestamp.
         wt.xconn.set_timestamp(event.time as xproto::Timestamp);
 
-        let device_id = mkdid(event.deviceid as xinput::DeviceId);
+        let device_id = self.mkdid(event.deviceid as xinput::DeviceId);
         let window = event.event as xproto::Window;
         let window_id = mkwid(window);
         let new_cursor_pos = (event.event_x, event.event_y);
@@ -1110,12 +1275,14 @@ impl EventProcessor {
             return;
         }
 
+        self.update_drag_target(window, event, &mut callback);
+
         // More gymnastics, for self.devices
         let mask = unsafe {
             slice::from_raw_parts(event.valuators.mask, event.valuators.mask_len as usize)
         };
         let mut devices = self.devices.borrow_mut();
-        let physical_device = match devices.get_mut(&DeviceId(event.sourceid as xinput::DeviceId)) {
+        let physical_device = match devices.get_mut(&(event.sourceid as xinput::DeviceId)) {
             Some(device) => device,
             None => return,
         };
@@ -1129,17 +1296,28 @@ impl EventProcessor {
 
             let x = unsafe { *value };
 
+            let touchpad = physical_device.touchpad;
             let event = if let Some(&mut (_, ref mut info)) =
                 physical_device.scroll_axes.iter_mut().find(|&&mut (axis, _)| axis == i as _)
             {
-                let delta = (x - info.position) / info.increment;
+                let delta = x - info.position;
                 info.position = x;
                 // X11 vertical scroll coordinates are opposite to winit's
-                let delta = match info.orientation {
-                    ScrollOrientation::Horizontal => {
-                        MouseScrollDelta::LineDelta(-delta as f32, 0.0)
+                let delta = match (info.orientation, touchpad) {
+                    // xf86-input-libinput passes on the scroll values of libinput unchanged, which
+                    // are in the same units as pointer motion for scrolling with the fingers.
+                    (ScrollOrientation::Horizontal, true) => {
+                        MouseScrollDelta::PixelDelta(PhysicalPosition::new(-delta, 0.0))
+                    },
+                    (ScrollOrientation::Vertical, true) => {
+                        MouseScrollDelta::PixelDelta(PhysicalPosition::new(0.0, -delta))
+                    },
+                    (ScrollOrientation::Horizontal, false) => {
+                        MouseScrollDelta::LineDelta((-delta / info.increment) as f32, 0.0)
+                    },
+                    (ScrollOrientation::Vertical, false) => {
+                        MouseScrollDelta::LineDelta(0.0, (-delta / info.increment) as f32)
                     },
-                    ScrollOrientation::Vertical => MouseScrollDelta::LineDelta(0.0, -delta as f32),
                 };
 
                 WindowEvent::MouseWheel { device_id, delta, phase: TouchPhase::Moved }
@@ -1157,6 +1335,67 @@ impl EventProcessor {
         }
     }
 
+    /// Emit the crossing events of the windows the pointer is moved over while it's grabbed by
+    /// `window`, which receives all pointer events in the meantime.
+    fn update_drag_target<T: 'static, F>(
+        &self,
+        window: xproto::Window,
+        event: &XIDeviceEvent,
+        callback: &mut F,
+    ) where
+        F: FnMut(&RootAEL, Event<T>),
+    {
+        let outside = self
+            .with_window(window, |window| {
+                let (width, height) = window.shared_state_lock().size.unwrap_or_default();
+                !(0.0..width as f64).contains(&event.event_x)
+                    || !(0.0..height as f64).contains(&event.event_y)
+            })
+            .unwrap_or(false);
+        if !outside && self.drag_target.get().is_none() {
+            return;
+        }
+
+        let target = if outside { self.window_under_pointer(event.deviceid) } else { None };
+        let target = target.filter(|&target| target != window);
+        let previous = self.drag_target.replace(target);
+        if previous == target {
+            return;
+        }
+
+        let device_id = self.mkdid(event.deviceid as xinput::DeviceId);
+        if let Some(previous) = previous {
+            let event = Event::WindowEvent {
+                window_id: mkwid(previous),
+                event: WindowEvent::CursorLeft { device_id },
+            };
+            callback(&self.target, event);
+        }
+        if let Some(target) = target {
+            let event = Event::WindowEvent {
+                window_id: mkwid(target),
+                event: WindowEvent::CursorEntered { device_id },
+            };
+            callback(&self.target, event);
+        }
+    }
+
+    /// Returns the window of this event loop the pointer is over, descending from the root window
+    /// through the frames of the window manager.
+    fn window_under_pointer(&self, device_id: c_int) -> Option<xproto::Window> {
+        let wt = Self::window_target(&self.target);
+        let mut window = wt.root;
+        loop {
+            let child = wt.xconn.query_pointer(window, device_id as _).ok()?.child;
+            if child == x11rb::NONE {
+                return None;
+            } else if self.window_exists(child) {
+                return Some(child);
+            }
+            window = child;
+        }
+    }
+
     fn xinput2_mouse_enter<T: 'static, F>(&self, event: &XIEnterEvent, mut callback: F)
     where
         F: FnMut(&RootAEL, Event<T>),
@@ -1168,7 +1407,7 @@ impl EventProcessor {
 
         let window = event.event as xproto::Window;
         let window_id = mkwid(window);
-        let device_id = mkdid(event.deviceid as xinput::DeviceId);
+        let device_id = self.mkdid(event.deviceid as xinput::DeviceId);
 
         if let Some(all_info) = DeviceInfo::get(&wt.xconn, super::ALL_DEVICES.into()) {
             let mut devices = self.devices.borrow_mut();
@@ -1179,8 +1418,7 @@ impl EventProcessor {
                 if device_info.deviceid == event.sourceid
                     || device_info.attachment == event.sourceid
                 {
-                    let device_id = DeviceId(device_info.deviceid as _);
-                    if let Some(device) = devices.get_mut(&device_id) {
+                    if let Some(device) = devices.get_mut(&(device_info.deviceid as _)) {
                         device.reset_scroll_position(device_info);
                     }
                 }
@@ -1218,7 +1456,7 @@ impl EventProcessor {
             let event = Event::WindowEvent {
                 window_id: mkwid(window),
                 event: WindowEvent::CursorLeft {
-                    device_id: mkdid(event.deviceid as xinput::DeviceId),
+                    device_id: self.mkdid(event.deviceid as xinput::DeviceId),
                 },
             };
             callback(&self.target, event);
@@ -1256,11 +1494,13 @@ impl EventProcessor {
 
         let event = Event::WindowEvent { window_id, event: WindowEvent::Focused(true) };
         callback(&self.target, event);
+        self.update_visibility(window, &mut callback);
 
         // Issue key press events for all pressed keys
         Self::handle_pressed_keys(
             &self.target,
             window_id,
+            self.mkdid(util::VIRTUAL_CORE_KEYBOARD),
             ElementState::Pressed,
             &mut self.xkb_context,
             &mut callback,
@@ -1273,13 +1513,13 @@ impl EventProcessor {
         let pointer_id = self
             .devices
             .borrow()
-            .get(&DeviceId(xev.deviceid as xinput::DeviceId))
+            .get(&(xev.deviceid as xinput::DeviceId))
             .map(|device| device.attachment)
             .unwrap_or(2);
 
         let event = Event::WindowEvent {
             window_id,
-            event: WindowEvent::CursorMoved { device_id: mkdid(pointer_id as _), position },
+            event: WindowEvent::CursorMoved { device_id: self.mkdid(pointer_id as _), position },
         };
         callback(&self.target, event);
     }
@@ -1318,6 +1558,7 @@ impl EventProcessor {
             Self::handle_pressed_keys(
                 &self.target,
                 window_id,
+                self.mkdid(util::VIRTUAL_CORE_KEYBOARD),
                 ElementState::Released,
                 &mut self.xkb_context,
                 &mut callback,
@@ -1332,7 +1573,8 @@ impl EventProcessor {
             }
 
             let event = Event::WindowEvent { window_id, event: WindowEvent::Focused(false) };
-            callback(&self.target, event)
+            callback(&self.target, event);
+            self.update_visibility(window, &mut callback);
         }
     }
 
@@ -1361,20 +1603,21 @@ impl EventProcessor {
                 let event = Event::WindowEvent {
                     window_id,
                     event: WindowEvent::CursorMoved {
-                        device_id: mkdid(util::VIRTUAL_CORE_POINTER),
+                        device_id: self.mkdid(util::VIRTUAL_CORE_POINTER),
                         position: location.cast(),
                     },
                 };
                 callback(&self.target, event);
             }
 
+            let force = self.touch_force(xev);
             let event = Event::WindowEvent {
                 window_id,
                 event: WindowEvent::Touch(Touch {
-                    device_id: mkdid(xev.deviceid as xinput::DeviceId),
+                    device_id: self.mkdid(xev.deviceid as xinput::DeviceId),
                     phase,
                     location,
-                    force: None, // TODO
+                    force,
                     id,
                 }),
             };
@@ -1382,6 +1625,24 @@ impl EventProcessor {
         }
     }
 
+    /// Read the pressure of a touch from the valuators of the device that reported it.
+    fn touch_force(&self, xev: &XIDeviceEvent) -> Option<Force> {
+        let devices = self.devices.borrow();
+        let axis = devices.get(&(xev.sourceid as xinput::DeviceId))?.pressure_axis?;
+
+        let mask =
+            unsafe { slice::from_raw_parts(xev.valuators.mask, xev.valuators.mask_len as usize) };
+        if !xinput2::XIMaskIsSet(mask, axis.number) {
+            return None;
+        }
+
+        // Values are only present for the valuators set in the mask, in order.
+        let index = (0..axis.number).filter(|&i| xinput2::XIMaskIsSet(mask, i)).count();
+        let value = unsafe { *xev.valuators.values.add(index) };
+
+        Some(Force::Normalized(((value - axis.min) / (axis.max - axis.min)).clamp(0.0, 1.0)))
+    }
+
     fn xinput2_raw_button_input<T: 'static, F>(
         &self,
         xev: &XIRawEvent,
@@ -1397,7 +1658,7 @@ impl EventProcessor {
 
         if xev.flags & xinput2::XIPointerEmulated == 0 {
             let event = Event::DeviceEvent {
-                device_id: mkdid(xev.deviceid as xinput::DeviceId),
+                device_id: self.mkdid(xev.deviceid as xinput::DeviceId),
                 event: DeviceEvent::Button { state, button: xev.detail as u32 },
             };
             callback(&self.target, event);
@@ -1413,7 +1674,7 @@ impl EventProcessor {
         // Set the timestamp.
         wt.xconn.set_timestamp(xev.time as xproto::Timestamp);
 
-        let did = mkdid(xev.deviceid as xinput::DeviceId);
+        let did = self.mkdid(xev.deviceid as xinput::DeviceId);
 
         let mask =
             unsafe { slice::from_raw_parts(xev.valuators.mask, xev.valuators.mask_len as usize) };
@@ -1477,7 +1738,7 @@ impl EventProcessor {
         // Set the timestamp.
         wt.xconn.set_timestamp(xev.time as xproto::Timestamp);
 
-        let device_id = mkdid(xev.sourceid as xinput::DeviceId);
+        let device_id = self.mkdid(xev.sourceid as xinput::DeviceId);
         let keycode = xev.detail as u32;
         if keycode < KEYCODE_OFFSET as u32 {
             return;
@@ -1502,19 +1763,23 @@ impl EventProcessor {
         for info in infos {
             if 0 != info.flags & (xinput2::XISlaveAdded | xinput2::XIMasterAdded) {
                 self.init_device(info.deviceid as xinput::DeviceId);
+                self.update_seats();
                 callback(&self.target, Event::DeviceEvent {
-                    device_id: mkdid(info.deviceid as xinput::DeviceId),
+                    device_id: self.mkdid(info.deviceid as xinput::DeviceId),
                     event: DeviceEvent::Added,
                 });
             } else if 0 != info.flags & (xinput2::XISlaveRemoved | xinput2::XIMasterRemoved) {
                 callback(&self.target, Event::DeviceEvent {
-                    device_id: mkdid(info.deviceid as xinput::DeviceId),
+                    device_id: self.mkdid(info.deviceid as xinput::DeviceId),
                     event: DeviceEvent::Removed,
                 });
                 let mut devices = self.devices.borrow_mut();
-                devices.remove(&DeviceId(info.deviceid as xinput::DeviceId));
+                devices.remove(&(info.deviceid as xinput::DeviceId));
             }
         }
+
+        // Devices may also have been attached to another master.
+        self.update_seats();
     }
 
     fn xkb_event<T: 'static, F>(&mut self, xev: &XkbAnyEvent, mut callback: F)
@@ -1761,14 +2026,13 @@ impl EventProcessor {
     fn handle_pressed_keys<T: 'static, F>(
         target: &RootAEL,
         window_id: crate::window::WindowId,
+        device_id: RootDeviceId,
         state: ElementState,
         xkb_context: &mut Context,
         callback: &mut F,
     ) where
         F: FnMut(&RootAEL, Event<T>),
     {
-        let device_id = mkdid(util::VIRTUAL_CORE_KEYBOARD);
-
         // Update modifiers state and emit key events based on which keys are currently pressed.
         let window_target = Self::window_target(target);
         let xcb = window_target.xconn.xcb_connection().get_raw_xcb_connection();
@@ -1807,7 +2071,6 @@ impl EventProcessor {
         let wt = Self::window_target(&self.target);
         wt.xconn.reload_database().expect("failed to reload Xft database");
 
-        // In the future, it would be quite easy to emit monitor hotplug events.
         let prev_list = {
             let prev_list = wt.xconn.invalidate_cached_monitor_list();
             match prev_list {
@@ -1817,7 +2080,15 @@ impl EventProcessor {
         };
 
         let new_list = wt.xconn.available_monitors().expect("Failed to get monitor list");
-        for new_monitor in new_list {
+        let monitors_changed = prev_list.len() != new_list.len()
+            || prev_list.iter().zip(&new_list).any(|(prev, new)| {
+                prev.name != new.name
+                    || prev.position() != new.position()
+                    || prev.size() != new.size()
+                    || prev.is_mirrored() != new.is_mirrored()
+            });
+
+        for new_monitor in &new_list {
             // Previous list may be empty, in case of disconnecting and
             // reconnecting the only one monitor. We still need to emit events in
             // this case.
@@ -1827,12 +2098,16 @@ impl EventProcessor {
                 .map(|prev_monitor| prev_monitor.scale_factor);
             if Some(new_monitor.scale_factor) != maybe_prev_scale_factor {
                 for window in wt.windows.borrow().iter().filter_map(|(_, w)| w.upgrade()) {
-                    window.refresh_dpi_for_monitor(&new_monitor, maybe_prev_scale_factor, |event| {
+                    window.refresh_dpi_for_monitor(new_monitor, maybe_prev_scale_factor, |event| {
                         callback(&self.target, event);
                     })
                 }
             }
         }
+
+        if monitors_changed {
+            callback(&self.target, Event::MonitorsChanged);
+        }
     }
 
     fn window_exists(&self, window_id: xproto::Window) -> bool {
@@ -1859,3 +2134,42 @@ fn is_first_touch(first: &mut Option<u64>, num: &mut u32, id: u64, phase: TouchP
 
     *first == Some(id)
 }
+
+/// The mouse button of a core button number. The X server numbers the buttons in the order of their
+/// evdev codes, after the scroll wheel buttons 4 to 7.
+fn mouse_button(button: u32) -> MouseButton {
+    match button {
+        xlib::Button1 => MouseButton::Left,
+        xlib::Button2 => MouseButton::Middle,
+        xlib::Button3 => MouseButton::Right,
+        // `BTN_SIDE` and `BTN_BACK`.
+        8 | 11 => MouseButton::Back,
+        // `BTN_EXTRA` and `BTN_FORWARD`.
+        9 | 10 => MouseButton::Forward,
+        // Count from `BTN_LEFT` like on Wayland, so that `BTN_TASK`, button 12, is `Other(7)`.
+        button => MouseButton::Other(button.saturating_sub(5) as u16),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_buttons_like_wayland() {
+        let buttons = [
+            (1, MouseButton::Left),
+            (2, MouseButton::Middle),
+            (3, MouseButton::Right),
+            (8, MouseButton::Back),
+            (9, MouseButton::Forward),
+            (10, MouseButton::Forward),
+            (11, MouseButton::Back),
+            (12, MouseButton::Other(7)),
+            (13, MouseButton::Other(8)),
+        ];
+        for (button, expected) in buttons {
+            assert_eq!(mouse_button(button), expected, "button {button}");
+        }
+    }
+}
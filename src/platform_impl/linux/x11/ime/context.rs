@@ -5,6 +5,7 @@ use std::{mem, ptr};
 
 use x11_dl::xlib::{XIMCallback, XIMPreeditCaretCallbackStruct, XIMPreeditDrawCallbackStruct};
 
+use crate::event::{PreeditSpan, PreeditUnderline};
 use crate::platform_impl::platform::x11::ime::input_method::{Style, XIMStyle};
 use crate::platform_impl::platform::x11::ime::{ImeEvent, ImeEventSender};
 
@@ -20,6 +21,11 @@ pub enum ImeContextCreationError {
     Null,
 }
 
+// The `XIMFeedback` bits, which aren't exposed by `x11-dl`.
+const XIM_REVERSE: ffi::XIMFeedback = 1;
+const XIM_UNDERLINE: ffi::XIMFeedback = 1 << 1;
+const XIM_HIGHLIGHT: ffi::XIMFeedback = 1 << 2;
+
 /// The callback used by XIM preedit functions.
 type XIMProcNonnull = unsafe extern "C" fn(ffi::XIM, ffi::XPointer, ffi::XPointer);
 
@@ -38,6 +44,7 @@ extern "C" fn preedit_start_callback(
     let client_data = unsafe { &mut *(client_data as *mut ImeContextClientData) };
 
     client_data.text.clear();
+    client_data.feedback.clear();
     client_data.cursor_pos = 0;
     client_data
         .event_sender
@@ -56,6 +63,7 @@ extern "C" fn preedit_done_callback(
 
     // Drop text buffer and reset cursor position on done.
     client_data.text = Vec::new();
+    client_data.feedback = Vec::new();
     client_data.cursor_pos = 0;
 
     client_data
@@ -68,6 +76,21 @@ fn calc_byte_position(text: &[char], pos: usize) -> usize {
     text.iter().take(pos).fold(0, |byte_pos, text| byte_pos + text.len_utf8())
 }
 
+fn preedit_update(client_data: &ImeContextClientData) -> ImeEvent {
+    let text: String = client_data.text.iter().collect();
+    let cursor_byte_pos = calc_byte_position(&client_data.text, client_data.cursor_pos);
+    let styles = client_data.feedback.iter().map(|&feedback| {
+        let underline = if feedback & XIM_UNDERLINE != 0 {
+            PreeditUnderline::Thin
+        } else {
+            PreeditUnderline::None
+        };
+        (underline, feedback & (XIM_REVERSE | XIM_HIGHLIGHT) != 0)
+    });
+    let spans = PreeditSpan::from_chars(&text, styles);
+    ImeEvent::Update(text, cursor_byte_pos, spans)
+}
+
 /// Preedit text information to be drawn inline by the client.
 extern "C" fn preedit_draw_callback(
     _xim: ffi::XIM,
@@ -91,8 +114,8 @@ extern "C" fn preedit_draw_callback(
     }
 
     // NULL indicate text deletion
-    let mut new_chars = if call_data.text.is_null() {
-        Vec::new()
+    let (mut new_chars, mut new_feedback) = if call_data.text.is_null() {
+        (Vec::new(), Vec::new())
     } else {
         let xim_text = unsafe { &mut *(call_data.text) };
         if xim_text.encoding_is_wchar > 0 {
@@ -107,20 +130,35 @@ extern "C" fn preedit_draw_callback(
 
         let new_text = unsafe { CStr::from_ptr(new_text) };
 
-        String::from(new_text.to_str().expect("Invalid UTF-8 String from IME")).chars().collect()
+        let new_chars: Vec<char> =
+            String::from(new_text.to_str().expect("Invalid UTF-8 String from IME"))
+                .chars()
+                .collect();
+
+        // The feedback is given per character, and may be missing.
+        let mut new_feedback = if xim_text.feedback.is_null() {
+            Vec::new()
+        } else {
+            unsafe { std::slice::from_raw_parts(xim_text.feedback, xim_text.length as usize) }
+                .to_vec()
+        };
+        new_feedback.resize(new_chars.len(), 0);
+
+        (new_chars, new_feedback)
     };
     let mut old_text_tail = client_data.text.split_off(chg_range.end);
     client_data.text.truncate(chg_range.start);
     client_data.text.append(&mut new_chars);
     client_data.text.append(&mut old_text_tail);
-    let cursor_byte_pos = calc_byte_position(&client_data.text, client_data.cursor_pos);
+
+    let mut old_feedback_tail = client_data.feedback.split_off(chg_range.end);
+    client_data.feedback.truncate(chg_range.start);
+    client_data.feedback.append(&mut new_feedback);
+    client_data.feedback.append(&mut old_feedback_tail);
 
     client_data
         .event_sender
-        .send((
-            client_data.window,
-            ImeEvent::Update(client_data.text.iter().collect(), cursor_byte_pos),
-        ))
+        .send((client_data.window, preedit_update(client_data)))
         .expect("failed to send preedit update event");
 }
 
@@ -135,14 +173,10 @@ extern "C" fn preedit_caret_callback(
 
     if call_data.direction == ffi::XIMCaretDirection::XIMAbsolutePosition {
         client_data.cursor_pos = call_data.position as usize;
-        let cursor_byte_pos = calc_byte_position(&client_data.text, client_data.cursor_pos);
 
         client_data
             .event_sender
-            .send((
-                client_data.window,
-                ImeEvent::Update(client_data.text.iter().collect(), cursor_byte_pos),
-            ))
+            .send((client_data.window, preedit_update(client_data)))
             .expect("failed to send preedit update event");
     }
 }
@@ -172,6 +206,8 @@ struct ImeContextClientData {
     window: ffi::Window,
     event_sender: ImeEventSender,
     text: Vec<char>,
+    /// The `XIMFeedback` of each character of `text`.
+    feedback: Vec<ffi::XIMFeedback>,
     cursor_pos: usize,
 }
 
@@ -201,6 +237,7 @@ impl ImeContext {
             window,
             event_sender,
             text: Vec::new(),
+            feedback: Vec::new(),
             cursor_pos: 0,
         }));
 
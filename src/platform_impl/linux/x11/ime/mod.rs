@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 use tracing::debug;
 
 use super::{ffi, util, XConnection, XError};
+use crate::event::PreeditSpan;
 
 use self::callbacks::*;
 use self::context::ImeContext;
@@ -25,7 +26,7 @@ use self::input_method::{PotentialInputMethods, Style};
 pub enum ImeEvent {
     Enabled,
     Start,
-    Update(String, usize),
+    Update(String, usize, Vec<PreeditSpan>),
     End,
     Disabled,
 }
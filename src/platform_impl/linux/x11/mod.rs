@@ -4,7 +4,7 @@ use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::ffi::CStr;
 use std::marker::PhantomData;
-use std::mem::MaybeUninit;
+use std::mem::{self, MaybeUninit};
 use std::ops::Deref;
 use std::os::raw::*;
 use std::os::unix::io::{AsFd, AsRawFd, BorrowedFd, RawFd};
@@ -28,10 +28,15 @@ use x11rb::x11_utils::X11Error as LogicalError;
 use x11rb::xcb_ffi::ReplyOrIdError;
 
 use crate::application::ApplicationHandler;
-use crate::error::{EventLoopError, OsError as RootOsError};
-use crate::event::{Event, StartCause, WindowEvent};
+use crate::dpi::PhysicalPosition;
+use crate::error::{EventLoopError, ExternalError, OsError as RootOsError};
+use crate::event::{Event, Seat, SeatId, Signal, StartCause, WindowEvent};
 use crate::event_loop::{ActiveEventLoop as RootAEL, ControlFlow, DeviceEvents, EventLoopClosed};
 use crate::platform::pump_events::PumpStatus;
+use crate::platform::unix::Interest;
+use crate::platform::x11::VisualSelector;
+use crate::platform_impl::common::fd::FdSources;
+use crate::platform_impl::common::session::{self, EndSession, SessionEvent};
 use crate::platform_impl::common::xkb::Context;
 use crate::platform_impl::platform::{min_timeout, WindowId};
 use crate::platform_impl::{
@@ -54,7 +59,7 @@ mod xsettings;
 pub use util::CustomCursor;
 
 use atoms::*;
-use dnd::{Dnd, DndState};
+use dnd::{Dnd, DndState, Transfer};
 use event_processor::{EventProcessor, MAX_MOD_REPLAY_LEN};
 use ime::{Ime, ImeCreationError, ImeReceiver, ImeRequest, ImeSender};
 pub(crate) use monitor::{MonitorHandle, VideoModeHandle};
@@ -141,6 +146,7 @@ pub struct ActiveEventLoop {
     redraw_sender: WakeSender<WindowId>,
     activation_sender: WakeSender<ActivationToken>,
     device_events: Cell<DeviceEvents>,
+    fd_sources: FdSources<EventLoopState>,
 }
 
 pub struct EventLoop<T: 'static> {
@@ -162,6 +168,15 @@ type ActivationToken = (WindowId, crate::event_loop::AsyncRequestSerial);
 struct EventLoopState {
     /// The latest readiness state for the x11 file descriptor
     x11_readiness: Readiness,
+    /// Whether `SIGTERM` was received since the last iteration, unless it was requested, or the
+    /// session ended.
+    terminating: bool,
+    /// The session manager asks whether to end the session.
+    session_end: Option<EndSession>,
+    /// The requested signals received since the last iteration.
+    signals: Vec<Signal>,
+    /// The tokens of the file descriptors of the application that are ready.
+    fd_ready: Vec<u64>,
 }
 
 pub struct EventLoopProxy<T: 'static> {
@@ -223,6 +238,16 @@ impl<T: 'static> EventLoop<T> {
         let randr_event_offset =
             xconn.select_xrandr_input(root).expect("Failed to query XRandR extension");
 
+        // Get PropertyNotify events for `_NET_WORKAREA` from the root window.
+        xconn
+            .xcb_connection()
+            .change_window_attributes(
+                root,
+                &xproto::ChangeWindowAttributesAux::new()
+                    .event_mask(xproto::EventMask::PROPERTY_CHANGE),
+            )
+            .expect("Failed to select events on the root window");
+
         let xi2ext = xconn
             .xcb_connection()
             .extension_information(xinput::X11_EXTENSION_NAME)
@@ -306,6 +331,7 @@ impl<T: 'static> EventLoop<T> {
                 waker: waker.clone(),
             },
             device_events: Default::default(),
+            fd_sources: FdSources::new(handle.clone()),
         };
 
         // Set initial device event filter.
@@ -318,6 +344,7 @@ impl<T: 'static> EventLoop<T> {
             target: root_window_target,
             dnd,
             devices: Default::default(),
+            seats: Default::default(),
             randr_event_offset,
             ime_receiver,
             ime_event_receiver,
@@ -332,6 +359,7 @@ impl<T: 'static> EventLoop<T> {
             active_window: None,
             modifiers: Default::default(),
             is_composing: false,
+            drag_target: Default::default(),
         };
 
         // Register for device hotplug events
@@ -356,6 +384,7 @@ impl<T: 'static> EventLoop<T> {
             .unwrap();
 
         event_processor.init_device(ALL_DEVICES);
+        event_processor.update_seats();
 
         EventLoop {
             loop_running: false,
@@ -366,7 +395,13 @@ impl<T: 'static> EventLoop<T> {
             activation_receiver: PeekableReceiver::from_recv(activation_token_channel),
             user_receiver: PeekableReceiver::from_recv(user_channel),
             user_sender,
-            state: EventLoopState { x11_readiness: Readiness::EMPTY },
+            state: EventLoopState {
+                x11_readiness: Readiness::EMPTY,
+                terminating: false,
+                session_end: None,
+                signals: Vec::new(),
+                fd_ready: Vec::new(),
+            },
         }
     }
 
@@ -380,6 +415,41 @@ impl<T: 'static> EventLoop<T> {
         &self.event_processor.target
     }
 
+    /// Receive `signals` in the event loop, and `SIGTERM` to shut down if it isn't requested.
+    pub(crate) fn receive_signals(&mut self, signals: &[Signal]) {
+        #[cfg(target_os = "linux")]
+        {
+            let requested = signals.to_vec();
+            let mut signals = requested.clone();
+            if !signals.contains(&Signal::Terminate) {
+                signals.push(Signal::Terminate);
+            }
+            crate::platform_impl::common::signal::insert_source(
+                &self.event_loop.handle(),
+                &signals,
+                move |received, state| {
+                    for &signal in received {
+                        if requested.contains(&signal) {
+                            state.signals.push(signal);
+                        } else {
+                            state.terminating = true;
+                        }
+                    }
+                },
+            );
+        }
+        #[cfg(not(target_os = "linux"))]
+        let _ = signals;
+    }
+
+    /// Receive the requests of the session manager in the event loop.
+    pub(crate) fn join_session(&mut self) {
+        session::insert_source(&self.event_loop.handle(), |event, state| match event {
+            SessionEvent::EndRequested(end) => state.session_end = Some(end),
+            SessionEvent::End => state.terminating = true,
+        });
+    }
+
     pub fn run_app<A: ApplicationHandler<T>>(mut self, app: &mut A) -> Result<(), EventLoopError> {
         self.run_app_on_demand(app)
     }
@@ -444,6 +514,9 @@ impl<T: 'static> EventLoop<T> {
 
     fn has_pending(&mut self) -> bool {
         self.event_processor.poll()
+            || self.state.terminating
+            || !self.state.signals.is_empty()
+            || !self.state.fd_ready.is_empty()
             || self.user_receiver.has_incoming()
             || self.redraw_receiver.has_incoming()
     }
@@ -473,9 +546,12 @@ impl<T: 'static> EventLoop<T> {
         };
 
         self.state.x11_readiness = Readiness::EMPTY;
-        if let Err(error) =
-            self.event_loop.dispatch(timeout, &mut self.state).map_err(std::io::Error::from)
-        {
+        #[cfg(feature = "trace")]
+        let span = tracing::trace_span!("winit::dispatch_sources", ?timeout).entered();
+        let result = self.event_loop.dispatch(timeout, &mut self.state);
+        #[cfg(feature = "trace")]
+        drop(span);
+        if let Err(error) = result.map_err(std::io::Error::from) {
             tracing::error!("Failed to poll for events: {error:?}");
             let exit_code = error.raw_os_error().unwrap_or(1);
             self.set_exit_code(exit_code);
@@ -554,6 +630,25 @@ impl<T: 'static> EventLoop<T> {
             }
         }
 
+        for token in mem::take(&mut self.state.fd_ready) {
+            app.fd_ready(&self.event_processor.target, token);
+        }
+
+        for signal in mem::take(&mut self.state.signals) {
+            app.signal(&self.event_processor.target, signal);
+        }
+
+        if let Some(end) = self.state.session_end.take() {
+            end.request_close(|respond| {
+                app.close_requested_by_os(&self.event_processor.target, respond)
+            });
+        }
+
+        if mem::take(&mut self.state.terminating) {
+            app.terminating(&self.event_processor.target);
+            EventProcessor::window_target(&self.event_processor.target).exit();
+        }
+
         // Empty the redraw requests
         {
             let mut windows = HashSet::new();
@@ -597,6 +692,13 @@ impl<T: 'static> EventLoop<T> {
                         Event::DeviceEvent { device_id, event } => {
                             app.device_event(window_target, device_id, event)
                         },
+                        Event::WorkAreaChanged => app.work_area_changed(window_target),
+                        Event::MonitorsChanged => app.monitors_changed(window_target),
+                        Event::ColorProfileChanged => app.color_profile_changed(window_target),
+                        Event::DragHover { window_id, position, respond } => {
+                            let operation = app.drag_hover(window_target, window_id, position);
+                            let _ = respond.respond(operation);
+                        },
                         _ => unreachable!("event which is neither device nor window event."),
                     }
                 }
@@ -652,6 +754,20 @@ impl ActiveEventLoop {
         self.xconn.primary_monitor().ok()
     }
 
+    /// Every master pointer stands for a seat, together with its paired master keyboard.
+    pub fn seats(&self) -> Vec<Seat> {
+        let Some(info) = DeviceInfo::get(&self.xconn, ALL_MASTER_DEVICES.into()) else {
+            return Vec::new();
+        };
+        info.iter()
+            .filter(|info| info._use == ffi::XIMasterPointer)
+            .map(|info| {
+                let name = unsafe { CStr::from_ptr(info.name) }.to_string_lossy();
+                Seat::new(SeatId(info.deviceid as u64), name.into_owned())
+            })
+            .collect()
+    }
+
     pub(crate) fn create_custom_cursor(&self, cursor: CustomCursorSource) -> RootCustomCursor {
         RootCustomCursor { inner: PlatformCustomCursor::X(CustomCursor::new(self, cursor.inner)) }
     }
@@ -660,6 +776,35 @@ impl ActiveEventLoop {
         self.device_events.set(allowed);
     }
 
+    pub fn pointer_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        let pointer = self
+            .xconn
+            .query_pointer(self.root, util::VIRTUAL_CORE_POINTER)
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
+        Ok(PhysicalPosition::new(
+            xinput_fp1616_to_float(pointer.root_x),
+            xinput_fp1616_to_float(pointer.root_y),
+        ))
+    }
+
+    pub fn set_video_mode(
+        &self,
+        mode: &VideoModeHandle,
+    ) -> Result<Box<dyn FnOnce()>, ExternalError> {
+        let x_error = |err: X11Error| ExternalError::Os(os_error!(OsError::XError(err.into())));
+        let crtc = mode.monitor().id;
+        let original = self.xconn.get_crtc_mode(crtc).map_err(x_error)?;
+        self.xconn.set_crtc_config(crtc, mode.native_mode).map_err(x_error)?;
+
+        // Unlike other platforms, the X server keeps the mode after the client disconnected.
+        let xconn = Arc::clone(&self.xconn);
+        Ok(Box::new(move || {
+            if let Err(err) = xconn.set_crtc_config(crtc, original) {
+                warn!("failed to restore the video mode: {err}");
+            }
+        }))
+    }
+
     /// Update the device event based on window focus.
     pub fn update_listen_device_events(&self, focus: bool) {
         let device_events = self.device_events.get() == DeviceEvents::Always
@@ -702,6 +847,19 @@ impl ActiveEventLoop {
         Ok(display_handle.into())
     }
 
+    pub(crate) fn register_fd(
+        &self,
+        fd: BorrowedFd<'_>,
+        interest: Interest,
+        token: u64,
+    ) -> Result<(), ExternalError> {
+        self.fd_sources.register(fd, interest, token, |token, state| state.fd_ready.push(token))
+    }
+
+    pub(crate) fn unregister_fd(&self, token: u64) {
+        self.fd_sources.unregister(token)
+    }
+
     pub(crate) fn set_control_flow(&self, control_flow: ControlFlow) {
         self.control_flow.set(control_flow)
     }
@@ -735,6 +893,20 @@ impl<T: 'static> EventLoopProxy<T> {
     pub fn send_event(&self, event: T) -> Result<(), EventLoopClosed<T>> {
         self.user_sender.send(event).map_err(|e| EventLoopClosed(e.0))
     }
+
+    pub fn waker(&self) -> EventLoopWaker {
+        EventLoopWaker(self.user_sender.waker.clone())
+    }
+}
+
+/// Wakes up the `EventLoop` from any thread, without a message.
+#[derive(Clone)]
+pub struct EventLoopWaker(Ping);
+
+impl EventLoopWaker {
+    pub fn wake(&self) {
+        self.0.ping();
+    }
 }
 
 struct DeviceInfo<'a> {
@@ -775,12 +947,24 @@ impl<'a> Deref for DeviceInfo<'a> {
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct DeviceId(xinput::DeviceId);
+pub struct DeviceId {
+    id: xinput::DeviceId,
+    /// The master pointer of the device, floating devices don't belong to a seat.
+    seat: Option<xinput::DeviceId>,
+}
 
 impl DeviceId {
+    fn new(id: xinput::DeviceId, seat: Option<xinput::DeviceId>) -> Self {
+        Self { id, seat }
+    }
+
     #[allow(unused)]
     pub const unsafe fn dummy() -> Self {
-        DeviceId(0)
+        DeviceId { id: 0, seat: None }
+    }
+
+    pub fn seat_id(&self) -> Option<SeatId> {
+        self.seat.map(|seat| SeatId(seat as u64))
     }
 }
 
@@ -847,6 +1031,9 @@ pub enum X11Error {
     /// Could not find a matching X11 visual for this visualid
     NoSuchVisual(xproto::Visualid),
 
+    /// Could not find an X11 visual matching the selector
+    NoMatchingVisual(VisualSelector),
+
     /// Unable to parse xsettings.
     XsettingsParse(xsettings::ParserError),
 
@@ -873,6 +1060,9 @@ impl fmt::Display for X11Error {
             X11Error::NoSuchVisual(visualid) => {
                 write!(f, "Could not find a matching X11 visual for ID `{:x}`", visualid)
             },
+            X11Error::NoMatchingVisual(selector) => {
+                write!(f, "Could not find an X11 visual matching {:?}", selector)
+            },
             X11Error::XsettingsParse(err) => {
                 write!(f, "Failed to parse xsettings: {:?}", err)
             },
@@ -974,14 +1164,14 @@ impl<'a, E: fmt::Debug> CookieResultExt for Result<VoidCookie<'a>, E> {
 fn mkwid(w: xproto::Window) -> crate::window::WindowId {
     crate::window::WindowId(crate::platform_impl::platform::WindowId(w as _))
 }
-fn mkdid(w: xinput::DeviceId) -> crate::event::DeviceId {
-    crate::event::DeviceId(crate::platform_impl::DeviceId::X(DeviceId(w)))
-}
-
 #[derive(Debug)]
 pub struct Device {
     _name: String,
     scroll_axes: Vec<(i32, ScrollAxis)>,
+    pressure_axis: Option<PressureAxis>,
+    // Touchpads report touches in device coordinates and scroll through the valuators with pixel
+    // precision, instead of in discrete wheel steps.
+    touchpad: bool,
     // For master devices, this is the paired device (pointer <-> keyboard).
     // For slave devices, this is the master.
     attachment: c_int,
@@ -994,6 +1184,13 @@ struct ScrollAxis {
     position: f64,
 }
 
+#[derive(Debug, Copy, Clone)]
+struct PressureAxis {
+    number: i32,
+    min: f64,
+    max: f64,
+}
+
 #[derive(Debug, Copy, Clone)]
 enum ScrollOrientation {
     Vertical,
@@ -1001,15 +1198,25 @@ enum ScrollOrientation {
 }
 
 impl Device {
-    fn new(info: &ffi::XIDeviceInfo) -> Self {
+    fn new(info: &ffi::XIDeviceInfo, pressure_label: xproto::Atom, touchpad: bool) -> Self {
         let name = unsafe { CStr::from_ptr(info.name).to_string_lossy() };
         let mut scroll_axes = Vec::new();
+        let mut pressure_axis = None;
 
         if Device::physical_device(info) {
-            // Identify scroll axes
+            // Identify scroll and pressure axes
             for &class_ptr in Device::classes(info) {
                 let ty = unsafe { (*class_ptr)._type };
-                if ty == ffi::XIScrollClass {
+                if ty == ffi::XIValuatorClass {
+                    let info = unsafe { &*(class_ptr as *const ffi::XIValuatorClassInfo) };
+                    if info.label as xproto::Atom == pressure_label && info.max > info.min {
+                        pressure_axis = Some(PressureAxis {
+                            number: info.number,
+                            min: info.min,
+                            max: info.max,
+                        });
+                    }
+                } else if ty == ffi::XIScrollClass {
                     let info = unsafe { &*(class_ptr as *const ffi::XIScrollClassInfo) };
                     scroll_axes.push((info.number, ScrollAxis {
                         increment: info.increment,
@@ -1024,8 +1231,13 @@ impl Device {
             }
         }
 
-        let mut device =
-            Device { _name: name.into_owned(), scroll_axes, attachment: info.attachment };
+        let mut device = Device {
+            _name: name.into_owned(),
+            scroll_axes,
+            pressure_axis,
+            touchpad,
+            attachment: info.attachment,
+        };
         device.reset_scroll_position(info);
         device
     }
@@ -1,9 +1,15 @@
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use super::atoms::*;
 use super::{util, X11Error, XConnection};
 use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::event_loop::DpiPolicy;
+use crate::monitor::Rect;
 use crate::platform_impl::VideoModeHandle as PlatformVideoModeHandle;
 use x11rb::connection::RequestConnection;
 use x11rb::protocol::randr::{self, ConnectionExt as _};
-use x11rb::protocol::xproto;
+use x11rb::protocol::xproto::{self, ConnectionExt as _};
 
 // Used for testing. This should always be committed as false.
 const DISABLE_MONITOR_LIST_CACHING: bool = false;
@@ -40,6 +46,16 @@ impl VideoModeHandle {
         self.refresh_rate_millihertz
     }
 
+    pub fn refresh_rate_range(&self) -> RangeInclusive<u32> {
+        let rate = self.refresh_rate_millihertz;
+        match self.monitor.as_ref() {
+            Some(monitor) if monitor.variable_refresh => {
+                monitor.min_refresh_rate_millihertz.unwrap_or(rate).min(rate)..=rate
+            },
+            _ => rate..=rate,
+        }
+    }
+
     #[inline]
     pub fn monitor(&self) -> MonitorHandle {
         self.monitor.clone().unwrap()
@@ -64,8 +80,20 @@ pub struct MonitorHandle {
     pub(crate) scale_factor: f64,
     /// Used to determine which windows are on this monitor
     pub(crate) rect: util::AaRect,
+    /// The position and the size of the part of the monitor not covered by panels
+    work_area: ((i32, i32), (u32, u32)),
+    /// The root window property holding the ICC profile
+    pub(crate) icc_profile_atom: xproto::Atom,
+    /// The ICC profile set by the color manager
+    icc_profile: Option<Arc<[u8]>>,
     /// Supported video modes on this monitor
     video_modes: Vec<VideoModeHandle>,
+    /// If the monitor supports variable refresh rates
+    variable_refresh: bool,
+    /// The lowest refresh rate of the monitor, from its EDID
+    min_refresh_rate_millihertz: Option<u32>,
+    /// If the monitor shows the same content as another one
+    mirrored: bool,
 }
 
 impl PartialEq for MonitorHandle {
@@ -111,6 +139,7 @@ impl MonitorHandle {
         id: randr::Crtc,
         crtc: &randr::GetCrtcInfoReply,
         primary: bool,
+        work_area: Option<(i32, i32, u32, u32)>,
     ) -> Option<Self> {
         let (name, scale_factor, video_modes) = xconn.get_output_info(resources, crtc)?;
         let dimensions = (crtc.width as u32, crtc.height as u32);
@@ -125,6 +154,20 @@ impl MonitorHandle {
             .and_then(mode_refresh_rate_millihertz);
 
         let rect = util::AaRect::new(position, dimensions);
+        let (variable_refresh, min_refresh_rate_millihertz) =
+            xconn.variable_refresh(crtc.outputs[0]);
+
+        // `_NET_WORKAREA` spans all monitors, so only the part on this monitor is used.
+        let work_area = work_area
+            .and_then(|(x, y, width, height)| {
+                let left = x.max(position.0);
+                let top = y.max(position.1);
+                let right = (x + width as i32).min(position.0 + dimensions.0 as i32);
+                let bottom = (y + height as i32).min(position.1 + dimensions.1 as i32);
+                (right > left && bottom > top)
+                    .then(|| ((left, top), ((right - left) as u32, (bottom - top) as u32)))
+            })
+            .unwrap_or((position, dimensions));
 
         Some(MonitorHandle {
             id,
@@ -135,7 +178,13 @@ impl MonitorHandle {
             position,
             primary,
             rect,
+            work_area,
+            icc_profile_atom: x11rb::NONE,
+            icc_profile: None,
             video_modes,
+            variable_refresh,
+            min_refresh_rate_millihertz,
+            mirrored: crtc.outputs.len() > 1,
         })
     }
 
@@ -143,13 +192,19 @@ impl MonitorHandle {
         MonitorHandle {
             id: 0,
             name: "<dummy monitor>".into(),
-            scale_factor: 1.0,
+            scale_factor: DpiPolicy::current().apply(1.0),
             dimensions: (1, 1),
             position: (0, 0),
             refresh_rate_millihertz: None,
             primary: true,
             rect: util::AaRect::new((0, 0), (1, 1)),
+            work_area: ((0, 0), (1, 1)),
+            icc_profile_atom: x11rb::NONE,
+            icc_profile: None,
             video_modes: Vec::new(),
+            variable_refresh: false,
+            min_refresh_rate_millihertz: None,
+            mirrored: false,
         }
     }
 
@@ -175,10 +230,29 @@ impl MonitorHandle {
         self.position.into()
     }
 
+    pub fn work_area(&self) -> Rect {
+        let (position, size) = self.work_area;
+        Rect::new(position.into(), size.into())
+    }
+
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
         self.refresh_rate_millihertz
     }
 
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        self.icc_profile.as_deref().map(<[u8]>::to_vec)
+    }
+
+    #[inline]
+    pub fn supports_variable_refresh(&self) -> bool {
+        self.variable_refresh
+    }
+
+    #[inline]
+    pub fn is_mirrored(&self) -> bool {
+        self.mirrored
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         self.scale_factor
@@ -247,6 +321,8 @@ impl XConnection {
             crtc_infos.push(reply);
         }
 
+        let work_area = self.query_work_area(root.root);
+
         let mut has_primary = false;
         let mut available_monitors = Vec::with_capacity(resources.crtcs().len());
         for (crtc_id, crtc) in resources.crtcs().iter().zip(crtc_infos.iter()) {
@@ -256,10 +332,34 @@ impl XConnection {
 
             let is_primary = crtc.outputs[0] == primary;
             has_primary |= is_primary;
-            let monitor = MonitorHandle::new(self, &resources, *crtc_id, crtc, is_primary);
+            let monitor =
+                MonitorHandle::new(self, &resources, *crtc_id, crtc, is_primary, work_area);
             available_monitors.extend(monitor);
         }
 
+        // Mirroring can also use a CRTC for each output, showing the same area of the screen.
+        let areas: Vec<_> = available_monitors
+            .iter()
+            .map(|monitor| (monitor.position, monitor.dimensions))
+            .collect();
+        for monitor in &mut available_monitors {
+            let area = (monitor.position, monitor.dimensions);
+            monitor.mirrored |= areas.iter().filter(|&&other| other == area).count() > 1;
+        }
+
+        // Color managers like colord store the profiles in the order of the Xinerama screens, which
+        // is the order of the CRTCs.
+        for (index, monitor) in available_monitors.iter_mut().enumerate() {
+            if let Some(atom) = self.icc_profile_atom(index) {
+                monitor.icc_profile_atom = atom;
+                monitor.icc_profile = self
+                    .get_property::<u8>(root.root, atom, xproto::AtomEnum::CARDINAL.into())
+                    .ok()
+                    .filter(|profile| !profile.is_empty())
+                    .map(Into::into);
+            }
+        }
+
         // If we don't have a primary monitor, just pick one ourselves!
         if !has_primary {
             if let Some(ref mut fallback) = available_monitors.first_mut() {
@@ -271,6 +371,32 @@ impl XConnection {
         Ok(available_monitors)
     }
 
+    /// Returns the `_NET_WORKAREA` of the current desktop.
+    fn query_work_area(&self, root: xproto::Window) -> Option<(i32, i32, u32, u32)> {
+        let atoms = self.atoms();
+        let cardinal = xproto::Atom::from(xproto::AtomEnum::CARDINAL);
+        let desktop = self
+            .get_property::<u32>(root, atoms[_NET_CURRENT_DESKTOP], cardinal)
+            .ok()
+            .and_then(|desktop| desktop.first().copied())
+            .unwrap_or(0);
+        let work_areas = self.get_property::<u32>(root, atoms[_NET_WORKAREA], cardinal).ok()?;
+        match *work_areas.chunks_exact(4).nth(desktop as usize)? {
+            [x, y, width, height] => Some((x as i32, y as i32, width, height)),
+            _ => None,
+        }
+    }
+
+    /// Returns the `_ICC_PROFILE` atom of a monitor, which is suffixed with the index of the
+    /// monitor from the second one on.
+    fn icc_profile_atom(&self, index: usize) -> Option<xproto::Atom> {
+        let name = match index {
+            0 => "_ICC_PROFILE".to_owned(),
+            index => format!("_ICC_PROFILE_{index}"),
+        };
+        Some(self.xcb_connection().intern_atom(false, name.as_bytes()).ok()?.reply().ok()?.atom)
+    }
+
     pub fn available_monitors(&self) -> Result<Vec<MonitorHandle>, X11Error> {
         let mut monitors_lock = self.monitor_handles.lock().unwrap();
         match *monitors_lock {
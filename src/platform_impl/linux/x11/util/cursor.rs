@@ -4,7 +4,9 @@ use std::sync::Arc;
 use std::{iter, slice};
 
 use x11rb::connection::Connection;
+use x11rb::protocol::xinput::{self, ConnectionExt as _};
 
+use crate::cursor::{self, CursorImage};
 use crate::platform_impl::PlatformCustomCursorSource;
 use crate::window::CursorIcon;
 
@@ -13,18 +15,45 @@ use super::*;
 
 impl XConnection {
     pub fn set_cursor_icon(&self, window: xproto::Window, cursor: Option<CursorIcon>) {
-        let cursor = *self
-            .cursor_cache
-            .lock()
-            .unwrap()
-            .entry(cursor)
-            .or_insert_with(|| self.get_cursor(cursor));
-
+        let cursor = self.cached_cursor(cursor);
         self.update_cursor(window, cursor).expect("Failed to set cursor");
     }
 
-    pub(crate) fn set_custom_cursor(&self, window: xproto::Window, cursor: &CustomCursor) {
-        self.update_cursor(window, cursor.inner.cursor).expect("Failed to set cursor");
+    /// Set the custom cursor of the window, using the image fitting `scale_factor` best.
+    pub(crate) fn set_custom_cursor(
+        &self,
+        window: xproto::Window,
+        cursor: &CustomCursor,
+        scale_factor: f64,
+    ) {
+        self.update_cursor(window, cursor.for_scale_factor(scale_factor))
+            .expect("Failed to set cursor");
+    }
+
+    /// Set the cursor of the window for a single master pointer.
+    pub(crate) fn set_device_cursor_icon(
+        &self,
+        window: xproto::Window,
+        device: xinput::DeviceId,
+        cursor: CursorIcon,
+    ) -> Result<(), X11Error> {
+        let cursor = self.cached_cursor(Some(cursor));
+        self.update_device_cursor(window, device, cursor)
+    }
+
+    /// Set the custom cursor of the window for a single master pointer.
+    pub(crate) fn set_device_custom_cursor(
+        &self,
+        window: xproto::Window,
+        device: xinput::DeviceId,
+        cursor: &CustomCursor,
+        scale_factor: f64,
+    ) -> Result<(), X11Error> {
+        self.update_device_cursor(window, device, cursor.for_scale_factor(scale_factor))
+    }
+
+    fn cached_cursor(&self, cursor: Option<CursorIcon>) -> ffi::Cursor {
+        *self.cursor_cache.lock().unwrap().entry(cursor).or_insert_with(|| self.get_cursor(cursor))
     }
 
     fn create_empty_cursor(&self) -> ffi::Cursor {
@@ -93,6 +122,20 @@ impl XConnection {
         self.xcb_connection().flush()?;
         Ok(())
     }
+
+    fn update_device_cursor(
+        &self,
+        window: xproto::Window,
+        device: xinput::DeviceId,
+        cursor: ffi::Cursor,
+    ) -> Result<(), X11Error> {
+        self.xcb_connection()
+            .xinput_xi_change_cursor(window, cursor as xproto::Cursor, device)?
+            .ignore_error();
+
+        self.xcb_connection().flush()?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -125,20 +168,37 @@ impl CustomCursor {
         event_loop: &ActiveEventLoop,
         cursor: PlatformCustomCursorSource,
     ) -> CustomCursor {
+        let cursors = cursor
+            .0
+            .iter()
+            .map(|image| (image.scale_factor, Self::load_cursor(event_loop, image)))
+            .collect();
+        Self { inner: Arc::new(CustomCursorInner { xconn: event_loop.xconn.clone(), cursors }) }
+    }
+
+    /// Returns the cursor for the image fitting the scale factor best.
+    fn for_scale_factor(&self, scale_factor: f64) -> ffi::Cursor {
+        cursor::for_scale_factor(&self.inner.cursors, scale_factor, |(scale_factor, _)| {
+            *scale_factor
+        })
+        .1
+    }
+
+    fn load_cursor(event_loop: &ActiveEventLoop, image: &CursorImage) -> ffi::Cursor {
         unsafe {
             let ximage = (event_loop.xconn.xcursor.XcursorImageCreate)(
-                cursor.0.width as i32,
-                cursor.0.height as i32,
+                image.width as i32,
+                image.height as i32,
             );
             if ximage.is_null() {
                 panic!("failed to allocate cursor image");
             }
-            (*ximage).xhot = cursor.0.hotspot_x as u32;
-            (*ximage).yhot = cursor.0.hotspot_y as u32;
+            (*ximage).xhot = image.hotspot_x as u32;
+            (*ximage).yhot = image.hotspot_y as u32;
             (*ximage).delay = 0;
 
-            let dst = slice::from_raw_parts_mut((*ximage).pixels, cursor.0.rgba.len() / 4);
-            for (dst, chunk) in dst.iter_mut().zip(cursor.0.rgba.chunks_exact(4)) {
+            let dst = slice::from_raw_parts_mut((*ximage).pixels, image.rgba.len() / 4);
+            for (dst, chunk) in dst.iter_mut().zip(image.rgba.chunks_exact(4)) {
                 *dst = (chunk[0] as u32) << 16
                     | (chunk[1] as u32) << 8
                     | (chunk[2] as u32)
@@ -148,7 +208,7 @@ impl CustomCursor {
             let cursor =
                 (event_loop.xconn.xcursor.XcursorImageLoadCursor)(event_loop.xconn.display, ximage);
             (event_loop.xconn.xcursor.XcursorImageDestroy)(ximage);
-            Self { inner: Arc::new(CustomCursorInner { xconn: event_loop.xconn.clone(), cursor }) }
+            cursor
         }
     }
 }
@@ -156,13 +216,16 @@ impl CustomCursor {
 #[derive(Debug)]
 struct CustomCursorInner {
     xconn: Arc<XConnection>,
-    cursor: ffi::Cursor,
+    /// A cursor for each image along with its scale factor, sorted by scale factor.
+    cursors: Vec<(f64, ffi::Cursor)>,
 }
 
 impl Drop for CustomCursorInner {
     fn drop(&mut self) {
-        unsafe {
-            (self.xconn.xlib.XFreeCursor)(self.xconn.display, self.cursor);
+        for &(_, cursor) in &self.cursors {
+            unsafe {
+                (self.xconn.xlib.XFreeCursor)(self.xconn.display, cursor);
+            }
         }
     }
 }
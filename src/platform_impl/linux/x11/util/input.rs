@@ -27,6 +27,40 @@ impl XConnection {
             .map_err(Into::into)
     }
 
+    /// Grab the pointer events of a master pointer, delivering the ones outside of our windows to
+    /// `window`.
+    pub fn grab_xinput_pointer(
+        &self,
+        window: xproto::Window,
+        device_id: xinput::DeviceId,
+    ) -> Result<xproto::GrabStatus, X11Error> {
+        let mask = xinput::XIEventMask::MOTION
+            | xinput::XIEventMask::BUTTON_PRESS
+            | xinput::XIEventMask::BUTTON_RELEASE
+            | xinput::XIEventMask::ENTER
+            | xinput::XIEventMask::LEAVE;
+        let reply = self
+            .xcb_connection()
+            .xinput_xi_grab_device(
+                window,
+                x11rb::CURRENT_TIME,
+                x11rb::NONE,
+                device_id,
+                xproto::GrabMode::ASYNC,
+                xproto::GrabMode::ASYNC,
+                xinput::GrabOwner::OWNER,
+                &[mask.into()],
+            )?
+            .reply()?;
+        Ok(reply.status)
+    }
+
+    pub fn ungrab_xinput_pointer(&self, device_id: xinput::DeviceId) -> Result<(), X11Error> {
+        self.xcb_connection().xinput_xi_ungrab_device(x11rb::CURRENT_TIME, device_id)?;
+        self.flush_requests()?;
+        Ok(())
+    }
+
     pub fn select_xkb_events(
         &self,
         device_id: xkb::DeviceSpec,
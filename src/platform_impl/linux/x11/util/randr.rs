@@ -3,6 +3,7 @@ use std::{env, str};
 
 use super::*;
 use crate::dpi::validate_scale_factor;
+use crate::event_loop::DpiPolicy;
 use crate::platform_impl::platform::x11::{monitor, VideoModeHandle};
 
 use tracing::warn;
@@ -153,7 +154,7 @@ impl XConnection {
             },
         };
 
-        Some((name, scale_factor, modes))
+        Some((name, DpiPolicy::current().apply(scale_factor), modes))
     }
 
     pub fn set_crtc_config(
@@ -183,4 +184,74 @@ impl XConnection {
     pub fn get_crtc_mode(&self, crtc_id: randr::Crtc) -> Result<randr::Mode, X11Error> {
         Ok(self.xcb_connection().randr_get_crtc_info(crtc_id, x11rb::CURRENT_TIME)?.reply()?.mode)
     }
+
+    /// Returns whether the output supports variable refresh rates, and the lowest refresh rate in
+    /// mHz the monitor reports in its EDID.
+    pub fn variable_refresh(&self, output: randr::Output) -> (bool, Option<u32>) {
+        let atoms = self.atoms();
+        let vrr_capable = self
+            .output_property(output, atoms[VrrCapable])
+            .is_some_and(|value| value.iter().any(|&byte| byte != 0));
+        if !vrr_capable {
+            return (false, None);
+        }
+
+        let min_refresh_rate =
+            self.output_property(output, atoms[Edid]).as_deref().and_then(edid_min_refresh_rate);
+        (true, min_refresh_rate)
+    }
+
+    fn output_property(&self, output: randr::Output, property: xproto::Atom) -> Option<Vec<u8>> {
+        let reply = self
+            .xcb_connection()
+            .randr_get_output_property(
+                output,
+                property,
+                xproto::AtomEnum::ANY,
+                0,
+                // In 4-byte units, enough for an EDID with extension blocks.
+                256,
+                false,
+                false,
+            )
+            .ok()?
+            .reply()
+            .ok()?;
+        (reply.num_items > 0).then_some(reply.data)
+    }
+}
+
+/// Returns the minimum vertical rate of the display range limits descriptor of an EDID, in mHz.
+fn edid_min_refresh_rate(edid: &[u8]) -> Option<u32> {
+    // The four 18 byte descriptors of the base block.
+    let descriptors = edid.get(54..126)?;
+    descriptors.chunks_exact(18).find_map(|descriptor| {
+        if descriptor[..3] != [0, 0, 0] || descriptor[3] != 0xfd {
+            return None;
+        }
+        // EDID 1.4 adds 255 Hz to the rate if the lowest bit of the flags is set.
+        let offset = if descriptor[4] & 0b1 != 0 { 255 } else { 0 };
+        let min = descriptor[5] as u32 + offset;
+        (min > 0).then_some(min * 1000)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edid_range_limits() {
+        let mut edid = [0; 128];
+        assert_eq!(edid_min_refresh_rate(&edid), None);
+
+        // A monitor name descriptor followed by range limits of 48-144 Hz.
+        edid[72..77].copy_from_slice(&[0, 0, 0, 0xfc, 0]);
+        edid[90..97].copy_from_slice(&[0, 0, 0, 0xfd, 0, 48, 144]);
+        assert_eq!(edid_min_refresh_rate(&edid), Some(48_000));
+
+        edid[94] = 0b1;
+        assert_eq!(edid_min_refresh_rate(&edid), Some(303_000));
+        assert_eq!(edid_min_refresh_rate(&edid[..100]), None);
+    }
 }
@@ -6,7 +6,7 @@ use std::sync::{Arc, Mutex, MutexGuard};
 use std::{cmp, env};
 
 use tracing::{debug, info, warn};
-use x11rb::connection::Connection;
+use x11rb::connection::{Connection, RequestConnection};
 use x11rb::properties::{WmHints, WmSizeHints, WmSizeHintsSpecification};
 use x11rb::protocol::shape::SK;
 use x11rb::protocol::xfixes::{ConnectionExt, RegionWrapper};
@@ -18,7 +18,8 @@ use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
 use crate::event::{Event, InnerSizeWriter, WindowEvent};
 use crate::event_loop::AsyncRequestSerial;
-use crate::platform::x11::WindowType;
+use crate::ime::SurroundingText;
+use crate::platform::x11::{CompositorBypass, VisualSelector, WindowType};
 use crate::platform_impl::x11::atoms::*;
 use crate::platform_impl::x11::{
     xinput_fp1616_to_float, MonitorHandle as X11MonitorHandle, WakeSender, X11Error,
@@ -28,13 +29,15 @@ use crate::platform_impl::{
     PlatformIcon, VideoModeHandle as PlatformVideoModeHandle,
 };
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
-    WindowButtons, WindowLevel,
+    CapturePolicy, CursorGrabMode, FocusReason, ImePurpose, KeyboardHints, Region, ResizeDirection,
+    TextServicesConfig, Theme, TilingState, UserAttentionType, WindowAttributes, WindowButtons,
+    WindowCapabilities, WindowLevel, WindowVisibility,
 };
 
 use super::util::{self, SelectedCursor};
 use super::{
-    ffi, ActiveEventLoop, CookieResultExt, ImeRequest, ImeSender, VoidCookie, WindowId, XConnection,
+    ffi, ActiveEventLoop, CookieResultExt, DeviceId, ImeRequest, ImeSender, VoidCookie, WindowId,
+    XConnection,
 };
 
 #[derive(Debug)]
@@ -62,8 +65,15 @@ pub struct SharedState {
     pub base_size: Option<Size>,
     pub visibility: Visibility,
     pub has_focus: bool,
+    pub occluded: bool,
+    /// The visibility last reported with `WindowEvent::VisibilityChanged`.
+    pub last_visibility: WindowVisibility,
+    /// The tiling state last reported with `WindowEvent::TilingStateChanged`.
+    pub last_tiling_state: TilingState,
     // Use `Option` to not apply hittest logic when it was never requested.
     pub cursor_hittest: Option<bool>,
+    /// The regions set with `Window::set_hittest_regions`, replacing `cursor_hittest`.
+    pub hittest_regions: Option<Vec<Region>>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -101,11 +111,30 @@ impl SharedState {
             resize_increments: None,
             base_size: None,
             has_focus: false,
+            occluded: false,
+            last_visibility: WindowVisibility::Hidden,
+            last_tiling_state: TilingState::Floating,
             cursor_hittest: None,
+            hittest_regions: None,
         })
     }
 }
 
+/// Whether a true color visual matches the selector.
+fn visual_matches(visual: &xproto::Visualtype, depth: u8, selector: VisualSelector) -> bool {
+    if visual.class != xproto::VisualClass::TRUE_COLOR {
+        return false;
+    }
+
+    match selector {
+        VisualSelector::Depth(selected) => depth == selected,
+        // The bits of the depth not used by the colors are the alpha channel.
+        VisualSelector::Rgba => {
+            (visual.red_mask | visual.green_mask | visual.blue_mask).count_ones() < depth as u32
+        },
+    }
+}
+
 unsafe impl Send for UnownedWindow {}
 unsafe impl Sync for UnownedWindow {}
 
@@ -222,34 +251,42 @@ impl UnownedWindow {
             .flat_map(|depth| depth.visuals.iter().map(move |visual| (visual, depth.depth)));
 
         // creating
-        let (visualtype, depth, require_colormap) =
-            match window_attrs.platform_specific.x11.visual_id {
-                Some(vi) => {
-                    // Find this specific visual.
-                    let (visualtype, depth) =
-                        all_visuals.find(|(visual, _)| visual.visual_id == vi).ok_or_else(
-                            || os_error!(OsError::XError(X11Error::NoSuchVisual(vi).into())),
-                        )?;
-
-                    (Some(visualtype), depth, true)
-                },
-                None if window_attrs.transparent => {
-                    // Find a suitable visual, true color with 32 bits of depth.
-                    all_visuals
-                        .find_map(|(visual, depth)| {
-                            (depth == 32 && visual.class == xproto::VisualClass::TRUE_COLOR)
-                                .then_some((Some(visual), depth, true))
-                        })
-                        .unwrap_or_else(|| {
-                            debug!(
-                                "Could not set transparency, because XMatchVisualInfo returned \
-                                 zero for the required parameters"
-                            );
-                            (None as _, x11rb::COPY_FROM_PARENT as _, false)
-                        })
-                },
-                _ => (None, x11rb::COPY_FROM_PARENT as _, false),
-            };
+        let x11_attrs = &window_attrs.platform_specific.x11;
+        let (visualtype, depth, require_colormap) = match (x11_attrs.visual_id, x11_attrs.visual) {
+            (Some(vi), _) => {
+                // Find this specific visual.
+                let (visualtype, depth) = all_visuals
+                    .find(|(visual, _)| visual.visual_id == vi)
+                    .ok_or_else(|| os_error!(OsError::XError(X11Error::NoSuchVisual(vi).into())))?;
+
+                (Some(visualtype), depth, true)
+            },
+            (None, Some(selector)) => {
+                let (visualtype, depth) = all_visuals
+                    .find(|&(visual, depth)| visual_matches(visual, depth, selector))
+                    .ok_or_else(|| {
+                        os_error!(OsError::XError(X11Error::NoMatchingVisual(selector).into()))
+                    })?;
+
+                (Some(visualtype), depth, true)
+            },
+            (None, None) if window_attrs.transparent => {
+                // Find a suitable visual, true color with 32 bits of depth.
+                all_visuals
+                    .find_map(|(visual, depth)| {
+                        (depth == 32 && visual.class == xproto::VisualClass::TRUE_COLOR)
+                            .then_some((Some(visual), depth, true))
+                    })
+                    .unwrap_or_else(|| {
+                        debug!(
+                            "Could not set transparency, because XMatchVisualInfo returned zero \
+                             for the required parameters"
+                        );
+                        (None as _, x11rb::COPY_FROM_PARENT as _, false)
+                    })
+            },
+            _ => (None, x11rb::COPY_FROM_PARENT as _, false),
+        };
         let mut visual = visualtype.map_or(x11rb::COPY_FROM_PARENT, |v| v.visual_id);
 
         let window_attributes = {
@@ -414,6 +451,15 @@ impl UnownedWindow {
             leap!(window.set_window_types(window_attrs.platform_specific.x11.x11_window_types))
                 .ignore_error();
 
+            if window_attrs.platform_specific.x11.bypass_compositor
+                != CompositorBypass::NoPreference
+            {
+                leap!(window.set_bypass_compositor_inner(
+                    window_attrs.platform_specific.x11.bypass_compositor
+                ))
+                .ignore_error();
+            }
+
             // Set size hints.
             let mut min_inner_size =
                 window_attrs.min_inner_size.map(|size| size.to_physical::<u32>(scale_factor));
@@ -469,6 +515,10 @@ impl UnownedWindow {
                 leap!(window.set_icon_inner(icon.inner)).ignore_error();
             }
 
+            if !window_attrs.focusable {
+                leap!(window.set_focusable_inner(false)).ignore_error();
+            }
+
             // Opt into handling window close
             let result = xconn.xcb_connection().change_property(
                 xproto::PropMode::REPLACE,
@@ -623,6 +673,35 @@ impl UnownedWindow {
         )
     }
 
+    fn set_bypass_compositor_inner(
+        &self,
+        bypass: CompositorBypass,
+    ) -> Result<VoidCookie<'_>, X11Error> {
+        let hint_atom = self.xconn.atoms()[_NET_WM_BYPASS_COMPOSITOR];
+        let value: u32 = match bypass {
+            CompositorBypass::NoPreference => 0,
+            CompositorBypass::Bypass => 1,
+            CompositorBypass::Disable => 2,
+        };
+
+        self.xconn.change_property(
+            self.xwindow,
+            hint_atom,
+            xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+            xproto::PropMode::REPLACE,
+            &[value],
+        )
+    }
+
+    #[inline]
+    pub fn set_bypass_compositor(&self, bypass: CompositorBypass) {
+        self.set_bypass_compositor_inner(bypass)
+            .expect("Failed to change compositor bypass")
+            .ignore_error();
+
+        self.xconn.flush_requests().expect("Failed to change compositor bypass");
+    }
+
     pub fn set_theme_inner(&self, theme: Option<Theme>) -> Result<VoidCookie<'_>, X11Error> {
         let atoms = self.xconn.atoms();
         let hint_atom = atoms[_GTK_THEME_VARIANT];
@@ -1038,6 +1117,14 @@ impl UnownedWindow {
         self.xconn.set_motif_hints(self.xwindow, &hints)
     }
 
+    #[inline]
+    pub fn capabilities(&self) -> WindowCapabilities {
+        WindowCapabilities::all()
+            - WindowCapabilities::CAPTURE_POLICY
+            - WindowCapabilities::SHARE
+            - WindowCapabilities::TRAY
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         self.set_decorations_inner(decorations)
@@ -1269,8 +1356,18 @@ impl UnownedWindow {
             )
             .expect_then_ignore_error("Failed to call `xcb_configure_window`");
         self.xconn.flush_requests().expect("Failed to call XResizeWindow");
-        // cursor_hittest needs to be reapplied after each window resize.
-        if self.shared_state_lock().cursor_hittest.unwrap_or(false) {
+        self.reload_cursor_hittest();
+    }
+
+    /// Apply the input shape again, which needs to be done after each window resize.
+    pub(crate) fn reload_cursor_hittest(&self) {
+        let (hittest, regions) = {
+            let shared_state = self.shared_state_lock();
+            (shared_state.cursor_hittest, shared_state.hittest_regions.clone())
+        };
+        if let Some(regions) = regions {
+            let _ = self.set_hittest_regions(&regions);
+        } else if hittest.unwrap_or(false) {
             let _ = self.set_cursor_hittest(true);
         }
     }
@@ -1472,7 +1569,7 @@ impl UnownedWindow {
             Cursor::Custom(RootCustomCursor { inner: PlatformCustomCursor::X(cursor) }) => {
                 #[allow(clippy::mutex_atomic)]
                 if *self.cursor_visible.lock().unwrap() {
-                    self.xconn.set_custom_cursor(self.xwindow, &cursor);
+                    self.xconn.set_custom_cursor(self.xwindow, &cursor, self.scale_factor());
                 }
 
                 *self.selected_cursor.lock().unwrap() = SelectedCursor::Custom(cursor);
@@ -1484,6 +1581,60 @@ impl UnownedWindow {
         }
     }
 
+    /// Set the cursor for a single master pointer, with the X Input Extension.
+    pub fn set_cursor_for_device(
+        &self,
+        device_id: DeviceId,
+        cursor: Cursor,
+    ) -> Result<(), ExternalError> {
+        let result = match cursor {
+            Cursor::Icon(icon) => {
+                self.xconn.set_device_cursor_icon(self.xwindow, device_id.id, icon)
+            },
+            Cursor::Custom(RootCustomCursor { inner: PlatformCustomCursor::X(cursor) }) => self
+                .xconn
+                .set_device_custom_cursor(self.xwindow, device_id.id, &cursor, self.scale_factor()),
+            #[cfg(wayland_platform)]
+            Cursor::Custom(RootCustomCursor { inner: PlatformCustomCursor::Wayland(_) }) => {
+                return Err(ExternalError::NotSupported(NotSupportedError::new()));
+            },
+        };
+        result.map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))
+    }
+
+    #[inline]
+    pub fn set_pointer_capture(
+        &self,
+        device_id: DeviceId,
+        capture: bool,
+    ) -> Result<(), ExternalError> {
+        if !capture {
+            return self
+                .xconn
+                .ungrab_xinput_pointer(device_id.id)
+                .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))));
+        }
+
+        let status = self
+            .xconn
+            .grab_xinput_pointer(self.xwindow, device_id.id)
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
+        match status {
+            xproto::GrabStatus::SUCCESS => Ok(()),
+            xproto::GrabStatus::ALREADY_GRABBED => {
+                Err("Pointer could not be captured: already grabbed by another client")
+            },
+            xproto::GrabStatus::NOT_VIEWABLE => {
+                Err("Pointer could not be captured: window not viewable")
+            },
+            xproto::GrabStatus::FROZEN => {
+                Err("Pointer could not be captured: frozen by another client")
+            },
+            _ => Err("Pointer could not be captured"),
+        }
+        .map_err(|err| ExternalError::Os(os_error!(OsError::Misc(err))))
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
@@ -1575,7 +1726,7 @@ impl UnownedWindow {
         drop(visible_lock);
         match cursor {
             Some(SelectedCursor::Custom(cursor)) => {
-                self.xconn.set_custom_cursor(self.xwindow, &cursor);
+                self.xconn.set_custom_cursor(self.xwindow, &cursor, self.scale_factor());
             },
             Some(SelectedCursor::Named(cursor)) => {
                 self.xconn.set_cursor_icon(self.xwindow, Some(cursor));
@@ -1591,6 +1742,19 @@ impl UnownedWindow {
         self.shared_state_lock().last_monitor.scale_factor
     }
 
+    /// Set the custom cursor again after the scale factor changed, to pick the image fitting it.
+    pub(crate) fn reload_custom_cursor(&self) {
+        #[allow(clippy::mutex_atomic)]
+        if !*self.cursor_visible.lock().unwrap() {
+            return;
+        }
+
+        let cursor = self.selected_cursor.lock().unwrap().clone();
+        if let SelectedCursor::Custom(cursor) = cursor {
+            self.xconn.set_custom_cursor(self.xwindow, &cursor, self.scale_factor());
+        }
+    }
+
     pub fn set_cursor_position_physical(&self, x: i32, y: i32) -> Result<(), ExternalError> {
         {
             self.xconn
@@ -1623,13 +1787,41 @@ impl UnownedWindow {
                 height: size.height as u16,
             })
         }
-        let region = RegionWrapper::create_region(self.xconn.xcb_connection(), &rectangles)
+        self.set_input_shape(&rectangles)?;
+        let mut shared_state = self.shared_state_lock();
+        shared_state.cursor_hittest = Some(hittest);
+        shared_state.hittest_regions = None;
+        Ok(())
+    }
+
+    pub fn set_hittest_regions(&self, regions: &[Region]) -> Result<(), ExternalError> {
+        let scale_factor = self.scale_factor();
+        let rectangles: Vec<Rectangle> = regions
+            .iter()
+            .map(|region| {
+                let position: PhysicalPosition<i32> = region.position.to_physical(scale_factor);
+                let size: PhysicalSize<u32> = region.size.to_physical(scale_factor);
+                Rectangle {
+                    x: position.x.clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+                    y: position.y.clamp(i16::MIN.into(), i16::MAX.into()) as i16,
+                    width: size.width.min(u16::MAX.into()) as u16,
+                    height: size.height.min(u16::MAX.into()) as u16,
+                }
+            })
+            .collect();
+        self.set_input_shape(&rectangles)?;
+        self.shared_state_lock().hittest_regions = Some(regions.to_vec());
+        Ok(())
+    }
+
+    /// Set the part of the window which receives pointer input.
+    fn set_input_shape(&self, rectangles: &[Rectangle]) -> Result<(), ExternalError> {
+        let region = RegionWrapper::create_region(self.xconn.xcb_connection(), rectangles)
             .map_err(|_e| ExternalError::Ignored)?;
         self.xconn
             .xcb_connection()
             .xfixes_set_window_shape_region(self.xwindow, SK::INPUT, 0, 0, region.region())
             .map_err(|_e| ExternalError::Ignored)?;
-        self.shared_state_lock().cursor_hittest = Some(hittest);
         Ok(())
     }
 
@@ -1655,6 +1847,18 @@ impl UnownedWindow {
         })
     }
 
+    #[inline]
+    pub fn cursor_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        let pointer = self
+            .xconn
+            .query_pointer(self.xwindow, util::VIRTUAL_CORE_POINTER)
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
+        Ok(PhysicalPosition::new(
+            xinput_fp1616_to_float(pointer.win_x),
+            xinput_fp1616_to_float(pointer.win_y),
+        ))
+    }
+
     /// Initiates a drag operation while the left mouse button is pressed.
     fn drag_initiate(&self, action: isize) -> Result<(), ExternalError> {
         let pointer = self
@@ -1729,8 +1933,79 @@ impl UnownedWindow {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, _text: SurroundingText) {}
+
+    #[inline]
+    pub fn set_text_services(&self, _config: TextServicesConfig) {}
+
+    #[inline]
+    pub fn set_keyboard_hints(&self, _hints: KeyboardHints) {}
+
     #[inline]
     pub fn focus_window(&self) {
+        self.activate(x11rb::CURRENT_TIME);
+    }
+
+    #[inline]
+    pub fn focus_window_with(&self, reason: FocusReason) {
+        match reason {
+            FocusReason::UserInitiated => self.focus_window(),
+            FocusReason::Token(token) => {
+                // The token carries the time of the user input it was created for, which the
+                // window manager compares against the time of the latest input.
+                let timestamp = token
+                    ._token
+                    .rsplit_once("_TIME")
+                    .and_then(|(_, time)| time.parse().ok())
+                    .unwrap_or(x11rb::CURRENT_TIME);
+                if let Err(error) = self.xconn.remove_activation_token(self.xwindow, &token._token)
+                {
+                    warn!("Failed to use the activation token: {error}");
+                }
+                self.activate(timestamp);
+            },
+            FocusReason::Programmatic => {
+                if self.is_application_active() {
+                    self.activate(self.xconn.timestamp());
+                } else {
+                    self.request_user_attention(Some(UserAttentionType::Informational));
+                }
+            },
+        }
+    }
+
+    /// Whether the active window belongs to this process.
+    fn is_application_active(&self) -> bool {
+        let atoms = self.xconn.atoms();
+        let active_window = self
+            .xconn
+            .get_property::<xproto::Window>(
+                self.root,
+                atoms[_NET_ACTIVE_WINDOW],
+                xproto::Atom::from(xproto::AtomEnum::WINDOW),
+            )
+            .ok()
+            .and_then(|windows| windows.first().copied());
+        let Some(active_window) = active_window.filter(|&window| window != x11rb::NONE) else {
+            return false;
+        };
+
+        let pid = self
+            .xconn
+            .get_property::<util::Cardinal>(
+                active_window,
+                atoms[_NET_WM_PID],
+                xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+            )
+            .ok()
+            .and_then(|pids| pids.first().copied());
+        pid == Some(rustix::process::getpid().as_raw_nonzero().get() as util::Cardinal)
+    }
+
+    /// Ask the window manager to activate the window, `timestamp` being the time of the user
+    /// input which caused the request.
+    fn activate(&self, timestamp: xproto::Timestamp) {
         let atoms = self.xconn.atoms();
         let state_atom = atoms[WM_STATE];
         let state_type_atom = atoms[CARD32];
@@ -1756,7 +2031,7 @@ impl UnownedWindow {
                         xproto::EventMask::SUBSTRUCTURE_REDIRECT
                             | xproto::EventMask::SUBSTRUCTURE_NOTIFY,
                     ),
-                    [1, x11rb::CURRENT_TIME, 0, 0, 0],
+                    [1, timestamp, 0, 0, 0],
                 )
                 .expect_then_ignore_error("Failed to send client message");
             if let Err(e) = self.xconn.flush_requests() {
@@ -1768,6 +2043,25 @@ impl UnownedWindow {
         }
     }
 
+    #[inline]
+    pub fn set_focusable(&self, focusable: bool) {
+        self.set_focusable_inner(focusable).expect_then_ignore_error("Failed to set WM hints");
+        self.xconn.flush_requests().expect("Failed to set WM hints");
+    }
+
+    /// Set the input hint, which tells the window manager whether to give the window focus.
+    fn set_focusable_inner(&self, focusable: bool) -> Result<VoidCookie<'_>, X11Error> {
+        let mut wm_hints =
+            WmHints::get(self.xconn.xcb_connection(), self.xwindow as xproto::Window)?
+                .reply()
+                .ok()
+                .flatten()
+                .unwrap_or_default();
+
+        wm_hints.input = Some(focusable);
+        Ok(wm_hints.set(self.xconn.xcb_connection(), self.xwindow as xproto::Window)?)
+    }
+
     #[inline]
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let mut wm_hints =
@@ -1826,6 +2120,74 @@ impl UnownedWindow {
         // TODO timer
     }
 
+    pub fn present_rgba(
+        &self,
+        buffer: &[u8],
+        size: PhysicalSize<u32>,
+    ) -> Result<(), ExternalError> {
+        let depth = self
+            .xconn
+            .xcb_connection()
+            .get_geometry(self.xwindow)
+            .map_err(X11Error::from)
+            .and_then(|cookie| Ok(cookie.reply()?.depth))
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))?;
+        // True color visuals of depth 24 and 32 store pixels in 32 bits, in the byte order of the
+        // server, with the unused or alpha bits on top.
+        if depth != 24 && depth != 32 {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+
+        self.put_image(buffer, size, depth)
+            .map_err(|err| ExternalError::Os(os_error!(OsError::XError(err.into()))))
+    }
+
+    fn put_image(&self, buffer: &[u8], size: PhysicalSize<u32>, depth: u8) -> Result<(), X11Error> {
+        if size.width == 0 || size.height == 0 {
+            return Ok(());
+        }
+
+        let conn = self.xconn.xcb_connection();
+        let msb_first = conn.setup().image_byte_order == xproto::ImageOrder::MSB_FIRST;
+        let pixels: Vec<u8> = buffer
+            .chunks_exact(4)
+            .flat_map(|pixel| {
+                let [r, g, b] = [pixel[0], pixel[1], pixel[2]];
+                if msb_first {
+                    [0xff, r, g, b]
+                } else {
+                    [b, g, r, 0xff]
+                }
+            })
+            .collect();
+
+        let gc = conn.generate_id()?;
+        conn.create_gc(gc, self.xwindow, &xproto::CreateGCAux::new())?;
+
+        // The size of requests is limited, so the image is sent in bands of rows, leaving room
+        // for the header of `PutImage`.
+        let row_bytes = 4 * size.width as usize;
+        let rows_per_request = ((conn.maximum_request_bytes() - 24) / row_bytes).max(1);
+        for (band, rows) in pixels.chunks(rows_per_request * row_bytes).enumerate() {
+            conn.put_image(
+                xproto::ImageFormat::Z_PIXMAP,
+                self.xwindow,
+                gc,
+                size.width as u16,
+                (rows.len() / row_bytes) as u16,
+                0,
+                (band * rows_per_request) as i16,
+                0,
+                depth,
+                rows,
+            )?;
+        }
+
+        conn.free_gc(gc)?;
+        conn.flush()?;
+        Ok(())
+    }
+
     #[cfg(feature = "rwh_04")]
     #[inline]
     pub fn raw_window_handle_rwh_04(&self) -> rwh_04::RawWindowHandle {
@@ -1884,11 +2246,62 @@ impl UnownedWindow {
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn set_capture_policy(&self, _policy: CapturePolicy) {}
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         self.shared_state_lock().has_focus
     }
 
+    pub fn visibility(&self) -> WindowVisibility {
+        let minimized = self.is_minimized().unwrap_or(false);
+        let shared_state = self.shared_state_lock();
+        WindowVisibility::new(
+            shared_state.visibility == Visibility::Yes,
+            minimized,
+            shared_state.occluded,
+            shared_state.has_focus,
+        )
+    }
+
+    /// Returns the visibility if it changed since the last call.
+    pub(crate) fn update_visibility(&self) -> Option<WindowVisibility> {
+        let visibility = self.visibility();
+        let last_visibility = replace(&mut self.shared_state_lock().last_visibility, visibility);
+        (last_visibility != visibility).then_some(visibility)
+    }
+
+    pub fn tiling_state(&self) -> TilingState {
+        let atoms = self.xconn.atoms();
+        let state: Vec<xproto::Atom> = match self.xconn.get_property(
+            self.xwindow,
+            atoms[_NET_WM_STATE],
+            xproto::Atom::from(xproto::AtomEnum::ATOM),
+        ) {
+            Ok(state) => state,
+            Err(_) => return TilingState::Floating,
+        };
+        let has_state = |atom| state.contains(&atoms[atom]);
+
+        // There is no standard way to tell whether a window is tiled.
+        if has_state(_NET_WM_STATE_FULLSCREEN) {
+            TilingState::Fullscreen
+        } else if has_state(_NET_WM_STATE_MAXIMIZED_HORZ) && has_state(_NET_WM_STATE_MAXIMIZED_VERT)
+        {
+            TilingState::Maximized
+        } else {
+            TilingState::Floating
+        }
+    }
+
+    /// Returns the tiling state if it changed since the last call.
+    pub(crate) fn update_tiling_state(&self) -> Option<TilingState> {
+        let tiling_state = self.tiling_state();
+        let last_tiling_state =
+            replace(&mut self.shared_state_lock().last_tiling_state, tiling_state);
+        (last_tiling_state != tiling_state).then_some(tiling_state)
+    }
+
     pub fn title(&self) -> String {
         String::new()
     }
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::error::Error;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+use std::time::Duration;
 use std::{fmt, ptr};
 
 use crate::window::CursorIcon;
@@ -55,6 +56,9 @@ pub struct XConnection {
     /// Atom for the XSettings screen.
     xsettings_screen: Option<xproto::Atom>,
 
+    /// The double click settings read from XSettings, until they change.
+    pub(crate) double_click_settings: Mutex<Option<Option<(Duration, u32)>>>,
+
     pub latest_error: Mutex<Option<XError>>,
     pub cursor_cache: Mutex<HashMap<Option<CursorIcon>, ffi::Cursor>>,
 }
@@ -139,6 +143,7 @@ impl XConnection {
             cursor_cache: Default::default(),
             randr_version: (randr_version.major_version, randr_version.minor_version),
             xsettings_screen,
+            double_click_settings: Mutex::new(None),
         })
     }
 
@@ -208,6 +213,18 @@ impl XConnection {
         &self.xcb_connection().setup().roots[self.default_screen]
     }
 
+    /// Whether a compositing manager is running on the default screen.
+    pub fn is_composited(&self) -> bool {
+        let xcb = self.xcb_connection();
+        let name = format!("_NET_WM_CM_S{}", self.default_screen);
+        xcb.intern_atom(true, name.as_bytes())
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .filter(|reply| reply.atom != x11rb::NONE)
+            .and_then(|reply| xcb.get_selection_owner(reply.atom).ok()?.reply().ok())
+            .is_some_and(|reply| reply.owner != x11rb::NONE)
+    }
+
     /// Get the resource database.
     #[inline]
     pub fn database(&self) -> RwLockReadGuard<'_, resource_manager::Database> {
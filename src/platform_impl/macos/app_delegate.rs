@@ -1,25 +1,50 @@
 use std::cell::{Cell, RefCell};
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
 use std::mem;
+use std::os::fd::BorrowedFd;
+use std::path::PathBuf;
 use std::rc::Weak;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use block2::Block;
 use objc2::rc::Retained;
 use objc2::runtime::AnyObject;
-use objc2::{declare_class, msg_send_id, mutability, ClassType, DeclaredClass};
-use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSApplicationDelegate};
-use objc2_foundation::{MainThreadMarker, NSObject, NSObjectProtocol, NSSize};
+use objc2::{declare_class, msg_send_id, mutability, sel, ClassType, DeclaredClass};
+use objc2_app_kit::{
+    NSApplication, NSApplicationActivationPolicy, NSApplicationDelegate,
+    NSApplicationTerminateReply, NSScreenColorSpaceDidChangeNotification, NSWorkspace,
+    NSWorkspaceScreensDidSleepNotification, NSWorkspaceScreensDidWakeNotification,
+};
+use objc2_foundation::{
+    ns_string, MainThreadMarker, NSActivityOptions, NSArray, NSCurrentLocaleDidChangeNotification,
+    NSDistributedNotificationCenter, NSNotificationCenter, NSObject, NSObjectProtocol,
+    NSProcessInfo, NSSize, NSURL,
+};
+use objc2_user_notifications::{
+    UNNotification, UNNotificationPresentationOptions, UNNotificationResponse,
+    UNUserNotificationCenter, UNUserNotificationCenterDelegate,
+};
 
 use super::event_handler::EventHandler;
 use super::event_loop::{stop_app_immediately, ActiveEventLoop, PanicInfo};
+use super::fd::FdSource;
 use super::observer::{EventLoopWaker, RunLoop};
 use super::window::WinitWindow;
-use super::{menu, WindowId, DEVICE_ID};
-use crate::dpi::PhysicalSize;
-use crate::event::{DeviceEvent, Event, InnerSizeWriter, StartCause, WindowEvent};
+use super::{menu, notification, WindowId, DEVICE_ID};
+use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::error::ExternalError;
+use crate::event::{
+    CloseResponse, DeviceEvent, DropOperation, DropResponse, Event, InnerSizeWriter, ResizeFrame,
+    StartCause, Url, WindowEvent,
+};
 use crate::event_loop::{ActiveEventLoop as RootActiveEventLoop, ControlFlow};
-use crate::window::WindowId as RootWindowId;
+use crate::platform::unix::Interest;
+use crate::window::{PerformanceHint, WindowId as RootWindowId};
+
+/// How long a live resize waits for the application to present a synchronized resize.
+const SYNCHRONIZED_RESIZE_TIMEOUT: Duration = Duration::from_millis(100);
 
 #[derive(Debug)]
 struct Policy(NSApplicationActivationPolicy);
@@ -46,12 +71,24 @@ pub(super) struct State {
     is_running: Cell<bool>,
     /// Whether the user has requested the event loop to exit.
     exit: Cell<bool>,
+    /// Whether an exit future passed to `exit_with` is pending.
+    exit_pending: Cell<bool>,
+    /// Whether `applicationShouldTerminate:` is waiting on the pending exit.
+    terminate_later: Cell<bool>,
     control_flow: Cell<ControlFlow>,
     waker: RefCell<EventLoopWaker>,
+    /// Activity keeping timer coalescing disabled while a fine timer resolution is requested.
+    timer_activity: RefCell<Option<Retained<NSObject>>>,
+    /// Activity for the `PerformanceHint` of the application.
+    performance_activity: RefCell<Option<Retained<NSObject>>>,
     start_time: Cell<Option<Instant>>,
     wait_timeout: Cell<Option<Instant>>,
     pending_events: RefCell<VecDeque<QueuedEvent>>,
     pending_redraw: RefCell<Vec<WindowId>>,
+    /// URLs and files opened before the application finished launching.
+    pending_opened: RefCell<Vec<Event<HandlePendingUserEvents>>>,
+    /// File descriptors registered by the application, by token.
+    fd_sources: RefCell<HashMap<u64, FdSource>>,
     // NOTE: This is strongly referenced by our `NSWindowDelegate` and our `NSView` subclass, and
     // as such should be careful to not add fields that, in turn, strongly reference those.
 }
@@ -118,13 +155,150 @@ declare_class!(
             }
         }
 
+        #[method(applicationShouldTerminate:)]
+        fn should_terminate(&self, _sender: &NSApplication) -> NSApplicationTerminateReply {
+            trace_scope!("applicationShouldTerminate:");
+            let cancelled = Arc::new(AtomicBool::new(false));
+            if self.ivars().event_handler.ready() && !self.ivars().exit_pending.get() {
+                self.handle_event(Event::CloseRequestedByOS {
+                    respond: CloseResponse::new(Arc::downgrade(&cancelled)),
+                });
+            }
+
+            if cancelled.load(Ordering::Relaxed) {
+                NSApplicationTerminateReply::NSTerminateCancel
+            } else if self.ivars().exit_pending.get() {
+                // Replied to in `set_exit_pending` once the application is done cleaning up.
+                self.ivars().terminate_later.set(true);
+                NSApplicationTerminateReply::NSTerminateLater
+            } else {
+                NSApplicationTerminateReply::NSTerminateNow
+            }
+        }
+
+        #[method(applicationDidChangeScreenParameters:)]
+        fn did_change_screen_parameters(&self, _: Option<&AnyObject>) {
+            trace_scope!("applicationDidChangeScreenParameters:");
+            self.handle_system_event(Event::WorkAreaChanged);
+            self.handle_system_event(Event::MonitorsChanged);
+        }
+
+        // Implementing this replaces `application:openFiles:`, files are opened as file URLs.
+        #[method(application:openURLs:)]
+        fn open_urls(&self, _application: &NSApplication, urls: &NSArray<NSURL>) {
+            trace_scope!("application:openURLs:");
+            let mut events = Vec::new();
+            let mut paths = Vec::new();
+            for url in urls.iter() {
+                if unsafe { url.isFileURL() } {
+                    paths.extend(unsafe { url.path() }.map(|path| PathBuf::from(path.to_string())));
+                } else if let Some(url) =
+                    unsafe { url.absoluteString() }.and_then(|url| Url::parse(&url.to_string()))
+                {
+                    events.push(Event::UrlOpened(url));
+                }
+            }
+            if !paths.is_empty() {
+                events.push(Event::FilesOpened(paths));
+            }
+
+            // Applications launched to open something are asked to before they finished
+            // launching.
+            if self.ivars().event_handler.ready() {
+                for event in events {
+                    self.handle_event(event);
+                }
+            } else {
+                self.ivars().pending_opened.borrow_mut().extend(events);
+            }
+        }
+
         #[method(applicationWillTerminate:)]
         fn will_terminate(&self, _sender: Option<&AnyObject>) {
             trace_scope!("applicationWillTerminate:");
             // TODO: Notify every window that it will be destroyed, like done in iOS?
+            self.handle_event(Event::Terminating);
             self.internal_exit();
         }
     }
+
+    unsafe impl UNUserNotificationCenterDelegate for ApplicationDelegate {
+        #[method(userNotificationCenter:willPresentNotification:withCompletionHandler:)]
+        fn will_present_notification(
+            &self,
+            _center: &UNUserNotificationCenter,
+            notification: &UNNotification,
+            completion_handler: &Block<dyn Fn(UNNotificationPresentationOptions)>,
+        ) {
+            trace_scope!("userNotificationCenter:willPresentNotification:withCompletionHandler:");
+            // Notifications are only shown while the application is in the foreground if
+            // requested explicitly.
+            let mut options = UNNotificationPresentationOptions::UNNotificationPresentationOptionBanner
+                | UNNotificationPresentationOptions::UNNotificationPresentationOptionList;
+            if unsafe { notification.request().content().sound() }.is_some() {
+                options |= UNNotificationPresentationOptions::UNNotificationPresentationOptionSound;
+            }
+            completion_handler.call((options,));
+        }
+
+        #[method(userNotificationCenter:didReceiveNotificationResponse:withCompletionHandler:)]
+        fn did_receive_notification_response(
+            &self,
+            _center: &UNUserNotificationCenter,
+            response: &UNNotificationResponse,
+            completion_handler: &Block<dyn Fn()>,
+        ) {
+            trace_scope!(
+                "userNotificationCenter:didReceiveNotificationResponse:withCompletionHandler:"
+            );
+            if let Some((id, event)) = notification::event_from_response(response) {
+                if self.ivars().event_handler.ready() {
+                    self.handle_event(Event::Notification { id, event });
+                }
+            }
+            completion_handler.call(());
+        }
+    }
+
+    unsafe impl ApplicationDelegate {
+        #[method(screenIsLocked:)]
+        fn screen_is_locked(&self, _: Option<&AnyObject>) {
+            trace_scope!("screenIsLocked:");
+            self.handle_system_event(Event::SessionLocked);
+        }
+
+        #[method(screenIsUnlocked:)]
+        fn screen_is_unlocked(&self, _: Option<&AnyObject>) {
+            trace_scope!("screenIsUnlocked:");
+            self.handle_system_event(Event::SessionUnlocked);
+        }
+
+        #[method(screensDidSleep:)]
+        fn screens_did_sleep(&self, _: Option<&AnyObject>) {
+            trace_scope!("screensDidSleep:");
+            self.handle_system_event(Event::DisplaySleep);
+        }
+
+        #[method(screensDidWake:)]
+        fn screens_did_wake(&self, _: Option<&AnyObject>) {
+            trace_scope!("screensDidWake:");
+            self.handle_system_event(Event::DisplayWake);
+        }
+
+        #[method(currentLocaleDidChange:)]
+        fn current_locale_did_change(&self, _: Option<&AnyObject>) {
+            trace_scope!("currentLocaleDidChange:");
+            if let Some(locale) = super::event_loop::locale() {
+                self.handle_system_event(Event::LocaleChanged(locale));
+            }
+        }
+
+        #[method(screenColorSpaceDidChange:)]
+        fn screen_color_space_did_change(&self, _: Option<&AnyObject>) {
+            trace_scope!("screenColorSpaceDidChange:");
+            self.handle_system_event(Event::ColorProfileChanged);
+        }
+    }
 );
 
 impl ApplicationDelegate {
@@ -140,7 +314,47 @@ impl ApplicationDelegate {
             activate_ignoring_other_apps,
             ..Default::default()
         });
-        unsafe { msg_send_id![super(this), init] }
+        let this: Retained<Self> = unsafe { msg_send_id![super(this), init] };
+
+        // Locking the session is only reported through distributed notifications.
+        let distributed_center = unsafe { NSDistributedNotificationCenter::defaultCenter() };
+        for (selector, name) in [
+            (sel!(screenIsLocked:), ns_string!("com.apple.screenIsLocked")),
+            (sel!(screenIsUnlocked:), ns_string!("com.apple.screenIsUnlocked")),
+        ] {
+            unsafe {
+                distributed_center.addObserver_selector_name_object(
+                    &this,
+                    selector,
+                    Some(name),
+                    None,
+                )
+            };
+        }
+
+        let workspace_center = unsafe { NSWorkspace::sharedWorkspace().notificationCenter() };
+        for (selector, name) in unsafe {
+            [
+                (sel!(screensDidSleep:), NSWorkspaceScreensDidSleepNotification),
+                (sel!(screensDidWake:), NSWorkspaceScreensDidWakeNotification),
+            ]
+        } {
+            unsafe {
+                workspace_center.addObserver_selector_name_object(&this, selector, Some(name), None)
+            };
+        }
+
+        let center = unsafe { NSNotificationCenter::defaultCenter() };
+        for (selector, name) in unsafe {
+            [
+                (sel!(currentLocaleDidChange:), NSCurrentLocaleDidChangeNotification),
+                (sel!(screenColorSpaceDidChange:), NSScreenColorSpaceDidChangeNotification),
+            ]
+        } {
+            unsafe { center.addObserver_selector_name_object(&this, selector, Some(name), None) };
+        }
+
+        this
     }
 
     pub fn get(mtm: MainThreadMarker) -> Retained<Self> {
@@ -222,6 +436,17 @@ impl ApplicationDelegate {
         self.ivars().exit.set(false)
     }
 
+    pub fn set_exit_pending(&self, pending: bool) {
+        self.ivars().exit_pending.set(pending);
+        if !pending && self.ivars().terminate_later.replace(false) {
+            // The event loop exits on its own once the exit future completed, so the termination
+            // is cancelled instead of letting `NSApplication` terminate the process while the
+            // event handler is running.
+            let app = NSApplication::sharedApplication(MainThreadMarker::from(self));
+            unsafe { app.replyToApplicationShouldTerminate(false) };
+        }
+    }
+
     pub fn exiting(&self) -> bool {
         self.ivars().exit.get()
     }
@@ -234,6 +459,49 @@ impl ApplicationDelegate {
         self.ivars().control_flow.get()
     }
 
+    pub fn set_timer_resolution(&self, resolution: Duration) {
+        // The system coalesces timers to save power, which makes them fire up to several
+        // milliseconds late. A latency critical activity opts the whole process out of that.
+        let fine = resolution < Duration::from_millis(15);
+        let process_info = NSProcessInfo::processInfo();
+        let mut activity = self.ivars().timer_activity.borrow_mut();
+        match (fine, activity.take()) {
+            (true, None) => {
+                let options = NSActivityOptions::NSActivityUserInitiatedAllowingIdleSystemSleep
+                    | NSActivityOptions::NSActivityLatencyCritical;
+                let reason = ns_string!("Precise ControlFlow::WaitUntil timers");
+                *activity =
+                    Some(unsafe { process_info.beginActivityWithOptions_reason(options, reason) });
+            },
+            (false, Some(old)) => unsafe { process_info.endActivity(&old) },
+            (_, old) => *activity = old,
+        }
+
+        let tolerance = if fine { resolution } else { Duration::ZERO };
+        self.ivars().waker.borrow_mut().set_tolerance(tolerance);
+    }
+
+    pub fn set_performance_hint(&self, hint: PerformanceHint) {
+        let process_info = NSProcessInfo::processInfo();
+        let mut activity = self.ivars().performance_activity.borrow_mut();
+        if let Some(old) = activity.take() {
+            unsafe { process_info.endActivity(&old) };
+        }
+
+        let options = match hint {
+            PerformanceHint::Default => return,
+            PerformanceHint::Game => {
+                NSActivityOptions::NSActivityUserInitiatedAllowingIdleSystemSleep
+            },
+            PerformanceHint::Realtime => {
+                NSActivityOptions::NSActivityUserInitiatedAllowingIdleSystemSleep
+                    | NSActivityOptions::NSActivityLatencyCritical
+            },
+        };
+        let reason = ns_string!("PerformanceHint of the application");
+        *activity = Some(unsafe { process_info.beginActivityWithOptions_reason(options, reason) });
+    }
+
     pub fn queue_window_event(&self, window_id: WindowId, event: WindowEvent) {
         self.ivars()
             .pending_events
@@ -241,6 +509,29 @@ impl ApplicationDelegate {
             .push_back(QueuedEvent::WindowEvent(window_id, event));
     }
 
+    /// Queue an event that isn't associated with the content of a window, like
+    /// `Event::SurfaceDestroyed`.
+    pub fn queue_event(&self, event: Event<HandlePendingUserEvents>) {
+        self.ivars().pending_events.borrow_mut().push_back(QueuedEvent::Event(event));
+    }
+
+    pub fn register_fd(
+        &self,
+        fd: BorrowedFd<'_>,
+        interest: Interest,
+        token: u64,
+    ) -> Result<(), ExternalError> {
+        // Replace the previous file descriptor first, so that it stops being watched.
+        self.unregister_fd(token);
+        let source = FdSource::new(fd, interest, token)?;
+        self.ivars().fd_sources.borrow_mut().insert(token, source);
+        Ok(())
+    }
+
+    pub fn unregister_fd(&self, token: u64) {
+        self.ivars().fd_sources.borrow_mut().remove(&token);
+    }
+
     pub fn queue_device_event(&self, event: DeviceEvent) {
         self.ivars().pending_events.borrow_mut().push_back(QueuedEvent::DeviceEvent(event));
     }
@@ -278,6 +569,46 @@ impl ApplicationDelegate {
         }
     }
 
+    /// Emit `Resized` right away and wait for the application to present the new size.
+    ///
+    /// Returns `false` if the event can't be handled right now.
+    pub fn handle_synchronized_resize(&self, window_id: WindowId, size: PhysicalSize<u32>) -> bool {
+        if !self.ivars().event_handler.ready() {
+            return false;
+        }
+
+        let (frame, waiter) = ResizeFrame::new(None);
+        self.handle_event(Event::WindowEvent {
+            window_id: RootWindowId(window_id),
+            event: WindowEvent::Resized { size, frame: Some(frame) },
+        });
+
+        if !waiter.is_presented() {
+            self.handle_redraw(window_id);
+            if !waiter.wait(SYNCHRONIZED_RESIZE_TIMEOUT) {
+                tracing::debug!("synchronized resize timed out");
+            }
+        }
+
+        true
+    }
+
+    /// Ask the application what a drop at the position would do.
+    pub fn handle_drag_hover(
+        &self,
+        window_id: WindowId,
+        position: PhysicalPosition<f64>,
+    ) -> DropOperation {
+        let operation = Arc::new(Mutex::new(DropOperation::Copy));
+        self.handle_system_event(Event::DragHover {
+            window_id: RootWindowId(window_id),
+            position,
+            respond: DropResponse::new(Arc::downgrade(&operation)),
+        });
+        let operation = *operation.lock().unwrap();
+        operation
+    }
+
     pub fn queue_redraw(&self, window_id: WindowId) {
         let mut pending_redraw = self.ivars().pending_redraw.borrow_mut();
         if !pending_redraw.contains(&window_id) {
@@ -290,12 +621,24 @@ impl ApplicationDelegate {
         self.ivars().event_handler.handle_event(event, &ActiveEventLoop::new_root(self.retain()))
     }
 
+    /// Handle an event reported by the system, which can happen while no event loop is running.
+    fn handle_system_event(&self, event: Event<HandlePendingUserEvents>) {
+        if self.ivars().event_handler.ready() {
+            self.handle_event(event);
+        }
+    }
+
     /// dispatch `NewEvents(Init)` + `Resumed`
     pub fn dispatch_init_events(&self) {
         self.handle_event(Event::NewEvents(StartCause::Init));
         // NB: For consistency all platforms must emit a 'resumed' event even though macOS
         // applications don't themselves have a formal suspend/resume lifecycle.
         self.handle_event(Event::Resumed);
+
+        let events = mem::take(&mut *self.ivars().pending_opened.borrow_mut());
+        for event in events {
+            self.handle_event(event);
+        }
     }
 
     // Called by RunLoopObserver after finishing waiting for new events
@@ -350,6 +693,7 @@ impl ApplicationDelegate {
         let events = mem::take(&mut *self.ivars().pending_events.borrow_mut());
         for event in events {
             match event {
+                QueuedEvent::Event(event) => self.handle_event(event),
                 QueuedEvent::WindowEvent(window_id, event) => {
                     self.handle_event(Event::WindowEvent {
                         window_id: RootWindowId(window_id),
@@ -383,7 +727,7 @@ impl ApplicationDelegate {
 
                     let resized_event = Event::WindowEvent {
                         window_id: RootWindowId(window.id()),
-                        event: WindowEvent::Resized(physical_size),
+                        event: WindowEvent::Resized { size: physical_size, frame: None },
                     };
                     self.handle_event(resized_event);
                 },
@@ -422,6 +766,7 @@ impl ApplicationDelegate {
 
 #[derive(Debug)]
 pub(crate) enum QueuedEvent {
+    Event(Event<HandlePendingUserEvents>),
     WindowEvent(WindowId, WindowEvent),
     DeviceEvent(DeviceEvent),
     ScaleFactorChanged {
@@ -24,11 +24,33 @@ unsafe impl Sync for CustomCursor {}
 
 impl CustomCursor {
     pub(crate) fn new(cursor: OnlyCursorImageSource) -> CustomCursor {
-        Self(cursor_from_image(&cursor.0))
+        Self(cursor_from_images(&cursor.0))
     }
 }
 
-pub(crate) fn cursor_from_image(cursor: &CursorImage) -> Retained<NSCursor> {
+/// Create a cursor showing the image fitting the backing scale factor best.
+pub(crate) fn cursor_from_images(images: &[CursorImage]) -> Retained<NSCursor> {
+    // The size and the hotspot of the cursor are in points, which are taken from the image with
+    // the smallest scale factor.
+    let base = &images[0];
+    let size =
+        NSSize::new(base.width as f64 / base.scale_factor, base.height as f64 / base.scale_factor);
+    let hotspot = NSPoint::new(
+        base.hotspot_x as f64 / base.scale_factor,
+        base.hotspot_y as f64 / base.scale_factor,
+    );
+
+    let image = unsafe { NSImage::initWithSize(NSImage::alloc(), size) };
+    for cursor in images {
+        let bitmap = bitmap_from_image(cursor);
+        unsafe { bitmap.setSize(size) };
+        unsafe { image.addRepresentation(&bitmap) };
+    }
+
+    NSCursor::initWithImage_hotSpot(NSCursor::alloc(), &image, hotspot)
+}
+
+fn bitmap_from_image(cursor: &CursorImage) -> Retained<NSBitmapImageRep> {
     let width = cursor.width;
     let height = cursor.height;
 
@@ -50,14 +72,7 @@ pub(crate) fn cursor_from_image(cursor: &CursorImage) -> Retained<NSCursor> {
     let bitmap_data = unsafe { slice::from_raw_parts_mut(bitmap.bitmapData(), cursor.rgba.len()) };
     bitmap_data.copy_from_slice(&cursor.rgba);
 
-    let image = unsafe {
-        NSImage::initWithSize(NSImage::alloc(), NSSize::new(width.into(), height.into()))
-    };
-    unsafe { image.addRepresentation(&bitmap) };
-
-    let hotspot = NSPoint::new(cursor.hotspot_x as f64, cursor.hotspot_y as f64);
-
-    NSCursor::initWithImage_hotSpot(NSCursor::alloc(), &image, hotspot)
+    bitmap
 }
 
 pub(crate) fn default_cursor() -> Retained<NSCursor> {
@@ -0,0 +1,56 @@
+use std::cell::Cell;
+
+use block2::RcBlock;
+use objc2_app_kit::{NSAlert, NSAlertFirstButtonReturn, NSAlertStyle};
+use objc2_foundation::{MainThreadMarker, NSRunLoop, NSString};
+
+use crate::dialog::{DialogButton, DialogSender, MessageDialog, MessageLevel};
+
+pub(crate) fn show_message_dialog(
+    mtm: MainThreadMarker,
+    dialog: MessageDialog,
+    sender: DialogSender<DialogButton>,
+) {
+    // `runModal` runs the run loop until the alert is closed. Starting it from a block instead of
+    // the event handler lets the observers of the event loop keep dispatching events meanwhile.
+    let pending = Cell::new(Some((dialog, sender)));
+    let block = RcBlock::new(move || {
+        let Some((dialog, sender)) = pending.take() else {
+            return;
+        };
+
+        let alert = unsafe { NSAlert::new(mtm) };
+        unsafe {
+            alert.setMessageText(&NSString::from_str(&dialog.title));
+            alert.setInformativeText(&NSString::from_str(&dialog.message));
+            alert.setAlertStyle(match dialog.level {
+                MessageLevel::Info => NSAlertStyle::Informational,
+                MessageLevel::Warning => NSAlertStyle::Warning,
+                MessageLevel::Error => NSAlertStyle::Critical,
+            });
+        }
+
+        let buttons = dialog.buttons.buttons();
+        for (index, &button) in buttons.iter().enumerate() {
+            let ns_button =
+                unsafe { alert.addButtonWithTitle(&NSString::from_str(button.label())) };
+            // The first button responds to the Return key, and only buttons titled "Cancel"
+            // respond to the Escape key by default.
+            if index > 0
+                && button == dialog.buttons.dismiss_button()
+                && button != DialogButton::Cancel
+            {
+                unsafe { ns_button.setKeyEquivalent(&NSString::from_str("\u{1b}")) };
+            }
+        }
+
+        let response = unsafe { alert.runModal() };
+        let button = usize::try_from(response - unsafe { NSAlertFirstButtonReturn })
+            .ok()
+            .and_then(|index| buttons.get(index).copied())
+            .unwrap_or(dialog.buttons.dismiss_button());
+        sender.send(button);
+    });
+
+    unsafe { NSRunLoop::mainRunLoop().performBlock(&block) };
+}
@@ -2,6 +2,7 @@ use std::any::Any;
 use std::cell::Cell;
 use std::collections::VecDeque;
 use std::marker::PhantomData;
+use std::os::fd::BorrowedFd;
 use std::os::raw::c_void;
 use std::panic::{catch_unwind, resume_unwind, RefUnwindSafe, UnwindSafe};
 use std::ptr;
@@ -17,23 +18,31 @@ use core_foundation::runloop::{
 use objc2::rc::{autoreleasepool, Retained};
 use objc2::runtime::ProtocolObject;
 use objc2::{msg_send_id, ClassType};
-use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSWindow};
-use objc2_foundation::{MainThreadMarker, NSObjectProtocol};
+use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSEvent, NSWindow};
+use objc2_foundation::{MainThreadMarker, NSLocale, NSObjectProtocol, NSRect, NSSize};
 
 use super::app::WinitApplication;
 use super::app_delegate::{ApplicationDelegate, HandlePendingUserEvents};
 use super::event::dummy_event;
-use super::monitor::{self, MonitorHandle};
+use super::monitor::{self, MonitorHandle, VideoModeHandle};
+use super::notification::Notification;
 use super::observer::setup_control_flow_observers;
+use super::{ffi, OsError};
 use crate::application::ApplicationHandler;
-use crate::error::EventLoopError;
-use crate::event::Event;
+use crate::dialog::{DialogButton, DialogSender, MessageDialog};
+use crate::dpi::{LogicalPosition, PhysicalPosition};
+use crate::error::{EventLoopError, ExternalError, NotSupportedError};
+use crate::event::{Event, Seat, Signal};
 use crate::event_loop::{
     ActiveEventLoop as RootWindowTarget, ControlFlow, DeviceEvents, EventLoopClosed,
 };
+use crate::locale::LanguageTag;
+use crate::notification::{NotificationAttributes, NotificationId};
 use crate::platform::macos::ActivationPolicy;
 use crate::platform::pump_events::PumpStatus;
+use crate::platform::unix::Interest;
 use crate::platform_impl::platform::cursor::CustomCursor;
+use crate::session::{SessionCapabilities, SessionInfo, SessionKind};
 use crate::window::{CustomCursor as RootCustomCursor, CustomCursorSource};
 
 #[derive(Default)]
@@ -98,6 +107,64 @@ impl ActiveEventLoop {
         Some(monitor)
     }
 
+    #[inline]
+    pub fn locale(&self) -> Option<LanguageTag> {
+        locale()
+    }
+
+    #[inline]
+    pub fn seats(&self) -> Vec<Seat> {
+        Vec::new()
+    }
+
+    #[inline]
+    pub fn session_info(&self) -> SessionInfo {
+        SessionInfo::new(SessionKind::Desktop, false, SessionCapabilities::all())
+    }
+
+    #[inline]
+    pub fn pointer_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        let point = unsafe { NSEvent::mouseLocation() };
+        let point = monitor::flip_window_screen_coordinates(NSRect::new(point, NSSize::ZERO));
+        let scale_factor =
+            monitor::monitor_containing(point).map_or(1.0, |monitor| monitor.scale_factor());
+        Ok(LogicalPosition::new(point.x, point.y).to_physical(scale_factor))
+    }
+
+    pub fn set_restart_after_crash(
+        &self,
+        _args: Option<Vec<std::ffi::OsString>>,
+    ) -> Result<(), ExternalError> {
+        // Relaunching after a crash is left to the user, who is asked by the crash reporter.
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_video_mode(
+        &self,
+        mode: &VideoModeHandle,
+    ) -> Result<Box<dyn FnOnce()>, ExternalError> {
+        let display = mode.monitor.native_identifier();
+        let original = monitor::current_display_mode(display);
+        // The system restores the original mode once the application exits.
+        let status =
+            unsafe { ffi::CGDisplaySetDisplayMode(display, mode.native_mode.0, ptr::null()) };
+        if status != ffi::kCGErrorSuccess {
+            return Err(ExternalError::Os(os_error!(OsError::CGError(status))));
+        }
+        Ok(Box::new(move || {
+            let status = unsafe { ffi::CGDisplaySetDisplayMode(display, original.0, ptr::null()) };
+            if status != ffi::kCGErrorSuccess {
+                tracing::warn!("failed to restore the video mode: CGError {status}");
+            }
+        }))
+    }
+
+    #[inline]
+    pub fn double_click_settings(&self) -> Option<(Duration, u32)> {
+        let interval = unsafe { NSEvent::doubleClickInterval() };
+        Some((Duration::from_secs_f64(interval), 4))
+    }
+
     #[inline]
     pub fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
@@ -123,6 +190,31 @@ impl ActiveEventLoop {
         self.delegate.control_flow()
     }
 
+    pub(crate) fn set_timer_resolution(&self, resolution: Duration) {
+        self.delegate.set_timer_resolution(resolution)
+    }
+
+    pub(crate) fn set_exit_pending(&self, pending: bool) {
+        self.delegate.set_exit_pending(pending)
+    }
+
+    pub(crate) fn show_notification(
+        &self,
+        id: NotificationId,
+        attributes: NotificationAttributes,
+    ) -> Result<Notification, ExternalError> {
+        Notification::new(&self.delegate, id, attributes)
+    }
+
+    pub(crate) fn show_message_dialog(
+        &self,
+        dialog: MessageDialog,
+        sender: DialogSender<DialogButton>,
+    ) -> Result<(), ExternalError> {
+        super::dialog::show_message_dialog(self.mtm, dialog, sender);
+        Ok(())
+    }
+
     pub(crate) fn exit(&self) {
         self.delegate.exit()
     }
@@ -135,6 +227,19 @@ impl ActiveEventLoop {
         self.delegate.exiting()
     }
 
+    pub(crate) fn register_fd(
+        &self,
+        fd: BorrowedFd<'_>,
+        interest: Interest,
+        token: u64,
+    ) -> Result<(), ExternalError> {
+        self.delegate.register_fd(fd, interest, token)
+    }
+
+    pub(crate) fn unregister_fd(&self, token: u64) {
+        self.delegate.unregister_fd(token)
+    }
+
     pub(crate) fn owned_display_handle(&self) -> OwnedDisplayHandle {
         OwnedDisplayHandle
     }
@@ -173,11 +278,40 @@ fn map_user_event<T: 'static, A: ApplicationHandler<T>>(
                 app.user_event(window_target, event);
             }
         },
+        Event::SurfaceCreated(window_id) => app.surface_created(window_target, window_id),
+        Event::SurfaceDestroyed(window_id) => app.surface_destroyed(window_target, window_id),
         Event::Suspended => app.suspended(window_target),
         Event::Resumed => app.resumed(window_target),
         Event::AboutToWait => app.about_to_wait(window_target),
+        Event::Idle { deadline } => app.idle(window_target, deadline),
+        Event::CloseRequestedByOS { respond } => app.close_requested_by_os(window_target, respond),
+        Event::Notification { id, event } => app.notification_event(window_target, id, event),
+        Event::Terminating => app.terminating(window_target),
         Event::LoopExiting => app.exiting(window_target),
         Event::MemoryWarning => app.memory_warning(window_target),
+        Event::SessionLocked => app.session_locked(window_target),
+        Event::SessionUnlocked => app.session_unlocked(window_target),
+        Event::DisplaySleep => app.display_sleep(window_target),
+        Event::DisplayWake => app.display_wake(window_target),
+        Event::LocaleChanged(locale) => app.locale_changed(window_target, locale),
+        Event::WorkAreaChanged => app.work_area_changed(window_target),
+        Event::Navigation(pop_state) => app.navigation(window_target, pop_state),
+        Event::ColorProfileChanged => app.color_profile_changed(window_target),
+        Event::ActivationRequest { args, activation_token } => {
+            app.activation_request(window_target, args, activation_token)
+        },
+        Event::UrlOpened(url) => app.url_opened(window_target, url),
+        Event::FilesOpened(paths) => app.files_opened(window_target, paths),
+        Event::DragHover { window_id, position, respond } => {
+            let _ = respond.respond(app.drag_hover(window_target, window_id, position));
+        },
+        Event::TrayActivated(window_id) => app.tray_activated(window_target, window_id),
+        Event::Signal(signal) => app.signal(window_target, signal),
+        Event::FdReady(token) => app.fd_ready(window_target, token),
+        Event::HandleSignaled(token) => app.handle_signaled(window_target, token),
+        Event::MonitorsChanged => app.monitors_changed(window_target),
+        Event::AudioInterrupted => app.audio_interrupted(window_target),
+        Event::AudioResumed => app.audio_resumed(window_target),
     }
 }
 
@@ -201,11 +335,12 @@ pub struct EventLoop<T: 'static> {
     panic_info: Rc<PanicInfo>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) activation_policy: ActivationPolicy,
     pub(crate) default_menu: bool,
     pub(crate) activate_ignoring_other_apps: bool,
+    pub(crate) signals: Vec<Signal>,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
@@ -214,6 +349,7 @@ impl Default for PlatformSpecificEventLoopAttributes {
             activation_policy: Default::default(), // Regular
             default_menu: true,
             activate_ignoring_other_apps: true,
+            signals: Vec::new(),
         }
     }
 }
@@ -253,6 +389,7 @@ impl<T> EventLoop<T> {
 
         let panic_info: Rc<PanicInfo> = Default::default();
         setup_control_flow_observers(Rc::downgrade(&panic_info));
+        super::signal::receive(&attributes.signals);
 
         let (sender, receiver) = mpsc::channel();
         Ok(EventLoop {
@@ -414,6 +551,12 @@ impl OwnedDisplayHandle {
     }
 }
 
+/// The language the user prefers the most.
+pub(super) fn locale() -> Option<LanguageTag> {
+    let languages = unsafe { NSLocale::preferredLanguages() };
+    languages.iter().find_map(|language| LanguageTag::parse(&language.to_string()))
+}
+
 pub(super) fn stop_app_immediately(app: &NSApplication) {
     autoreleasepool(|_| {
         app.stop(None);
@@ -511,4 +654,22 @@ impl<T> EventLoopProxy<T> {
         }
         Ok(())
     }
+
+    pub fn waker(&self) -> EventLoopWaker {
+        // With its own run loop source, which stays valid after this proxy is dropped.
+        EventLoopWaker(EventLoopProxy::new(mpsc::channel().0))
+    }
+}
+
+/// Wakes up the `EventLoop` from any thread, without a message.
+#[derive(Clone)]
+pub struct EventLoopWaker(EventLoopProxy<()>);
+
+impl EventLoopWaker {
+    pub fn wake(&self) {
+        unsafe {
+            CFRunLoopSourceSignal(self.0.source);
+            CFRunLoopWakeUp(CFRunLoopGetMain());
+        }
+    }
 }
@@ -0,0 +1,86 @@
+//! Watching file descriptors of the application in the main run loop.
+
+use std::ffi::c_void;
+use std::fmt;
+use std::os::fd::{AsRawFd, BorrowedFd};
+
+use core_foundation::base::CFOptionFlags;
+use core_foundation::filedescriptor::{
+    kCFFileDescriptorReadCallBack, kCFFileDescriptorWriteCallBack, CFFileDescriptor,
+    CFFileDescriptorContext, CFFileDescriptorEnableCallBacks, CFFileDescriptorRef,
+};
+use core_foundation::runloop::{kCFRunLoopCommonModes, CFRunLoop, CFRunLoopSource};
+use objc2_foundation::MainThreadMarker;
+
+use super::app_delegate::ApplicationDelegate;
+use super::OsError;
+use crate::error::ExternalError;
+use crate::event::Event;
+use crate::platform::unix::Interest;
+
+/// A file descriptor borrowed from the application until it's unregistered, and removed from the
+/// main run loop when dropped.
+pub(super) struct FdSource {
+    fd: CFFileDescriptor,
+    source: CFRunLoopSource,
+}
+
+impl FdSource {
+    pub(super) fn new(
+        fd: BorrowedFd<'_>,
+        interest: Interest,
+        token: u64,
+    ) -> Result<Self, ExternalError> {
+        // The token is passed to the callout directly, pointers are 64-bit on macOS.
+        let context = CFFileDescriptorContext {
+            version: 0,
+            info: token as usize as *mut c_void,
+            retain: None,
+            release: None,
+            copyDescription: None,
+        };
+        let error = || {
+            ExternalError::Os(os_error!(OsError::CreationError(
+                "failed to register the file descriptor"
+            )))
+        };
+        let fd = CFFileDescriptor::new(fd.as_raw_fd(), false, callout, Some(&context))
+            .ok_or_else(error)?;
+
+        let mut callbacks = 0;
+        if interest.readable {
+            callbacks |= kCFFileDescriptorReadCallBack;
+        }
+        if interest.writable {
+            callbacks |= kCFFileDescriptorWriteCallBack;
+        }
+        fd.enable_callbacks(callbacks);
+
+        let source = fd.to_run_loop_source(0).ok_or_else(error)?;
+        CFRunLoop::get_main().add_source(&source, unsafe { kCFRunLoopCommonModes });
+
+        Ok(Self { fd, source })
+    }
+}
+
+impl Drop for FdSource {
+    fn drop(&mut self) {
+        CFRunLoop::get_main().remove_source(&self.source, unsafe { kCFRunLoopCommonModes });
+        self.fd.invalidate();
+    }
+}
+
+impl fmt::Debug for FdSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FdSource").finish_non_exhaustive()
+    }
+}
+
+extern "C" fn callout(fd: CFFileDescriptorRef, callbacks: CFOptionFlags, info: *mut c_void) {
+    let mtm = MainThreadMarker::new().unwrap();
+    ApplicationDelegate::get(mtm).queue_event(Event::FdReady(info as usize as u64));
+
+    // The callbacks that were called are disabled, so they are enabled again to be called on the
+    // next iteration of the run loop while the file descriptor stays ready.
+    unsafe { CFFileDescriptorEnableCallBacks(fd, callbacks) };
+}
@@ -5,11 +5,13 @@
 use std::ffi::c_void;
 
 use core_foundation::array::CFArrayRef;
+use core_foundation::data::CFDataRef;
 use core_foundation::dictionary::CFDictionaryRef;
 use core_foundation::string::CFStringRef;
 use core_foundation::uuid::CFUUIDRef;
 use core_graphics::base::CGError;
 use core_graphics::display::{CGDirectDisplayID, CGDisplayConfigRef};
+use core_graphics::sys::CGColorSpaceRef;
 use objc2::ffi::NSInteger;
 use objc2::runtime::AnyObject;
 
@@ -114,6 +116,8 @@ extern "C" {
     pub fn CGDisplayModeCopyPixelEncoding(mode: CGDisplayModeRef) -> CFStringRef;
     pub fn CGDisplayModeRetain(mode: CGDisplayModeRef);
     pub fn CGDisplayModeRelease(mode: CGDisplayModeRef);
+    pub fn CGDisplayCopyColorSpace(display: CGDirectDisplayID) -> CGColorSpaceRef;
+    pub fn CGColorSpaceCopyICCData(space: CGColorSpaceRef) -> CFDataRef;
 
     // Wildly used private APIs; Apple uses them for their Terminal.app.
     pub fn CGSMainConnectionID() -> *mut AnyObject;
@@ -196,6 +200,9 @@ extern "C" {
 
     pub fn LMGetKbdType() -> u8;
 
+    pub fn EnableSecureEventInput() -> OSStatus;
+    pub fn DisableSecureEventInput() -> OSStatus;
+
     #[allow(non_snake_case)]
     pub fn UCKeyTranslate(
         keyLayoutPtr: *const UCKeyboardLayout,
@@ -4,13 +4,17 @@ mod util;
 mod app;
 mod app_delegate;
 mod cursor;
+mod dialog;
 mod event;
 mod event_handler;
 mod event_loop;
+mod fd;
 mod ffi;
 mod menu;
 mod monitor;
+mod notification;
 mod observer;
+mod signal;
 mod view;
 mod window;
 mod window_delegate;
@@ -19,16 +23,17 @@ use std::fmt;
 
 pub(crate) use self::event::{physicalkey_to_scancode, scancode_to_physicalkey, KeyEventExtra};
 pub(crate) use self::event_loop::{
-    ActiveEventLoop, EventLoop, EventLoopProxy, OwnedDisplayHandle,
+    ActiveEventLoop, EventLoop, EventLoopProxy, EventLoopWaker, OwnedDisplayHandle,
     PlatformSpecificEventLoopAttributes,
 };
 pub(crate) use self::monitor::{MonitorHandle, VideoModeHandle};
+pub(crate) use self::notification::Notification as PlatformNotification;
 pub(crate) use self::window::WindowId;
 pub(crate) use self::window_delegate::PlatformSpecificWindowAttributes;
-use crate::event::DeviceId as RootDeviceId;
+use crate::event::{DeviceId as RootDeviceId, SeatId};
 
 pub(crate) use self::cursor::CustomCursor as PlatformCustomCursor;
-pub(crate) use self::window::Window;
+pub(crate) use self::window::{MainThreadWindow, Window};
 pub(crate) use crate::cursor::OnlyCursorImageSource as PlatformCustomCursorSource;
 pub(crate) use crate::icon::NoIcon as PlatformIcon;
 pub(crate) use crate::platform_impl::Fullscreen;
@@ -40,6 +45,10 @@ impl DeviceId {
     pub const unsafe fn dummy() -> Self {
         DeviceId
     }
+
+    pub fn seat_id(&self) -> Option<SeatId> {
+        None
+    }
 }
 
 // Constant device ID; to be removed when if backend is updated to report real device IDs.
@@ -2,20 +2,26 @@
 
 use std::collections::VecDeque;
 use std::fmt;
+use std::ops::RangeInclusive;
 
 use core_foundation::array::{CFArrayGetCount, CFArrayGetValueAtIndex};
 use core_foundation::base::{CFRelease, TCFType};
+use core_foundation::data::CFData;
 use core_foundation::string::CFString;
 use core_graphics::display::{
     CGDirectDisplayID, CGDisplay, CGDisplayBounds, CGDisplayCopyDisplayMode,
 };
 use objc2::rc::Retained;
 use objc2::runtime::AnyObject;
+use objc2::sel;
 use objc2_app_kit::NSScreen;
-use objc2_foundation::{ns_string, run_on_main, MainThreadMarker, NSNumber, NSPoint, NSRect};
+use objc2_foundation::{
+    ns_string, run_on_main, MainThreadMarker, NSNumber, NSObjectProtocol, NSPoint, NSRect,
+};
 
 use super::ffi;
-use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
+use crate::monitor::Rect;
 
 #[derive(Clone)]
 pub struct VideoModeHandle {
@@ -92,6 +98,14 @@ impl VideoModeHandle {
         self.refresh_rate_millihertz
     }
 
+    pub fn refresh_rate_range(&self) -> RangeInclusive<u32> {
+        let rate = self.refresh_rate_millihertz;
+        match self.monitor.min_refresh_rate_millihertz() {
+            Some(min) => min.min(rate)..=rate,
+            None => rate..=rate,
+        }
+    }
+
     pub fn monitor(&self) -> MonitorHandle {
         self.monitor.clone()
     }
@@ -202,6 +216,36 @@ impl MonitorHandle {
         position.to_physical(self.scale_factor())
     }
 
+    pub fn work_area(&self) -> Rect {
+        run_on_main(|mtm| {
+            let Some(screen) = self.ns_screen(mtm) else {
+                return Rect::new(self.position(), self.size());
+            };
+            // The visible frame is in the flipped coordinates of `NSScreen`.
+            let frame = screen.visibleFrame();
+            let scale_factor = screen.backingScaleFactor() as f64;
+            let origin = flip_window_screen_coordinates(frame);
+            Rect::new(
+                LogicalPosition::new(origin.x, origin.y).to_physical(scale_factor),
+                LogicalSize::new(frame.size.width, frame.size.height).to_physical(scale_factor),
+            )
+        })
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        let color_space = unsafe { ffi::CGDisplayCopyColorSpace(self.0) };
+        if color_space.is_null() {
+            return None;
+        }
+        let data = unsafe { ffi::CGColorSpaceCopyICCData(color_space) };
+        unsafe { CFRelease(color_space as *const _) };
+        if data.is_null() {
+            return None;
+        }
+        let data = unsafe { CFData::wrap_under_create_rule(data) };
+        Some(data.bytes().to_vec())
+    }
+
     pub fn scale_factor(&self) -> f64 {
         run_on_main(|mtm| {
             match self.ns_screen(mtm) {
@@ -213,7 +257,7 @@ impl MonitorHandle {
 
     pub fn refresh_rate_millihertz(&self) -> Option<u32> {
         unsafe {
-            let current_display_mode = NativeDisplayMode(CGDisplayCopyDisplayMode(self.0) as _);
+            let current_display_mode = current_display_mode(self.0);
             let refresh_rate = ffi::CGDisplayModeGetRefreshRate(current_display_mode.0);
             if refresh_rate > 0.0 {
                 return Some((refresh_rate * 1000.0).round() as u32);
@@ -237,6 +281,29 @@ impl MonitorHandle {
         }
     }
 
+    pub fn supports_variable_refresh(&self) -> bool {
+        self.min_refresh_rate_millihertz().is_some()
+    }
+
+    pub fn is_mirrored(&self) -> bool {
+        CGDisplay::new(self.0).is_in_mirror_set()
+    }
+
+    /// The lowest rate of screens with adaptive sync, like ProMotion displays, in mHz.
+    fn min_refresh_rate_millihertz(&self) -> Option<u32> {
+        run_on_main(|mtm| {
+            let screen = self.ns_screen(mtm)?;
+            // Available since macOS 12.
+            if !screen.respondsToSelector(sel!(maximumRefreshInterval)) {
+                return None;
+            }
+            let min_interval = unsafe { screen.minimumRefreshInterval() };
+            let max_interval = unsafe { screen.maximumRefreshInterval() };
+            (min_interval > 0.0 && max_interval > min_interval)
+                .then(|| (1000.0 / max_interval).round() as u32)
+        })
+    }
+
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         let refresh_rate_millihertz = self.refresh_rate_millihertz().unwrap_or(0);
         let monitor = self.clone();
@@ -307,6 +374,10 @@ impl MonitorHandle {
     }
 }
 
+pub(crate) fn current_display_mode(display: CGDirectDisplayID) -> NativeDisplayMode {
+    NativeDisplayMode(unsafe { CGDisplayCopyDisplayMode(display) } as _)
+}
+
 pub(crate) fn get_display_id(screen: &NSScreen) -> u32 {
     let key = ns_string!("NSScreenNumber");
 
@@ -329,6 +400,15 @@ pub(crate) fn get_display_id(screen: &NSScreen) -> u32 {
     })
 }
 
+/// Returns the monitor containing the point, in core graphics screen coordinates.
+pub(crate) fn monitor_containing(point: NSPoint) -> Option<MonitorHandle> {
+    available_monitors().into_iter().find(|monitor| {
+        let bounds = unsafe { CGDisplayBounds(monitor.native_identifier()) };
+        (bounds.origin.x..bounds.origin.x + bounds.size.width).contains(&point.x)
+            && (bounds.origin.y..bounds.origin.y + bounds.size.height).contains(&point.y)
+    })
+}
+
 /// Core graphics screen coordinates are relative to the top-left corner of
 /// the so-called "main" display, with y increasing downwards - which is
 /// exactly what we want in Winit.
@@ -0,0 +1,122 @@
+use std::sync::Once;
+
+use block2::RcBlock;
+use objc2::runtime::{Bool, ProtocolObject};
+use objc2_foundation::{NSArray, NSBundle, NSError, NSSet, NSString};
+use objc2_user_notifications::{
+    UNAuthorizationOptions, UNMutableNotificationContent, UNNotificationCategory,
+    UNNotificationCategoryOptions, UNNotificationDefaultActionIdentifier,
+    UNNotificationDismissActionIdentifier, UNNotificationRequest, UNNotificationResponse,
+    UNNotificationSound, UNUserNotificationCenter,
+};
+
+use super::app_delegate::ApplicationDelegate;
+use crate::error::{ExternalError, NotSupportedError};
+use crate::notification::{NotificationAttributes, NotificationEvent, NotificationId};
+
+const IDENTIFIER_PREFIX: &str = "winit-notification-";
+
+/// The category of every notification, used to be informed when one is dismissed.
+const CATEGORY_IDENTIFIER: &str = "winit-notification";
+
+#[derive(Debug)]
+pub struct Notification {
+    identifier: String,
+}
+
+impl Notification {
+    pub(super) fn new(
+        delegate: &ApplicationDelegate,
+        id: NotificationId,
+        attributes: NotificationAttributes,
+    ) -> Result<Self, ExternalError> {
+        // `UNUserNotificationCenter` throws an exception when used outside of an application
+        // bundle.
+        if unsafe { NSBundle::mainBundle().bundleIdentifier() }.is_none() {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        }
+
+        let center = unsafe { UNUserNotificationCenter::currentNotificationCenter() };
+
+        static REGISTER: Once = Once::new();
+        REGISTER.call_once(|| unsafe {
+            center.setDelegate(Some(ProtocolObject::from_ref(delegate)));
+
+            let category =
+                UNNotificationCategory::categoryWithIdentifier_actions_intentIdentifiers_options(
+                    &NSString::from_str(CATEGORY_IDENTIFIER),
+                    &NSArray::new(),
+                    &NSArray::new(),
+                    UNNotificationCategoryOptions::UNNotificationCategoryOptionCustomDismissAction,
+                );
+            center.setNotificationCategories(&NSSet::from_id_slice(&[category]));
+        });
+
+        let identifier = format!("{IDENTIFIER_PREFIX}{}", id.into_raw());
+
+        let content = unsafe { UNMutableNotificationContent::new() };
+        unsafe {
+            content.setTitle(&NSString::from_str(&attributes.title));
+            content.setBody(&NSString::from_str(&attributes.body));
+            content.setCategoryIdentifier(&NSString::from_str(CATEGORY_IDENTIFIER));
+            if !attributes.silent {
+                content.setSound(Some(&UNNotificationSound::defaultSound()));
+            }
+        }
+        let request = unsafe {
+            UNNotificationRequest::requestWithIdentifier_content_trigger(
+                &NSString::from_str(&identifier),
+                &content,
+                None,
+            )
+        };
+
+        // The system only prompts the user the first time authorization is requested.
+        let options = UNAuthorizationOptions::UNAuthorizationOptionAlert
+            | UNAuthorizationOptions::UNAuthorizationOptionSound;
+        let completion_handler = RcBlock::new(move |granted: Bool, _: *mut NSError| {
+            if !granted.as_bool() {
+                tracing::warn!("permission to show notifications wasn't granted");
+                return;
+            }
+
+            unsafe {
+                UNUserNotificationCenter::currentNotificationCenter()
+                    .addNotificationRequest_withCompletionHandler(&request, None)
+            };
+        });
+        unsafe {
+            center.requestAuthorizationWithOptions_completionHandler(options, &completion_handler)
+        };
+
+        Ok(Self { identifier })
+    }
+
+    pub fn close(&self) {
+        let identifiers = NSArray::from_id_slice(&[NSString::from_str(&self.identifier)]);
+        unsafe {
+            let center = UNUserNotificationCenter::currentNotificationCenter();
+            center.removePendingNotificationRequestsWithIdentifiers(&identifiers);
+            center.removeDeliveredNotificationsWithIdentifiers(&identifiers);
+        }
+    }
+}
+
+/// Translates the response of the user to a notification shown by winit.
+pub(super) fn event_from_response(
+    response: &UNNotificationResponse,
+) -> Option<(NotificationId, NotificationEvent)> {
+    let identifier = unsafe { response.notification().request().identifier() }.to_string();
+    let id = identifier.strip_prefix(IDENTIFIER_PREFIX)?.parse().ok()?;
+
+    let action = unsafe { response.actionIdentifier() };
+    let event = if &*action == unsafe { UNNotificationDefaultActionIdentifier } {
+        NotificationEvent::Clicked
+    } else if &*action == unsafe { UNNotificationDismissActionIdentifier } {
+        NotificationEvent::Dismissed
+    } else {
+        return None;
+    };
+
+    Some((NotificationId::from_raw(id), event))
+}
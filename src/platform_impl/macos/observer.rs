@@ -2,7 +2,7 @@ use std::ffi::c_void;
 use std::panic::{AssertUnwindSafe, UnwindSafe};
 use std::ptr;
 use std::rc::Weak;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use core_foundation::base::{CFIndex, CFOptionFlags, CFRelease};
 use core_foundation::date::CFAbsoluteTimeGetCurrent;
@@ -11,7 +11,7 @@ use core_foundation::runloop::{
     CFRunLoopActivity, CFRunLoopAddObserver, CFRunLoopAddTimer, CFRunLoopGetMain,
     CFRunLoopObserverCallBack, CFRunLoopObserverContext, CFRunLoopObserverCreate,
     CFRunLoopObserverRef, CFRunLoopRef, CFRunLoopTimerCreate, CFRunLoopTimerInvalidate,
-    CFRunLoopTimerRef, CFRunLoopTimerSetNextFireDate, CFRunLoopWakeUp,
+    CFRunLoopTimerRef, CFRunLoopTimerSetNextFireDate, CFRunLoopTimerSetTolerance, CFRunLoopWakeUp,
 };
 use objc2_foundation::MainThreadMarker;
 
@@ -188,6 +188,11 @@ impl Default for EventLoopWaker {
 }
 
 impl EventLoopWaker {
+    /// Set how late the timer is allowed to fire, which lets the system coalesce it with others.
+    pub fn set_tolerance(&mut self, tolerance: Duration) {
+        unsafe { CFRunLoopTimerSetTolerance(self.timer, tolerance.as_secs_f64()) }
+    }
+
     pub fn stop(&mut self) {
         if self.next_fire_date.is_some() {
             self.next_fire_date = None;
@@ -0,0 +1,128 @@
+//! Receiving signals in the event loop through a `kqueue`.
+//!
+//! Unlike `signalfd`, `EVFILT_SIGNAL` also reports signals that are ignored, so the signals are
+//! ignored by the process, and the `kqueue` is added to the main run loop as a file descriptor.
+
+use std::ffi::c_void;
+use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd};
+use std::{io, mem, ptr};
+
+use core_foundation::base::CFOptionFlags;
+use core_foundation::filedescriptor::{
+    kCFFileDescriptorReadCallBack, CFFileDescriptor, CFFileDescriptorEnableCallBacks,
+    CFFileDescriptorGetNativeDescriptor, CFFileDescriptorRef,
+};
+use core_foundation::runloop::{kCFRunLoopCommonModes, CFRunLoop};
+use libc::c_int;
+use objc2_foundation::MainThreadMarker;
+
+use super::app_delegate::ApplicationDelegate;
+use crate::event::{Event, Signal};
+
+fn to_raw(signal: Signal) -> c_int {
+    match signal {
+        Signal::Interrupt => libc::SIGINT,
+        Signal::Terminate => libc::SIGTERM,
+        Signal::Hangup => libc::SIGHUP,
+        Signal::Quit => libc::SIGQUIT,
+        Signal::User1 => libc::SIGUSR1,
+        Signal::User2 => libc::SIGUSR2,
+    }
+}
+
+fn from_raw(signal: c_int) -> Option<Signal> {
+    Some(match signal {
+        libc::SIGINT => Signal::Interrupt,
+        libc::SIGTERM => Signal::Terminate,
+        libc::SIGHUP => Signal::Hangup,
+        libc::SIGQUIT => Signal::Quit,
+        libc::SIGUSR1 => Signal::User1,
+        libc::SIGUSR2 => Signal::User2,
+        _ => return None,
+    })
+}
+
+/// Take over the signals which weren't handled by the application yet, returning `None` if there
+/// are none.
+fn kqueue(signals: &[Signal]) -> io::Result<Option<OwnedFd>> {
+    let fd = unsafe { libc::kqueue() };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let kqueue = unsafe { OwnedFd::from_raw_fd(fd) };
+
+    let mut any = false;
+    for &signal in signals {
+        let signal = to_raw(signal);
+        let mut action: libc::sigaction = unsafe { mem::zeroed() };
+        if unsafe { libc::sigaction(signal, ptr::null(), &mut action) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if action.sa_sigaction != libc::SIG_DFL {
+            continue;
+        }
+
+        let mut change: libc::kevent = unsafe { mem::zeroed() };
+        change.ident = signal as libc::uintptr_t;
+        change.filter = libc::EVFILT_SIGNAL;
+        change.flags = libc::EV_ADD;
+        let result = unsafe {
+            libc::kevent(kqueue.as_raw_fd(), &change, 1, ptr::null_mut(), 0, ptr::null())
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        unsafe { libc::signal(signal, libc::SIG_IGN) };
+        any = true;
+    }
+
+    Ok(any.then_some(kqueue))
+}
+
+extern "C" fn callout(fd: CFFileDescriptorRef, _: CFOptionFlags, _: *mut c_void) {
+    let mtm = MainThreadMarker::new().unwrap();
+    let kqueue = unsafe { CFFileDescriptorGetNativeDescriptor(fd) };
+
+    let delegate = ApplicationDelegate::get(mtm);
+    let timeout = libc::timespec { tv_sec: 0, tv_nsec: 0 };
+    loop {
+        let mut event: libc::kevent = unsafe { mem::zeroed() };
+        let count = unsafe { libc::kevent(kqueue, ptr::null(), 0, &mut event, 1, &timeout) };
+        if count != 1 {
+            break;
+        }
+        if let Some(signal) = from_raw(event.ident as c_int) {
+            delegate.queue_event(Event::Signal(signal));
+        }
+    }
+
+    // Callbacks are disabled after each call.
+    unsafe { CFFileDescriptorEnableCallBacks(fd, kCFFileDescriptorReadCallBack) };
+}
+
+/// Receive `signals` in the main run loop, queueing them as events on the application delegate.
+///
+/// The `kqueue` lives as long as the process, like the application delegate.
+pub(crate) fn receive(signals: &[Signal]) {
+    let kqueue = match kqueue(signals) {
+        Ok(Some(kqueue)) => kqueue,
+        Ok(None) => return,
+        Err(err) => {
+            tracing::warn!("failed to receive signals in the event loop: {err}");
+            return;
+        },
+    };
+
+    let Some(fd) = CFFileDescriptor::new(kqueue.into_raw_fd(), true, callout, None) else {
+        tracing::warn!("failed to receive signals in the event loop");
+        return;
+    };
+    fd.enable_callbacks(kCFFileDescriptorReadCallBack);
+    let Some(source) = fd.to_run_loop_source(0) else {
+        tracing::warn!("failed to receive signals in the event loop");
+        return;
+    };
+    CFRunLoop::get_main().add_source(&source, unsafe { kCFRunLoopCommonModes });
+    mem::forget(fd);
+}
@@ -8,13 +8,16 @@ use objc2::runtime::{AnyObject, Sel};
 use objc2::{declare_class, msg_send_id, mutability, sel, ClassType, DeclaredClass};
 use objc2_app_kit::{
     NSApplication, NSCursor, NSEvent, NSEventPhase, NSResponder, NSTextInputClient,
-    NSTrackingRectTag, NSView, NSViewFrameDidChangeNotification,
+    NSTrackingRectTag, NSUnderlineStyle, NSUnderlineStyleAttributeName, NSView,
+    NSViewFrameDidChangeNotification,
 };
 use objc2_foundation::{
-    MainThreadMarker, NSArray, NSAttributedString, NSAttributedStringKey, NSCopying,
-    NSMutableAttributedString, NSNotFound, NSNotificationCenter, NSObject, NSObjectProtocol,
-    NSPoint, NSRange, NSRect, NSSize, NSString, NSUInteger,
+    MainThreadMarker, NSArray, NSAttributedString, NSAttributedStringKey, NSCopying, NSInteger,
+    NSMutableAttributedString, NSNotFound, NSNotificationCenter, NSNumber, NSObject,
+    NSObjectProtocol, NSPoint, NSRange, NSRect, NSRunLoop, NSRunLoopCommonModes, NSSize, NSString,
+    NSUInteger,
 };
+use objc2_quartz_core::{CADisplayLink, CAFrameRateRange, CAMetalLayer};
 
 use super::app_delegate::ApplicationDelegate;
 use super::cursor::{default_cursor, invisible_cursor};
@@ -24,13 +27,16 @@ use super::event::{
 };
 use super::window::WinitWindow;
 use super::DEVICE_ID;
-use crate::dpi::{LogicalPosition, LogicalSize};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition};
 use crate::event::{
-    DeviceEvent, ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta, TouchPhase,
-    WindowEvent,
+    DeviceEvent, ElementState, Ime, Modifiers, MouseButton, MouseScrollDelta, PreeditSpan,
+    PreeditUnderline, TouchPhase, WindowEvent,
 };
+use crate::ime::SurroundingText;
 use crate::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey};
-use crate::platform::macos::OptionAsAlt;
+use crate::platform::macos::{CAMetalLayerConfig, OptionAsAlt};
+use crate::velocity::VelocityTracker;
+use crate::window::{FrameRateRange, TextServicesConfig};
 
 #[derive(Debug)]
 struct CursorState {
@@ -132,6 +138,9 @@ pub struct ViewState {
     forward_key_to_app: Cell<bool>,
 
     marked_text: RefCell<Retained<NSMutableAttributedString>>,
+    /// The text around the cursor, which is what the ranges of `NSTextInputClient` refer to.
+    surrounding_text: RefCell<Option<SurroundingText>>,
+    text_services: Cell<TextServicesConfig>,
     accepts_first_mouse: bool,
 
     // Weak reference because the window keeps a strong reference to the view
@@ -139,6 +148,19 @@ pub struct ViewState {
 
     /// The state of the `Option` as `Alt`.
     option_as_alt: Cell<OptionAsAlt>,
+
+    /// Whether live resizes wait for the application to present the new size.
+    synchronized_resize: bool,
+
+    /// The distance scrolled by the current trackpad gesture, for [`WindowEvent::PanEnded`].
+    scroll_offset: Cell<PhysicalPosition<f64>>,
+    scroll_velocity: RefCell<VelocityTracker>,
+
+    /// The layer created by `WindowExtMacOS::set_layer_config`.
+    metal_layer: RefCell<Option<Retained<CAMetalLayer>>>,
+
+    /// The display link asking for the frame rates of `Window::set_preferred_frame_rate_range`.
+    display_link: RefCell<Option<Retained<CADisplayLink>>>,
 }
 
 declare_class!(
@@ -177,6 +199,12 @@ declare_class!(
             self.ivars().tracking_rect.set(Some(tracking_rect));
         }
 
+        #[method(displayLinkFired:)]
+        fn display_link_fired(&self, _display_link: &CADisplayLink) {
+            // Nothing to do, waking up the event loop on every frame of the display is enough for
+            // the redraws requested in the meantime to be emitted.
+        }
+
         #[method(frameDidChange:)]
         fn frame_did_change(&self, _event: &NSEvent) {
             trace_scope!("frameDidChange:");
@@ -190,13 +218,23 @@ declare_class!(
             };
             assert_ne!(tracking_rect, 0, "failed adding tracking rect");
             self.ivars().tracking_rect.set(Some(tracking_rect));
+            self.update_metal_layer();
 
             // Emit resize event here rather than from windowDidResize because:
             // 1. When a new window is created as a tab, the frame size may change without a window resize occurring.
             // 2. Even when a window resize does occur on a new tabbed window, it contains the wrong size (includes tab height).
             let logical_size = LogicalSize::new(rect.size.width as f64, rect.size.height as f64);
             let size = logical_size.to_physical::<u32>(self.scale_factor());
-            self.queue_event(WindowEvent::Resized(size));
+
+            // Outside of a live resize, the window server doesn't show the frame before the
+            // application had the chance to redraw anyhow.
+            if self.ivars().synchronized_resize
+                && unsafe { self.inLiveResize() }
+                && self.ivars().app_delegate.handle_synchronized_resize(self.window().id(), size)
+            {
+                return;
+            }
+            self.queue_event(WindowEvent::Resized { size, frame: None });
         }
 
         #[method(drawRect:)]
@@ -238,6 +276,56 @@ declare_class!(
                 self.addCursorRect_cursor(bounds, &invisible_cursor());
             }
         }
+
+        // The optional methods of `NSTextInputTraits`, see `Window::set_text_services`.
+
+        #[method(autocorrectionType)]
+        fn autocorrection_type(&self) -> NSInteger {
+            trace_scope!("autocorrectionType");
+            text_input_trait(self.ivars().text_services.get().autocorrect)
+        }
+
+        #[method(textReplacementType)]
+        fn text_replacement_type(&self) -> NSInteger {
+            trace_scope!("textReplacementType");
+            text_input_trait(self.ivars().text_services.get().autocorrect)
+        }
+
+        #[method(spellCheckingType)]
+        fn spell_checking_type(&self) -> NSInteger {
+            trace_scope!("spellCheckingType");
+            text_input_trait(self.ivars().text_services.get().spellcheck)
+        }
+
+        #[method(grammarCheckingType)]
+        fn grammar_checking_type(&self) -> NSInteger {
+            trace_scope!("grammarCheckingType");
+            text_input_trait(self.ivars().text_services.get().spellcheck)
+        }
+
+        #[method(smartQuotesType)]
+        fn smart_quotes_type(&self) -> NSInteger {
+            trace_scope!("smartQuotesType");
+            text_input_trait(self.ivars().text_services.get().smart_punctuation)
+        }
+
+        #[method(smartDashesType)]
+        fn smart_dashes_type(&self) -> NSInteger {
+            trace_scope!("smartDashesType");
+            text_input_trait(self.ivars().text_services.get().smart_punctuation)
+        }
+
+        #[method(textCompletionType)]
+        fn text_completion_type(&self) -> NSInteger {
+            trace_scope!("textCompletionType");
+            text_input_trait(self.ivars().text_services.get().suggestions)
+        }
+
+        #[method(inlinePredictionType)]
+        fn inline_prediction_type(&self) -> NSInteger {
+            trace_scope!("inlinePredictionType");
+            text_input_trait(self.ivars().text_services.get().suggestions)
+        }
     }
 
     unsafe impl NSTextInputClient for WinitView {
@@ -252,7 +340,8 @@ declare_class!(
             trace_scope!("markedRange");
             let length = self.ivars().marked_text.borrow().length();
             if length > 0 {
-                NSRange::new(0, length)
+                // The marked text replaces the selection.
+                NSRange::new(self.selected_utf16_range().map_or(0, |range| range.location), length)
             } else {
                 // Documented to return `{NSNotFound, 0}` if there is no marked range.
                 NSRange::new(NSNotFound as NSUInteger, 0)
@@ -262,8 +351,18 @@ declare_class!(
         #[method(selectedRange)]
         fn selected_range(&self) -> NSRange {
             trace_scope!("selectedRange");
-            // Documented to return `{NSNotFound, 0}` if there is no selection.
-            NSRange::new(NSNotFound as NSUInteger, 0)
+            let Some(range) = self.selected_utf16_range() else {
+                // Documented to return `{NSNotFound, 0}` if there is no selection.
+                return NSRange::new(NSNotFound as NSUInteger, 0);
+            };
+
+            // The cursor is at the end of the marked text.
+            let marked_length = self.ivars().marked_text.borrow().length();
+            if marked_length > 0 {
+                NSRange::new(range.location + marked_length, 0)
+            } else {
+                range
+            }
         }
 
         #[method(setMarkedText:selectedRange:replacementRange:)]
@@ -271,18 +370,26 @@ declare_class!(
             &self,
             string: &NSObject,
             _selected_range: NSRange,
-            _replacement_range: NSRange,
+            replacement_range: NSRange,
         ) {
             trace_scope!("setMarkedText:selectedRange:replacementRange:");
 
+            // Reconverting committed text starts with replacing it by marked text.
+            if !unsafe { self.hasMarkedText() } {
+                self.delete_surrounding_text(replacement_range);
+            }
+
             // SAFETY: This method is guaranteed to get either a `NSString` or a `NSAttributedString`.
-            let (marked_text, preedit_string) = if string.is_kind_of::<NSAttributedString>() {
+            let (marked_text, preedit_string, spans) = if string.is_kind_of::<NSAttributedString>() {
                 let string: *const NSObject = string;
                 let string: *const NSAttributedString = string.cast();
                 let string = unsafe { &*string };
+                let preedit_string = string.string().to_string();
+                let spans = preedit_spans(string, &preedit_string);
                 (
                     NSMutableAttributedString::from_attributed_nsstring(string),
-                    string.string().to_string(),
+                    preedit_string,
+                    spans,
                 )
             } else {
                 let string: *const NSObject = string;
@@ -291,6 +398,7 @@ declare_class!(
                 (
                     NSMutableAttributedString::from_nsstring(string),
                     string.to_string(),
+                    Vec::new(),
                 )
             };
 
@@ -319,7 +427,7 @@ declare_class!(
             };
 
             // Send WindowEvent for updating marked text
-            self.queue_event(WindowEvent::Ime(Ime::Preedit(preedit_string, cursor_range)));
+            self.queue_event(WindowEvent::Ime(Ime::Preedit(preedit_string, cursor_range, spans)));
         }
 
         #[method(unmarkText)]
@@ -330,7 +438,7 @@ declare_class!(
             let input_context = self.inputContext().expect("input context");
             input_context.discardMarkedText();
 
-            self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+            self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())));
             if self.is_ime_enabled() {
                 // Leave the Preedit self.ivars()
                 self.ivars().ime_state.set(ImeState::Ground);
@@ -348,11 +456,27 @@ declare_class!(
         #[method_id(attributedSubstringForProposedRange:actualRange:)]
         fn attributed_substring_for_proposed_range(
             &self,
-            _range: NSRange,
-            _actual_range: *mut NSRange,
+            range: NSRange,
+            actual_range: *mut NSRange,
         ) -> Option<Retained<NSAttributedString>> {
             trace_scope!("attributedSubstringForProposedRange:actualRange:");
-            None
+            // The surrounding text doesn't include the marked text.
+            if unsafe { self.hasMarkedText() } {
+                return None;
+            }
+            let surrounding_text = self.ivars().surrounding_text.borrow();
+            let text = surrounding_text.as_ref()?;
+
+            let start = text.byte_offset(range.location);
+            let end = text.byte_offset(range.end()).max(start);
+            if !actual_range.is_null() {
+                let location = text.utf16_offset(start);
+                unsafe {
+                    *actual_range = NSRange::new(location, text.utf16_offset(end) - location)
+                };
+            }
+            let substring = NSString::from_str(&text.text()[start..end]);
+            Some(NSAttributedString::from_nsstring(&substring))
         }
 
         #[method(characterIndexForPoint:)]
@@ -396,7 +520,7 @@ declare_class!(
 
             // Commit only if we have marked text.
             if unsafe { self.hasMarkedText() } && self.is_ime_enabled() && !is_control {
-                self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+                self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())));
                 self.queue_event(WindowEvent::Ime(Ime::Commit(string)));
                 self.ivars().ime_state.set(ImeState::Committed);
             }
@@ -685,6 +809,14 @@ declare_class!(
                 delta,
                 phase,
             });
+
+            // Track the fingers on the trackpad, but not the momentum scrolling that follows.
+            if let MouseScrollDelta::PixelDelta(delta) = delta {
+                if unsafe { event.momentumPhase() } == NSEventPhase::None {
+                    let (phase, timestamp) = unsafe { (event.phase(), event.timestamp()) };
+                    self.track_scroll_velocity(phase, timestamp, delta);
+                }
+            }
         }
 
         #[method(magnifyWithEvent:)]
@@ -771,11 +903,37 @@ declare_class!(
 );
 
 impl WinitView {
+    fn track_scroll_velocity(
+        &self,
+        phase: NSEventPhase,
+        timestamp: f64,
+        delta: PhysicalPosition<f64>,
+    ) {
+        let mut tracker = self.ivars().scroll_velocity.borrow_mut();
+        if phase == NSEventPhase::Began {
+            tracker.clear();
+            self.ivars().scroll_offset.set(PhysicalPosition::new(0.0, 0.0));
+        }
+
+        let offset = self.ivars().scroll_offset.get();
+        let offset = PhysicalPosition::new(offset.x + delta.x, offset.y + delta.y);
+        self.ivars().scroll_offset.set(offset);
+        tracker.add(timestamp, offset);
+
+        if phase == NSEventPhase::Ended {
+            let velocity = tracker.velocity();
+            tracker.clear();
+            drop(tracker);
+            self.queue_event(WindowEvent::PanEnded { device_id: DEVICE_ID, velocity });
+        }
+    }
+
     pub(super) fn new(
         app_delegate: &ApplicationDelegate,
         window: &WinitWindow,
         accepts_first_mouse: bool,
         option_as_alt: OptionAsAlt,
+        synchronized_resize: bool,
     ) -> Retained<Self> {
         let mtm = MainThreadMarker::from(window);
         let this = mtm.alloc().set_ivars(ViewState {
@@ -791,9 +949,16 @@ impl WinitView {
             ime_allowed: Default::default(),
             forward_key_to_app: Default::default(),
             marked_text: Default::default(),
+            surrounding_text: Default::default(),
+            text_services: Default::default(),
             accepts_first_mouse,
             _ns_window: WeakId::new(&window.retain()),
             option_as_alt: Cell::new(option_as_alt),
+            synchronized_resize,
+            scroll_offset: Default::default(),
+            scroll_velocity: Default::default(),
+            metal_layer: Default::default(),
+            display_link: Default::default(),
         });
         let this: Retained<Self> = unsafe { msg_send_id![super(this), init] };
 
@@ -813,6 +978,72 @@ impl WinitView {
         this
     }
 
+    pub(super) fn set_layer_config(&self, config: CAMetalLayerConfig) {
+        let layer = self.metal_layer().unwrap_or_else(|| {
+            let layer = unsafe { CAMetalLayer::new() };
+            // The view is already layer-backed, so AppKit keeps managing the frame of the layer.
+            unsafe { self.setLayer(Some(&layer)) };
+            self.setWantsLayer(true);
+            *self.ivars().metal_layer.borrow_mut() = Some(layer.clone());
+            layer
+        });
+
+        unsafe {
+            layer.setDisplaySyncEnabled(config.display_sync);
+            layer.setWantsExtendedDynamicRangeContent(config.wants_extended_dynamic_range);
+            layer.setMaximumDrawableCount(config.maximum_drawable_count as NSUInteger);
+        }
+        self.update_metal_layer();
+    }
+
+    pub(super) fn metal_layer(&self) -> Option<Retained<CAMetalLayer>> {
+        self.ivars().metal_layer.borrow().clone()
+    }
+
+    /// Match the layer to the size of the view and the scale factor of the window.
+    pub(super) fn update_metal_layer(&self) {
+        let Some(layer) = self.metal_layer() else {
+            return;
+        };
+
+        let scale_factor = self.scale_factor();
+        let size = self.bounds().size;
+        layer.setContentsScale(scale_factor);
+        unsafe {
+            layer
+                .setDrawableSize(NSSize::new(size.width * scale_factor, size.height * scale_factor))
+        };
+    }
+
+    pub(super) fn set_preferred_frame_rate_range(&self, range: Option<FrameRateRange>) {
+        if let Some(display_link) = self.ivars().display_link.take() {
+            // The display link retains the view until it's invalidated.
+            unsafe { display_link.invalidate() };
+        }
+        let Some(range) = range else {
+            return;
+        };
+
+        // Display links are only available since macOS 14, created by the view to follow the
+        // display it's on.
+        if !self.respondsToSelector(sel!(displayLinkWithTarget:selector:)) {
+            return;
+        }
+        let display_link: Retained<CADisplayLink> = unsafe {
+            msg_send_id![self, displayLinkWithTarget: self, selector: sel!(displayLinkFired:)]
+        };
+        let range = CAFrameRateRange {
+            minimum: range.minimum,
+            maximum: range.maximum,
+            preferred: range.preferred,
+        };
+        unsafe {
+            display_link.setPreferredFrameRateRange(range);
+            display_link.addToRunLoop_forMode(&NSRunLoop::mainRunLoop(), NSRunLoopCommonModes);
+        }
+        self.ivars().display_link.replace(Some(display_link));
+    }
+
     fn window(&self) -> Retained<WinitWindow> {
         // TODO: Simply use `window` property on `NSView`.
         // That only returns a window _after_ the view has been attached though!
@@ -886,6 +1117,45 @@ impl WinitView {
         }
     }
 
+    pub(super) fn set_ime_surrounding_text(&self, text: SurroundingText) {
+        *self.ivars().surrounding_text.borrow_mut() = Some(text);
+        let input_context = self.inputContext().expect("input context");
+        input_context.invalidateCharacterCoordinates();
+    }
+
+    pub(super) fn set_text_services(&self, config: TextServicesConfig) {
+        self.ivars().text_services.set(config);
+    }
+
+    /// The selection in the surrounding text, in UTF-16 code units.
+    fn selected_utf16_range(&self) -> Option<NSRange> {
+        let surrounding_text = self.ivars().surrounding_text.borrow();
+        let text = surrounding_text.as_ref()?;
+        let selection = text.selection();
+        let location = text.utf16_offset(selection.start);
+        Some(NSRange::new(location, text.utf16_offset(selection.end) - location))
+    }
+
+    /// Ask the application to delete the text in `range` of the surrounding text.
+    fn delete_surrounding_text(&self, range: NSRange) {
+        if range.location == NSNotFound as NSUInteger {
+            return;
+        }
+        let surrounding_text = self.ivars().surrounding_text.borrow();
+        let Some(text) = surrounding_text.as_ref() else {
+            return;
+        };
+
+        let range = text.byte_offset(range.location)..text.byte_offset(range.end());
+        let (before_bytes, after_bytes) = text.delete_range(range);
+        if before_bytes > 0 || after_bytes > 0 {
+            self.queue_event(WindowEvent::Ime(Ime::DeleteSurrounding {
+                before_bytes,
+                after_bytes,
+            }));
+        }
+    }
+
     pub(super) fn set_ime_cursor_area(&self, position: NSPoint, size: NSSize) {
         self.ivars().ime_position.set(position);
         self.ivars().ime_size.set(size);
@@ -1063,6 +1333,60 @@ impl WinitView {
     }
 }
 
+/// The `NSTextInputTraitType` of a text service: `Default` if it's enabled, `No` otherwise.
+fn text_input_trait(enabled: bool) -> NSInteger {
+    if enabled {
+        0
+    } else {
+        1
+    }
+}
+
+/// The styling of marked text, from the underline attributes set by the input method.
+fn preedit_spans(string: &NSAttributedString, text: &str) -> Vec<PreeditSpan> {
+    // The runs of the attribute, indexed by UTF-16 code units.
+    let mut runs = Vec::new();
+    let mut location = 0;
+    while location < string.length() {
+        let mut range = NSRange::new(location, 0);
+        let value = unsafe {
+            string.attribute_atIndex_effectiveRange(
+                NSUnderlineStyleAttributeName,
+                location,
+                &mut range,
+            )
+        };
+        let style = value.map_or(NSUnderlineStyle::None, |value| {
+            let value: Retained<NSNumber> = unsafe { Retained::cast(value) };
+            NSUnderlineStyle(value.integerValue())
+        });
+        runs.push((range, style));
+        location = range.end().max(location + 1);
+    }
+
+    let mut utf16_index = 0;
+    let styles = text.chars().map(|char| {
+        let style = runs
+            .iter()
+            .find(|(range, _)| range.location <= utf16_index && utf16_index < range.end())
+            .map_or(NSUnderlineStyle::None, |(_, style)| *style);
+        utf16_index += char.len_utf16();
+
+        let underline = if style.0 & 0xff == 0 {
+            PreeditUnderline::None
+        } else if style.0 & 0x0700 == NSUnderlineStyle::PatternDot.0 {
+            PreeditUnderline::Dotted
+        } else if style.contains(NSUnderlineStyle::Thick) {
+            PreeditUnderline::Thick
+        } else {
+            PreeditUnderline::Thin
+        };
+        // Input methods mark the clause being converted with a thick underline.
+        (underline, underline == PreeditUnderline::Thick)
+    });
+    PreeditSpan::from_chars(text, styles)
+}
+
 /// Get the mouse button from the NSEvent.
 fn mouse_button(event: &NSEvent) -> MouseButton {
     // The buttonNumber property only makes sense for the mouse events:
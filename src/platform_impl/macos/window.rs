@@ -1,5 +1,7 @@
 #![allow(clippy::unnecessary_cast)]
 
+use std::cell::Cell;
+
 use objc2::rc::{autoreleasepool, Retained};
 use objc2::{declare_class, mutability, ClassType, DeclaredClass};
 use objc2_app_kit::{NSResponder, NSWindow};
@@ -10,6 +12,9 @@ use super::window_delegate::WindowDelegate;
 use crate::error::OsError as RootOsError;
 use crate::window::WindowAttributes;
 
+/// The part of a window that the closures of `Window::maybe_queue_on_main` run with.
+pub(crate) type MainThreadWindow = WindowDelegate;
+
 pub(crate) struct Window {
     window: MainThreadBound<Retained<WinitWindow>>,
     /// The window only keeps a weak reference to this, so we must keep it around here.
@@ -91,6 +96,12 @@ impl From<u64> for WindowId {
     }
 }
 
+#[derive(Debug)]
+pub(crate) struct State {
+    /// Whether the window can become the key window, see `Window::set_focusable`.
+    pub(super) focusable: Cell<bool>,
+}
+
 declare_class!(
     #[derive(Debug)]
     pub struct WinitWindow;
@@ -102,19 +113,21 @@ declare_class!(
         const NAME: &'static str = "WinitWindow";
     }
 
-    impl DeclaredClass for WinitWindow {}
+    impl DeclaredClass for WinitWindow {
+        type Ivars = State;
+    }
 
     unsafe impl WinitWindow {
         #[method(canBecomeMainWindow)]
         fn can_become_main_window(&self) -> bool {
             trace_scope!("canBecomeMainWindow");
-            true
+            self.ivars().focusable.get()
         }
 
         #[method(canBecomeKeyWindow)]
         fn can_become_key_window(&self) -> bool {
             trace_scope!("canBecomeKeyWindow");
-            true
+            self.ivars().focusable.get()
         }
     }
 );
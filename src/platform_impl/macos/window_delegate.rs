@@ -1,39 +1,60 @@
 #![allow(clippy::unnecessary_cast)]
 use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
-
+use std::ffi::c_void;
+use std::ptr::NonNull;
+use std::sync::Arc;
+
+use block2::RcBlock;
+use core_graphics::base::{kCGImageAlphaNoneSkipLast, kCGRenderingIntentDefault};
+use core_graphics::color_space::CGColorSpace;
+use core_graphics::data_provider::CGDataProvider;
 use core_graphics::display::{CGDisplay, CGPoint};
+use core_graphics::image::{CGImage, CGImageRef};
 use monitor::VideoModeHandle;
-use objc2::rc::{autoreleasepool, Retained};
+use objc2::rc::{autoreleasepool, Retained, WeakId};
 use objc2::runtime::{AnyObject, ProtocolObject};
-use objc2::{declare_class, msg_send_id, mutability, sel, ClassType, DeclaredClass};
+use objc2::{declare_class, msg_send, msg_send_id, mutability, sel, ClassType, DeclaredClass};
 use objc2_app_kit::{
     NSAppKitVersionNumber, NSAppKitVersionNumber10_12, NSAppearance, NSApplication,
-    NSApplicationPresentationOptions, NSBackingStoreType, NSDraggingDestination,
-    NSFilenamesPboardType, NSPasteboard, NSRequestUserAttentionType, NSScreen, NSView,
-    NSWindowButton, NSWindowDelegate, NSWindowFullScreenButton, NSWindowLevel,
-    NSWindowOcclusionState, NSWindowOrderingMode, NSWindowSharingType, NSWindowStyleMask,
-    NSWindowTabbingMode, NSWindowTitleVisibility,
+    NSApplicationPresentationOptions, NSBackingStoreType, NSButton, NSControl,
+    NSCustomTouchBarItem, NSDragOperation, NSDraggingDestination, NSEvent, NSEventMask,
+    NSFilenamesPboardType, NSLayoutAttribute, NSPasteboard, NSRequestUserAttentionType, NSScreen,
+    NSSharingService, NSSharingServiceDelegate, NSSharingServicePicker,
+    NSSharingServicePickerDelegate, NSSliderTouchBarItem, NSStatusBar, NSStatusItem,
+    NSTitlebarAccessoryViewController, NSToolbar, NSTouchBar, NSTouchBarItem,
+    NSVariableStatusItemLength, NSView, NSWindowButton, NSWindowDelegate, NSWindowFullScreenButton,
+    NSWindowLevel, NSWindowOcclusionState, NSWindowOrderingMode, NSWindowSharingType,
+    NSWindowStyleMask, NSWindowTabbingMode, NSWindowTitleVisibility, NSWindowToolbarStyle,
 };
 use objc2_foundation::{
-    ns_string, CGFloat, MainThreadMarker, NSArray, NSCopying, NSDistributedNotificationCenter,
-    NSObject, NSObjectNSDelayedPerforming, NSObjectNSThreadPerformAdditions, NSObjectProtocol,
-    NSPoint, NSRect, NSSize, NSString,
+    ns_string, CGFloat, MainThreadMarker, NSArray, NSCocoaErrorDomain, NSCopying,
+    NSDistributedNotificationCenter, NSError, NSInteger, NSObject, NSObjectNSDelayedPerforming,
+    NSObjectNSThreadPerformAdditions, NSObjectProtocol, NSPoint, NSRect, NSRectEdge, NSSet, NSSize,
+    NSString, NSUserCancelledError, NSURL,
 };
 
 use super::app_delegate::ApplicationDelegate;
 use super::cursor::cursor_from_icon;
 use super::monitor::{self, flip_window_screen_coordinates, get_display_id};
 use super::view::WinitView;
-use super::window::WinitWindow;
+use super::window::{State as WindowState, WinitWindow};
 use super::{ffi, Fullscreen, MonitorHandle, OsError, WindowId};
 use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
-use crate::event::WindowEvent;
-use crate::platform::macos::{OptionAsAlt, WindowExtMacOS};
+use crate::event::{
+    DataTransfer, DropOperation, Event, ShareOutcome, TouchBarEvent, Transition, WindowEvent,
+};
+use crate::ime::SurroundingText;
+use crate::platform::macos::{
+    CAMetalLayerConfig, OptionAsAlt, TitlebarAccessory, TitlebarAccessoryPosition, TitlebarStyle,
+    ToolbarStyle, TouchBarItem, TouchBarSpec, WindowExtMacOS,
+};
 use crate::window::{
-    Cursor, CursorGrabMode, Icon, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowLevel,
+    CapturePolicy, CoordinateTransform, Cursor, CursorGrabMode, FocusReason, FrameRateRange, Icon,
+    ImePurpose, KeyboardHints, PerformanceHint, Region, ResizeDirection, ShareData,
+    TextServicesConfig, Theme, TiledEdges, TilingState, UserAttentionType, WindowAttributes,
+    WindowButtons, WindowCapabilities, WindowId as RootWindowId, WindowLevel, WindowVisibility,
 };
 
 #[derive(Clone, Debug)]
@@ -49,6 +70,9 @@ pub struct PlatformSpecificWindowAttributes {
     pub accepts_first_mouse: bool,
     pub tabbing_identifier: Option<String>,
     pub option_as_alt: OptionAsAlt,
+    pub toolbar_style: ToolbarStyle,
+    pub titlebar_accessories: Vec<Arc<dyn TitlebarAccessory>>,
+    pub traffic_light_inset: Option<LogicalPosition<f64>>,
 }
 
 impl Default for PlatformSpecificWindowAttributes {
@@ -66,6 +90,9 @@ impl Default for PlatformSpecificWindowAttributes {
             accepts_first_mouse: true,
             tabbing_identifier: None,
             option_as_alt: Default::default(),
+            toolbar_style: Default::default(),
+            titlebar_accessories: Vec::new(),
+            traffic_light_inset: None,
         }
     }
 }
@@ -93,6 +120,10 @@ pub(crate) struct State {
     decorations: Cell<bool>,
     resizable: Cell<bool>,
     maximized: Cell<bool>,
+    /// The visibility last reported with `WindowEvent::VisibilityChanged`.
+    last_visibility: Cell<WindowVisibility>,
+    /// The tiling state last reported with `WindowEvent::TilingStateChanged`.
+    last_tiling_state: Cell<TilingState>,
 
     /// Presentation options saved before entering `set_simple_fullscreen`, and
     /// restored upon exiting it. Also used when transitioning from Borderless to
@@ -116,6 +147,46 @@ pub(crate) struct State {
     standard_frame: Cell<Option<NSRect>>,
     is_simple_fullscreen: Cell<bool>,
     saved_style: Cell<Option<NSWindowStyleMask>>,
+    /// Where the titlebar buttons should be placed, if they were moved.
+    traffic_light_inset: Cell<Option<LogicalPosition<f64>>>,
+    /// Whether the window is transparent until the first frame was presented.
+    splash_screen: Cell<bool>,
+    /// The regions set with `Window::set_hittest_regions`.
+    hittest_regions: RefCell<Option<HittestRegions>>,
+    /// Whether secure event input was requested with `WindowExtMacOS::set_secure_input`.
+    secure_input: Cell<bool>,
+    ime_purpose: Cell<ImePurpose>,
+    /// Whether this window enabled secure event input, which the system counts per process.
+    secure_input_enabled: Cell<bool>,
+    /// The MIME types negotiated for the current drag, and the pasteboard types they are read
+    /// from.
+    drop_types: RefCell<Vec<(String, Retained<NSString>)>>,
+    /// Whether the drag hovering the window has files or accepted types.
+    drag_accepted: Cell<bool>,
+    /// The ids of the Touch Bar items, indexed by the tags of their controls.
+    touch_bar_ids: RefCell<Vec<u64>>,
+    /// The picker shown by `Window::share`, and the subject for the chosen service.
+    share_picker: RefCell<Option<Retained<NSSharingServicePicker>>>,
+    share_subject: RefCell<Option<String>>,
+    /// The status item shown while the window is hidden with `Window::hide_to_tray`.
+    status_item: RefCell<Option<Retained<NSStatusItem>>>,
+}
+
+/// Switches whether the window ignores mouse events as the cursor moves in and out of the regions.
+#[derive(Debug)]
+struct HittestRegions {
+    regions: Vec<Region>,
+    /// Monitors of the cursor moving over this application and over others, since the window
+    /// doesn't receive mouse events while it ignores them.
+    monitors: Vec<Retained<AnyObject>>,
+}
+
+impl Drop for HittestRegions {
+    fn drop(&mut self) {
+        for monitor in &self.monitors {
+            unsafe { NSEvent::removeMonitor(monitor) };
+        }
+    }
 }
 
 declare_class!(
@@ -150,6 +221,11 @@ declare_class!(
                 // be called after the window closes.
                 self.window().setDelegate(None);
             });
+            self.ivars().secure_input.set(false);
+            self.ivars().ime_purpose.set(ImePurpose::Normal);
+            self.update_secure_input();
+            self.remove_status_item();
+            self.view().set_preferred_frame_rate_range(None);
             self.queue_event(WindowEvent::Destroyed);
         }
 
@@ -158,6 +234,9 @@ declare_class!(
             trace_scope!("windowDidResize:");
             // NOTE: WindowEvent::Resized is reported in frameDidChange.
             self.emit_move_event();
+            // AppKit lays out the titlebar again on resize.
+            self.reposition_traffic_lights();
+            self.update_tiling_state();
         }
 
         #[method(windowWillStartLiveResize:)]
@@ -166,12 +245,14 @@ declare_class!(
 
             let increments = self.ivars().resize_increments.get();
             self.set_resize_increments_inner(increments);
+            self.queue_event(WindowEvent::MoveResizeBegan);
         }
 
         #[method(windowDidEndLiveResize:)]
         fn window_did_end_live_resize(&self, _: Option<&AnyObject>) {
             trace_scope!("windowDidEndLiveResize:");
             self.set_resize_increments_inner(NSSize::new(1., 1.));
+            self.queue_event(WindowEvent::MoveResizeEnded);
         }
 
         // This won't be triggered if the move was part of a resize.
@@ -179,11 +260,13 @@ declare_class!(
         fn window_did_move(&self, _: Option<&AnyObject>) {
             trace_scope!("windowDidMove:");
             self.emit_move_event();
+            self.update_tiling_state();
         }
 
         #[method(windowDidChangeBackingProperties:)]
         fn window_did_change_backing_properties(&self, _: Option<&AnyObject>) {
             trace_scope!("windowDidChangeBackingProperties:");
+            self.view().update_metal_layer();
             self.queue_static_scale_factor_changed_event();
         }
 
@@ -193,6 +276,8 @@ declare_class!(
             // TODO: center the cursor if the window had mouse grab when it
             // lost focus
             self.queue_event(WindowEvent::Focused(true));
+            self.update_visibility();
+            self.update_secure_input();
         }
 
         #[method(windowDidResignKey:)]
@@ -208,6 +293,8 @@ declare_class!(
             self.view().reset_modifiers();
 
             self.queue_event(WindowEvent::Focused(false));
+            self.update_visibility();
+            self.update_secure_input();
         }
 
         /// Invoked when before enter fullscreen
@@ -234,6 +321,8 @@ declare_class!(
                 },
             }
             self.ivars().in_fullscreen_transition.set(true);
+            drop(fullscreen);
+            self.queue_event(WindowEvent::TransitionStarted(Transition::EnterFullscreen));
         }
 
         /// Invoked when before exit fullscreen
@@ -242,6 +331,7 @@ declare_class!(
             trace_scope!("windowWillExitFullScreen:");
 
             self.ivars().in_fullscreen_transition.set(true);
+            self.queue_event(WindowEvent::TransitionStarted(Transition::ExitFullscreen));
         }
 
         #[method(window:willUseFullScreenPresentationOptions:)]
@@ -276,6 +366,8 @@ declare_class!(
             trace_scope!("windowDidEnterFullScreen:");
             self.ivars().initial_fullscreen.set(false);
             self.ivars().in_fullscreen_transition.set(false);
+            self.queue_event(WindowEvent::TransitionEnded(Transition::EnterFullscreen));
+            self.update_tiling_state();
             if let Some(target_fullscreen) = self.ivars().target_fullscreen.take() {
                 self.set_fullscreen(target_fullscreen);
             }
@@ -287,7 +379,10 @@ declare_class!(
             trace_scope!("windowDidExitFullScreen:");
 
             self.restore_state_from_fullscreen();
+            self.reposition_traffic_lights();
             self.ivars().in_fullscreen_transition.set(false);
+            self.queue_event(WindowEvent::TransitionEnded(Transition::ExitFullscreen));
+            self.update_tiling_state();
             if let Some(target_fullscreen) = self.ivars().target_fullscreen.take() {
                 self.set_fullscreen(target_fullscreen);
             }
@@ -313,6 +408,7 @@ declare_class!(
         fn window_did_fail_to_enter_fullscreen(&self, _: Option<&AnyObject>) {
             trace_scope!("windowDidFailToEnterFullScreen:");
             self.ivars().in_fullscreen_transition.set(false);
+            self.queue_event(WindowEvent::TransitionEnded(Transition::EnterFullscreen));
             self.ivars().target_fullscreen.replace(None);
             if self.ivars().initial_fullscreen.get() {
                 unsafe {
@@ -327,12 +423,35 @@ declare_class!(
             }
         }
 
+        /// Invoked when fail to exit fullscreen
+        #[method(windowDidFailToExitFullScreen:)]
+        fn window_did_fail_to_exit_fullscreen(&self, _: Option<&AnyObject>) {
+            trace_scope!("windowDidFailToExitFullScreen:");
+            self.ivars().in_fullscreen_transition.set(false);
+            self.ivars().target_fullscreen.replace(None);
+            self.queue_event(WindowEvent::TransitionEnded(Transition::ExitFullscreen));
+        }
+
+        #[method(windowWillMiniaturize:)]
+        fn window_will_miniaturize(&self, _: Option<&AnyObject>) {
+            trace_scope!("windowWillMiniaturize:");
+            self.queue_event(WindowEvent::TransitionStarted(Transition::Minimize));
+        }
+
+        #[method(windowDidMiniaturize:)]
+        fn window_did_miniaturize(&self, _: Option<&AnyObject>) {
+            trace_scope!("windowDidMiniaturize:");
+            self.queue_event(WindowEvent::TransitionEnded(Transition::Minimize));
+        }
+
         // Invoked when the occlusion state of the window changes
         #[method(windowDidChangeOcclusionState:)]
         fn window_did_change_occlusion_state(&self, _: Option<&AnyObject>) {
             trace_scope!("windowDidChangeOcclusionState:");
             let visible = self.window().occlusionState().contains(NSWindowOcclusionState::Visible);
             self.queue_event(WindowEvent::Occluded(!visible));
+            // Hiding and minimizing the window also changes its occlusion state.
+            self.update_visibility();
         }
 
         #[method(windowDidChangeScreen:)]
@@ -350,21 +469,34 @@ declare_class!(
     unsafe impl NSDraggingDestination for WindowDelegate {
         /// Invoked when the dragged image enters destination bounds or frame
         #[method(draggingEntered:)]
-        fn dragging_entered(&self, sender: &NSObject) -> bool {
+        fn dragging_entered(&self, sender: &NSObject) -> NSDragOperation {
             trace_scope!("draggingEntered:");
 
             use std::path::PathBuf;
 
             let pb: Retained<NSPasteboard> = unsafe { msg_send_id![sender, draggingPasteboard] };
-            let filenames = pb.propertyListForType(unsafe { NSFilenamesPboardType }).unwrap();
-            let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
+            let filenames = pb.propertyListForType(unsafe { NSFilenamesPboardType });
+            let has_files = filenames.is_some();
+            if let Some(filenames) = filenames {
+                let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
+                filenames.into_iter().for_each(|file| {
+                    let path = PathBuf::from(file.to_string());
+                    self.queue_event(WindowEvent::HoveredFile(path));
+                });
+            }
 
-            filenames.into_iter().for_each(|file| {
-                let path = PathBuf::from(file.to_string());
-                self.queue_event(WindowEvent::HoveredFile(path));
-            });
+            // Files are always accepted, other data if the window accepts any of its types.
+            let drop_types = negotiate_drop_types(&pb, RootWindowId(self.window().id()));
+            self.ivars().drag_accepted.set(has_files || !drop_types.is_empty());
+            *self.ivars().drop_types.borrow_mut() = drop_types;
+            self.drag_hover(sender)
+        }
 
-            true
+        /// Invoked periodically as the image is held within the destination area
+        #[method(draggingUpdated:)]
+        fn dragging_updated(&self, sender: &NSObject) -> NSDragOperation {
+            trace_scope!("draggingUpdated:");
+            self.drag_hover(sender)
         }
 
         /// Invoked when the image is released
@@ -382,13 +514,19 @@ declare_class!(
             use std::path::PathBuf;
 
             let pb: Retained<NSPasteboard> = unsafe { msg_send_id![sender, draggingPasteboard] };
-            let filenames = pb.propertyListForType(unsafe { NSFilenamesPboardType }).unwrap();
-            let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
+            if let Some(filenames) = pb.propertyListForType(unsafe { NSFilenamesPboardType }) {
+                let filenames: Retained<NSArray<NSString>> = unsafe { Retained::cast(filenames) };
+                filenames.into_iter().for_each(|file| {
+                    let path = PathBuf::from(file.to_string());
+                    self.queue_event(WindowEvent::DroppedFile(path));
+                });
+            }
 
-            filenames.into_iter().for_each(|file| {
-                let path = PathBuf::from(file.to_string());
-                self.queue_event(WindowEvent::DroppedFile(path));
-            });
+            let drop_types = self.ivars().drop_types.take();
+            let data = read_drop_data(&pb, drop_types);
+            if !data.is_empty() {
+                self.queue_event(WindowEvent::DroppedData(data));
+            }
 
             true
         }
@@ -403,10 +541,67 @@ declare_class!(
         #[method(draggingExited:)]
         fn dragging_exited(&self, _sender: Option<&NSObject>) {
             trace_scope!("draggingExited:");
+            self.ivars().drag_accepted.set(false);
+            self.ivars().drop_types.borrow_mut().clear();
             self.queue_event(WindowEvent::HoveredFileCancelled);
         }
     }
 
+    unsafe impl NSSharingServicePickerDelegate for WindowDelegate {
+        #[method_id(sharingServicePicker:delegateForSharingService:)]
+        fn sharing_service_picker_delegate_for_sharing_service(
+            &self,
+            _picker: &NSSharingServicePicker,
+            service: &NSSharingService,
+        ) -> Option<Retained<ProtocolObject<dyn NSSharingServiceDelegate>>> {
+            trace_scope!("sharingServicePicker:delegateForSharingService:");
+            if let Some(subject) = self.ivars().share_subject.borrow().as_deref() {
+                unsafe { service.setSubject(Some(&NSString::from_str(subject))) };
+            }
+            Some(ProtocolObject::from_retained(self.retain()))
+        }
+
+        #[method(sharingServicePicker:didChooseSharingService:)]
+        fn sharing_service_picker_did_choose_sharing_service(
+            &self,
+            _picker: &NSSharingServicePicker,
+            service: Option<&NSSharingService>,
+        ) {
+            trace_scope!("sharingServicePicker:didChooseSharingService:");
+            // The picker was closed without choosing a service.
+            if service.is_none() {
+                self.queue_event(WindowEvent::ShareCompleted(ShareOutcome::Cancelled));
+            }
+        }
+    }
+
+    unsafe impl NSSharingServiceDelegate for WindowDelegate {
+        #[method(sharingService:didShareItems:)]
+        fn sharing_service_did_share_items(&self, _service: &NSSharingService, _items: &NSArray) {
+            trace_scope!("sharingService:didShareItems:");
+            self.queue_event(WindowEvent::ShareCompleted(ShareOutcome::Shared));
+        }
+
+        #[method(sharingService:didFailToShareItems:error:)]
+        fn sharing_service_did_fail_to_share_items(
+            &self,
+            _service: &NSSharingService,
+            _items: &NSArray,
+            error: &NSError,
+        ) {
+            trace_scope!("sharingService:didFailToShareItems:error:");
+            // Services fail with `NSUserCancelledError` when their own sheet is cancelled.
+            let outcome = if error.code() == NSUserCancelledError
+                && &*error.domain() == unsafe { NSCocoaErrorDomain }
+            {
+                ShareOutcome::Cancelled
+            } else {
+                ShareOutcome::Failed
+            };
+            self.queue_event(WindowEvent::ShareCompleted(outcome));
+        }
+    }
+
     unsafe impl WindowDelegate {
         // Observe theme change
         #[method(effectiveAppearanceDidChange:)]
@@ -430,6 +625,40 @@ declare_class!(
                 self.queue_event(WindowEvent::ThemeChanged(theme));
             }
         }
+
+        #[method(touchBarButtonPressed:)]
+        fn touch_bar_button_pressed(&self, sender: &NSControl) {
+            trace_scope!("touchBarButtonPressed:");
+            if let Some(id) = self.touch_bar_id(sender) {
+                self.queue_event(WindowEvent::TouchBar(TouchBarEvent::ButtonPressed { id }));
+            }
+        }
+
+        #[method(touchBarSliderChanged:)]
+        fn touch_bar_slider_changed(&self, sender: &NSControl) {
+            trace_scope!("touchBarSliderChanged:");
+            if let Some(id) = self.touch_bar_id(sender) {
+                let value = unsafe { sender.doubleValue() };
+                self.queue_event(WindowEvent::TouchBar(TouchBarEvent::SliderChanged { id, value }));
+            }
+        }
+
+        #[method(statusItemClicked:)]
+        fn status_item_clicked(&self, _: Option<&AnyObject>) {
+            trace_scope!("statusItemClicked:");
+            if !self.remove_status_item() {
+                return;
+            }
+            let mtm = MainThreadMarker::from(self);
+            self.window().makeKeyAndOrderFront(None);
+            #[allow(deprecated)]
+            NSApplication::sharedApplication(mtm).activateIgnoringOtherApps(true);
+
+            let window_id = RootWindowId(self.window().id());
+            let app_delegate = &self.ivars().app_delegate;
+            app_delegate.queue_event(Event::SurfaceCreated(window_id));
+            app_delegate.queue_event(Event::TrayActivated(window_id));
+        }
     }
 );
 
@@ -511,7 +740,9 @@ fn new_window(
 
         let window: Option<Retained<WinitWindow>> = unsafe {
             msg_send_id![
-                super(mtm.alloc().set_ivars(())),
+                super(mtm.alloc().set_ivars(WindowState {
+                    focusable: Cell::new(attrs.focusable),
+                })),
                 initWithContentRect: frame,
                 styleMask: masks,
                 backing: NSBackingStoreType::NSBackingStoreBuffered,
@@ -543,6 +774,12 @@ fn new_window(
         if attrs.platform_specific.title_hidden {
             window.setTitleVisibility(NSWindowTitleVisibility::NSWindowTitleHidden);
         }
+        if attrs.platform_specific.toolbar_style != ToolbarStyle::Automatic {
+            set_toolbar_style(&window, attrs.platform_specific.toolbar_style, mtm);
+        }
+        for accessory in &attrs.platform_specific.titlebar_accessories {
+            add_titlebar_accessory(&window, &**accessory, mtm);
+        }
         if attrs.platform_specific.titlebar_buttons_hidden {
             for titlebar_button in &[
                 #[allow(deprecated)]
@@ -578,6 +815,7 @@ fn new_window(
             &window,
             attrs.platform_specific.accepts_first_mouse,
             attrs.platform_specific.option_as_alt,
+            attrs.synchronized_resize,
         );
 
         // The default value of `setWantsBestResolutionOpenGLSurface:` was `false` until
@@ -604,15 +842,81 @@ fn new_window(
         }
 
         // register for drag and drop operations.
-        window
-            .registerForDraggedTypes(&NSArray::from_id_slice(&[
-                unsafe { NSFilenamesPboardType }.copy()
-            ]));
+        let mut dragged_types = vec![unsafe { NSFilenamesPboardType }.copy()];
+        dragged_types.extend(DROP_TYPES.iter().map(|&(uti, _)| NSString::from_str(uti)));
+        window.registerForDraggedTypes(&NSArray::from_vec(dragged_types));
 
         Some(window)
     })
 }
 
+/// The pasteboard types of dragged data that is offered to windows, and their MIME types.
+///
+/// Drags are only delivered to the window if they contain one of these types, or files, but the
+/// window is offered all the types of the drag.
+const DROP_TYPES: [(&str, &str); 9] = [
+    ("public.utf8-plain-text", DataTransfer::TEXT),
+    ("public.file-url", DataTransfer::URI_LIST),
+    ("public.url", DataTransfer::URI_LIST),
+    ("public.html", "text/html"),
+    ("public.rtf", "text/rtf"),
+    ("public.png", "image/png"),
+    ("public.jpeg", "image/jpeg"),
+    ("public.tiff", "image/tiff"),
+    ("com.adobe.pdf", "application/pdf"),
+];
+
+/// Negotiate the types of a drag with the window, the pasteboard types without a MIME type being
+/// offered by their uniform type identifier.
+fn negotiate_drop_types(
+    pb: &NSPasteboard,
+    window_id: RootWindowId,
+) -> Vec<(String, Retained<NSString>)> {
+    let mut offered: Vec<(String, Retained<NSString>)> = Vec::new();
+    for pb_type in unsafe { pb.types() }.into_iter().flatten() {
+        let uti = pb_type.to_string();
+        let name = match DROP_TYPES.iter().find(|&&(drop_type, _)| drop_type == uti) {
+            Some(&(_, mime_type)) => mime_type.to_owned(),
+            // Skip dynamic and legacy types, which aren't meaningful to applications.
+            None if uti.starts_with("dyn.") || uti.contains(' ') => continue,
+            None => uti,
+        };
+        if !offered.iter().any(|(offered, _)| *offered == name) {
+            offered.push((name, pb_type));
+        }
+    }
+
+    let names: Vec<String> = offered.iter().map(|(name, _)| name.clone()).collect();
+    crate::drop_negotiation::negotiate(window_id, &names)
+        .into_iter()
+        .filter_map(|name| offered.iter().find(|(offered, _)| *offered == name).cloned())
+        .collect()
+}
+
+/// Read the accepted types of a drop, URIs being collected from all the dragged items.
+fn read_drop_data(
+    pb: &NSPasteboard,
+    drop_types: Vec<(String, Retained<NSString>)>,
+) -> DataTransfer {
+    let mut data = DataTransfer::new();
+    for (name, pb_type) in drop_types {
+        if name == DataTransfer::URI_LIST {
+            let Some(items) = (unsafe { pb.pasteboardItems() }) else { continue };
+            let uris: Vec<String> = items
+                .iter()
+                .filter_map(|item| unsafe { item.stringForType(&pb_type) })
+                .map(|uri| format!("{uri}\r\n"))
+                .collect();
+            if !uris.is_empty() {
+                data.insert(name, uris.concat().into_bytes());
+            }
+        } else if let Some(bytes) = unsafe { pb.dataForType(&pb_type) } {
+            data.insert(name, bytes.bytes().to_vec());
+        }
+    }
+    data
+}
+
 impl WindowDelegate {
     pub(super) fn new(
         app_delegate: &ApplicationDelegate,
@@ -669,6 +973,8 @@ impl WindowDelegate {
             decorations: Cell::new(attrs.decorations),
             resizable: Cell::new(attrs.resizable),
             maximized: Cell::new(attrs.maximized),
+            last_visibility: Cell::new(WindowVisibility::Hidden),
+            last_tiling_state: Cell::new(TilingState::Floating),
             save_presentation_opts: Cell::new(None),
             initial_fullscreen: Cell::new(attrs.fullscreen.is_some()),
             fullscreen: RefCell::new(None),
@@ -677,6 +983,18 @@ impl WindowDelegate {
             standard_frame: Cell::new(None),
             is_simple_fullscreen: Cell::new(false),
             saved_style: Cell::new(None),
+            traffic_light_inset: Cell::new(attrs.platform_specific.traffic_light_inset),
+            splash_screen: Cell::new(attrs.splash_screen),
+            hittest_regions: RefCell::new(None),
+            secure_input: Cell::new(false),
+            ime_purpose: Cell::new(ImePurpose::Normal),
+            secure_input_enabled: Cell::new(false),
+            drop_types: RefCell::new(Vec::new()),
+            drag_accepted: Cell::new(false),
+            touch_bar_ids: RefCell::new(Vec::new()),
+            share_picker: RefCell::new(None),
+            share_subject: RefCell::new(None),
+            status_item: RefCell::new(None),
         });
         let delegate: Retained<WindowDelegate> = unsafe { msg_send_id![super(delegate), init] };
 
@@ -684,6 +1002,7 @@ impl WindowDelegate {
             delegate.queue_static_scale_factor_changed_event();
         }
         window.setDelegate(Some(ProtocolObject::from_ref(&*delegate)));
+        delegate.reposition_traffic_lights();
 
         // Enable theme change event
         let notification_center = unsafe { NSDistributedNotificationCenter::defaultCenter() };
@@ -715,6 +1034,11 @@ impl WindowDelegate {
         // obscure the real focused events on the startup.
         delegate.queue_event(WindowEvent::Focused(false));
 
+        // Hide the contents before the window is shown, so the empty window is never drawn.
+        if attrs.splash_screen {
+            unsafe { window.setAlphaValue(0.0) };
+        }
+
         // Set fullscreen mode after we setup everything
         delegate.set_fullscreen(attrs.fullscreen.map(Into::into));
 
@@ -753,6 +1077,35 @@ impl WindowDelegate {
         self.window().id()
     }
 
+    /// Ask the application what a drop at the location of the drag would do, limited to the
+    /// operations the source allows.
+    fn drag_hover(&self, sender: &NSObject) -> NSDragOperation {
+        if !self.ivars().drag_accepted.get() {
+            return NSDragOperation::None;
+        }
+
+        let window_point: NSPoint = unsafe { msg_send![sender, draggingLocation] };
+        let view_point = self.view().convertPoint_fromView(window_point, None);
+        let position =
+            LogicalPosition::new(view_point.x, view_point.y).to_physical(self.scale_factor());
+        let operation = self.ivars().app_delegate.handle_drag_hover(self.window().id(), position);
+
+        let allowed: NSDragOperation = unsafe { msg_send![sender, draggingSourceOperationMask] };
+        let operation = match operation {
+            DropOperation::Copy => NSDragOperation::Copy,
+            DropOperation::Move => NSDragOperation::Move,
+            DropOperation::Link => NSDragOperation::Link,
+            DropOperation::Reject => NSDragOperation::None,
+        };
+        operation & allowed
+    }
+
+    /// The id of the Touch Bar item of a control.
+    fn touch_bar_id(&self, control: &NSControl) -> Option<u64> {
+        let index = unsafe { control.tag() };
+        self.ivars().touch_bar_ids.borrow().get(usize::try_from(index).ok()?).copied()
+    }
+
     pub(crate) fn queue_event(&self, event: WindowEvent) {
         self.ivars().app_delegate.queue_window_event(self.window().id(), event);
     }
@@ -787,6 +1140,44 @@ impl WindowDelegate {
         self.queue_event(WindowEvent::Moved(position));
     }
 
+    /// Move the titlebar buttons to `traffic_light_inset`, if it's set.
+    fn reposition_traffic_lights(&self) {
+        let Some(inset) = self.ivars().traffic_light_inset.get() else {
+            return;
+        };
+
+        let window = self.window();
+        let buttons = [
+            NSWindowButton::NSWindowCloseButton,
+            NSWindowButton::NSWindowMiniaturizeButton,
+            NSWindowButton::NSWindowZoomButton,
+        ]
+        .map(|button| window.standardWindowButton(button));
+        let [Some(close), Some(miniaturize), Some(zoom)] = buttons else {
+            return;
+        };
+
+        // The buttons live in `NSTitlebarView`, which is inside of `NSTitlebarContainerView`. The
+        // container has to grow with the inset, otherwise the buttons get clipped.
+        let Some(container) = (unsafe { close.superview() }).and_then(|v| unsafe { v.superview() })
+        else {
+            return;
+        };
+
+        let close_frame = close.frame();
+        let titlebar_height = close_frame.size.height + inset.y;
+        let mut container_frame = container.frame();
+        container_frame.size.height = titlebar_height;
+        container_frame.origin.y = window.frame().size.height - titlebar_height;
+        unsafe { container.setFrame(container_frame) };
+
+        let spacing = miniaturize.frame().origin.x - close_frame.origin.x;
+        for (i, button) in [close, miniaturize, zoom].iter().enumerate() {
+            let origin = NSPoint::new(inset.x + i as CGFloat * spacing, button.frame().origin.y);
+            unsafe { button.setFrameOrigin(origin) };
+        }
+    }
+
     fn set_style_mask(&self, mask: NSWindowStyleMask) {
         self.window().setStyleMask(mask);
         // If we don't do this, key handling will break
@@ -818,7 +1209,13 @@ impl WindowDelegate {
 
     pub fn set_visible(&self, visible: bool) {
         match visible {
-            true => self.window().makeKeyAndOrderFront(None),
+            true => {
+                if self.remove_status_item() {
+                    let window_id = RootWindowId(self.window().id());
+                    self.ivars().app_delegate.queue_event(Event::SurfaceCreated(window_id));
+                }
+                self.window().makeKeyAndOrderFront(None)
+            },
             false => self.window().orderOut(None),
         }
     }
@@ -828,6 +1225,46 @@ impl WindowDelegate {
         Some(self.window().isVisible())
     }
 
+    pub fn hide_to_tray(&self) {
+        if self.ivars().status_item.borrow().is_some() {
+            return;
+        }
+        let mtm = MainThreadMarker::from(self);
+
+        let item = unsafe {
+            NSStatusBar::systemStatusBar().statusItemWithLength(NSVariableStatusItemLength)
+        };
+        if let Some(button) = unsafe { item.button(mtm) } {
+            // Scaled to the height of the menu bar, without changing the icon of the Dock.
+            let icon = unsafe { NSApplication::sharedApplication(mtm).applicationIconImage() };
+            match icon.map(|icon| icon.copy()) {
+                Some(icon) => unsafe {
+                    icon.setSize(NSSize::new(18.0, 18.0));
+                    button.setImage(Some(&icon));
+                },
+                None => unsafe { button.setTitle(&self.window().title()) },
+            }
+            unsafe { button.setToolTip(Some(&self.window().title())) };
+            let target: &AnyObject = self;
+            unsafe { button.setTarget(Some(target)) };
+            unsafe { button.setAction(Some(sel!(statusItemClicked:))) };
+        }
+        *self.ivars().status_item.borrow_mut() = Some(item);
+
+        self.window().orderOut(None);
+        let window_id = RootWindowId(self.window().id());
+        self.ivars().app_delegate.queue_event(Event::SurfaceDestroyed(window_id));
+    }
+
+    /// Remove the status item of a window hidden to the tray, returning whether there was one.
+    fn remove_status_item(&self) -> bool {
+        let Some(item) = self.ivars().status_item.take() else {
+            return false;
+        };
+        unsafe { NSStatusBar::systemStatusBar().removeStatusItem(&item) };
+        true
+    }
+
     pub fn request_redraw(&self) {
         self.ivars().app_delegate.queue_redraw(self.window().id());
     }
@@ -835,6 +1272,47 @@ impl WindowDelegate {
     #[inline]
     pub fn pre_present_notify(&self) {}
 
+    pub fn present_rgba(
+        &self,
+        buffer: &[u8],
+        size: PhysicalSize<u32>,
+    ) -> Result<(), ExternalError> {
+        if size.width == 0 || size.height == 0 {
+            return Ok(());
+        }
+
+        let provider = CGDataProvider::from_buffer(Arc::new(buffer.to_vec()));
+        let image = CGImage::new(
+            size.width as usize,
+            size.height as usize,
+            8,
+            32,
+            4 * size.width as usize,
+            &CGColorSpace::create_device_rgb(),
+            kCGImageAlphaNoneSkipLast,
+            &provider,
+            false,
+            kCGRenderingIntentDefault,
+        );
+
+        let view = self.view();
+        view.setWantsLayer(true);
+        let Some(layer) = (unsafe { view.layer() }) else {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        };
+        // A reference to a `CGImageRef` is the pointer to the image, which the layer retains.
+        let image: &CGImageRef = &image;
+        let contents = image as *const CGImageRef as *const AnyObject;
+        unsafe { layer.setContents(Some(&*contents)) };
+        Ok(())
+    }
+
+    pub fn notify_first_frame_presented(&self) {
+        if self.ivars().splash_screen.replace(false) {
+            unsafe { self.window().setAlphaValue(1.0) };
+        }
+    }
+
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         let position = flip_window_screen_coordinates(self.window().frame());
         Ok(LogicalPosition::new(position.x, position.y).to_physical(self.scale_factor()))
@@ -846,6 +1324,10 @@ impl WindowDelegate {
         Ok(LogicalPosition::new(position.x, position.y).to_physical(self.scale_factor()))
     }
 
+    pub fn coordinate_transform(&self) -> CoordinateTransform {
+        CoordinateTransform::from_scale_factor(self.inner_position().ok(), self.scale_factor())
+    }
+
     pub fn set_outer_position(&self, position: Position) {
         let position = position.to_logical(self.scale_factor());
         let point = flip_window_screen_coordinates(NSRect::new(
@@ -1034,6 +1516,22 @@ impl WindowDelegate {
         self.window().invalidateCursorRectsForView(&view);
     }
 
+    pub fn set_cursor_for_device(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _cursor: Cursor,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_pointer_capture(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _capture: bool,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         let associate_mouse_cursor = match mode {
@@ -1081,6 +1579,14 @@ impl WindowDelegate {
         Ok(())
     }
 
+    #[inline]
+    pub fn cursor_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        let window_point = unsafe { self.window().mouseLocationOutsideOfEventStream() };
+        let view_point = self.view().convertPoint_fromView(window_point, None);
+        let position = LogicalPosition::new(view_point.x, view_point.y);
+        Ok(position.to_physical(self.scale_factor()))
+    }
+
     #[inline]
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         let mtm = MainThreadMarker::from(self);
@@ -1099,10 +1605,69 @@ impl WindowDelegate {
 
     #[inline]
     pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
+        self.ivars().hittest_regions.replace(None);
         self.window().setIgnoresMouseEvents(!hittest);
         Ok(())
     }
 
+    pub fn set_hittest_regions(&self, regions: &[Region]) -> Result<(), ExternalError> {
+        let mut hittest_regions = self.ivars().hittest_regions.borrow_mut();
+        if let Some(hittest_regions) = &mut *hittest_regions {
+            hittest_regions.regions = regions.to_vec();
+        } else {
+            let global_delegate = WeakId::new(&self.retain());
+            let global_handler = RcBlock::new(move |_: NonNull<NSEvent>| {
+                if let Some(delegate) = global_delegate.load() {
+                    delegate.update_hittest();
+                }
+            });
+            let local_delegate = WeakId::new(&self.retain());
+            let local_handler = RcBlock::new(move |event: NonNull<NSEvent>| {
+                if let Some(delegate) = local_delegate.load() {
+                    delegate.update_hittest();
+                }
+                event.as_ptr()
+            });
+
+            let mask = NSEventMask::MouseMoved
+                | NSEventMask::LeftMouseDragged
+                | NSEventMask::RightMouseDragged
+                | NSEventMask::OtherMouseDragged;
+            let monitors = unsafe {
+                [
+                    NSEvent::addGlobalMonitorForEventsMatchingMask_handler(mask, &global_handler),
+                    NSEvent::addLocalMonitorForEventsMatchingMask_handler(mask, &local_handler),
+                ]
+            };
+            *hittest_regions = Some(HittestRegions {
+                regions: regions.to_vec(),
+                monitors: monitors.into_iter().flatten().collect(),
+            });
+        }
+        drop(hittest_regions);
+
+        self.update_hittest();
+        Ok(())
+    }
+
+    /// Catch mouse events only while the cursor is in one of the hit test regions.
+    fn update_hittest(&self) {
+        let hittest = match &*self.ivars().hittest_regions.borrow() {
+            Some(hittest_regions) => {
+                let Ok(position) = self.cursor_position() else {
+                    return;
+                };
+                let scale_factor = self.scale_factor();
+                hittest_regions.regions.iter().any(|region| region.contains(position, scale_factor))
+            },
+            None => return,
+        };
+
+        if unsafe { self.window().ignoresMouseEvents() } == hittest {
+            self.window().setIgnoresMouseEvents(!hittest);
+        }
+    }
+
     pub(crate) fn is_zoomed(&self) -> bool {
         // because `isZoomed` doesn't work if the window's borderless,
         // we make it resizable temporarily.
@@ -1403,6 +1968,13 @@ impl WindowDelegate {
         };
     }
 
+    #[inline]
+    pub fn capabilities(&self) -> WindowCapabilities {
+        WindowCapabilities::all()
+            - WindowCapabilities::POINTER_CAPTURE
+            - WindowCapabilities::REDRAW_THREAD
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         if decorations == self.ivars().decorations.get() {
@@ -1482,7 +2054,49 @@ impl WindowDelegate {
     }
 
     #[inline]
-    pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.ivars().ime_purpose.set(purpose);
+        self.update_secure_input();
+    }
+
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, text: SurroundingText) {
+        self.view().set_ime_surrounding_text(text);
+    }
+
+    #[inline]
+    pub fn set_text_services(&self, config: TextServicesConfig) {
+        self.view().set_text_services(config);
+    }
+
+    #[inline]
+    pub fn set_keyboard_hints(&self, _hints: KeyboardHints) {}
+
+    /// Enable secure event input while the window is focused and asks for it, either explicitly or
+    /// for password input.
+    ///
+    /// Secure event input is global to the system, so it shouldn't stay on in the background.
+    fn update_secure_input(&self) {
+        let wanted = (self.ivars().secure_input.get()
+            || self.ivars().ime_purpose.get() == ImePurpose::Password)
+            && self.window().isKeyWindow();
+        if wanted == self.ivars().secure_input_enabled.get() {
+            return;
+        }
+
+        let status = unsafe {
+            if wanted {
+                ffi::EnableSecureEventInput()
+            } else {
+                ffi::DisableSecureEventInput()
+            }
+        };
+        if status == 0 {
+            self.ivars().secure_input_enabled.set(wanted);
+        } else {
+            tracing::warn!("failed to change secure event input: {status}");
+        }
+    }
 
     #[inline]
     pub fn focus_window(&self) {
@@ -1497,6 +2111,31 @@ impl WindowDelegate {
         }
     }
 
+    #[inline]
+    pub fn focus_window_with(&self, reason: FocusReason) {
+        if reason == FocusReason::UserInitiated {
+            return self.focus_window();
+        }
+
+        let mtm = MainThreadMarker::from(self);
+        if self.window().isMiniaturized() || !self.window().isVisible() {
+            return;
+        }
+
+        // Only activating the window, but not the application, lets the window take focus only
+        // when the application has it already.
+        if unsafe { NSApplication::sharedApplication(mtm).isActive() } {
+            self.window().makeKeyAndOrderFront(None);
+        } else {
+            self.request_user_attention(Some(UserAttentionType::Informational));
+        }
+    }
+
+    #[inline]
+    pub fn set_focusable(&self, focusable: bool) {
+        self.window().ivars().focusable.set(focusable);
+    }
+
     #[inline]
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
         let mtm = MainThreadMarker::from(self);
@@ -1585,6 +2224,48 @@ impl WindowDelegate {
         self.window().isKeyWindow()
     }
 
+    pub fn visibility(&self) -> WindowVisibility {
+        let window = self.window();
+        let occluded = !window.occlusionState().contains(NSWindowOcclusionState::Visible);
+        WindowVisibility::new(
+            window.isVisible(),
+            window.isMiniaturized(),
+            occluded,
+            window.isKeyWindow(),
+        )
+    }
+
+    /// Queue `VisibilityChanged` if the visibility of the window changed.
+    fn update_visibility(&self) {
+        let visibility = self.visibility();
+        if self.ivars().last_visibility.replace(visibility) != visibility {
+            self.queue_event(WindowEvent::VisibilityChanged(visibility));
+        }
+    }
+
+    pub fn tiling_state(&self) -> TilingState {
+        let window = self.window();
+        if self.fullscreen().is_some() || window.styleMask().contains(NSWindowStyleMask::FullScreen)
+        {
+            TilingState::Fullscreen
+        } else if self.is_zoomed() {
+            TilingState::Maximized
+        } else {
+            window
+                .screen()
+                .and_then(|screen| tiled_edges(window.frame(), screen.visibleFrame()))
+                .map_or(TilingState::Floating, TilingState::Tiled)
+        }
+    }
+
+    /// Queue `TilingStateChanged` if the tiling state of the window changed.
+    fn update_tiling_state(&self) {
+        let tiling_state = self.tiling_state();
+        if self.ivars().last_tiling_state.replace(tiling_state) != tiling_state {
+            self.queue_event(WindowEvent::TilingStateChanged(tiling_state));
+        }
+    }
+
     pub fn set_theme(&self, theme: Option<Theme>) {
         let mtm = MainThreadMarker::from(self);
         set_ns_theme(theme, mtm);
@@ -1600,6 +2281,66 @@ impl WindowDelegate {
         })
     }
 
+    #[inline]
+    pub fn set_capture_policy(&self, policy: CapturePolicy) {
+        self.window().setSharingType(match policy {
+            CapturePolicy::Allow => NSWindowSharingType::NSWindowSharingReadOnly,
+            CapturePolicy::ExcludeFromCapture | CapturePolicy::Protected => {
+                NSWindowSharingType::NSWindowSharingNone
+            },
+        })
+    }
+
+    pub fn set_performance_hint(&self, hint: PerformanceHint) {
+        self.ivars().app_delegate.set_performance_hint(hint);
+    }
+
+    pub fn set_preferred_frame_rate_range(&self, range: Option<FrameRateRange>) {
+        self.view().set_preferred_frame_rate_range(range);
+    }
+
+    pub fn share(&self, data: ShareData) {
+        let mut items: Vec<Retained<AnyObject>> = Vec::new();
+        if let Some(text) = data.text {
+            items.push(Retained::into_super(Retained::into_super(NSString::from_str(&text))));
+        }
+        if let Some(url) = data.url {
+            match unsafe { NSURL::URLWithString(&NSString::from_str(&url)) } {
+                Some(url) => items.push(Retained::into_super(Retained::into_super(url))),
+                None => tracing::warn!("can't share invalid URL {url:?}"),
+            }
+        }
+        for path in data.files {
+            let path = NSString::from_str(&path.to_string_lossy());
+            let url = unsafe { NSURL::fileURLWithPath(&path) };
+            items.push(Retained::into_super(Retained::into_super(url)));
+        }
+        if items.is_empty() {
+            self.queue_event(WindowEvent::ShareCompleted(ShareOutcome::Failed));
+            return;
+        }
+
+        *self.ivars().share_subject.borrow_mut() = data.title;
+        let picker = unsafe {
+            NSSharingServicePicker::initWithItems(
+                NSSharingServicePicker::alloc(),
+                &NSArray::from_vec(items),
+            )
+        };
+        // The view is flipped, so the picker is shown below the top edge of the window.
+        let view = self.view();
+        let anchor = NSRect::new(NSPoint::new(view.bounds().size.width / 2.0, 0.0), NSSize::ZERO);
+        unsafe {
+            picker.setDelegate(Some(ProtocolObject::from_ref(self)));
+            picker.showRelativeToRect_ofView_preferredEdge(anchor, &view, NSRectEdge::MaxY);
+        }
+        // Replacing a previous picker closes it, without reporting it as cancelled.
+        if let Some(previous) = self.ivars().share_picker.replace(Some(picker)) {
+            unsafe { previous.setDelegate(None) };
+            unsafe { previous.close() };
+        }
+    }
+
     pub fn title(&self) -> String {
         self.window().title().to_string()
     }
@@ -1742,6 +2483,182 @@ impl WindowExtMacOS for WindowDelegate {
     fn option_as_alt(&self) -> OptionAsAlt {
         self.view().option_as_alt()
     }
+
+    fn set_titlebar_style(&self, style: TitlebarStyle) {
+        let mtm = MainThreadMarker::from(self);
+        let window = self.window();
+
+        window.setTitlebarAppearsTransparent(style.transparent);
+        window.setTitleVisibility(if style.title_hidden {
+            NSWindowTitleVisibility::NSWindowTitleHidden
+        } else {
+            NSWindowTitleVisibility::NSWindowTitleVisible
+        });
+        self.toggle_style_mask(NSWindowStyleMask::FullSizeContentView, style.fullsize_content_view);
+        set_toolbar_style(window, style.toolbar_style, mtm);
+
+        self.ivars().traffic_light_inset.set(style.traffic_light_inset);
+        self.reposition_traffic_lights();
+    }
+
+    fn titlebar_style(&self) -> TitlebarStyle {
+        let window = self.window();
+        let toolbar_style = if window.respondsToSelector(sel!(toolbarStyle)) {
+            match unsafe { window.toolbarStyle() } {
+                NSWindowToolbarStyle::Expanded => ToolbarStyle::Expanded,
+                NSWindowToolbarStyle::Preference => ToolbarStyle::Preference,
+                NSWindowToolbarStyle::Unified => ToolbarStyle::Unified,
+                NSWindowToolbarStyle::UnifiedCompact => ToolbarStyle::UnifiedCompact,
+                _ => ToolbarStyle::Automatic,
+            }
+        } else {
+            ToolbarStyle::Automatic
+        };
+
+        TitlebarStyle {
+            transparent: unsafe { window.titlebarAppearsTransparent() },
+            title_hidden: unsafe { window.titleVisibility() }
+                == NSWindowTitleVisibility::NSWindowTitleHidden,
+            fullsize_content_view: window
+                .styleMask()
+                .contains(NSWindowStyleMask::FullSizeContentView),
+            toolbar_style,
+            traffic_light_inset: self.ivars().traffic_light_inset.get(),
+        }
+    }
+
+    fn set_secure_input(&self, enabled: bool) {
+        self.ivars().secure_input.set(enabled);
+        self.update_secure_input();
+    }
+
+    fn set_layer_config(&self, config: CAMetalLayerConfig) {
+        self.view().set_layer_config(config);
+    }
+
+    fn metal_layer(&self) -> Option<*mut c_void> {
+        self.view().metal_layer().map(|layer| Retained::as_ptr(&layer) as *mut c_void)
+    }
+
+    fn set_touch_bar(&self, spec: TouchBarSpec) {
+        let mtm = MainThreadMarker::from(self);
+        let window = self.window();
+        let mut ids = self.ivars().touch_bar_ids.borrow_mut();
+        ids.clear();
+        if spec.items.is_empty() {
+            unsafe { window.setTouchBar(None) };
+            return;
+        }
+
+        // The controls report to the delegate, which lives as long as the window.
+        let target: &AnyObject = self;
+        let mut identifiers = Vec::new();
+        let mut items: Vec<Retained<NSTouchBarItem>> = Vec::new();
+        for (index, item) in spec.items.into_iter().enumerate() {
+            let identifier = NSString::from_str(&format!("rs.winit.touchbar.{index}"));
+            let item = match item {
+                TouchBarItem::Button { id, title } => unsafe {
+                    let button = NSButton::buttonWithTitle_target_action(
+                        &NSString::from_str(&title),
+                        Some(target),
+                        Some(sel!(touchBarButtonPressed:)),
+                        mtm,
+                    );
+                    button.setTag(index as NSInteger);
+                    let item = NSCustomTouchBarItem::initWithIdentifier(mtm.alloc(), &identifier);
+                    item.setView(&button);
+                    ids.push(id);
+                    Retained::into_super(item)
+                },
+                TouchBarItem::Slider { id, label, min, max, value } => unsafe {
+                    let item = NSSliderTouchBarItem::initWithIdentifier(mtm.alloc(), &identifier);
+                    let slider = item.slider();
+                    slider.setMinValue(min);
+                    slider.setMaxValue(max);
+                    slider.setDoubleValue(value);
+                    slider.setTag(index as NSInteger);
+                    slider.setTarget(Some(target));
+                    slider.setAction(Some(sel!(touchBarSliderChanged:)));
+                    item.setLabel(Some(&NSString::from_str(&label)));
+                    ids.push(id);
+                    Retained::into_super(item)
+                },
+            };
+            identifiers.push(identifier);
+            items.push(item);
+        }
+
+        unsafe {
+            let touch_bar = NSTouchBar::new(mtm);
+            touch_bar.setDefaultItemIdentifiers(&NSArray::from_vec(identifiers));
+            touch_bar.setTemplateItems(&NSSet::from_vec(items));
+            window.setTouchBar(Some(&touch_bar));
+        }
+    }
+}
+
+/// Identifier of the empty toolbar installed by [`set_toolbar_style`].
+const WINIT_TOOLBAR_IDENTIFIER: &str = "WinitToolbar";
+
+fn set_toolbar_style(window: &WinitWindow, style: ToolbarStyle, mtm: MainThreadMarker) {
+    // `toolbarStyle` is only available on macOS 11.0 and later.
+    if !window.respondsToSelector(sel!(setToolbarStyle:)) {
+        return;
+    }
+
+    let toolbar = unsafe { window.toolbar() };
+    let is_ours = toolbar.as_ref().map_or(false, |toolbar| {
+        unsafe { toolbar.identifier() }.to_string() == WINIT_TOOLBAR_IDENTIFIER
+    });
+
+    // The style only has an effect when the window has a toolbar, so install an empty one if the
+    // user hasn't set one up themselves, and remove it again when it's no longer needed.
+    match style {
+        ToolbarStyle::Automatic if is_ours => unsafe { window.setToolbar(None) },
+        ToolbarStyle::Automatic => (),
+        _ if toolbar.is_none() => {
+            let toolbar = unsafe {
+                NSToolbar::initWithIdentifier(
+                    mtm.alloc(),
+                    &NSString::from_str(WINIT_TOOLBAR_IDENTIFIER),
+                )
+            };
+            unsafe { window.setToolbar(Some(&toolbar)) };
+        },
+        _ => (),
+    }
+
+    let style = match style {
+        ToolbarStyle::Automatic => NSWindowToolbarStyle::Automatic,
+        ToolbarStyle::Expanded => NSWindowToolbarStyle::Expanded,
+        ToolbarStyle::Preference => NSWindowToolbarStyle::Preference,
+        ToolbarStyle::Unified => NSWindowToolbarStyle::Unified,
+        ToolbarStyle::UnifiedCompact => NSWindowToolbarStyle::UnifiedCompact,
+    };
+    unsafe { window.setToolbarStyle(style) };
+}
+
+fn add_titlebar_accessory(
+    window: &WinitWindow,
+    accessory: &dyn TitlebarAccessory,
+    mtm: MainThreadMarker,
+) {
+    // SAFETY: `TitlebarAccessory` requires the pointer to be a valid `NSView`.
+    let view: Retained<NSView> =
+        unsafe { Retained::retain(accessory.ns_view().as_ptr().cast()) }.unwrap();
+
+    let layout_attribute = match accessory.position() {
+        TitlebarAccessoryPosition::Trailing => NSLayoutAttribute::Trailing,
+        TitlebarAccessoryPosition::Leading => NSLayoutAttribute::Leading,
+        TitlebarAccessoryPosition::Bottom => NSLayoutAttribute::Bottom,
+    };
+
+    unsafe {
+        let controller = NSTitlebarAccessoryViewController::new(mtm);
+        controller.setView(&view);
+        controller.setLayoutAttribute(layout_attribute);
+        window.addTitlebarAccessoryViewController(&controller);
+    }
 }
 
 const DEFAULT_STANDARD_FRAME: NSRect =
@@ -1765,6 +2682,40 @@ pub(super) fn get_ns_theme(mtm: MainThreadMarker) -> Theme {
     }
 }
 
+/// The edges of the screen a window is tiled against, if it fills a half or a quarter of the
+/// visible frame of the screen, as the tiling of macOS 15 arranges windows without margins.
+fn tiled_edges(frame: NSRect, visible_frame: NSRect) -> Option<TiledEdges> {
+    let near = |a: CGFloat, b: CGFloat| (a - b).abs() < 1.0;
+    let full_width = near(frame.size.width, visible_frame.size.width);
+    let full_height = near(frame.size.height, visible_frame.size.height);
+    let half_width = near(frame.size.width, visible_frame.size.width / 2.0);
+    let half_height = near(frame.size.height, visible_frame.size.height / 2.0);
+    // Filling the whole visible frame is zooming.
+    if !(full_width || half_width) || !(full_height || half_height) || (full_width && full_height) {
+        return None;
+    }
+
+    let mut edges = TiledEdges::empty();
+    edges.set(TiledEdges::LEFT, near(frame.origin.x, visible_frame.origin.x));
+    edges.set(
+        TiledEdges::RIGHT,
+        near(frame.origin.x + frame.size.width, visible_frame.origin.x + visible_frame.size.width),
+    );
+    // The origin is at the bottom.
+    edges.set(TiledEdges::BOTTOM, near(frame.origin.y, visible_frame.origin.y));
+    edges.set(
+        TiledEdges::TOP,
+        near(
+            frame.origin.y + frame.size.height,
+            visible_frame.origin.y + visible_frame.size.height,
+        ),
+    );
+    // A half or a quarter is placed against the edges of the screen in both directions.
+    let horizontal = edges.intersects(TiledEdges::LEFT | TiledEdges::RIGHT);
+    let vertical = edges.intersects(TiledEdges::TOP | TiledEdges::BOTTOM);
+    (horizontal && vertical).then_some(edges)
+}
+
 fn set_ns_theme(theme: Option<Theme>, mtm: MainThreadMarker) {
     let app = NSApplication::sharedApplication(mtm);
     if app.respondsToSelector(sel!(effectiveAppearance)) {
@@ -2,7 +2,7 @@ use std::cell::Cell;
 use std::collections::VecDeque;
 use std::marker::PhantomData;
 use std::sync::{mpsc, Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{mem, slice};
 
 use bitflags::bitflags;
@@ -13,20 +13,26 @@ use orbclient::{
 use smol_str::SmolStr;
 
 use crate::application::ApplicationHandler;
-use crate::error::EventLoopError;
+use crate::dialog::{DialogButton, DialogSender, MessageDialog};
+use crate::dpi::PhysicalPosition;
+use crate::error::{EventLoopError, ExternalError, NotSupportedError};
 use crate::event::{self, Ime, Modifiers, StartCause};
 use crate::event_loop::{self, ControlFlow, DeviceEvents};
 use crate::keyboard::{
     Key, KeyCode, KeyLocation, ModifiersKeys, ModifiersState, NamedKey, NativeKey, NativeKeyCode,
     PhysicalKey,
 };
+use crate::locale::LanguageTag;
+use crate::notification::{NotificationAttributes, NotificationId};
+use crate::session::{SessionCapabilities, SessionInfo, SessionKind};
 use crate::window::{
     CustomCursor as RootCustomCursor, CustomCursorSource, WindowId as RootWindowId,
 };
 
 use super::{
-    DeviceId, KeyEventExtra, MonitorHandle, OsError, PlatformSpecificEventLoopAttributes,
-    RedoxSocket, TimeSocket, WindowId, WindowProperties,
+    DeviceId, KeyEventExtra, MonitorHandle, OsError, PlatformNotification,
+    PlatformSpecificEventLoopAttributes, RedoxSocket, TimeSocket, VideoModeHandle, WindowId,
+    WindowProperties,
 };
 
 fn convert_scancode(scancode: u8) -> (PhysicalKey, Option<NamedKey>) {
@@ -404,7 +410,7 @@ impl<T: 'static> EventLoop<T> {
                 app.window_event(
                     window_target,
                     RootWindowId(window_id),
-                    event::WindowEvent::Ime(Ime::Preedit("".into(), None)),
+                    event::WindowEvent::Ime(Ime::Preedit("".into(), None, Vec::new())),
                 );
                 app.window_event(
                     window_target,
@@ -478,7 +484,7 @@ impl<T: 'static> EventLoop<T> {
                 app.window_event(
                     window_target,
                     RootWindowId(window_id),
-                    event::WindowEvent::Resized((width, height).into()),
+                    event::WindowEvent::Resized { size: (width, height).into(), frame: None },
                 );
 
                 // Acknowledge resize after event loop.
@@ -525,7 +531,10 @@ impl<T: 'static> EventLoop<T> {
                 let window_id = RootWindowId(window_id);
 
                 // Send resize event on create to indicate first size.
-                let event = event::WindowEvent::Resized((properties.w, properties.h).into());
+                let event = event::WindowEvent::Resized {
+                    size: (properties.w, properties.h).into(),
+                    frame: None,
+                };
                 app.window_event(&self.window_target, window_id, event);
 
                 // Send moved event on create to indicate first position.
@@ -658,7 +667,11 @@ impl<T: 'static> EventLoop<T> {
 
             // Wait for event if needed.
             let mut event = syscall::Event::default();
+            #[cfg(feature = "trace")]
+            let span = tracing::trace_span!("winit::read_event_socket").entered();
             self.window_target.p.event_socket.read(&mut event).unwrap();
+            #[cfg(feature = "trace")]
+            drop(span);
 
             // TODO: handle spurious wakeups (redraw caused wakeup but redraw already handled)
             match requested_resume {
@@ -706,6 +719,20 @@ impl<T> EventLoopProxy<T> {
 
         Ok(())
     }
+
+    pub fn waker(&self) -> EventLoopWaker {
+        EventLoopWaker(self.wake_socket.clone())
+    }
+}
+
+/// Wakes up the `EventLoop` from any thread, without a message.
+#[derive(Clone)]
+pub struct EventLoopWaker(Arc<TimeSocket>);
+
+impl EventLoopWaker {
+    pub fn wake(&self) {
+        self.0.wake().unwrap();
+    }
 }
 
 impl<T> Clone for EventLoopProxy<T> {
@@ -739,6 +766,44 @@ impl ActiveEventLoop {
         Some(MonitorHandle)
     }
 
+    pub fn locale(&self) -> Option<LanguageTag> {
+        None
+    }
+
+    pub fn seats(&self) -> Vec<event::Seat> {
+        Vec::new()
+    }
+
+    pub fn session_info(&self) -> SessionInfo {
+        SessionInfo::new(
+            SessionKind::Desktop,
+            false,
+            SessionCapabilities::SET_POSITION | SessionCapabilities::TRANSPARENCY,
+        )
+    }
+
+    pub fn double_click_settings(&self) -> Option<(Duration, u32)> {
+        None
+    }
+
+    pub fn pointer_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_restart_after_crash(
+        &self,
+        _args: Option<Vec<std::ffi::OsString>>,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_video_mode(
+        &self,
+        _mode: &VideoModeHandle,
+    ) -> Result<Box<dyn FnOnce()>, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub fn available_monitors(&self) -> VecDeque<MonitorHandle> {
         let mut v = VecDeque::with_capacity(1);
         v.push_back(MonitorHandle);
@@ -770,6 +835,27 @@ impl ActiveEventLoop {
         self.control_flow.get()
     }
 
+    #[inline]
+    pub fn set_timer_resolution(&self, _resolution: Duration) {}
+
+    pub fn set_exit_pending(&self, _pending: bool) {}
+
+    pub fn show_notification(
+        &self,
+        _id: NotificationId,
+        _attributes: NotificationAttributes,
+    ) -> Result<PlatformNotification, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn show_message_dialog(
+        &self,
+        _dialog: MessageDialog,
+        _sender: DialogSender<DialogButton>,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
     pub(crate) fn exit(&self) {
         self.exit.set(true);
     }
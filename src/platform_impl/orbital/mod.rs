@@ -1,17 +1,23 @@
 #![cfg(target_os = "redox")]
 
 use std::fmt::{self, Display, Formatter};
+use std::ops::RangeInclusive;
 use std::str;
 use std::sync::Arc;
 
 use smol_str::SmolStr;
 
 use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::event::SeatId;
 use crate::keyboard::Key;
+use crate::monitor::Rect;
 
-pub(crate) use self::event_loop::{ActiveEventLoop, EventLoop, EventLoopProxy, OwnedDisplayHandle};
+pub(crate) use self::event_loop::{
+    ActiveEventLoop, EventLoop, EventLoopProxy, EventLoopWaker, OwnedDisplayHandle,
+};
 mod event_loop;
 
+pub(crate) use self::window::MainThreadWindow;
 pub use self::window::Window;
 mod window;
 
@@ -126,6 +132,10 @@ impl DeviceId {
     pub const fn dummy() -> Self {
         DeviceId
     }
+
+    pub fn seat_id(&self) -> Option<SeatId> {
+        None
+    }
 }
 
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
@@ -183,6 +193,7 @@ pub(crate) use crate::cursor::{
     NoCustomCursor as PlatformCustomCursor, NoCustomCursor as PlatformCustomCursorSource,
 };
 pub(crate) use crate::icon::NoIcon as PlatformIcon;
+pub(crate) use crate::notification::NoNotification as PlatformNotification;
 
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct MonitorHandle;
@@ -200,6 +211,14 @@ impl MonitorHandle {
         (0, 0).into()
     }
 
+    pub fn work_area(&self) -> Rect {
+        Rect::new(self.position(), self.size())
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        None
+    }
+
     pub fn scale_factor(&self) -> f64 {
         1.0 // TODO
     }
@@ -209,6 +228,14 @@ impl MonitorHandle {
         None
     }
 
+    pub fn supports_variable_refresh(&self) -> bool {
+        false
+    }
+
+    pub fn is_mirrored(&self) -> bool {
+        false
+    }
+
     pub fn video_modes(&self) -> impl Iterator<Item = VideoModeHandle> {
         let size = self.size().into();
         // FIXME this is not the real refresh rate
@@ -243,6 +270,10 @@ impl VideoModeHandle {
         self.refresh_rate_millihertz
     }
 
+    pub fn refresh_rate_range(&self) -> RangeInclusive<u32> {
+        self.refresh_rate_millihertz..=self.refresh_rate_millihertz
+    }
+
     pub fn monitor(&self) -> MonitorHandle {
         self.monitor.clone()
     }
@@ -3,8 +3,12 @@ use std::sync::{Arc, Mutex};
 
 use crate::cursor::Cursor;
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
+use crate::ime::SurroundingText;
 use crate::platform_impl::Fullscreen;
-use crate::window::ImePurpose;
+use crate::window::{
+    CoordinateTransform, FocusReason, ImePurpose, KeyboardHints, TextServicesConfig, TilingState,
+    WindowVisibility,
+};
 use crate::{error, window};
 
 use super::{
@@ -22,6 +26,9 @@ const ORBITAL_FLAG_MAXIMIZED: char = 'm';
 const ORBITAL_FLAG_RESIZABLE: char = 'r';
 const ORBITAL_FLAG_TRANSPARENT: char = 't';
 
+/// The part of a window that the closures of `Window::maybe_queue_on_main` run with.
+pub(crate) type MainThreadWindow = Window;
+
 pub struct Window {
     window_socket: Arc<RedoxSocket>,
     redraws: Arc<Mutex<VecDeque<WindowId>>>,
@@ -192,6 +199,9 @@ impl Window {
     #[inline]
     pub fn pre_present_notify(&self) {}
 
+    #[inline]
+    pub fn notify_first_frame_presented(&self) {}
+
     #[inline]
     pub fn reset_dead_keys(&self) {
         // TODO?
@@ -205,6 +215,10 @@ impl Window {
         Ok((properties.x, properties.y).into())
     }
 
+    pub fn coordinate_transform(&self) -> CoordinateTransform {
+        CoordinateTransform::from_scale_factor(self.inner_position().ok(), self.scale_factor())
+    }
+
     #[inline]
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, error::NotSupportedError> {
         // TODO: adjust for window decorations
@@ -320,6 +334,19 @@ impl Window {
         None
     }
 
+    #[inline]
+    pub fn capabilities(&self) -> window::WindowCapabilities {
+        window::WindowCapabilities::all()
+            - window::WindowCapabilities::FOCUS
+            - window::WindowCapabilities::FOCUSABLE
+            - window::WindowCapabilities::CAPTURE_POLICY
+            - window::WindowCapabilities::POINTER_CAPTURE
+            - window::WindowCapabilities::REDRAW_THREAD
+            - window::WindowCapabilities::SHARE
+            - window::WindowCapabilities::TRAY
+            - window::WindowCapabilities::SOFTWARE_PRESENT
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         let _ = self.set_flag(ORBITAL_FLAG_BORDERLESS, !decorations);
@@ -358,20 +385,58 @@ impl Window {
     #[inline]
     pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
 
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, _text: SurroundingText) {}
+
+    #[inline]
+    pub fn set_text_services(&self, _config: TextServicesConfig) {}
+
+    #[inline]
+    pub fn set_keyboard_hints(&self, _hints: KeyboardHints) {}
+
     #[inline]
     pub fn focus_window(&self) {}
 
+    #[inline]
+    pub fn focus_window_with(&self, _reason: FocusReason) {}
+
+    #[inline]
+    pub fn set_focusable(&self, _focusable: bool) {}
+
     #[inline]
     pub fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
 
     #[inline]
     pub fn set_cursor(&self, _: Cursor) {}
 
+    #[inline]
+    pub fn set_cursor_for_device(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _cursor: Cursor,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn set_pointer_capture(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _capture: bool,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, _: Position) -> Result<(), error::ExternalError> {
         Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn cursor_position(&self) -> Result<PhysicalPosition<f64>, error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     #[inline]
     pub fn set_cursor_grab(
         &self,
@@ -433,6 +498,13 @@ impl Window {
         Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
     }
 
+    pub fn set_hittest_regions(
+        &self,
+        _regions: &[window::Region],
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
     #[cfg(feature = "rwh_04")]
     #[inline]
     pub fn raw_window_handle_rwh_04(&self) -> rwh_04::RawWindowHandle {
@@ -491,10 +563,39 @@ impl Window {
         false
     }
 
+    pub fn visibility(&self) -> WindowVisibility {
+        WindowVisibility::new(true, false, false, self.has_focus())
+    }
+
+    pub fn tiling_state(&self) -> TilingState {
+        TilingState::Floating
+    }
+
     #[inline]
     pub fn set_theme(&self, _theme: Option<window::Theme>) {}
 
     pub fn set_content_protected(&self, _protected: bool) {}
+
+    pub fn set_capture_policy(&self, _policy: window::CapturePolicy) {}
+
+    #[inline]
+    pub fn set_performance_hint(&self, _hint: window::PerformanceHint) {}
+
+    #[inline]
+    pub fn set_preferred_frame_rate_range(&self, _range: Option<window::FrameRateRange>) {}
+
+    #[inline]
+    pub fn share(&self, _data: window::ShareData) {}
+
+    pub fn present_rgba(
+        &self,
+        _buffer: &[u8],
+        _size: PhysicalSize<u32>,
+    ) -> Result<(), error::ExternalError> {
+        Err(error::ExternalError::NotSupported(error::NotSupportedError::new()))
+    }
+
+    pub fn hide_to_tray(&self) {}
 }
 
 impl Drop for Window {
@@ -30,6 +30,7 @@ use crate::platform::web::CustomCursorError;
 #[derive(Debug)]
 pub(crate) enum CustomCursorSource {
     Image(CursorImage),
+    ImageSet(Vec<CursorImage>),
     Url { url: String, hotspot_x: u16, hotspot_y: u16 },
     Animation { duration: Duration, cursors: Vec<RootCustomCursor> },
 }
@@ -46,6 +47,10 @@ impl CustomCursorSource {
             rgba, width, height, hotspot_x, hotspot_y,
         )?))
     }
+
+    pub fn from_rgba_multi(images: Vec<CursorImage>) -> CustomCursorSource {
+        CustomCursorSource::ImageSet(images)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -76,6 +81,15 @@ impl CustomCursor {
                 from_rgba(event_loop.runner.window(), event_loop.runner.document().clone(), &image),
                 false,
             ),
+            CustomCursorSource::ImageSet(images) => Self::build_spawn(
+                event_loop,
+                from_image_set(
+                    event_loop.runner.window(),
+                    event_loop.runner.document().clone(),
+                    &images,
+                ),
+                false,
+            ),
             CustomCursorSource::Url { url, hotspot_x, hotspot_y } => Self::build_spawn(
                 event_loop,
                 from_url(UrlType::Plain(url), hotspot_x, hotspot_y),
@@ -436,8 +450,8 @@ enum ImageState {
 #[derive(Debug)]
 struct Image {
     style: String,
-    _object_url: Option<ObjectUrl>,
-    _image: HtmlImageElement,
+    _object_urls: Vec<ObjectUrl>,
+    _images: Vec<HtmlImageElement>,
 }
 
 impl From<Image> for ImageState {
@@ -497,18 +511,54 @@ fn from_rgba(
     document: Document,
     image: &CursorImage,
 ) -> impl Future<Output = Result<Image, CustomCursorError>> {
-    // 1. Create an `ImageData` from the RGBA data.
-    // 2. Create an `ImageBitmap` from the `ImageData`.
-    // 3. Draw `ImageBitmap` on an `HTMLCanvasElement`.
-    // 4. Create a `Blob` from the `HTMLCanvasElement`.
-    // 5. Create an object URL from the `Blob`.
-    // 6. Decode the image on an `HTMLImageElement` from the URL.
+    let url = object_url_from_rgba(window, document, image);
+    let CursorImage { hotspot_x, hotspot_y, .. } = *image;
 
-    // 1. Create an `ImageData` from the RGBA data.
+    async move { from_url(UrlType::Object(url.await?), hotspot_x, hotspot_y).await }
+}
+
+fn from_image_set(
+    window: &Window,
+    document: Document,
+    images: &[CursorImage],
+) -> impl Future<Output = Result<Image, CustomCursorError>> {
+    let urls: Vec<_> = images
+        .iter()
+        .map(|image| (object_url_from_rgba(window, document.clone(), image), image.scale_factor))
+        .collect();
+
+    // The hotspot is in CSS pixels, which are taken from the image with the smallest scale
+    // factor.
+    let base = &images[0];
+    let hotspot_x = (base.hotspot_x as f64 / base.scale_factor).round();
+    let hotspot_y = (base.hotspot_y as f64 / base.scale_factor).round();
+
+    async move {
+        let mut set = Vec::with_capacity(urls.len());
+        let mut object_urls = Vec::with_capacity(urls.len());
+        let mut images = Vec::with_capacity(urls.len());
+
+        for (url, scale_factor) in urls {
+            let url = url.await?;
+            images.push(decode(&url.0).await?);
+            set.push(format!("url({}) {scale_factor}x", url.0));
+            object_urls.push(url);
+        }
+
+        Ok(Image {
+            style: format!("image-set({}) {hotspot_x} {hotspot_y}, auto", set.join(", ")),
+            _object_urls: object_urls,
+            _images: images,
+        })
+    }
+}
+
+/// Create an `ImageData` from RGBA pixels, in rows of `width`.
+pub(super) fn image_data(rgba: &[u8], width: u32) -> Result<ImageData, wasm_bindgen::JsValue> {
     // Adapted from https://github.com/rust-windowing/softbuffer/blob/ab7688e2ed2e2eca51b3c4e1863a5bd7fe85800e/src/web.rs#L196-L223
     #[cfg(target_feature = "atomics")]
     // Can't share `SharedArrayBuffer` with `ImageData`.
-    let result = {
+    {
         use js_sys::{Uint8Array, Uint8ClampedArray};
         use wasm_bindgen::JsValue;
 
@@ -520,19 +570,31 @@ fn from_rgba(
             fn new(array: Uint8ClampedArray, sw: u32) -> Result<ImageDataExt, JsValue>;
         }
 
-        let array = Uint8Array::new_with_length(image.rgba.len() as u32);
-        array.copy_from(&image.rgba);
+        let array = Uint8Array::new_with_length(rgba.len() as u32);
+        array.copy_from(rgba);
         let array = Uint8ClampedArray::new(&array);
-        ImageDataExt::new(array, image.width as u32)
-            .map(JsValue::from)
-            .map(ImageData::unchecked_from_js)
-    };
+        ImageDataExt::new(array, width).map(JsValue::from).map(ImageData::unchecked_from_js)
+    }
     #[cfg(not(target_feature = "atomics"))]
-    let result = ImageData::new_with_u8_clamped_array(
-        wasm_bindgen::Clamped(&image.rgba),
-        image.width as u32,
-    );
-    let image_data = result.expect("found wrong image size");
+    {
+        ImageData::new_with_u8_clamped_array(wasm_bindgen::Clamped(rgba), width)
+    }
+}
+
+fn object_url_from_rgba(
+    window: &Window,
+    document: Document,
+    image: &CursorImage,
+) -> impl Future<Output = Result<ObjectUrl, CustomCursorError>> {
+    // 1. Create an `ImageData` from the RGBA data.
+    // 2. Create an `ImageBitmap` from the `ImageData`.
+    // 3. Draw `ImageBitmap` on an `HTMLCanvasElement`.
+    // 4. Create a `Blob` from the `HTMLCanvasElement`.
+    // 5. Create an object URL from the `Blob`.
+    // 6. Decode the image on an `HTMLImageElement` from the URL.
+
+    // 1. Create an `ImageData` from the RGBA data.
+    let image_data = image_data(&image.rgba, image.width as u32).expect("found wrong image size");
 
     // 2. Create an `ImageBitmap` from the `ImageData`.
     //
@@ -546,7 +608,7 @@ fn from_rgba(
             .expect("unexpected exception in `createImageBitmap()`"),
     );
 
-    let CursorImage { width, height, hotspot_x, hotspot_y, .. } = *image;
+    let CursorImage { width, height, .. } = *image;
     async move {
         let bitmap: ImageBitmap =
             bitmap.await.expect("found invalid state in `ImageData`").unchecked_into();
@@ -607,9 +669,8 @@ fn from_rgba(
         // 5. Create an object URL from the `Blob`.
         let url = Url::create_object_url_with_blob(&blob)
             .expect("unexpected exception in `URL.createObjectURL()`");
-        let url = UrlType::Object(ObjectUrl(url));
 
-        from_url(url, hotspot_x, hotspot_y).await
+        Ok(ObjectUrl(url))
     }
 }
 
@@ -618,9 +679,22 @@ async fn from_url(
     hotspot_x: u16,
     hotspot_y: u16,
 ) -> Result<Image, CustomCursorError> {
+    let image = decode(url.url()).await?;
+
+    Ok(Image {
+        style: format!("url({}) {hotspot_x} {hotspot_y}, auto", url.url()),
+        _object_urls: match url {
+            UrlType::Plain(_) => Vec::new(),
+            UrlType::Object(object_url) => vec![object_url],
+        },
+        _images: vec![image],
+    })
+}
+
+async fn decode(url: &str) -> Result<HtmlImageElement, CustomCursorError> {
     // 6. Decode the image on an `HTMLImageElement` from the URL.
     let image = HtmlImageElement::new().expect("unexpected exception in `new HtmlImageElement`");
-    image.set_src(url.url());
+    image.set_src(url);
     let result = JsFuture::from(image.decode()).await;
 
     if let Err(error) = result {
@@ -632,14 +706,7 @@ async fn from_url(
         return Err(CustomCursorError::Decode(error));
     }
 
-    Ok(Image {
-        style: format!("url({}) {hotspot_x} {hotspot_y}, auto", url.url()),
-        _object_url: match url {
-            UrlType::Plain(_) => None,
-            UrlType::Object(object_url) => Some(object_url),
-        },
-        _image: image,
-    })
+    Ok(image)
 }
 
 #[allow(clippy::await_holding_refcell_ref)] // false-positive
@@ -1,3 +1,5 @@
+use crate::event::SeatId;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct DeviceId(pub i32);
 
@@ -5,4 +7,8 @@ impl DeviceId {
     pub const unsafe fn dummy() -> Self {
         Self(0)
     }
+
+    pub fn seat_id(&self) -> Option<SeatId> {
+        None
+    }
 }
@@ -0,0 +1,133 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{Document, Element, Event, HtmlElement, KeyboardEvent};
+
+use super::backend::EventListenerHandle;
+use super::event_loop::runner;
+use super::OsError;
+use crate::dialog::{DialogButton, DialogSender, MessageDialog};
+use crate::error::ExternalError;
+
+const OVERLAY_STYLE: &str = concat!(
+    "position: fixed; inset: 0; z-index: 2147483647; display: flex; align-items: center; ",
+    "justify-content: center; background: rgba(0, 0, 0, 0.3); ",
+    "font: 14px system-ui, sans-serif; color-scheme: light dark;",
+);
+const BOX_STYLE: &str = concat!(
+    "max-width: min(400px, 90vw); padding: 16px; border-radius: 8px; background: Canvas; ",
+    "color: CanvasText; box-shadow: 0 4px 16px rgba(0, 0, 0, 0.4);",
+);
+const TITLE_STYLE: &str = "font-weight: bold; margin-bottom: 8px;";
+const MESSAGE_STYLE: &str = "white-space: pre-wrap; overflow-wrap: anywhere;";
+const BUTTONS_STYLE: &str = "display: flex; justify-content: flex-end; gap: 8px; margin-top: 16px;";
+const BUTTON_STYLE: &str = "min-width: 64px;";
+
+struct Inner {
+    overlay: Element,
+    sender: RefCell<Option<DialogSender<DialogButton>>>,
+    /// The listeners keep `Inner` alive until the dialog is closed.
+    listeners: RefCell<Vec<EventListenerHandle<dyn FnMut(Event)>>>,
+}
+
+/// Show the dialog as an overlay on top of the page, in the style of `window.confirm()`, which
+/// would block the page until it is closed.
+pub fn show_message_dialog(
+    runner: &runner::Shared,
+    dialog: MessageDialog,
+    sender: DialogSender<DialogButton>,
+) -> Result<(), ExternalError> {
+    let map_err = |error: JsValue| ExternalError::Os(os_error!(OsError(format!("{error:?}"))));
+    let document = runner.document();
+    let body = document.body().ok_or_else(|| {
+        ExternalError::Os(os_error!(OsError("the document has no body".to_owned())))
+    })?;
+
+    let overlay = create_element(document, "div", OVERLAY_STYLE).map_err(map_err)?;
+    let dialog_box = create_element(document, "div", BOX_STYLE).map_err(map_err)?;
+    dialog_box.set_attribute("role", "alertdialog").map_err(map_err)?;
+    dialog_box.set_attribute("aria-modal", "true").map_err(map_err)?;
+    overlay.append_child(&dialog_box).map_err(map_err)?;
+
+    if !dialog.title.is_empty() {
+        let title = create_element(document, "div", TITLE_STYLE).map_err(map_err)?;
+        title.set_text_content(Some(&dialog.title));
+        dialog_box.append_child(&title).map_err(map_err)?;
+    }
+    let message = create_element(document, "div", MESSAGE_STYLE).map_err(map_err)?;
+    message.set_text_content(Some(&dialog.message));
+    dialog_box.append_child(&message).map_err(map_err)?;
+
+    let button_row = create_element(document, "div", BUTTONS_STYLE).map_err(map_err)?;
+    dialog_box.append_child(&button_row).map_err(map_err)?;
+
+    let inner = Rc::new(Inner {
+        overlay,
+        sender: RefCell::new(Some(sender)),
+        listeners: RefCell::new(Vec::new()),
+    });
+
+    let mut listeners = Vec::new();
+    let mut buttons = Vec::new();
+    for &button in dialog.buttons.buttons() {
+        let element = create_element(document, "button", BUTTON_STYLE).map_err(map_err)?;
+        element.set_text_content(Some(button.label()));
+        button_row.append_child(&element).map_err(map_err)?;
+
+        listeners.push(EventListenerHandle::new(
+            element.clone(),
+            "click",
+            Closure::<dyn FnMut(Event)>::new({
+                let inner = Rc::clone(&inner);
+                move |_: Event| inner.close(button)
+            }),
+        ));
+        buttons.push(element);
+    }
+
+    let dismiss_button = dialog.buttons.dismiss_button();
+    listeners.push(EventListenerHandle::new(
+        inner.overlay.clone(),
+        "keydown",
+        Closure::<dyn FnMut(Event)>::new({
+            let inner = Rc::clone(&inner);
+            move |event: Event| {
+                if event.dyn_ref::<KeyboardEvent>().is_some_and(|event| event.key() == "Escape") {
+                    event.prevent_default();
+                    inner.close(dismiss_button);
+                }
+            }
+        }),
+    ));
+    *inner.listeners.borrow_mut() = listeners;
+
+    body.append_child(&inner.overlay).map_err(|error| {
+        drop(inner.listeners.take());
+        map_err(error)
+    })?;
+
+    // Like `window.confirm()`, pressing Enter chooses the first button.
+    if let Some(button) = buttons.first().and_then(|button| button.dyn_ref::<HtmlElement>()) {
+        let _ = button.focus();
+    }
+
+    Ok(())
+}
+
+fn create_element(document: &Document, name: &str, style: &str) -> Result<Element, JsValue> {
+    let element = document.create_element(name)?;
+    element.set_attribute("style", style)?;
+    Ok(element)
+}
+
+impl Inner {
+    fn close(&self, button: DialogButton) {
+        self.overlay.remove();
+        if let Some(sender) = self.sender.take() {
+            sender.send(button);
+        }
+        drop(self.listeners.take());
+    }
+}
@@ -13,7 +13,7 @@ pub(crate) mod runner;
 mod state;
 mod window_target;
 
-pub(crate) use proxy::EventLoopProxy;
+pub(crate) use proxy::{EventLoopProxy, EventLoopWaker};
 pub(crate) use window_target::{ActiveEventLoop, OwnedDisplayHandle};
 
 pub struct EventLoop<T: 'static> {
@@ -84,15 +84,45 @@ fn handle_event<T: 'static, A: ApplicationHandler<T>>(
         Event::NewEvents(cause) => app.new_events(target, cause),
         Event::WindowEvent { window_id, event } => app.window_event(target, window_id, event),
         Event::DeviceEvent { device_id, event } => app.device_event(target, device_id, event),
+        // The channel is empty if the event loop was only woken up by an `EventLoopWaker`.
         Event::UserEvent(_) => {
-            let event =
-                user_event_receiver.try_recv().expect("user event signaled but not received");
-            app.user_event(target, event);
+            if let Ok(event) = user_event_receiver.try_recv() {
+                app.user_event(target, event);
+            }
         },
+        Event::SurfaceCreated(window_id) => app.surface_created(target, window_id),
+        Event::SurfaceDestroyed(window_id) => app.surface_destroyed(target, window_id),
         Event::Suspended => app.suspended(target),
         Event::Resumed => app.resumed(target),
         Event::AboutToWait => app.about_to_wait(target),
+        Event::Idle { deadline } => app.idle(target, deadline),
+        Event::CloseRequestedByOS { respond } => app.close_requested_by_os(target, respond),
+        Event::Notification { id, event } => app.notification_event(target, id, event),
+        Event::Terminating => app.terminating(target),
         Event::LoopExiting => app.exiting(target),
         Event::MemoryWarning => app.memory_warning(target),
+        Event::SessionLocked => app.session_locked(target),
+        Event::SessionUnlocked => app.session_unlocked(target),
+        Event::DisplaySleep => app.display_sleep(target),
+        Event::DisplayWake => app.display_wake(target),
+        Event::LocaleChanged(locale) => app.locale_changed(target, locale),
+        Event::WorkAreaChanged => app.work_area_changed(target),
+        Event::Navigation(pop_state) => app.navigation(target, pop_state),
+        Event::ColorProfileChanged => app.color_profile_changed(target),
+        Event::ActivationRequest { args, activation_token } => {
+            app.activation_request(target, args, activation_token)
+        },
+        Event::UrlOpened(url) => app.url_opened(target, url),
+        Event::FilesOpened(paths) => app.files_opened(target, paths),
+        Event::DragHover { window_id, position, respond } => {
+            let _ = respond.respond(app.drag_hover(target, window_id, position));
+        },
+        Event::TrayActivated(window_id) => app.tray_activated(target, window_id),
+        Event::Signal(signal) => app.signal(target, signal),
+        Event::FdReady(token) => app.fd_ready(target, token),
+        Event::HandleSignaled(token) => app.handle_signaled(target, token),
+        Event::MonitorsChanged => app.monitors_changed(target),
+        Event::AudioInterrupted => app.audio_interrupted(target),
+        Event::AudioResumed => app.audio_resumed(target),
     }
 }
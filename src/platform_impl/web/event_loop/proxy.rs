@@ -20,6 +20,20 @@ impl<T: 'static> EventLoopProxy<T> {
         self.runner.wake();
         Ok(())
     }
+
+    pub fn waker(&self) -> EventLoopWaker {
+        EventLoopWaker(self.runner.clone())
+    }
+}
+
+/// Wakes up the `EventLoop` from any thread, without a message.
+#[derive(Clone)]
+pub struct EventLoopWaker(Waker<Weak<Execution>>);
+
+impl EventLoopWaker {
+    pub fn wake(&self) {
+        self.0.wake();
+    }
 }
 
 impl<T: 'static> Clone for EventLoopProxy<T> {
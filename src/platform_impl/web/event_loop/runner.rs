@@ -1,26 +1,36 @@
 use super::super::main_thread::MainThreadMarker;
 use super::super::DeviceId;
 use super::backend;
+use super::backend::event::PointerType;
 use super::state::State;
 use crate::dpi::PhysicalSize;
 use crate::event::{
-    DeviceEvent, DeviceId as RootDeviceId, ElementState, Event, RawKeyEvent, StartCause,
-    WindowEvent,
+    CloseResponse, DeviceEvent, DeviceId as RootDeviceId, ElementState, Event, PopState,
+    PrintPhase, RawKeyEvent, StartCause, Url, WindowEvent,
 };
 use crate::event_loop::{ControlFlow, DeviceEvents};
+use crate::frame_budget;
 use crate::platform::web::PollStrategy;
 use crate::platform_impl::platform::backend::EventListenerHandle;
 use crate::platform_impl::platform::r#async::{DispatchRunner, Waker, WakerSpawner};
 use crate::platform_impl::platform::window::Inner;
 use crate::window::WindowId;
 
+use js_sys::{Array, Function, Reflect};
 use std::cell::{Cell, RefCell};
 use std::collections::{HashSet, VecDeque};
 use std::iter;
 use std::ops::Deref;
+use std::path::PathBuf;
 use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use wasm_bindgen::prelude::Closure;
-use web_sys::{Document, KeyboardEvent, PageTransitionEvent, PointerEvent, WheelEvent};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    BeforeUnloadEvent, Document, Element, KeyboardEvent, PageTransitionEvent, PointerEvent,
+    WheelEvent,
+};
 use web_time::{Duration, Instant};
 
 pub struct Shared(Rc<Execution>);
@@ -40,7 +50,10 @@ pub struct Execution {
     proxy_spawner: WakerSpawner<Weak<Self>>,
     control_flow: Cell<ControlFlow>,
     poll_strategy: Cell<PollStrategy>,
+    timer_resolution: Cell<Duration>,
     exit: Cell<bool>,
+    /// Whether an exit future passed to `exit_with` is pending.
+    exit_pending: Cell<bool>,
     runner: RefCell<RunnerEnum>,
     suspended: Cell<bool>,
     event_loop_recreation: Cell<bool>,
@@ -51,6 +64,7 @@ pub struct Execution {
     #[allow(clippy::type_complexity)]
     all_canvases: RefCell<Vec<(WindowId, Weak<RefCell<backend::Canvas>>, DispatchRunner<Inner>)>>,
     redraw_pending: RefCell<HashSet<WindowId>>,
+    redraw_buffer: Cell<Vec<WindowId>>,
     destroy_pending: RefCell<VecDeque<WindowId>>,
     page_transition_event_handle: RefCell<Option<backend::PageTransitionEventHandle>>,
     device_events: Cell<DeviceEvents>,
@@ -61,6 +75,19 @@ pub struct Execution {
     on_key_press: OnEventHandle<KeyboardEvent>,
     on_key_release: OnEventHandle<KeyboardEvent>,
     on_visibility_change: OnEventHandle<web_sys::Event>,
+    on_freeze: OnEventHandle<web_sys::Event>,
+    on_resume: OnEventHandle<web_sys::Event>,
+    on_language_change: OnEventHandle<web_sys::Event>,
+    on_screen_change: OnEventHandle<web_sys::Event>,
+    on_before_unload: OnEventHandle<BeforeUnloadEvent>,
+    on_before_print: OnEventHandle<web_sys::Event>,
+    on_after_print: OnEventHandle<web_sys::Event>,
+    on_pop_state: OnEventHandle<web_sys::Event>,
+    /// The `launchQueue` of the page, and the consumer set on it.
+    #[allow(clippy::type_complexity)]
+    on_launch: RefCell<Option<(JsValue, Closure<dyn FnMut(JsValue)>)>>,
+    /// The `FileSystemFileHandle`s of the last launch with files.
+    opened_file_handles: RefCell<Vec<JsValue>>,
 }
 
 enum RunnerEnum {
@@ -99,7 +126,9 @@ impl Runner {
         Some(match self.state {
             State::Init => StartCause::Init,
             State::Poll { .. } => StartCause::Poll,
-            State::Wait { start } => StartCause::WaitCancelled { start, requested_resume: None },
+            State::Wait { start, .. } => {
+                StartCause::WaitCancelled { start, requested_resume: None }
+            },
             State::WaitUntil { start, end, .. } => {
                 StartCause::WaitCancelled { start, requested_resume: Some(end) }
             },
@@ -145,6 +174,8 @@ impl Shared {
                 proxy_spawner,
                 control_flow: Cell::new(ControlFlow::default()),
                 poll_strategy: Cell::new(PollStrategy::default()),
+                timer_resolution: Cell::new(Duration::MAX),
+                exit_pending: Cell::new(false),
                 exit: Cell::new(false),
                 runner: RefCell::new(RunnerEnum::Pending),
                 suspended: Cell::new(false),
@@ -155,6 +186,7 @@ impl Shared {
                 id: RefCell::new(0),
                 all_canvases: RefCell::new(Vec::new()),
                 redraw_pending: RefCell::new(HashSet::new()),
+                redraw_buffer: Cell::new(Vec::new()),
                 destroy_pending: RefCell::new(VecDeque::new()),
                 page_transition_event_handle: RefCell::new(None),
                 device_events: Cell::default(),
@@ -165,6 +197,16 @@ impl Shared {
                 on_key_press: RefCell::new(None),
                 on_key_release: RefCell::new(None),
                 on_visibility_change: RefCell::new(None),
+                on_freeze: RefCell::new(None),
+                on_resume: RefCell::new(None),
+                on_language_change: RefCell::new(None),
+                on_screen_change: RefCell::new(None),
+                on_before_unload: RefCell::new(None),
+                on_before_print: RefCell::new(None),
+                on_after_print: RefCell::new(None),
+                on_pop_state: RefCell::new(None),
+                on_launch: RefCell::new(None),
+                opened_file_handles: RefCell::new(Vec::new()),
             }
         }))
     }
@@ -181,6 +223,10 @@ impl Shared {
         &self.0.document
     }
 
+    pub fn opened_file_handles(&self) -> Vec<JsValue> {
+        self.0.opened_file_handles.borrow().clone()
+    }
+
     pub fn add_canvas(
         &self,
         id: WindowId,
@@ -190,6 +236,15 @@ impl Shared {
         self.0.all_canvases.borrow_mut().push((id, canvas, runner));
     }
 
+    /// Returns the window whose canvas is the given element.
+    pub fn window_of(&self, element: &Element) -> Option<WindowId> {
+        self.0.all_canvases.borrow().iter().find_map(|(id, canvas, _)| {
+            let canvas = canvas.upgrade()?;
+            let is_canvas = canvas.borrow().raw().is_same_node(Some(element));
+            is_canvas.then_some(*id)
+        })
+    }
+
     pub fn notify_destroy_window(&self, id: WindowId) {
         self.0.destroy_pending.borrow_mut().push_back(id);
     }
@@ -229,6 +284,78 @@ impl Shared {
             },
         ));
 
+        let runner = self.clone();
+        *self.0.on_before_unload.borrow_mut() = Some(EventListenerHandle::new(
+            self.window().clone(),
+            "beforeunload",
+            Closure::new(move |event: BeforeUnloadEvent| {
+                if runner.handle_before_unload() {
+                    event.prevent_default();
+                    // Required by older browsers.
+                    event.set_return_value("");
+                }
+            }),
+        ));
+
+        let runner = self.clone();
+        *self.0.on_before_print.borrow_mut() = Some(EventListenerHandle::new(
+            self.window().clone(),
+            "beforeprint",
+            Closure::new(move |_| runner.handle_print(PrintPhase::Started)),
+        ));
+        let runner = self.clone();
+        *self.0.on_after_print.borrow_mut() = Some(EventListenerHandle::new(
+            self.window().clone(),
+            "afterprint",
+            Closure::new(move |_| runner.handle_print(PrintPhase::Finished)),
+        ));
+
+        let runner = self.clone();
+        *self.0.on_pop_state.borrow_mut() = Some(EventListenerHandle::new(
+            self.window().clone(),
+            "popstate",
+            Closure::new(move |_| match runner.window().location().href() {
+                Ok(url) => runner.send_event(Event::Navigation(PopState { url })),
+                Err(error) => tracing::error!("failed to get the URL of the page: {error:?}"),
+            }),
+        ));
+
+        // Installed web applications are launched through the launch queue, e.g. for the
+        // protocol handlers of their manifest.
+        let launch_queue = Reflect::get(self.window(), &JsValue::from_str("launchQueue"))
+            .unwrap_or(JsValue::UNDEFINED);
+        if !launch_queue.is_undefined() {
+            let runner = self.clone();
+            let consumer = Closure::new(move |params: JsValue| {
+                let url = Reflect::get(&params, &JsValue::from_str("targetURL"))
+                    .ok()
+                    .and_then(|url| url.as_string());
+                if let Some(url) = url.as_deref().and_then(Url::parse) {
+                    runner.send_event(Event::UrlOpened(url));
+                }
+
+                let handles: Vec<JsValue> = Reflect::get(&params, &JsValue::from_str("files"))
+                    .ok()
+                    .and_then(|files| files.dyn_into::<Array>().ok())
+                    .map(|files| files.iter().collect())
+                    .unwrap_or_default();
+                if !handles.is_empty() {
+                    let paths = handles
+                        .iter()
+                        .filter_map(|handle| Reflect::get(handle, &JsValue::from_str("name")).ok())
+                        .filter_map(|name| name.as_string())
+                        .map(PathBuf::from)
+                        .collect();
+                    *runner.0.opened_file_handles.borrow_mut() = handles;
+                    runner.send_event(Event::FilesOpened(paths));
+                }
+            });
+            match set_launch_consumer(&launch_queue, consumer.as_ref()) {
+                Ok(()) => *self.0.on_launch.borrow_mut() = Some((launch_queue, consumer)),
+                Err(error) => tracing::error!("failed to consume the launch queue: {error:?}"),
+            }
+        }
+
         let runner = self.clone();
         let window = self.window().clone();
         *self.0.on_mouse_move.borrow_mut() = Some(EventListenerHandle::new(
@@ -239,9 +366,9 @@ impl Shared {
                     return;
                 }
 
-                let pointer_type = event.pointer_type();
+                let pointer_type = backend::event::pointer_type(&event);
 
-                if pointer_type != "mouse" {
+                if pointer_type != PointerType::Mouse {
                     return;
                 }
 
@@ -250,7 +377,8 @@ impl Shared {
 
                 if let Some(button) = backend::event::mouse_button(&event) {
                     debug_assert_eq!(
-                        pointer_type, "mouse",
+                        pointer_type,
+                        PointerType::Mouse,
                         "expect pointer type of a chorded button event to be a mouse"
                     );
 
@@ -317,7 +445,7 @@ impl Shared {
                     return;
                 }
 
-                if event.pointer_type() != "mouse" {
+                if backend::event::pointer_type(&event) != PointerType::Mouse {
                     return;
                 }
 
@@ -340,7 +468,7 @@ impl Shared {
                     return;
                 }
 
-                if event.pointer_type() != "mouse" {
+                if backend::event::pointer_type(&event) != PointerType::Mouse {
                     return;
                 }
 
@@ -412,11 +540,74 @@ impl Shared {
                                     event: WindowEvent::Occluded(!is_visible),
                                 });
                             }
+
+                            if let Some(visibility) = canvas.borrow().update_visibility() {
+                                runner.send_event(Event::WindowEvent {
+                                    window_id: *id,
+                                    event: WindowEvent::VisibilityChanged(visibility),
+                                });
+                            }
                         }
                     }
                 }
             }),
         ));
+        // Browsers freeze hidden pages, e.g. when the device goes to sleep, which is the closest to
+        // the display going to sleep that can be detected.
+        let runner = self.clone();
+        *self.0.on_freeze.borrow_mut() = Some(EventListenerHandle::new(
+            self.document().clone(),
+            "freeze",
+            Closure::new(move |_| runner.send_event(Event::DisplaySleep)),
+        ));
+        let runner = self.clone();
+        *self.0.on_resume.borrow_mut() = Some(EventListenerHandle::new(
+            self.document().clone(),
+            "resume",
+            Closure::new(move |_| runner.send_event(Event::DisplayWake)),
+        ));
+        let runner = self.clone();
+        *self.0.on_language_change.borrow_mut() = Some(EventListenerHandle::new(
+            self.window().clone(),
+            "languagechange",
+            Closure::new(move |_| {
+                if let Some(locale) = backend::locale(runner.window()) {
+                    runner.send_event(Event::LocaleChanged(locale));
+                }
+            }),
+        ));
+        // Only some browsers fire `change` events on the screen.
+        if let Some(screen) = backend::screen(self.window()) {
+            let runner = self.clone();
+            *self.0.on_screen_change.borrow_mut() = Some(EventListenerHandle::new(
+                screen,
+                "change",
+                Closure::new(move |_| runner.send_event(Event::WorkAreaChanged)),
+            ));
+        }
+    }
+
+    /// Send `VisibilityChanged` if the visibility of the window changed.
+    pub fn update_visibility(&self, id: WindowId) {
+        let visibility = self
+            .0
+            .all_canvases
+            .borrow()
+            .iter()
+            .find(|(canvas_id, ..)| *canvas_id == id)
+            .and_then(|(_, canvas, _)| canvas.upgrade())
+            .and_then(|canvas| {
+                // The canvas is still borrowed while its window is being created.
+                let canvas = canvas.try_borrow().ok()?;
+                canvas.update_visibility()
+            });
+
+        if let Some(visibility) = visibility {
+            self.send_event(Event::WindowEvent {
+                window_id: id,
+                event: WindowEvent::VisibilityChanged(visibility),
+            });
+        }
     }
 
     // Generate a strictly increasing ID
@@ -453,6 +644,33 @@ impl Shared {
         self.run_until_cleared(iter::once(start_cause));
     }
 
+    // Run the logic for the idle period reported while waiting, capped to the `WaitUntil` time
+    fn request_idle(&self, end: Option<Instant>) -> Option<backend::IdleRequest> {
+        let cloned = self.clone();
+        backend::IdleRequest::new(self.window(), move |remaining| {
+            let deadline = Instant::now() + remaining;
+            cloned.idle(end.map_or(deadline, |end| deadline.min(end)))
+        })
+    }
+
+    fn idle(&self, deadline: Instant) {
+        let control_flow = self.control_flow();
+        self.handle_event(Event::Idle { deadline });
+
+        // Idle periods are reported only once per wait, so anything requested from the idle
+        // callback has to be picked up here.
+        if self.exiting() || self.control_flow() != control_flow {
+            self.apply_control_flow();
+            if self.is_closed() {
+                self.handle_loop_destroyed();
+                return;
+            }
+        }
+        if !self.0.redraw_pending.borrow().is_empty() {
+            self.send_events::<EventWrapper>(iter::empty());
+        }
+    }
+
     // Add an event to the event loop runner, from the user or an event handler
     //
     // It will determine if the event should be immediately sent to the user or buffered for later
@@ -539,14 +757,17 @@ impl Shared {
         }
         self.process_destroy_pending_windows();
 
-        // Collect all of the redraw events to avoid double-locking the RefCell
-        let redraw_events: Vec<WindowId> = self.0.redraw_pending.borrow_mut().drain().collect();
-        for window_id in redraw_events {
+        // Collect all of the redraw events to avoid double-locking the RefCell. The buffer is
+        // reused across iterations, so that redrawing doesn't allocate every frame.
+        let mut redraw_events = self.0.redraw_buffer.take();
+        redraw_events.extend(self.0.redraw_pending.borrow_mut().drain());
+        for window_id in redraw_events.drain(..) {
             self.handle_event(Event::WindowEvent {
                 window_id,
                 event: WindowEvent::RedrawRequested,
             });
         }
+        self.0.redraw_buffer.replace(redraw_events);
 
         self.handle_event(Event::AboutToWait);
 
@@ -558,7 +779,48 @@ impl Shared {
         }
     }
 
+    /// Returns whether the user should be asked to confirm leaving the page.
+    fn handle_before_unload(&self) -> bool {
+        // The event can be dispatched synchronously while handling another event, e.g. when the
+        // application navigates away.
+        if !self.0.exit_pending.get() && self.0.runner.try_borrow_mut().is_ok() {
+            let cancelled = Arc::new(AtomicBool::new(false));
+            self.handle_event(Event::CloseRequestedByOS {
+                respond: CloseResponse::new(Arc::downgrade(&cancelled)),
+            });
+            if cancelled.load(Ordering::Relaxed) {
+                return true;
+            }
+        }
+
+        self.0.exit_pending.get()
+    }
+
+    fn handle_print(&self, phase: PrintPhase) {
+        let events: Vec<_> = self
+            .0
+            .all_canvases
+            .borrow()
+            .iter()
+            .map(|(window_id, ..)| Event::WindowEvent {
+                window_id: *window_id,
+                event: WindowEvent::PrintRequested(phase),
+            })
+            .collect();
+
+        // The page is captured right after `beforeprint`, so the events can't wait for the next
+        // poll. They are only queued if `print()` is called while handling another event.
+        if self.0.runner.try_borrow_mut().is_ok() {
+            for event in events {
+                self.handle_event(event);
+            }
+        } else {
+            self.send_events(events);
+        }
+    }
+
     fn handle_unload(&self) {
+        self.handle_event(Event::Terminating);
         self.exit();
         self.apply_control_flow();
         // We don't call `handle_loop_destroyed` here because we don't need to
@@ -626,16 +888,24 @@ impl Shared {
         } else {
             match self.control_flow() {
                 ControlFlow::Poll => {
+                    // Polling with the scheduler would compete with drawing frames that already
+                    // miss their budget.
+                    let strategy = match self.poll_strategy() {
+                        PollStrategy::Scheduler if frame_budget::over_budget() => {
+                            PollStrategy::IdleCallback
+                        },
+                        strategy => strategy,
+                    };
                     let cloned = self.clone();
                     State::Poll {
-                        _request: backend::Schedule::new(
-                            self.poll_strategy(),
-                            self.window(),
-                            move || cloned.poll(),
-                        ),
+                        _request: backend::Schedule::new(strategy, self.window(), move || {
+                            cloned.poll()
+                        }),
                     }
                 },
-                ControlFlow::Wait => State::Wait { start: Instant::now() },
+                ControlFlow::Wait => {
+                    State::Wait { _idle: self.request_idle(None), start: Instant::now() }
+                },
                 ControlFlow::WaitUntil(end) => {
                     let start = Instant::now();
 
@@ -644,12 +914,14 @@ impl Shared {
                     let cloned = self.clone();
 
                     State::WaitUntil {
+                        _idle: self.request_idle(Some(end)),
                         start,
                         end,
                         _timeout: backend::Schedule::new_with_duration(
                             self.window(),
                             move || cloned.resume_time_reached(start, end),
                             delay,
+                            self.0.timer_resolution.get(),
                         ),
                     }
                 },
@@ -672,6 +944,17 @@ impl Shared {
         *self.0.on_key_press.borrow_mut() = None;
         *self.0.on_key_release.borrow_mut() = None;
         *self.0.on_visibility_change.borrow_mut() = None;
+        *self.0.on_freeze.borrow_mut() = None;
+        *self.0.on_resume.borrow_mut() = None;
+        *self.0.on_language_change.borrow_mut() = None;
+        *self.0.on_screen_change.borrow_mut() = None;
+        *self.0.on_before_unload.borrow_mut() = None;
+        *self.0.on_before_print.borrow_mut() = None;
+        *self.0.on_after_print.borrow_mut() = None;
+        *self.0.on_pop_state.borrow_mut() = None;
+        if let Some((launch_queue, _)) = self.0.on_launch.borrow_mut().take() {
+            let _ = set_launch_consumer(&launch_queue, &Function::new_no_args(""));
+        }
         // Dropping the `Runner` drops the event handler closure, which will in
         // turn drop all `Window`s moved into the closure.
         *self.0.runner.borrow_mut() = RunnerEnum::Destroyed;
@@ -759,6 +1042,14 @@ impl Shared {
         self.0.poll_strategy.get()
     }
 
+    pub(crate) fn set_timer_resolution(&self, resolution: Duration) {
+        self.0.timer_resolution.set(resolution)
+    }
+
+    pub(crate) fn set_exit_pending(&self, pending: bool) {
+        self.0.exit_pending.set(pending)
+    }
+
     pub(crate) fn waker(&self) -> Waker<Weak<Execution>> {
         self.0.proxy_spawner.waker()
     }
@@ -774,3 +1065,9 @@ impl From<Event<()>> for EventWrapper {
         Self::Event(value)
     }
 }
+
+fn set_launch_consumer(launch_queue: &JsValue, consumer: &JsValue) -> Result<(), JsValue> {
+    let set_consumer: Function =
+        Reflect::get(launch_queue, &JsValue::from_str("setConsumer"))?.dyn_into()?;
+    set_consumer.call1(launch_queue, consumer).map(|_| ())
+}
@@ -5,9 +5,19 @@ use web_time::Instant;
 #[derive(Debug)]
 pub enum State {
     Init,
-    WaitUntil { _timeout: backend::Schedule, start: Instant, end: Instant },
-    Wait { start: Instant },
-    Poll { _request: backend::Schedule },
+    WaitUntil {
+        _timeout: backend::Schedule,
+        _idle: Option<backend::IdleRequest>,
+        start: Instant,
+        end: Instant,
+    },
+    Wait {
+        _idle: Option<backend::IdleRequest>,
+        start: Instant,
+    },
+    Poll {
+        _request: backend::Schedule,
+    },
     Exit,
 }
 
@@ -2,40 +2,85 @@ use std::cell::{Cell, RefCell};
 use std::clone::Clone;
 use std::collections::vec_deque::IntoIter as VecDequeIter;
 use std::collections::VecDeque;
+use std::ffi::OsString;
 use std::iter;
 use std::rc::{Rc, Weak};
+use std::sync::{Arc, Mutex};
 
+use wasm_bindgen::JsValue;
 use web_sys::Element;
+use web_time::Duration;
 
-use super::super::monitor::MonitorHandle;
-use super::super::KeyEventExtra;
+use super::super::monitor::{MonitorHandle, VideoModeHandle};
+use super::super::{KeyEventExtra, OsError, PlatformNotification};
 use super::device::DeviceId;
 use super::runner::{EventWrapper, Execution};
 use super::window::WindowId;
 use super::{backend, runner};
+use crate::dialog::{DialogButton, DialogSender, MessageDialog};
+use crate::dpi::PhysicalPosition;
+use crate::error::{ExternalError, NotSupportedError};
 use crate::event::{
-    DeviceId as RootDeviceId, ElementState, Event, KeyEvent, Touch, TouchPhase, WindowEvent,
+    DeviceId as RootDeviceId, DropOperation, DropResponse, ElementState, Event, KeyEvent,
+    Modifiers, Seat, Touch, TouchPhase, Transition, WindowEvent,
 };
 use crate::event_loop::{ControlFlow, DeviceEvents};
-use crate::keyboard::ModifiersState;
-use crate::platform::web::{CustomCursorFuture, PollStrategy};
+use crate::keyboard::{Key, KeyLocation, ModifiersKeys, ModifiersState};
+use crate::locale::LanguageTag;
+use crate::monitor::Rect;
+use crate::notification::{NotificationAttributes, NotificationId};
+use crate::platform::web::{CustomCursorFuture, Permission, PermissionFuture, PollStrategy};
 use crate::platform_impl::platform::cursor::CustomCursor;
+use crate::platform_impl::platform::permission::PermissionFuture as PlatformPermissionFuture;
 use crate::platform_impl::platform::r#async::Waker;
+use crate::session::{SessionCapabilities, SessionInfo, SessionKind};
 use crate::window::{
-    CustomCursor as RootCustomCursor, CustomCursorSource, Theme, WindowId as RootWindowId,
+    CustomCursor as RootCustomCursor, CustomCursorSource, Theme, TilingState,
+    WindowId as RootWindowId,
 };
 
+/// Invalid URLs and URLs of other origins throw a `SecurityError`.
+fn history_error(error: JsValue) -> ExternalError {
+    ExternalError::Os(os_error!(OsError(format!("{error:?}"))))
+}
+
 #[derive(Default)]
-struct ModifiersShared(Rc<Cell<ModifiersState>>);
+struct ModifiersShared(Rc<Cell<Modifiers>>);
 
 impl ModifiersShared {
     fn set(&self, new: ModifiersState) {
-        self.0.set(new)
+        self.0.set(Modifiers {
+            state: new,
+            pressed_mods: self.0.get().pressed_mods.retain_active(new),
+        })
     }
 
     fn get(&self) -> ModifiersState {
+        self.0.get().state
+    }
+
+    fn modifiers(&self) -> Modifiers {
         self.0.get()
     }
+
+    /// Track the side of pressed modifier keys, returning whether the modifiers changed.
+    fn key(
+        &self,
+        key: &Key,
+        location: KeyLocation,
+        state: ElementState,
+        active_modifiers: ModifiersState,
+    ) -> bool {
+        let old = self.0.get();
+        let mut pressed_mods = old.pressed_mods;
+        pressed_mods.set(ModifiersKeys::from_key(key, location), state == ElementState::Pressed);
+        let new = Modifiers {
+            state: active_modifiers,
+            pressed_mods: pressed_mods.retain_active(active_modifiers),
+        };
+        self.0.set(new);
+        old != new
+    }
 }
 
 impl Clone for ModifiersShared {
@@ -48,11 +93,17 @@ impl Clone for ModifiersShared {
 pub struct ActiveEventLoop {
     pub(crate) runner: runner::Shared,
     modifiers: ModifiersShared,
+    /// The click count the browser reported with the last mouse press, if any.
+    click_count: Rc<Cell<Option<u32>>>,
 }
 
 impl ActiveEventLoop {
     pub fn new() -> Self {
-        Self { runner: runner::Shared::new(), modifiers: ModifiersShared::default() }
+        Self {
+            runner: runner::Shared::new(),
+            modifiers: ModifiersShared::default(),
+            click_count: Rc::default(),
+        }
     }
 
     pub fn run(&self, event_handler: Box<runner::EventHandler>, event_loop_recreation: bool) {
@@ -72,6 +123,58 @@ impl ActiveEventLoop {
         CustomCursorFuture(CustomCursor::new_async(self, source.inner))
     }
 
+    pub fn show_notification(
+        &self,
+        id: NotificationId,
+        attributes: NotificationAttributes,
+    ) -> Result<PlatformNotification, ExternalError> {
+        PlatformNotification::new(&self.runner, id, attributes)
+    }
+
+    pub fn show_message_dialog(
+        &self,
+        dialog: MessageDialog,
+        sender: DialogSender<DialogButton>,
+    ) -> Result<(), ExternalError> {
+        super::super::dialog::show_message_dialog(&self.runner, dialog, sender)
+    }
+
+    pub fn work_area(&self) -> Rect {
+        backend::work_area(self.runner.window())
+    }
+
+    pub fn opened_file_handles(&self) -> Vec<JsValue> {
+        self.runner.opened_file_handles()
+    }
+
+    pub fn push_state(&self, url: &str) -> Result<(), ExternalError> {
+        let history = self.runner.window().history().map_err(history_error)?;
+        history.push_state_with_url(&JsValue::NULL, "", Some(url)).map_err(history_error)
+    }
+
+    pub fn replace_state(&self, url: &str) -> Result<(), ExternalError> {
+        let history = self.runner.window().history().map_err(history_error)?;
+        history.replace_state_with_url(&JsValue::NULL, "", Some(url)).map_err(history_error)
+    }
+
+    pub fn query_permission(&self, permission: Permission) -> PermissionFuture {
+        PermissionFuture(PlatformPermissionFuture::query(self.runner.window().clone(), permission))
+    }
+
+    pub fn request_permission(&self, permission: Permission) -> PermissionFuture {
+        PermissionFuture(PlatformPermissionFuture::request(
+            self.runner.window().clone(),
+            permission,
+        ))
+    }
+
+    pub fn permission_changed(&self, permission: Permission) -> PermissionFuture {
+        PermissionFuture(PlatformPermissionFuture::changed(
+            self.runner.window().clone(),
+            permission,
+        ))
+    }
+
     pub fn register(&self, canvas: &Rc<RefCell<backend::Canvas>>, id: WindowId) {
         let canvas_clone = canvas.clone();
         let mut canvas = canvas.borrow_mut();
@@ -98,6 +201,7 @@ impl ActiveEventLoop {
                 window_id: RootWindowId(id),
                 event: WindowEvent::Focused(false),
             })));
+            runner.update_visibility(RootWindowId(id));
         });
 
         let runner = self.runner.clone();
@@ -108,6 +212,7 @@ impl ActiveEventLoop {
                     window_id: RootWindowId(id),
                     event: WindowEvent::Focused(true),
                 });
+                runner.update_visibility(RootWindowId(id));
             }
         });
 
@@ -134,13 +239,12 @@ impl ActiveEventLoop {
         let modifiers = self.modifiers.clone();
         canvas.on_keyboard_press(
             move |physical_key, logical_key, text, location, repeat, active_modifiers| {
-                let modifiers_changed = (modifiers.get() != active_modifiers).then(|| {
-                    modifiers.set(active_modifiers);
-                    Event::WindowEvent {
+                let modifiers_changed = modifiers
+                    .key(&logical_key, location, ElementState::Pressed, active_modifiers)
+                    .then(|| Event::WindowEvent {
                         window_id: RootWindowId(id),
-                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
-                    }
-                });
+                        event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
+                    });
 
                 let device_id = RootDeviceId(unsafe { DeviceId::dummy() });
 
@@ -170,13 +274,12 @@ impl ActiveEventLoop {
         let modifiers = self.modifiers.clone();
         canvas.on_keyboard_release(
             move |physical_key, logical_key, text, location, repeat, active_modifiers| {
-                let modifiers_changed = (modifiers.get() != active_modifiers).then(|| {
-                    modifiers.set(active_modifiers);
-                    Event::WindowEvent {
+                let modifiers_changed = modifiers
+                    .key(&logical_key, location, ElementState::Released, active_modifiers)
+                    .then(|| Event::WindowEvent {
                         window_id: RootWindowId(id),
-                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
-                    }
-                });
+                        event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
+                    });
 
                 let device_id = RootDeviceId(unsafe { DeviceId::dummy() });
 
@@ -202,6 +305,14 @@ impl ActiveEventLoop {
             },
         );
 
+        let runner = self.runner.clone();
+        canvas.on_ime(move |ime| {
+            runner.send_event(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::Ime(ime),
+            })
+        });
+
         let has_focus = canvas.has_focus.clone();
         canvas.on_cursor_leave({
             let runner = self.runner.clone();
@@ -213,7 +324,7 @@ impl ActiveEventLoop {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
                         window_id: RootWindowId(id),
-                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                        event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                     }
                 });
 
@@ -240,7 +351,7 @@ impl ActiveEventLoop {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
                         window_id: RootWindowId(id),
-                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                        event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                     }
                 });
 
@@ -257,6 +368,38 @@ impl ActiveEventLoop {
             }
         });
 
+        // While the mouse is captured by the canvas, the other canvases don't receive boundary
+        // events, so they are emitted from here.
+        canvas.on_pointer_drag({
+            let runner = self.runner.clone();
+            let hovered = Cell::new(Some(id));
+
+            move |pointer_id, element: Option<Option<Element>>| {
+                let Some(element) = element else {
+                    hovered.set(Some(id));
+                    return;
+                };
+
+                let target = element.and_then(|element| runner.window_of(&element));
+                let previous = hovered.replace(target);
+                if previous == target {
+                    return;
+                }
+
+                let device_id = RootDeviceId(DeviceId(pointer_id));
+                let left = previous.map(|previous| Event::WindowEvent {
+                    window_id: RootWindowId(previous),
+                    event: WindowEvent::CursorLeft { device_id },
+                });
+                let entered = target.map(|target| Event::WindowEvent {
+                    window_id: RootWindowId(target),
+                    event: WindowEvent::CursorEntered { device_id },
+                });
+                runner.send_events(left.into_iter().chain(entered));
+            }
+        });
+
+        let cursor_position = canvas.cursor_position.clone();
         canvas.on_cursor_move(
             {
                 let runner = self.runner.clone();
@@ -268,7 +411,7 @@ impl ActiveEventLoop {
                         modifiers.set(active_modifiers);
                         runner.send_event(Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                            event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                         })
                     }
                 }
@@ -284,12 +427,13 @@ impl ActiveEventLoop {
                             modifiers.set(active_modifiers);
                             Event::WindowEvent {
                                 window_id: RootWindowId(id),
-                                event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                                event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                             }
                         });
 
                     runner.send_events(modifiers.into_iter().chain(events.flat_map(|position| {
                         let device_id = RootDeviceId(DeviceId(pointer_id));
+                        cursor_position.set(Some(position));
 
                         iter::once(Event::WindowEvent {
                             window_id: RootWindowId(id),
@@ -309,7 +453,7 @@ impl ActiveEventLoop {
                             modifiers.set(active_modifiers);
                             Event::WindowEvent {
                                 window_id: RootWindowId(id),
-                                event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                                event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                             }
                         });
 
@@ -342,7 +486,7 @@ impl ActiveEventLoop {
                             modifiers.set(active_modifiers);
                             Event::WindowEvent {
                                 window_id: RootWindowId(id),
-                                event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                                event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                             }
                         });
 
@@ -381,7 +525,7 @@ impl ActiveEventLoop {
                         modifiers.set(active_modifiers);
                         runner.send_event(Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                            event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                         })
                     }
                 }
@@ -389,17 +533,19 @@ impl ActiveEventLoop {
             {
                 let runner = self.runner.clone();
                 let modifiers = self.modifiers.clone();
+                let click_count = self.click_count.clone();
 
-                move |active_modifiers, pointer_id, position, button| {
+                move |active_modifiers, pointer_id, position, button, count| {
                     let modifiers = (modifiers.get() != active_modifiers).then(|| {
                         modifiers.set(active_modifiers);
                         Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                            event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                         }
                     });
 
                     let device_id: RootDeviceId = RootDeviceId(DeviceId(pointer_id));
+                    click_count.set(count);
 
                     // A mouse down event may come in without any prior CursorMoved events,
                     // therefore we should send a CursorMoved event to make sure that the
@@ -429,7 +575,7 @@ impl ActiveEventLoop {
                         modifiers.set(active_modifiers);
                         Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                            event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                         }
                     });
 
@@ -460,7 +606,7 @@ impl ActiveEventLoop {
                         modifiers.set(active_modifiers);
                         runner.send_event(Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                            event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                         });
                     }
                 }
@@ -476,7 +622,7 @@ impl ActiveEventLoop {
                             modifiers.set(active_modifiers);
                             Event::WindowEvent {
                                 window_id: RootWindowId(id),
-                                event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                                event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                             }
                         });
 
@@ -512,7 +658,7 @@ impl ActiveEventLoop {
                             modifiers.set(active_modifiers);
                             Event::WindowEvent {
                                 window_id: RootWindowId(id),
-                                event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                                event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                             }
                         });
 
@@ -540,7 +686,7 @@ impl ActiveEventLoop {
                     modifiers.set(active_modifiers);
                     Event::WindowEvent {
                         window_id: RootWindowId(id),
-                        event: WindowEvent::ModifiersChanged(active_modifiers.into()),
+                        event: WindowEvent::ModifiersChanged(modifiers.modifiers()),
                     }
                 });
 
@@ -570,6 +716,31 @@ impl ActiveEventLoop {
             });
         });
 
+        canvas.on_drop(
+            {
+                let runner = self.runner.clone();
+                move |position| {
+                    let operation = Arc::new(Mutex::new(DropOperation::Copy));
+                    runner.send_event(Event::DragHover {
+                        window_id: RootWindowId(id),
+                        position,
+                        respond: DropResponse::new(Arc::downgrade(&operation)),
+                    });
+                    let operation = *operation.lock().unwrap();
+                    operation
+                }
+            },
+            {
+                let runner = self.runner.clone();
+                move |data| {
+                    runner.send_event(Event::WindowEvent {
+                        window_id: RootWindowId(id),
+                        event: WindowEvent::DroppedData(data),
+                    });
+                }
+            },
+        );
+
         let runner = self.runner.clone();
         canvas.on_dark_mode(move |is_dark_mode| {
             let theme = if is_dark_mode { Theme::Dark } else { Theme::Light };
@@ -603,7 +774,7 @@ impl ActiveEventLoop {
                         canvas.set_old_size(new_size);
                         runner.send_event(Event::WindowEvent {
                             window_id: RootWindowId(id),
-                            event: WindowEvent::Resized(new_size),
+                            event: WindowEvent::Resized { size: new_size, frame: None },
                         });
                         runner.request_redraw(RootWindowId(id));
                     }
@@ -611,6 +782,111 @@ impl ActiveEventLoop {
             },
         );
 
+        let runner = self.runner.clone();
+        let mut position = canvas.position().to_physical(backend::scale_factor(canvas.window()));
+        canvas.on_move({
+            let canvas = canvas_clone.clone();
+
+            move || {
+                let new_position = {
+                    let canvas = canvas.borrow();
+                    canvas.position().to_physical(backend::scale_factor(canvas.window()))
+                };
+                if new_position != position {
+                    position = new_position;
+                    runner.send_event(Event::WindowEvent {
+                        window_id: RootWindowId(id),
+                        event: WindowEvent::Moved(new_position),
+                    });
+                }
+            }
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_surface(move |available| {
+            let window_id = RootWindowId(id);
+            runner.send_event(if available {
+                Event::SurfaceCreated(window_id)
+            } else {
+                Event::SurfaceDestroyed(window_id)
+            });
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_keyboard_visibility(move |visible, occluded_rect| {
+            runner.send_event(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::KeyboardVisibilityChanged { visible, occluded_rect },
+            });
+        });
+
+        let runner = self.runner.clone();
+        canvas.on_context_loss(move |lost| {
+            runner.send_event(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: if lost {
+                    WindowEvent::GraphicsContextLost
+                } else {
+                    WindowEvent::GraphicsContextRestored
+                },
+            });
+        });
+
+        let runner = self.runner.clone();
+        let mut was_fullscreen = canvas.is_fullscreen();
+        canvas.on_fullscreen_change({
+            let canvas = canvas_clone.clone();
+
+            move |changed| {
+                let canvas = canvas.borrow();
+                let window_id = RootWindowId(id);
+                if !changed {
+                    // Changing the fullscreen state failed.
+                    if let Some(transition) = canvas.fullscreen_transition.take() {
+                        runner.send_event(Event::WindowEvent {
+                            window_id,
+                            event: WindowEvent::TransitionEnded(transition),
+                        });
+                    }
+                    return;
+                }
+
+                let is_fullscreen = canvas.is_fullscreen();
+                // Another element entered or exited fullscreen.
+                if is_fullscreen == was_fullscreen {
+                    return;
+                }
+                was_fullscreen = is_fullscreen;
+
+                let transition = if is_fullscreen {
+                    Transition::EnterFullscreen
+                } else {
+                    Transition::ExitFullscreen
+                };
+                // The browser changed the state by itself, so `TransitionStarted` wasn't sent yet.
+                let started =
+                    (canvas.fullscreen_transition.take() != Some(transition)).then(|| {
+                        Event::WindowEvent {
+                            window_id,
+                            event: WindowEvent::TransitionStarted(transition),
+                        }
+                    });
+                drop(canvas);
+                let tiling_state =
+                    if is_fullscreen { TilingState::Fullscreen } else { TilingState::Floating };
+                runner.send_events(started.into_iter().chain([
+                    Event::WindowEvent {
+                        window_id,
+                        event: WindowEvent::TransitionEnded(transition),
+                    },
+                    Event::WindowEvent {
+                        window_id,
+                        event: WindowEvent::TilingStateChanged(tiling_state),
+                    },
+                ]));
+            }
+        });
+
         let runner = self.runner.clone();
         canvas.on_intersection(move |is_intersecting| {
             // only fire if visible while skipping the first event if it's intersecting
@@ -624,12 +900,19 @@ impl ActiveEventLoop {
             }
 
             canvas_clone.borrow_mut().is_intersecting = Some(is_intersecting);
+            runner.update_visibility(RootWindowId(id));
         });
 
         let runner = self.runner.clone();
         canvas.on_animation_frame(move || runner.request_redraw(RootWindowId(id)));
 
-        canvas.on_context_menu();
+        let runner = self.runner.clone();
+        canvas.on_context_menu(move |position, kind| {
+            runner.send_event(Event::WindowEvent {
+                window_id: RootWindowId(id),
+                event: WindowEvent::ContextMenuRequested { position, kind },
+            });
+        });
     }
 
     pub fn available_monitors(&self) -> VecDequeIter<MonitorHandle> {
@@ -640,6 +923,74 @@ impl ActiveEventLoop {
         None
     }
 
+    pub fn locale(&self) -> Option<LanguageTag> {
+        backend::locale(self.runner.window())
+    }
+
+    pub fn seats(&self) -> Vec<Seat> {
+        Vec::new()
+    }
+
+    pub fn session_info(&self) -> SessionInfo {
+        let window = self.runner.window();
+        let kind = if backend::is_mobile(window) {
+            SessionKind::Mobile
+        } else if backend::matches_media(window, "(display-mode: fullscreen)")
+            || backend::matches_media(window, "(display-mode: kiosk)")
+        {
+            SessionKind::Kiosk
+        } else {
+            SessionKind::Desktop
+        };
+
+        let mut capabilities =
+            SessionCapabilities::SET_POSITION | SessionCapabilities::TRANSPARENCY;
+        if kind != SessionKind::Mobile && backend::has_pointer_lock(self.runner.document()) {
+            capabilities |= SessionCapabilities::GRAB_CURSOR;
+        }
+
+        SessionInfo::new(kind, false, capabilities)
+    }
+
+    pub fn double_click_settings(&self) -> Option<(Duration, u32)> {
+        None
+    }
+
+    /// The click count the browser reported with the last mouse press, see [`MouseEvent.detail`].
+    ///
+    /// [`MouseEvent.detail`]: https://developer.mozilla.org/en-US/docs/Web/API/UIEvent/detail
+    pub fn take_click_count(&self) -> Option<u32> {
+        self.click_count.take()
+    }
+
+    pub fn pointer_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        // Browsers don't expose positions outside of the page.
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_restart_after_crash(
+        &self,
+        _args: Option<Vec<OsString>>,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    pub fn set_video_mode(
+        &self,
+        _mode: &VideoModeHandle,
+    ) -> Result<Box<dyn FnOnce()>, ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    /// Restart the application, see [`ActiveEventLoop::request_restart()`].
+    ///
+    /// [`ActiveEventLoop::request_restart()`]: crate::event_loop::ActiveEventLoop::request_restart
+    pub fn reload(&self) {
+        if let Err(error) = self.runner.window().location().reload() {
+            tracing::error!("failed to reload the page: {error:?}");
+        }
+    }
+
     #[cfg(feature = "rwh_05")]
     #[inline]
     pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
@@ -682,6 +1033,14 @@ impl ActiveEventLoop {
         self.runner.poll_strategy()
     }
 
+    pub(crate) fn set_timer_resolution(&self, resolution: Duration) {
+        self.runner.set_timer_resolution(resolution)
+    }
+
+    pub(crate) fn set_exit_pending(&self, pending: bool) {
+        self.runner.set_exit_pending(pending)
+    }
+
     pub(crate) fn waker(&self) -> Waker<Weak<Execution>> {
         self.runner.waker()
     }
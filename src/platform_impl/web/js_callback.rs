@@ -0,0 +1,66 @@
+use std::fmt::{self, Debug, Formatter};
+
+use js_sys::{Error, Object, Reflect};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsValue;
+
+use super::OsError;
+use crate::error::ExternalError;
+use crate::event_loop::EventLoopProxy;
+
+pub struct JsEventSource {
+    name: String,
+    global: Object,
+    closure: Closure<dyn FnMut(JsValue) -> Result<(), JsValue>>,
+}
+
+impl JsEventSource {
+    pub fn new<T: 'static>(
+        name: &str,
+        proxy: EventLoopProxy<T>,
+        mut convert: impl FnMut(JsValue) -> Result<T, JsValue> + 'static,
+    ) -> Result<Self, ExternalError> {
+        let global = js_sys::global();
+        let key = JsValue::from_str(name);
+        let map_err = |error: JsValue| ExternalError::Os(os_error!(OsError(format!("{error:?}"))));
+
+        if Reflect::has(&global, &key).map_err(map_err)? {
+            return Err(ExternalError::Os(os_error!(OsError(format!(
+                "the global object already has a property named `{name}`"
+            )))));
+        }
+
+        let closure = Closure::<dyn FnMut(JsValue) -> Result<(), JsValue>>::new(move |payload| {
+            // The error is thrown in the caller.
+            let event = convert(payload)?;
+            proxy.send_event(event).map_err(|_| Error::new("the event loop was closed").into())
+        });
+        Reflect::set(&global, &key, closure.as_ref()).map_err(map_err)?;
+
+        Ok(Self { name: name.to_owned(), global, closure })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn function(&self) -> &JsValue {
+        self.closure.as_ref()
+    }
+}
+
+impl Drop for JsEventSource {
+    fn drop(&mut self) {
+        let key = JsValue::from_str(&self.name);
+        // Leave the property alone if JavaScript replaced it in the meantime.
+        if Reflect::get(&self.global, &key).is_ok_and(|value| &value == self.closure.as_ref()) {
+            let _ = Reflect::delete_property(&self.global, &key);
+        }
+    }
+}
+
+impl Debug for JsEventSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsEventSource").field("name", &self.name).finish_non_exhaustive()
+    }
+}
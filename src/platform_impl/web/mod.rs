@@ -23,11 +23,15 @@
 mod r#async;
 mod cursor;
 mod device;
+mod dialog;
 mod error;
 mod event_loop;
+mod js_callback;
 mod keyboard;
 mod main_thread;
 mod monitor;
+mod notification;
+mod permission;
 mod window;
 
 #[path = "web_sys/mod.rs"]
@@ -36,10 +40,14 @@ mod backend;
 pub use self::device::DeviceId;
 pub use self::error::OsError;
 pub(crate) use self::event_loop::{
-    ActiveEventLoop, EventLoop, EventLoopProxy, OwnedDisplayHandle,
+    ActiveEventLoop, EventLoop, EventLoopProxy, EventLoopWaker, OwnedDisplayHandle,
     PlatformSpecificEventLoopAttributes,
 };
+pub(crate) use self::js_callback::JsEventSource as PlatformJsEventSource;
 pub use self::monitor::{MonitorHandle, VideoModeHandle};
+pub(crate) use self::notification::Notification as PlatformNotification;
+pub(crate) use self::permission::PermissionFuture;
+pub(crate) use self::window::MainThreadWindow;
 pub use self::window::{PlatformSpecificWindowAttributes, Window, WindowId};
 
 pub(crate) use self::keyboard::KeyEventExtra;
@@ -1,6 +1,8 @@
 use std::iter::Empty;
+use std::ops::RangeInclusive;
 
 use crate::dpi::{PhysicalPosition, PhysicalSize};
+use crate::monitor::Rect;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct MonitorHandle;
@@ -14,6 +16,14 @@ impl MonitorHandle {
         unreachable!()
     }
 
+    pub fn work_area(&self) -> Rect {
+        unreachable!()
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        unreachable!()
+    }
+
     pub fn name(&self) -> Option<String> {
         unreachable!()
     }
@@ -22,6 +32,14 @@ impl MonitorHandle {
         unreachable!()
     }
 
+    pub fn supports_variable_refresh(&self) -> bool {
+        unreachable!()
+    }
+
+    pub fn is_mirrored(&self) -> bool {
+        unreachable!()
+    }
+
     pub fn size(&self) -> PhysicalSize<u32> {
         unreachable!()
     }
@@ -47,6 +65,10 @@ impl VideoModeHandle {
         unreachable!();
     }
 
+    pub fn refresh_rate_range(&self) -> RangeInclusive<u32> {
+        unreachable!();
+    }
+
     pub fn monitor(&self) -> MonitorHandle {
         unreachable!();
     }
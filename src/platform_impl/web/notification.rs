@@ -0,0 +1,117 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use js_sys::{Object, Reflect};
+use wasm_bindgen::prelude::{wasm_bindgen, Closure};
+use wasm_bindgen::JsValue;
+use web_sys::Event;
+
+use super::backend::EventListenerHandle;
+use super::event_loop::runner;
+use super::main_thread::{MainThreadMarker, MainThreadSafe};
+use super::OsError;
+use crate::error::{ExternalError, NotSupportedError};
+use crate::event::Event as RootEvent;
+use crate::notification::{NotificationAttributes, NotificationEvent, NotificationId};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = web_sys::EventTarget, js_name = Notification)]
+    type JsNotification;
+
+    #[wasm_bindgen(catch, constructor, js_class = Notification)]
+    fn new(title: &str, options: &Object) -> Result<JsNotification, JsValue>;
+
+    #[wasm_bindgen(method)]
+    fn close(this: &JsNotification);
+}
+
+pub struct Notification(MainThreadSafe<Rc<Inner>>);
+
+struct Inner {
+    notification: JsNotification,
+    /// The listeners keep `Inner` alive until the notification is clicked or closed.
+    listeners: RefCell<Option<[EventListenerHandle<dyn FnMut(Event)>; 2]>>,
+}
+
+impl Notification {
+    pub fn new(
+        runner: &runner::Shared,
+        id: NotificationId,
+        attributes: NotificationAttributes,
+    ) -> Result<Self, ExternalError> {
+        let Some(permission) = super::permission::notification(runner.window()) else {
+            return Err(ExternalError::NotSupported(NotSupportedError::new()));
+        };
+        let permission = Reflect::get(&permission, &"permission".into())
+            .expect("unexpected exception in `Reflect.get()`");
+        if permission.as_string().as_deref() != Some("granted") {
+            return Err(ExternalError::Os(os_error!(OsError(
+                "permission to show notifications wasn't granted".to_owned()
+            ))));
+        }
+
+        let options = Object::new();
+        Reflect::set(&options, &"body".into(), &attributes.body.as_str().into())
+            .expect("unexpected exception in `Reflect.set()`");
+        Reflect::set(&options, &"silent".into(), &attributes.silent.into())
+            .expect("unexpected exception in `Reflect.set()`");
+
+        // Throws in some browsers when not called from a service worker, e.g. on Android.
+        let notification = JsNotification::new(&attributes.title, &options)
+            .map_err(|error| ExternalError::Os(os_error!(OsError(format!("{error:?}")))))?;
+
+        let inner = Rc::new(Inner { notification, listeners: RefCell::new(None) });
+
+        let on_click = EventListenerHandle::new(
+            inner.notification.clone(),
+            "click",
+            Closure::<dyn FnMut(Event)>::new({
+                let inner = Rc::clone(&inner);
+                let runner = runner.clone();
+                move |_: Event| {
+                    // Browsers don't close the notification or focus the page on their own.
+                    inner.close();
+                    let _ = runner.window().focus();
+                    runner.send_event(RootEvent::Notification {
+                        id,
+                        event: NotificationEvent::Clicked,
+                    });
+                }
+            }),
+        );
+        let on_close = EventListenerHandle::new(
+            inner.notification.clone(),
+            "close",
+            Closure::<dyn FnMut(Event)>::new({
+                let inner = Rc::clone(&inner);
+                let runner = runner.clone();
+                move |_: Event| {
+                    drop(inner.listeners.take());
+                    runner.send_event(RootEvent::Notification {
+                        id,
+                        event: NotificationEvent::Dismissed,
+                    });
+                }
+            }),
+        );
+        *inner.listeners.borrow_mut() = Some([on_click, on_close]);
+
+        Ok(Self(MainThreadSafe::new(runner.main_thread(), inner)))
+    }
+
+    pub fn close(&self) {
+        if let Some(main_thread) = MainThreadMarker::new() {
+            self.0.get(main_thread).close();
+        }
+    }
+}
+
+impl Inner {
+    fn close(&self) {
+        // Remove the listeners first, so closing doesn't emit `NotificationEvent::Dismissed`.
+        let listeners = self.listeners.take();
+        self.notification.close();
+        drop(listeners);
+    }
+}
@@ -0,0 +1,229 @@
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use js_sys::{Function, Object, Promise, Reflect};
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::DomException;
+
+use crate::platform::web::{Permission, PermissionError, PermissionState};
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(extends = web_sys::Window)]
+    type NavigatorWindow;
+
+    #[wasm_bindgen(method, getter)]
+    fn navigator(this: &NavigatorWindow) -> Navigator;
+
+    type Navigator;
+
+    #[wasm_bindgen(method, getter)]
+    fn permissions(this: &Navigator) -> Option<Permissions>;
+
+    #[wasm_bindgen(method, getter)]
+    fn storage(this: &Navigator) -> Option<StorageManager>;
+
+    #[wasm_bindgen(method, getter)]
+    fn clipboard(this: &Navigator) -> Option<Clipboard>;
+
+    type Permissions;
+
+    #[wasm_bindgen(catch, method)]
+    fn query(this: &Permissions, descriptor: &Object) -> Result<Promise, JsValue>;
+
+    type PermissionStatus;
+
+    #[wasm_bindgen(method, getter)]
+    fn state(this: &PermissionStatus) -> String;
+
+    #[wasm_bindgen(method, setter)]
+    fn set_onchange(this: &PermissionStatus, handler: Option<&Function>);
+
+    type StorageManager;
+
+    #[wasm_bindgen(catch, method)]
+    fn persist(this: &StorageManager) -> Result<Promise, JsValue>;
+
+    #[wasm_bindgen(catch, method)]
+    fn persisted(this: &StorageManager) -> Result<Promise, JsValue>;
+
+    type Clipboard;
+
+    #[wasm_bindgen(catch, method, js_name = readText)]
+    fn read_text(this: &Clipboard) -> Result<Promise, JsValue>;
+}
+
+type PermissionResult = Result<PermissionState, PermissionError>;
+
+pub struct PermissionFuture(Pin<Box<dyn Future<Output = PermissionResult>>>);
+
+impl PermissionFuture {
+    pub fn query(window: web_sys::Window, permission: Permission) -> Self {
+        Self(Box::pin(async move { query(&window, permission).await }))
+    }
+
+    pub fn request(window: web_sys::Window, permission: Permission) -> Self {
+        Self(Box::pin(async move { request(&window, permission).await }))
+    }
+
+    pub fn changed(window: web_sys::Window, permission: Permission) -> Self {
+        Self(Box::pin(async move {
+            let status = query_status(&window, permission).await?;
+            let change = Promise::new(&mut |resolve, _| status.set_onchange(Some(&resolve)));
+            // `status` is kept alive until here, otherwise it could be garbage collected before
+            // it changed.
+            let _ = JsFuture::from(change).await;
+            status.set_onchange(None);
+
+            Ok(state_from_str(&status.state()))
+        }))
+    }
+}
+
+impl Debug for PermissionFuture {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PermissionFuture").finish_non_exhaustive()
+    }
+}
+
+impl Future for PermissionFuture {
+    type Output = PermissionResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.0.as_mut().poll(cx)
+    }
+}
+
+fn navigator(window: &web_sys::Window) -> Navigator {
+    window.unchecked_ref::<NavigatorWindow>().navigator()
+}
+
+fn name(permission: Permission) -> &'static str {
+    match permission {
+        Permission::PointerLock => "pointer-lock",
+        Permission::ClipboardRead => "clipboard-read",
+        Permission::ClipboardWrite => "clipboard-write",
+        Permission::Notifications => "notifications",
+        Permission::PersistentStorage => "persistent-storage",
+    }
+}
+
+fn state_from_str(state: &str) -> PermissionState {
+    match state {
+        "granted" => PermissionState::Granted,
+        "denied" => PermissionState::Denied,
+        // `Notification.permission` reports "default" instead of "prompt".
+        _ => PermissionState::Prompt,
+    }
+}
+
+fn error_from_js(error: JsValue) -> PermissionError {
+    match error.dyn_into::<DomException>() {
+        Ok(error) => PermissionError::Failed(error.message()),
+        Err(error) => PermissionError::Failed(format!("{error:?}")),
+    }
+}
+
+async fn query_status(
+    window: &web_sys::Window,
+    permission: Permission,
+) -> Result<PermissionStatus, PermissionError> {
+    let permissions = navigator(window).permissions().ok_or(PermissionError::Unsupported)?;
+
+    let descriptor = Object::new();
+    Reflect::set(&descriptor, &"name".into(), &name(permission).into())
+        .expect("unexpected exception in `Reflect.set()`");
+
+    // Browsers reject permissions they don't know with a `TypeError`.
+    let promise = permissions.query(&descriptor).map_err(|_| PermissionError::Unsupported)?;
+    let status = JsFuture::from(promise).await.map_err(|_| PermissionError::Unsupported)?;
+
+    Ok(status.unchecked_into())
+}
+
+async fn query(window: &web_sys::Window, permission: Permission) -> PermissionResult {
+    let error = match query_status(window, permission).await {
+        Ok(status) => return Ok(state_from_str(&status.state())),
+        Err(error) => error,
+    };
+
+    // Fall back to the APIs specific to the permission if the Permissions API doesn't know it.
+    match permission {
+        Permission::Notifications => {
+            let notification = notification(window).ok_or(error)?;
+            let state = Reflect::get(&notification, &"permission".into())
+                .expect("unexpected exception in `Reflect.get()`");
+
+            Ok(state_from_str(&state.as_string().unwrap_or_default()))
+        },
+        Permission::PersistentStorage => {
+            let storage = navigator(window).storage().ok_or(error)?;
+            let persisted = storage.persisted().map_err(error_from_js)?;
+            let persisted = JsFuture::from(persisted).await.map_err(error_from_js)?;
+
+            // Without the Permissions API, there is no way to know if persisting was denied.
+            Ok(if persisted.is_truthy() {
+                PermissionState::Granted
+            } else {
+                PermissionState::Prompt
+            })
+        },
+        _ => Err(error),
+    }
+}
+
+async fn request(window: &web_sys::Window, permission: Permission) -> PermissionResult {
+    match permission {
+        Permission::Notifications => {
+            let notification = notification(window).ok_or(PermissionError::Unsupported)?;
+            let request_permission: Function =
+                Reflect::get(&notification, &"requestPermission".into())
+                    .expect("unexpected exception in `Reflect.get()`")
+                    .dyn_into()
+                    .map_err(|_| PermissionError::Unsupported)?;
+            // Older versions of Safari only support passing a callback instead of returning a
+            // `Promise`.
+            let promise = request_permission.call0(&notification).map_err(error_from_js)?;
+            let state = JsFuture::from(Promise::resolve(&promise)).await.map_err(error_from_js)?;
+
+            Ok(state_from_str(&state.as_string().unwrap_or_default()))
+        },
+        Permission::PersistentStorage => {
+            let storage = navigator(window).storage().ok_or(PermissionError::Unsupported)?;
+            let persist = storage.persist().map_err(error_from_js)?;
+            // Browsers usually don't prompt for this, but decide on their own.
+            let persisted = JsFuture::from(persist).await.map_err(error_from_js)?;
+
+            Ok(if persisted.is_truthy() {
+                PermissionState::Granted
+            } else {
+                PermissionState::Denied
+            })
+        },
+        Permission::ClipboardRead => {
+            let clipboard = navigator(window).clipboard().ok_or(PermissionError::Unsupported)?;
+            // There is no way to request reading the clipboard without actually reading it.
+            let read = clipboard.read_text().map_err(|_| PermissionError::Unsupported)?;
+
+            match JsFuture::from(read).await {
+                Ok(_) => Ok(PermissionState::Granted),
+                // The prompt might have only been dismissed.
+                Err(_) => query(window, permission).await.or(Ok(PermissionState::Denied)),
+            }
+        },
+        // These are granted when used, so there is nothing to request ahead of time.
+        Permission::PointerLock | Permission::ClipboardWrite => query(window, permission).await,
+    }
+}
+
+/// Returns the `Notification` class, if supported.
+pub(super) fn notification(window: &web_sys::Window) -> Option<JsValue> {
+    let notification = Reflect::get(window, &"Notification".into())
+        .expect("unexpected exception in `Reflect.get()`");
+
+    (!notification.is_undefined()).then_some(notification)
+}
@@ -1,34 +1,93 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
+
+use js_sys::Function;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 
+/// How much earlier than the frame interval an animation frame may come, as the timestamps of
+/// animation frames jitter.
+const FRAME_TOLERANCE_MS: f64 = 1.;
+
 pub struct AnimationFrameHandler {
     window: web_sys::Window,
-    closure: Closure<dyn FnMut()>,
+    closure: Closure<dyn FnMut(f64)>,
     handle: Rc<Cell<Option<i32>>>,
+    throttle: Rc<Throttle>,
+}
+
+/// Animation frames are skipped to emulate a frame rate lower than the one of the display.
+#[derive(Default)]
+struct Throttle {
+    /// The shortest time between two frames in milliseconds, if frames are skipped.
+    interval: Cell<Option<f64>>,
+    /// The timestamp of the last frame that wasn't skipped.
+    last_frame: Cell<Option<f64>>,
+    /// The function of the closure, to request the next frame when one is skipped.
+    callback: RefCell<Option<Function>>,
+}
+
+impl Throttle {
+    fn skip(&self, timestamp: f64) -> bool {
+        if let (Some(interval), Some(last_frame)) = (self.interval.get(), self.last_frame.get()) {
+            if timestamp - last_frame < interval - FRAME_TOLERANCE_MS {
+                return true;
+            }
+        }
+        self.last_frame.set(Some(timestamp));
+        false
+    }
 }
 
 impl AnimationFrameHandler {
     pub fn new(window: web_sys::Window) -> Self {
         let handle = Rc::new(Cell::new(None));
+        let throttle = Rc::new(Throttle::default());
+        let closure = Self::closure(&window, &handle, &throttle, || ());
+
+        Self { window, closure, handle, throttle }
+    }
+
+    fn closure<F>(
+        window: &web_sys::Window,
+        handle: &Rc<Cell<Option<i32>>>,
+        throttle: &Rc<Throttle>,
+        mut f: F,
+    ) -> Closure<dyn FnMut(f64)>
+    where
+        F: 'static + FnMut(),
+    {
         let closure = Closure::new({
+            let window = window.clone();
             let handle = handle.clone();
-            move || handle.set(None)
+            let throttle = throttle.clone();
+            move |timestamp: f64| {
+                handle.set(None);
+                if !throttle.skip(timestamp) {
+                    f();
+                } else if let Some(callback) = &*throttle.callback.borrow() {
+                    let next = window
+                        .request_animation_frame(callback)
+                        .expect("Failed to request animation frame");
+                    handle.set(Some(next));
+                }
+            }
         });
-
-        Self { window, closure, handle }
+        throttle.callback.replace(Some(closure.as_ref().clone().unchecked_into()));
+        closure
     }
 
-    pub fn on_animation_frame<F>(&mut self, mut f: F)
+    pub fn on_animation_frame<F>(&mut self, f: F)
     where
         F: 'static + FnMut(),
     {
-        let handle = self.handle.clone();
-        self.closure = Closure::new(move || {
-            handle.set(None);
-            f();
-        })
+        self.closure = Self::closure(&self.window, &self.handle, &self.throttle, f);
+    }
+
+    /// Skip animation frames to stay below `max_frame_rate`, in Hz.
+    pub fn set_max_frame_rate(&self, max_frame_rate: Option<f32>) {
+        let interval = max_frame_rate.filter(|&rate| rate > 0.).map(|rate| 1000. / f64::from(rate));
+        self.throttle.interval.set(interval);
     }
 
     pub fn request(&self) {
@@ -1,31 +1,45 @@
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
+use std::mem;
 use std::ops::Deref;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
+use js_sys::{Array, Object, Reflect};
 use smol_str::SmolStr;
 use wasm_bindgen::closure::Closure;
-use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
-    CssStyleDeclaration, Document, Event, FocusEvent, HtmlCanvasElement, KeyboardEvent,
-    PointerEvent, WheelEvent,
+    CssStyleDeclaration, Document, DragEvent, Element, Event, EventTarget, FocusEvent,
+    HtmlCanvasElement, HtmlElement, HtmlInputElement, KeyboardEvent, PointerEvent, WheelEvent,
 };
 
-use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize};
+use crate::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use crate::error::OsError as RootOE;
-use crate::event::{Force, InnerSizeWriter, MouseButton, MouseScrollDelta};
+use crate::event::{
+    ContextMenuKind, DataTransfer, DropOperation, Force, Ime, InnerSizeWriter, MouseButton,
+    MouseScrollDelta, Transition,
+};
 use crate::keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey};
 use crate::platform_impl::OsError;
-use crate::window::{WindowAttributes, WindowId as RootWindowId};
+use crate::window::{
+    CoordinateTransform, Region, WindowAttributes, WindowId as RootWindowId, WindowVisibility,
+};
 
 use super::super::cursor::CursorHandler;
 use super::super::main_thread::MainThreadMarker;
 use super::super::WindowId;
 use super::animation_frame::AnimationFrameHandler;
+use super::context_handle::ContextLossHandle;
 use super::event_handle::EventListenerHandle;
+use super::ime::{ImeHandlers, ImeInput, SharedHandler};
 use super::intersection_handle::IntersectionObserverHandle;
+use super::keyboard_handle::KeyboardObserverHandle;
 use super::media_query_handle::MediaQueryListHandle;
+use super::overlay::Overlay;
 use super::pointer::PointerHandler;
+use super::position_handle::PositionObserverHandle;
+use super::surface_handle::SurfaceObserverHandle;
 use super::{event, fullscreen, ButtonsState, ResizeScaleHandle};
 
 #[allow(dead_code)]
@@ -34,7 +48,15 @@ pub struct Canvas {
     id: WindowId,
     pub has_focus: Rc<Cell<bool>>,
     pub prevent_default: Rc<Cell<bool>>,
+    /// Overrides `prevent_default` for the menu of the browser on `contextmenu`.
+    pub prevent_context_menu: Rc<Cell<Option<bool>>>,
+    /// The position of the last mouse event, browsers don't allow querying it.
+    pub cursor_position: Rc<Cell<Option<PhysicalPosition<f64>>>>,
     pub is_intersecting: Option<bool>,
+    /// The visibility last reported with `WindowEvent::VisibilityChanged`.
+    last_visibility: Cell<WindowVisibility>,
+    /// The fullscreen change requested by `Window::set_fullscreen` which didn't finish yet.
+    pub fullscreen_transition: Cell<Option<Transition>>,
     on_touch_start: Option<EventListenerHandle<dyn FnMut(Event)>>,
     on_focus: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
     on_blur: Option<EventListenerHandle<dyn FnMut(FocusEvent)>>,
@@ -45,9 +67,30 @@ pub struct Canvas {
     pointer_handler: PointerHandler,
     on_resize_scale: Option<ResizeScaleHandle>,
     on_intersect: Option<IntersectionObserverHandle>,
+    on_move: Option<PositionObserverHandle>,
+    on_surface: Option<SurfaceObserverHandle>,
+    on_context_loss: Option<ContextLossHandle>,
+    on_keyboard_visibility: Option<KeyboardObserverHandle>,
     animation_frame_handler: AnimationFrameHandler,
     on_touch_end: Option<EventListenerHandle<dyn FnMut(Event)>>,
     on_context_menu: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_hittest: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_fullscreen_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_fullscreen_error: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    on_drag_enter: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_drag_over: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_drag_leave: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    on_drop: Option<EventListenerHandle<dyn FnMut(DragEvent)>>,
+    overlay: Option<Overlay>,
+    ime_handlers: ImeHandlers,
+    /// The hidden text field receiving the keyboard input while IME is allowed.
+    ime: Option<ImeInput>,
+    /// The element of `ime`, for the focus listeners of the canvas.
+    ime_element: Rc<RefCell<Option<HtmlInputElement>>>,
+    ime_cursor_area: Option<(LogicalPosition<f64>, LogicalSize<f64>)>,
+    /// Whether the canvas is hidden until the first frame was presented.
+    splash_screen: bool,
+    splash_element: Option<HtmlElement>,
     pub cursor: CursorHandler,
 }
 
@@ -102,7 +145,7 @@ impl Canvas {
         // sequential keyboard navigation, but its order is defined by the
         // document's source order.
         // https://developer.mozilla.org/en-US/docs/Web/HTML/Global_attributes/tabindex
-        if attr.platform_specific.focusable {
+        if attr.focusable {
             canvas
                 .set_attribute("tabindex", "0")
                 .map_err(|_| os_error!(OsError("Failed to set a tabindex".to_owned())))?;
@@ -149,12 +192,25 @@ impl Canvas {
             let _ = common.raw.focus();
         }
 
+        if attr.splash_screen {
+            common.style.set("opacity", "0");
+        }
+        let splash_element = attr.platform_specific.splash_element.take().map(|element| {
+            Arc::try_unwrap(element)
+                .map(|element| element.into_inner(main_thread))
+                .unwrap_or_else(|element| element.get(main_thread).clone())
+        });
+
         Ok(Canvas {
             common,
             id,
             has_focus: Rc::new(Cell::new(false)),
             prevent_default: Rc::new(Cell::new(attr.platform_specific.prevent_default)),
+            prevent_context_menu: Rc::new(Cell::new(attr.platform_specific.prevent_context_menu)),
+            cursor_position: Rc::new(Cell::new(None)),
             is_intersecting: None,
+            last_visibility: Cell::new(WindowVisibility::Hidden),
+            fullscreen_transition: Cell::new(None),
             on_touch_start: None,
             on_blur: None,
             on_focus: None,
@@ -165,9 +221,27 @@ impl Canvas {
             pointer_handler: PointerHandler::new(),
             on_resize_scale: None,
             on_intersect: None,
+            on_move: None,
+            on_surface: None,
+            on_context_loss: None,
+            on_keyboard_visibility: None,
             animation_frame_handler: AnimationFrameHandler::new(window),
             on_touch_end: None,
             on_context_menu: None,
+            on_hittest: None,
+            on_fullscreen_change: None,
+            on_fullscreen_error: None,
+            on_drag_enter: None,
+            on_drag_over: None,
+            on_drag_leave: None,
+            on_drop: None,
+            overlay: None,
+            ime_handlers: ImeHandlers::default(),
+            ime: None,
+            ime_element: Rc::new(RefCell::new(None)),
+            ime_cursor_area: None,
+            splash_screen: attr.splash_screen,
+            splash_element,
             cursor,
         })
     }
@@ -181,6 +255,43 @@ impl Canvas {
         Ok(())
     }
 
+    /// Fails if the pointer isn't pressed.
+    pub fn set_pointer_capture(&self, pointer_id: i32, capture: bool) -> Result<(), JsValue> {
+        if capture {
+            self.raw().set_pointer_capture(pointer_id)
+        } else {
+            self.raw().release_pointer_capture(pointer_id)
+        }
+    }
+
+    pub fn set_cursor_hittest(&mut self, hittest: bool) {
+        self.on_hittest = None;
+        set_pointer_events(self.style(), hittest);
+    }
+
+    /// The canvas doesn't receive pointer events while passing them through, so the pointer is
+    /// tracked on the whole document instead.
+    pub fn set_hittest_regions(&mut self, regions: Vec<Region>) {
+        let canvas = Rc::clone(&self.common.raw);
+        let window = self.common.window.clone();
+        let style = self.common.style.clone();
+        self.on_hittest = Some(EventListenerHandle::new(
+            self.common.document.clone(),
+            "pointermove",
+            Closure::new(move |event: PointerEvent| {
+                let bounds = canvas.get_bounding_client_rect();
+                let scale_factor = super::scale_factor(&window);
+                let position = LogicalPosition::new(
+                    f64::from(event.client_x()) - bounds.x(),
+                    f64::from(event.client_y()) - bounds.y(),
+                )
+                .to_physical(scale_factor);
+                let hittest = regions.iter().any(|region| region.contains(position, scale_factor));
+                set_pointer_events(&style, hittest);
+            }),
+        ));
+    }
+
     pub fn set_attribute(&self, attribute: &str, value: &str) {
         self.common
             .raw
@@ -202,6 +313,32 @@ impl Canvas {
         position
     }
 
+    /// Maps CSS pixels relative to the viewport to physical pixels of the canvas, taking into
+    /// account CSS transforms scaling the canvas.
+    pub fn coordinate_transform(&self) -> CoordinateTransform {
+        let bounds = self.common.raw.get_bounding_client_rect();
+        let scale_factor = super::scale_factor(self.window());
+
+        // The layout size isn't affected by transforms, unlike the bounding rectangle.
+        let (layout_width, layout_height) =
+            (self.common.raw.offset_width(), self.common.raw.offset_height());
+        let scale_x = if layout_width > 0 { bounds.width() / f64::from(layout_width) } else { 1. };
+        let scale_y =
+            if layout_height > 0 { bounds.height() / f64::from(layout_height) } else { 1. };
+
+        let mut origin = LogicalPosition::new(bounds.x(), bounds.y());
+        if self.document().contains(Some(self.raw())) && self.style().get("display") != "none" {
+            origin.x += (super::style_size_property(self.style(), "border-left-width")
+                + super::style_size_property(self.style(), "padding-left"))
+                * scale_x;
+            origin.y += (super::style_size_property(self.style(), "border-top-width")
+                + super::style_size_property(self.style(), "padding-top"))
+                * scale_y;
+        }
+
+        CoordinateTransform::new(origin, scale_factor / scale_x, scale_factor / scale_y)
+    }
+
     #[inline]
     pub fn old_size(&self) -> PhysicalSize<u32> {
         self.common.old_size.get()
@@ -232,6 +369,17 @@ impl Canvas {
         &self.common.document
     }
 
+    pub fn visibility(&self) -> WindowVisibility {
+        let occluded = !super::is_visible(self.document()) || self.is_intersecting == Some(false);
+        WindowVisibility::new(true, false, occluded, self.has_focus.get())
+    }
+
+    /// Returns the visibility if it changed since the last call.
+    pub fn update_visibility(&self) -> Option<WindowVisibility> {
+        let visibility = self.visibility();
+        (self.last_visibility.replace(visibility) != visibility).then_some(visibility)
+    }
+
     #[inline]
     pub fn raw(&self) -> &HtmlCanvasElement {
         &self.common.raw
@@ -255,8 +403,20 @@ impl Canvas {
     where
         F: 'static + FnMut(),
     {
-        self.on_blur = Some(self.common.add_event("blur", move |_: FocusEvent| {
-            handler();
+        let handler: SharedHandler<()> = Rc::new(RefCell::new(move |()| handler()));
+        self.ime_handlers.blur = Some(Rc::clone(&handler));
+        let ime_element = Rc::clone(&self.ime_element);
+        self.on_blur = Some(self.common.add_event("blur", move |event: FocusEvent| {
+            // The focus moved to the IME field, see `set_ime_allowed()`.
+            let to_ime = event.related_target().is_some_and(|target| {
+                ime_element.borrow().as_ref().is_some_and(|element| {
+                    let element: &EventTarget = element;
+                    *element == target
+                })
+            });
+            if !to_ime {
+                (handler.borrow_mut())(());
+            }
         }));
     }
 
@@ -264,57 +424,114 @@ impl Canvas {
     where
         F: 'static + FnMut(),
     {
+        let handler: SharedHandler<()> = Rc::new(RefCell::new(move |()| handler()));
+        self.ime_handlers.focus = Some(Rc::clone(&handler));
+        let ime_element = Rc::clone(&self.ime_element);
         self.on_focus = Some(self.common.add_event("focus", move |_: FocusEvent| {
-            handler();
+            (handler.borrow_mut())(());
+            // Hand the focus over to the IME field, which receives the keyboard input instead.
+            let element = ime_element.borrow().clone();
+            if let Some(element) = element {
+                let _ = element.focus();
+            }
         }));
     }
 
-    pub fn on_keyboard_release<F>(&mut self, mut handler: F)
+    pub fn on_keyboard_release<F>(&mut self, handler: F)
     where
         F: 'static + FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, ModifiersState),
     {
+        let handler = keyboard_handler(handler);
+        self.ime_handlers.keyboard_release = Some(Rc::clone(&handler));
         let prevent_default = Rc::clone(&self.prevent_default);
         self.on_keyboard_release =
             Some(self.common.add_event("keyup", move |event: KeyboardEvent| {
                 if prevent_default.get() {
                     event.prevent_default();
                 }
-                let key = event::key(&event);
-                let modifiers = event::keyboard_modifiers(&event);
-                handler(
-                    event::key_code(&event),
-                    key,
-                    event::key_text(&event),
-                    event::key_location(&event),
-                    event.repeat(),
-                    modifiers,
-                );
+                (handler.borrow_mut())(event);
             }));
     }
 
-    pub fn on_keyboard_press<F>(&mut self, mut handler: F)
+    pub fn on_keyboard_press<F>(&mut self, handler: F)
     where
         F: 'static + FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, ModifiersState),
     {
+        let handler = keyboard_handler(handler);
+        self.ime_handlers.keyboard_press = Some(Rc::clone(&handler));
         let prevent_default = Rc::clone(&self.prevent_default);
         self.on_keyboard_press =
             Some(self.common.add_event("keydown", move |event: KeyboardEvent| {
                 if prevent_default.get() {
                     event.prevent_default();
                 }
-                let key = event::key(&event);
-                let modifiers = event::keyboard_modifiers(&event);
-                handler(
-                    event::key_code(&event),
-                    key,
-                    event::key_text(&event),
-                    event::key_location(&event),
-                    event.repeat(),
-                    modifiers,
-                );
+                (handler.borrow_mut())(event);
             }));
     }
 
+    pub fn on_ime<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(Ime),
+    {
+        self.ime_handlers.ime = Some(Rc::new(RefCell::new(handler)));
+    }
+
+    /// Returns `true` if IME was allowed or disallowed.
+    ///
+    /// While IME is allowed, the keyboard input is received by a hidden text field, see
+    /// [`ImeInput`].
+    pub fn set_ime_allowed(&mut self, allowed: bool) -> bool {
+        if allowed == self.ime.is_some() {
+            return false;
+        }
+
+        let focused = self.has_focus.get();
+        if allowed {
+            let ime = ImeInput::new(
+                self.document(),
+                self.raw(),
+                &self.ime_handlers,
+                Rc::clone(&self.prevent_default),
+            );
+            let element = ime.element().clone();
+            *self.ime_element.borrow_mut() = Some(element.clone());
+            self.ime = Some(ime);
+            self.update_ime_area();
+            if focused {
+                let _ = element.focus();
+            }
+        } else {
+            *self.ime_element.borrow_mut() = None;
+            // Return the focus before removing the field, which doesn't cause a `blur` event.
+            if focused {
+                let _ = self.raw().focus();
+            }
+            self.ime = None;
+        }
+
+        true
+    }
+
+    pub fn ime_element(&self) -> Option<&HtmlInputElement> {
+        self.ime.as_ref().map(ImeInput::element)
+    }
+
+    /// Set the IME cursor area, relative to the canvas.
+    pub fn set_ime_cursor_area(&mut self, position: LogicalPosition<f64>, size: LogicalSize<f64>) {
+        self.ime_cursor_area = Some((position, size));
+        self.update_ime_area();
+    }
+
+    fn update_ime_area(&self) {
+        let Some(ime) = &self.ime else {
+            return;
+        };
+
+        let origin = self.position();
+        let (position, size) = self.ime_cursor_area.unwrap_or_default();
+        ime.set_area(LogicalPosition::new(origin.x + position.x, origin.y + position.y), size);
+    }
+
     pub fn on_cursor_leave<F>(&mut self, handler: F)
     where
         F: 'static + FnMut(ModifiersState, Option<i32>),
@@ -329,6 +546,13 @@ impl Canvas {
         self.pointer_handler.on_cursor_enter(&self.common, handler)
     }
 
+    pub fn on_pointer_drag<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(i32, Option<Option<Element>>),
+    {
+        self.pointer_handler.on_pointer_drag(&self.common, handler)
+    }
+
     pub fn on_mouse_release<MOD, M, T>(
         &mut self,
         modifier_handler: MOD,
@@ -354,7 +578,7 @@ impl Canvas {
         touch_handler: T,
     ) where
         MOD: 'static + FnMut(ModifiersState),
-        M: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, MouseButton),
+        M: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, MouseButton, Option<u32>),
         T: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, Force),
     {
         self.pointer_handler.on_mouse_press(
@@ -414,6 +638,92 @@ impl Canvas {
         }));
     }
 
+    /// Negotiate the types of drags entering the canvas, allowing them to be dropped where the
+    /// hover handler doesn't reject them if any type was accepted, and read the accepted types
+    /// when dropped.
+    pub fn on_drop<H, F>(&mut self, hover_handler: H, mut handler: F)
+    where
+        H: 'static + FnMut(PhysicalPosition<f64>) -> DropOperation,
+        F: 'static + FnMut(DataTransfer),
+    {
+        let accepted = Rc::new(RefCell::new(Vec::<String>::new()));
+
+        // The drop is only allowed if the default of the last `dragover` is prevented.
+        let hover = Rc::new({
+            let window = self.common.window.clone();
+            let hover_handler = RefCell::new(hover_handler);
+            move |event: &DragEvent| {
+                let position =
+                    event::mouse_position(event).to_physical(super::scale_factor(&window));
+                let effect = match hover_handler.borrow_mut()(position) {
+                    DropOperation::Copy => "copy",
+                    DropOperation::Move => "move",
+                    DropOperation::Link => "link",
+                    DropOperation::Reject => return,
+                };
+                event.prevent_default();
+                if let Some(data) = event.data_transfer() {
+                    data.set_drop_effect(effect);
+                }
+            }
+        });
+
+        let window_id = RootWindowId(self.id);
+        self.on_drag_enter = Some(self.common.add_event("dragenter", {
+            let accepted = Rc::clone(&accepted);
+            let hover = Rc::clone(&hover);
+            move |event: DragEvent| {
+                // Files are only offered as the `Files` type, and can't be read as strings.
+                let offered: Vec<String> = event
+                    .data_transfer()
+                    .map(|data| {
+                        let types = data.types().iter().filter_map(|mime| mime.as_string());
+                        types.filter(|mime| mime != "Files").collect()
+                    })
+                    .unwrap_or_default();
+                *accepted.borrow_mut() = crate::drop_negotiation::negotiate(window_id, &offered);
+                if !accepted.borrow().is_empty() {
+                    hover(&event);
+                }
+            }
+        }));
+
+        self.on_drag_over = Some(self.common.add_event("dragover", {
+            let accepted = Rc::clone(&accepted);
+            move |event: DragEvent| {
+                if !accepted.borrow().is_empty() {
+                    hover(&event);
+                }
+            }
+        }));
+
+        self.on_drag_leave = Some(self.common.add_event("dragleave", {
+            let accepted = Rc::clone(&accepted);
+            move |_: DragEvent| accepted.borrow_mut().clear()
+        }));
+
+        self.on_drop = Some(self.common.add_event("drop", move |event: DragEvent| {
+            let accepted = mem::take(&mut *accepted.borrow_mut());
+            if accepted.is_empty() {
+                return;
+            }
+            // Prevents the browser from opening the data.
+            event.prevent_default();
+
+            let mut data = DataTransfer::new();
+            if let Some(transfer) = event.data_transfer() {
+                for mime in accepted {
+                    if let Ok(string) = transfer.get_data(&mime) {
+                        data.insert(mime, string.into_bytes());
+                    }
+                }
+            }
+            if !data.is_empty() {
+                handler(data);
+            }
+        }));
+    }
+
     pub fn on_dark_mode<F>(&mut self, mut handler: F)
     where
         F: 'static + FnMut(bool),
@@ -447,6 +757,80 @@ impl Canvas {
         self.on_intersect = Some(IntersectionObserverHandle::new(self.raw(), handler));
     }
 
+    pub(crate) fn on_move<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(),
+    {
+        self.on_move =
+            Some(PositionObserverHandle::new(self.window(), self.document(), self.raw(), handler));
+    }
+
+    pub(crate) fn on_keyboard_visibility<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(bool, Option<Region>),
+    {
+        self.on_keyboard_visibility =
+            Some(KeyboardObserverHandle::new(self.window(), self.raw(), handler));
+    }
+
+    pub(crate) fn on_surface<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(bool),
+    {
+        self.on_surface = Some(SurfaceObserverHandle::new(self.document(), self.raw(), handler));
+    }
+
+    pub(crate) fn on_context_loss<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(bool),
+    {
+        self.on_context_loss = Some(ContextLossHandle::new(self.raw(), handler));
+    }
+
+    /// Remove the splash element and fade the canvas in.
+    pub(crate) fn notify_first_frame_presented(&mut self) {
+        const FADE_IN_DURATION_MS: f64 = 200.;
+
+        if !mem::take(&mut self.splash_screen) {
+            return;
+        }
+
+        if let Some(element) = self.splash_element.take() {
+            element.remove();
+        }
+
+        self.style().remove("opacity");
+
+        #[wasm_bindgen]
+        extern "C" {
+            type ElementExt;
+
+            #[wasm_bindgen(method, getter, js_name = animate)]
+            fn has_animate(this: &ElementExt) -> JsValue;
+
+            // `web-sys` only exposes this behind `--cfg=web_sys_unstable_apis`.
+            #[wasm_bindgen(method)]
+            fn animate(this: &ElementExt, keyframes: &Array, duration: f64);
+        }
+
+        let element: &ElementExt = self.raw().unchecked_ref();
+        if !element.has_animate().is_undefined() {
+            let keyframe = |opacity: f64| {
+                let keyframe = Object::new();
+                Reflect::set(&keyframe, &"opacity".into(), &opacity.into())
+                    .expect("unexpected exception in `Reflect.set()`");
+                keyframe
+            };
+            element.animate(&Array::of2(&keyframe(0.), &keyframe(1.)), FADE_IN_DURATION_MS);
+        }
+    }
+
+    pub(crate) fn watch_gpu_device(&self, device: &JsValue) {
+        if let Some(handle) = &self.on_context_loss {
+            handle.watch_gpu_device(device);
+        }
+    }
+
     pub(crate) fn on_animation_frame<F>(&mut self, f: F)
     where
         F: 'static + FnMut(),
@@ -454,16 +838,78 @@ impl Canvas {
         self.animation_frame_handler.on_animation_frame(f)
     }
 
-    pub(crate) fn on_context_menu(&mut self) {
+    pub(crate) fn on_context_menu<F>(&mut self, mut handler: F)
+    where
+        F: 'static + FnMut(PhysicalPosition<f64>, ContextMenuKind),
+    {
+        let window = self.common.window.clone();
         let prevent_default = Rc::clone(&self.prevent_default);
+        let prevent_context_menu = Rc::clone(&self.prevent_context_menu);
         self.on_context_menu =
             Some(self.common.add_event("contextmenu", move |event: PointerEvent| {
-                if prevent_default.get() {
+                if prevent_context_menu.get().unwrap_or_else(|| prevent_default.get()) {
                     event.prevent_default();
                 }
+
+                // Only some browsers dispatch a `PointerEvent`, the others a `MouseEvent` without
+                // `pointerType`.
+                let pointer_type = Reflect::get(&event, &"pointerType".into())
+                    .ok()
+                    .and_then(|pointer_type| pointer_type.as_string());
+                let kind = match pointer_type.as_deref() {
+                    Some("touch" | "pen") => ContextMenuKind::Touch,
+                    _ if event::mouse_button(&event) == Some(MouseButton::Right) => {
+                        ContextMenuKind::Mouse
+                    },
+                    _ => ContextMenuKind::Keyboard,
+                };
+                let position =
+                    event::mouse_position(&event).to_physical(super::scale_factor(&window));
+                handler(position, kind);
             }));
     }
 
+    /// Calls `handler` with `true` when the fullscreen element of the document changed, and with
+    /// `false` when changing it failed.
+    pub(crate) fn on_fullscreen_change<F>(&mut self, handler: F)
+    where
+        F: 'static + FnMut(bool),
+    {
+        let handler = Rc::new(RefCell::new(handler));
+        self.on_fullscreen_change = Some(EventListenerHandle::new(
+            self.common.document.clone(),
+            "fullscreenchange",
+            Closure::new({
+                let handler = Rc::clone(&handler);
+                move |_: Event| (handler.borrow_mut())(true)
+            }),
+        ));
+        self.on_fullscreen_error = Some(EventListenerHandle::new(
+            self.common.document.clone(),
+            "fullscreenerror",
+            Closure::new(move |_: Event| (handler.borrow_mut())(false)),
+        ));
+    }
+
+    /// Returns the overlay element, creating it on first use.
+    pub fn overlay_container(&mut self) -> &HtmlElement {
+        match &self.overlay {
+            // The canvas might have been moved since the overlay was last positioned.
+            Some(overlay) => overlay.sync(),
+            None => {
+                let common = &self.common;
+                self.overlay = Some(Overlay::new(
+                    &common.window,
+                    &common.document,
+                    &common.raw,
+                    &common.style,
+                ));
+            },
+        }
+
+        self.overlay.as_ref().unwrap().element()
+    }
+
     pub fn request_fullscreen(&self) {
         fullscreen::request_fullscreen(self.document(), self.raw());
     }
@@ -480,6 +926,10 @@ impl Canvas {
         self.animation_frame_handler.request();
     }
 
+    pub fn set_max_frame_rate(&self, max_frame_rate: Option<f32>) {
+        self.animation_frame_handler.set_max_frame_rate(max_frame_rate);
+    }
+
     pub(crate) fn handle_scale_change(
         &self,
         runner: &super::super::event_loop::runner::Shared,
@@ -519,7 +969,7 @@ impl Canvas {
             self.set_old_size(new_size);
             runner.send_event(crate::event::Event::WindowEvent {
                 window_id: RootWindowId(self.id),
-                event: crate::event::WindowEvent::Resized(new_size),
+                event: crate::event::WindowEvent::Resized { size: new_size, frame: None },
             })
         }
     }
@@ -535,9 +985,50 @@ impl Canvas {
         self.pointer_handler.remove_listeners();
         self.on_resize_scale = None;
         self.on_intersect = None;
+        self.on_move = None;
+        self.on_surface = None;
+        self.on_context_loss = None;
+        self.on_keyboard_visibility = None;
         self.animation_frame_handler.cancel();
         self.on_touch_end = None;
         self.on_context_menu = None;
+        self.on_hittest = None;
+        self.on_fullscreen_change = None;
+        self.on_fullscreen_error = None;
+        self.on_drag_enter = None;
+        self.on_drag_over = None;
+        self.on_drag_leave = None;
+        self.on_drop = None;
+        self.overlay = None;
+        *self.ime_element.borrow_mut() = None;
+        self.ime = None;
+    }
+}
+
+fn keyboard_handler<F>(mut handler: F) -> SharedHandler<KeyboardEvent>
+where
+    F: 'static + FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, ModifiersState),
+{
+    Rc::new(RefCell::new(move |event: KeyboardEvent| {
+        let key = event::key(&event);
+        let text = event::key_text(&key);
+        let modifiers = event::keyboard_modifiers(&event);
+        handler(
+            event::key_code(&event),
+            key,
+            text,
+            event::key_location(&event),
+            event.repeat(),
+            modifiers,
+        );
+    }))
+}
+
+fn set_pointer_events(style: &Style, hittest: bool) {
+    if hittest {
+        style.remove("pointer-events");
+    } else {
+        style.set("pointer-events", "none");
     }
 }
 
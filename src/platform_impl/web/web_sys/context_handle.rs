@@ -0,0 +1,97 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use js_sys::{Promise, Reflect};
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Event, HtmlCanvasElement};
+
+use super::event_handle::EventListenerHandle;
+
+/// Calls the handler with `true` when the graphics context rendering to the canvas is lost and
+/// with `false` when it is restored.
+pub(super) struct ContextLossHandle {
+    inner: Rc<Inner>,
+    _on_context_lost: EventListenerHandle<dyn FnMut(Event)>,
+    _on_context_restored: EventListenerHandle<dyn FnMut(Event)>,
+}
+
+struct Inner {
+    /// Incremented for every watched `GPUDevice`, so only the loss of the latest one is reported.
+    generation: Cell<u64>,
+    /// Set when the handle is dropped, outstanding `GPUDevice.lost` promises are ignored then.
+    dropped: Cell<bool>,
+    handler: RefCell<Box<dyn FnMut(bool)>>,
+}
+
+impl ContextLossHandle {
+    pub fn new<F>(canvas: &HtmlCanvasElement, handler: F) -> Self
+    where
+        F: 'static + FnMut(bool),
+    {
+        let inner = Rc::new(Inner {
+            generation: Cell::new(0),
+            dropped: Cell::new(false),
+            handler: RefCell::new(Box::new(handler)),
+        });
+
+        let on_context_lost = EventListenerHandle::new(
+            canvas.clone(),
+            "webglcontextlost",
+            Closure::new({
+                let inner = inner.clone();
+                move |event: Event| {
+                    // Otherwise the browser doesn't attempt to restore the context.
+                    event.prevent_default();
+                    (inner.handler.borrow_mut())(true);
+                }
+            }),
+        );
+        let on_context_restored = EventListenerHandle::new(
+            canvas.clone(),
+            "webglcontextrestored",
+            Closure::new({
+                let inner = inner.clone();
+                move |_: Event| (inner.handler.borrow_mut())(false)
+            }),
+        );
+
+        Self { inner, _on_context_lost: on_context_lost, _on_context_restored: on_context_restored }
+    }
+
+    /// Report the loss of `device`, which has to be a `GPUDevice`, replacing the previously
+    /// watched one.
+    pub fn watch_gpu_device(&self, device: &JsValue) {
+        let generation = self.inner.generation.get() + 1;
+        self.inner.generation.set(generation);
+
+        // `web-sys` only exposes WebGPU behind `--cfg=web_sys_unstable_apis`.
+        let lost = match Reflect::get(device, &"lost".into()) {
+            Ok(lost) if lost.is_instance_of::<Promise>() => lost.unchecked_into::<Promise>(),
+            _ => return,
+        };
+
+        let inner = self.inner.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let Ok(info) = JsFuture::from(lost).await else { return };
+
+            if inner.dropped.get() || inner.generation.get() != generation {
+                return;
+            }
+
+            // Destroying the device is done by the application itself.
+            let reason =
+                Reflect::get(&info, &"reason".into()).ok().and_then(|reason| reason.as_string());
+            if reason.as_deref() != Some("destroyed") {
+                (inner.handler.borrow_mut())(true);
+            }
+        });
+    }
+}
+
+impl Drop for ContextLossHandle {
+    fn drop(&mut self) {
+        self.inner.dropped.set(true);
+    }
+}
@@ -28,7 +28,8 @@ impl From<ButtonsState> for MouseButton {
             ButtonsState::MIDDLE => MouseButton::Middle,
             ButtonsState::BACK => MouseButton::Back,
             ButtonsState::FORWARD => MouseButton::Forward,
-            _ => MouseButton::Other(value.bits()),
+            // Beyond the first three buttons, the bit index matches the `button` property.
+            _ => MouseButton::Other(value.bits().trailing_zeros() as u16),
         }
     }
 }
@@ -41,7 +42,9 @@ impl From<MouseButton> for ButtonsState {
             MouseButton::Middle => ButtonsState::MIDDLE,
             MouseButton::Back => ButtonsState::BACK,
             MouseButton::Forward => ButtonsState::FORWARD,
-            MouseButton::Other(value) => ButtonsState::from_bits_retain(value),
+            MouseButton::Other(value) => {
+                ButtonsState::from_bits_retain(1u16.checked_shl(value.into()).unwrap_or(0))
+            },
         }
     }
 }
@@ -78,6 +81,41 @@ impl MouseButton {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointerType {
+    Mouse,
+    Touch,
+    Other,
+}
+
+// `PointerEvent::pointer_type()` copies the string out of JS on every event, so compare it with
+// cached JS strings instead.
+pub fn pointer_type(event: &PointerEvent) -> PointerType {
+    #[wasm_bindgen]
+    extern "C" {
+        type PointerEventExt;
+
+        #[wasm_bindgen(method, getter, js_name = pointerType)]
+        fn pointer_type(this: &PointerEventExt) -> JsValue;
+    }
+
+    thread_local! {
+        static MOUSE: JsValue = JsValue::from_str("mouse");
+        static TOUCH: JsValue = JsValue::from_str("touch");
+    }
+
+    let event: &PointerEventExt = event.unchecked_ref();
+    let pointer_type = event.pointer_type();
+
+    if MOUSE.with(|mouse| pointer_type == *mouse) {
+        PointerType::Mouse
+    } else if TOUCH.with(|touch| pointer_type == *touch) {
+        PointerType::Touch
+    } else {
+        PointerType::Other
+    }
+}
+
 pub fn mouse_position(event: &MouseEvent) -> LogicalPosition<f64> {
     #[wasm_bindgen]
     extern "C" {
@@ -161,17 +199,15 @@ pub fn key(event: &KeyboardEvent) -> Key {
     Key::from_key_attribute_value(&event.key())
 }
 
-pub fn key_text(event: &KeyboardEvent) -> Option<SmolStr> {
-    let key = event.key();
-    let key = Key::from_key_attribute_value(&key);
-    match &key {
+// Takes the already parsed key, to avoid copying the key string out of JS twice.
+pub fn key_text(key: &Key) -> Option<SmolStr> {
+    match key {
         Key::Character(text) => Some(text.clone()),
-        Key::Named(NamedKey::Tab) => Some(SmolStr::new("\t")),
-        Key::Named(NamedKey::Enter) => Some(SmolStr::new("\r")),
-        Key::Named(NamedKey::Space) => Some(SmolStr::new(" ")),
+        Key::Named(NamedKey::Tab) => Some(SmolStr::new_static("\t")),
+        Key::Named(NamedKey::Enter) => Some(SmolStr::new_static("\r")),
+        Key::Named(NamedKey::Space) => Some(SmolStr::new_static(" ")),
         _ => None,
     }
-    .map(SmolStr::new)
 }
 
 pub fn key_location(event: &KeyboardEvent) -> KeyLocation {
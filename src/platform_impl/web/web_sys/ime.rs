@@ -0,0 +1,269 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    CompositionEvent, CssStyleDeclaration, Document, Event, EventTarget, FocusEvent,
+    HtmlCanvasElement, HtmlInputElement, KeyboardEvent,
+};
+
+use super::event;
+use super::event_handle::EventListenerHandle;
+use crate::dpi::{LogicalPosition, LogicalSize};
+use crate::event::Ime;
+use crate::keyboard::Key;
+
+pub type SharedHandler<E> = Rc<RefCell<dyn FnMut(E)>>;
+
+/// The handlers of the canvas, which also have to receive the events of the [`ImeInput`].
+#[derive(Default)]
+pub struct ImeHandlers {
+    pub ime: Option<SharedHandler<Ime>>,
+    pub focus: Option<SharedHandler<()>>,
+    pub blur: Option<SharedHandler<()>>,
+    pub keyboard_press: Option<SharedHandler<KeyboardEvent>>,
+    pub keyboard_release: Option<SharedHandler<KeyboardEvent>>,
+}
+
+/// A hidden text field which takes the focus of the canvas while IME is allowed.
+///
+/// Browsers only compose text in editable elements, and place the candidate window next to the
+/// focused one. So the field is kept at the cursor area set by the application.
+pub struct ImeInput {
+    element: HtmlInputElement,
+    style: CssStyleDeclaration,
+    _on_composition_start: EventListenerHandle<dyn FnMut(CompositionEvent)>,
+    _on_composition_update: EventListenerHandle<dyn FnMut(CompositionEvent)>,
+    _on_composition_end: EventListenerHandle<dyn FnMut(CompositionEvent)>,
+    _on_input: EventListenerHandle<dyn FnMut(Event)>,
+    _on_focus: EventListenerHandle<dyn FnMut(FocusEvent)>,
+    _on_blur: EventListenerHandle<dyn FnMut(FocusEvent)>,
+    _on_keyboard_press: EventListenerHandle<dyn FnMut(KeyboardEvent)>,
+    _on_keyboard_release: EventListenerHandle<dyn FnMut(KeyboardEvent)>,
+}
+
+impl ImeInput {
+    pub fn new(
+        document: &Document,
+        canvas: &HtmlCanvasElement,
+        handlers: &ImeHandlers,
+        prevent_default: Rc<Cell<bool>>,
+    ) -> Self {
+        let element: HtmlInputElement = document
+            .create_element("input")
+            .expect("Failed to create IME input element")
+            .unchecked_into();
+        #[allow(clippy::disallowed_methods)]
+        let style = element.style();
+
+        for (property, value) in [
+            ("position", "fixed"),
+            ("opacity", "0"),
+            ("pointer-events", "none"),
+            ("box-sizing", "border-box"),
+            ("margin", "0"),
+            ("padding", "0"),
+            ("border", "none"),
+            ("outline", "none"),
+            ("background", "transparent"),
+            ("color", "transparent"),
+            ("caret-color", "transparent"),
+        ] {
+            style.set_property(property, value).expect("Property is read only");
+        }
+        // Keep it out of the sequential keyboard navigation, the canvas is part of it instead.
+        let _ = element.set_attribute("tabindex", "-1");
+        let _ = element.set_attribute("aria-hidden", "true");
+        // Take over the attributes set for the `ImePurpose`, the `TextServicesConfig` and the
+        // `KeyboardHints`.
+        for attribute in [
+            "autocomplete",
+            "autocorrect",
+            "autocapitalize",
+            "spellcheck",
+            "inputmode",
+            "enterkeyhint",
+        ] {
+            if let Some(value) = canvas.get_attribute(attribute) {
+                let _ = element.set_attribute(attribute, &value);
+            }
+        }
+
+        // Keep the field next to the canvas, so it is part of the same fullscreen element or
+        // dialog.
+        match canvas.parent_node() {
+            Some(parent) => {
+                let _ = parent.insert_before(&element, canvas.next_sibling().as_ref());
+            },
+            None => {
+                if let Some(body) = document.body() {
+                    let _ = body.append_child(&element);
+                }
+            },
+        }
+
+        let composing = Rc::new(Cell::new(false));
+        let send = {
+            let handler = handlers.ime.clone();
+            move |ime: Ime| {
+                if let Some(handler) = &handler {
+                    (handler.borrow_mut())(ime);
+                }
+            }
+        };
+
+        let on_composition_start = EventListenerHandle::new(
+            element.clone(),
+            "compositionstart",
+            Closure::new({
+                let composing = Rc::clone(&composing);
+                move |_: CompositionEvent| composing.set(true)
+            }),
+        );
+        let on_composition_update = EventListenerHandle::new(
+            element.clone(),
+            "compositionupdate",
+            Closure::new({
+                let send = send.clone();
+                move |event: CompositionEvent| {
+                    let text = event.data().unwrap_or_default();
+                    let cursor = text.len();
+                    send(Ime::Preedit(text, Some((cursor, cursor)), Vec::new()));
+                }
+            }),
+        );
+        let on_composition_end = EventListenerHandle::new(
+            element.clone(),
+            "compositionend",
+            Closure::new({
+                let element = element.clone();
+                let composing = Rc::clone(&composing);
+                move |event: CompositionEvent| {
+                    composing.set(false);
+                    element.set_value("");
+
+                    send(Ime::Preedit(String::new(), None, Vec::new()));
+                    let text = event.data().unwrap_or_default();
+                    if !text.is_empty() {
+                        send(Ime::Commit(text));
+                    }
+                }
+            }),
+        );
+        // Text entered without composing was already reported by the keyboard events.
+        let on_input = EventListenerHandle::new(
+            element.clone(),
+            "input",
+            Closure::new({
+                let element = element.clone();
+                let composing = Rc::clone(&composing);
+                move |_: Event| {
+                    if !composing.get() {
+                        element.set_value("");
+                    }
+                }
+            }),
+        );
+
+        // Moving the focus between the canvas and the field doesn't change the focus of the window.
+        let on_focus = EventListenerHandle::new(
+            element.clone(),
+            "focus",
+            Closure::new({
+                let handler = handlers.focus.clone();
+                move |_: FocusEvent| {
+                    if let Some(handler) = &handler {
+                        (handler.borrow_mut())(());
+                    }
+                }
+            }),
+        );
+        let on_blur = EventListenerHandle::new(
+            element.clone(),
+            "blur",
+            Closure::new({
+                let canvas: EventTarget = canvas.clone().into();
+                let handler = handlers.blur.clone();
+                move |event: FocusEvent| {
+                    if event.related_target().as_ref() == Some(&canvas) {
+                        return;
+                    }
+                    if let Some(handler) = &handler {
+                        (handler.borrow_mut())(());
+                    }
+                }
+            }),
+        );
+
+        let keyboard_listener =
+            |handler: Option<SharedHandler<KeyboardEvent>>| -> Closure<dyn FnMut(KeyboardEvent)> {
+                let prevent_default = Rc::clone(&prevent_default);
+                let composing = Rc::clone(&composing);
+                Closure::new(move |event: KeyboardEvent| {
+                    // Keys handled by the IME are reported through `Ime` events instead.
+                    if composing.get() || event.is_composing() || event.key() == "Process" {
+                        return;
+                    }
+
+                    // Keys producing text have to reach the field to start composing.
+                    let key = event::key(&event);
+                    if prevent_default.get() && !matches!(key, Key::Character(_) | Key::Dead(_)) {
+                        event.prevent_default();
+                    }
+
+                    if let Some(handler) = &handler {
+                        (handler.borrow_mut())(event);
+                    }
+                })
+            };
+        let on_keyboard_press = EventListenerHandle::new(
+            element.clone(),
+            "keydown",
+            keyboard_listener(handlers.keyboard_press.clone()),
+        );
+        let on_keyboard_release = EventListenerHandle::new(
+            element.clone(),
+            "keyup",
+            keyboard_listener(handlers.keyboard_release.clone()),
+        );
+
+        Self {
+            element,
+            style,
+            _on_composition_start: on_composition_start,
+            _on_composition_update: on_composition_update,
+            _on_composition_end: on_composition_end,
+            _on_input: on_input,
+            _on_focus: on_focus,
+            _on_blur: on_blur,
+            _on_keyboard_press: on_keyboard_press,
+            _on_keyboard_release: on_keyboard_release,
+        }
+    }
+
+    pub fn element(&self) -> &HtmlInputElement {
+        &self.element
+    }
+
+    /// Move the field to an area relative to the viewport.
+    pub fn set_area(&self, position: LogicalPosition<f64>, size: LogicalSize<f64>) {
+        for (property, value) in [
+            ("left", position.x),
+            ("top", position.y),
+            // Browsers ignore empty elements for positioning the candidate window.
+            ("width", size.width.max(1.)),
+            ("height", size.height.max(1.)),
+        ] {
+            self.style
+                .set_property(property, &format!("{value}px"))
+                .expect("Property is read only");
+        }
+    }
+}
+
+impl Drop for ImeInput {
+    fn drop(&mut self) {
+        self.element.remove();
+    }
+}
@@ -0,0 +1,121 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use js_sys::Reflect;
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{DomRect, Event, EventTarget, HtmlCanvasElement};
+
+use super::event_handle::EventListenerHandle;
+use crate::dpi::{LogicalPosition, LogicalSize};
+use crate::window::Region;
+
+/// The share of the viewport height the visual viewport has to shrink by to assume that it's the
+/// on-screen keyboard, rather than the toolbars of the browser.
+const KEYBOARD_MIN_HEIGHT: f64 = 0.15;
+
+/// Calls the handler whenever the on-screen keyboard is shown, hidden or moved, with the part of
+/// the canvas it covers.
+///
+/// The VirtualKeyboard API reports the keyboard itself, but only while the page sets
+/// `navigator.virtualKeyboard.overlaysContent`, as the keyboard doesn't resize the viewport then.
+/// Otherwise, the keyboard shrinks the visual viewport from the bottom.
+pub(super) struct KeyboardObserverHandle {
+    _on_geometry_change: Option<EventListenerHandle<dyn FnMut(Event)>>,
+    _on_viewport_resize: Option<EventListenerHandle<dyn FnMut(Event)>>,
+}
+
+struct Inner {
+    window: web_sys::Window,
+    canvas: HtmlCanvasElement,
+    virtual_keyboard: Option<JsValue>,
+    /// Whether the keyboard is visible and the part of the canvas it covers, as last reported.
+    state: Cell<(bool, Option<Region>)>,
+    handler: RefCell<Box<dyn FnMut(bool, Option<Region>)>>,
+}
+
+impl KeyboardObserverHandle {
+    pub fn new<F>(window: &web_sys::Window, canvas: &HtmlCanvasElement, handler: F) -> Self
+    where
+        F: 'static + FnMut(bool, Option<Region>),
+    {
+        // Only Chromium supports the VirtualKeyboard API.
+        let virtual_keyboard = Reflect::get(window, &"navigator".into())
+            .and_then(|navigator| Reflect::get(&navigator, &"virtualKeyboard".into()))
+            .ok()
+            .filter(|virtual_keyboard| !virtual_keyboard.is_undefined());
+        let inner = Rc::new(Inner {
+            window: window.clone(),
+            canvas: canvas.clone(),
+            virtual_keyboard: virtual_keyboard.clone(),
+            state: Cell::new((false, None)),
+            handler: RefCell::new(Box::new(handler)),
+        });
+
+        let listener = |target: EventTarget,
+                        event_type: &'static str|
+         -> EventListenerHandle<dyn FnMut(Event)> {
+            let inner = inner.clone();
+            EventListenerHandle::new(
+                target,
+                event_type,
+                Closure::new(move |_: Event| inner.update()),
+            )
+        };
+        let on_geometry_change = virtual_keyboard
+            .map(|virtual_keyboard| listener(virtual_keyboard.unchecked_into(), "geometrychange"));
+        let on_viewport_resize =
+            window.visual_viewport().map(|viewport| listener(viewport.into(), "resize"));
+
+        Self { _on_geometry_change: on_geometry_change, _on_viewport_resize: on_viewport_resize }
+    }
+}
+
+impl Inner {
+    fn update(&self) {
+        let keyboard = self.keyboard_rect();
+        let occluded_rect = keyboard.and_then(|keyboard| self.canvas_intersection(keyboard));
+        let state = (keyboard.is_some(), occluded_rect);
+        if state != self.state.get() {
+            self.state.set(state);
+            (self.handler.borrow_mut())(state.0, state.1);
+        }
+    }
+
+    /// The bounds of the keyboard in the viewport, as `(x, y, width, height)`.
+    fn keyboard_rect(&self) -> Option<(f64, f64, f64, f64)> {
+        if let Some(virtual_keyboard) = &self.virtual_keyboard {
+            let overlays_content = Reflect::get(virtual_keyboard, &"overlaysContent".into())
+                .ok()
+                .and_then(|overlays_content| overlays_content.as_bool())
+                .unwrap_or(false);
+            if overlays_content {
+                let rect: DomRect =
+                    Reflect::get(virtual_keyboard, &"boundingRect".into()).ok()?.dyn_into().ok()?;
+                return (rect.width() > 0. && rect.height() > 0.)
+                    .then(|| (rect.x(), rect.y(), rect.width(), rect.height()));
+            }
+        }
+
+        let viewport = self.window.visual_viewport()?;
+        let width = self.window.inner_width().ok()?.as_f64()?;
+        let height = self.window.inner_height().ok()?.as_f64()?;
+        let bottom = viewport.offset_top() + viewport.height();
+        let covered = height - bottom;
+        (covered > height * KEYBOARD_MIN_HEIGHT).then_some((0., bottom, width, covered))
+    }
+
+    fn canvas_intersection(&self, (x, y, width, height): (f64, f64, f64, f64)) -> Option<Region> {
+        let canvas = self.canvas.get_bounding_client_rect();
+        let left = x.max(canvas.left());
+        let top = y.max(canvas.top());
+        let right = (x + width).min(canvas.right());
+        let bottom = (y + height).min(canvas.bottom());
+        (right > left && bottom > top).then(|| {
+            Region::new(
+                LogicalPosition::new(left - canvas.left(), top - canvas.top()),
+                LogicalSize::new(right - left, bottom - top),
+            )
+        })
+    }
+}
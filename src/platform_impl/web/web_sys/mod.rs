@@ -1,22 +1,33 @@
 mod animation_frame;
 mod canvas;
+mod context_handle;
 pub mod event;
 mod event_handle;
 mod fullscreen;
+mod ime;
 mod intersection_handle;
+mod keyboard_handle;
 mod media_query_handle;
+mod overlay;
 mod pointer;
+mod position_handle;
 mod resize_scaling;
 mod schedule;
+mod surface_handle;
 
 pub use self::canvas::{Canvas, Style};
 pub use self::event::ButtonsState;
 pub use self::event_handle::EventListenerHandle;
 pub use self::resize_scaling::ResizeScaleHandle;
-pub use self::schedule::Schedule;
+pub use self::schedule::{IdleRequest, Schedule};
 
 use crate::dpi::{LogicalPosition, LogicalSize};
+use crate::event_loop::DpiPolicy;
+use crate::locale::LanguageTag;
+use crate::monitor::Rect;
+use js_sys::{Array, Reflect};
 use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
 use web_sys::{Document, HtmlCanvasElement, PageTransitionEvent, VisibilityState};
 
 pub fn throw(msg: &str) {
@@ -43,7 +54,7 @@ pub fn on_page_transition(
 }
 
 pub fn scale_factor(window: &web_sys::Window) -> f64 {
-    window.device_pixel_ratio()
+    DpiPolicy::current().apply(window.device_pixel_ratio())
 }
 
 fn fix_canvas_size(style: &Style, mut size: LogicalSize<f64>) -> LogicalSize<f64> {
@@ -153,6 +164,64 @@ pub fn is_dark_mode(window: &web_sys::Window) -> Option<bool> {
     window.match_media("(prefers-color-scheme: dark)").ok().flatten().map(|media| media.matches())
 }
 
+pub fn matches_media(window: &web_sys::Window, query: &str) -> bool {
+    window.match_media(query).ok().flatten().is_some_and(|media| media.matches())
+}
+
+/// Whether the browser runs on a mobile device.
+pub fn is_mobile(window: &web_sys::Window) -> bool {
+    // `navigator.userAgentData` is only available in Chromium based browsers, otherwise a
+    // touchscreen as the primary input device is taken as a sign of a mobile device.
+    let user_agent_data = Reflect::get(window, &"navigator".into())
+        .and_then(|navigator| Reflect::get(&navigator, &"userAgentData".into()))
+        .ok()
+        .filter(|data| !data.is_undefined());
+    match user_agent_data.and_then(|data| Reflect::get(&data, &"mobile".into()).ok()) {
+        Some(mobile) => mobile.is_truthy(),
+        None => matches_media(window, "(pointer: coarse) and (hover: none)"),
+    }
+}
+
+/// The language the user prefers the most, from `navigator.languages`.
+pub fn locale(window: &web_sys::Window) -> Option<LanguageTag> {
+    let navigator = Reflect::get(window, &"navigator".into()).ok()?;
+    let languages = Reflect::get(&navigator, &"languages".into()).ok()?;
+    // Older browsers only support `navigator.language`.
+    let language = match languages.dyn_into::<Array>() {
+        Ok(languages) => languages.get(0),
+        Err(_) => Reflect::get(&navigator, &"language".into()).ok()?,
+    };
+    LanguageTag::parse(&language.as_string()?)
+}
+
+/// The `window.screen` object, which fires `change` events in some browsers.
+pub fn screen(window: &web_sys::Window) -> Option<web_sys::EventTarget> {
+    Reflect::get(window, &"screen".into()).ok()?.dyn_into().ok()
+}
+
+/// The area of the screen available to windows, from `screen.availLeft` and friends.
+pub fn work_area(window: &web_sys::Window) -> Rect {
+    let screen = screen(window);
+    let get = |name: &str| {
+        screen
+            .as_ref()
+            .and_then(|screen| Reflect::get(screen, &name.into()).ok())
+            .and_then(|value| value.as_f64())
+            .unwrap_or_default()
+    };
+
+    let scale_factor = scale_factor(window);
+    Rect::new(
+        LogicalPosition::new(get("availLeft"), get("availTop")).to_physical(scale_factor),
+        LogicalSize::new(get("availWidth"), get("availHeight")).to_physical(scale_factor),
+    )
+}
+
+/// Whether the browser supports the Pointer Lock API.
+pub fn has_pointer_lock(document: &Document) -> bool {
+    Reflect::has(document, &"pointerLockElement".into()).unwrap_or(false)
+}
+
 pub fn is_visible(document: &Document) -> bool {
     document.visibility_state() == VisibilityState::Visible
 }
@@ -0,0 +1,211 @@
+use std::rc::Rc;
+
+use js_sys::Array;
+use wasm_bindgen::prelude::{wasm_bindgen, Closure};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{
+    CssStyleDeclaration, Document, Event, HtmlCanvasElement, HtmlElement, Node, ResizeObserver,
+};
+
+use super::canvas::Style;
+use super::event_handle::EventListenerHandle;
+use super::fullscreen;
+
+/// An element kept positioned exactly over the canvas, see
+/// [`WindowExtWebSys::overlay_container()`](crate::platform::web::WindowExtWebSys::overlay_container).
+pub struct Overlay {
+    inner: Rc<Inner>,
+    observer: ResizeObserver,
+    _observer_closure: Closure<dyn FnMut(Array, ResizeObserver)>,
+    _on_window_resize: EventListenerHandle<dyn FnMut(Event)>,
+    _on_fullscreen_change: EventListenerHandle<dyn FnMut(Event)>,
+    _on_webkit_fullscreen_change: EventListenerHandle<dyn FnMut(Event)>,
+}
+
+struct Inner {
+    document: Document,
+    canvas: HtmlCanvasElement,
+    canvas_style: Style,
+    element: HtmlElement,
+    style: CssStyleDeclaration,
+}
+
+impl Overlay {
+    pub fn new(
+        window: &web_sys::Window,
+        document: &Document,
+        canvas: &HtmlCanvasElement,
+        canvas_style: &Style,
+    ) -> Self {
+        let element: HtmlElement = document
+            .create_element("div")
+            .expect("Failed to create overlay element")
+            .unchecked_into();
+        #[allow(clippy::disallowed_methods)]
+        let style = element.style();
+
+        // The overlay itself should be transparent to input, so only the elements added to it
+        // receive pointer events instead of the canvas.
+        for (property, value) in [
+            ("pointer-events", "none"),
+            ("box-sizing", "border-box"),
+            ("margin", "0"),
+            ("padding", "0"),
+            ("border", "none"),
+            ("background", "transparent"),
+            ("overflow", "visible"),
+        ] {
+            style.set_property(property, value).expect("Property is read only");
+        }
+
+        let inner = Rc::new(Inner {
+            document: document.clone(),
+            canvas: canvas.clone(),
+            canvas_style: canvas_style.clone(),
+            element,
+            style,
+        });
+
+        let observer_closure: Closure<dyn FnMut(Array, ResizeObserver)> = Closure::new({
+            let inner = Rc::clone(&inner);
+            move |_: Array, _: ResizeObserver| inner.sync()
+        });
+        let observer = ResizeObserver::new(observer_closure.as_ref().unchecked_ref())
+            .expect("Failed to create `ResizeObserver`");
+        observer.observe(canvas);
+
+        // A resize of the window can move the canvas without resizing it.
+        let on_window_resize = EventListenerHandle::new(
+            window.clone(),
+            "resize",
+            Closure::new({
+                let inner = Rc::clone(&inner);
+                move |_: Event| inner.sync()
+            }),
+        );
+        let on_fullscreen_change = EventListenerHandle::new(
+            canvas.clone(),
+            "fullscreenchange",
+            Closure::new({
+                let inner = Rc::clone(&inner);
+                move |_: Event| inner.sync()
+            }),
+        );
+        // TODO: Remove when Safari supports the unprefixed Fullscreen API on iOS.
+        let on_webkit_fullscreen_change = EventListenerHandle::new(
+            canvas.clone(),
+            "webkitfullscreenchange",
+            Closure::new({
+                let inner = Rc::clone(&inner);
+                move |_: Event| inner.sync()
+            }),
+        );
+
+        inner.sync();
+
+        Self {
+            inner,
+            observer,
+            _observer_closure: observer_closure,
+            _on_window_resize: on_window_resize,
+            _on_fullscreen_change: on_fullscreen_change,
+            _on_webkit_fullscreen_change: on_webkit_fullscreen_change,
+        }
+    }
+
+    pub fn element(&self) -> &HtmlElement {
+        &self.inner.element
+    }
+
+    /// Re-position the overlay, e.g. if the canvas was moved without resizing it.
+    pub fn sync(&self) {
+        self.inner.sync()
+    }
+}
+
+impl Drop for Overlay {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+        self.inner.element.remove();
+    }
+}
+
+impl Inner {
+    fn sync(&self) {
+        #[wasm_bindgen]
+        extern "C" {
+            #[wasm_bindgen(extends = HtmlElement)]
+            type Popover;
+
+            #[wasm_bindgen(catch, method, js_name = showPopover)]
+            fn show_popover(this: &Popover) -> Result<(), JsValue>;
+
+            #[wasm_bindgen(catch, method, js_name = hidePopover)]
+            fn hide_popover(this: &Popover) -> Result<(), JsValue>;
+        }
+
+        let popover: &Popover = self.element.unchecked_ref();
+        let fullscreen = fullscreen::is_fullscreen(&self.document, &self.canvas);
+
+        // Keep the overlay next to the canvas, even if the canvas was moved in the DOM.
+        let Some(parent) = self.canvas.parent_node() else {
+            self.element.remove();
+            return;
+        };
+        let canvas: &Node = &self.canvas;
+        if self.element.previous_sibling().as_ref() != Some(canvas) {
+            let _ = parent.insert_before(&self.element, self.canvas.next_sibling().as_ref());
+        }
+
+        if fullscreen {
+            // A fullscreen canvas is rendered in the top layer, above the rest of the page. The
+            // only way to render the overlay above it is to add it to the top layer as well, which
+            // isn't supported by all browsers.
+            if !self.element.has_attribute("popover") {
+                let _ = self.element.set_attribute("popover", "manual");
+                if popover.show_popover().is_err() {
+                    let _ = self.element.remove_attribute("popover");
+                    self.set("display", "none");
+                    return;
+                }
+            }
+
+            self.set_geometry("fixed", 0., 0., "100%", "100%");
+            return;
+        } else if self.element.has_attribute("popover") {
+            let _ = popover.hide_popover();
+            let _ = self.element.remove_attribute("popover");
+        }
+
+        if !self.document.contains(Some(&self.canvas)) || self.canvas_style.get("display") == "none"
+        {
+            self.set("display", "none");
+            return;
+        }
+
+        let width = format!("{}px", self.canvas.offset_width());
+        let height = format!("{}px", self.canvas.offset_height());
+        if self.canvas_style.get("position") == "fixed" {
+            let bounds = self.canvas.get_bounding_client_rect();
+            self.set_geometry("fixed", bounds.x(), bounds.y(), &width, &height);
+        } else {
+            // The overlay shares the containing block of the canvas, so the offsets of the canvas
+            // can be used as is.
+            let (left, top) = (self.canvas.offset_left(), self.canvas.offset_top());
+            self.set_geometry("absolute", left.into(), top.into(), &width, &height);
+        }
+    }
+
+    fn set_geometry(&self, position: &str, left: f64, top: f64, width: &str, height: &str) {
+        self.set("display", "block");
+        self.set("position", position);
+        self.set("left", &format!("{left}px"));
+        self.set("top", &format!("{top}px"));
+        self.set("width", width);
+        self.set("height", height);
+    }
+
+    fn set(&self, property: &str, value: &str) {
+        self.style.set_property(property, value).expect("Property is read only");
+    }
+}
@@ -8,8 +8,8 @@ use crate::dpi::PhysicalPosition;
 use crate::event::{Force, MouseButton};
 use crate::keyboard::ModifiersState;
 
-use event::ButtonsState;
-use web_sys::PointerEvent;
+use event::{ButtonsState, PointerType};
+use web_sys::{Element, PointerEvent};
 
 #[allow(dead_code)]
 pub(super) struct PointerHandler {
@@ -19,6 +19,7 @@ pub(super) struct PointerHandler {
     on_pointer_press: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
     on_pointer_release: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
     on_touch_cancel: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
+    on_pointer_drag: Option<EventListenerHandle<dyn FnMut(PointerEvent)>>,
 }
 
 impl PointerHandler {
@@ -30,6 +31,7 @@ impl PointerHandler {
             on_pointer_press: None,
             on_pointer_release: None,
             on_touch_cancel: None,
+            on_pointer_drag: None,
         }
     }
 
@@ -44,7 +46,8 @@ impl PointerHandler {
                 // touch events are handled separately
                 // handling them here would produce duplicate mouse events, inconsistent with
                 // other platforms.
-                let pointer_id = (event.pointer_type() == "mouse").then(|| event.pointer_id());
+                let pointer_id =
+                    (event::pointer_type(&event) == PointerType::Mouse).then(|| event.pointer_id());
 
                 handler(modifiers, pointer_id);
             }));
@@ -61,12 +64,35 @@ impl PointerHandler {
                 // touch events are handled separately
                 // handling them here would produce duplicate mouse events, inconsistent with
                 // other platforms.
-                let pointer_id = (event.pointer_type() == "mouse").then(|| event.pointer_id());
+                let pointer_id =
+                    (event::pointer_type(&event) == PointerType::Mouse).then(|| event.pointer_id());
 
                 handler(modifiers, pointer_id);
             }));
     }
 
+    /// Calls the handler on mouse movement, with the element under the mouse if it's captured by
+    /// the canvas, during which boundary events are only fired for the canvas.
+    pub fn on_pointer_drag<F>(&mut self, canvas_common: &Common, mut handler: F)
+    where
+        F: 'static + FnMut(i32, Option<Option<Element>>),
+    {
+        let document = canvas_common.document.clone();
+        let canvas = canvas_common.raw().clone();
+        self.on_pointer_drag =
+            Some(canvas_common.add_event("pointermove", move |event: PointerEvent| {
+                if event::pointer_type(&event) != PointerType::Mouse {
+                    return;
+                }
+
+                let pointer_id = event.pointer_id();
+                let element = canvas.has_pointer_capture(pointer_id).then(|| {
+                    document.element_from_point(event.client_x() as f32, event.client_y() as f32)
+                });
+                handler(pointer_id, element);
+            }));
+    }
+
     pub fn on_mouse_release<MOD, M, T>(
         &mut self,
         canvas_common: &Common,
@@ -83,20 +109,20 @@ impl PointerHandler {
             Some(canvas_common.add_event("pointerup", move |event: PointerEvent| {
                 let modifiers = event::mouse_modifiers(&event);
 
-                match event.pointer_type().as_str() {
-                    "touch" => touch_handler(
+                match event::pointer_type(&event) {
+                    PointerType::Touch => touch_handler(
                         modifiers,
                         event.pointer_id(),
                         event::mouse_position(&event).to_physical(super::scale_factor(&window)),
                         Force::Normalized(event.pressure() as f64),
                     ),
-                    "mouse" => mouse_handler(
+                    PointerType::Mouse => mouse_handler(
                         modifiers,
                         event.pointer_id(),
                         event::mouse_position(&event).to_physical(super::scale_factor(&window)),
                         event::mouse_button(&event).expect("no mouse button released"),
                     ),
-                    _ => modifier_handler(modifiers),
+                    PointerType::Other => modifier_handler(modifiers),
                 }
             }));
     }
@@ -110,7 +136,7 @@ impl PointerHandler {
         prevent_default: Rc<Cell<bool>>,
     ) where
         MOD: 'static + FnMut(ModifiersState),
-        M: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, MouseButton),
+        M: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, MouseButton, Option<u32>),
         T: 'static + FnMut(ModifiersState, i32, PhysicalPosition<f64>, Force),
     {
         let window = canvas_common.window.clone();
@@ -126,8 +152,8 @@ impl PointerHandler {
 
                 let modifiers = event::mouse_modifiers(&event);
 
-                match event.pointer_type().as_str() {
-                    "touch" => {
+                match event::pointer_type(&event) {
+                    PointerType::Touch => {
                         touch_handler(
                             modifiers,
                             event.pointer_id(),
@@ -135,12 +161,14 @@ impl PointerHandler {
                             Force::Normalized(event.pressure() as f64),
                         );
                     },
-                    "mouse" => {
+                    PointerType::Mouse => {
                         mouse_handler(
                             modifiers,
                             event.pointer_id(),
                             event::mouse_position(&event).to_physical(super::scale_factor(&window)),
                             event::mouse_button(&event).expect("no mouse button pressed"),
+                            // Browsers that count clicks for pointer events report it in `detail`.
+                            u32::try_from(event.detail()).ok().filter(|&count| count > 0),
                         );
 
                         // Error is swallowed here since the error would occur every time the mouse
@@ -149,7 +177,7 @@ impl PointerHandler {
                         // fail, that we care if it fails.
                         let _e = canvas.set_pointer_capture(event.pointer_id());
                     },
-                    _ => modifier_handler(modifiers),
+                    PointerType::Other => modifier_handler(modifiers),
                 }
             }));
     }
@@ -175,10 +203,9 @@ impl PointerHandler {
             Some(canvas_common.add_event("pointermove", move |event: PointerEvent| {
                 let modifiers = event::mouse_modifiers(&event);
 
-                let pointer_type = event.pointer_type();
+                let pointer_type = event::pointer_type(&event);
 
-                if let "touch" | "mouse" = pointer_type.as_str() {
-                } else {
+                if pointer_type == PointerType::Other {
                     modifier_handler(modifiers);
                     return;
                 }
@@ -188,7 +215,8 @@ impl PointerHandler {
                 // chorded button event
                 if let Some(button) = event::mouse_button(&event) {
                     debug_assert_eq!(
-                        pointer_type, "mouse",
+                        pointer_type,
+                        PointerType::Mouse,
                         "expect pointer type of a chorded button event to be a mouse"
                     );
 
@@ -212,14 +240,14 @@ impl PointerHandler {
 
                 // pointer move event
                 let scale = super::scale_factor(&window);
-                match pointer_type.as_str() {
-                    "mouse" => mouse_handler(
+                match pointer_type {
+                    PointerType::Mouse => mouse_handler(
                         modifiers,
                         id,
                         &mut event::pointer_move_event(event)
                             .map(|event| event::mouse_position(&event).to_physical(scale)),
                     ),
-                    "touch" => touch_handler(
+                    PointerType::Touch => touch_handler(
                         modifiers,
                         id,
                         &mut event::pointer_move_event(event).map(|event| {
@@ -229,7 +257,7 @@ impl PointerHandler {
                             )
                         }),
                     ),
-                    _ => unreachable!("didn't return early before"),
+                    PointerType::Other => unreachable!("didn't return early before"),
                 };
             }));
     }
@@ -241,7 +269,7 @@ impl PointerHandler {
         let window = canvas_common.window.clone();
         self.on_touch_cancel =
             Some(canvas_common.add_event("pointercancel", move |event: PointerEvent| {
-                if event.pointer_type() == "touch" {
+                if event::pointer_type(&event) == PointerType::Touch {
                     handler(
                         event.pointer_id(),
                         event::mouse_position(&event).to_physical(super::scale_factor(&window)),
@@ -258,5 +286,6 @@ impl PointerHandler {
         self.on_pointer_press = None;
         self.on_pointer_release = None;
         self.on_touch_cancel = None;
+        self.on_pointer_drag = None;
     }
 }
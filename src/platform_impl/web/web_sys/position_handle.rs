@@ -0,0 +1,176 @@
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    Document, Event, HtmlCanvasElement, IntersectionObserver, IntersectionObserverEntry,
+    IntersectionObserverInit, ResizeObserver,
+};
+
+use super::event_handle::EventListenerHandle;
+
+/// Calls the handler whenever the canvas might have moved relative to the viewport.
+///
+/// There is no event for an element changing its position, so an `IntersectionObserver` is
+/// created whose root is shrunk to the current bounds of the canvas. Any movement of the canvas,
+/// be it by scrolling or by a layout change anywhere on the page, then changes the intersection
+/// ratio and the observer is re-created for the new bounds.
+pub(super) struct PositionObserverHandle {
+    inner: Rc<Inner>,
+    resize_observer: ResizeObserver,
+    _resize_closure: Closure<dyn FnMut(Array, ResizeObserver)>,
+    _on_window_resize: EventListenerHandle<dyn FnMut(Event)>,
+}
+
+struct Inner {
+    document: Document,
+    canvas: HtmlCanvasElement,
+    observer: RefCell<Option<IntersectionObserver>>,
+    closure: Closure<dyn FnMut(Array)>,
+    /// The intersection ratio expected while the canvas didn't move.
+    threshold: Cell<f64>,
+    /// The first callback of a new observer only reports the initial intersection ratio.
+    first_update: Cell<bool>,
+    handler: RefCell<Box<dyn FnMut()>>,
+}
+
+impl PositionObserverHandle {
+    pub fn new<F>(
+        window: &web_sys::Window,
+        document: &Document,
+        canvas: &HtmlCanvasElement,
+        handler: F,
+    ) -> Self
+    where
+        F: 'static + FnMut(),
+    {
+        let inner = Rc::new_cyclic(|weak: &Weak<Inner>| {
+            let weak = weak.clone();
+            Inner {
+                document: document.clone(),
+                canvas: canvas.clone(),
+                observer: RefCell::new(None),
+                closure: Closure::new(move |entries: Array| {
+                    if let Some(inner) = weak.upgrade() {
+                        inner.handle_entries(entries);
+                    }
+                }),
+                threshold: Cell::new(1.),
+                first_update: Cell::new(true),
+                handler: RefCell::new(Box::new(handler)),
+            }
+        });
+        inner.refresh(1.);
+
+        // The root of the observer doesn't follow changes of the canvas size or the viewport.
+        let resize_closure: Closure<dyn FnMut(Array, ResizeObserver)> = Closure::new({
+            let inner = Rc::downgrade(&inner);
+            move |_: Array, _: ResizeObserver| {
+                if let Some(inner) = inner.upgrade() {
+                    inner.moved();
+                }
+            }
+        });
+        let resize_observer = ResizeObserver::new(resize_closure.as_ref().unchecked_ref())
+            .expect("Failed to create `ResizeObserver`");
+        resize_observer.observe(canvas);
+
+        let on_window_resize = EventListenerHandle::new(
+            window.clone(),
+            "resize",
+            Closure::new({
+                let inner = Rc::downgrade(&inner);
+                move |_: Event| {
+                    if let Some(inner) = inner.upgrade() {
+                        inner.moved();
+                    }
+                }
+            }),
+        );
+
+        Self {
+            inner,
+            resize_observer,
+            _resize_closure: resize_closure,
+            _on_window_resize: on_window_resize,
+        }
+    }
+}
+
+impl Drop for PositionObserverHandle {
+    fn drop(&mut self) {
+        self.resize_observer.disconnect();
+        if let Some(observer) = self.inner.observer.take() {
+            observer.disconnect();
+        }
+    }
+}
+
+impl Inner {
+    fn handle_entries(&self, entries: Array) {
+        let Some(entry) = entries.pop().dyn_into::<IntersectionObserverEntry>().ok() else {
+            return;
+        };
+        let ratio = entry.intersection_ratio();
+
+        if ratio != self.threshold.get() {
+            if self.first_update.get() {
+                // The canvas is clipped by one of its ancestors, so observe the clipped ratio
+                // instead. If it is fully clipped, the observer is only notified once it becomes
+                // visible again.
+                self.refresh(ratio);
+                return;
+            }
+
+            self.moved();
+            return;
+        }
+
+        self.first_update.set(false);
+    }
+
+    fn moved(&self) {
+        self.refresh(1.);
+        (self.handler.borrow_mut())();
+    }
+
+    fn refresh(&self, threshold: f64) {
+        if let Some(observer) = self.observer.take() {
+            observer.disconnect();
+        }
+
+        let bounds = self.canvas.get_bounding_client_rect();
+        let Some(root) = self.document.document_element() else {
+            return;
+        };
+        // An empty canvas never intersects, so only the other listeners can detect movement.
+        if bounds.width() == 0. || bounds.height() == 0. {
+            return;
+        }
+
+        let top = bounds.top().floor();
+        let right = (f64::from(root.client_width()) - bounds.right()).floor();
+        let bottom = (f64::from(root.client_height()) - bounds.bottom()).floor();
+        let left = bounds.left().floor();
+        let root_margin = format!("{}px {}px {}px {}px", -top, -right, -bottom, -left);
+
+        let options = Object::new();
+        Reflect::set(&options, &"rootMargin".into(), &root_margin.into())
+            .expect("unexpected exception in `Reflect.set()`");
+        Reflect::set(&options, &"threshold".into(), &threshold.into())
+            .expect("unexpected exception in `Reflect.set()`");
+
+        let observer = IntersectionObserver::new_with_options(
+            self.closure.as_ref().unchecked_ref(),
+            options.unchecked_ref::<IntersectionObserverInit>(),
+        )
+        .expect("Invalid `options`");
+        observer.observe(&self.canvas);
+
+        self.threshold.set(threshold);
+        self.first_update.set(true);
+        *self.observer.borrow_mut() = Some(observer);
+    }
+}
@@ -8,6 +8,7 @@ use web_sys::{
 };
 
 use crate::dpi::{LogicalSize, PhysicalSize};
+use crate::event_loop::DpiPolicy;
 
 use super::super::backend;
 use super::canvas::Style;
@@ -59,6 +60,7 @@ struct ResizeScaleInternal {
     scale_handler: Box<dyn FnMut(PhysicalSize<u32>, f64)>,
     resize_handler: Box<dyn FnMut(PhysicalSize<u32>)>,
     notify_scale: Cell<bool>,
+    scale_factor: f64,
 }
 
 impl ResizeScaleInternal {
@@ -101,6 +103,7 @@ impl ResizeScaleInternal {
             });
             let observer = Self::create_observer(&canvas, observer_closure.as_ref());
 
+            let scale_factor = super::scale_factor(&window);
             RefCell::new(Self {
                 window,
                 document,
@@ -112,6 +115,7 @@ impl ResizeScaleInternal {
                 scale_handler: Box::new(scale_handler),
                 resize_handler: Box::new(resize_handler),
                 notify_scale: Cell::new(false),
+                scale_factor,
             })
         })
     }
@@ -120,7 +124,8 @@ impl ResizeScaleInternal {
     where
         F: 'static + FnMut(&MediaQueryList),
     {
-        let current_scale = super::scale_factor(window);
+        // Track the `devicePixelRatio` itself, the DPI policy is applied when it changes.
+        let current_scale = window.device_pixel_ratio();
         // TODO: Remove `-webkit-device-pixel-ratio`. Requires Safari v16.
         let media_query = format!(
             "(resolution: {current_scale}dppx),
@@ -130,7 +135,7 @@ impl ResizeScaleInternal {
         debug_assert!(
             mql.mql().matches(),
             "created media query doesn't match, {current_scale} != {}",
-            super::scale_factor(window)
+            window.device_pixel_ratio()
         );
         mql
     }
@@ -140,7 +145,7 @@ impl ResizeScaleInternal {
             .expect("Failed to create `ResizeObserver`");
 
         // Safari doesn't support `devicePixelContentBoxSize`
-        if has_device_pixel_support() {
+        if use_device_pixels() {
             observer.observe_with_options(
                 canvas,
                 ResizeObserverOptions::new().box_(ResizeObserverBoxOptions::DevicePixelContentBox),
@@ -167,7 +172,7 @@ impl ResizeScaleInternal {
         }
 
         // Safari doesn't support `devicePixelContentBoxSize`
-        if has_device_pixel_support() {
+        if use_device_pixels() {
             self.observer.unobserve(&self.canvas);
             self.observer.observe(&self.canvas);
 
@@ -224,6 +229,11 @@ impl ResizeScaleInternal {
         });
         this.mql = new_mql;
 
+        // The DPI policy might not change the scale factor with the `devicePixelRatio`.
+        if std::mem::replace(&mut this.scale_factor, scale) == scale {
+            return;
+        }
+
         this.notify_scale.set(true);
         this.notify();
     }
@@ -232,7 +242,7 @@ impl ResizeScaleInternal {
         let entry: ResizeObserverEntry = entries.get(0).unchecked_into();
 
         // Safari doesn't support `devicePixelContentBoxSize`
-        if !has_device_pixel_support() {
+        if !use_device_pixels() {
             let rect = entry.content_rect();
 
             return LogicalSize::new(rect.width(), rect.height())
@@ -280,6 +290,12 @@ impl Drop for ResizeScaleInternal {
     }
 }
 
+/// Whether the physical size can be taken from `devicePixelContentBoxSize`, which is measured in
+/// actual device pixels and thus ignores the DPI policy.
+fn use_device_pixels() -> bool {
+    has_device_pixel_support() && matches!(DpiPolicy::current(), DpiPolicy::System)
+}
+
 // TODO: Remove when Safari supports `devicePixelContentBoxSize`.
 // See <https://bugs.webkit.org/show_bug.cgi?id=219005>.
 pub fn has_device_pixel_support() -> bool {
@@ -1,10 +1,12 @@
-use js_sys::{Function, Object, Promise, Reflect};
+use js_sys::{Array, Function, Object, Promise, Reflect};
 use std::cell::OnceCell;
 use std::time::Duration;
 use wasm_bindgen::closure::Closure;
 use wasm_bindgen::prelude::wasm_bindgen;
 use wasm_bindgen::{JsCast, JsValue};
-use web_sys::{AbortController, AbortSignal, MessageChannel, MessagePort};
+use web_sys::{
+    AbortController, AbortSignal, Blob, BlobPropertyBag, MessageChannel, MessagePort, Url, Worker,
+};
 
 use crate::platform::web::PollStrategy;
 
@@ -29,6 +31,9 @@ enum Inner {
         port: MessagePort,
         _timeout_closure: Closure<dyn FnMut()>,
     },
+    Worker {
+        port: MessagePort,
+    },
 }
 
 impl Schedule {
@@ -45,11 +50,22 @@ impl Schedule {
         }
     }
 
-    pub fn new_with_duration<F>(window: &web_sys::Window, f: F, duration: Duration) -> Schedule
+    pub fn new_with_duration<F>(
+        window: &web_sys::Window,
+        f: F,
+        duration: Duration,
+        resolution: Duration,
+    ) -> Schedule
     where
         F: 'static + FnMut(),
     {
-        if has_scheduler_support(window) {
+        // Timers on the main thread are clamped to 4ms when nested and heavily throttled in
+        // background tabs, timers in a worker aren't.
+        let worker = (resolution < Duration::from_millis(15)).then(timer_worker).flatten();
+
+        if let Some(worker) = worker {
+            Self::new_worker(&worker, f, duration)
+        } else if has_scheduler_support(window) {
             Self::new_scheduler(window, f, Some(duration))
         } else {
             Self::new_timeout(window.clone(), f, Some(duration))
@@ -155,6 +171,33 @@ impl Schedule {
     }
 }
 
+impl Schedule {
+    fn new_worker<F>(worker: &Worker, f: F, duration: Duration) -> Schedule
+    where
+        F: 'static + FnMut(),
+    {
+        let channel = MessageChannel::new().unwrap();
+        let closure = Closure::new(f);
+        let port_1 = channel.port1();
+        port_1.set_onmessage(Some(closure.as_ref().unchecked_ref()));
+        port_1.start();
+
+        // `Duration::as_millis()` always rounds down (because of truncation), we want to round
+        // up instead. This makes sure that the we never wake up **before** the given time.
+        let duration = duration
+            .as_secs()
+            .checked_mul(1000)
+            .and_then(|secs| secs.checked_add(duration_millis_ceil(duration).into()))
+            .unwrap_or(u64::MAX);
+
+        worker
+            .post_message_with_transfer(&(duration as f64).into(), &Array::of1(&channel.port2()))
+            .expect("Failed to send message");
+
+        Schedule { _closure: closure, inner: Inner::Worker { port: port_1 } }
+    }
+}
+
 impl Drop for Schedule {
     fn drop(&mut self) {
         match &self.inner {
@@ -165,7 +208,51 @@ impl Drop for Schedule {
                 port.close();
                 port.set_onmessage(None);
             },
+            Inner::Worker { port } => {
+                // Cancels the timeout in the worker.
+                let _ = port.post_message(&JsValue::UNDEFINED);
+                port.close();
+                port.set_onmessage(None);
+            },
+        }
+    }
+}
+
+/// A pending [`requestIdleCallback()`], cancelled when dropped.
+///
+/// [`requestIdleCallback()`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestIdleCallback
+#[derive(Debug)]
+pub struct IdleRequest {
+    _closure: Closure<dyn FnMut(IdleDeadline)>,
+    window: web_sys::Window,
+    handle: u32,
+}
+
+impl IdleRequest {
+    /// Calls `f` with the remaining time of the next idle period, if supported by the browser.
+    pub fn new<F>(window: &web_sys::Window, mut f: F) -> Option<IdleRequest>
+    where
+        F: 'static + FnMut(Duration),
+    {
+        if !has_idle_callback_support(window) {
+            return None;
         }
+
+        let closure = Closure::new(move |deadline: IdleDeadline| {
+            let remaining = deadline.time_remaining().max(0.);
+            f(Duration::from_secs_f64(remaining / 1000.))
+        });
+        let handle = window
+            .request_idle_callback(closure.as_ref().unchecked_ref())
+            .expect("Failed to request idle callback");
+
+        Some(IdleRequest { _closure: closure, window: window.clone(), handle })
+    }
+}
+
+impl Drop for IdleRequest {
+    fn drop(&mut self) {
+        self.window.cancel_idle_callback(self.handle)
     }
 }
 
@@ -205,6 +292,40 @@ fn has_scheduler_support(window: &web_sys::Window) -> bool {
     })
 }
 
+/// A worker that sends a message on the received port after the received delay, until a message is
+/// received on the port.
+fn timer_worker() -> Option<Worker> {
+    thread_local! {
+        static TIMER_WORKER: OnceCell<Option<Worker>> = const { OnceCell::new() };
+    }
+
+    const SCRIPT: &str = "onmessage = (event) => {
+        const port = event.ports[0];
+        const handle = setTimeout(() => port.postMessage(undefined), event.data);
+        port.onmessage = () => {
+            clearTimeout(handle);
+            port.close();
+        };
+    };";
+
+    TIMER_WORKER.with(|worker| {
+        worker
+            .get_or_init(|| {
+                let options: BlobPropertyBag = Object::new().unchecked_into();
+                let r = Reflect::set(&options, &JsValue::from("type"), &"text/javascript".into());
+                debug_assert!(r.is_ok(), "Failed to set `type` property");
+
+                let blob =
+                    Blob::new_with_str_sequence_and_options(&Array::of1(&SCRIPT.into()), &options)
+                        .ok()?;
+                let url = Url::create_object_url_with_blob(&blob).ok()?;
+                // Fails when prohibited by the Content Security Policy.
+                Worker::new(&url).ok()
+            })
+            .clone()
+    })
+}
+
 fn has_idle_callback_support(window: &web_sys::Window) -> bool {
     thread_local! {
         static IDLE_CALLBACK_SUPPORT: OnceCell<bool> = const { OnceCell::new() };
@@ -243,6 +364,11 @@ extern "C" {
     ) -> Promise;
 
     type SchedulerPostTaskOptions;
+
+    type IdleDeadline;
+
+    #[wasm_bindgen(method, js_name = timeRemaining)]
+    fn time_remaining(this: &IdleDeadline) -> f64;
 }
 
 impl SchedulerPostTaskOptions {
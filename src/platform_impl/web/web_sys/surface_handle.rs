@@ -0,0 +1,107 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::{Document, Event, HtmlCanvasElement, MutationObserver, MutationObserverInit};
+
+use super::event_handle::EventListenerHandle;
+
+/// Calls the handler whenever the canvas becomes usable for rendering or stops being so.
+///
+/// The canvas is usable while it is attached to the document and its WebGL context, if any,
+/// isn't lost.
+pub(super) struct SurfaceObserverHandle {
+    observer: MutationObserver,
+    _closure: Closure<dyn FnMut(Array, MutationObserver)>,
+    _on_context_lost: EventListenerHandle<dyn FnMut(Event)>,
+    _on_context_restored: EventListenerHandle<dyn FnMut(Event)>,
+}
+
+struct Inner {
+    canvas: HtmlCanvasElement,
+    context_lost: Cell<bool>,
+    available: Cell<bool>,
+    handler: RefCell<Box<dyn FnMut(bool)>>,
+}
+
+impl SurfaceObserverHandle {
+    pub fn new<F>(document: &Document, canvas: &HtmlCanvasElement, handler: F) -> Self
+    where
+        F: 'static + FnMut(bool),
+    {
+        let inner = Rc::new(Inner {
+            canvas: canvas.clone(),
+            context_lost: Cell::new(false),
+            available: Cell::new(false),
+            handler: RefCell::new(Box::new(handler)),
+        });
+
+        // There is no event for an element being attached or detached, so the whole document is
+        // observed instead.
+        let closure: Closure<dyn FnMut(Array, MutationObserver)> = Closure::new({
+            let inner = inner.clone();
+            move |_: Array, _: MutationObserver| inner.update()
+        });
+        let observer = MutationObserver::new(closure.as_ref().unchecked_ref())
+            .expect("Failed to create `MutationObserver`");
+        let options = Object::new();
+        Reflect::set(&options, &"childList".into(), &true.into())
+            .expect("unexpected exception in `Reflect.set()`");
+        Reflect::set(&options, &"subtree".into(), &true.into())
+            .expect("unexpected exception in `Reflect.set()`");
+        observer
+            .observe_with_options(document, options.unchecked_ref::<MutationObserverInit>())
+            .expect("Invalid `options`");
+
+        let on_context_lost = EventListenerHandle::new(
+            canvas.clone(),
+            "webglcontextlost",
+            Closure::new({
+                let inner = inner.clone();
+                move |event: Event| {
+                    // Otherwise the browser doesn't attempt to restore the context.
+                    event.prevent_default();
+                    inner.context_lost.set(true);
+                    inner.update();
+                }
+            }),
+        );
+        let on_context_restored = EventListenerHandle::new(
+            canvas.clone(),
+            "webglcontextrestored",
+            Closure::new({
+                let inner = inner.clone();
+                move |_: Event| {
+                    inner.context_lost.set(false);
+                    inner.update();
+                }
+            }),
+        );
+
+        inner.update();
+
+        Self {
+            observer,
+            _closure: closure,
+            _on_context_lost: on_context_lost,
+            _on_context_restored: on_context_restored,
+        }
+    }
+}
+
+impl Drop for SurfaceObserverHandle {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl Inner {
+    fn update(&self) {
+        let available = self.canvas.is_connected() && !self.context_lost.get();
+        if self.available.replace(available) != available {
+            (self.handler.borrow_mut())(available);
+        }
+    }
+}
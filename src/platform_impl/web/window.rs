@@ -1,22 +1,34 @@
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOE};
+use crate::event::{Event, Ime, ShareOutcome, Transition, WindowEvent};
 use crate::icon::Icon;
+use crate::ime::SurroundingText;
 use crate::window::{
-    Cursor, CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType,
-    WindowAttributes, WindowButtons, WindowId as RootWI, WindowLevel,
+    Autocapitalization, CapturePolicy, CoordinateTransform, Cursor, CursorGrabMode, EnterKeyLabel,
+    FocusReason, FrameRateRange, ImePurpose, KeyboardHints, KeyboardKind, PerformanceHint, Region,
+    ResizeDirection, ShareData, TextServicesConfig, Theme, TilingState, UserAttentionType,
+    WindowAttributes, WindowButtons, WindowCapabilities, WindowId as RootWI, WindowLevel,
+    WindowVisibility,
 };
 
+use super::event_loop::runner;
 use super::main_thread::{MainThreadMarker, MainThreadSafe};
 use super::monitor::MonitorHandle;
 use super::r#async::Dispatcher;
-use super::{backend, ActiveEventLoop, Fullscreen};
-use web_sys::HtmlCanvasElement;
+use super::{backend, cursor, ActiveEventLoop, Fullscreen, OsError};
+use js_sys::{Function, Object, Promise, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{CanvasRenderingContext2d, DomException, Element, HtmlCanvasElement, HtmlElement};
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::rc::Rc;
 use std::sync::Arc;
 
+/// The part of a window that the closures of `Window::maybe_queue_on_main` run with.
+pub(crate) type MainThreadWindow = Inner;
+
 pub struct Window {
     inner: Dispatcher<Inner>,
 }
@@ -25,7 +37,11 @@ pub struct Inner {
     id: WindowId,
     pub window: web_sys::Window,
     canvas: Rc<RefCell<backend::Canvas>>,
+    runner: runner::Shared,
     destroy_fn: Option<Box<dyn FnOnce()>>,
+    ime_purpose: Cell<ImePurpose>,
+    text_services: Cell<TextServicesConfig>,
+    keyboard_hints: Cell<KeyboardHints>,
 }
 
 impl Window {
@@ -51,7 +67,16 @@ impl Window {
         let runner = target.runner.clone();
         let destroy_fn = Box::new(move || runner.notify_destroy_window(RootWI(id)));
 
-        let inner = Inner { id, window: window.clone(), canvas, destroy_fn: Some(destroy_fn) };
+        let inner = Inner {
+            id,
+            window: window.clone(),
+            canvas,
+            runner: target.runner.clone(),
+            destroy_fn: Some(destroy_fn),
+            ime_purpose: Cell::new(ImePurpose::Normal),
+            text_services: Cell::new(TextServicesConfig::default()),
+            keyboard_hints: Cell::new(KeyboardHints::default()),
+        };
 
         inner.set_title(&attr.title);
         inner.set_maximized(attr.maximized);
@@ -78,6 +103,30 @@ impl Window {
         self.inner.value().map(|inner| inner.canvas.borrow().raw().clone())
     }
 
+    pub fn overlay_container(&self) -> Option<HtmlElement> {
+        self.inner.value().map(|inner| inner.canvas.borrow_mut().overlay_container().clone())
+    }
+
+    pub(crate) fn watch_gpu_device(&self, device: &JsValue) {
+        if let Some(inner) = self.inner.value() {
+            inner.canvas.borrow().watch_gpu_device(device);
+        }
+    }
+
+    pub(crate) fn print(&self) {
+        self.inner.dispatch(|inner| {
+            let window = inner.window.clone();
+            // `print()` dispatches `beforeprint` synchronously, which would be queued while the
+            // application is still handling the current event and delivered only after the page
+            // was captured.
+            wasm_bindgen_futures::spawn_local(async move {
+                if let Err(error) = window.print() {
+                    tracing::warn!("failed to open the print dialog: {error:?}");
+                }
+            });
+        })
+    }
+
     pub(crate) fn prevent_default(&self) -> bool {
         self.inner.queue(|inner| inner.canvas.borrow().prevent_default.get())
     }
@@ -86,6 +135,19 @@ impl Window {
         self.inner.dispatch(move |inner| inner.canvas.borrow().prevent_default.set(prevent_default))
     }
 
+    pub(crate) fn prevent_context_menu(&self) -> bool {
+        self.inner.queue(|inner| {
+            let canvas = inner.canvas.borrow();
+            canvas.prevent_context_menu.get().unwrap_or_else(|| canvas.prevent_default.get())
+        })
+    }
+
+    pub(crate) fn set_prevent_context_menu(&self, prevent_context_menu: bool) {
+        self.inner.dispatch(move |inner| {
+            inner.canvas.borrow().prevent_context_menu.set(Some(prevent_context_menu))
+        })
+    }
+
     #[cfg(feature = "rwh_06")]
     #[inline]
     pub fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
@@ -135,6 +197,40 @@ impl Inner {
 
     pub fn pre_present_notify(&self) {}
 
+    pub fn present_rgba(
+        &self,
+        buffer: &[u8],
+        size: PhysicalSize<u32>,
+    ) -> Result<(), ExternalError> {
+        if size.width == 0 || size.height == 0 {
+            return Ok(());
+        }
+
+        let map_err = |error: JsValue| ExternalError::Os(os_error!(OsError(format!("{error:?}"))));
+        let canvas = self.canvas.borrow();
+        // Fails if the canvas already has another kind of context.
+        let context: CanvasRenderingContext2d = canvas
+            .raw()
+            .get_context("2d")
+            .map_err(map_err)?
+            .ok_or_else(|| {
+                ExternalError::Os(os_error!(OsError(
+                    "the canvas already has another kind of context".to_owned()
+                )))
+            })?
+            .unchecked_into();
+
+        // The canvas blends the alpha channel, so the pixels are made opaque.
+        let pixels: Vec<u8> =
+            buffer.chunks_exact(4).flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 0xff]).collect();
+        let image_data = cursor::image_data(&pixels, size.width).map_err(map_err)?;
+        context.put_image_data(&image_data, 0., 0.).map_err(map_err)
+    }
+
+    pub fn notify_first_frame_presented(&self) {
+        self.canvas.borrow_mut().notify_first_frame_presented()
+    }
+
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         Ok(self.canvas.borrow().position().to_physical(self.scale_factor()))
     }
@@ -144,6 +240,10 @@ impl Inner {
         self.outer_position()
     }
 
+    pub fn coordinate_transform(&self) -> CoordinateTransform {
+        self.canvas.borrow().coordinate_transform()
+    }
+
     pub fn set_outer_position(&self, position: Position) {
         let canvas = self.canvas.borrow();
         let position = position.to_logical::<f64>(self.scale_factor());
@@ -221,11 +321,38 @@ impl Inner {
         self.canvas.borrow_mut().cursor.set_cursor(cursor)
     }
 
+    #[inline]
+    pub fn set_cursor_for_device(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _cursor: Cursor,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn set_pointer_capture(
+        &self,
+        device_id: crate::event::DeviceId,
+        capture: bool,
+    ) -> Result<(), ExternalError> {
+        self.canvas
+            .borrow()
+            .set_pointer_capture(device_id.0 .0, capture)
+            .map_err(|_| ExternalError::Ignored)
+    }
+
     #[inline]
     pub fn set_cursor_position(&self, _position: Position) -> Result<(), ExternalError> {
         Err(ExternalError::NotSupported(NotSupportedError::new()))
     }
 
+    #[inline]
+    pub fn cursor_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        // No mouse event was received yet.
+        self.canvas.borrow().cursor_position.get().ok_or(ExternalError::Ignored)
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         let lock = match mode {
@@ -258,8 +385,15 @@ impl Inner {
     pub fn show_window_menu(&self, _position: Position) {}
 
     #[inline]
-    pub fn set_cursor_hittest(&self, _hittest: bool) -> Result<(), ExternalError> {
-        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
+        self.canvas.borrow_mut().set_cursor_hittest(hittest);
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_hittest_regions(&self, regions: &[Region]) -> Result<(), ExternalError> {
+        self.canvas.borrow_mut().set_hittest_regions(regions.to_vec());
+        Ok(())
     }
 
     #[inline]
@@ -295,12 +429,48 @@ impl Inner {
 
     #[inline]
     pub(crate) fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
-        let canvas = &self.canvas.borrow();
+        let canvas = self.canvas.borrow();
+        let was_fullscreen = canvas.is_fullscreen();
 
-        if fullscreen.is_some() {
+        let transition = if fullscreen.is_some() {
             canvas.request_fullscreen();
+            Transition::EnterFullscreen
         } else {
-            canvas.exit_fullscreen()
+            canvas.exit_fullscreen();
+            Transition::ExitFullscreen
+        };
+
+        // Ended with `fullscreenchange` or `fullscreenerror`, see `ActiveEventLoop::register`.
+        if was_fullscreen != (transition == Transition::EnterFullscreen)
+            && canvas.fullscreen_transition.get().is_none()
+        {
+            canvas.fullscreen_transition.set(Some(transition));
+            drop(canvas);
+            self.runner.send_event(Event::WindowEvent {
+                window_id: RootWI(self.id),
+                event: WindowEvent::TransitionStarted(transition),
+            });
+        }
+    }
+
+    #[inline]
+    pub fn capabilities(&self) -> WindowCapabilities {
+        WindowCapabilities::SET_POSITION
+            | WindowCapabilities::CURSOR_GRAB
+            | WindowCapabilities::FOCUS
+            | WindowCapabilities::FOCUSABLE
+            | WindowCapabilities::POINTER_CAPTURE
+            | WindowCapabilities::SOFTWARE_PRESENT
+            | self.share_capability()
+    }
+
+    fn share_capability(&self) -> WindowCapabilities {
+        let navigator = Reflect::get(&self.window, &"navigator".into())
+            .expect("unexpected exception in `Reflect.get()`");
+        if Reflect::has(&navigator, &"share".into()).unwrap_or(false) {
+            WindowCapabilities::SHARE
+        } else {
+            WindowCapabilities::empty()
         }
     }
 
@@ -324,18 +494,80 @@ impl Inner {
     }
 
     #[inline]
-    pub fn set_ime_cursor_area(&self, _position: Position, _size: Size) {
-        // Currently a no-op as it does not seem there is good support for this on web
+    pub fn set_ime_cursor_area(&self, position: Position, size: Size) {
+        let scale_factor = self.scale_factor();
+        self.canvas
+            .borrow_mut()
+            .set_ime_cursor_area(position.to_logical(scale_factor), size.to_logical(scale_factor));
+    }
+
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        if self.canvas.borrow_mut().set_ime_allowed(allowed) {
+            self.runner.send_event(Event::WindowEvent {
+                window_id: RootWI(self.id),
+                event: WindowEvent::Ime(if allowed { Ime::Enabled } else { Ime::Disabled }),
+            });
+        }
+    }
+
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, _text: SurroundingText) {
+        // Browsers don't expose the context of an input method
     }
 
     #[inline]
-    pub fn set_ime_allowed(&self, _allowed: bool) {
-        // Currently not implemented
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        self.ime_purpose.set(purpose);
+        self.update_text_attributes();
     }
 
     #[inline]
-    pub fn set_ime_purpose(&self, _purpose: ImePurpose) {
-        // Currently not implemented
+    pub fn set_text_services(&self, config: TextServicesConfig) {
+        self.text_services.set(config);
+        self.update_text_attributes();
+    }
+
+    #[inline]
+    pub fn set_keyboard_hints(&self, hints: KeyboardHints) {
+        self.keyboard_hints.set(hints);
+        self.update_text_attributes();
+    }
+
+    /// Apply the `ImePurpose`, the `TextServicesConfig` and the `KeyboardHints` to the attributes
+    /// of the canvas.
+    fn update_text_attributes(&self) {
+        // Keep the browser and virtual keyboards from remembering and correcting passwords and
+        // terminal input.
+        let restricted =
+            matches!(self.ime_purpose.get(), ImePurpose::Password | ImePurpose::Terminal);
+        let config = self.text_services.get();
+        let hints = self.keyboard_hints.get();
+        let off = |value, enabled| (restricted || !enabled).then_some(value);
+        let autocapitalize = match hints.autocap {
+            _ if restricted => Some("off"),
+            Some(autocap) => Some(autocapitalize(autocap)),
+            None => off("off", config.autocapitalize),
+        };
+        let attributes = [
+            ("autocomplete", off("off", config.suggestions)),
+            ("autocorrect", off("off", config.autocorrect)),
+            ("autocapitalize", autocapitalize),
+            ("spellcheck", off("false", config.spellcheck)),
+            ("inputmode", input_mode(hints.kind)),
+            ("enterkeyhint", enter_key_hint(hints.enter_label)),
+        ];
+
+        let canvas = self.canvas.borrow();
+        let elements = [Some::<&Element>(canvas.raw()), canvas.ime_element().map(AsRef::as_ref)];
+        for element in elements.into_iter().flatten() {
+            for (attribute, value) in attributes {
+                let _ = match value {
+                    Some(value) => element.set_attribute(attribute, value),
+                    None => element.remove_attribute(attribute),
+                };
+            }
+        }
     }
 
     #[inline]
@@ -343,6 +575,34 @@ impl Inner {
         let _ = self.canvas.borrow().raw().focus();
     }
 
+    #[inline]
+    pub fn focus_window_with(&self, reason: FocusReason) {
+        let canvas = self.canvas.borrow();
+
+        // Don't take the focus from another element of the page, e.g. a text field.
+        if reason != FocusReason::UserInitiated {
+            let document = canvas.document();
+            let body = document.body().map(Element::from);
+            if !document.has_focus().unwrap_or(false)
+                || document.active_element().is_some_and(|element| Some(&element) != body.as_ref())
+            {
+                return;
+            }
+        }
+
+        let _ = canvas.raw().focus();
+    }
+
+    #[inline]
+    pub fn set_focusable(&self, focusable: bool) {
+        let canvas = self.canvas.borrow();
+        let _ = if focusable {
+            canvas.raw().set_attribute("tabindex", "0")
+        } else {
+            canvas.raw().remove_attribute("tabindex")
+        };
+    }
+
     #[inline]
     pub fn request_user_attention(&self, _request_type: Option<UserAttentionType>) {
         // Currently an intentional no-op
@@ -406,11 +666,81 @@ impl Inner {
 
     pub fn set_content_protected(&self, _protected: bool) {}
 
+    pub fn set_capture_policy(&self, _policy: CapturePolicy) {}
+
+    #[inline]
+    pub fn set_performance_hint(&self, _hint: PerformanceHint) {}
+
+    pub fn set_preferred_frame_rate_range(&self, range: Option<FrameRateRange>) {
+        self.canvas.borrow().set_max_frame_rate(range.map(|range| range.maximum));
+    }
+
+    pub fn share(&self, data: ShareData) {
+        let navigator = Reflect::get(&self.window, &"navigator".into())
+            .expect("unexpected exception in `Reflect.get()`");
+        let options = Object::new();
+        for (key, value) in [("title", data.title), ("text", data.text), ("url", data.url)] {
+            if let Some(value) = value {
+                Reflect::set(&options, &key.into(), &value.into())
+                    .expect("unexpected exception in `Reflect.set()`");
+            }
+        }
+
+        // Older versions of `web-sys` only expose `share()` behind `--cfg=web_sys_unstable_apis`.
+        let promise = Reflect::get(&navigator, &"share".into())
+            .ok()
+            .and_then(|share| share.dyn_into::<Function>().ok())
+            .and_then(|share| share.call1(&navigator, &options).ok())
+            .and_then(|promise| promise.dyn_into::<Promise>().ok());
+
+        let runner = self.runner.clone();
+        let window_id = RootWI(self.id);
+        wasm_bindgen_futures::spawn_local(async move {
+            let outcome = match promise {
+                Some(promise) => match JsFuture::from(promise).await {
+                    Ok(_) => ShareOutcome::Shared,
+                    // Rejected with an `AbortError` when the user closes the share sheet.
+                    Err(error)
+                        if error
+                            .dyn_ref::<DomException>()
+                            .is_some_and(|error| error.name() == "AbortError") =>
+                    {
+                        ShareOutcome::Cancelled
+                    },
+                    Err(error) => {
+                        tracing::warn!("failed to share: {error:?}");
+                        ShareOutcome::Failed
+                    },
+                },
+                None => ShareOutcome::Failed,
+            };
+            runner.send_event(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::ShareCompleted(outcome),
+            });
+        });
+    }
+
+    pub fn hide_to_tray(&self) {}
+
     #[inline]
     pub fn has_focus(&self) -> bool {
         self.canvas.borrow().has_focus.get()
     }
 
+    #[inline]
+    pub fn visibility(&self) -> WindowVisibility {
+        self.canvas.borrow().visibility()
+    }
+
+    pub fn tiling_state(&self) -> TilingState {
+        if self.canvas.borrow().is_fullscreen() {
+            TilingState::Fullscreen
+        } else {
+            TilingState::Floating
+        }
+    }
+
     pub fn title(&self) -> String {
         String::new()
     }
@@ -451,8 +781,9 @@ impl From<u64> for WindowId {
 #[derive(Clone, Debug)]
 pub struct PlatformSpecificWindowAttributes {
     pub(crate) canvas: Option<Arc<MainThreadSafe<backend::RawCanvasType>>>,
+    pub(crate) splash_element: Option<Arc<MainThreadSafe<HtmlElement>>>,
     pub(crate) prevent_default: bool,
-    pub(crate) focusable: bool,
+    pub(crate) prevent_context_menu: Option<bool>,
     pub(crate) append: bool,
 }
 
@@ -468,10 +799,62 @@ impl PlatformSpecificWindowAttributes {
 
         self.canvas = Some(Arc::new(MainThreadSafe::new(main_thread, canvas)));
     }
+
+    pub(crate) fn set_splash_element(&mut self, element: Option<HtmlElement>) {
+        let Some(element) = element else {
+            self.splash_element = None;
+            return;
+        };
+
+        let main_thread =
+            MainThreadMarker::new().expect("received a `HtmlElement` outside the window context");
+
+        self.splash_element = Some(Arc::new(MainThreadSafe::new(main_thread, element)));
+    }
+}
+
+fn autocapitalize(autocap: Autocapitalization) -> &'static str {
+    match autocap {
+        Autocapitalization::None => "off",
+        Autocapitalization::Sentences => "sentences",
+        Autocapitalization::Words => "words",
+        Autocapitalization::Characters => "characters",
+    }
+}
+
+fn input_mode(kind: KeyboardKind) -> Option<&'static str> {
+    match kind {
+        KeyboardKind::Text => None,
+        KeyboardKind::Numeric => Some("numeric"),
+        KeyboardKind::Decimal => Some("decimal"),
+        KeyboardKind::Phone => Some("tel"),
+        KeyboardKind::Email => Some("email"),
+        KeyboardKind::Url => Some("url"),
+        KeyboardKind::Search => Some("search"),
+    }
+}
+
+fn enter_key_hint(label: EnterKeyLabel) -> Option<&'static str> {
+    match label {
+        EnterKeyLabel::Default => None,
+        EnterKeyLabel::Enter => Some("enter"),
+        EnterKeyLabel::Done => Some("done"),
+        EnterKeyLabel::Go => Some("go"),
+        EnterKeyLabel::Next => Some("next"),
+        EnterKeyLabel::Previous => Some("previous"),
+        EnterKeyLabel::Search => Some("search"),
+        EnterKeyLabel::Send => Some("send"),
+    }
 }
 
 impl Default for PlatformSpecificWindowAttributes {
     fn default() -> Self {
-        Self { canvas: None, prevent_default: true, focusable: true, append: false }
+        Self {
+            canvas: None,
+            splash_element: None,
+            prevent_default: true,
+            prevent_context_menu: None,
+            append: false,
+        }
     }
 }
@@ -5,12 +5,18 @@ use std::{ffi::c_void, ptr};
 use crate::utils::Lazy;
 use windows_sys::core::PCSTR;
 use windows_sys::Win32::Foundation::{BOOL, HWND, NTSTATUS, S_OK};
+use windows_sys::Win32::Graphics::Dwm::{
+    DwmSetWindowAttribute, DWMWA_BORDER_COLOR, DWMWA_CAPTION_COLOR, DWMWA_TEXT_COLOR,
+    DWMWA_USE_IMMERSIVE_DARK_MODE, DWMWA_WINDOW_CORNER_PREFERENCE, DWMWINDOWATTRIBUTE,
+    DWM_WINDOW_CORNER_PREFERENCE,
+};
 use windows_sys::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryA};
 use windows_sys::Win32::System::SystemInformation::OSVERSIONINFOW;
 use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTA};
 use windows_sys::Win32::UI::Controls::SetWindowTheme;
 use windows_sys::Win32::UI::WindowsAndMessaging::{SystemParametersInfoA, SPI_GETHIGHCONTRAST};
 
+use crate::platform::windows::DwmConfig;
 use crate::window::Theme;
 
 use super::util;
@@ -80,6 +86,31 @@ pub fn try_theme(hwnd: HWND, preferred_theme: Option<Theme>) -> Theme {
     Theme::Light
 }
 
+/// Apply all attributes of `config`, with the title bar following `theme` unless overridden.
+pub fn apply_dwm_config(hwnd: HWND, config: &DwmConfig, theme: Theme) {
+    fn set_attribute<T>(hwnd: HWND, attribute: DWMWINDOWATTRIBUTE, value: T) {
+        unsafe {
+            DwmSetWindowAttribute(
+                hwnd,
+                attribute as u32,
+                &value as *const T as _,
+                std::mem::size_of::<T>() as _,
+            );
+        }
+    }
+
+    let is_dark_mode = config.titlebar_theme.unwrap_or(theme) == Theme::Dark;
+    set_attribute(hwnd, DWMWA_USE_IMMERSIVE_DARK_MODE, BOOL::from(is_dark_mode));
+    set_attribute(
+        hwnd,
+        DWMWA_WINDOW_CORNER_PREFERENCE,
+        config.corner_preference.unwrap_or_default() as DWM_WINDOW_CORNER_PREFERENCE,
+    );
+    set_attribute(hwnd, DWMWA_BORDER_COLOR, config.border_color.unwrap_or_default());
+    set_attribute(hwnd, DWMWA_CAPTION_COLOR, config.caption_color.unwrap_or_default());
+    set_attribute(hwnd, DWMWA_TEXT_COLOR, config.text_color.unwrap_or_default());
+}
+
 fn set_dark_mode_for_window(hwnd: HWND, is_dark_mode: bool) -> bool {
     // Uses Windows undocumented API SetWindowCompositionAttribute,
     // as seen in win32-darkmode example linked at top of file.
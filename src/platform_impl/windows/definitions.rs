@@ -3,7 +3,7 @@
 
 use std::ffi::c_void;
 
-use windows_sys::core::{IUnknown, GUID, HRESULT};
+use windows_sys::core::{IUnknown, GUID, HRESULT, HSTRING};
 use windows_sys::Win32::Foundation::{BOOL, HWND, POINTL};
 use windows_sys::Win32::System::Com::{
     IAdviseSink, IDataObject, IEnumFORMATETC, IEnumSTATDATA, FORMATETC, STGMEDIUM,
@@ -65,6 +65,23 @@ pub struct IDataObjectVtbl {
     ) -> HRESULT,
 }
 
+#[repr(C)]
+pub struct IEnumFORMATETCVtbl {
+    pub parent: IUnknownVtbl,
+    pub Next: unsafe extern "system" fn(
+        This: *mut IEnumFORMATETC,
+        celt: u32,
+        rgelt: *mut FORMATETC,
+        pceltFetched: *mut u32,
+    ) -> HRESULT,
+    pub Skip: unsafe extern "system" fn(This: *mut IEnumFORMATETC, celt: u32) -> HRESULT,
+    pub Reset: unsafe extern "system" fn(This: *mut IEnumFORMATETC) -> HRESULT,
+    pub Clone: unsafe extern "system" fn(
+        This: *mut IEnumFORMATETC,
+        ppenum: *mut *mut IEnumFORMATETC,
+    ) -> HRESULT,
+}
+
 #[repr(C)]
 pub struct IDropTargetVtbl {
     pub parent: IUnknownVtbl,
@@ -126,6 +143,227 @@ pub struct ITaskbarList2 {
     pub lpVtbl: *const ITaskbarList2Vtbl,
 }
 
+// WinRT interfaces of the Share UI, only declaring the methods up to the ones used.
+
+#[repr(C)]
+pub struct IInspectableVtbl {
+    pub parent: IUnknownVtbl,
+    pub GetIids: unsafe extern "system" fn(
+        This: *mut c_void,
+        iidCount: *mut u32,
+        iids: *mut *mut GUID,
+    ) -> HRESULT,
+    pub GetRuntimeClassName:
+        unsafe extern "system" fn(This: *mut c_void, className: *mut HSTRING) -> HRESULT,
+    pub GetTrustLevel:
+        unsafe extern "system" fn(This: *mut c_void, trustLevel: *mut i32) -> HRESULT,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct EventRegistrationToken {
+    pub value: i64,
+}
+
+#[repr(C)]
+pub struct ITypedEventHandlerVtbl {
+    pub parent: IUnknownVtbl,
+    pub Invoke: unsafe extern "system" fn(
+        This: *mut ITypedEventHandler,
+        sender: *mut c_void,
+        args: *mut c_void,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct ITypedEventHandler {
+    pub lpVtbl: *const ITypedEventHandlerVtbl,
+}
+
+#[repr(C)]
+pub struct IDataTransferManagerInteropVtbl {
+    pub parent: IUnknownVtbl,
+    pub GetForWindow: unsafe extern "system" fn(
+        This: *mut IDataTransferManagerInterop,
+        appWindow: HWND,
+        riid: *const GUID,
+        dataTransferManager: *mut *mut c_void,
+    ) -> HRESULT,
+    pub ShowShareUIForWindow: unsafe extern "system" fn(
+        This: *mut IDataTransferManagerInterop,
+        appWindow: HWND,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDataTransferManagerInterop {
+    pub lpVtbl: *const IDataTransferManagerInteropVtbl,
+}
+
+#[repr(C)]
+pub struct IDataTransferManagerVtbl {
+    pub parent: IInspectableVtbl,
+    pub add_DataRequested: unsafe extern "system" fn(
+        This: *mut IDataTransferManager,
+        handler: *mut ITypedEventHandler,
+        token: *mut EventRegistrationToken,
+    ) -> HRESULT,
+    pub remove_DataRequested: unsafe extern "system" fn(
+        This: *mut IDataTransferManager,
+        token: EventRegistrationToken,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDataTransferManager {
+    pub lpVtbl: *const IDataTransferManagerVtbl,
+}
+
+#[repr(C)]
+pub struct IDataRequestedEventArgsVtbl {
+    pub parent: IInspectableVtbl,
+    pub get_Request: unsafe extern "system" fn(
+        This: *mut IDataRequestedEventArgs,
+        value: *mut *mut IDataRequest,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDataRequestedEventArgs {
+    pub lpVtbl: *const IDataRequestedEventArgsVtbl,
+}
+
+#[repr(C)]
+pub struct IDataRequestVtbl {
+    pub parent: IInspectableVtbl,
+    pub get_Data: unsafe extern "system" fn(
+        This: *mut IDataRequest,
+        value: *mut *mut IDataPackage,
+    ) -> HRESULT,
+    pub put_Data:
+        unsafe extern "system" fn(This: *mut IDataRequest, value: *mut IDataPackage) -> HRESULT,
+    pub get_Deadline:
+        unsafe extern "system" fn(This: *mut IDataRequest, value: *mut i64) -> HRESULT,
+    pub FailWithDisplayText:
+        unsafe extern "system" fn(This: *mut IDataRequest, value: HSTRING) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDataRequest {
+    pub lpVtbl: *const IDataRequestVtbl,
+}
+
+#[repr(C)]
+pub struct IDataPackageVtbl {
+    pub parent: IInspectableVtbl,
+    pub GetView:
+        unsafe extern "system" fn(This: *mut IDataPackage, result: *mut *mut c_void) -> HRESULT,
+    pub get_Properties: unsafe extern "system" fn(
+        This: *mut IDataPackage,
+        value: *mut *mut IDataPackagePropertySet,
+    ) -> HRESULT,
+    pub get_RequestedOperation:
+        unsafe extern "system" fn(This: *mut IDataPackage, value: *mut u32) -> HRESULT,
+    pub put_RequestedOperation:
+        unsafe extern "system" fn(This: *mut IDataPackage, value: u32) -> HRESULT,
+    pub add_OperationCompleted: unsafe extern "system" fn(
+        This: *mut IDataPackage,
+        handler: *mut ITypedEventHandler,
+        token: *mut EventRegistrationToken,
+    ) -> HRESULT,
+    pub remove_OperationCompleted: unsafe extern "system" fn(
+        This: *mut IDataPackage,
+        token: EventRegistrationToken,
+    ) -> HRESULT,
+    pub add_Destroyed: unsafe extern "system" fn(
+        This: *mut IDataPackage,
+        handler: *mut ITypedEventHandler,
+        token: *mut EventRegistrationToken,
+    ) -> HRESULT,
+    pub remove_Destroyed: unsafe extern "system" fn(
+        This: *mut IDataPackage,
+        token: EventRegistrationToken,
+    ) -> HRESULT,
+    pub SetData: unsafe extern "system" fn(
+        This: *mut IDataPackage,
+        formatId: HSTRING,
+        value: *mut c_void,
+    ) -> HRESULT,
+    pub SetDataProvider: unsafe extern "system" fn(
+        This: *mut IDataPackage,
+        formatId: HSTRING,
+        delayRenderer: *mut c_void,
+    ) -> HRESULT,
+    pub SetText: unsafe extern "system" fn(This: *mut IDataPackage, value: HSTRING) -> HRESULT,
+    pub SetUri: unsafe extern "system" fn(This: *mut IDataPackage, value: *mut c_void) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDataPackage {
+    pub lpVtbl: *const IDataPackageVtbl,
+}
+
+#[repr(C)]
+pub struct IDataPackage3Vtbl {
+    pub parent: IInspectableVtbl,
+    pub add_ShareCompleted: unsafe extern "system" fn(
+        This: *mut IDataPackage3,
+        handler: *mut ITypedEventHandler,
+        token: *mut EventRegistrationToken,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDataPackage3 {
+    pub lpVtbl: *const IDataPackage3Vtbl,
+}
+
+#[repr(C)]
+pub struct IDataPackage4Vtbl {
+    pub parent: IInspectableVtbl,
+    pub add_ShareCanceled: unsafe extern "system" fn(
+        This: *mut IDataPackage4,
+        handler: *mut ITypedEventHandler,
+        token: *mut EventRegistrationToken,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDataPackage4 {
+    pub lpVtbl: *const IDataPackage4Vtbl,
+}
+
+#[repr(C)]
+pub struct IDataPackagePropertySetVtbl {
+    pub parent: IInspectableVtbl,
+    pub get_Title: unsafe extern "system" fn(
+        This: *mut IDataPackagePropertySet,
+        value: *mut HSTRING,
+    ) -> HRESULT,
+    pub put_Title:
+        unsafe extern "system" fn(This: *mut IDataPackagePropertySet, value: HSTRING) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IDataPackagePropertySet {
+    pub lpVtbl: *const IDataPackagePropertySetVtbl,
+}
+
+#[repr(C)]
+pub struct IUriRuntimeClassFactoryVtbl {
+    pub parent: IInspectableVtbl,
+    pub CreateUri: unsafe extern "system" fn(
+        This: *mut IUriRuntimeClassFactory,
+        uri: HSTRING,
+        instance: *mut *mut c_void,
+    ) -> HRESULT,
+}
+
+#[repr(C)]
+pub struct IUriRuntimeClassFactory {
+    pub lpVtbl: *const IUriRuntimeClassFactoryVtbl,
+}
+
 pub const CLSID_TaskbarList: GUID = GUID {
     data1: 0x56fdf344,
     data2: 0xfd6d,
@@ -146,3 +384,75 @@ pub const IID_ITaskbarList2: GUID = GUID {
     data3: 0x429b,
     data4: [0xa6, 0x6e, 0x19, 0x35, 0xe4, 0x4f, 0x43, 0x17],
 };
+
+pub const IID_IUnknown: GUID = GUID {
+    data1: 0x00000000,
+    data2: 0x0000,
+    data3: 0x0000,
+    data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+};
+
+pub const IID_IAgileObject: GUID = GUID {
+    data1: 0x94ea2b94,
+    data2: 0xe9cc,
+    data3: 0x49e0,
+    data4: [0xc0, 0xff, 0xee, 0x64, 0xca, 0x8f, 0x5b, 0x90],
+};
+
+pub const IID_IDataTransferManagerInterop: GUID = GUID {
+    data1: 0x3a3dcd6c,
+    data2: 0x3eab,
+    data3: 0x43dc,
+    data4: [0xbc, 0xde, 0x45, 0x67, 0x1c, 0xe8, 0x00, 0xc8],
+};
+
+pub const IID_IDataTransferManager: GUID = GUID {
+    data1: 0xa5caee9b,
+    data2: 0x8708,
+    data3: 0x49d1,
+    data4: [0x8d, 0x36, 0x67, 0xd2, 0x5a, 0x8d, 0xa0, 0x0c],
+};
+
+pub const IID_IDataPackage3: GUID = GUID {
+    data1: 0x88f31f5d,
+    data2: 0x787b,
+    data3: 0x4d32,
+    data4: [0x96, 0x5a, 0xa9, 0x83, 0x81, 0x05, 0xa0, 0x56],
+};
+
+pub const IID_IDataPackage4: GUID = GUID {
+    data1: 0x13a24ec8,
+    data2: 0x9382,
+    data3: 0x536f,
+    data4: [0x85, 0x2a, 0x30, 0x45, 0xe1, 0xb2, 0x9a, 0x3b],
+};
+
+pub const IID_IUriRuntimeClassFactory: GUID = GUID {
+    data1: 0x44a9796f,
+    data2: 0x723e,
+    data3: 0x4fdf,
+    data4: [0xa2, 0x18, 0x03, 0x3e, 0x75, 0xb0, 0xc0, 0x84],
+};
+
+// The `TypedEventHandler` instances of the `DataRequested`, `ShareCompleted` and `ShareCanceled`
+// events.
+pub const IID_DataRequestedHandler: GUID = GUID {
+    data1: 0xec6f9cc8,
+    data2: 0x46d0,
+    data3: 0x5e0e,
+    data4: [0xb4, 0xd2, 0x7d, 0x77, 0x73, 0xae, 0x37, 0xa0],
+};
+
+pub const IID_ShareCompletedHandler: GUID = GUID {
+    data1: 0xf8f7e24a,
+    data2: 0x56fe,
+    data3: 0x58df,
+    data4: [0xbc, 0x15, 0x23, 0x65, 0xae, 0xc0, 0x39, 0x66],
+};
+
+pub const IID_ShareCanceledHandler: GUID = GUID {
+    data1: 0xc156b0c3,
+    data2: 0x1cbc,
+    data3: 0x5ca4,
+    data4: [0x90, 0x1c, 0x62, 0xc5, 0xa8, 0xca, 0x5c, 0xb5],
+};
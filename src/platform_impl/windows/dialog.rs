@@ -0,0 +1,100 @@
+use std::{mem, ptr, thread};
+
+use windows_sys::Win32::Foundation::S_OK;
+use windows_sys::Win32::UI::Controls::{
+    TASKDIALOGCONFIG, TDCBF_CANCEL_BUTTON, TDCBF_NO_BUTTON, TDCBF_OK_BUTTON, TDCBF_YES_BUTTON,
+    TDF_ALLOW_DIALOG_CANCELLATION, TDF_SIZE_TO_CONTENT, TD_ERROR_ICON, TD_INFORMATION_ICON,
+    TD_WARNING_ICON,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    MessageBoxW, IDCANCEL, IDNO, IDOK, IDYES, MB_ICONERROR, MB_ICONINFORMATION, MB_ICONWARNING,
+    MB_OK, MB_OKCANCEL, MB_SETFOREGROUND, MB_YESNO, MB_YESNOCANCEL, MESSAGEBOX_RESULT,
+};
+
+use super::util::{self, encode_wide};
+use crate::dialog::{DialogButton, DialogSender, MessageButtons, MessageDialog, MessageLevel};
+use crate::error::ExternalError;
+
+/// Show the dialog on its own thread, so it doesn't block the event loop.
+pub(crate) fn show_message_dialog(
+    dialog: MessageDialog,
+    sender: DialogSender<DialogButton>,
+) -> Result<(), ExternalError> {
+    thread::Builder::new()
+        .name("winit message dialog".to_owned())
+        .spawn(move || {
+            let result = match *util::TASK_DIALOG_INDIRECT {
+                Some(task_dialog_indirect) => task_dialog(task_dialog_indirect, &dialog),
+                None => message_box(&dialog),
+            };
+
+            let button = match result {
+                IDOK => DialogButton::Ok,
+                IDYES => DialogButton::Yes,
+                IDNO => DialogButton::No,
+                // The dialog reports `IDCANCEL` when it is closed, even without a cancel button.
+                IDCANCEL if dialog.buttons.buttons().contains(&DialogButton::Cancel) => {
+                    DialogButton::Cancel
+                },
+                _ => dialog.buttons.dismiss_button(),
+            };
+            sender.send(button);
+        })
+        .map_err(|error| ExternalError::Os(os_error!(error)))?;
+
+    Ok(())
+}
+
+fn task_dialog(
+    task_dialog_indirect: util::TaskDialogIndirect,
+    dialog: &MessageDialog,
+) -> MESSAGEBOX_RESULT {
+    let title = encode_wide(&dialog.title);
+    let message = encode_wide(&dialog.message);
+
+    let mut config: TASKDIALOGCONFIG = unsafe { mem::zeroed() };
+    config.cbSize = mem::size_of::<TASKDIALOGCONFIG>() as u32;
+    config.dwFlags = TDF_ALLOW_DIALOG_CANCELLATION | TDF_SIZE_TO_CONTENT;
+    config.dwCommonButtons = match dialog.buttons {
+        MessageButtons::Ok => TDCBF_OK_BUTTON,
+        MessageButtons::OkCancel => TDCBF_OK_BUTTON | TDCBF_CANCEL_BUTTON,
+        MessageButtons::YesNo => TDCBF_YES_BUTTON | TDCBF_NO_BUTTON,
+        MessageButtons::YesNoCancel => TDCBF_YES_BUTTON | TDCBF_NO_BUTTON | TDCBF_CANCEL_BUTTON,
+    };
+    config.pszWindowTitle = title.as_ptr();
+    config.Anonymous1.pszMainIcon = match dialog.level {
+        MessageLevel::Info => TD_INFORMATION_ICON,
+        MessageLevel::Warning => TD_WARNING_ICON,
+        MessageLevel::Error => TD_ERROR_ICON,
+    };
+    config.pszContent = message.as_ptr();
+
+    let mut button = 0;
+    let result =
+        unsafe { task_dialog_indirect(&config, &mut button, ptr::null_mut(), ptr::null_mut()) };
+    if result != S_OK {
+        tracing::warn!("failed to show task dialog: {result:#x}");
+        return message_box(dialog);
+    }
+
+    button
+}
+
+fn message_box(dialog: &MessageDialog) -> MESSAGEBOX_RESULT {
+    let title = encode_wide(&dialog.title);
+    let message = encode_wide(&dialog.message);
+
+    let buttons = match dialog.buttons {
+        MessageButtons::Ok => MB_OK,
+        MessageButtons::OkCancel => MB_OKCANCEL,
+        MessageButtons::YesNo => MB_YESNO,
+        MessageButtons::YesNoCancel => MB_YESNOCANCEL,
+    };
+    let icon = match dialog.level {
+        MessageLevel::Info => MB_ICONINFORMATION,
+        MessageLevel::Warning => MB_ICONWARNING,
+        MessageLevel::Error => MB_ICONERROR,
+    };
+
+    unsafe { MessageBoxW(0, message.as_ptr(), title.as_ptr(), buttons | icon | MB_SETFOREGROUND) }
+}
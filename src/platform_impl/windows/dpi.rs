@@ -8,13 +8,17 @@ use windows_sys::Win32::Graphics::Gdi::{
 };
 use windows_sys::Win32::UI::HiDpi::{
     DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE, DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2,
-    MDT_EFFECTIVE_DPI, PROCESS_PER_MONITOR_DPI_AWARE,
+    DPI_AWARENESS_CONTEXT_SYSTEM_AWARE, MDT_EFFECTIVE_DPI, PROCESS_DPI_UNAWARE,
+    PROCESS_PER_MONITOR_DPI_AWARE, PROCESS_SYSTEM_DPI_AWARE,
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::IsProcessDPIAware;
 
+use crate::event_loop::DpiPolicy;
+use crate::platform::windows::DpiAwareness;
 use crate::platform_impl::platform::util::{
-    ENABLE_NON_CLIENT_DPI_SCALING, GET_DPI_FOR_MONITOR, GET_DPI_FOR_WINDOW, SET_PROCESS_DPI_AWARE,
-    SET_PROCESS_DPI_AWARENESS, SET_PROCESS_DPI_AWARENESS_CONTEXT,
+    ARE_DPI_AWARENESS_CONTEXTS_EQUAL, ENABLE_NON_CLIENT_DPI_SCALING, GET_DPI_FOR_MONITOR,
+    GET_DPI_FOR_WINDOW, GET_PROCESS_DPI_AWARENESS, GET_WINDOW_DPI_AWARENESS_CONTEXT,
+    SET_PROCESS_DPI_AWARE, SET_PROCESS_DPI_AWARENESS, SET_PROCESS_DPI_AWARENESS_CONTEXT,
 };
 
 pub fn become_dpi_aware() {
@@ -41,6 +45,44 @@ pub fn become_dpi_aware() {
     });
 }
 
+pub fn window_dpi_awareness(hwnd: HWND) -> DpiAwareness {
+    unsafe {
+        if let (Some(GetWindowDpiAwarenessContext), Some(AreDpiAwarenessContextsEqual)) =
+            (*GET_WINDOW_DPI_AWARENESS_CONTEXT, *ARE_DPI_AWARENESS_CONTEXTS_EQUAL)
+        {
+            // We are on Windows 10 Anniversary Update (1607) or later.
+            let context = GetWindowDpiAwarenessContext(hwnd);
+            let known_contexts = [
+                (DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2, DpiAwareness::PerMonitorV2),
+                (DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE, DpiAwareness::PerMonitor),
+                (DPI_AWARENESS_CONTEXT_SYSTEM_AWARE, DpiAwareness::System),
+            ];
+            for (known_context, awareness) in known_contexts {
+                if AreDpiAwarenessContextsEqual(context, known_context) != false.into() {
+                    return awareness;
+                }
+            }
+            DpiAwareness::Unaware
+        } else if let Some(GetProcessDpiAwareness) = *GET_PROCESS_DPI_AWARENESS {
+            // We are on Windows 8.1 or later.
+            let mut awareness = PROCESS_DPI_UNAWARE;
+            if GetProcessDpiAwareness(0, &mut awareness) != S_OK {
+                return DpiAwareness::Unaware;
+            }
+            match awareness {
+                PROCESS_PER_MONITOR_DPI_AWARE => DpiAwareness::PerMonitor,
+                PROCESS_SYSTEM_DPI_AWARE => DpiAwareness::System,
+                _ => DpiAwareness::Unaware,
+            }
+        } else if IsProcessDPIAware() != false.into() {
+            // We are on Vista or later.
+            DpiAwareness::System
+        } else {
+            DpiAwareness::Unaware
+        }
+    }
+}
+
 pub fn enable_non_client_dpi_scaling(hwnd: HWND) {
     unsafe {
         if let Some(EnableNonClientDpiScaling) = *ENABLE_NON_CLIENT_DPI_SCALING {
@@ -68,7 +110,7 @@ pub fn get_monitor_dpi(hmonitor: HMONITOR) -> Option<u32> {
 
 pub const BASE_DPI: u32 = 96;
 pub fn dpi_to_scale_factor(dpi: u32) -> f64 {
-    dpi as f64 / BASE_DPI as f64
+    DpiPolicy::current().apply(dpi as f64 / BASE_DPI as f64)
 }
 
 pub unsafe fn hwnd_dpi(hwnd: HWND) -> u32 {
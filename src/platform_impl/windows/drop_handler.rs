@@ -1,25 +1,38 @@
 use std::ffi::{c_void, OsString};
 use std::os::windows::ffi::OsStringExt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::ptr;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 
 use windows_sys::core::{IUnknown, GUID, HRESULT};
-use windows_sys::Win32::Foundation::{DV_E_FORMATETC, HWND, POINTL, S_OK};
-use windows_sys::Win32::System::Com::{IDataObject, DVASPECT_CONTENT, FORMATETC, TYMED_HGLOBAL};
-use windows_sys::Win32::System::Ole::{CF_HDROP, DROPEFFECT_COPY, DROPEFFECT_NONE};
+use windows_sys::Win32::Foundation::{DV_E_FORMATETC, HWND, POINT, POINTL, S_OK};
+use windows_sys::Win32::Graphics::Gdi::ScreenToClient;
+use windows_sys::Win32::System::Com::{
+    IDataObject, IEnumFORMATETC, DATADIR_GET, DVASPECT_CONTENT, FORMATETC, TYMED_HGLOBAL,
+};
+use windows_sys::Win32::System::DataExchange::GetClipboardFormatNameW;
+use windows_sys::Win32::System::Memory::{GlobalLock, GlobalSize, GlobalUnlock};
+use windows_sys::Win32::System::Ole::{
+    ReleaseStgMedium, CF_HDROP, CF_UNICODETEXT, DROPEFFECT_COPY, DROPEFFECT_LINK, DROPEFFECT_MOVE,
+    DROPEFFECT_NONE,
+};
 use windows_sys::Win32::UI::Shell::{DragFinish, DragQueryFileW, HDROP};
 
 use tracing::debug;
 
 use crate::platform_impl::platform::definitions::{
-    IDataObjectVtbl, IDropTarget, IDropTargetVtbl, IUnknownVtbl,
+    IDataObjectVtbl, IDropTarget, IDropTargetVtbl, IEnumFORMATETCVtbl, IUnknownVtbl,
 };
 use crate::platform_impl::platform::WindowId;
 
-use crate::event::Event;
+use crate::dpi::PhysicalPosition;
+use crate::event::{DataTransfer, DropOperation, DropResponse, Event};
 use crate::window::WindowId as RootWindowId;
 
+/// The registered format of URLs dragged from browsers and the shell.
+const URL_FORMAT: &str = "UniformResourceLocatorW";
+
 #[repr(C)]
 pub struct FileDropHandlerData {
     pub interface: IDropTarget,
@@ -29,6 +42,7 @@ pub struct FileDropHandlerData {
     cursor_effect: u32,
     hovered_is_valid: bool, /* If the currently hovered item is not valid there must not be any
                              * `HoveredFileCancelled` emitted */
+    accepted: Vec<(String, u16)>, // The negotiated MIME types and their clipboard formats
 }
 
 pub struct FileDropHandler {
@@ -45,6 +59,7 @@ impl FileDropHandler {
             send_event,
             cursor_effect: DROPEFFECT_NONE,
             hovered_is_valid: false,
+            accepted: Vec::new(),
         });
         FileDropHandler { data: Box::into_raw(data) }
     }
@@ -80,7 +95,7 @@ impl FileDropHandler {
         this: *mut IDropTarget,
         pDataObj: *const IDataObject,
         _grfKeyState: u32,
-        _pt: *const POINTL,
+        pt: *const POINTL,
         pdwEffect: *mut u32,
     ) -> HRESULT {
         use crate::event::WindowEvent::HoveredFile;
@@ -94,10 +109,16 @@ impl FileDropHandler {
             })
         };
         drop_handler.hovered_is_valid = hdrop.is_some();
-        drop_handler.cursor_effect =
-            if drop_handler.hovered_is_valid { DROPEFFECT_COPY } else { DROPEFFECT_NONE };
+        let offered = unsafe { Self::offered_types(pDataObj) };
+        let names: Vec<String> = offered.iter().map(|(name, _)| name.clone()).collect();
+        let window_id = RootWindowId(WindowId(drop_handler.window));
+        drop_handler.accepted = crate::drop_negotiation::negotiate(window_id, &names)
+            .into_iter()
+            .filter_map(|name| offered.iter().find(|(offered, _)| *offered == name).cloned())
+            .collect();
         unsafe {
-            *pdwEffect = drop_handler.cursor_effect;
+            drop_handler.cursor_effect = drop_handler.drag_hover(*pt);
+            *pdwEffect &= drop_handler.cursor_effect;
         }
 
         S_OK
@@ -106,12 +127,13 @@ impl FileDropHandler {
     pub unsafe extern "system" fn DragOver(
         this: *mut IDropTarget,
         _grfKeyState: u32,
-        _pt: *const POINTL,
+        pt: *const POINTL,
         pdwEffect: *mut u32,
     ) -> HRESULT {
         let drop_handler = unsafe { Self::from_interface(this) };
         unsafe {
-            *pdwEffect = drop_handler.cursor_effect;
+            drop_handler.cursor_effect = drop_handler.drag_hover(*pt);
+            *pdwEffect &= drop_handler.cursor_effect;
         }
 
         S_OK
@@ -135,10 +157,13 @@ impl FileDropHandler {
         pDataObj: *const IDataObject,
         _grfKeyState: u32,
         _pt: *const POINTL,
-        _pdwEffect: *mut u32,
+        pdwEffect: *mut u32,
     ) -> HRESULT {
-        use crate::event::WindowEvent::DroppedFile;
+        use crate::event::WindowEvent::{DroppedData, DroppedFile};
         let drop_handler = unsafe { Self::from_interface(this) };
+        unsafe {
+            *pdwEffect &= drop_handler.cursor_effect;
+        }
         let hdrop = unsafe {
             Self::iterate_filenames(pDataObj, |filename| {
                 drop_handler.send_event(Event::WindowEvent {
@@ -151,6 +176,19 @@ impl FileDropHandler {
             unsafe { DragFinish(hdrop) };
         }
 
+        let mut data = DataTransfer::new();
+        for (name, format) in std::mem::take(&mut drop_handler.accepted) {
+            if let Some(bytes) = unsafe { Self::get_data(pDataObj, &name, format) } {
+                data.insert(name, bytes);
+            }
+        }
+        if !data.is_empty() {
+            drop_handler.send_event(Event::WindowEvent {
+                window_id: RootWindowId(WindowId(drop_handler.window)),
+                event: DroppedData(data),
+            });
+        }
+
         S_OK
     }
 
@@ -158,6 +196,115 @@ impl FileDropHandler {
         unsafe { &mut *(this as *mut _) }
     }
 
+    /// The MIME types offered by the data object, with the clipboard formats they are read from.
+    ///
+    /// Text is offered as [`DataTransfer::TEXT`], files and URLs as [`DataTransfer::URI_LIST`],
+    /// and other registered formats by their name.
+    unsafe fn offered_types(data_obj: *const IDataObject) -> Vec<(String, u16)> {
+        let mut offered: Vec<(String, u16)> = Vec::new();
+        let enum_format_etc_fn = unsafe { (*(*data_obj).cast::<IDataObjectVtbl>()).EnumFormatEtc };
+        let mut formats: *mut IEnumFORMATETC = ptr::null_mut();
+        if unsafe { enum_format_etc_fn(data_obj as *mut _, DATADIR_GET as u32, &mut formats) } < 0
+            || formats.is_null()
+        {
+            return offered;
+        }
+
+        let vtbl = unsafe { &*(*formats).cast::<IEnumFORMATETCVtbl>() };
+        let mut format: FORMATETC = unsafe { std::mem::zeroed() };
+        while unsafe { (vtbl.Next)(formats, 1, &mut format, ptr::null_mut()) } == S_OK {
+            if format.tymed & TYMED_HGLOBAL as u32 == 0 {
+                continue;
+            }
+            let name = match format.cfFormat {
+                CF_UNICODETEXT => DataTransfer::TEXT.to_owned(),
+                CF_HDROP => DataTransfer::URI_LIST.to_owned(),
+                // Registered formats.
+                0xc000..=0xffff => {
+                    let mut name = [0u16; 256];
+                    let len = unsafe {
+                        GetClipboardFormatNameW(
+                            format.cfFormat as u32,
+                            name.as_mut_ptr(),
+                            name.len() as i32,
+                        )
+                    };
+                    match String::from_utf16_lossy(&name[..len.max(0) as usize]) {
+                        name if name == URL_FORMAT => DataTransfer::URI_LIST.to_owned(),
+                        name if name.is_empty() => continue,
+                        name => name,
+                    }
+                },
+                _ => continue,
+            };
+            if !offered.iter().any(|(offered, _)| *offered == name) {
+                offered.push((name, format.cfFormat));
+            }
+        }
+        unsafe { (vtbl.parent.Release)(formats as *mut IUnknown) };
+
+        offered
+    }
+
+    /// Read the data of a type from its format, converting text to UTF-8 and files to URIs.
+    unsafe fn get_data(
+        data_obj: *const IDataObject,
+        mime_type: &str,
+        format: u16,
+    ) -> Option<Vec<u8>> {
+        let data_format = FORMATETC {
+            cfFormat: format,
+            ptd: ptr::null_mut(),
+            dwAspect: DVASPECT_CONTENT,
+            lindex: -1,
+            tymed: TYMED_HGLOBAL as u32,
+        };
+
+        let mut medium = unsafe { std::mem::zeroed() };
+        let get_data_fn = unsafe { (*(*data_obj).cast::<IDataObjectVtbl>()).GetData };
+        if unsafe { get_data_fn(data_obj as *mut _, &data_format, &mut medium) } < 0 {
+            return None;
+        }
+
+        let bytes = if format == CF_HDROP {
+            let mut uri_list = String::new();
+            unsafe {
+                Self::read_filenames(medium.u.hGlobal as HDROP, |path| {
+                    uri_list.push_str(&file_uri(&path));
+                    uri_list.push_str("\r\n");
+                })
+            };
+            Some(uri_list.into_bytes())
+        } else {
+            let global = unsafe { medium.u.hGlobal };
+            let data = unsafe { GlobalLock(global) };
+            if data.is_null() {
+                None
+            } else {
+                let len = unsafe { GlobalSize(global) };
+                let bytes = unsafe { std::slice::from_raw_parts(data as *const u8, len) }.to_vec();
+                unsafe { GlobalUnlock(global) };
+                Some(bytes)
+            }
+        };
+        unsafe { ReleaseStgMedium(&mut medium) };
+
+        let bytes = bytes?;
+        // Text and URLs are UTF-16.
+        let is_utf16 =
+            format == CF_UNICODETEXT || mime_type == DataTransfer::URI_LIST && format != CF_HDROP;
+        if is_utf16 {
+            let wide: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|pair| u16::from_ne_bytes([pair[0], pair[1]]))
+                .take_while(|&unit| unit != 0)
+                .collect();
+            Some(String::from_utf16_lossy(&wide).into_bytes())
+        } else {
+            Some(bytes)
+        }
+    }
+
     unsafe fn iterate_filenames<F>(data_obj: *const IDataObject, callback: F) -> Option<HDROP>
     where
         F: Fn(PathBuf),
@@ -175,28 +322,7 @@ impl FileDropHandler {
         let get_data_result = unsafe { get_data_fn(data_obj as *mut _, &drop_format, &mut medium) };
         if get_data_result >= 0 {
             let hdrop = unsafe { medium.u.hGlobal as HDROP };
-
-            // The second parameter (0xFFFFFFFF) instructs the function to return the item count
-            let item_count = unsafe { DragQueryFileW(hdrop, 0xffffffff, ptr::null_mut(), 0) };
-
-            for i in 0..item_count {
-                // Get the length of the path string NOT including the terminating null character.
-                // Previously, this was using a fixed size array of MAX_PATH length, but the
-                // Windows API allows longer paths under certain circumstances.
-                let character_count =
-                    unsafe { DragQueryFileW(hdrop, i, ptr::null_mut(), 0) as usize };
-                let str_len = character_count + 1;
-
-                // Fill path_buf with the null-terminated file name
-                let mut path_buf = Vec::with_capacity(str_len);
-                unsafe {
-                    DragQueryFileW(hdrop, i, path_buf.as_mut_ptr(), str_len as u32);
-                    path_buf.set_len(str_len);
-                }
-
-                callback(OsString::from_wide(&path_buf[0..character_count]).into());
-            }
-
+            unsafe { Self::read_filenames(hdrop, callback) };
             Some(hdrop)
         } else if get_data_result == DV_E_FORMATETC {
             // If the dropped item is not a file this error will occur.
@@ -208,12 +334,73 @@ impl FileDropHandler {
             None
         }
     }
+
+    unsafe fn read_filenames(hdrop: HDROP, mut callback: impl FnMut(PathBuf)) {
+        // The second parameter (0xFFFFFFFF) instructs the function to return the item count
+        let item_count = unsafe { DragQueryFileW(hdrop, 0xffffffff, ptr::null_mut(), 0) };
+
+        for i in 0..item_count {
+            // Get the length of the path string NOT including the terminating null character.
+            // Previously, this was using a fixed size array of MAX_PATH length, but the
+            // Windows API allows longer paths under certain circumstances.
+            let character_count = unsafe { DragQueryFileW(hdrop, i, ptr::null_mut(), 0) as usize };
+            let str_len = character_count + 1;
+
+            // Fill path_buf with the null-terminated file name
+            let mut path_buf = Vec::with_capacity(str_len);
+            unsafe {
+                DragQueryFileW(hdrop, i, path_buf.as_mut_ptr(), str_len as u32);
+                path_buf.set_len(str_len);
+            }
+
+            callback(OsString::from_wide(&path_buf[0..character_count]).into());
+        }
+    }
+}
+
+/// The `file:` URI of a path, escaping the characters URIs can't contain.
+fn file_uri(path: &Path) -> String {
+    let mut uri = String::from("file:///");
+    for byte in path.to_string_lossy().replace('\\', "/").bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b':' | b'-' | b'.' | b'_' | b'~' => {
+                uri.push(byte as char)
+            },
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
 }
 
 impl FileDropHandlerData {
     fn send_event(&self, event: Event<()>) {
         (self.send_event)(event);
     }
+
+    /// Ask the application what a drop at the point would do, files always being accepted and
+    /// other data if the window accepts any of its types.
+    fn drag_hover(&self, pt: POINTL) -> u32 {
+        if !self.hovered_is_valid && self.accepted.is_empty() {
+            return DROPEFFECT_NONE;
+        }
+
+        let mut point = POINT { x: pt.x, y: pt.y };
+        unsafe { ScreenToClient(self.window, &mut point) };
+        let operation = Arc::new(Mutex::new(DropOperation::Copy));
+        self.send_event(Event::DragHover {
+            window_id: RootWindowId(WindowId(self.window)),
+            position: PhysicalPosition::new(point.x as f64, point.y as f64),
+            respond: DropResponse::new(Arc::downgrade(&operation)),
+        });
+
+        let operation = *operation.lock().unwrap();
+        match operation {
+            DropOperation::Copy => DROPEFFECT_COPY,
+            DropOperation::Move => DROPEFFECT_MOVE,
+            DropOperation::Link => DROPEFFECT_LINK,
+            DropOperation::Reject => DROPEFFECT_NONE,
+        }
+    }
 }
 
 impl Drop for FileDropHandler {
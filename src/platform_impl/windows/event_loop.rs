@@ -2,31 +2,53 @@
 
 mod runner;
 
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
-use std::ffi::c_void;
+use std::ffi::{c_void, OsString};
 use std::marker::PhantomData;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
-use std::{mem, panic, ptr};
+use std::{io, mem, panic, ptr};
 
 use crate::utils::Lazy;
 
-use windows_sys::Win32::Devices::HumanInterfaceDevice::MOUSE_MOVE_RELATIVE;
-use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, POINT, RECT, WPARAM};
+use windows_sys::Win32::Foundation::{
+    HANDLE, HWND, LPARAM, LRESULT, POINT, RECT, SIZE, TRUE, WAIT_OBJECT_0, WPARAM,
+};
+use windows_sys::Win32::Globalization::{GetUserPreferredUILanguages, MUI_LANGUAGE_NAME};
 use windows_sys::Win32::Graphics::Gdi::{
-    GetMonitorInfoW, MonitorFromRect, MonitorFromWindow, RedrawWindow, ScreenToClient,
-    ValidateRect, MONITORINFO, MONITOR_DEFAULTTONULL, RDW_INTERNALPAINT, SC_SCREENSAVE,
+    ChangeDisplaySettingsExW, ClientToScreen, GetMonitorInfoW, MonitorFromRect, MonitorFromWindow,
+    RedrawWindow, ScreenToClient, ValidateRect, CDS_FULLSCREEN, DISP_CHANGE_SUCCESSFUL,
+    MONITORINFO, MONITOR_DEFAULTTONULL, RDW_INTERNALPAINT, RDW_UPDATENOW, SC_SCREENSAVE,
 };
 use windows_sys::Win32::System::Ole::RevokeDragDrop;
-use windows_sys::Win32::System::Threading::{GetCurrentThreadId, INFINITE};
+use windows_sys::Win32::System::Power::{
+    RegisterPowerSettingNotification, UnregisterPowerSettingNotification, HPOWERNOTIFY,
+    POWERBROADCAST_SETTING,
+};
+use windows_sys::Win32::System::Recovery::{
+    RegisterApplicationRestart, UnregisterApplicationRestart,
+};
+use windows_sys::Win32::System::RemoteDesktop::{
+    WTSRegisterSessionNotification, WTSUnRegisterSessionNotification, NOTIFY_FOR_THIS_SESSION,
+};
+use windows_sys::Win32::System::SystemServices::{
+    GUID_CONSOLE_DISPLAY_STATE, MAXIMUM_WAIT_OBJECTS,
+};
+use windows_sys::Win32::System::Threading::{
+    GetCurrentThreadId, SetWaitableTimer, WaitForSingleObject, INFINITE,
+};
 use windows_sys::Win32::UI::Controls::{HOVER_DEFAULT, WM_MOUSELEAVE};
-use windows_sys::Win32::UI::Input::Ime::{GCS_COMPSTR, GCS_RESULTSTR, ISC_SHOWUICOMPOSITIONWINDOW};
+use windows_sys::Win32::UI::Input::Ime::{
+    GCS_COMPSTR, GCS_RESULTSTR, IMR_CONFIRMRECONVERTSTRING, IMR_DOCUMENTFEED, IMR_RECONVERTSTRING,
+    ISC_SHOWUICOMPOSITIONWINDOW,
+};
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
-    ReleaseCapture, SetCapture, TrackMouseEvent, TME_LEAVE, TRACKMOUSEEVENT,
+    GetDoubleClickTime, ReleaseCapture, SetCapture, TrackMouseEvent, TME_LEAVE, TRACKMOUSEEVENT,
+    VK_APPS,
 };
 use windows_sys::Win32::UI::Input::Pointer::{
     POINTER_FLAG_DOWN, POINTER_FLAG_UP, POINTER_FLAG_UPDATE,
@@ -37,43 +59,64 @@ use windows_sys::Win32::UI::Input::Touch::{
 };
 use windows_sys::Win32::UI::Input::{RAWINPUT, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetClientRect, GetCursorPos,
-    GetMenu, GetMessageW, KillTimer, LoadCursorW, PeekMessageW, PostMessageW, RegisterClassExW,
-    RegisterWindowMessageA, SetCursor, SetTimer, SetWindowPos, TranslateMessage, CREATESTRUCTW,
-    GIDC_ARRIVAL, GIDC_REMOVAL, GWL_STYLE, GWL_USERDATA, HTCAPTION, HTCLIENT, MINMAXINFO,
-    MNC_CLOSE, MSG, NCCALCSIZE_PARAMS, PM_REMOVE, PT_PEN, PT_TOUCH, RI_MOUSE_HWHEEL,
-    RI_MOUSE_WHEEL, SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SWP_NOACTIVATE, SWP_NOMOVE,
-    SWP_NOSIZE, SWP_NOZORDER, WHEEL_DELTA, WINDOWPOS, WMSZ_BOTTOM, WMSZ_BOTTOMLEFT,
-    WMSZ_BOTTOMRIGHT, WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT, WMSZ_TOPRIGHT,
-    WM_CAPTURECHANGED, WM_CLOSE, WM_CREATE, WM_DESTROY, WM_DPICHANGED, WM_ENTERSIZEMOVE,
-    WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION,
-    WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION, WM_INPUT, WM_INPUT_DEVICE_CHANGE, WM_KEYDOWN,
-    WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP,
-    WM_MENUCHAR, WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCACTIVATE, WM_NCCALCSIZE,
-    WM_NCCREATE, WM_NCDESTROY, WM_NCLBUTTONDOWN, WM_PAINT, WM_POINTERDOWN, WM_POINTERUP,
-    WM_POINTERUPDATE, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS, WM_SETTINGCHANGE,
-    WM_SIZE, WM_SIZING, WM_SYSCOMMAND, WM_SYSKEYDOWN, WM_SYSKEYUP, WM_TOUCH, WM_WINDOWPOSCHANGED,
-    WM_WINDOWPOSCHANGING, WM_XBUTTONDOWN, WM_XBUTTONUP, WNDCLASSEXW, WS_EX_LAYERED,
-    WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT, WS_OVERLAPPED, WS_POPUP, WS_VISIBLE,
+    CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GetAncestor, GetClientRect,
+    GetCursorPos, GetMenu, GetMessageW, GetSystemMetrics, GetWindowThreadProcessId, IsIconic,
+    IsZoomed, KillTimer, LoadCursorW, MsgWaitForMultipleObjectsEx, PeekMessageW, PostMessageW,
+    RegisterClassExW, RegisterWindowMessageA, SetCursor, SetForegroundWindow, SetTimer,
+    SetWindowPos, TranslateMessage, WindowFromPoint, CREATESTRUCTW, DEVICE_NOTIFY_WINDOW_HANDLE,
+    GA_PARENT, GIDC_ARRIVAL, GIDC_REMOVAL, GWLP_WNDPROC, GWL_STYLE, GWL_USERDATA, HTBOTTOM,
+    HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTCLIENT, HTCLOSE, HTLEFT, HTMAXBUTTON, HTMINBUTTON,
+    HTRIGHT, HTTOP, HTTOPLEFT, HTTOPRIGHT, HTTRANSPARENT, MINMAXINFO, MNC_CLOSE, MSG,
+    MWMO_INPUTAVAILABLE, NCCALCSIZE_PARAMS, PBT_POWERSETTINGCHANGE, PM_REMOVE, PT_PEN, PT_TOUCH,
+    QS_ALLINPUT, RI_MOUSE_HWHEEL, RI_MOUSE_WHEEL, SC_CLOSE, SC_MAXIMIZE, SC_MINIMIZE, SC_RESTORE,
+    SIZE_MAXIMIZED, SIZE_MINIMIZED, SM_CXDOUBLECLK, SM_CYDOUBLECLK, SM_REMOTESESSION,
+    SPI_SETWORKAREA, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SWP_SHOWWINDOW,
+    USER_TIMER_MINIMUM, WHEEL_DELTA, WINDOWPOS, WMSZ_BOTTOM, WMSZ_BOTTOMLEFT, WMSZ_BOTTOMRIGHT,
+    WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT, WMSZ_TOPRIGHT, WM_CAPTURECHANGED, WM_CLOSE,
+    WM_CONTEXTMENU, WM_CREATE, WM_DESTROY, WM_DISPLAYCHANGE, WM_DPICHANGED, WM_ENDSESSION,
+    WM_ENTERMENULOOP, WM_ENTERSIZEMOVE, WM_EXITMENULOOP, WM_EXITSIZEMOVE, WM_GETDPISCALEDSIZE,
+    WM_GETMINMAXINFO, WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION, WM_IME_REQUEST, WM_IME_SETCONTEXT,
+    WM_IME_STARTCOMPOSITION, WM_INPUT, WM_INPUT_DEVICE_CHANGE, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS,
+    WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MENUCHAR, WM_MOUSEFIRST,
+    WM_MOUSEHWHEEL, WM_MOUSELAST, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCACTIVATE, WM_NCCALCSIZE,
+    WM_NCCREATE, WM_NCDESTROY, WM_NCHITTEST, WM_NCLBUTTONDOWN, WM_NCLBUTTONUP, WM_PAINT,
+    WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE, WM_POWERBROADCAST, WM_QUERYENDSESSION, WM_QUIT,
+    WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SETCURSOR, WM_SETFOCUS, WM_SETTINGCHANGE, WM_SIZE, WM_SIZING,
+    WM_SYSCOMMAND, WM_SYSKEYDOWN, WM_SYSKEYUP, WM_TIMER, WM_TOUCH, WM_WINDOWPOSCHANGED,
+    WM_WINDOWPOSCHANGING, WM_WTSSESSION_CHANGE, WM_XBUTTONDOWN, WM_XBUTTONUP, WNDCLASSEXW,
+    WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW, WS_EX_TRANSPARENT, WS_OVERLAPPED, WS_POPUP,
+    WS_VISIBLE, WTS_SESSION_LOCK, WTS_SESSION_UNLOCK,
 };
 
 use crate::application::ApplicationHandler;
+use crate::dialog::{DialogButton, DialogSender, MessageDialog};
 use crate::dpi::{PhysicalPosition, PhysicalSize};
-use crate::error::EventLoopError;
+use crate::error::{EventLoopError, ExternalError};
 use crate::event::{
-    DeviceEvent, Event, Force, Ime, InnerSizeWriter, RawKeyEvent, Touch, TouchPhase, WindowEvent,
+    ContextMenuKind, DeviceEvent, Event, Force, Ime, InnerSizeWriter, RawKeyEvent, ResizeFrame,
+    Seat, Touch, TouchPhase, Transition, WindowEvent,
 };
 use crate::event_loop::{ActiveEventLoop as RootAEL, ControlFlow, DeviceEvents, EventLoopClosed};
 use crate::keyboard::ModifiersState;
+use crate::locale::LanguageTag;
+use crate::notification::{NotificationAttributes, NotificationId};
 use crate::platform::pump_events::PumpStatus;
-use crate::platform_impl::platform::dark_mode::try_theme;
+use crate::platform::windows::{HitTestResult, HookResult, RawInputConfig};
+use crate::platform_impl::platform::dark_mode::{apply_dwm_config, try_theme};
 use crate::platform_impl::platform::dpi::{become_dpi_aware, dpi_to_scale_factor};
 use crate::platform_impl::platform::drop_handler::FileDropHandler;
 use crate::platform_impl::platform::icon::WinCursor;
-use crate::platform_impl::platform::ime::ImeContext;
+use crate::platform_impl::platform::ime::{self, ImeContext};
 use crate::platform_impl::platform::keyboard::KeyEventBuilder;
 use crate::platform_impl::platform::keyboard_layout::LAYOUT_CACHE;
-use crate::platform_impl::platform::monitor::{self, MonitorHandle};
+use crate::platform_impl::platform::monitor::{self, MonitorHandle, VideoModeHandle};
+use crate::platform_impl::platform::mouse_thread::{self, MouseThread, RAW_MOUSE_MSG_ID};
+use crate::platform_impl::platform::notification::{self, Notification, NOTIFICATION_MSG_ID};
+use crate::platform_impl::platform::raw_input::{RawInputDevices, RawMouseSample};
+use crate::platform_impl::platform::share::{self, SHARE_MSG_ID};
+use crate::platform_impl::platform::timer::TimerResolution;
+use crate::platform_impl::platform::tray::{self, HIDE_TO_TRAY_MSG_ID, TRAY_MSG_ID};
+use crate::platform_impl::platform::util::MouseMessageSource;
 use crate::platform_impl::platform::window::InitData;
 use crate::platform_impl::platform::window_state::{
     CursorFlags, ImeState, WindowFlags, WindowState,
@@ -81,8 +124,9 @@ use crate::platform_impl::platform::window_state::{
 use crate::platform_impl::platform::{
     raw_input, util, wrap_device_id, Fullscreen, WindowId, DEVICE_ID,
 };
+use crate::session::{SessionCapabilities, SessionInfo, SessionKind};
 use crate::window::{
-    CustomCursor as RootCustomCursor, CustomCursorSource, WindowId as RootWindowId,
+    CustomCursor as RootCustomCursor, CustomCursorSource, ResizeDirection, WindowId as RootWindowId,
 };
 use runner::{EventLoopRunner, EventLoopRunnerShared};
 
@@ -131,6 +175,13 @@ impl WindowData {
 
 struct ThreadMsgTargetData {
     event_loop_runner: EventLoopRunnerShared<UserEventPlaceholder>,
+    mouse_thread: Option<MouseThread>,
+    /// The registration for changes of `GUID_CONSOLE_DISPLAY_STATE`.
+    display_state_notification: HPOWERNOTIFY,
+    /// Whether the display was on when its state last changed.
+    display_on: Cell<bool>,
+    /// The locale when the international settings last changed.
+    locale: RefCell<Option<LanguageTag>>,
 }
 
 impl ThreadMsgTargetData {
@@ -157,17 +208,25 @@ pub(crate) struct PlatformSpecificEventLoopAttributes {
     pub(crate) any_thread: bool,
     pub(crate) dpi_aware: bool,
     pub(crate) msg_hook: Option<Box<dyn FnMut(*const c_void) -> bool + 'static>>,
+    pub(crate) raw_input: RawInputConfig,
 }
 
 impl Default for PlatformSpecificEventLoopAttributes {
     fn default() -> Self {
-        Self { any_thread: false, dpi_aware: true, msg_hook: None }
+        Self { any_thread: false, dpi_aware: true, msg_hook: None, raw_input: Default::default() }
     }
 }
 
 pub struct ActiveEventLoop {
     thread_id: u32,
     thread_msg_target: HWND,
+    /// The devices registered for raw input on `thread_msg_target`.
+    raw_input_devices: RawInputDevices,
+    /// The window of the raw mouse input thread, if enabled.
+    mouse_thread_window: Option<HWND>,
+    timer_resolution: TimerResolution,
+    /// The handles registered by the application with their tokens, waited on with the messages.
+    wait_handles: RefCell<Vec<(HANDLE, u64)>>,
     pub(crate) runner_shared: EventLoopRunnerShared<UserEventPlaceholder>,
 }
 
@@ -195,19 +254,37 @@ impl<T: 'static> EventLoop<T> {
         let runner_shared = Rc::new(EventLoopRunner::new(thread_msg_target));
 
         let (user_event_sender, user_event_receiver) = mpsc::channel();
-        insert_event_target_window_data(thread_msg_target, runner_shared.clone());
-        raw_input::register_all_mice_and_keyboards_for_raw_input(
+
+        let raw_input = attributes.raw_input;
+        let mouse_thread = if raw_input.mouse && raw_input.mouse_thread {
+            MouseThread::spawn(thread_msg_target, raw_input.coalesce_mouse_motion)
+        } else {
+            None
+        };
+        let mouse_thread_window = mouse_thread.as_ref().map(MouseThread::window);
+        let raw_input_devices = RawInputDevices {
+            // Fall back to reading mouse input on this thread if the thread couldn't be spawned.
+            mouse: raw_input.mouse && mouse_thread.is_none(),
+            keyboard: raw_input.keyboard,
+        };
+
+        insert_event_target_window_data(thread_msg_target, runner_shared.clone(), mouse_thread);
+
+        let window_target = ActiveEventLoop {
+            thread_id,
             thread_msg_target,
-            Default::default(),
-        );
+            raw_input_devices,
+            mouse_thread_window,
+            timer_resolution: TimerResolution::default(),
+            wait_handles: Default::default(),
+            runner_shared,
+        };
+        window_target.listen_device_events(Default::default());
 
         Ok(EventLoop {
             user_event_sender,
             user_event_receiver,
-            window_target: RootAEL {
-                p: ActiveEventLoop { thread_id, thread_msg_target, runner_shared },
-                _marker: PhantomData,
-            },
+            window_target: RootAEL { p: window_target, _marker: PhantomData },
             msg_hook: attributes.msg_hook.take(),
         })
     }
@@ -247,18 +324,69 @@ impl<T: 'static> EventLoop<T> {
                         // to wakeup and dispatch a placeholder `UserEvent`,
                         // when we received the placeholder event here, the
                         // real UserEvent(T) should already be put in the
-                        // mpsc channel and ready to be pulled
+                        // mpsc channel and ready to be pulled, unless the event loop was only
+                        // woken up by an `EventLoopWaker`.
                         Event::UserEvent(_) => {
-                            let event = user_event_receiver
-                                .try_recv()
-                                .expect("user event signaled but not received");
-                            app.user_event(event_loop_windows_ref, event);
+                            if let Ok(event) = user_event_receiver.try_recv() {
+                                app.user_event(event_loop_windows_ref, event);
+                            }
+                        },
+                        Event::SurfaceCreated(window_id) => {
+                            app.surface_created(event_loop_windows_ref, window_id)
+                        },
+                        Event::SurfaceDestroyed(window_id) => {
+                            app.surface_destroyed(event_loop_windows_ref, window_id)
                         },
                         Event::Suspended => app.suspended(event_loop_windows_ref),
                         Event::Resumed => app.resumed(event_loop_windows_ref),
                         Event::AboutToWait => app.about_to_wait(event_loop_windows_ref),
+                        Event::Idle { deadline } => app.idle(event_loop_windows_ref, deadline),
+                        Event::CloseRequestedByOS { respond } => {
+                            app.close_requested_by_os(event_loop_windows_ref, respond)
+                        },
+                        Event::Notification { id, event } => {
+                            app.notification_event(event_loop_windows_ref, id, event)
+                        },
+                        Event::Terminating => app.terminating(event_loop_windows_ref),
                         Event::LoopExiting => app.exiting(event_loop_windows_ref),
                         Event::MemoryWarning => app.memory_warning(event_loop_windows_ref),
+                        Event::SessionLocked => app.session_locked(event_loop_windows_ref),
+                        Event::SessionUnlocked => app.session_unlocked(event_loop_windows_ref),
+                        Event::DisplaySleep => app.display_sleep(event_loop_windows_ref),
+                        Event::DisplayWake => app.display_wake(event_loop_windows_ref),
+                        Event::LocaleChanged(locale) => {
+                            app.locale_changed(event_loop_windows_ref, locale)
+                        },
+                        Event::WorkAreaChanged => app.work_area_changed(event_loop_windows_ref),
+                        Event::Navigation(pop_state) => {
+                            app.navigation(event_loop_windows_ref, pop_state)
+                        },
+                        Event::ColorProfileChanged => {
+                            app.color_profile_changed(event_loop_windows_ref)
+                        },
+                        Event::ActivationRequest { args, activation_token } => {
+                            app.activation_request(event_loop_windows_ref, args, activation_token)
+                        },
+                        Event::UrlOpened(url) => app.url_opened(event_loop_windows_ref, url),
+                        Event::FilesOpened(paths) => {
+                            app.files_opened(event_loop_windows_ref, paths)
+                        },
+                        Event::DragHover { window_id, position, respond } => {
+                            let operation =
+                                app.drag_hover(event_loop_windows_ref, window_id, position);
+                            let _ = respond.respond(operation);
+                        },
+                        Event::TrayActivated(window_id) => {
+                            app.tray_activated(event_loop_windows_ref, window_id)
+                        },
+                        Event::Signal(signal) => app.signal(event_loop_windows_ref, signal),
+                        Event::FdReady(token) => app.fd_ready(event_loop_windows_ref, token),
+                        Event::HandleSignaled(token) => {
+                            app.handle_signaled(event_loop_windows_ref, token)
+                        },
+                        Event::MonitorsChanged => app.monitors_changed(event_loop_windows_ref),
+                        Event::AudioInterrupted => app.audio_interrupted(event_loop_windows_ref),
+                        Event::AudioResumed => app.audio_resumed(event_loop_windows_ref),
                     }
                 });
             }
@@ -325,18 +453,69 @@ impl<T: 'static> EventLoop<T> {
                         // to wakeup and dispatch a placeholder `UserEvent`,
                         // when we received the placeholder event here, the
                         // real UserEvent(T) should already be put in the
-                        // mpsc channel and ready to be pulled
+                        // mpsc channel and ready to be pulled, unless the event loop was only
+                        // woken up by an `EventLoopWaker`.
                         Event::UserEvent(_) => {
-                            let event = user_event_receiver
-                                .try_recv()
-                                .expect("user event signaled but not received");
-                            app.user_event(event_loop_windows_ref, event);
+                            if let Ok(event) = user_event_receiver.try_recv() {
+                                app.user_event(event_loop_windows_ref, event);
+                            }
+                        },
+                        Event::SurfaceCreated(window_id) => {
+                            app.surface_created(event_loop_windows_ref, window_id)
+                        },
+                        Event::SurfaceDestroyed(window_id) => {
+                            app.surface_destroyed(event_loop_windows_ref, window_id)
                         },
                         Event::Suspended => app.suspended(event_loop_windows_ref),
                         Event::Resumed => app.resumed(event_loop_windows_ref),
                         Event::AboutToWait => app.about_to_wait(event_loop_windows_ref),
+                        Event::Idle { deadline } => app.idle(event_loop_windows_ref, deadline),
+                        Event::CloseRequestedByOS { respond } => {
+                            app.close_requested_by_os(event_loop_windows_ref, respond)
+                        },
+                        Event::Notification { id, event } => {
+                            app.notification_event(event_loop_windows_ref, id, event)
+                        },
+                        Event::Terminating => app.terminating(event_loop_windows_ref),
                         Event::LoopExiting => app.exiting(event_loop_windows_ref),
                         Event::MemoryWarning => app.memory_warning(event_loop_windows_ref),
+                        Event::SessionLocked => app.session_locked(event_loop_windows_ref),
+                        Event::SessionUnlocked => app.session_unlocked(event_loop_windows_ref),
+                        Event::DisplaySleep => app.display_sleep(event_loop_windows_ref),
+                        Event::DisplayWake => app.display_wake(event_loop_windows_ref),
+                        Event::LocaleChanged(locale) => {
+                            app.locale_changed(event_loop_windows_ref, locale)
+                        },
+                        Event::WorkAreaChanged => app.work_area_changed(event_loop_windows_ref),
+                        Event::Navigation(pop_state) => {
+                            app.navigation(event_loop_windows_ref, pop_state)
+                        },
+                        Event::ColorProfileChanged => {
+                            app.color_profile_changed(event_loop_windows_ref)
+                        },
+                        Event::ActivationRequest { args, activation_token } => {
+                            app.activation_request(event_loop_windows_ref, args, activation_token)
+                        },
+                        Event::UrlOpened(url) => app.url_opened(event_loop_windows_ref, url),
+                        Event::FilesOpened(paths) => {
+                            app.files_opened(event_loop_windows_ref, paths)
+                        },
+                        Event::DragHover { window_id, position, respond } => {
+                            let operation =
+                                app.drag_hover(event_loop_windows_ref, window_id, position);
+                            let _ = respond.respond(operation);
+                        },
+                        Event::TrayActivated(window_id) => {
+                            app.tray_activated(event_loop_windows_ref, window_id)
+                        },
+                        Event::Signal(signal) => app.signal(event_loop_windows_ref, signal),
+                        Event::FdReady(token) => app.fd_ready(event_loop_windows_ref, token),
+                        Event::HandleSignaled(token) => {
+                            app.handle_signaled(event_loop_windows_ref, token)
+                        },
+                        Event::MonitorsChanged => app.monitors_changed(event_loop_windows_ref),
+                        Event::AudioInterrupted => app.audio_interrupted(event_loop_windows_ref),
+                        Event::AudioResumed => app.audio_resumed(event_loop_windows_ref),
                     }
                 });
 
@@ -394,21 +573,111 @@ impl<T: 'static> EventLoop<T> {
             }
         }
 
+        /// Wait for the next MSG or until the waitable `timer` fires after `timeout`
+        ///
+        /// Returns `None` if no MSG was read, else a `Continue` or `Exit` status
+        fn get_msg_with_waitable_timer(
+            msg: &mut MSG,
+            timeout: Duration,
+            timer: HANDLE,
+        ) -> Option<PumpStatus> {
+            unsafe {
+                // Negative due times are relative, in 100ns intervals.
+                let due_time = -i64::try_from(timeout.as_nanos() / 100).unwrap_or(i64::MAX);
+                if SetWaitableTimer(timer, &due_time, 0, None, ptr::null(), false.into())
+                    == false.into()
+                {
+                    return Some(get_msg_with_timeout(msg, Some(timeout)));
+                }
+
+                let status = MsgWaitForMultipleObjectsEx(
+                    1,
+                    &timer,
+                    INFINITE,
+                    QS_ALLINPUT,
+                    MWMO_INPUTAVAILABLE,
+                );
+                if status == WAIT_OBJECT_0 || PeekMessageW(msg, 0, 0, 0, PM_REMOVE) == false.into()
+                {
+                    return None;
+                }
+
+                if msg.message == WM_QUIT {
+                    Some(PumpStatus::Exit(0))
+                } else {
+                    Some(PumpStatus::Continue)
+                }
+            }
+        }
+
+        /// Wait for the next MSG, until one of the `handles` is signaled, or until `timeout`,
+        /// with the waitable `timer` if there is one
+        ///
+        /// Returns `None` if no MSG was read, else a `Continue` or `Exit` status, and the index of
+        /// the handle that was signaled
+        fn get_msg_with_wait_handles(
+            msg: &mut MSG,
+            timeout: Option<Duration>,
+            timer: Option<HANDLE>,
+            handles: &[HANDLE],
+        ) -> (Option<PumpStatus>, Option<usize>) {
+            unsafe {
+                let mut objects = handles.to_vec();
+                let mut wait = timeout.map_or(INFINITE, dur2timeout);
+                if let (Some(timeout), Some(timer)) = (timeout, timer) {
+                    // Negative due times are relative, in 100ns intervals.
+                    let due_time = -i64::try_from(timeout.as_nanos() / 100).unwrap_or(i64::MAX);
+                    if timeout != Duration::ZERO
+                        && SetWaitableTimer(timer, &due_time, 0, None, ptr::null(), false.into())
+                            != false.into()
+                    {
+                        objects.push(timer);
+                        wait = INFINITE;
+                    }
+                }
+
+                let status = MsgWaitForMultipleObjectsEx(
+                    objects.len() as u32,
+                    objects.as_ptr(),
+                    wait,
+                    QS_ALLINPUT,
+                    MWMO_INPUTAVAILABLE,
+                );
+                let index = status.wrapping_sub(WAIT_OBJECT_0) as usize;
+                if index < handles.len() {
+                    return (None, Some(index));
+                }
+                if PeekMessageW(msg, 0, 0, 0, PM_REMOVE) == false.into() {
+                    return (None, None);
+                }
+
+                if msg.message == WM_QUIT {
+                    (Some(PumpStatus::Exit(0)), None)
+                } else {
+                    (Some(PumpStatus::Continue), None)
+                }
+            }
+        }
+
         /// Fetch the next MSG either via PeekMessage or GetMessage depending on whether the
         /// requested timeout is `ZERO` (and so we don't want to block)
         ///
         /// Returns `None` if no MSG was read, else a `Continue` or `Exit` status
-        fn wait_for_msg(msg: &mut MSG, timeout: Option<Duration>) -> Option<PumpStatus> {
-            if timeout == Some(Duration::ZERO) {
-                unsafe {
+        fn wait_for_msg(
+            msg: &mut MSG,
+            timeout: Option<Duration>,
+            timer: Option<HANDLE>,
+        ) -> Option<PumpStatus> {
+            match (timeout, timer) {
+                (Some(Duration::ZERO), _) => unsafe {
                     if PeekMessageW(msg, 0, 0, 0, PM_REMOVE) != 0 {
                         Some(PumpStatus::Continue)
                     } else {
                         None
                     }
-                }
-            } else {
-                Some(get_msg_with_timeout(msg, timeout))
+                },
+                (Some(timeout), Some(timer)) => get_msg_with_waitable_timer(msg, timeout, timer),
+                _ => Some(get_msg_with_timeout(msg, timeout)),
             }
         }
 
@@ -441,11 +710,31 @@ impl<T: 'static> EventLoop<T> {
         // API) and there's no API to construct or initialize a `MSG`. This
         // is the simplest way avoid uninitialized memory in Rust
         let mut msg = unsafe { mem::zeroed() };
-        let msg_status = wait_for_msg(&mut msg, timeout);
+        let timer = self.window_target.p.timer_resolution.timer();
+        let wait_handles = self.window_target.p.wait_handles.borrow().clone();
+        #[cfg(feature = "trace")]
+        let span = tracing::trace_span!("winit::wait_for_msg", ?timeout).entered();
+        let (msg_status, signaled) = if wait_handles.is_empty() {
+            (wait_for_msg(&mut msg, timeout, timer), None)
+        } else {
+            let handles: Vec<_> = wait_handles.iter().map(|&(handle, _)| handle).collect();
+            get_msg_with_wait_handles(&mut msg, timeout, timer, &handles)
+        };
+        #[cfg(feature = "trace")]
+        drop(span);
 
         // Before we potentially exit, make sure to consistently emit an event for the wake up
         runner.wakeup();
 
+        // The wait only reports the first signaled handle, so the others are checked as well, to
+        // not starve them while it stays signaled.
+        for (index, &(handle, token)) in wait_handles.iter().enumerate() {
+            if signaled == Some(index) || unsafe { WaitForSingleObject(handle, 0) } == WAIT_OBJECT_0
+            {
+                runner.send_event(Event::HandleSignaled(token));
+            }
+        }
+
         match msg_status {
             None => {}, // No MSG to dispatch
             Some(PumpStatus::Exit(code)) => {
@@ -540,6 +829,28 @@ impl ActiveEventLoop {
         EventLoopThreadExecutor { thread_id: self.thread_id, target_window: self.thread_msg_target }
     }
 
+    pub(crate) fn register_wait_handle(
+        &self,
+        handle: HANDLE,
+        token: u64,
+    ) -> Result<(), ExternalError> {
+        let mut wait_handles = self.wait_handles.borrow_mut();
+        wait_handles.retain(|&(_, registered)| registered != token);
+        // `MsgWaitForMultipleObjectsEx` waits on one handle less than the maximum, and the
+        // waitable timer may take another.
+        if wait_handles.len() >= MAXIMUM_WAIT_OBJECTS as usize - 2 {
+            let error =
+                io::Error::new(io::ErrorKind::Other, "too many wait handles are registered");
+            return Err(ExternalError::Os(os_error!(error)));
+        }
+        wait_handles.push((handle, token));
+        Ok(())
+    }
+
+    pub(crate) fn unregister_wait_handle(&self, token: u64) {
+        self.wait_handles.borrow_mut().retain(|&(_, registered)| registered != token);
+    }
+
     pub fn create_custom_cursor(&self, source: CustomCursorSource) -> RootCustomCursor {
         let inner = match WinCursor::new(&source.inner.0) {
             Ok(cursor) => cursor,
@@ -562,6 +873,92 @@ impl ActiveEventLoop {
         Some(monitor)
     }
 
+    pub fn locale(&self) -> Option<LanguageTag> {
+        locale()
+    }
+
+    pub fn seats(&self) -> Vec<Seat> {
+        Vec::new()
+    }
+
+    pub fn session_info(&self) -> SessionInfo {
+        let kind = if unsafe { GetSystemMetrics(SM_REMOTESESSION) } != 0 {
+            SessionKind::Remote
+        } else {
+            SessionKind::Desktop
+        };
+        SessionInfo::new(kind, false, SessionCapabilities::all())
+    }
+
+    pub fn pointer_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        let mut point = POINT { x: 0, y: 0 };
+        if unsafe { GetCursorPos(&mut point) } == false.into() {
+            return Err(ExternalError::Os(os_error!(std::io::Error::last_os_error())));
+        }
+        Ok(PhysicalPosition::new(point.x as f64, point.y as f64))
+    }
+
+    pub fn set_restart_after_crash(
+        &self,
+        args: Option<Vec<OsString>>,
+    ) -> Result<(), ExternalError> {
+        let result = match args {
+            Some(args) => {
+                let command_line = util::encode_wide(util::join_command_line(&args));
+                unsafe { RegisterApplicationRestart(command_line.as_ptr(), 0) }
+            },
+            None => unsafe { UnregisterApplicationRestart() },
+        };
+        if result < 0 {
+            return Err(ExternalError::Os(os_error!(std::io::Error::from_raw_os_error(result))));
+        }
+        Ok(())
+    }
+
+    pub fn set_video_mode(
+        &self,
+        mode: &VideoModeHandle,
+    ) -> Result<Box<dyn FnOnce()>, ExternalError> {
+        let monitor_info = monitor::get_monitor_info(mode.monitor.hmonitor())
+            .map_err(|error| ExternalError::Os(os_error!(error)))?;
+        let device = monitor_info.szDevice;
+        // Without `CDS_UPDATEREGISTRY` the change is temporary, and the system restores the mode
+        // once the process exits.
+        let result = unsafe {
+            ChangeDisplaySettingsExW(
+                device.as_ptr(),
+                &*mode.native_video_mode,
+                0,
+                CDS_FULLSCREEN,
+                ptr::null(),
+            )
+        };
+        if result != DISP_CHANGE_SUCCESSFUL {
+            let error = std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("failed to change the video mode: {result}"),
+            );
+            return Err(ExternalError::Os(os_error!(error)));
+        }
+        Ok(Box::new(move || {
+            // Switches back to the mode stored in the registry.
+            let result = unsafe {
+                ChangeDisplaySettingsExW(device.as_ptr(), ptr::null(), 0, 0, ptr::null())
+            };
+            if result != DISP_CHANGE_SUCCESSFUL {
+                tracing::warn!("failed to restore the video mode: {result}");
+            }
+        }))
+    }
+
+    pub fn double_click_settings(&self) -> Option<(Duration, u32)> {
+        let interval = Duration::from_millis(unsafe { GetDoubleClickTime() }.into());
+        // The metrics describe a rectangle centered on the first click.
+        let distance =
+            unsafe { GetSystemMetrics(SM_CXDOUBLECLK).max(GetSystemMetrics(SM_CYDOUBLECLK)) };
+        Some((interval, distance as u32 / 2))
+    }
+
     #[cfg(feature = "rwh_05")]
     pub fn raw_display_handle_rwh_05(&self) -> rwh_05::RawDisplayHandle {
         rwh_05::RawDisplayHandle::Windows(rwh_05::WindowsDisplayHandle::empty())
@@ -575,7 +972,10 @@ impl ActiveEventLoop {
     }
 
     pub fn listen_device_events(&self, allowed: DeviceEvents) {
-        raw_input::register_all_mice_and_keyboards_for_raw_input(self.thread_msg_target, allowed);
+        raw_input::register_for_raw_input(self.thread_msg_target, allowed, self.raw_input_devices);
+        if let Some(window) = self.mouse_thread_window {
+            mouse_thread::listen_device_events(window, allowed);
+        }
     }
 
     pub(crate) fn set_control_flow(&self, control_flow: ControlFlow) {
@@ -586,6 +986,28 @@ impl ActiveEventLoop {
         self.runner_shared.control_flow()
     }
 
+    pub(crate) fn set_timer_resolution(&self, resolution: Duration) {
+        self.timer_resolution.set(resolution)
+    }
+
+    pub(crate) fn set_exit_pending(&self, _pending: bool) {}
+
+    pub(crate) fn show_notification(
+        &self,
+        id: NotificationId,
+        attributes: NotificationAttributes,
+    ) -> Result<Notification, ExternalError> {
+        Notification::new(self.thread_msg_target, id, attributes)
+    }
+
+    pub(crate) fn show_message_dialog(
+        &self,
+        dialog: MessageDialog,
+        sender: DialogSender<DialogButton>,
+    ) -> Result<(), ExternalError> {
+        super::dialog::show_message_dialog(dialog, sender)
+    }
+
     pub(crate) fn exit(&self) {
         self.runner_shared.set_exit_code(0)
     }
@@ -778,6 +1200,22 @@ impl<T: 'static> EventLoopProxy<T> {
             })
             .map_err(|e| EventLoopClosed(e.0))
     }
+
+    pub fn waker(&self) -> EventLoopWaker {
+        EventLoopWaker { target_window: self.target_window }
+    }
+}
+
+/// Wakes up the `EventLoop` from any thread, without a message.
+#[derive(Clone)]
+pub struct EventLoopWaker {
+    target_window: HWND,
+}
+
+impl EventLoopWaker {
+    pub fn wake(&self) {
+        unsafe { PostMessageW(self.target_window, USER_EVENT_MSG_ID.get(), 0, 0) };
+    }
 }
 
 /// A lazily-initialized window message ID.
@@ -794,7 +1232,7 @@ const INVALID_ID: u32 = 0x0;
 
 impl LazyMessageId {
     /// Create a new `LazyId`.
-    const fn new(name: &'static str) -> Self {
+    pub(crate) const fn new(name: &'static str) -> Self {
         Self { id: AtomicU32::new(INVALID_ID), name }
     }
 
@@ -830,6 +1268,18 @@ impl LazyMessageId {
     }
 }
 
+/// How long `WM_SIZE` waits for the application to present a synchronized resize.
+const SYNCHRONIZED_RESIZE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// The timer of a window with hit test regions. The window doesn't receive cursor events while it
+/// passes them through, so the position of the cursor is checked periodically instead.
+pub(crate) const HITTEST_TIMER_ID: usize = 1;
+/// How often the position of the cursor is checked against the hit test regions, in milliseconds.
+pub(crate) const HITTEST_TIMER_INTERVAL: u32 = 16;
+/// The timer of a window in a modal loop of the system, i.e. while it is being moved or resized
+/// or one of its menus is open, which iterates the event loop from there.
+const MODAL_LOOP_TIMER_ID: usize = 2;
+
 // Message sent by the `EventLoopProxy` when we want to wake up the thread.
 // WPARAM and LPARAM are unused.
 static USER_EVENT_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::WakeupMsg\0");
@@ -906,11 +1356,67 @@ fn create_event_target_window() -> HWND {
     }
 }
 
+/// The first of the preferred UI languages of the user.
+fn locale() -> Option<LanguageTag> {
+    let mut count = 0;
+    let mut len = 0;
+    let result = unsafe {
+        GetUserPreferredUILanguages(MUI_LANGUAGE_NAME, &mut count, ptr::null_mut(), &mut len)
+    };
+    if result == false.into() {
+        return None;
+    }
+
+    // The languages are separated by nulls, followed by an additional null.
+    let mut buffer = vec![0u16; len as usize];
+    let result = unsafe {
+        GetUserPreferredUILanguages(MUI_LANGUAGE_NAME, &mut count, buffer.as_mut_ptr(), &mut len)
+    };
+    if result == false.into() {
+        return None;
+    }
+    LanguageTag::parse(&util::decode_wide(&buffer).to_string_lossy())
+}
+
+/// Whether the `lParam` of `WM_SETTINGCHANGE` names the given setting.
+///
+/// # Safety
+///
+/// `lparam` has to be null or point to a null-terminated string.
+unsafe fn is_setting(lparam: LPARAM, name: &str) -> bool {
+    if lparam == 0 {
+        return false;
+    }
+    // Stops at the first difference, which is at the latest the null of either string.
+    util::encode_wide(name)
+        .into_iter()
+        .enumerate()
+        .all(|(index, c)| unsafe { *(lparam as *const u16).add(index) } == c)
+}
+
 fn insert_event_target_window_data(
     thread_msg_target: HWND,
     event_loop_runner: EventLoopRunnerShared<UserEventPlaceholder>,
+    mouse_thread: Option<MouseThread>,
 ) {
-    let userdata = ThreadMsgTargetData { event_loop_runner };
+    // Report locking the session and turning the display off and on. Registering for the display
+    // state immediately reports the current state, which is ignored as the display starts out on.
+    unsafe { WTSRegisterSessionNotification(thread_msg_target, NOTIFY_FOR_THIS_SESSION) };
+    let display_state_notification = unsafe {
+        RegisterPowerSettingNotification(
+            thread_msg_target,
+            &GUID_CONSOLE_DISPLAY_STATE,
+            DEVICE_NOTIFY_WINDOW_HANDLE,
+        )
+    };
+
+    let userdata = ThreadMsgTargetData {
+        event_loop_runner,
+        mouse_thread,
+        display_state_notification,
+        display_on: Cell::new(true),
+        locale: RefCell::new(locale()),
+    };
     let input_ptr = Box::into_raw(Box::new(userdata));
 
     unsafe { super::set_window_long(thread_msg_target, GWL_USERDATA, input_ptr as isize) };
@@ -918,14 +1424,14 @@ fn insert_event_target_window_data(
 
 /// Capture mouse input, allowing `window` to receive mouse events when the cursor is outside of
 /// the window.
-unsafe fn capture_mouse(window: HWND, window_state: &mut WindowState) {
+pub(super) unsafe fn capture_mouse(window: HWND, window_state: &mut WindowState) {
     window_state.mouse.capture_count += 1;
     unsafe { SetCapture(window) };
 }
 
 /// Release mouse input, stopping windows on this thread from receiving mouse input when the cursor
 /// is outside the window.
-unsafe fn release_mouse(mut window_state: MutexGuard<'_, WindowState>) {
+pub(super) unsafe fn release_mouse(mut window_state: MutexGuard<'_, WindowState>) {
     window_state.mouse.capture_count = window_state.mouse.capture_count.saturating_sub(1);
     if window_state.mouse.capture_count == 0 {
         // ReleaseCapture() causes a WM_CAPTURECHANGED where we lock the window_state.
@@ -974,6 +1480,7 @@ unsafe fn gain_active_focus(window: HWND, userdata: &WindowData) {
         window_id: RootWindowId(WindowId(window)),
         event: Focused(true),
     });
+    update_visibility(window, userdata);
 }
 
 unsafe fn lose_active_focus(window: HWND, userdata: &WindowData) {
@@ -989,6 +1496,45 @@ unsafe fn lose_active_focus(window: HWND, userdata: &WindowData) {
         window_id: RootWindowId(WindowId(window)),
         event: Focused(false),
     });
+    update_visibility(window, userdata);
+}
+
+/// Emit `VisibilityChanged` if the visibility of the window changed.
+fn update_visibility(window: HWND, userdata: &WindowData) {
+    use crate::event::WindowEvent::VisibilityChanged;
+
+    let visibility = {
+        let mut window_state = userdata.window_state_lock();
+        let visibility = window_state.visibility(window);
+        if mem::replace(&mut window_state.last_visibility, visibility) == visibility {
+            return;
+        }
+        visibility
+    };
+
+    userdata.send_event(Event::WindowEvent {
+        window_id: RootWindowId(WindowId(window)),
+        event: VisibilityChanged(visibility),
+    });
+}
+
+/// Emit `TilingStateChanged` if the tiling state of the window changed.
+fn update_tiling_state(window: HWND, userdata: &WindowData) {
+    use crate::event::WindowEvent::TilingStateChanged;
+
+    let tiling_state = {
+        let mut window_state = userdata.window_state_lock();
+        let tiling_state = window_state.tiling_state(window);
+        if mem::replace(&mut window_state.last_tiling_state, tiling_state) == tiling_state {
+            return;
+        }
+        tiling_state
+    };
+
+    userdata.send_event(Event::WindowEvent {
+        window_id: RootWindowId(WindowId(window)),
+        event: TilingStateChanged(tiling_state),
+    });
 }
 
 /// Any window whose callback is configured to this function will have its events propagated
@@ -1056,6 +1602,36 @@ pub(super) unsafe extern "system" fn public_window_callback(
     result
 }
 
+/// Runs the message hooks of the window in order, until one of them handles the message.
+fn run_message_hooks(
+    window: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    userdata: &WindowData,
+) -> Option<LRESULT> {
+    // Don't hold the lock while running the hooks, which may call back into the window.
+    let hooks: Vec<_> = {
+        let window_state = userdata.window_state_lock();
+        if window_state.message_hooks.is_empty() {
+            return None;
+        }
+        window_state.message_hooks.iter().map(|hook| hook.hook.clone()).collect()
+    };
+
+    for hook in hooks {
+        // The hook is already running further up the stack.
+        let Ok(mut hook) = hook.try_lock() else {
+            continue;
+        };
+        if let HookResult::Handled(value) = (*hook)(window, msg, wparam, lparam) {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
 unsafe fn public_window_callback_inner(
     window: HWND,
     msg: u32,
@@ -1063,6 +1639,32 @@ unsafe fn public_window_callback_inner(
     lparam: LPARAM,
     userdata: &WindowData,
 ) -> LRESULT {
+    // Message hooks run before winit, except for the message winit needs to clean up.
+    if msg != WM_NCDESTROY {
+        let hooks_callback = || run_message_hooks(window, msg, wparam, lparam, userdata);
+        match userdata.event_loop_runner.catch_unwind(hooks_callback) {
+            Some(Some(value)) => return value,
+            Some(None) => (),
+            None => return -1,
+        }
+    }
+
+    // Drop the mouse messages Windows synthesized from touch and pen input, if asked to.
+    if (WM_MOUSEFIRST..=WM_MOUSELAST).contains(&msg)
+        && msg != WM_MOUSEWHEEL
+        && msg != WM_MOUSEHWHEEL
+    {
+        let policy = userdata.window_state_lock().pointer_promotion;
+        let promoted = match util::mouse_message_source() {
+            MouseMessageSource::Mouse => true,
+            MouseMessageSource::Touch => policy.touch_to_mouse,
+            MouseMessageSource::Pen => policy.pen_to_mouse,
+        };
+        if !promoted {
+            return unsafe { DefWindowProcW(window, msg, wparam, lparam) };
+        }
+    }
+
     let mut result = ProcResult::DefWindowProc(wparam);
 
     // Send new modifiers before sending key events.
@@ -1122,10 +1724,15 @@ unsafe fn public_window_callback_inner(
                 if let Ok(monitor_info) = monitor::get_monitor_info(monitor) {
                     params.rgrc[0] = monitor_info.monitorInfo.rcWork;
                 }
-            } else if window_flags.contains(WindowFlags::MARKER_UNDECORATED_SHADOW) {
+            } else if window_flags.contains(WindowFlags::MARKER_UNDECORATED_SHADOW)
+                && !window_flags.is_fullscreen_optimized()
+            {
                 // Extend the client area to cover the whole non-client area.
                 // https://docs.microsoft.com/en-us/windows/win32/winmsg/wm-nccalcsize#remarks
                 //
+                // Skipped for optimized fullscreen windows, where the client area has to cover the
+                // monitor exactly.
+                //
                 // HACK(msiglreith): To add the drop shadow we slightly tweak the non-client area.
                 // This leads to a small black 1px border on the top. Adding a margin manually
                 // on all 4 borders would result in the caption getting drawn by the DWM.
@@ -1145,6 +1752,12 @@ unsafe fn public_window_callback_inner(
             userdata
                 .window_state_lock()
                 .set_window_flags_in_place(|f| f.insert(WindowFlags::MARKER_IN_SIZE_MOVE));
+            // The modal loop only dispatches messages, so the event loop can't wake up on its own.
+            unsafe { SetTimer(window, MODAL_LOOP_TIMER_ID, USER_TIMER_MINIMUM, None) };
+            userdata.send_event(Event::WindowEvent {
+                window_id: RootWindowId(WindowId(window)),
+                event: WindowEvent::MoveResizeBegan,
+            });
             result = ProcResult::Value(0);
         },
 
@@ -1156,6 +1769,89 @@ unsafe fn public_window_callback_inner(
             }
 
             state.set_window_flags_in_place(|f| f.remove(WindowFlags::MARKER_IN_SIZE_MOVE));
+            drop(state);
+            unsafe { KillTimer(window, MODAL_LOOP_TIMER_ID) };
+            userdata.send_event(Event::WindowEvent {
+                window_id: RootWindowId(WindowId(window)),
+                event: WindowEvent::MoveResizeEnded,
+            });
+            result = ProcResult::Value(0);
+        },
+
+        WM_ENTERMENULOOP => {
+            unsafe { SetTimer(window, MODAL_LOOP_TIMER_ID, USER_TIMER_MINIMUM, None) };
+            result = ProcResult::Value(0);
+        },
+
+        WM_EXITMENULOOP => {
+            unsafe { KillTimer(window, MODAL_LOOP_TIMER_ID) };
+            result = ProcResult::Value(0);
+        },
+
+        WM_TIMER if wparam == MODAL_LOOP_TIMER_ID => {
+            userdata.event_loop_runner.wakeup_in_modal_loop();
+            result = ProcResult::Value(0);
+        },
+
+        WM_NCHITTEST => {
+            let (hit_test_handler, maximize_button_bounds, scale_factor) = {
+                let window_state = userdata.window_state_lock();
+                (
+                    window_state.hit_test_handler.clone(),
+                    window_state.maximize_button_bounds,
+                    window_state.scale_factor,
+                )
+            };
+
+            if hit_test_handler.is_none() && maximize_button_bounds.is_none() {
+                result = ProcResult::DefWindowProc(wparam);
+                return;
+            }
+
+            let mut point = POINT {
+                x: super::get_x_lparam(lparam as u32) as i32,
+                y: super::get_y_lparam(lparam as u32) as i32,
+            };
+            unsafe { ScreenToClient(window, &mut point) };
+            let position = PhysicalPosition::new(point.x, point.y);
+
+            // The user's handler is called without holding the lock.
+            let hit = hit_test_handler.and_then(|handler| handler(position)).or_else(|| {
+                let (button_position, button_size) = maximize_button_bounds?;
+                let button_position: PhysicalPosition<i32> =
+                    button_position.to_physical(scale_factor);
+                let button_size: PhysicalSize<i32> = button_size.to_physical(scale_factor);
+                let x = button_position.x..button_position.x + button_size.width;
+                let y = button_position.y..button_position.y + button_size.height;
+                (x.contains(&position.x) && y.contains(&position.y))
+                    .then_some(HitTestResult::MaximizeButton)
+            });
+
+            result = match hit {
+                Some(hit) => ProcResult::Value(hit_test_result_to_ht(hit)),
+                None => ProcResult::DefWindowProc(wparam),
+            };
+        },
+
+        WM_TIMER if wparam == HITTEST_TIMER_ID => {
+            let mut point = POINT { x: 0, y: 0 };
+            unsafe {
+                GetCursorPos(&mut point);
+                ScreenToClient(window, &mut point);
+            }
+            let position = PhysicalPosition::new(point.x as f64, point.y as f64);
+
+            let window_state = userdata.window_state_lock();
+            let hittest = window_state.hittest_regions.as_ref().map(|regions| {
+                regions.iter().any(|region| region.contains(position, window_state.scale_factor))
+            });
+            let ignored = window_state.window_flags.contains(WindowFlags::IGNORE_CURSOR_EVENT);
+            if let Some(hittest) = hittest.filter(|&hittest| hittest == ignored) {
+                WindowState::set_window_flags(window_state, window, |f| {
+                    f.set(WindowFlags::IGNORE_CURSOR_EVENT, !hittest)
+                });
+            }
+
             result = ProcResult::Value(0);
         },
 
@@ -1163,7 +1859,24 @@ unsafe fn public_window_callback_inner(
             if wparam == HTCAPTION as _ {
                 unsafe { PostMessageW(window, WM_MOUSEMOVE, 0, lparam) };
             }
-            result = ProcResult::DefWindowProc(wparam);
+
+            // The system would draw its own caption button over the custom one, so they're
+            // handled on release instead.
+            result = if caption_button_command(window, userdata, wparam).is_some() {
+                ProcResult::Value(0)
+            } else {
+                ProcResult::DefWindowProc(wparam)
+            };
+        },
+
+        WM_NCLBUTTONUP => {
+            result = match caption_button_command(window, userdata, wparam) {
+                Some(command) => {
+                    unsafe { PostMessageW(window, WM_SYSCOMMAND, command as WPARAM, 0) };
+                    ProcResult::Value(0)
+                },
+                None => ProcResult::DefWindowProc(wparam),
+            };
         },
 
         WM_CLOSE => {
@@ -1175,9 +1888,22 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        WM_QUERYENDSESSION => {
+            let cancelled = userdata.event_loop_runner.query_end_session();
+            result = ProcResult::Value(!cancelled as isize);
+        },
+
+        WM_ENDSESSION => {
+            userdata.event_loop_runner.end_session(wparam != 0);
+            result = ProcResult::Value(0);
+        },
+
         WM_DESTROY => {
             use crate::event::WindowEvent::Destroyed;
             unsafe { RevokeDragDrop(window) };
+            if userdata.window_state_lock().in_tray {
+                tray::remove(window);
+            }
             userdata.send_event(Event::WindowEvent {
                 window_id: RootWindowId(WindowId(window)),
                 event: Destroyed,
@@ -1308,6 +2034,20 @@ unsafe fn public_window_callback_inner(
                 });
             }
 
+            // Shown while hidden to the tray, either when its icon was activated or with
+            // `Window::set_visible`.
+            if unsafe { (*windowpos).flags & SWP_SHOWWINDOW != 0 }
+                && mem::take(&mut userdata.window_state_lock().in_tray)
+            {
+                tray::remove(window);
+                userdata.send_event(Event::SurfaceCreated(RootWindowId(WindowId(window))));
+            }
+
+            // Sent when the window is shown, hidden, minimized or restored.
+            update_visibility(window, userdata);
+            // Sent when the window is snapped, maximized, made fullscreen or restored.
+            update_tiling_state(window, userdata);
+
             // This is necessary for us to still get sent WM_SIZE.
             result = ProcResult::DefWindowProc(wparam);
         },
@@ -1318,12 +2058,8 @@ unsafe fn public_window_callback_inner(
             let h = super::hiword(lparam as u32) as u32;
 
             let physical_size = PhysicalSize::new(w, h);
-            let event = Event::WindowEvent {
-                window_id: RootWindowId(WindowId(window)),
-                event: Resized(physical_size),
-            };
 
-            {
+            let (frame, waiter) = {
                 let mut w = userdata.window_state_lock();
                 // See WindowFlags::MARKER_RETAIN_STATE_ON_SIZE docs for info on why this `if` check
                 // exists.
@@ -1331,8 +2067,36 @@ unsafe fn public_window_callback_inner(
                     let maximized = wparam == SIZE_MAXIMIZED as usize;
                     w.set_window_flags_in_place(|f| f.set(WindowFlags::MAXIMIZED, maximized));
                 }
+
+                // A buffered event is only handled after this message was processed, so there is
+                // nothing to wait for.
+                if w.synchronized_resize
+                    && wparam != SIZE_MINIMIZED as usize
+                    && !userdata.event_loop_runner.should_buffer()
+                {
+                    let (frame, waiter) = ResizeFrame::new(None);
+                    (Some(frame), Some(waiter))
+                } else {
+                    (None, None)
+                }
+            };
+            userdata.send_event(Event::WindowEvent {
+                window_id: RootWindowId(WindowId(window)),
+                event: Resized { size: physical_size, frame },
+            });
+
+            if let Some(waiter) = waiter {
+                if !waiter.is_presented() {
+                    // Give the application the chance to present the new size before the system
+                    // repaints the window.
+                    unsafe {
+                        RedrawWindow(window, ptr::null(), 0, RDW_INTERNALPAINT | RDW_UPDATENOW)
+                    };
+                    if !waiter.wait(SYNCHRONIZED_RESIZE_TIMEOUT) {
+                        tracing::debug!("synchronized resize timed out");
+                    }
+                }
             }
-            userdata.send_event(event);
             result = ProcResult::Value(0);
         },
 
@@ -1455,7 +2219,7 @@ unsafe fn public_window_callback_inner(
                 if lparam == 0 {
                     userdata.send_event(Event::WindowEvent {
                         window_id: RootWindowId(WindowId(window)),
-                        event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                        event: WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                     });
                 }
 
@@ -1467,7 +2231,7 @@ unsafe fn public_window_callback_inner(
 
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
-                            event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                            event: WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                         });
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
@@ -1478,7 +2242,7 @@ unsafe fn public_window_callback_inner(
 
                 // Next, receive preedit range for next composing if exist.
                 if (lparam as u32 & GCS_COMPSTR) != 0 {
-                    if let Some((text, first, last)) =
+                    if let Some((text, first, last, spans)) =
                         unsafe { ime_context.get_composing_text_and_cursor() }
                     {
                         userdata.window_state_lock().ime_state = ImeState::Preedit;
@@ -1486,7 +2250,7 @@ unsafe fn public_window_callback_inner(
 
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
-                            event: WindowEvent::Ime(Ime::Preedit(text, cursor_range)),
+                            event: WindowEvent::Ime(Ime::Preedit(text, cursor_range, spans)),
                         });
                     }
                 }
@@ -1509,7 +2273,7 @@ unsafe fn public_window_callback_inner(
                     if let Some(text) = unsafe { ime_context.get_composed_text() } {
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
-                            event: WindowEvent::Ime(Ime::Preedit(String::new(), None)),
+                            event: WindowEvent::Ime(Ime::Preedit(String::new(), None, Vec::new())),
                         });
                         userdata.send_event(Event::WindowEvent {
                             window_id: RootWindowId(WindowId(window)),
@@ -1529,6 +2293,46 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::DefWindowProc(wparam);
         },
 
+        WM_IME_REQUEST => {
+            let request = wparam as u32;
+            let surrounding_text = {
+                let w = userdata.window_state_lock();
+                w.ime_surrounding_text.clone().filter(|_| w.ime_allowed)
+            };
+
+            result = match (request, surrounding_text) {
+                (IMR_RECONVERTSTRING | IMR_DOCUMENTFEED, Some(text)) => {
+                    // The context of the document feed is around the cursor, while reconversion
+                    // starts with the selected text.
+                    let composition = match request {
+                        IMR_RECONVERTSTRING => text.selection(),
+                        _ => text.cursor()..text.cursor(),
+                    };
+                    let size = unsafe {
+                        ime::write_reconvert_string(&text, composition, lparam as *mut _)
+                    };
+                    ProcResult::Value(size as isize)
+                },
+                (IMR_CONFIRMRECONVERTSTRING, Some(text)) if lparam != 0 => {
+                    // The IME may have extended the text to reconvert, which replaces the selection
+                    // when it's committed.
+                    let range = unsafe { ime::reconvert_string_range(&text, lparam as *const _) };
+                    let (before_bytes, after_bytes) = text.delete_range(range);
+                    if before_bytes > 0 || after_bytes > 0 {
+                        userdata.send_event(Event::WindowEvent {
+                            window_id: RootWindowId(WindowId(window)),
+                            event: WindowEvent::Ime(Ime::DeleteSurrounding {
+                                before_bytes,
+                                after_bytes,
+                            }),
+                        });
+                    }
+                    ProcResult::Value(1)
+                },
+                _ => ProcResult::DefWindowProc(wparam),
+            };
+        },
+
         WM_IME_SETCONTEXT => {
             // Hide composing text drawn by IME.
             let wparam = wparam & (!ISC_SHOWUICOMPOSITIONWINDOW as usize);
@@ -1537,6 +2341,13 @@ unsafe fn public_window_callback_inner(
 
         // this is necessary for us to maintain minimize/restore state
         WM_SYSCOMMAND => {
+            let is_minimized = unsafe { IsIconic(window) } != false.into();
+            let transition = match wparam as u32 {
+                SC_MINIMIZE if !is_minimized => Some(Transition::Minimize),
+                SC_RESTORE if is_minimized => Some(Transition::Restore),
+                _ => None,
+            };
+
             if wparam == SC_RESTORE as usize {
                 let mut w = userdata.window_state_lock();
                 w.set_window_flags_in_place(|f| f.set(WindowFlags::MINIMIZED, false));
@@ -1555,7 +2366,20 @@ unsafe fn public_window_callback_inner(
                 }
             }
 
-            result = ProcResult::DefWindowProc(wparam);
+            if let Some(transition) = transition {
+                // The window is minimized or restored by the time `DefWindowProcW` returns.
+                userdata.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: WindowEvent::TransitionStarted(transition),
+                });
+                result = ProcResult::Value(unsafe { DefWindowProcW(window, msg, wparam, lparam) });
+                userdata.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: WindowEvent::TransitionEnded(transition),
+                });
+            } else {
+                result = ProcResult::DefWindowProc(wparam);
+            }
         },
 
         WM_MOUSEMOVE => {
@@ -1565,6 +2389,13 @@ unsafe fn public_window_callback_inner(
             let y = super::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
 
+            // The other windows don't receive mouse messages while the mouse is captured.
+            if userdata.window_state_lock().mouse.capture_count > 0 {
+                let mut point = POINT { x, y };
+                unsafe { ClientToScreen(window, &mut point) };
+                update_drag_target(window, userdata, unsafe { window_from_point(point) });
+            }
+
             let cursor_moved;
             {
                 let mut w = userdata.window_state_lock();
@@ -1693,6 +2524,9 @@ unsafe fn public_window_callback_inner(
                 // let Windows handle event if the window has a native menu, a modal event loop
                 // is started here on Alt key up.
                 result = ProcResult::DefWindowProc(wparam);
+            } else if msg == WM_KEYUP && wparam == VK_APPS as WPARAM {
+                // `DefWindowProcW` sends `WM_CONTEXTMENU` for the menu key.
+                result = ProcResult::DefWindowProc(wparam);
             }
         },
 
@@ -1757,7 +2591,37 @@ unsafe fn public_window_callback_inner(
                 window_id: RootWindowId(WindowId(window)),
                 event: MouseInput { device_id: DEVICE_ID, state: Released, button: Right },
             });
-            result = ProcResult::Value(0);
+            // `DefWindowProcW` sends `WM_CONTEXTMENU`.
+            result = ProcResult::DefWindowProc(wparam);
+        },
+
+        WM_CONTEXTMENU => {
+            // Messages bubbling up from child windows were already reported by them.
+            if wparam as HWND == window {
+                let (position, kind) = if lparam == -1 {
+                    (PhysicalPosition::new(0.0, 0.0), ContextMenuKind::Keyboard)
+                } else {
+                    let mut point = POINT {
+                        x: super::get_x_lparam(lparam as u32) as i32,
+                        y: super::get_y_lparam(lparam as u32) as i32,
+                    };
+                    unsafe { ScreenToClient(window, &mut point) };
+                    // Still the source of the `WM_RBUTTONUP` this is sent from.
+                    let kind = match util::mouse_message_source() {
+                        MouseMessageSource::Mouse => ContextMenuKind::Mouse,
+                        MouseMessageSource::Touch | MouseMessageSource::Pen => {
+                            ContextMenuKind::Touch
+                        },
+                    };
+                    (PhysicalPosition::new(point.x as f64, point.y as f64), kind)
+                };
+
+                userdata.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: WindowEvent::ContextMenuRequested { position, kind },
+                });
+                result = ProcResult::Value(0);
+            }
         },
 
         WM_MBUTTONDOWN => {
@@ -1810,7 +2674,7 @@ unsafe fn public_window_callback_inner(
                     button: match xbutton {
                         1 => Back,
                         2 => Forward,
-                        _ => Other(xbutton),
+                        _ => Other(xbutton + 2),
                     },
                 },
             });
@@ -1835,7 +2699,7 @@ unsafe fn public_window_callback_inner(
                     button: match xbutton {
                         1 => Back,
                         2 => Forward,
-                        _ => Other(xbutton),
+                        _ => Other(xbutton + 2),
                     },
                 },
             });
@@ -1848,7 +2712,26 @@ unsafe fn public_window_callback_inner(
             // can happen if `SetCapture` is called on our window when it already has the mouse
             // capture.
             if lparam != window {
-                userdata.window_state_lock().mouse.capture_count = 0;
+                let drag_target = {
+                    let mut w = userdata.window_state_lock();
+                    w.mouse.capture_count = 0;
+                    w.mouse.pointer_captured = false;
+                    w.mouse.drag_target.take()
+                };
+
+                // The window under the cursor receives mouse messages again, so it only has to be
+                // left here if the cursor isn't over it anymore.
+                if let Some(drag_target) = drag_target {
+                    let mut point = POINT { x: 0, y: 0 };
+                    let hovered = unsafe { GetCursorPos(&mut point) } != false.into()
+                        && unsafe { window_from_point(point) } == Some(drag_target);
+                    if !hovered {
+                        userdata.send_event(Event::WindowEvent {
+                            window_id: RootWindowId(WindowId(drag_target)),
+                            event: WindowEvent::CursorLeft { device_id: DEVICE_ID },
+                        });
+                    }
+                }
             }
             result = ProcResult::Value(0);
         },
@@ -2084,19 +2967,19 @@ unsafe fn public_window_callback_inner(
                 // `WM_MOUSEMOVE` seems to come after `WM_SETCURSOR` for a given cursor movement.
                 let in_client_area = super::loword(lparam as u32) as u32 == HTCLIENT;
                 if in_client_area {
-                    Some(window_state.mouse.selected_cursor.clone())
+                    Some((window_state.mouse.selected_cursor.clone(), window_state.scale_factor))
                 } else {
                     None
                 }
             };
 
             match set_cursor_to {
-                Some(selected_cursor) => {
+                Some((selected_cursor, scale_factor)) => {
                     let hcursor = match selected_cursor {
                         SelectedCursor::Named(cursor_icon) => unsafe {
                             LoadCursorW(0, util::to_windows_cursor(cursor_icon))
                         },
-                        SelectedCursor::Custom(cursor) => cursor.as_raw_handle(),
+                        SelectedCursor::Custom(cursor) => cursor.as_raw_handle(scale_factor),
                     };
                     unsafe { SetCursor(hcursor) };
                     result = ProcResult::Value(0);
@@ -2129,11 +3012,72 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        // Only sent on Windows 10 Creators Update (1703) or newer to per-monitor v2 aware windows,
+        // right before `WM_DPICHANGED`. Emitting `ScaleFactorChanged` here lets Windows size the
+        // window for the new DPI itself, instead of scaling it linearly mid-drag and then having
+        // us resize it a second time in `WM_DPICHANGED`.
+        WM_GETDPISCALEDSIZE => {
+            let new_dpi = wparam as u32;
+            let new_scale_factor = dpi_to_scale_factor(new_dpi);
+
+            let (old_scale_factor, allow_resize, window_flags) = {
+                let mut window_state = userdata.window_state_lock();
+                let old_scale_factor = window_state.scale_factor;
+                if new_scale_factor == old_scale_factor {
+                    // Let Windows scale the window linearly, which is a no-op here.
+                    result = ProcResult::Value(0);
+                    return;
+                }
+
+                window_state.scale_factor = new_scale_factor;
+                (old_scale_factor, allow_dpi_resize(&window_state), window_state.window_flags)
+            };
+
+            let old_physical_inner_size = match util::WindowArea::Inner.get_rect(window) {
+                Ok(rect) => PhysicalSize::new(
+                    (rect.right - rect.left) as u32,
+                    (rect.bottom - rect.top) as u32,
+                ),
+                Err(_) => {
+                    userdata.window_state_lock().scale_factor = old_scale_factor;
+                    result = ProcResult::Value(0);
+                    return;
+                },
+            };
+
+            let suggested_inner_size = dpi_scaled_inner_size(
+                old_physical_inner_size,
+                old_scale_factor,
+                new_scale_factor,
+                allow_resize,
+            );
+            let new_physical_inner_size =
+                send_scale_factor_changed(window, userdata, new_scale_factor, suggested_inner_size);
+            userdata.window_state_lock().dpi_scaled_size = Some((new_dpi, new_physical_inner_size));
+
+            // Windows expects the outer size at the new DPI.
+            let inner_rect = RECT {
+                left: 0,
+                top: 0,
+                right: new_physical_inner_size.width as i32,
+                bottom: new_physical_inner_size.height as i32,
+            };
+            let outer_rect = window_flags
+                .adjust_rect_for_dpi(window, inner_rect, Some(new_dpi))
+                .unwrap_or(inner_rect);
+            unsafe {
+                *(lparam as *mut SIZE) = SIZE {
+                    cx: outer_rect.right - outer_rect.left,
+                    cy: outer_rect.bottom - outer_rect.top,
+                };
+            }
+
+            result = ProcResult::Value(1);
+        },
+
         // Only sent on Windows 8.1 or newer. On Windows 7 and older user has to log out to change
         // DPI, therefore all applications are closed while DPI is changing.
         WM_DPICHANGED => {
-            use crate::event::WindowEvent::ScaleFactorChanged;
-
             // This message actually provides two DPI values - x and y. However MSDN says that
             // "you only need to use either the X-axis or the Y-axis value when scaling your
             // application since they are the same".
@@ -2142,20 +3086,25 @@ unsafe fn public_window_callback_inner(
             let new_scale_factor = dpi_to_scale_factor(new_dpi_x);
             let old_scale_factor: f64;
 
-            let (allow_resize, window_flags) = {
+            let (allow_resize, window_flags, negotiated_inner_size) = {
                 let mut window_state = userdata.window_state_lock();
                 old_scale_factor = window_state.scale_factor;
                 window_state.scale_factor = new_scale_factor;
 
-                if new_scale_factor == old_scale_factor {
+                // If `WM_GETDPISCALEDSIZE` was handled, `ScaleFactorChanged` has already been
+                // emitted and the suggested rect already has the size the application asked for.
+                let negotiated_inner_size = window_state
+                    .dpi_scaled_size
+                    .take()
+                    .filter(|&(dpi, _)| dpi == new_dpi_x)
+                    .map(|(_, size)| size);
+
+                if new_scale_factor == old_scale_factor && negotiated_inner_size.is_none() {
                     result = ProcResult::Value(0);
                     return;
                 }
 
-                let allow_resize = window_state.fullscreen.is_none()
-                    && !window_state.window_flags().contains(WindowFlags::MAXIMIZED);
-
-                (allow_resize, window_state.window_flags)
+                (allow_dpi_resize(&window_state), window_state.window_flags, negotiated_inner_size)
             };
 
             // New size as suggested by Windows.
@@ -2186,28 +3135,23 @@ unsafe fn public_window_callback_inner(
                 (old_physical_inner_rect.bottom - old_physical_inner_rect.top) as u32,
             );
 
-            // `allow_resize` prevents us from re-applying DPI adjustment to the restored size after
-            // exiting fullscreen (the restored size is already DPI adjusted).
-            let new_physical_inner_size = match allow_resize {
-                // We calculate our own size because the default suggested rect doesn't do a great
-                // job of preserving the window's logical size.
-                true => old_physical_inner_size
-                    .to_logical::<f64>(old_scale_factor)
-                    .to_physical::<u32>(new_scale_factor),
-                false => old_physical_inner_size,
-            };
-
-            let new_inner_size = Arc::new(Mutex::new(new_physical_inner_size));
-            userdata.send_event(Event::WindowEvent {
-                window_id: RootWindowId(WindowId(window)),
-                event: ScaleFactorChanged {
-                    scale_factor: new_scale_factor,
-                    inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_inner_size)),
+            let new_physical_inner_size = match negotiated_inner_size {
+                Some(size) => size,
+                None => {
+                    let suggested_inner_size = dpi_scaled_inner_size(
+                        old_physical_inner_size,
+                        old_scale_factor,
+                        new_scale_factor,
+                        allow_resize,
+                    );
+                    send_scale_factor_changed(
+                        window,
+                        userdata,
+                        new_scale_factor,
+                        suggested_inner_size,
+                    )
                 },
-            });
-
-            let new_physical_inner_size = *new_inner_size.lock().unwrap();
-            drop(new_inner_size);
+            };
 
             let dragging_window: bool;
 
@@ -2349,7 +3293,11 @@ unsafe fn public_window_callback_inner(
 
                 if window_state.current_theme != new_theme {
                     window_state.current_theme = new_theme;
+                    let dwm_config = window_state.dwm_config;
                     drop(window_state);
+                    if let Some(config) = dwm_config {
+                        apply_dwm_config(window, &config, new_theme);
+                    }
                     userdata.send_event(Event::WindowEvent {
                         window_id: RootWindowId(WindowId(window)),
                         event: ThemeChanged(new_theme),
@@ -2369,9 +3317,49 @@ unsafe fn public_window_callback_inner(
                     f.set(WindowFlags::MARKER_RETAIN_STATE_ON_SIZE, wparam != 0)
                 });
                 result = ProcResult::Value(0);
+            } else if msg == SHARE_MSG_ID.get() {
+                userdata.send_event(Event::WindowEvent {
+                    window_id: RootWindowId(WindowId(window)),
+                    event: WindowEvent::ShareCompleted(share::outcome(wparam)),
+                });
+                result = ProcResult::Value(0);
+            } else if msg == HIDE_TO_TRAY_MSG_ID.get() {
+                let mut window_state = userdata.window_state_lock();
+                if !window_state.in_tray {
+                    match tray::add(window, &window_state) {
+                        Ok(()) => {
+                            window_state.in_tray = true;
+                            WindowState::set_window_flags(window_state, window, |f| {
+                                f.set(WindowFlags::VISIBLE, false)
+                            });
+                            userdata.send_event(Event::SurfaceDestroyed(RootWindowId(WindowId(
+                                window,
+                            ))));
+                        },
+                        Err(err) => tracing::warn!("failed to add the tray icon: {err}"),
+                    }
+                }
+                result = ProcResult::Value(0);
+            } else if msg == TRAY_MSG_ID.get() {
+                let window_state = userdata.window_state_lock();
+                if tray::is_activation(lparam) && window_state.in_tray {
+                    // Removes the icon and emits `SurfaceCreated` in `WM_WINDOWPOSCHANGED`.
+                    WindowState::set_window_flags(window_state, window, |f| {
+                        f.set(WindowFlags::VISIBLE, true)
+                    });
+                    unsafe { SetForegroundWindow(window) };
+                    userdata.send_event(Event::TrayActivated(RootWindowId(WindowId(window))));
+                }
+                result = ProcResult::Value(0);
             } else if msg == TASKBAR_CREATED.get() {
                 let window_state = userdata.window_state_lock();
                 unsafe { set_skip_taskbar(window, window_state.skip_taskbar) };
+                // The icons of the tray are lost when Explorer restarts.
+                if window_state.in_tray {
+                    if let Err(err) = tray::add(window, &window_state) {
+                        tracing::warn!("failed to add the tray icon again: {err}");
+                    }
+                }
                 result = ProcResult::DefWindowProc(wparam);
             } else {
                 result = ProcResult::DefWindowProc(wparam);
@@ -2390,6 +3378,93 @@ unsafe fn public_window_callback_inner(
     }
 }
 
+fn hit_test_result_to_ht(hit: HitTestResult) -> isize {
+    let ht = match hit {
+        HitTestResult::Client => HTCLIENT,
+        HitTestResult::Caption => HTCAPTION,
+        HitTestResult::MinimizeButton => HTMINBUTTON,
+        HitTestResult::MaximizeButton => HTMAXBUTTON,
+        HitTestResult::CloseButton => HTCLOSE,
+        HitTestResult::Resize(direction) => match direction {
+            ResizeDirection::East => HTRIGHT,
+            ResizeDirection::North => HTTOP,
+            ResizeDirection::NorthEast => HTTOPRIGHT,
+            ResizeDirection::NorthWest => HTTOPLEFT,
+            ResizeDirection::South => HTBOTTOM,
+            ResizeDirection::SouthEast => HTBOTTOMRIGHT,
+            ResizeDirection::SouthWest => HTBOTTOMLEFT,
+            ResizeDirection::West => HTLEFT,
+        },
+        HitTestResult::Transparent => return HTTRANSPARENT as isize,
+    };
+    ht as isize
+}
+
+/// The `WM_SYSCOMMAND` for clicking a caption button reported by custom hit testing, if any.
+fn caption_button_command(window: HWND, userdata: &WindowData, hit: WPARAM) -> Option<u32> {
+    {
+        let window_state = userdata.window_state_lock();
+        if window_state.hit_test_handler.is_none() && window_state.maximize_button_bounds.is_none()
+        {
+            return None;
+        }
+    }
+
+    match hit as u32 {
+        HTMINBUTTON => Some(SC_MINIMIZE),
+        HTMAXBUTTON if unsafe { IsZoomed(window) } != false.into() => Some(SC_RESTORE),
+        HTMAXBUTTON => Some(SC_MAXIMIZE),
+        HTCLOSE => Some(SC_CLOSE),
+        _ => None,
+    }
+}
+
+/// Whether the window's size should follow DPI changes.
+///
+/// This prevents us from re-applying DPI adjustment to the restored size after exiting fullscreen
+/// (the restored size is already DPI adjusted).
+fn allow_dpi_resize(window_state: &WindowState) -> bool {
+    window_state.fullscreen.is_none()
+        && !window_state.window_flags().contains(WindowFlags::MAXIMIZED)
+}
+
+/// The inner size that keeps the window's logical size across a DPI change.
+///
+/// We calculate our own size because the default suggested rect doesn't do a great job of
+/// preserving the window's logical size.
+fn dpi_scaled_inner_size(
+    old_inner_size: PhysicalSize<u32>,
+    old_scale_factor: f64,
+    new_scale_factor: f64,
+    allow_resize: bool,
+) -> PhysicalSize<u32> {
+    if allow_resize {
+        old_inner_size.to_logical::<f64>(old_scale_factor).to_physical::<u32>(new_scale_factor)
+    } else {
+        old_inner_size
+    }
+}
+
+/// Emits `ScaleFactorChanged` and returns the inner size the application asked for.
+fn send_scale_factor_changed(
+    window: HWND,
+    userdata: &WindowData,
+    scale_factor: f64,
+    suggested_inner_size: PhysicalSize<u32>,
+) -> PhysicalSize<u32> {
+    let new_inner_size = Arc::new(Mutex::new(suggested_inner_size));
+    userdata.send_event(Event::WindowEvent {
+        window_id: RootWindowId(WindowId(window)),
+        event: WindowEvent::ScaleFactorChanged {
+            scale_factor,
+            inner_size_writer: InnerSizeWriter::new(Arc::downgrade(&new_inner_size)),
+        },
+    });
+
+    let new_inner_size = *new_inner_size.lock().unwrap();
+    new_inner_size
+}
+
 unsafe extern "system" fn thread_event_target_callback(
     window: HWND,
     msg: u32,
@@ -2416,6 +3491,11 @@ unsafe extern "system" fn thread_event_target_callback(
     // the git blame and history would be preserved.
     let callback = || match msg {
         WM_NCDESTROY => {
+            notification::remove_all(window);
+            unsafe { WTSUnRegisterSessionNotification(window) };
+            if userdata.display_state_notification != 0 {
+                unsafe { UnregisterPowerSettingNotification(userdata.display_state_notification) };
+            }
             unsafe { super::set_window_long(window, GWL_USERDATA, 0) };
             userdata_removed = true;
             0
@@ -2427,6 +3507,55 @@ unsafe extern "system" fn thread_event_target_callback(
             DefWindowProcW(window, msg, wparam, lparam)
         },
 
+        WM_WTSSESSION_CHANGE => {
+            match wparam as u32 {
+                WTS_SESSION_LOCK => userdata.send_event(Event::SessionLocked),
+                WTS_SESSION_UNLOCK => userdata.send_event(Event::SessionUnlocked),
+                _ => (),
+            }
+            0
+        },
+
+        WM_POWERBROADCAST if wparam as u32 == PBT_POWERSETTINGCHANGE => {
+            // Only `GUID_CONSOLE_DISPLAY_STATE` is registered, which is 0 when the display is off,
+            // 1 when it is on and 2 when it is dimmed.
+            let setting = unsafe { &*(lparam as *const POWERBROADCAST_SETTING) };
+            let display_on = setting.Data[0] != 0;
+            if userdata.display_on.replace(display_on) != display_on {
+                userdata.send_event(if display_on {
+                    Event::DisplayWake
+                } else {
+                    Event::DisplaySleep
+                });
+            }
+            TRUE as LRESULT
+        },
+
+        WM_SETTINGCHANGE if wparam as u32 == SPI_SETWORKAREA => {
+            userdata.send_event(Event::WorkAreaChanged);
+            0
+        },
+
+        // Changing the display settings can switch to another profile, e.g. with the
+        // advanced color settings. Changes in the color management settings aren't broadcast.
+        WM_DISPLAYCHANGE => {
+            userdata.send_event(Event::ColorProfileChanged);
+            userdata.send_event(Event::MonitorsChanged);
+            0
+        },
+
+        // The international settings are reported as "intl".
+        WM_SETTINGCHANGE if unsafe { is_setting(lparam, "intl") } => {
+            let locale = locale();
+            if *userdata.locale.borrow() != locale {
+                *userdata.locale.borrow_mut() = locale.clone();
+                if let Some(locale) = locale {
+                    userdata.send_event(Event::LocaleChanged(locale));
+                }
+            }
+            0
+        },
+
         WM_INPUT_DEVICE_CHANGE => {
             let event = match wparam as u32 {
                 GIDC_ARRIVAL => DeviceEvent::Added,
@@ -2448,6 +3577,22 @@ unsafe extern "system" fn thread_event_target_callback(
             unsafe { DefWindowProcW(window, msg, wparam, lparam) }
         },
 
+        _ if msg == RAW_MOUSE_MSG_ID.get() => {
+            if let Some(mouse_thread) = &userdata.mouse_thread {
+                for sample in mouse_thread.drain() {
+                    handle_raw_mouse(&userdata, sample);
+                }
+            }
+            0
+        },
+
+        _ if msg == NOTIFICATION_MSG_ID.get() => {
+            if let Some((id, event)) = notification::handle_message(lparam) {
+                userdata.send_event(Event::Notification { id, event });
+            }
+            0
+        },
+
         _ if msg == USER_EVENT_MSG_ID.get() => {
             // synthesis a placeholder UserEvent, so that if the callback is
             // re-entered it can be buffered for later delivery. the real
@@ -2475,68 +3620,14 @@ unsafe extern "system" fn thread_event_target_callback(
 }
 
 unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
-    use crate::event::DeviceEvent::{Button, Key, Motion, MouseMotion, MouseWheel};
+    use crate::event::DeviceEvent::Key;
     use crate::event::ElementState::{Pressed, Released};
-    use crate::event::MouseScrollDelta::LineDelta;
 
     let device_id = wrap_device_id(data.header.hDevice as _);
 
     if data.header.dwType == RIM_TYPEMOUSE {
         let mouse = unsafe { data.data.mouse };
-
-        if util::has_flag(mouse.usFlags as u32, MOUSE_MOVE_RELATIVE) {
-            let x = mouse.lLastX as f64;
-            let y = mouse.lLastY as f64;
-
-            if x != 0.0 {
-                userdata.send_event(Event::DeviceEvent {
-                    device_id,
-                    event: Motion { axis: 0, value: x },
-                });
-            }
-
-            if y != 0.0 {
-                userdata.send_event(Event::DeviceEvent {
-                    device_id,
-                    event: Motion { axis: 1, value: y },
-                });
-            }
-
-            if x != 0.0 || y != 0.0 {
-                userdata.send_event(Event::DeviceEvent {
-                    device_id,
-                    event: MouseMotion { delta: (x, y) },
-                });
-            }
-        }
-
-        let button_flags = unsafe { mouse.Anonymous.Anonymous.usButtonFlags };
-        if util::has_flag(button_flags as u32, RI_MOUSE_WHEEL) {
-            let button_data = unsafe { mouse.Anonymous.Anonymous.usButtonData } as i16;
-            let delta = button_data as f32 / WHEEL_DELTA as f32;
-            userdata.send_event(Event::DeviceEvent {
-                device_id,
-                event: MouseWheel { delta: LineDelta(0.0, delta) },
-            });
-        }
-        if util::has_flag(button_flags as u32, RI_MOUSE_HWHEEL) {
-            let button_data = unsafe { mouse.Anonymous.Anonymous.usButtonData } as i16;
-            let delta = -button_data as f32 / WHEEL_DELTA as f32;
-            userdata.send_event(Event::DeviceEvent {
-                device_id,
-                event: MouseWheel { delta: LineDelta(delta, 0.0) },
-            });
-        }
-
-        let button_state = raw_input::get_raw_mouse_button_state(button_flags as u32);
-        for (button, state) in button_state.iter().enumerate() {
-            if let Some(state) = *state {
-                userdata.send_event(Event::DeviceEvent {
-                    device_id,
-                    event: Button { button: button as _, state },
-                });
-            }
-        }
+        handle_raw_mouse(userdata, RawMouseSample::new(data.header.hDevice, &mouse));
     } else if data.header.dwType == RIM_TYPEKEYBOARD {
         let keyboard = unsafe { data.data.keyboard };
 
@@ -2558,6 +3649,53 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
     }
 }
 
+fn handle_raw_mouse(userdata: &ThreadMsgTargetData, sample: RawMouseSample) {
+    use crate::event::DeviceEvent::{Button, Motion, MouseMotion, MouseWheel};
+    use crate::event::MouseScrollDelta::LineDelta;
+
+    let device_id = wrap_device_id(sample.device as _);
+
+    let x = sample.delta.0 as f64;
+    let y = sample.delta.1 as f64;
+
+    if x != 0.0 {
+        userdata.send_event(Event::DeviceEvent { device_id, event: Motion { axis: 0, value: x } });
+    }
+
+    if y != 0.0 {
+        userdata.send_event(Event::DeviceEvent { device_id, event: Motion { axis: 1, value: y } });
+    }
+
+    if x != 0.0 || y != 0.0 {
+        userdata.send_event(Event::DeviceEvent { device_id, event: MouseMotion { delta: (x, y) } });
+    }
+
+    if util::has_flag(sample.button_flags, RI_MOUSE_WHEEL) {
+        let delta = sample.button_data as f32 / WHEEL_DELTA as f32;
+        userdata.send_event(Event::DeviceEvent {
+            device_id,
+            event: MouseWheel { delta: LineDelta(0.0, delta) },
+        });
+    }
+    if util::has_flag(sample.button_flags, RI_MOUSE_HWHEEL) {
+        let delta = -sample.button_data as f32 / WHEEL_DELTA as f32;
+        userdata.send_event(Event::DeviceEvent {
+            device_id,
+            event: MouseWheel { delta: LineDelta(delta, 0.0) },
+        });
+    }
+
+    let button_state = raw_input::get_raw_mouse_button_state(sample.button_flags);
+    for (button, state) in button_state.iter().enumerate() {
+        if let Some(state) = *state {
+            userdata.send_event(Event::DeviceEvent {
+                device_id,
+                event: Button { button: button as _, state },
+            });
+        }
+    }
+}
+
 enum PointerMoveKind {
     /// Pointer enterd to the window.
     Enter,
@@ -2567,6 +3705,59 @@ enum PointerMoveKind {
     None,
 }
 
+/// Emit the crossing events of the windows the cursor is moved over while the mouse is captured by
+/// `window`, which receives all mouse messages in the meantime.
+fn update_drag_target(window: HWND, userdata: &WindowData, hit: Option<HWND>) {
+    use crate::event::WindowEvent::{CursorEntered, CursorLeft};
+
+    let target = hit.filter(|&hit| hit != window);
+    let (previous, in_window) = {
+        let mut w = userdata.window_state_lock();
+        let previous = mem::replace(&mut w.mouse.drag_target, target);
+        (previous, w.mouse.cursor_flags().contains(CursorFlags::IN_WINDOW))
+    };
+    if previous == target {
+        return;
+    }
+
+    if let Some(previous) = previous {
+        userdata.send_event(Event::WindowEvent {
+            window_id: RootWindowId(WindowId(previous)),
+            event: CursorLeft { device_id: DEVICE_ID },
+        });
+    }
+    let entered = match target {
+        Some(target) => Some(target),
+        // Back over the capturing window, which the other window was on top of, so it wasn't
+        // left through `WM_MOUSEMOVE`.
+        None if in_window && hit == Some(window) => Some(window),
+        None => None,
+    };
+    if let Some(entered) = entered {
+        userdata.send_event(Event::WindowEvent {
+            window_id: RootWindowId(WindowId(entered)),
+            event: CursorEntered { device_id: DEVICE_ID },
+        });
+    }
+}
+
+/// Returns the window of this event loop at the given point in screen coordinates.
+unsafe fn window_from_point(point: POINT) -> Option<HWND> {
+    let window_proc: unsafe extern "system" fn(HWND, u32, WPARAM, LPARAM) -> LRESULT =
+        public_window_callback;
+    let thread_id = unsafe { GetCurrentThreadId() };
+    let mut hwnd = unsafe { WindowFromPoint(point) };
+    while hwnd != 0 {
+        let is_ours = unsafe { GetWindowThreadProcessId(hwnd, ptr::null_mut()) } == thread_id
+            && unsafe { super::get_window_long(hwnd, GWLP_WNDPROC) } == window_proc as isize;
+        if is_ours {
+            return Some(hwnd);
+        }
+        hwnd = unsafe { GetAncestor(hwnd, GA_PARENT) };
+    }
+    None
+}
+
 fn get_pointer_move_kind(
     window: HWND,
     mouse_was_inside_window: bool,
@@ -2,6 +2,7 @@ use std::any::Any;
 use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use std::{mem, panic};
@@ -9,7 +10,7 @@ use std::{mem, panic};
 use windows_sys::Win32::Foundation::HWND;
 
 use crate::dpi::PhysicalSize;
-use crate::event::{Event, InnerSizeWriter, StartCause, WindowEvent};
+use crate::event::{CloseResponse, Event, InnerSizeWriter, StartCause, WindowEvent};
 use crate::platform_impl::platform::event_loop::{WindowData, GWL_USERDATA};
 use crate::platform_impl::platform::get_window_long;
 use crate::window::WindowId;
@@ -35,6 +36,10 @@ pub(crate) struct EventLoopRunner<T: 'static> {
     last_events_cleared: Cell<Instant>,
     event_handler: EventHandler<T>,
     event_buffer: RefCell<VecDeque<BufferedEvent<T>>>,
+    /// The response to the current `WM_QUERYENDSESSION`.
+    end_session_cancelled: Cell<Option<bool>>,
+    /// Whether `Terminating` was emitted for `WM_ENDSESSION`.
+    session_ended: Cell<bool>,
 
     panic_error: Cell<Option<PanicError>>,
 }
@@ -72,6 +77,8 @@ impl<T> EventLoopRunner<T> {
             last_events_cleared: Cell::new(Instant::now()),
             event_handler: Cell::new(None),
             event_buffer: RefCell::new(VecDeque::new()),
+            end_session_cancelled: Cell::new(None),
+            session_ended: Cell::new(false),
         }
     }
 
@@ -114,12 +121,16 @@ impl<T> EventLoopRunner<T> {
             last_events_cleared: _,
             event_handler,
             event_buffer: _,
+            end_session_cancelled,
+            session_ended,
         } = self;
         interrupt_msg_dispatch.set(false);
         runner_state.set(RunnerState::Uninitialized);
         panic_error.set(None);
         exit.set(None);
         event_handler.set(None);
+        end_session_cancelled.set(None);
+        session_ended.set(false);
     }
 }
 
@@ -205,6 +216,21 @@ impl<T> EventLoopRunner<T> {
         self.move_state_to(RunnerState::HandlingMainEvents);
     }
 
+    /// Iterate the event loop from a modal loop of the system, which doesn't return to it until
+    /// the user stops moving or resizing a window or closes a menu, when the control flow asks to
+    /// be woken up.
+    pub(crate) fn wakeup_in_modal_loop(&self) {
+        let wake = match self.control_flow() {
+            ControlFlow::Poll => true,
+            ControlFlow::WaitUntil(requested_resume) => Instant::now() >= requested_resume,
+            ControlFlow::Wait => false,
+        };
+        if wake && self.exit.get().is_none() && !self.should_buffer() {
+            self.prepare_wait();
+            self.wakeup();
+        }
+    }
+
     pub(crate) fn send_event(&self, event: Event<T>) {
         if let Event::WindowEvent { event: WindowEvent::RedrawRequested, .. } = event {
             self.call_event_handler(event);
@@ -222,6 +248,33 @@ impl<T> EventLoopRunner<T> {
         }
     }
 
+    /// Emit `CloseRequestedByOS` for `WM_QUERYENDSESSION`, returning whether it was cancelled.
+    ///
+    /// The message is sent to every top-level window, but the application is only asked once
+    /// until the session ends or the end is cancelled with `WM_ENDSESSION`.
+    pub(crate) fn query_end_session(&self) -> bool {
+        if let Some(cancelled) = self.end_session_cancelled.get() {
+            return cancelled;
+        }
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.send_event(Event::CloseRequestedByOS {
+            respond: CloseResponse::new(Arc::downgrade(&cancelled)),
+        });
+        let cancelled = cancelled.load(Ordering::Relaxed);
+        self.end_session_cancelled.set(Some(cancelled));
+        cancelled
+    }
+
+    /// Emit `Terminating` for `WM_ENDSESSION` if the session ends, which is also sent to every
+    /// top-level window.
+    pub(crate) fn end_session(&self, ending: bool) {
+        self.end_session_cancelled.set(None);
+        if ending && !self.session_ended.replace(true) {
+            self.send_event(Event::Terminating);
+        }
+    }
+
     pub(crate) fn loop_destroyed(&self) {
         self.move_state_to(RunnerState::Destroyed);
     }
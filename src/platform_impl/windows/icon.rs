@@ -1,4 +1,5 @@
 use std::ffi::c_void;
+use std::hash::{Hash, Hasher};
 use std::path::Path;
 use std::sync::Arc;
 use std::{fmt, io, mem};
@@ -14,7 +15,7 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
     HICON, ICONINFO, ICON_BIG, ICON_SMALL, IMAGE_ICON, LR_DEFAULTSIZE, LR_LOADFROMFILE, WM_SETICON,
 };
 
-use crate::cursor::CursorImage;
+use crate::cursor::{self, CursorImage};
 use crate::dpi::PhysicalSize;
 use crate::icon::*;
 
@@ -182,7 +183,18 @@ pub enum WinCursor {
 }
 
 impl WinCursor {
-    pub(crate) fn new(image: &CursorImage) -> Result<Self, io::Error> {
+    pub(crate) fn new(images: &[CursorImage]) -> Result<Self, io::Error> {
+        let mut cursor = RaiiCursor { handles: Vec::with_capacity(images.len()) };
+        for image in images {
+            // On failure, the cursors created so far are destroyed along with `cursor`.
+            let handle = Self::create_handle(image)?;
+            cursor.handles.push((image.scale_factor, handle));
+        }
+
+        Ok(Self::Cursor(Arc::new(cursor)))
+    }
+
+    fn create_handle(image: &CursorImage) -> Result<HCURSOR, io::Error> {
         let mut bgra = image.rgba.clone();
         bgra.chunks_exact_mut(4).for_each(|chunk| chunk.swap(0, 2));
 
@@ -227,24 +239,46 @@ impl WinCursor {
                 return Err(io::Error::last_os_error());
             }
 
-            Ok(Self::Cursor(Arc::new(RaiiCursor { handle })))
+            Ok(handle)
         }
     }
 }
 
-#[derive(Debug, Hash, Eq, PartialEq)]
+#[derive(Debug)]
 pub struct RaiiCursor {
-    handle: HCURSOR,
+    /// A cursor for each image along with its scale factor, sorted by scale factor.
+    handles: Vec<(f64, HCURSOR)>,
 }
 
 impl Drop for RaiiCursor {
     fn drop(&mut self) {
-        unsafe { DestroyCursor(self.handle) };
+        for &(_, handle) in &self.handles {
+            unsafe { DestroyCursor(handle) };
+        }
     }
 }
 
+impl Hash for RaiiCursor {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.handles.iter().for_each(|(_, handle)| handle.hash(state));
+    }
+}
+
+impl PartialEq for RaiiCursor {
+    fn eq(&self, other: &Self) -> bool {
+        // Handles are unique, so the scale factors don't need to be compared.
+        let handles = |cursor: &Self| -> Vec<HCURSOR> {
+            cursor.handles.iter().map(|&(_, handle)| handle).collect()
+        };
+        handles(self) == handles(other)
+    }
+}
+
+impl Eq for RaiiCursor {}
+
 impl RaiiCursor {
-    pub fn as_raw_handle(&self) -> HICON {
-        self.handle
+    /// Returns the cursor for the image fitting the scale factor best.
+    pub fn as_raw_handle(&self, scale_factor: f64) -> HICON {
+        cursor::for_scale_factor(&self.handles, scale_factor, |(scale_factor, _)| *scale_factor).1
     }
 }
@@ -1,18 +1,23 @@
 use std::ffi::{c_void, OsString};
+use std::mem;
+use std::ops::Range;
 use std::os::windows::prelude::OsStringExt;
-use std::ptr::null_mut;
+use std::ptr::{self, null_mut};
 
 use windows_sys::Win32::Foundation::{POINT, RECT};
 use windows_sys::Win32::Globalization::HIMC;
 use windows_sys::Win32::UI::Input::Ime::{
     ImmAssociateContextEx, ImmGetCompositionStringW, ImmGetContext, ImmReleaseContext,
-    ImmSetCandidateWindow, ImmSetCompositionWindow, ATTR_TARGET_CONVERTED,
-    ATTR_TARGET_NOTCONVERTED, CANDIDATEFORM, CFS_EXCLUDE, CFS_POINT, COMPOSITIONFORM, GCS_COMPATTR,
-    GCS_COMPSTR, GCS_CURSORPOS, GCS_RESULTSTR, IACE_CHILDREN, IACE_DEFAULT,
+    ImmSetCandidateWindow, ImmSetCompositionWindow, ATTR_CONVERTED, ATTR_FIXEDCONVERTED,
+    ATTR_TARGET_CONVERTED, ATTR_TARGET_NOTCONVERTED, CANDIDATEFORM, CFS_EXCLUDE, CFS_POINT,
+    COMPOSITIONFORM, GCS_COMPATTR, GCS_COMPSTR, GCS_CURSORPOS, GCS_RESULTSTR, IACE_CHILDREN,
+    IACE_DEFAULT, RECONVERTSTRING,
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_IMMENABLED};
 
 use crate::dpi::{Position, Size};
+use crate::event::{PreeditSpan, PreeditUnderline};
+use crate::ime::SurroundingText;
 use crate::platform::windows::HWND;
 
 pub struct ImeContext {
@@ -28,10 +33,19 @@ impl ImeContext {
 
     pub unsafe fn get_composing_text_and_cursor(
         &self,
-    ) -> Option<(String, Option<usize>, Option<usize>)> {
+    ) -> Option<(String, Option<usize>, Option<usize>, Vec<PreeditSpan>)> {
         let text = unsafe { self.get_composition_string(GCS_COMPSTR) }?;
         let attrs = unsafe { self.get_composition_data(GCS_COMPATTR) }.unwrap_or_default();
 
+        let spans = PreeditSpan::from_chars(
+            &text,
+            attrs.iter().map(|&attr| match attr as u32 {
+                ATTR_TARGET_CONVERTED | ATTR_TARGET_NOTCONVERTED => (PreeditUnderline::Thick, true),
+                ATTR_CONVERTED | ATTR_FIXEDCONVERTED => (PreeditUnderline::Thin, false),
+                _ => (PreeditUnderline::Dotted, false),
+            }),
+        );
+
         let mut first = None;
         let mut last = None;
         let mut boundary_before_char = 0;
@@ -59,7 +73,7 @@ impl ImeContext {
             last = cursor;
         }
 
-        Some((text, first, last))
+        Some((text, first, last, spans))
     }
 
     pub unsafe fn get_composed_text(&self) -> Option<String> {
@@ -154,3 +168,56 @@ impl Drop for ImeContext {
         unsafe { ImmReleaseContext(self.hwnd, self.himc) };
     }
 }
+
+/// Answer `IMR_RECONVERTSTRING` and `IMR_DOCUMENTFEED` requests with the surrounding text, and the
+/// bytes of it that are composed. Returns the size of the structure, which is first requested with
+/// a null `buffer`, or 0 if the `buffer` is too small.
+pub unsafe fn write_reconvert_string(
+    text: &SurroundingText,
+    composition: Range<usize>,
+    buffer: *mut RECONVERTSTRING,
+) -> usize {
+    let wide: Vec<u16> = text.text().encode_utf16().collect();
+    let header_size = mem::size_of::<RECONVERTSTRING>();
+    let size = header_size + wide.len() * mem::size_of::<u16>();
+    if buffer.is_null() {
+        return size;
+    }
+    if (unsafe { ptr::read_unaligned(buffer) }.dwSize as usize) < size {
+        return 0;
+    }
+
+    // The lengths are in characters, but the offsets in bytes.
+    let start = text.utf16_offset(composition.start);
+    let len = text.utf16_offset(composition.end) - start;
+    let offset = (start * mem::size_of::<u16>()) as u32;
+    let reconvert_string = RECONVERTSTRING {
+        dwSize: size as u32,
+        dwVersion: 0,
+        dwStrLen: wide.len() as u32,
+        dwStrOffset: header_size as u32,
+        dwCompStrLen: len as u32,
+        dwCompStrOffset: offset,
+        dwTargetStrLen: len as u32,
+        dwTargetStrOffset: offset,
+    };
+    unsafe {
+        ptr::write_unaligned(buffer, reconvert_string);
+        let string = buffer.cast::<u8>().add(header_size);
+        ptr::copy_nonoverlapping(wide.as_ptr().cast::<u8>(), string, size - header_size);
+    }
+
+    size
+}
+
+/// The bytes of the surrounding text which the IME chose to reconvert in
+/// `IMR_CONFIRMRECONVERTSTRING`.
+pub unsafe fn reconvert_string_range(
+    text: &SurroundingText,
+    buffer: *const RECONVERTSTRING,
+) -> Range<usize> {
+    let reconvert_string = unsafe { ptr::read_unaligned(buffer) };
+    let start = reconvert_string.dwCompStrOffset as usize / mem::size_of::<u16>();
+    let end = start + reconvert_string.dwCompStrLen as usize;
+    text.byte_offset(start)..text.byte_offset(end)
+}
@@ -5,20 +5,21 @@ use windows_sys::Win32::Foundation::{HANDLE, HWND};
 use windows_sys::Win32::UI::WindowsAndMessaging::{HMENU, WINDOW_LONG_PTR_INDEX};
 
 pub(crate) use self::event_loop::{
-    ActiveEventLoop, EventLoop, EventLoopProxy, OwnedDisplayHandle,
+    ActiveEventLoop, EventLoop, EventLoopProxy, EventLoopWaker, OwnedDisplayHandle,
     PlatformSpecificEventLoopAttributes,
 };
 pub(crate) use self::icon::{SelectedCursor, WinIcon};
 pub(crate) use self::keyboard::{physicalkey_to_scancode, scancode_to_physicalkey};
 pub(crate) use self::monitor::{MonitorHandle, VideoModeHandle};
-pub(crate) use self::window::Window;
+pub(crate) use self::notification::Notification as PlatformNotification;
+pub(crate) use self::window::{MainThreadWindow, Window};
 
 pub(crate) use self::icon::WinCursor as PlatformCustomCursor;
 pub use self::icon::WinIcon as PlatformIcon;
 pub(crate) use crate::cursor::OnlyCursorImageSource as PlatformCustomCursorSource;
 use crate::platform_impl::Fullscreen;
 
-use crate::event::DeviceId as RootDeviceId;
+use crate::event::{DeviceId as RootDeviceId, SeatId};
 use crate::icon::Icon;
 use crate::keyboard::Key;
 use crate::platform::windows::{BackdropType, Color, CornerPreference};
@@ -72,6 +73,10 @@ impl DeviceId {
     pub const unsafe fn dummy() -> Self {
         DeviceId(0)
     }
+
+    pub fn seat_id(&self) -> Option<SeatId> {
+        None
+    }
 }
 
 impl DeviceId {
@@ -185,6 +190,7 @@ unsafe fn set_window_long(hwnd: HWND, nindex: WINDOW_LONG_PTR_INDEX, dwnewlong:
 mod util;
 mod dark_mode;
 mod definitions;
+mod dialog;
 mod dpi;
 mod drop_handler;
 mod event_loop;
@@ -193,6 +199,11 @@ mod ime;
 mod keyboard;
 mod keyboard_layout;
 mod monitor;
+mod mouse_thread;
+mod notification;
 mod raw_input;
+mod share;
+mod timer;
+mod tray;
 mod window;
 mod window_state;
@@ -1,18 +1,22 @@
 use std::collections::{BTreeSet, VecDeque};
 use std::hash::Hash;
+use std::ops::RangeInclusive;
 use std::{io, mem, ptr};
 
 use windows_sys::Win32::Foundation::{BOOL, HWND, LPARAM, POINT, RECT};
 use windows_sys::Win32::Graphics::Gdi::{
-    EnumDisplayMonitors, EnumDisplaySettingsExW, GetMonitorInfoW, MonitorFromPoint,
-    MonitorFromWindow, DEVMODEW, DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH,
+    CreateDCW, DeleteDC, EnumDisplayDevicesW, EnumDisplayMonitors, EnumDisplaySettingsExW,
+    GetMonitorInfoW, MonitorFromPoint, MonitorFromWindow, DEVMODEW, DISPLAY_DEVICEW,
+    DISPLAY_DEVICE_ACTIVE, DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH,
     ENUM_CURRENT_SETTINGS, HDC, HMONITOR, MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST,
     MONITOR_DEFAULTTOPRIMARY,
 };
 
+use windows_sys::Win32::UI::ColorSystem::GetICMProfileW;
+
 use super::util::decode_wide;
 use crate::dpi::{PhysicalPosition, PhysicalSize};
-use crate::monitor::VideoModeHandle as RootVideoModeHandle;
+use crate::monitor::{Rect, VideoModeHandle as RootVideoModeHandle};
 use crate::platform_impl::platform::dpi::{dpi_to_scale_factor, get_monitor_dpi};
 use crate::platform_impl::platform::util::has_flag;
 use crate::platform_impl::platform::window::Window;
@@ -71,6 +75,10 @@ impl VideoModeHandle {
         self.refresh_rate_millihertz
     }
 
+    pub fn refresh_rate_range(&self) -> RangeInclusive<u32> {
+        self.refresh_rate_millihertz..=self.refresh_rate_millihertz
+    }
+
     pub fn monitor(&self) -> MonitorHandle {
         self.monitor.clone()
     }
@@ -192,6 +200,34 @@ impl MonitorHandle {
         }
     }
 
+    /// The variable refresh rate support of a monitor is only exposed by the driver.
+    #[inline]
+    pub fn supports_variable_refresh(&self) -> bool {
+        false
+    }
+
+    /// In clone mode, the displays showing the same content are attached to one output of the
+    /// adapter, which is a single monitor.
+    pub fn is_mirrored(&self) -> bool {
+        let Ok(monitor_info) = get_monitor_info(self.0) else {
+            return false;
+        };
+        let device_name = monitor_info.szDevice.as_ptr();
+
+        let mut active = 0;
+        for index in 0.. {
+            let mut device: DISPLAY_DEVICEW = unsafe { mem::zeroed() };
+            device.cb = mem::size_of_val(&device) as u32;
+            if unsafe { EnumDisplayDevicesW(device_name, index, &mut device, 0) } == false.into() {
+                break;
+            }
+            if has_flag(device.StateFlags, DISPLAY_DEVICE_ACTIVE) {
+                active += 1;
+            }
+        }
+        active > 1
+    }
+
     #[inline]
     pub fn position(&self) -> PhysicalPosition<i32> {
         get_monitor_info(self.0)
@@ -202,6 +238,44 @@ impl MonitorHandle {
             .unwrap_or(PhysicalPosition { x: 0, y: 0 })
     }
 
+    #[inline]
+    pub fn work_area(&self) -> Rect {
+        match get_monitor_info(self.0) {
+            Ok(info) => {
+                let rc_work = info.monitorInfo.rcWork;
+                Rect::new(
+                    PhysicalPosition::new(rc_work.left, rc_work.top),
+                    PhysicalSize::new(
+                        (rc_work.right - rc_work.left) as u32,
+                        (rc_work.bottom - rc_work.top) as u32,
+                    ),
+                )
+            },
+            Err(_) => Rect::new(self.position(), self.size()),
+        }
+    }
+
+    pub fn icc_profile(&self) -> Option<Vec<u8>> {
+        let monitor_info = get_monitor_info(self.0).ok()?;
+        let device = monitor_info.szDevice.as_ptr();
+        let hdc = unsafe { CreateDCW(device, device, ptr::null(), ptr::null()) };
+        if hdc == 0 {
+            return None;
+        }
+
+        // The first call returns the length of the path.
+        let mut len = 0;
+        unsafe { GetICMProfileW(hdc, &mut len, ptr::null_mut()) };
+        let mut path = vec![0; len as usize];
+        let status = unsafe { GetICMProfileW(hdc, &mut len, path.as_mut_ptr()) };
+        unsafe { DeleteDC(hdc) };
+        if status == false.into() {
+            return None;
+        }
+
+        std::fs::read(decode_wide(&path)).ok()
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         dpi_to_scale_factor(get_monitor_dpi(self.0).unwrap_or(96))
@@ -0,0 +1,250 @@
+//! Reads raw mouse input on a dedicated thread.
+//!
+//! High polling rate mice can flood the event loop thread with `WM_INPUT` messages. When enabled
+//! with `RawInputConfig::mouse_thread`, mouse input is instead registered for a message-only window
+//! owned by a separate thread, which queues the samples and wakes up the event loop once per batch.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::{mem, ptr, thread};
+
+use windows_sys::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows_sys::Win32::System::Threading::GetCurrentProcessId;
+use windows_sys::Win32::UI::Input::RIM_TYPEMOUSE;
+use windows_sys::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DispatchMessageW, GetForegroundWindow, GetMessageW,
+    GetWindowThreadProcessId, PostMessageW, PostQuitMessage, RegisterClassExW, GWL_USERDATA,
+    HWND_MESSAGE, MSG, WM_CLOSE, WM_DESTROY, WM_INPUT, WM_INPUT_DEVICE_CHANGE, WM_NCDESTROY,
+    WNDCLASSEXW,
+};
+
+use super::event_loop::LazyMessageId;
+use super::raw_input::{self, RawInputDevices, RawMouseSample};
+use crate::event_loop::DeviceEvents;
+use crate::platform_impl::platform::util;
+use crate::utils::Lazy;
+
+// Message sent to the event loop's thread target window when samples were queued.
+// WPARAM and LPARAM are unused.
+pub(crate) static RAW_MOUSE_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::RawMouseMsg\0");
+// Message sent to the mouse thread to change the raw input registration.
+// WPARAM is the `DeviceEvents` filter, LPARAM is unused.
+static LISTEN_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::RawMouseListenMsg\0");
+static MOUSE_THREAD_WINDOW_CLASS: Lazy<Vec<u16>> =
+    Lazy::new(|| util::encode_wide("Winit Raw Mouse Target"));
+
+struct Shared {
+    samples: Mutex<VecDeque<RawMouseSample>>,
+    wakeup_pending: AtomicBool,
+    focused_only: AtomicBool,
+    coalesce: bool,
+    event_target: HWND,
+}
+
+impl Shared {
+    fn push(&self, sample: RawMouseSample) {
+        {
+            let mut samples = self.samples.lock().unwrap();
+            let coalesced =
+                self.coalesce && samples.back_mut().map_or(false, |last| last.coalesce(&sample));
+            if !coalesced {
+                samples.push_back(sample);
+            }
+        }
+
+        // Only wake up the event loop once per batch.
+        if !self.wakeup_pending.swap(true, Ordering::AcqRel) {
+            unsafe { PostMessageW(self.event_target, RAW_MOUSE_MSG_ID.get(), 0, 0) };
+        }
+    }
+}
+
+/// Handle to the raw mouse input thread. The thread exits when this is dropped.
+pub(crate) struct MouseThread {
+    window: HWND,
+    shared: Arc<Shared>,
+}
+
+impl MouseThread {
+    /// Spawn the thread, delivering samples to `event_target`.
+    pub fn spawn(event_target: HWND, coalesce: bool) -> Option<Self> {
+        let shared = Arc::new(Shared {
+            samples: Mutex::new(VecDeque::new()),
+            wakeup_pending: AtomicBool::new(false),
+            focused_only: AtomicBool::new(false),
+            coalesce,
+            event_target,
+        });
+
+        let (sender, receiver) = mpsc::channel();
+        let thread_shared = shared.clone();
+        thread::Builder::new()
+            .name("winit raw mouse input".to_owned())
+            .spawn(move || {
+                let window = create_mouse_thread_window(thread_shared);
+                let _ = sender.send(window);
+                if window == 0 {
+                    return;
+                }
+
+                unsafe {
+                    let mut msg: MSG = mem::zeroed();
+                    while GetMessageW(&mut msg, 0, 0, 0) > 0 {
+                        DispatchMessageW(&msg);
+                    }
+                }
+            })
+            .ok()?;
+
+        match receiver.recv() {
+            Ok(window) if window != 0 => Some(Self { window, shared }),
+            _ => None,
+        }
+    }
+
+    pub fn window(&self) -> HWND {
+        self.window
+    }
+
+    /// Take all samples queued since the last call.
+    pub fn drain(&self) -> VecDeque<RawMouseSample> {
+        self.shared.wakeup_pending.store(false, Ordering::Release);
+        mem::take(&mut *self.shared.samples.lock().unwrap())
+    }
+}
+
+impl Drop for MouseThread {
+    fn drop(&mut self) {
+        unsafe { PostMessageW(self.window, WM_CLOSE, 0, 0) };
+    }
+}
+
+/// Change the raw input registration of the mouse thread owning `window`.
+pub(crate) fn listen_device_events(window: HWND, filter: DeviceEvents) {
+    let filter = match filter {
+        DeviceEvents::Always => 0,
+        DeviceEvents::WhenFocused => 1,
+        DeviceEvents::Never => 2,
+    };
+    unsafe { PostMessageW(window, LISTEN_MSG_ID.get(), filter, 0) };
+}
+
+fn create_mouse_thread_window(shared: Arc<Shared>) -> HWND {
+    unsafe {
+        let class = WNDCLASSEXW {
+            cbSize: mem::size_of::<WNDCLASSEXW>() as u32,
+            style: 0,
+            lpfnWndProc: Some(mouse_thread_callback),
+            cbClsExtra: 0,
+            cbWndExtra: 0,
+            hInstance: util::get_instance_handle(),
+            hIcon: 0,
+            hCursor: 0,
+            hbrBackground: 0,
+            lpszMenuName: ptr::null(),
+            lpszClassName: MOUSE_THREAD_WINDOW_CLASS.as_ptr(),
+            hIconSm: 0,
+        };
+
+        RegisterClassExW(&class);
+
+        let window = CreateWindowExW(
+            0,
+            MOUSE_THREAD_WINDOW_CLASS.as_ptr(),
+            ptr::null(),
+            0,
+            0,
+            0,
+            0,
+            0,
+            HWND_MESSAGE,
+            0,
+            util::get_instance_handle(),
+            ptr::null(),
+        );
+
+        if window != 0 {
+            let shared = Arc::into_raw(shared);
+            super::set_window_long(window, GWL_USERDATA, shared as isize);
+        }
+
+        window
+    }
+}
+
+/// Whether the foreground window belongs to this process.
+fn process_is_foreground() -> bool {
+    unsafe {
+        let mut process_id = 0;
+        GetWindowThreadProcessId(GetForegroundWindow(), &mut process_id);
+        process_id == GetCurrentProcessId()
+    }
+}
+
+unsafe extern "system" fn mouse_thread_callback(
+    window: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    let shared_ptr = unsafe { super::get_window_long(window, GWL_USERDATA) } as *const Shared;
+    if shared_ptr.is_null() {
+        return unsafe { DefWindowProcW(window, msg, wparam, lparam) };
+    }
+    let shared = unsafe { &*shared_ptr };
+
+    match msg {
+        WM_INPUT => {
+            if let Some(data) = raw_input::get_raw_input_data(lparam as _) {
+                let ignored =
+                    shared.focused_only.load(Ordering::Relaxed) && !process_is_foreground();
+                if data.header.dwType == RIM_TYPEMOUSE && !ignored {
+                    let mouse = unsafe { data.data.mouse };
+                    shared.push(RawMouseSample::new(data.header.hDevice, &mouse));
+                }
+            }
+
+            unsafe { DefWindowProcW(window, msg, wparam, lparam) }
+        },
+
+        // Hotplug notifications are handled by the event loop as usual.
+        WM_INPUT_DEVICE_CHANGE => {
+            unsafe { PostMessageW(shared.event_target, msg, wparam, lparam) };
+            0
+        },
+
+        _ if msg == LISTEN_MSG_ID.get() => {
+            // Raw input is only delivered to the foreground thread without `RIDEV_INPUTSINK`, which
+            // this thread never is, so focus is checked manually instead.
+            let filter = match wparam {
+                0 => DeviceEvents::Always,
+                1 => DeviceEvents::WhenFocused,
+                _ => DeviceEvents::Never,
+            };
+            shared.focused_only.store(filter == DeviceEvents::WhenFocused, Ordering::Relaxed);
+            let filter = match filter {
+                DeviceEvents::WhenFocused => DeviceEvents::Always,
+                filter => filter,
+            };
+            raw_input::register_for_raw_input(window, filter, RawInputDevices {
+                mouse: true,
+                keyboard: false,
+            });
+            0
+        },
+
+        WM_DESTROY => {
+            unsafe { PostQuitMessage(0) };
+            0
+        },
+
+        WM_NCDESTROY => {
+            unsafe { super::set_window_long(window, GWL_USERDATA, 0) };
+            drop(unsafe { Arc::from_raw(shared_ptr) });
+            unsafe { DefWindowProcW(window, msg, wparam, lparam) }
+        },
+
+        _ => unsafe { DefWindowProcW(window, msg, wparam, lparam) },
+    }
+}
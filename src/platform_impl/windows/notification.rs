@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::{io, mem};
+
+use windows_sys::Win32::Foundation::{HWND, LPARAM};
+use windows_sys::Win32::UI::Shell::{
+    Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_MESSAGE, NIIF_INFO, NIIF_NOSOUND, NIM_ADD,
+    NIM_DELETE, NIM_MODIFY, NIM_SETVERSION, NIN_BALLOONTIMEOUT, NIN_BALLOONUSERCLICK,
+    NOTIFYICONDATAW, NOTIFYICON_VERSION_4,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{LoadIconW, IDI_APPLICATION};
+
+use super::event_loop::LazyMessageId;
+use super::{hiword, loword};
+use crate::error::ExternalError;
+use crate::notification::{NotificationAttributes, NotificationEvent, NotificationId};
+use crate::utils::Lazy;
+
+// Message sent by the shell to the thread event target window when the user interacts with a
+// notification. The low word of LPARAM contains the `NIN_*` event and the high word the `uID`
+// of the notification icon.
+pub(crate) static NOTIFICATION_MSG_ID: LazyMessageId =
+    LazyMessageId::new("Winit::NotificationMsg\0");
+
+/// The notification icons which are currently shown, by their `uID`.
+static ACTIVE: Lazy<Mutex<HashMap<u32, (HWND, NotificationId)>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A balloon notification shown from a notification area icon, which Windows 10 and later
+/// present as a toast.
+///
+/// The icon is only added for the lifetime of the notification.
+#[derive(Debug)]
+pub struct Notification {
+    id: NotificationId,
+    uid: u32,
+}
+
+impl Notification {
+    pub fn new(
+        window: HWND,
+        id: NotificationId,
+        attributes: NotificationAttributes,
+    ) -> Result<Self, ExternalError> {
+        // The shell reports the `uID` in the high word of LPARAM, so only 16 bits are usable.
+        let uid = (id.into_raw() & 0xffff) as u32;
+
+        let mut data = icon_data(window, uid);
+        data.uFlags = NIF_MESSAGE | NIF_ICON;
+        data.uCallbackMessage = NOTIFICATION_MSG_ID.get();
+        data.hIcon = unsafe { LoadIconW(0, IDI_APPLICATION) };
+        if unsafe { Shell_NotifyIconW(NIM_ADD, &data) } == false.into() {
+            return Err(ExternalError::Os(os_error!(io::Error::last_os_error())));
+        }
+
+        let notification = Self { id, uid };
+        ACTIVE.lock().unwrap().insert(uid, (window, id));
+
+        data.Anonymous.uVersion = NOTIFYICON_VERSION_4;
+        if unsafe { Shell_NotifyIconW(NIM_SETVERSION, &data) } == false.into() {
+            let error = io::Error::last_os_error();
+            notification.close();
+            return Err(ExternalError::Os(os_error!(error)));
+        }
+
+        data.uFlags = NIF_INFO;
+        copy_truncated(&mut data.szInfoTitle, &attributes.title);
+        copy_truncated(&mut data.szInfo, &attributes.body);
+        data.dwInfoFlags = if attributes.silent { NIIF_INFO | NIIF_NOSOUND } else { NIIF_INFO };
+        if unsafe { Shell_NotifyIconW(NIM_MODIFY, &data) } == false.into() {
+            let error = io::Error::last_os_error();
+            notification.close();
+            return Err(ExternalError::Os(os_error!(error)));
+        }
+
+        Ok(notification)
+    }
+
+    pub fn close(&self) {
+        // The `uID` might have been reused by a newer notification once this one was dismissed.
+        let active = ACTIVE.lock().unwrap().get(&self.uid).map(|(_, id)| *id);
+        if active == Some(self.id) {
+            remove(self.uid);
+        }
+    }
+}
+
+/// Translates a [`NOTIFICATION_MSG_ID`] message, removing the icon of the notification once
+/// the user interacted with it.
+pub(crate) fn handle_message(lparam: LPARAM) -> Option<(NotificationId, NotificationEvent)> {
+    let event = match loword(lparam as u32) as u32 {
+        NIN_BALLOONUSERCLICK => NotificationEvent::Clicked,
+        NIN_BALLOONTIMEOUT => NotificationEvent::Dismissed,
+        _ => return None,
+    };
+
+    remove(hiword(lparam as u32) as u32).map(|id| (id, event))
+}
+
+/// Removes the icons of all notifications shown from `window`, which is about to be destroyed.
+pub(crate) fn remove_all(window: HWND) {
+    let uids: Vec<_> = ACTIVE
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|(_, (hwnd, _))| *hwnd == window)
+        .map(|(uid, _)| *uid)
+        .collect();
+    for uid in uids {
+        remove(uid);
+    }
+}
+
+fn remove(uid: u32) -> Option<NotificationId> {
+    let (window, id) = ACTIVE.lock().unwrap().remove(&uid)?;
+    unsafe { Shell_NotifyIconW(NIM_DELETE, &icon_data(window, uid)) };
+    Some(id)
+}
+
+fn icon_data(window: HWND, uid: u32) -> NOTIFYICONDATAW {
+    let mut data: NOTIFYICONDATAW = unsafe { mem::zeroed() };
+    data.cbSize = mem::size_of::<NOTIFYICONDATAW>() as u32;
+    data.hWnd = window;
+    data.uID = uid;
+    data
+}
+
+/// Copies `string` into the fixed size buffer, leaving room for the null terminator.
+fn copy_truncated(buffer: &mut [u16], string: &str) {
+    let mut len = 0;
+    for (dst, src) in buffer.iter_mut().zip(string.encode_utf16()).take(buffer.len() - 1) {
+        *dst = src;
+        len += 1;
+    }
+    buffer[len] = 0;
+}
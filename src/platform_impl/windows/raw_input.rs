@@ -3,6 +3,7 @@ use std::ptr;
 
 use windows_sys::Win32::Devices::HumanInterfaceDevice::{
     HID_USAGE_GENERIC_KEYBOARD, HID_USAGE_GENERIC_MOUSE, HID_USAGE_PAGE_GENERIC,
+    MOUSE_MOVE_RELATIVE,
 };
 use windows_sys::Win32::Foundation::{HANDLE, HWND};
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
@@ -10,7 +11,7 @@ use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
 };
 use windows_sys::Win32::UI::Input::{
     GetRawInputData, GetRawInputDeviceInfoW, GetRawInputDeviceList, RegisterRawInputDevices,
-    HRAWINPUT, RAWINPUT, RAWINPUTDEVICE, RAWINPUTDEVICELIST, RAWINPUTHEADER, RAWKEYBOARD,
+    HRAWINPUT, RAWINPUT, RAWINPUTDEVICE, RAWINPUTDEVICELIST, RAWINPUTHEADER, RAWKEYBOARD, RAWMOUSE,
     RIDEV_DEVNOTIFY, RIDEV_INPUTSINK, RIDEV_REMOVE, RIDI_DEVICEINFO, RIDI_DEVICENAME,
     RID_DEVICE_INFO, RID_DEVICE_INFO_HID, RID_DEVICE_INFO_KEYBOARD, RID_DEVICE_INFO_MOUSE,
     RID_INPUT, RIM_TYPEHID, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
@@ -130,9 +131,17 @@ pub fn register_raw_input_devices(devices: &[RAWINPUTDEVICE]) -> bool {
     }
 }
 
-pub fn register_all_mice_and_keyboards_for_raw_input(
+/// Which device classes to register for in [`register_for_raw_input`].
+#[derive(Debug, Clone, Copy)]
+pub struct RawInputDevices {
+    pub mouse: bool,
+    pub keyboard: bool,
+}
+
+pub fn register_for_raw_input(
     mut window_handle: HWND,
     filter: DeviceEvents,
+    classes: RawInputDevices,
 ) -> bool {
     // RIDEV_DEVNOTIFY: receive hotplug events
     // RIDEV_INPUTSINK: receive events even if we're not in the foreground
@@ -146,24 +155,61 @@ pub fn register_all_mice_and_keyboards_for_raw_input(
         DeviceEvents::Always => RIDEV_DEVNOTIFY | RIDEV_INPUTSINK,
     };
 
-    let devices: [RAWINPUTDEVICE; 2] = [
-        RAWINPUTDEVICE {
-            usUsagePage: HID_USAGE_PAGE_GENERIC,
-            usUsage: HID_USAGE_GENERIC_MOUSE,
-            dwFlags: flags,
-            hwndTarget: window_handle,
-        },
-        RAWINPUTDEVICE {
-            usUsagePage: HID_USAGE_PAGE_GENERIC,
-            usUsage: HID_USAGE_GENERIC_KEYBOARD,
-            dwFlags: flags,
-            hwndTarget: window_handle,
-        },
-    ];
+    let devices: Vec<RAWINPUTDEVICE> =
+        [(classes.mouse, HID_USAGE_GENERIC_MOUSE), (classes.keyboard, HID_USAGE_GENERIC_KEYBOARD)]
+            .into_iter()
+            .filter(|&(enabled, _)| enabled)
+            .map(|(_, usage)| RAWINPUTDEVICE {
+                usUsagePage: HID_USAGE_PAGE_GENERIC,
+                usUsage: usage,
+                dwFlags: flags,
+                hwndTarget: window_handle,
+            })
+            .collect();
+
+    if devices.is_empty() {
+        return true;
+    }
 
     register_raw_input_devices(&devices)
 }
 
+/// The parts of a `RAWMOUSE` that are turned into device events.
+#[derive(Debug, Clone, Copy)]
+pub struct RawMouseSample {
+    pub device: HANDLE,
+    pub delta: (i32, i32),
+    pub button_flags: u32,
+    pub button_data: i16,
+}
+
+impl RawMouseSample {
+    pub fn new(device: HANDLE, mouse: &RAWMOUSE) -> Self {
+        let delta = if util::has_flag(mouse.usFlags as u32, MOUSE_MOVE_RELATIVE) {
+            (mouse.lLastX, mouse.lLastY)
+        } else {
+            (0, 0)
+        };
+        let (button_flags, button_data) = unsafe {
+            let buttons = mouse.Anonymous.Anonymous;
+            (buttons.usButtonFlags as u32, buttons.usButtonData as i16)
+        };
+
+        Self { device, delta, button_flags, button_data }
+    }
+
+    /// Merge `other` into `self` if both only contain motion of the same device.
+    pub fn coalesce(&mut self, other: &RawMouseSample) -> bool {
+        if self.device != other.device || self.button_flags != 0 || other.button_flags != 0 {
+            return false;
+        }
+
+        self.delta.0 = self.delta.0.saturating_add(other.delta.0);
+        self.delta.1 = self.delta.1.saturating_add(other.delta.1);
+        true
+    }
+}
+
 pub fn get_raw_input_data(handle: HRAWINPUT) -> Option<RAWINPUT> {
     let mut data: RAWINPUT = unsafe { mem::zeroed() };
     let mut data_size = size_of::<RAWINPUT>() as u32;
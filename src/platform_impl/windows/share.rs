@@ -0,0 +1,323 @@
+//! Sharing data with the Share UI of `DataTransferManager`, see `Window::share`.
+//!
+//! The Share UI is a WinRT API, which is reached through hand written vtables like the other COM
+//! interfaces, since `windows-sys` doesn't include WinRT.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::c_void;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use windows_sys::core::{IUnknown, GUID, HRESULT, HSTRING};
+use windows_sys::Win32::Foundation::{E_FAIL, E_NOINTERFACE, E_NOTIMPL, HWND, S_OK, WPARAM};
+use windows_sys::Win32::UI::WindowsAndMessaging::PostMessageW;
+
+use super::definitions::{
+    EventRegistrationToken, IDataPackage, IDataPackage3, IDataPackage4, IDataPackagePropertySet,
+    IDataRequest, IDataRequestedEventArgs, IDataTransferManager, IDataTransferManagerInterop,
+    IID_DataRequestedHandler, IID_IAgileObject, IID_IDataPackage3, IID_IDataPackage4,
+    IID_IDataTransferManager, IID_IDataTransferManagerInterop, IID_IUnknown,
+    IID_IUriRuntimeClassFactory, IID_ShareCanceledHandler, IID_ShareCompletedHandler,
+    ITypedEventHandler, ITypedEventHandlerVtbl, IUnknownVtbl, IUriRuntimeClassFactory,
+};
+use super::event_loop::LazyMessageId;
+use crate::event::ShareOutcome;
+use crate::utils::Lazy;
+use crate::window::ShareData;
+
+// Message posted to a window when sharing its data ended. WPARAM contains the `ShareOutcome`.
+pub(crate) static SHARE_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::ShareMsg\0");
+
+type RoGetActivationFactory = unsafe extern "system" fn(
+    class_id: HSTRING,
+    iid: *const GUID,
+    factory: *mut *mut c_void,
+) -> HRESULT;
+type WindowsCreateString =
+    unsafe extern "system" fn(source: *const u16, length: u32, string: *mut HSTRING) -> HRESULT;
+type WindowsDeleteString = unsafe extern "system" fn(string: HSTRING) -> HRESULT;
+
+// Only available on Windows 8 and later.
+static RO_GET_ACTIVATION_FACTORY: Lazy<Option<RoGetActivationFactory>> =
+    Lazy::new(|| get_function!("combase.dll", RoGetActivationFactory));
+static WINDOWS_CREATE_STRING: Lazy<Option<WindowsCreateString>> =
+    Lazy::new(|| get_function!("combase.dll", WindowsCreateString));
+static WINDOWS_DELETE_STRING: Lazy<Option<WindowsDeleteString>> =
+    Lazy::new(|| get_function!("combase.dll", WindowsDeleteString));
+
+thread_local! {
+    /// The `DataRequested` handlers registered for windows, replaced by every share.
+    static DATA_REQUESTED: RefCell<HashMap<HWND, EventRegistrationToken>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Show the Share UI for a window, posting [`SHARE_MSG_ID`] to it once sharing ended.
+pub(crate) fn share(window: HWND, data: ShareData) {
+    if data.text.is_none() && data.url.is_none() {
+        return post_outcome(window, ShareOutcome::Failed);
+    }
+    if !data.files.is_empty() {
+        tracing::warn!("sharing files isn't supported on Windows");
+    }
+
+    if let Err(error) = unsafe { show(window, data) } {
+        tracing::warn!("failed to show the Share UI: {error:#x}");
+        post_outcome(window, ShareOutcome::Failed);
+    }
+}
+
+/// Translates the WPARAM of a [`SHARE_MSG_ID`] message.
+pub(crate) fn outcome(wparam: WPARAM) -> ShareOutcome {
+    match wparam {
+        0 => ShareOutcome::Shared,
+        1 => ShareOutcome::Cancelled,
+        _ => ShareOutcome::Failed,
+    }
+}
+
+fn post_outcome(window: HWND, outcome: ShareOutcome) {
+    let wparam = match outcome {
+        ShareOutcome::Shared => 0,
+        ShareOutcome::Cancelled => 1,
+        ShareOutcome::Failed => 2,
+    };
+    unsafe { PostMessageW(window, SHARE_MSG_ID.get(), wparam, 0) };
+}
+
+unsafe fn show(window: HWND, data: ShareData) -> Result<(), HRESULT> {
+    let interop: Com<IDataTransferManagerInterop> = unsafe {
+        activation_factory(
+            "Windows.ApplicationModel.DataTransfer.DataTransferManager",
+            &IID_IDataTransferManagerInterop,
+        )?
+    };
+    let manager: Com<IDataTransferManager> = unsafe {
+        Com::get(|manager| {
+            ((*(*interop.0).lpVtbl).GetForWindow)(
+                interop.0,
+                window,
+                &IID_IDataTransferManager,
+                manager,
+            )
+        })?
+    };
+
+    // The data is provided once the Share UI asks for it.
+    let handler = EventHandler::new(IID_DataRequestedHandler, move |args| unsafe {
+        if let Err(error) = provide(window, &data, args.cast()) {
+            tracing::warn!("failed to provide the shared data: {error:#x}");
+            post_outcome(window, ShareOutcome::Failed);
+        }
+    });
+    let mut token = EventRegistrationToken { value: 0 };
+    let result =
+        unsafe { ((*(*manager.0).lpVtbl).add_DataRequested)(manager.0, handler.0, &mut token) };
+    check(result)?;
+    let previous = DATA_REQUESTED.with(|tokens| tokens.borrow_mut().insert(window, token));
+    if let Some(previous) = previous {
+        unsafe { ((*(*manager.0).lpVtbl).remove_DataRequested)(manager.0, previous) };
+    }
+
+    check(unsafe { ((*(*interop.0).lpVtbl).ShowShareUIForWindow)(interop.0, window) })
+}
+
+/// Fill the data package requested by the Share UI, and watch how sharing ends.
+unsafe fn provide(
+    window: HWND,
+    data: &ShareData,
+    args: *mut IDataRequestedEventArgs,
+) -> Result<(), HRESULT> {
+    let request: Com<IDataRequest> =
+        unsafe { Com::get(|request| ((*(*args).lpVtbl).get_Request)(args, request))? };
+    let package: Com<IDataPackage> =
+        unsafe { Com::get(|package| ((*(*request.0).lpVtbl).get_Data)(request.0, package))? };
+    let properties: Com<IDataPackagePropertySet> = unsafe {
+        Com::get(|properties| ((*(*package.0).lpVtbl).get_Properties)(package.0, properties))?
+    };
+
+    // The Share UI refuses data without a title.
+    let title = data.title.as_deref().or(data.text.as_deref()).or(data.url.as_deref());
+    let title = HString::new(title.unwrap_or_default())?;
+    check(unsafe { ((*(*properties.0).lpVtbl).put_Title)(properties.0, title.0) })?;
+    if let Some(text) = &data.text {
+        let text = HString::new(text)?;
+        check(unsafe { ((*(*package.0).lpVtbl).SetText)(package.0, text.0) })?;
+    }
+    if let Some(url) = &data.url {
+        let factory: Com<IUriRuntimeClassFactory> =
+            unsafe { activation_factory("Windows.Foundation.Uri", &IID_IUriRuntimeClassFactory)? };
+        let url = HString::new(url)?;
+        let uri: Com<c_void> =
+            unsafe { Com::get(|uri| ((*(*factory.0).lpVtbl).CreateUri)(factory.0, url.0, uri))? };
+        check(unsafe { ((*(*package.0).lpVtbl).SetUri)(package.0, uri.0) })?;
+    }
+
+    // `ShareCompleted` is only available on Windows 10 version 1709 and later, `ShareCanceled` on
+    // version 2004 and later.
+    if let Ok(package) = unsafe { package.cast::<IDataPackage3>(&IID_IDataPackage3) } {
+        let handler = EventHandler::new(IID_ShareCompletedHandler, move |_| {
+            post_outcome(window, ShareOutcome::Shared)
+        });
+        let mut token = EventRegistrationToken { value: 0 };
+        unsafe { ((*(*package.0).lpVtbl).add_ShareCompleted)(package.0, handler.0, &mut token) };
+    }
+    if let Ok(package) = unsafe { package.cast::<IDataPackage4>(&IID_IDataPackage4) } {
+        let handler = EventHandler::new(IID_ShareCanceledHandler, move |_| {
+            post_outcome(window, ShareOutcome::Cancelled)
+        });
+        let mut token = EventRegistrationToken { value: 0 };
+        unsafe { ((*(*package.0).lpVtbl).add_ShareCanceled)(package.0, handler.0, &mut token) };
+    }
+
+    Ok(())
+}
+
+unsafe fn activation_factory<T>(class_id: &str, iid: &GUID) -> Result<Com<T>, HRESULT> {
+    let ro_get_activation_factory = (*RO_GET_ACTIVATION_FACTORY).ok_or(E_NOTIMPL)?;
+    let class_id = HString::new(class_id)?;
+    unsafe { Com::get(|factory| ro_get_activation_factory(class_id.0, iid, factory)) }
+}
+
+fn check(result: HRESULT) -> Result<(), HRESULT> {
+    if result == S_OK {
+        Ok(())
+    } else {
+        Err(result)
+    }
+}
+
+fn guid_eq(a: &GUID, b: &GUID) -> bool {
+    a.data1 == b.data1 && a.data2 == b.data2 && a.data3 == b.data3 && a.data4 == b.data4
+}
+
+/// A reference to a COM object, released when dropped.
+struct Com<T>(*mut T);
+
+impl<T> Com<T> {
+    /// Take the reference returned by `f`.
+    unsafe fn get(f: impl FnOnce(*mut *mut c_void) -> HRESULT) -> Result<Self, HRESULT> {
+        let mut object = ptr::null_mut();
+        check(f(&mut object))?;
+        if object.is_null() {
+            return Err(E_FAIL);
+        }
+        Ok(Com(object.cast()))
+    }
+
+    unsafe fn cast<U>(&self, iid: &GUID) -> Result<Com<U>, HRESULT> {
+        let unknown = self.0 as *mut IUnknown;
+        let vtbl = unsafe { &**(unknown as *mut *const IUnknownVtbl) };
+        unsafe { Com::get(|object| (vtbl.QueryInterface)(unknown, iid, object)) }
+    }
+}
+
+impl<T> Drop for Com<T> {
+    fn drop(&mut self) {
+        let unknown = self.0 as *mut IUnknown;
+        unsafe { ((**(unknown as *mut *const IUnknownVtbl)).Release)(unknown) };
+    }
+}
+
+/// A WinRT string, deleted when dropped.
+struct HString(HSTRING);
+
+impl HString {
+    fn new(string: &str) -> Result<Self, HRESULT> {
+        let windows_create_string = (*WINDOWS_CREATE_STRING).ok_or(E_NOTIMPL)?;
+        let wide: Vec<u16> = string.encode_utf16().collect();
+        let mut hstring = ptr::null_mut();
+        check(unsafe { windows_create_string(wide.as_ptr(), wide.len() as u32, &mut hstring) })?;
+        Ok(HString(hstring))
+    }
+}
+
+impl Drop for HString {
+    fn drop(&mut self) {
+        if let Some(windows_delete_string) = *WINDOWS_DELETE_STRING {
+            unsafe { windows_delete_string(self.0) };
+        }
+    }
+}
+
+/// A `TypedEventHandler`, calling a closure with the arguments of the event.
+#[repr(C)]
+struct EventHandlerData {
+    interface: ITypedEventHandler,
+    refcount: AtomicUsize,
+    iid: GUID,
+    handler: Box<dyn Fn(*mut c_void)>,
+}
+
+struct EventHandler;
+
+#[allow(non_snake_case)]
+impl EventHandler {
+    fn new(iid: GUID, handler: impl Fn(*mut c_void) + 'static) -> Com<ITypedEventHandler> {
+        let data = Box::new(EventHandlerData {
+            interface: ITypedEventHandler { lpVtbl: &EVENT_HANDLER_VTBL },
+            refcount: AtomicUsize::new(1),
+            iid,
+            handler: Box::new(handler),
+        });
+        Com(Box::into_raw(data).cast())
+    }
+
+    unsafe fn from_interface<'a, InterfaceT>(this: *mut InterfaceT) -> &'a EventHandlerData {
+        unsafe { &*(this as *mut _) }
+    }
+
+    unsafe extern "system" fn QueryInterface(
+        this: *mut IUnknown,
+        riid: *const GUID,
+        ppvObject: *mut *mut c_void,
+    ) -> HRESULT {
+        let handler = unsafe { Self::from_interface(this) };
+        let riid = unsafe { &*riid };
+        if guid_eq(riid, &IID_IUnknown)
+            || guid_eq(riid, &IID_IAgileObject)
+            || guid_eq(riid, &handler.iid)
+        {
+            unsafe { Self::AddRef(this) };
+            unsafe { *ppvObject = this.cast() };
+            S_OK
+        } else {
+            unsafe { *ppvObject = ptr::null_mut() };
+            E_NOINTERFACE
+        }
+    }
+
+    unsafe extern "system" fn AddRef(this: *mut IUnknown) -> u32 {
+        let handler = unsafe { Self::from_interface(this) };
+        let count = handler.refcount.fetch_add(1, Ordering::Release) + 1;
+        count as u32
+    }
+
+    unsafe extern "system" fn Release(this: *mut IUnknown) -> u32 {
+        let handler = unsafe { Self::from_interface(this) };
+        let count = handler.refcount.fetch_sub(1, Ordering::Release) - 1;
+        if count == 0 {
+            drop(unsafe { Box::from_raw(handler as *const _ as *mut EventHandlerData) });
+        }
+        count as u32
+    }
+
+    unsafe extern "system" fn Invoke(
+        this: *mut ITypedEventHandler,
+        _sender: *mut c_void,
+        args: *mut c_void,
+    ) -> HRESULT {
+        let handler = unsafe { Self::from_interface(this) };
+        (handler.handler)(args);
+        S_OK
+    }
+}
+
+static EVENT_HANDLER_VTBL: ITypedEventHandlerVtbl = ITypedEventHandlerVtbl {
+    parent: IUnknownVtbl {
+        QueryInterface: EventHandler::QueryInterface,
+        AddRef: EventHandler::AddRef,
+        Release: EventHandler::Release,
+    },
+    Invoke: EventHandler::Invoke,
+};
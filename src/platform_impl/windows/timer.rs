@@ -0,0 +1,101 @@
+//! High resolution timers for `ControlFlow::WaitUntil`.
+//!
+//! The timeout of `GetMessageW` is implemented with `SetTimer`, which is bound to the system clock
+//! tick of ~15.6ms by default. When a finer resolution is requested with
+//! `EventLoop::set_timer_resolution`, the tick rate is raised with `timeBeginPeriod`, and waits are
+//! done on a high resolution waitable timer with `MsgWaitForMultipleObjectsEx` instead.
+
+use std::cell::Cell;
+use std::ptr;
+use std::time::Duration;
+
+use windows_sys::Win32::Foundation::{CloseHandle, HANDLE};
+use windows_sys::Win32::Media::{timeBeginPeriod, timeEndPeriod};
+use windows_sys::Win32::System::Threading::{
+    CREATE_WAITABLE_TIMER_HIGH_RESOLUTION, TIMER_ALL_ACCESS,
+};
+
+use crate::platform_impl::platform::util::CREATE_WAITABLE_TIMER_EX_W;
+
+/// Resolutions at or above the default system clock tick don't need any special handling.
+const DEFAULT_RESOLUTION: Duration = Duration::from_millis(15);
+
+#[derive(Debug, Default)]
+pub(crate) struct TimerResolution {
+    /// The period passed to `timeBeginPeriod`, in milliseconds.
+    period: Cell<Option<u32>>,
+    /// The waitable timer used for waits, or `0` when the default resolution is used.
+    timer: Cell<HANDLE>,
+}
+
+impl TimerResolution {
+    pub fn set(&self, resolution: Duration) {
+        if resolution >= DEFAULT_RESOLUTION {
+            self.reset();
+            return;
+        }
+
+        // `timeBeginPeriod` takes whole milliseconds, and fails below the 1ms minimum.
+        let period = ((resolution.as_micros() as u32 + 999) / 1000).max(1);
+        if self.period.get() != Some(period) {
+            self.end_period();
+            if unsafe { timeBeginPeriod(period) } == 0 {
+                self.period.set(Some(period));
+            }
+        }
+
+        if self.timer.get() == 0 {
+            self.timer.set(create_waitable_timer());
+        }
+    }
+
+    /// The waitable timer to wait on, if a high resolution was requested.
+    pub fn timer(&self) -> Option<HANDLE> {
+        match self.timer.get() {
+            0 => None,
+            timer => Some(timer),
+        }
+    }
+
+    fn reset(&self) {
+        self.end_period();
+        let timer = self.timer.replace(0);
+        if timer != 0 {
+            unsafe { CloseHandle(timer) };
+        }
+    }
+
+    fn end_period(&self) {
+        if let Some(period) = self.period.take() {
+            unsafe { timeEndPeriod(period) };
+        }
+    }
+}
+
+impl Drop for TimerResolution {
+    fn drop(&mut self) {
+        self.reset();
+    }
+}
+
+fn create_waitable_timer() -> HANDLE {
+    let Some(create_waitable_timer_ex) = *CREATE_WAITABLE_TIMER_EX_W else {
+        return 0;
+    };
+
+    unsafe {
+        let timer = create_waitable_timer_ex(
+            ptr::null(),
+            ptr::null(),
+            CREATE_WAITABLE_TIMER_HIGH_RESOLUTION,
+            TIMER_ALL_ACCESS,
+        );
+        if timer != 0 {
+            return timer;
+        }
+
+        // High resolution timers are only supported since Windows 10 version 1803, but a regular
+        // waitable timer still benefits from the raised tick rate.
+        create_waitable_timer_ex(ptr::null(), ptr::null(), 0, TIMER_ALL_ACCESS)
+    }
+}
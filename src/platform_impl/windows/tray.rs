@@ -0,0 +1,75 @@
+use std::{io, mem};
+
+use windows_sys::Win32::Foundation::{HWND, LPARAM};
+use windows_sys::Win32::UI::Shell::{
+    Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_SHOWTIP, NIF_TIP, NIM_ADD, NIM_DELETE,
+    NIM_SETVERSION, NINF_KEY, NIN_SELECT, NOTIFYICONDATAW, NOTIFYICON_VERSION_4,
+};
+use windows_sys::Win32::UI::WindowsAndMessaging::{GetWindowTextW, LoadIconW, IDI_APPLICATION};
+
+use super::event_loop::LazyMessageId;
+use super::loword;
+use super::window_state::WindowState;
+
+// Message sent to a window by `Window::hide_to_tray`, so that it is hidden from its window
+// procedure, which can emit events.
+pub(crate) static HIDE_TO_TRAY_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::HideToTray\0");
+
+// Message sent by the shell to a window hidden to the tray when the user interacts with its icon.
+// The low word of LPARAM contains the `NIN_*` event.
+pub(crate) static TRAY_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::TrayMsg\0");
+
+// Not defined by `windows-sys`.
+const NIN_KEYSELECT: u32 = NIN_SELECT | NINF_KEY;
+
+/// The `uID` of the icon of a window, which is unique as each window has one icon at most.
+/// Notifications are shown from the thread event target window instead.
+const UID: u32 = 0;
+
+/// Adds the icon of a window hidden to the tray, showing the title of the window as tooltip.
+pub(crate) fn add(window: HWND, window_state: &WindowState) -> Result<(), io::Error> {
+    let icon = window_state.window_icon.as_ref().or(window_state.taskbar_icon.as_ref());
+
+    let mut data = icon_data(window);
+    data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP | NIF_SHOWTIP;
+    data.uCallbackMessage = TRAY_MSG_ID.get();
+    data.hIcon = match icon {
+        Some(icon) => icon.inner.as_raw_handle(),
+        None => unsafe { LoadIconW(0, IDI_APPLICATION) },
+    };
+    // Truncated to the length of the tooltip, `szTip` can't be borrowed as the struct is packed
+    // on x86.
+    let mut tip = [0; 128];
+    unsafe { GetWindowTextW(window, tip.as_mut_ptr(), tip.len() as i32) };
+    data.szTip = tip;
+    if unsafe { Shell_NotifyIconW(NIM_ADD, &data) } == false.into() {
+        return Err(io::Error::last_os_error());
+    }
+
+    // Version 4 reports selecting the icon with the mouse or the keyboard as `NIN_SELECT`.
+    data.Anonymous.uVersion = NOTIFYICON_VERSION_4;
+    if unsafe { Shell_NotifyIconW(NIM_SETVERSION, &data) } == false.into() {
+        let error = io::Error::last_os_error();
+        remove(window);
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+pub(crate) fn remove(window: HWND) {
+    unsafe { Shell_NotifyIconW(NIM_DELETE, &icon_data(window)) };
+}
+
+/// Whether a [`TRAY_MSG_ID`] message reports that the user activated the icon.
+pub(crate) fn is_activation(lparam: LPARAM) -> bool {
+    matches!(loword(lparam as u32) as u32, NIN_SELECT | NIN_KEYSELECT)
+}
+
+fn icon_data(window: HWND) -> NOTIFYICONDATAW {
+    let mut data: NOTIFYICONDATAW = unsafe { mem::zeroed() };
+    data.cbSize = mem::size_of::<NOTIFYICONDATAW>() as u32;
+    data.hWnd = window;
+    data.uID = UID;
+    data
+}
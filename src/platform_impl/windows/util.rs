@@ -1,5 +1,5 @@
 use std::ffi::{c_void, OsStr, OsString};
-use std::iter::once;
+use std::iter::{self, once};
 use std::ops::BitAnd;
 use std::os::windows::prelude::{OsStrExt, OsStringExt};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -8,28 +8,74 @@ use std::{io, mem, ptr};
 use crate::utils::Lazy;
 use windows_sys::core::{HRESULT, PCWSTR};
 use windows_sys::Win32::Foundation::{BOOL, HANDLE, HMODULE, HWND, RECT};
-use windows_sys::Win32::Graphics::Gdi::{ClientToScreen, HMONITOR};
+use windows_sys::Win32::Graphics::Gdi::{
+    ClientToScreen, MonitorFromWindow, HMONITOR, MONITOR_DEFAULTTONEAREST,
+};
 use windows_sys::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryA};
 use windows_sys::Win32::System::SystemServices::IMAGE_DOS_HEADER;
+use windows_sys::Win32::System::Threading::PROCESS_INFORMATION_CLASS;
+use windows_sys::Win32::UI::Controls::{FEEDBACK_TYPE, TASKDIALOGCONFIG};
 use windows_sys::Win32::UI::HiDpi::{
     DPI_AWARENESS_CONTEXT, MONITOR_DPI_TYPE, PROCESS_DPI_AWARENESS,
 };
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::GetActiveWindow;
 use windows_sys::Win32::UI::Input::Pointer::{POINTER_INFO, POINTER_PEN_INFO, POINTER_TOUCH_INFO};
+use windows_sys::Win32::UI::TextServices::InputScope;
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    ClipCursor, GetClientRect, GetClipCursor, GetSystemMetrics, GetWindowPlacement, GetWindowRect,
-    IsIconic, ShowCursor, IDC_APPSTARTING, IDC_ARROW, IDC_CROSS, IDC_HAND, IDC_HELP, IDC_IBEAM,
-    IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS, IDC_SIZENWSE, IDC_SIZEWE, IDC_WAIT,
-    SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN, SM_YVIRTUALSCREEN, SW_MAXIMIZE,
-    WINDOWPLACEMENT,
+    ClipCursor, GetClientRect, GetClipCursor, GetMessageExtraInfo, GetSystemMetrics,
+    GetWindowPlacement, GetWindowRect, IsIconic, ShowCursor, IDC_APPSTARTING, IDC_ARROW, IDC_CROSS,
+    IDC_HAND, IDC_HELP, IDC_IBEAM, IDC_NO, IDC_SIZEALL, IDC_SIZENESW, IDC_SIZENS, IDC_SIZENWSE,
+    IDC_SIZEWE, IDC_WAIT, SM_CXVIRTUALSCREEN, SM_CYVIRTUALSCREEN, SM_XVIRTUALSCREEN,
+    SM_YVIRTUALSCREEN, SW_MAXIMIZE, WINDOWPLACEMENT,
 };
 
-use crate::window::CursorIcon;
+use crate::platform_impl::platform::monitor::get_monitor_info;
+use crate::window::{CursorIcon, TiledEdges};
 
 pub fn encode_wide(string: impl AsRef<OsStr>) -> Vec<u16> {
     string.as_ref().encode_wide().chain(once(0)).collect()
 }
 
+/// Join arguments into a command line, quoted so that `CommandLineToArgvW()` splits it into the
+/// same arguments again.
+pub fn join_command_line(args: &[OsString]) -> OsString {
+    const BACKSLASH: u16 = b'\\' as u16;
+    const QUOTE: u16 = b'"' as u16;
+
+    let mut command_line = Vec::new();
+    for arg in args {
+        if !command_line.is_empty() {
+            command_line.push(b' ' as u16);
+        }
+        let arg: Vec<u16> = arg.encode_wide().collect();
+        if !arg.is_empty()
+            && !arg.iter().any(|&c| c == b' ' as u16 || c == b'\t' as u16 || c == QUOTE)
+        {
+            command_line.extend(arg);
+            continue;
+        }
+
+        command_line.push(QUOTE);
+        let mut backslashes = 0;
+        for c in arg {
+            if c == BACKSLASH {
+                backslashes += 1;
+            } else {
+                // Backslashes are only special in front of quotes, which are escaped as well.
+                if c == QUOTE {
+                    command_line.extend(iter::repeat(BACKSLASH).take(backslashes + 1));
+                }
+                backslashes = 0;
+            }
+            command_line.push(c);
+        }
+        // Trailing backslashes would escape the closing quote.
+        command_line.extend(iter::repeat(BACKSLASH).take(backslashes));
+        command_line.push(QUOTE);
+    }
+    OsString::from_wide(&command_line)
+}
+
 pub fn decode_wide(mut wide_c_string: &[u16]) -> OsString {
     if let Some(null_pos) = wide_c_string.iter().position(|c| *c == 0) {
         wide_c_string = &wide_c_string[..null_pos];
@@ -91,6 +137,51 @@ pub fn is_maximized(window: HWND) -> bool {
     }
 }
 
+/// The edges of its monitor a window is snapped against, or `None` if it isn't snapped.
+pub fn tiled_edges(window: HWND) -> Option<TiledEdges> {
+    let is_window_arranged = (*IS_WINDOW_ARRANGED)?;
+    if unsafe { is_window_arranged(window) } == false.into() {
+        return None;
+    }
+
+    let monitor = unsafe { MonitorFromWindow(window, MONITOR_DEFAULTTONEAREST) };
+    let work_area = get_monitor_info(monitor).ok()?.monitorInfo.rcWork;
+    // The window rectangle includes the invisible resize borders, which extend past the work area.
+    let rect = WindowArea::Outer.get_rect(window).ok()?;
+    let mut edges = TiledEdges::empty();
+    edges.set(TiledEdges::LEFT, rect.left <= work_area.left);
+    edges.set(TiledEdges::RIGHT, rect.right >= work_area.right);
+    edges.set(TiledEdges::TOP, rect.top <= work_area.top);
+    edges.set(TiledEdges::BOTTOM, rect.bottom >= work_area.bottom);
+    Some(edges)
+}
+
+/// Where the mouse message that is currently being processed came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseMessageSource {
+    Mouse,
+    Touch,
+    Pen,
+}
+
+/// Distinguish mouse messages that Windows synthesized from touch or pen input.
+///
+/// See <https://learn.microsoft.com/en-us/windows/win32/tablet/system-events-and-mouse-messages>.
+pub fn mouse_message_source() -> MouseMessageSource {
+    const MI_WP_SIGNATURE: u32 = 0xff515700;
+    const SIGNATURE_MASK: u32 = 0xffffff00;
+    const TOUCH_FLAG: u32 = 0x80;
+
+    let extra_info = unsafe { GetMessageExtraInfo() } as u32;
+    if extra_info & SIGNATURE_MASK != MI_WP_SIGNATURE {
+        MouseMessageSource::Mouse
+    } else if extra_info & TOUCH_FLAG != 0 {
+        MouseMessageSource::Touch
+    } else {
+        MouseMessageSource::Pen
+    }
+}
+
 pub fn set_cursor_hidden(hidden: bool) {
     static HIDDEN: AtomicBool = AtomicBool::new(false);
     let changed = HIDDEN.swap(hidden, Ordering::SeqCst) ^ hidden;
@@ -211,6 +302,14 @@ pub type SetProcessDpiAwareness =
     unsafe extern "system" fn(value: PROCESS_DPI_AWARENESS) -> HRESULT;
 pub type SetProcessDpiAwarenessContext =
     unsafe extern "system" fn(value: DPI_AWARENESS_CONTEXT) -> BOOL;
+pub type GetProcessDpiAwareness =
+    unsafe extern "system" fn(hprocess: HANDLE, value: *mut PROCESS_DPI_AWARENESS) -> HRESULT;
+pub type GetWindowDpiAwarenessContext =
+    unsafe extern "system" fn(hwnd: HWND) -> DPI_AWARENESS_CONTEXT;
+pub type AreDpiAwarenessContextsEqual = unsafe extern "system" fn(
+    dpiContextA: DPI_AWARENESS_CONTEXT,
+    dpiContextB: DPI_AWARENESS_CONTEXT,
+) -> BOOL;
 pub type GetDpiForWindow = unsafe extern "system" fn(hwnd: HWND) -> u32;
 pub type GetDpiForMonitor = unsafe extern "system" fn(
     hmonitor: HMONITOR,
@@ -247,6 +346,39 @@ pub type GetPointerTouchInfo =
 pub type GetPointerPenInfo =
     unsafe extern "system" fn(pointId: u32, penInfo: *mut POINTER_PEN_INFO) -> BOOL;
 
+pub type SetWindowFeedbackSetting = unsafe extern "system" fn(
+    hwnd: HWND,
+    feedback: FEEDBACK_TYPE,
+    dwFlags: u32,
+    size: u32,
+    configuration: *const c_void,
+) -> BOOL;
+
+pub type CreateWaitableTimerExW = unsafe extern "system" fn(
+    lpTimerAttributes: *const c_void,
+    lpTimerName: PCWSTR,
+    dwFlags: u32,
+    dwDesiredAccess: u32,
+) -> HANDLE;
+
+pub type SetProcessInformation = unsafe extern "system" fn(
+    hProcess: HANDLE,
+    ProcessInformationClass: PROCESS_INFORMATION_CLASS,
+    ProcessInformation: *const c_void,
+    ProcessInformationSize: u32,
+) -> BOOL;
+
+pub type SetInputScope = unsafe extern "system" fn(hwnd: HWND, inputscope: InputScope) -> HRESULT;
+
+pub type IsWindowArranged = unsafe extern "system" fn(hwnd: HWND) -> BOOL;
+
+pub type TaskDialogIndirect = unsafe extern "system" fn(
+    pTaskConfig: *const TASKDIALOGCONFIG,
+    pnButton: *mut i32,
+    pnRadioButton: *mut i32,
+    pfVerificationFlagChecked: *mut BOOL,
+) -> HRESULT;
+
 pub(crate) static GET_DPI_FOR_WINDOW: Lazy<Option<GetDpiForWindow>> =
     Lazy::new(|| get_function!("user32.dll", GetDpiForWindow));
 pub(crate) static ADJUST_WINDOW_RECT_EX_FOR_DPI: Lazy<Option<AdjustWindowRectExForDpi>> =
@@ -261,6 +393,12 @@ pub(crate) static SET_PROCESS_DPI_AWARENESS: Lazy<Option<SetProcessDpiAwareness>
     Lazy::new(|| get_function!("shcore.dll", SetProcessDpiAwareness));
 pub(crate) static SET_PROCESS_DPI_AWARE: Lazy<Option<SetProcessDPIAware>> =
     Lazy::new(|| get_function!("user32.dll", SetProcessDPIAware));
+pub(crate) static GET_PROCESS_DPI_AWARENESS: Lazy<Option<GetProcessDpiAwareness>> =
+    Lazy::new(|| get_function!("shcore.dll", GetProcessDpiAwareness));
+pub(crate) static GET_WINDOW_DPI_AWARENESS_CONTEXT: Lazy<Option<GetWindowDpiAwarenessContext>> =
+    Lazy::new(|| get_function!("user32.dll", GetWindowDpiAwarenessContext));
+pub(crate) static ARE_DPI_AWARENESS_CONTEXTS_EQUAL: Lazy<Option<AreDpiAwarenessContextsEqual>> =
+    Lazy::new(|| get_function!("user32.dll", AreDpiAwarenessContextsEqual));
 pub(crate) static GET_POINTER_FRAME_INFO_HISTORY: Lazy<Option<GetPointerFrameInfoHistory>> =
     Lazy::new(|| get_function!("user32.dll", GetPointerFrameInfoHistory));
 pub(crate) static SKIP_POINTER_FRAME_MESSAGES: Lazy<Option<SkipPointerFrameMessages>> =
@@ -271,3 +409,16 @@ pub(crate) static GET_POINTER_TOUCH_INFO: Lazy<Option<GetPointerTouchInfo>> =
     Lazy::new(|| get_function!("user32.dll", GetPointerTouchInfo));
 pub(crate) static GET_POINTER_PEN_INFO: Lazy<Option<GetPointerPenInfo>> =
     Lazy::new(|| get_function!("user32.dll", GetPointerPenInfo));
+pub(crate) static SET_WINDOW_FEEDBACK_SETTING: Lazy<Option<SetWindowFeedbackSetting>> =
+    Lazy::new(|| get_function!("user32.dll", SetWindowFeedbackSetting));
+pub(crate) static CREATE_WAITABLE_TIMER_EX_W: Lazy<Option<CreateWaitableTimerExW>> =
+    Lazy::new(|| get_function!("kernel32.dll", CreateWaitableTimerExW));
+pub(crate) static SET_PROCESS_INFORMATION: Lazy<Option<SetProcessInformation>> =
+    Lazy::new(|| get_function!("kernel32.dll", SetProcessInformation));
+pub(crate) static SET_INPUT_SCOPE: Lazy<Option<SetInputScope>> =
+    Lazy::new(|| get_function!("msctf.dll", SetInputScope));
+pub(crate) static IS_WINDOW_ARRANGED: Lazy<Option<IsWindowArranged>> =
+    Lazy::new(|| get_function!("user32.dll", IsWindowArranged));
+// Only available when the application opted into version 6 of the common controls.
+pub(crate) static TASK_DIALOG_INDIRECT: Lazy<Option<TaskDialogIndirect>> =
+    Lazy::new(|| get_function!("comctl32.dll", TaskDialogIndirect));
@@ -3,45 +3,61 @@
 use std::cell::Cell;
 use std::ffi::c_void;
 use std::mem::{self, MaybeUninit};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::mpsc::channel;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::{io, panic, ptr};
 
 use windows_sys::Win32::Foundation::{
-    HWND, LPARAM, OLE_E_WRONGCOMPOBJ, POINT, POINTS, RECT, RPC_E_CHANGED_MODE, S_OK, WPARAM,
+    BOOL, HWND, LPARAM, OLE_E_WRONGCOMPOBJ, POINT, POINTS, RECT, RPC_E_CHANGED_MODE, S_OK, WPARAM,
 };
 use windows_sys::Win32::Graphics::Dwm::{
     DwmEnableBlurBehindWindow, DwmSetWindowAttribute, DWMWA_BORDER_COLOR, DWMWA_CAPTION_COLOR,
-    DWMWA_SYSTEMBACKDROP_TYPE, DWMWA_TEXT_COLOR, DWMWA_WINDOW_CORNER_PREFERENCE, DWM_BB_BLURREGION,
-    DWM_BB_ENABLE, DWM_BLURBEHIND, DWM_SYSTEMBACKDROP_TYPE, DWM_WINDOW_CORNER_PREFERENCE,
+    DWMWA_CLOAK, DWMWA_SYSTEMBACKDROP_TYPE, DWMWA_TEXT_COLOR, DWMWA_WINDOW_CORNER_PREFERENCE,
+    DWM_BB_BLURREGION, DWM_BB_ENABLE, DWM_BLURBEHIND, DWM_SYSTEMBACKDROP_TYPE,
+    DWM_WINDOW_CORNER_PREFERENCE,
 };
 use windows_sys::Win32::Graphics::Gdi::{
-    ChangeDisplaySettingsExW, ClientToScreen, CreateRectRgn, DeleteObject, InvalidateRgn,
-    RedrawWindow, CDS_FULLSCREEN, DISP_CHANGE_BADFLAGS, DISP_CHANGE_BADMODE, DISP_CHANGE_BADPARAM,
-    DISP_CHANGE_FAILED, DISP_CHANGE_SUCCESSFUL, RDW_INTERNALPAINT,
+    ChangeDisplaySettingsExW, ClientToScreen, CreateRectRgn, DeleteObject, GetDC, InvalidateRgn,
+    RedrawWindow, ReleaseDC, ScreenToClient, StretchDIBits, BITMAPINFO, BITMAPINFOHEADER, BI_RGB,
+    CDS_FULLSCREEN, DIB_RGB_COLORS, DISP_CHANGE_BADFLAGS, DISP_CHANGE_BADMODE,
+    DISP_CHANGE_BADPARAM, DISP_CHANGE_FAILED, DISP_CHANGE_SUCCESSFUL, RDW_INTERNALPAINT, SRCCOPY,
 };
 use windows_sys::Win32::System::Com::{
     CoCreateInstance, CoInitializeEx, CoUninitialize, CLSCTX_ALL, COINIT_APARTMENTTHREADED,
 };
 use windows_sys::Win32::System::Ole::{OleInitialize, RegisterDragDrop};
+use windows_sys::Win32::System::Threading::{
+    GetCurrentProcess, ProcessPowerThrottling, PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+    PROCESS_POWER_THROTTLING_EXECUTION_SPEED, PROCESS_POWER_THROTTLING_IGNORE_TIMER_RESOLUTION,
+    PROCESS_POWER_THROTTLING_STATE,
+};
+use windows_sys::Win32::UI::Controls::{
+    FEEDBACK_GESTURE_PRESSANDTAP, FEEDBACK_PEN_BARRELVISUALIZATION, FEEDBACK_PEN_DOUBLETAP,
+    FEEDBACK_PEN_PRESSANDHOLD, FEEDBACK_PEN_RIGHTTAP, FEEDBACK_PEN_TAP,
+    FEEDBACK_TOUCH_CONTACTVISUALIZATION, FEEDBACK_TOUCH_DOUBLETAP, FEEDBACK_TOUCH_PRESSANDHOLD,
+    FEEDBACK_TOUCH_RIGHTTAP, FEEDBACK_TOUCH_TAP, FEEDBACK_TYPE,
+};
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
     EnableWindow, GetActiveWindow, MapVirtualKeyW, ReleaseCapture, SendInput, ToUnicode, INPUT,
     INPUT_0, INPUT_KEYBOARD, KEYBDINPUT, KEYEVENTF_EXTENDEDKEY, KEYEVENTF_KEYUP, MAPVK_VK_TO_VSC,
     VIRTUAL_KEY, VK_LMENU, VK_MENU, VK_SPACE,
 };
 use windows_sys::Win32::UI::Input::Touch::{RegisterTouchWindow, TWF_WANTPALM};
+use windows_sys::Win32::UI::TextServices::{IS_ALPHANUMERIC_HALFWIDTH, IS_DEFAULT};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
     CreateWindowExW, EnableMenuItem, FlashWindowEx, GetClientRect, GetCursorPos,
     GetForegroundWindow, GetSystemMenu, GetSystemMetrics, GetWindowPlacement, GetWindowTextLengthW,
-    GetWindowTextW, IsWindowVisible, LoadCursorW, PeekMessageW, PostMessageW, RegisterClassExW,
-    SetCursor, SetCursorPos, SetForegroundWindow, SetMenuDefaultItem, SetWindowDisplayAffinity,
-    SetWindowPlacement, SetWindowPos, SetWindowTextW, TrackPopupMenu, CS_HREDRAW, CS_VREDRAW,
-    CW_USEDEFAULT, FLASHWINFO, FLASHW_ALL, FLASHW_STOP, FLASHW_TIMERNOFG, FLASHW_TRAY,
-    GWLP_HINSTANCE, HTBOTTOM, HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT, HTTOP,
-    HTTOPLEFT, HTTOPRIGHT, MENU_ITEM_STATE, MFS_DISABLED, MFS_ENABLED, MF_BYCOMMAND, NID_READY,
-    PM_NOREMOVE, SC_CLOSE, SC_MAXIMIZE, SC_MINIMIZE, SC_MOVE, SC_RESTORE, SC_SIZE, SM_DIGITIZER,
-    SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, TPM_LEFTALIGN, TPM_RETURNCMD,
-    WDA_EXCLUDEFROMCAPTURE, WDA_NONE, WM_NCLBUTTONDOWN, WM_SYSCOMMAND, WNDCLASSEXW,
+    GetWindowTextW, IsWindowVisible, KillTimer, LoadCursorW, PeekMessageW, PostMessageW,
+    RegisterClassExW, SetCursor, SetCursorPos, SetForegroundWindow, SetMenuDefaultItem, SetTimer,
+    SetWindowDisplayAffinity, SetWindowPlacement, SetWindowPos, SetWindowTextW, TrackPopupMenu,
+    CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT, FLASHWINFO, FLASHW_ALL, FLASHW_STOP, FLASHW_TIMERNOFG,
+    FLASHW_TRAY, GWLP_HINSTANCE, HTBOTTOM, HTBOTTOMLEFT, HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT,
+    HTTOP, HTTOPLEFT, HTTOPRIGHT, MENU_ITEM_STATE, MFS_DISABLED, MFS_ENABLED, MF_BYCOMMAND,
+    NID_READY, PM_NOREMOVE, SC_CLOSE, SC_MAXIMIZE, SC_MINIMIZE, SC_MOVE, SC_RESTORE, SC_SIZE,
+    SM_DIGITIZER, SWP_ASYNCWINDOWPOS, SWP_NOACTIVATE, SWP_NOSIZE, SWP_NOZORDER, TPM_LEFTALIGN,
+    TPM_RETURNCMD, WDA_EXCLUDEFROMCAPTURE, WDA_MONITOR, WDA_NONE, WM_NCLBUTTONDOWN, WM_SYSCOMMAND,
+    WNDCLASSEXW,
 };
 
 use tracing::warn;
@@ -50,13 +66,17 @@ use crate::cursor::Cursor;
 use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
 use crate::error::{ExternalError, NotSupportedError, OsError as RootOsError};
 use crate::icon::Icon;
-use crate::platform::windows::{BackdropType, Color, CornerPreference};
-use crate::platform_impl::platform::dark_mode::try_theme;
+use crate::ime::SurroundingText;
+use crate::platform::windows::{
+    BackdropType, Color, CornerPreference, DpiAwareness, DwmConfig, HitTestHandler, HookResult,
+    MessageHookId, PromotionPolicy,
+};
+use crate::platform_impl::platform::dark_mode::{apply_dwm_config, try_theme};
 use crate::platform_impl::platform::definitions::{
     CLSID_TaskbarList, IID_ITaskbarList, IID_ITaskbarList2, ITaskbarList, ITaskbarList2,
 };
 use crate::platform_impl::platform::dpi::{
-    dpi_to_scale_factor, enable_non_client_dpi_scaling, hwnd_dpi,
+    dpi_to_scale_factor, enable_non_client_dpi_scaling, hwnd_dpi, window_dpi_awareness,
 };
 use crate::platform_impl::platform::drop_handler::FileDropHandler;
 use crate::platform_impl::platform::event_loop::{self, ActiveEventLoop, DESTROY_MSG_ID};
@@ -65,15 +85,20 @@ use crate::platform_impl::platform::ime::ImeContext;
 use crate::platform_impl::platform::keyboard::KeyEventBuilder;
 use crate::platform_impl::platform::monitor::{self, MonitorHandle};
 use crate::platform_impl::platform::window_state::{
-    CursorFlags, SavedWindow, WindowFlags, WindowState,
+    CursorFlags, MessageHook, SavedWindow, WindowFlags, WindowState,
 };
-use crate::platform_impl::platform::{util, Fullscreen, SelectedCursor, WindowId};
+use crate::platform_impl::platform::{share, tray, util, Fullscreen, SelectedCursor, WindowId};
 use crate::window::{
-    CursorGrabMode, ImePurpose, ResizeDirection, Theme, UserAttentionType, WindowAttributes,
-    WindowButtons, WindowLevel,
+    CapturePolicy, CoordinateTransform, CursorGrabMode, FocusReason, FrameRateRange, ImePurpose,
+    KeyboardHints, PerformanceHint, Region, ResizeDirection, ShareData, TextServicesConfig, Theme,
+    TilingState, UserAttentionType, WindowAttributes, WindowButtons, WindowCapabilities,
+    WindowLevel, WindowVisibility,
 };
 
 /// The Win32 implementation of the main `Window` object.
+/// The part of a window that the closures of `Window::maybe_queue_on_main` run with.
+pub(crate) type MainThreadWindow = Window;
+
 pub(crate) struct Window {
     /// Main handle for the window.
     window: HWND,
@@ -148,6 +173,12 @@ impl Window {
         Some(unsafe { IsWindowVisible(self.window) == 1 })
     }
 
+    #[inline]
+    pub fn hide_to_tray(&self) {
+        // Hidden from the window procedure, which reports the surface as destroyed.
+        unsafe { PostMessageW(self.hwnd(), tray::HIDE_TO_TRAY_MSG_ID.get(), 0, 0) };
+    }
+
     #[inline]
     pub fn request_redraw(&self) {
         // NOTE: mark that we requested a redraw to handle requests during `WM_PAINT` handling.
@@ -160,6 +191,79 @@ impl Window {
     #[inline]
     pub fn pre_present_notify(&self) {}
 
+    pub fn present_rgba(
+        &self,
+        buffer: &[u8],
+        size: PhysicalSize<u32>,
+    ) -> Result<(), ExternalError> {
+        // Device independent bitmaps of 32 bits per pixel are in BGRX order.
+        let pixels: Vec<u8> =
+            buffer.chunks_exact(4).flat_map(|pixel| [pixel[2], pixel[1], pixel[0], 0]).collect();
+        let info = BITMAPINFO {
+            bmiHeader: BITMAPINFOHEADER {
+                biSize: mem::size_of::<BITMAPINFOHEADER>() as u32,
+                biWidth: size.width as i32,
+                // A negative height makes the rows go from the top down.
+                biHeight: -(size.height as i32),
+                biPlanes: 1,
+                biBitCount: 32,
+                biCompression: BI_RGB,
+                ..unsafe { mem::zeroed() }
+            },
+            ..unsafe { mem::zeroed() }
+        };
+
+        let inner_size = self.inner_size();
+        let hdc = unsafe { GetDC(self.hwnd()) };
+        if hdc == 0 {
+            return Err(ExternalError::Os(os_error!(io::Error::last_os_error())));
+        }
+        let lines = unsafe {
+            StretchDIBits(
+                hdc,
+                0,
+                0,
+                inner_size.width as i32,
+                inner_size.height as i32,
+                0,
+                0,
+                size.width as i32,
+                size.height as i32,
+                pixels.as_ptr().cast(),
+                &info,
+                DIB_RGB_COLORS,
+                SRCCOPY,
+            )
+        };
+        unsafe { ReleaseDC(self.hwnd(), hdc) };
+
+        if lines == 0 && size.width != 0 && size.height != 0 {
+            return Err(ExternalError::Os(os_error!(io::Error::new(
+                io::ErrorKind::Other,
+                "`StretchDIBits` failed"
+            ))));
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn notify_first_frame_presented(&self) {
+        if mem::take(&mut self.window_state_lock().splash_screen) {
+            self.set_cloaked(false);
+        }
+    }
+
+    fn set_cloaked(&self, cloaked: bool) {
+        unsafe {
+            DwmSetWindowAttribute(
+                self.hwnd(),
+                DWMWA_CLOAK as u32,
+                &BOOL::from(cloaked) as *const _ as _,
+                mem::size_of::<BOOL>() as _,
+            );
+        }
+    }
+
     #[inline]
     pub fn outer_position(&self) -> Result<PhysicalPosition<i32>, NotSupportedError> {
         util::WindowArea::Outer
@@ -183,6 +287,10 @@ impl Window {
         Ok(PhysicalPosition::new(position.x, position.y))
     }
 
+    pub fn coordinate_transform(&self) -> CoordinateTransform {
+        CoordinateTransform::from_scale_factor(self.inner_position().ok(), self.scale_factor())
+    }
+
     #[inline]
     pub fn set_outer_position(&self, position: Position) {
         let (x, y): (i32, i32) = position.to_physical::<i32>(self.scale_factor()).into();
@@ -419,13 +527,51 @@ impl Window {
                 };
                 self.window_state_lock().mouse.selected_cursor =
                     SelectedCursor::Custom(new_cursor.clone());
+                let scale_factor = self.scale_factor();
                 self.thread_executor.execute_in_thread(move || unsafe {
-                    SetCursor(new_cursor.as_raw_handle());
+                    SetCursor(new_cursor.as_raw_handle(scale_factor));
                 });
             },
         }
     }
 
+    #[inline]
+    pub fn set_cursor_for_device(
+        &self,
+        _device_id: crate::event::DeviceId,
+        _cursor: Cursor,
+    ) -> Result<(), ExternalError> {
+        Err(ExternalError::NotSupported(NotSupportedError::new()))
+    }
+
+    #[inline]
+    pub fn set_pointer_capture(
+        &self,
+        _device_id: crate::event::DeviceId,
+        capture: bool,
+    ) -> Result<(), ExternalError> {
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            let mut window_state = window_state.lock().unwrap();
+            if window_state.mouse.pointer_captured == capture {
+                return;
+            }
+            window_state.mouse.pointer_captured = capture;
+            // Keep the capture taken while a button is pressed.
+            unsafe {
+                if capture {
+                    event_loop::capture_mouse(window, &mut window_state);
+                } else {
+                    event_loop::release_mouse(window_state);
+                }
+            }
+        });
+        Ok(())
+    }
+
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
         let confine = match mode {
@@ -494,6 +640,20 @@ impl Window {
         Ok(())
     }
 
+    #[inline]
+    pub fn cursor_position(&self) -> Result<PhysicalPosition<f64>, ExternalError> {
+        let mut point = POINT { x: 0, y: 0 };
+        unsafe {
+            if GetCursorPos(&mut point) == false.into() {
+                return Err(ExternalError::Os(os_error!(io::Error::last_os_error())));
+            }
+            if ScreenToClient(self.hwnd(), &mut point) == false.into() {
+                return Err(ExternalError::Os(os_error!(io::Error::last_os_error())));
+            }
+        }
+        Ok(PhysicalPosition::new(point.x as f64, point.y as f64))
+    }
+
     unsafe fn handle_os_dragging(&self, wparam: WPARAM) {
         let window = self.window;
         let window_state = self.window_state.clone();
@@ -638,7 +798,11 @@ impl Window {
         let window = self.window;
         let window_state = Arc::clone(&self.window_state);
         self.thread_executor.execute_in_thread(move || {
-            WindowState::set_window_flags(window_state.lock().unwrap(), window, |f| {
+            let mut window_state = window_state.lock().unwrap();
+            if window_state.hittest_regions.take().is_some() {
+                unsafe { KillTimer(window, event_loop::HITTEST_TIMER_ID) };
+            }
+            WindowState::set_window_flags(window_state, window, |f| {
                 f.set(WindowFlags::IGNORE_CURSOR_EVENT, !hittest)
             });
         });
@@ -646,6 +810,26 @@ impl Window {
         Ok(())
     }
 
+    #[inline]
+    pub fn set_hittest_regions(&self, regions: &[Region]) -> Result<(), ExternalError> {
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+        let regions = regions.to_vec();
+        self.thread_executor.execute_in_thread(move || {
+            window_state.lock().unwrap().hittest_regions = Some(regions);
+            unsafe {
+                SetTimer(
+                    window,
+                    event_loop::HITTEST_TIMER_ID,
+                    event_loop::HITTEST_TIMER_INTERVAL,
+                    None,
+                )
+            };
+        });
+
+        Ok(())
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId {
         WindowId(self.hwnd())
@@ -850,6 +1034,11 @@ impl Window {
         });
     }
 
+    #[inline]
+    pub fn capabilities(&self) -> WindowCapabilities {
+        WindowCapabilities::all()
+    }
+
     #[inline]
     pub fn set_decorations(&self, decorations: bool) {
         let window = self.window;
@@ -928,13 +1117,53 @@ impl Window {
         let window = self.window;
         let state = self.window_state.clone();
         self.thread_executor.execute_in_thread(move || unsafe {
-            state.lock().unwrap().ime_allowed = allowed;
-            ImeContext::set_ime_allowed(window, allowed);
+            let purpose = {
+                let mut state = state.lock().unwrap();
+                state.ime_allowed = allowed;
+                state.ime_purpose
+            };
+            ImeContext::set_ime_allowed(window, allowed && purpose != ImePurpose::Password);
+        })
+    }
+
+    #[inline]
+    pub fn set_ime_purpose(&self, purpose: ImePurpose) {
+        let window = self.window;
+        let state = self.window_state.clone();
+        self.thread_executor.execute_in_thread(move || unsafe {
+            let allowed = {
+                let mut state = state.lock().unwrap();
+                state.ime_purpose = purpose;
+                state.ime_allowed
+            };
+            ImeContext::set_ime_allowed(window, allowed && purpose != ImePurpose::Password);
         })
     }
 
     #[inline]
-    pub fn set_ime_purpose(&self, _purpose: ImePurpose) {}
+    pub fn set_ime_surrounding_text(&self, text: SurroundingText) {
+        self.window_state_lock().ime_surrounding_text = Some(text);
+    }
+
+    #[inline]
+    pub fn set_text_services(&self, config: TextServicesConfig) {
+        let window = self.window;
+        self.thread_executor.execute_in_thread(move || {
+            // The input scope is the only way to influence the text services of the system
+            // without implementing TSF, and text suggestions aren't offered for alphanumeric input.
+            let scope = if config.suggestions && config.autocorrect {
+                IS_DEFAULT
+            } else {
+                IS_ALPHANUMERIC_HALFWIDTH
+            };
+            if let Some(set_input_scope) = *util::SET_INPUT_SCOPE {
+                unsafe { set_input_scope(window, scope) };
+            }
+        })
+    }
+
+    #[inline]
+    pub fn set_keyboard_hints(&self, _hints: KeyboardHints) {}
 
     #[inline]
     pub fn request_user_attention(&self, request_type: Option<UserAttentionType>) {
@@ -965,7 +1194,16 @@ impl Window {
 
     #[inline]
     pub fn set_theme(&self, theme: Option<Theme>) {
-        try_theme(self.window, theme);
+        let new_theme = try_theme(self.window, theme);
+        let mut window_state = self.window_state_lock();
+        window_state.preferred_theme = theme;
+        window_state.current_theme = new_theme;
+        let dwm_config = window_state.dwm_config;
+        drop(window_state);
+
+        if let Some(config) = dwm_config {
+            apply_dwm_config(self.window, &config, new_theme);
+        }
     }
 
     #[inline]
@@ -979,6 +1217,14 @@ impl Window {
         window_state.has_active_focus()
     }
 
+    pub fn visibility(&self) -> WindowVisibility {
+        self.window_state.lock().unwrap().visibility(self.window)
+    }
+
+    pub fn tiling_state(&self) -> TilingState {
+        self.window_state.lock().unwrap().tiling_state(self.window)
+    }
+
     pub fn title(&self) -> String {
         let len = unsafe { GetWindowTextLengthW(self.window) } + 1;
         let mut buf = vec![0; len as usize];
@@ -1005,6 +1251,39 @@ impl Window {
         });
     }
 
+    #[inline]
+    pub fn set_fullscreen_optimizations(&self, enabled: bool) {
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            WindowState::set_window_flags(window_state.lock().unwrap(), window, |f| {
+                f.set(WindowFlags::FULLSCREEN_OPTIMIZATIONS, enabled)
+            });
+        });
+    }
+
+    pub fn is_fullscreen_optimized(&self) -> bool {
+        let window_flags = self.window_state_lock().window_flags;
+        // Layered and transparent windows are always composed.
+        if !window_flags.is_fullscreen_optimized()
+            || window_flags.intersects(WindowFlags::IGNORE_CURSOR_EVENT | WindowFlags::TRANSPARENT)
+        {
+            return false;
+        }
+
+        let hmonitor = monitor::current_monitor(self.hwnd()).hmonitor();
+        let (Ok(client), Ok(monitor_info)) =
+            (util::WindowArea::Inner.get_rect(self.hwnd()), monitor::get_monitor_info(hmonitor))
+        else {
+            return false;
+        };
+        let monitor = monitor_info.monitorInfo.rcMonitor;
+        (client.left, client.top, client.right, client.bottom)
+            == (monitor.left, monitor.top, monitor.right, monitor.bottom)
+    }
+
     #[inline]
     pub fn set_system_backdrop(&self, backdrop_type: BackdropType) {
         unsafe {
@@ -1030,6 +1309,42 @@ impl Window {
         }
     }
 
+    #[inline]
+    pub fn focus_window_with(&self, reason: FocusReason) {
+        if reason == FocusReason::UserInitiated {
+            return self.focus_window();
+        }
+
+        let window_flags = self.window_state_lock().window_flags();
+
+        let is_visible = window_flags.contains(WindowFlags::VISIBLE);
+        let is_minimized = util::is_minimized(self.hwnd());
+        let is_foreground = self.window == unsafe { GetForegroundWindow() };
+
+        // Unlike `force_window_active`, this follows the foreground lock of the system, which
+        // only lets the application take focus when it is allowed to.
+        if is_visible
+            && !is_minimized
+            && !is_foreground
+            && unsafe { SetForegroundWindow(self.window) } == false.into()
+        {
+            self.request_user_attention(Some(UserAttentionType::Informational));
+        }
+    }
+
+    #[inline]
+    pub fn set_focusable(&self, focusable: bool) {
+        let window = self.window;
+        let window_state = Arc::clone(&self.window_state);
+
+        self.thread_executor.execute_in_thread(move || {
+            let _ = &window;
+            WindowState::set_window_flags(window_state.lock().unwrap(), window, |f| {
+                f.set(WindowFlags::NO_ACTIVATE, !focusable)
+            });
+        });
+    }
+
     #[inline]
     pub fn set_content_protected(&self, protected: bool) {
         unsafe {
@@ -1040,6 +1355,59 @@ impl Window {
         };
     }
 
+    #[inline]
+    pub fn set_capture_policy(&self, policy: CapturePolicy) {
+        let affinity = match policy {
+            CapturePolicy::Allow => WDA_NONE,
+            CapturePolicy::ExcludeFromCapture => WDA_EXCLUDEFROMCAPTURE,
+            CapturePolicy::Protected => WDA_MONITOR,
+        };
+        // `WDA_EXCLUDEFROMCAPTURE` fails before Windows 10 version 2004.
+        if unsafe { SetWindowDisplayAffinity(self.hwnd(), affinity) } == false.into()
+            && affinity == WDA_EXCLUDEFROMCAPTURE
+        {
+            unsafe { SetWindowDisplayAffinity(self.hwnd(), WDA_MONITOR) };
+        }
+    }
+
+    #[inline]
+    pub fn set_performance_hint(&self, hint: PerformanceHint) {
+        // Power throttling (EcoQoS) is applied by the system unless the process opts out, the
+        // default leaves it to the system again.
+        let control_mask = match hint {
+            PerformanceHint::Default => 0,
+            PerformanceHint::Game => PROCESS_POWER_THROTTLING_EXECUTION_SPEED,
+            PerformanceHint::Realtime => {
+                PROCESS_POWER_THROTTLING_EXECUTION_SPEED
+                    | PROCESS_POWER_THROTTLING_IGNORE_TIMER_RESOLUTION
+            },
+        };
+        let state = PROCESS_POWER_THROTTLING_STATE {
+            Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+            ControlMask: control_mask,
+            StateMask: 0,
+        };
+        // Not available before Windows 8.
+        if let Some(set_process_information) = *util::SET_PROCESS_INFORMATION {
+            unsafe {
+                set_process_information(
+                    GetCurrentProcess(),
+                    ProcessPowerThrottling,
+                    &state as *const PROCESS_POWER_THROTTLING_STATE as *const c_void,
+                    mem::size_of::<PROCESS_POWER_THROTTLING_STATE>() as u32,
+                )
+            };
+        }
+    }
+
+    #[inline]
+    pub fn set_preferred_frame_rate_range(&self, _range: Option<FrameRateRange>) {}
+
+    #[inline]
+    pub fn share(&self, data: ShareData) {
+        share::share(self.hwnd(), data)
+    }
+
     #[inline]
     pub fn reset_dead_keys(&self) {
         // `ToUnicode` consumes the dead-key by default, so we are constructing a fake (but valid)
@@ -1062,6 +1430,10 @@ impl Window {
 
     #[inline]
     pub fn set_border_color(&self, color: Color) {
+        if let Some(config) = &mut self.window_state_lock().dwm_config {
+            config.border_color = Some(color);
+        }
+
         unsafe {
             DwmSetWindowAttribute(
                 self.hwnd(),
@@ -1074,6 +1446,10 @@ impl Window {
 
     #[inline]
     pub fn set_title_background_color(&self, color: Color) {
+        if let Some(config) = &mut self.window_state_lock().dwm_config {
+            config.caption_color = Some(color);
+        }
+
         unsafe {
             DwmSetWindowAttribute(
                 self.hwnd(),
@@ -1086,6 +1462,10 @@ impl Window {
 
     #[inline]
     pub fn set_title_text_color(&self, color: Color) {
+        if let Some(config) = &mut self.window_state_lock().dwm_config {
+            config.text_color = Some(color);
+        }
+
         unsafe {
             DwmSetWindowAttribute(
                 self.hwnd(),
@@ -1098,6 +1478,10 @@ impl Window {
 
     #[inline]
     pub fn set_corner_preference(&self, preference: CornerPreference) {
+        if let Some(config) = &mut self.window_state_lock().dwm_config {
+            config.corner_preference = Some(preference);
+        }
+
         unsafe {
             DwmSetWindowAttribute(
                 self.hwnd(),
@@ -1107,6 +1491,92 @@ impl Window {
             );
         }
     }
+
+    #[inline]
+    pub fn set_dwm_attributes(&self, config: DwmConfig) {
+        let mut window_state = self.window_state_lock();
+        window_state.dwm_config = Some(config);
+        let current_theme = window_state.current_theme;
+        drop(window_state);
+
+        apply_dwm_config(self.window, &config, current_theme);
+    }
+
+    #[inline]
+    pub fn dpi_awareness(&self) -> DpiAwareness {
+        window_dpi_awareness(self.hwnd())
+    }
+
+    #[inline]
+    pub fn set_caption_button_bounds(&self, maximize_button: Option<(Position, Size)>) {
+        self.window_state_lock().maximize_button_bounds = maximize_button;
+    }
+
+    #[inline]
+    pub fn set_hit_test_handler(&self, handler: Option<HitTestHandler>) {
+        self.window_state_lock().hit_test_handler = handler.map(Arc::from);
+    }
+
+    pub fn set_pointer_promotion(&self, policy: PromotionPolicy) {
+        self.window_state_lock().pointer_promotion = policy;
+
+        const FEEDBACK_TYPES: [FEEDBACK_TYPE; 11] = [
+            FEEDBACK_TOUCH_CONTACTVISUALIZATION,
+            FEEDBACK_PEN_BARRELVISUALIZATION,
+            FEEDBACK_PEN_TAP,
+            FEEDBACK_PEN_DOUBLETAP,
+            FEEDBACK_PEN_PRESSANDHOLD,
+            FEEDBACK_PEN_RIGHTTAP,
+            FEEDBACK_TOUCH_TAP,
+            FEEDBACK_TOUCH_DOUBLETAP,
+            FEEDBACK_TOUCH_PRESSANDHOLD,
+            FEEDBACK_TOUCH_RIGHTTAP,
+            FEEDBACK_GESTURE_PRESSANDTAP,
+        ];
+
+        if let Some(set_window_feedback_setting) = *util::SET_WINDOW_FEEDBACK_SETTING {
+            let disabled = BOOL::from(false);
+            for feedback in FEEDBACK_TYPES {
+                unsafe {
+                    if policy.visual_feedback {
+                        // Passing no configuration restores the default.
+                        set_window_feedback_setting(self.hwnd(), feedback, 0, 0, ptr::null());
+                    } else {
+                        set_window_feedback_setting(
+                            self.hwnd(),
+                            feedback,
+                            0,
+                            mem::size_of::<BOOL>() as u32,
+                            &disabled as *const _ as _,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn add_message_hook<F>(&self, priority: i32, hook: F) -> MessageHookId
+    where
+        F: FnMut(HWND, u32, WPARAM, LPARAM) -> HookResult + Send + 'static,
+    {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        let id = MessageHookId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+
+        let mut window_state = self.window_state_lock();
+        let hooks = &mut window_state.message_hooks;
+        // Insert after the hooks with the same priority, so that those run first.
+        let index = hooks.partition_point(|hook| hook.priority >= priority);
+        hooks.insert(index, MessageHook { id, priority, hook: Arc::new(Mutex::new(hook)) });
+        id
+    }
+
+    pub fn remove_message_hook(&self, id: MessageHookId) -> bool {
+        let mut window_state = self.window_state_lock();
+        let hooks = &mut window_state.message_hooks;
+        let len = hooks.len();
+        hooks.retain(|hook| hook.id != id);
+        hooks.len() != len
+    }
 }
 
 impl Drop for Window {
@@ -1263,6 +1733,11 @@ impl<'a> InitData<'a> {
 
         win.set_cursor(attributes.cursor);
 
+        // Cloak before the window is shown, so the empty window is never drawn.
+        if attributes.splash_screen {
+            win.set_cloaked(true);
+        }
+
         // Set visible before setting the size to ensure the
         // attribute is correctly applied.
         win.set_visible(attributes.visible);
@@ -1327,6 +1802,8 @@ unsafe fn init(
     window_flags
         .set(WindowFlags::NO_BACK_BUFFER, attributes.platform_specific.no_redirection_bitmap);
     window_flags.set(WindowFlags::MARKER_ACTIVATE, attributes.active);
+    window_flags.set(WindowFlags::NO_ACTIVATE, !attributes.focusable);
+    window_flags.set(WindowFlags::FULLSCREEN_OPTIMIZATIONS, true);
     window_flags.set(WindowFlags::TRANSPARENT, attributes.transparent);
     // WindowFlags::VISIBLE and MAXIMIZED are set down below after the window has been configured.
     window_flags.set(WindowFlags::RESIZABLE, attributes.resizable);
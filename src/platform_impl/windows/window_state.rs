@@ -1,23 +1,28 @@
-use crate::dpi::{PhysicalPosition, PhysicalSize, Size};
+use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
 use crate::icon::Icon;
+use crate::ime::SurroundingText;
 use crate::keyboard::ModifiersState;
+use crate::platform::windows::{
+    DwmConfig, HitTestResult, HookResult, MessageHookId, PromotionPolicy,
+};
 use crate::platform_impl::platform::{event_loop, util, Fullscreen, SelectedCursor};
-use crate::window::{Theme, WindowAttributes};
+use crate::window::{ImePurpose, Region, Theme, TilingState, WindowAttributes, WindowVisibility};
 use bitflags::bitflags;
 use std::io;
-use std::sync::MutexGuard;
-use windows_sys::Win32::Foundation::{HWND, RECT};
+use std::sync::{Arc, Mutex, MutexGuard};
+use windows_sys::Win32::Foundation::{HWND, LPARAM, RECT, WPARAM};
 use windows_sys::Win32::Graphics::Gdi::InvalidateRgn;
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    AdjustWindowRectEx, EnableMenuItem, GetMenu, GetSystemMenu, GetWindowLongW, SendMessageW,
-    SetWindowLongW, SetWindowPos, ShowWindow, GWL_EXSTYLE, GWL_STYLE, HWND_BOTTOM, HWND_NOTOPMOST,
-    HWND_TOPMOST, MF_BYCOMMAND, MF_DISABLED, MF_ENABLED, SC_CLOSE, SWP_ASYNCWINDOWPOS,
-    SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOREPOSITION, SWP_NOSIZE, SWP_NOZORDER,
-    SW_HIDE, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE, SW_SHOW, SW_SHOWNOACTIVATE, WINDOWPLACEMENT,
-    WINDOW_EX_STYLE, WINDOW_STYLE, WS_BORDER, WS_CAPTION, WS_CHILD, WS_CLIPCHILDREN,
-    WS_CLIPSIBLINGS, WS_EX_ACCEPTFILES, WS_EX_APPWINDOW, WS_EX_LAYERED, WS_EX_NOREDIRECTIONBITMAP,
-    WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_EX_WINDOWEDGE, WS_MAXIMIZE, WS_MAXIMIZEBOX, WS_MINIMIZE,
-    WS_MINIMIZEBOX, WS_OVERLAPPEDWINDOW, WS_POPUP, WS_SIZEBOX, WS_SYSMENU, WS_VISIBLE,
+    AdjustWindowRectEx, EnableMenuItem, GetMenu, GetSystemMenu, GetWindowLongW, IsWindowVisible,
+    SendMessageW, SetWindowLongW, SetWindowPos, ShowWindow, GWL_EXSTYLE, GWL_STYLE, HWND_BOTTOM,
+    HWND_NOTOPMOST, HWND_TOPMOST, MF_BYCOMMAND, MF_DISABLED, MF_ENABLED, SC_CLOSE,
+    SWP_ASYNCWINDOWPOS, SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOREPOSITION, SWP_NOSIZE,
+    SWP_NOZORDER, SW_HIDE, SW_MAXIMIZE, SW_MINIMIZE, SW_RESTORE, SW_SHOW, SW_SHOWNOACTIVATE,
+    WINDOWPLACEMENT, WINDOW_EX_STYLE, WINDOW_STYLE, WS_BORDER, WS_CAPTION, WS_CHILD,
+    WS_CLIPCHILDREN, WS_CLIPSIBLINGS, WS_EX_ACCEPTFILES, WS_EX_APPWINDOW, WS_EX_LAYERED,
+    WS_EX_NOACTIVATE, WS_EX_NOREDIRECTIONBITMAP, WS_EX_TOPMOST, WS_EX_TRANSPARENT,
+    WS_EX_WINDOWEDGE, WS_MAXIMIZE, WS_MAXIMIZEBOX, WS_MINIMIZE, WS_MINIMIZEBOX,
+    WS_OVERLAPPEDWINDOW, WS_POPUP, WS_SIZEBOX, WS_SYSMENU, WS_VISIBLE,
 };
 
 /// Contains information about states and the window that the callback is going to use.
@@ -36,26 +41,69 @@ pub(crate) struct WindowState {
     pub saved_window: Option<SavedWindow>,
     pub scale_factor: f64,
 
+    /// The DPI and inner size negotiated in `WM_GETDPISCALEDSIZE`, consumed by the following
+    /// `WM_DPICHANGED`.
+    pub dpi_scaled_size: Option<(u32, PhysicalSize<u32>)>,
+
     pub modifiers_state: ModifiersState,
     pub fullscreen: Option<Fullscreen>,
     pub current_theme: Theme,
     pub preferred_theme: Option<Theme>,
+    /// Applied again whenever the theme changes.
+    pub dwm_config: Option<DwmConfig>,
 
     pub window_flags: WindowFlags,
 
     pub ime_state: ImeState,
     pub ime_allowed: bool,
+    /// The IME is kept disabled for [`ImePurpose::Password`], like in password edit controls.
+    pub ime_purpose: ImePurpose,
+    pub ime_surrounding_text: Option<SurroundingText>,
 
     // Used by WM_NCACTIVATE, WM_SETFOCUS and WM_KILLFOCUS
     pub is_active: bool,
     pub is_focused: bool,
 
+    /// The visibility last reported with `WindowEvent::VisibilityChanged`.
+    pub last_visibility: WindowVisibility,
+    /// The tiling state last reported with `WindowEvent::TilingStateChanged`.
+    pub last_tiling_state: TilingState,
+    /// Whether the window is hidden to the tray, with its icon added.
+    pub in_tray: bool,
+
     // Flag whether redraw was requested.
     pub redraw_requested: bool,
 
+    /// Whether `WindowEvent::Resized` waits for the application to present the new size.
+    pub synchronized_resize: bool,
+
+    /// Whether the window is cloaked until the first frame was presented.
+    pub splash_screen: bool,
+
     pub dragging: bool,
 
     pub skip_taskbar: bool,
+
+    /// Used by `WM_NCHITTEST`.
+    pub maximize_button_bounds: Option<(Position, Size)>,
+    pub hit_test_handler:
+        Option<Arc<dyn Fn(PhysicalPosition<i32>) -> Option<HitTestResult> + Send + Sync>>,
+
+    /// Used to drop the mouse messages synthesized from touch and pen input.
+    pub pointer_promotion: PromotionPolicy,
+
+    /// Sorted by descending priority, see `public_window_callback_inner`.
+    pub message_hooks: Vec<MessageHook>,
+
+    /// The regions which catch cursor events, checked on `HITTEST_TIMER_ID`.
+    pub hittest_regions: Option<Vec<Region>>,
+}
+
+pub(crate) struct MessageHook {
+    pub id: MessageHookId,
+    pub priority: i32,
+    // Locked while the hook runs, so that it isn't re-entered.
+    pub hook: Arc<Mutex<dyn FnMut(HWND, u32, WPARAM, LPARAM) -> HookResult + Send>>,
 }
 
 #[derive(Clone)]
@@ -67,8 +115,14 @@ pub struct SavedWindow {
 pub struct MouseProperties {
     pub(crate) selected_cursor: SelectedCursor,
     pub capture_count: u32,
+    /// Whether the mouse was captured with [`Window::set_pointer_capture()`].
+    ///
+    /// [`Window::set_pointer_capture()`]: crate::window::Window::set_pointer_capture
+    pub pointer_captured: bool,
     cursor_flags: CursorFlags,
     pub last_position: Option<PhysicalPosition<f64>>,
+    /// The window the cursor is over while the mouse is captured by this window.
+    pub drag_target: Option<HWND>,
 }
 
 bitflags! {
@@ -123,6 +177,12 @@ bitflags! {
 
         const CLIP_CHILDREN = 1 << 22;
 
+        /// The window doesn't take focus when it is shown or clicked.
+        const NO_ACTIVATE = 1 << 23;
+
+        /// Set up fullscreen windows for independent flip presentation.
+        const FULLSCREEN_OPTIMIZATIONS = 1 << 24;
+
         const EXCLUSIVE_FULLSCREEN_OR_MASK = WindowFlags::ALWAYS_ON_TOP.bits();
     }
 }
@@ -145,8 +205,10 @@ impl WindowState {
             mouse: MouseProperties {
                 selected_cursor: SelectedCursor::default(),
                 capture_count: 0,
+                pointer_captured: false,
                 cursor_flags: CursorFlags::empty(),
                 last_position: None,
+                drag_target: None,
             },
 
             min_size: attributes.min_inner_size,
@@ -159,23 +221,38 @@ impl WindowState {
 
             saved_window: None,
             scale_factor,
+            dpi_scaled_size: None,
 
             modifiers_state: ModifiersState::default(),
             fullscreen: None,
             current_theme,
             preferred_theme,
+            dwm_config: None,
             window_flags: WindowFlags::empty(),
 
             ime_state: ImeState::Disabled,
             ime_allowed: false,
+            ime_purpose: ImePurpose::Normal,
+            ime_surrounding_text: None,
 
             is_active: false,
             is_focused: false,
+            last_visibility: WindowVisibility::Hidden,
+            last_tiling_state: TilingState::Floating,
+            in_tray: false,
             redraw_requested: false,
+            synchronized_resize: attributes.synchronized_resize,
+            splash_screen: attributes.splash_screen,
 
             dragging: false,
 
             skip_taskbar: false,
+
+            maximize_button_bounds: None,
+            hit_test_handler: None,
+            pointer_promotion: PromotionPolicy::default(),
+            message_hooks: Vec::new(),
+            hittest_regions: None,
         }
     }
 
@@ -206,6 +283,22 @@ impl WindowState {
         self.is_active && self.is_focused
     }
 
+    pub fn visibility(&self, window: HWND) -> WindowVisibility {
+        let visible = unsafe { IsWindowVisible(window) } != 0;
+        // Occlusion isn't reported on Windows.
+        WindowVisibility::new(visible, util::is_minimized(window), false, self.has_active_focus())
+    }
+
+    pub fn tiling_state(&self, window: HWND) -> TilingState {
+        if self.fullscreen.is_some() {
+            TilingState::Fullscreen
+        } else if util::is_maximized(window) {
+            TilingState::Maximized
+        } else {
+            util::tiled_edges(window).map_or(TilingState::Floating, TilingState::Tiled)
+        }
+    }
+
     // Updates is_active and returns whether active-focus state has changed
     pub fn set_active(&mut self, is_active: bool) -> bool {
         let old = self.has_active_focus();
@@ -252,6 +345,16 @@ impl WindowFlags {
         self
     }
 
+    /// Whether the window is fullscreen and set up for independent flip presentation.
+    pub fn is_fullscreen_optimized(self) -> bool {
+        self.contains(WindowFlags::FULLSCREEN_OPTIMIZATIONS)
+            && !self.contains(WindowFlags::CHILD)
+            && self.intersects(
+                WindowFlags::MARKER_EXCLUSIVE_FULLSCREEN
+                    | WindowFlags::MARKER_BORDERLESS_FULLSCREEN,
+            )
+    }
+
     pub fn to_window_styles(self) -> (WINDOW_STYLE, WINDOW_EX_STYLE) {
         // Required styles to properly support common window functionality like aero snap.
         let mut style = WS_CAPTION | WS_BORDER | WS_CLIPSIBLINGS | WS_SYSMENU;
@@ -302,6 +405,9 @@ impl WindowFlags {
         if self.contains(WindowFlags::CLIP_CHILDREN) {
             style |= WS_CLIPCHILDREN;
         }
+        if self.contains(WindowFlags::NO_ACTIVATE) {
+            style_ex |= WS_EX_NOACTIVATE;
+        }
 
         if self.intersects(
             WindowFlags::MARKER_EXCLUSIVE_FULLSCREEN | WindowFlags::MARKER_BORDERLESS_FULLSCREEN,
@@ -309,6 +415,12 @@ impl WindowFlags {
             style &= !WS_OVERLAPPEDWINDOW;
         }
 
+        // A plain popup without any frame, so DWM can present the swap chain directly.
+        if self.is_fullscreen_optimized() {
+            style |= WS_POPUP;
+            style_ex &= !WS_EX_WINDOWEDGE;
+        }
+
         (style, style_ex)
     }
 
@@ -327,6 +439,8 @@ impl WindowFlags {
             let flag = if !self.contains(WindowFlags::MARKER_ACTIVATE) {
                 self.set(WindowFlags::MARKER_ACTIVATE, true);
                 SW_SHOWNOACTIVATE
+            } else if new.contains(WindowFlags::NO_ACTIVATE) {
+                SW_SHOWNOACTIVATE
             } else {
                 SW_SHOW
             };
@@ -424,7 +538,18 @@ impl WindowFlags {
         }
     }
 
-    pub fn adjust_rect(self, hwnd: HWND, mut rect: RECT) -> Result<RECT, io::Error> {
+    pub fn adjust_rect(self, hwnd: HWND, rect: RECT) -> Result<RECT, io::Error> {
+        self.adjust_rect_for_dpi(hwnd, rect, None)
+    }
+
+    /// Like [`WindowFlags::adjust_rect`], but uses `dpi` instead of the window's current DPI when
+    /// `AdjustWindowRectExForDpi` is available.
+    pub fn adjust_rect_for_dpi(
+        self,
+        hwnd: HWND,
+        mut rect: RECT,
+        dpi: Option<u32>,
+    ) -> Result<RECT, io::Error> {
         unsafe {
             let mut style = GetWindowLongW(hwnd, GWL_STYLE) as u32;
             let style_ex = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
@@ -440,7 +565,7 @@ impl WindowFlags {
                 if let (Some(get_dpi_for_window), Some(adjust_window_rect_ex_for_dpi)) =
                     (*util::GET_DPI_FOR_WINDOW, *util::ADJUST_WINDOW_RECT_EX_FOR_DPI)
                 {
-                    let dpi = get_dpi_for_window(hwnd);
+                    let dpi = dpi.unwrap_or_else(|| get_dpi_for_window(hwnd));
                     adjust_window_rect_ex_for_dpi(&mut rect, style, b_menu.into(), style_ex, dpi)
                 } else {
                     AdjustWindowRectEx(&mut rect, style, b_menu.into(), style_ex)
@@ -0,0 +1,212 @@
+//! Dedicated redraw threads of windows, see [`Window::start_redraw_thread()`].
+//!
+//! Each thread waits for redraw requests of its window. While the application handles a resize,
+//! the event loop waits for the current frame to be drawn and holds back the next one, so the
+//! application never resizes its surface in the middle of a frame, and frames are always drawn
+//! with the size the application has seen last.
+//!
+//! [`Window::start_redraw_thread()`]: crate::window::Window::start_redraw_thread
+
+use std::collections::HashMap;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::dpi::PhysicalSize;
+use crate::event::WindowEvent;
+use crate::window::WindowId;
+
+/// A frame to draw on the redraw thread of a window, see [`Window::start_redraw_thread()`].
+///
+/// [`Window::start_redraw_thread()`]: crate::window::Window::start_redraw_thread
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct RedrawFrame {
+    /// The window to draw.
+    pub window_id: WindowId,
+    /// The inner size of the window, as of the last [`WindowEvent::Resized`].
+    pub size: PhysicalSize<u32>,
+    /// The scale factor of the window, as of the last [`WindowEvent::ScaleFactorChanged`].
+    pub scale_factor: f64,
+}
+
+/// The handler drawing the frames of a window.
+pub(crate) type RedrawHandler = Box<dyn FnMut(RedrawFrame) + Send>;
+
+struct State {
+    size: PhysicalSize<u32>,
+    scale_factor: f64,
+    requested: bool,
+    exit: bool,
+    /// Whether the handler is drawing a frame.
+    drawing: bool,
+    /// Whether the application is handling a resize.
+    resizing: bool,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+struct RedrawThread {
+    shared: Arc<Shared>,
+    handle: JoinHandle<()>,
+}
+
+// Windows can be used from any thread, so unlike the other bookkeeping of the event loop this
+// can't be thread local.
+static THREADS: Mutex<Option<HashMap<WindowId, RedrawThread>>> = Mutex::new(None);
+
+fn with_threads<R>(f: impl FnOnce(&mut HashMap<WindowId, RedrawThread>) -> R) -> R {
+    f(THREADS.lock().unwrap().get_or_insert_with(HashMap::new))
+}
+
+fn shared(window_id: WindowId) -> Option<Arc<Shared>> {
+    with_threads(|threads| threads.get(&window_id).map(|thread| Arc::clone(&thread.shared)))
+}
+
+/// Start the redraw thread of a window, replacing the previous one.
+pub(crate) fn start(
+    window_id: WindowId,
+    size: PhysicalSize<u32>,
+    scale_factor: f64,
+    mut handler: RedrawHandler,
+) {
+    stop(window_id);
+
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            size,
+            scale_factor,
+            requested: false,
+            exit: false,
+            drawing: false,
+            resizing: false,
+        }),
+        changed: Condvar::new(),
+    });
+    let handle = thread::Builder::new().name(format!("winit redraw {window_id:?}")).spawn({
+        let shared = Arc::clone(&shared);
+        move || loop {
+            let frame = {
+                let mut state = shared.state.lock().unwrap();
+                while !state.exit && (!state.requested || state.resizing) {
+                    state = shared.changed.wait(state).unwrap();
+                }
+                if state.exit {
+                    return;
+                }
+                state.requested = false;
+                state.drawing = true;
+                RedrawFrame { window_id, size: state.size, scale_factor: state.scale_factor }
+            };
+
+            handler(frame);
+
+            shared.state.lock().unwrap().drawing = false;
+            shared.changed.notify_all();
+        }
+    });
+    let handle = handle.expect("failed to spawn the redraw thread");
+
+    with_threads(|threads| threads.insert(window_id, RedrawThread { shared, handle }));
+}
+
+/// Stop the redraw thread of a window, waiting for the current frame to be drawn.
+pub(crate) fn stop(window_id: WindowId) {
+    let Some(thread) = with_threads(|threads| threads.remove(&window_id)) else {
+        return;
+    };
+    thread.shared.state.lock().unwrap().exit = true;
+    thread.shared.changed.notify_all();
+    // The handler itself may drop the window.
+    if thread.handle.thread().id() != thread::current().id() {
+        let _ = thread.handle.join();
+    }
+}
+
+/// Request a frame from the redraw thread of a window, returning `false` if it has none.
+pub(crate) fn request(window_id: WindowId) -> bool {
+    let Some(shared) = shared(window_id) else {
+        return false;
+    };
+    shared.state.lock().unwrap().requested = true;
+    shared.changed.notify_all();
+    true
+}
+
+/// Holds back the frames of a window until dropped, see [`window_event()`].
+pub(crate) struct Resizing(Arc<Shared>);
+
+impl Drop for Resizing {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().resizing = false;
+        self.0.changed.notify_all();
+    }
+}
+
+/// Keep track of the size of windows, returning a guard that holds back frames while the
+/// application handles a resize.
+pub(crate) fn window_event(window_id: WindowId, event: &WindowEvent) -> Option<Resizing> {
+    match event {
+        WindowEvent::Resized { .. } | WindowEvent::ScaleFactorChanged { .. } => (),
+        WindowEvent::Destroyed => {
+            stop(window_id);
+            return None;
+        },
+        _ => return None,
+    }
+
+    let shared = shared(window_id)?;
+    let mut state = shared.state.lock().unwrap();
+    state.resizing = true;
+    while state.drawing {
+        state = shared.changed.wait(state).unwrap();
+    }
+    match *event {
+        WindowEvent::Resized { size, .. } => state.size = size,
+        WindowEvent::ScaleFactorChanged { scale_factor, .. } => state.scale_factor = scale_factor,
+        _ => (),
+    }
+    drop(state);
+
+    Some(Resizing(shared))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn resize() {
+        let window_id = unsafe { WindowId::dummy() };
+        let (sender, receiver) = mpsc::channel();
+        start(
+            window_id,
+            PhysicalSize::new(100, 100),
+            1.0,
+            Box::new(move |frame| sender.send(frame).unwrap()),
+        );
+
+        assert!(request(window_id));
+        let frame = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(frame.size, PhysicalSize::new(100, 100));
+
+        // Frames requested during a resize are drawn after it.
+        let resizing = window_event(window_id, &WindowEvent::Resized {
+            size: PhysicalSize::new(200, 50),
+            frame: None,
+        });
+        assert!(request(window_id));
+        assert!(receiver.recv_timeout(Duration::from_millis(50)).is_err());
+        drop(resizing);
+        let frame = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(frame.size, PhysicalSize::new(200, 50));
+
+        assert!(window_event(window_id, &WindowEvent::Destroyed).is_none());
+        assert!(!request(window_id));
+    }
+}
@@ -0,0 +1,98 @@
+//! The [`SessionInfo`] struct and associated types.
+
+/// Describes the session the application runs in, see
+/// [`ActiveEventLoop::session_info()`](crate::event_loop::ActiveEventLoop::session_info).
+///
+/// This allows applications to degrade gracefully, e.g. to avoid relying on window positions
+/// where they can't be set, without having to inspect environment variables or user agents
+/// themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SessionInfo {
+    kind: SessionKind,
+    wayland: bool,
+    capabilities: SessionCapabilities,
+}
+
+impl SessionInfo {
+    pub(crate) const fn new(
+        kind: SessionKind,
+        wayland: bool,
+        capabilities: SessionCapabilities,
+    ) -> Self {
+        Self { kind, wayland, capabilities }
+    }
+
+    /// Returns the kind of the session.
+    pub const fn kind(&self) -> SessionKind {
+        self.kind
+    }
+
+    /// Returns whether the desktop session is a Wayland session.
+    ///
+    /// This is also the case if the application itself is connected through XWayland.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Always `false`.
+    pub const fn is_wayland(&self) -> bool {
+        self.wayland
+    }
+
+    /// Returns what the application can do in this session.
+    pub const fn capabilities(&self) -> SessionCapabilities {
+        self.capabilities
+    }
+}
+
+/// The kind of a session, see [`SessionInfo::kind()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SessionKind {
+    /// A regular desktop session on a local display.
+    Desktop,
+
+    /// A session displayed on another machine, e.g. through RDP, VNC or X11 forwarding.
+    ///
+    /// Rendering is usually slower, and input latency higher.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Detects Remote Desktop sessions.
+    /// - **X11:** Detects `xrdp` sessions and displays on other hosts, including X11 forwarding
+    ///   over SSH.
+    /// - **Wayland:** Detects `xrdp` sessions.
+    /// - **Android / iOS / macOS / Orbital / Web:** Never reported.
+    Remote,
+
+    /// A session dedicated to a single application, which is shown fullscreen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Reported for installed web apps using the `fullscreen` or `kiosk` display mode.
+    /// - **Android / iOS / macOS / Orbital / Wayland / Windows / X11:** Never reported.
+    Kiosk,
+
+    /// A mobile device, where windows are always fullscreen and input is primarily touch-based.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS:** Always reported.
+    /// - **Web:** Reported for mobile browsers.
+    /// - **macOS / Orbital / Wayland / Windows / X11:** Never reported.
+    Mobile,
+}
+
+bitflags::bitflags! {
+    /// What the application can do in a session, see [`SessionInfo::capabilities()`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct SessionCapabilities: u32 {
+        /// Windows can be placed with
+        /// [`Window::set_outer_position()`](crate::window::Window::set_outer_position).
+        const SET_POSITION = 1 << 0;
+        /// The cursor can be grabbed with
+        /// [`Window::set_cursor_grab()`](crate::window::Window::set_cursor_grab).
+        const GRAB_CURSOR = 1 << 1;
+        /// Windows can be transparent, see
+        /// [`WindowAttributes::with_transparent()`](crate::window::WindowAttributes::with_transparent).
+        const TRANSPARENCY = 1 << 2;
+    }
+}
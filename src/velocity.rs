@@ -0,0 +1,97 @@
+//! Estimating the velocity at the end of gestures, for [`WindowEvent::PanEnded`].
+//!
+//! [`WindowEvent::PanEnded`]: crate::event::WindowEvent::PanEnded
+
+use std::collections::VecDeque;
+
+use crate::dpi::PhysicalPosition;
+
+/// How far back samples are taken into account, in seconds.
+const HORIZON: f64 = 0.1;
+const MAX_SAMPLES: usize = 20;
+
+/// Fits a line through the recent positions of a pointer or a scroll gesture.
+#[derive(Debug, Default)]
+pub(crate) struct VelocityTracker {
+    /// The time in seconds, and the position.
+    samples: VecDeque<(f64, PhysicalPosition<f64>)>,
+}
+
+impl VelocityTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[allow(dead_code)] // Not used on every platform
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Add a position at `time`, which only has to be monotonic.
+    pub fn add(&mut self, time: f64, position: PhysicalPosition<f64>) {
+        if self.samples.len() == MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((time, position));
+    }
+
+    /// The velocity as of the last sample, in units of the positions per second.
+    ///
+    /// Zero if the positions didn't change within the last tenth of a second.
+    pub fn velocity(&self) -> PhysicalPosition<f64> {
+        let Some(&(end, _)) = self.samples.back() else {
+            return PhysicalPosition::new(0.0, 0.0);
+        };
+        let recent = || self.samples.iter().filter(move |(time, _)| end - time <= HORIZON);
+
+        let count = recent().count() as f64;
+        let (sum_t, sum_x, sum_y) = recent()
+            .fold((0.0, 0.0, 0.0), |(t, x, y), (time, position)| {
+                (t + time, x + position.x, y + position.y)
+            });
+        let (mean_t, mean_x, mean_y) = (sum_t / count, sum_x / count, sum_y / count);
+
+        // Least squares slope of both coordinates over time.
+        let (var_t, cov_x, cov_y) =
+            recent().fold((0.0, 0.0, 0.0), |(var_t, cov_x, cov_y), (time, position)| {
+                let dt = time - mean_t;
+                (
+                    var_t + dt * dt,
+                    cov_x + dt * (position.x - mean_x),
+                    cov_y + dt * (position.y - mean_y),
+                )
+            });
+        if var_t <= f64::EPSILON {
+            return PhysicalPosition::new(0.0, 0.0);
+        }
+
+        PhysicalPosition::new(cov_x / var_t, cov_y / var_t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn velocity() {
+        let mut tracker = VelocityTracker::new();
+        assert_eq!(tracker.velocity(), PhysicalPosition::new(0.0, 0.0));
+
+        // 1000 pixels per second to the right, 500 upwards.
+        for i in 0..10 {
+            let time = i as f64 * 0.01;
+            tracker.add(time, PhysicalPosition::new(time * 1000.0, time * -500.0));
+        }
+        let velocity = tracker.velocity();
+        assert!((velocity.x - 1000.0).abs() < 1e-6);
+        assert!((velocity.y + 500.0).abs() < 1e-6);
+
+        // Resting before the end.
+        tracker.add(0.5, PhysicalPosition::new(90.0, -45.0));
+        assert_eq!(tracker.velocity(), PhysicalPosition::new(0.0, 0.0));
+
+        tracker.clear();
+        assert_eq!(tracker.velocity(), PhysicalPosition::new(0.0, 0.0));
+    }
+}
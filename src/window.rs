@@ -1,13 +1,25 @@
 //! The [`Window`] struct and associated types.
 use std::fmt;
-
-use crate::dpi::{PhysicalPosition, PhysicalSize, Position, Size};
-use crate::error::{ExternalError, NotSupportedError};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize, Pixel, Position, Size};
+use crate::error::{ExternalError, NotSupportedError, RequestError};
+use crate::event::DeviceId;
+use crate::event_loop::{long_press, Backend};
+use crate::ime::SurroundingText;
 use crate::monitor::{MonitorHandle, VideoModeHandle};
+use crate::placement::{self, Align, LayoutDirection, MonitorSelector};
 use crate::platform_impl::{self, PlatformSpecificWindowAttributes};
+use crate::{command_queue, drop_negotiation, frame_budget, performance_hint, redraw_thread};
 
-pub use crate::cursor::{BadImage, Cursor, CustomCursor, CustomCursorSource, MAX_CURSOR_SIZE};
+pub use crate::cursor::{
+    BadImage, Cursor, CursorImage, CustomCursor, CustomCursorSource, MAX_CURSOR_SIZE,
+};
+pub use crate::frame_budget::FrameStatistics;
 pub use crate::icon::{BadIcon, Icon};
+pub use crate::redraw_thread::RedrawFrame;
 
 #[doc(inline)]
 pub use cursor_icon::{CursorIcon, ParseError as CursorIconParseError};
@@ -28,12 +40,31 @@ use serde::{Deserialize, Serialize};
 /// window from a thread other than the main, the code is scheduled to run on
 /// the main thread, and your thread may be blocked until that completes.
 ///
+/// ## Event handlers
+///
+/// Setters called from an event handler, like [`Window::set_fullscreen()`], don't change the
+/// window right away. They are queued and applied in the order they were called, across all
+/// windows, once the handler returns and before the next event is delivered, or earlier with
+/// [`Window::flush()`]. The windowing system may be delivering the event, or applying a previous
+/// change which the event reports, and changing the window from within that is a common source of
+/// crashes and deadlocks.
+///
+/// Setters that only fail when the window lacks a capability, like [`Window::set_decorations()`],
+/// check it right away and queue the change. Setters returning the outcome of the change, like
+/// [`Window::request_inner_size()`] or [`Window::set_cursor_grab()`], can't be queued, so they
+/// apply the queued changes first, as [`Window::flush()`] does, which keeps the calls in order.
+/// Getters don't see the changes that are still queued, so call [`Window::flush()`] before them if
+/// that matters. [`Window::request_redraw()`] and [`Window::pre_present_notify()`] aren't queued.
+///
 /// ## Platform-specific
 ///
 /// **Web:** The [`Window`], which is represented by a `HTMLElementCanvas`, can
 /// not be closed by dropping the [`Window`].
 pub struct Window {
-    pub(crate) window: platform_impl::Window,
+    pub(crate) window: Arc<platform_impl::Window>,
+    /// Fixed for the lifetime of the window, so that checking them doesn't need the main thread.
+    capabilities: WindowCapabilities,
+    backend: Backend,
 }
 
 impl fmt::Debug for Window {
@@ -47,7 +78,15 @@ impl Drop for Window {
     ///
     /// See [`Window`] for more details.
     fn drop(&mut self) {
+        redraw_thread::stop(self.id());
+        drop_negotiation::set(self.id(), None);
+        frame_budget::remove(self.id());
+        let performance_hint = performance_hint::set(self.id(), PerformanceHint::Default);
         self.window.maybe_wait_on_main(|w| {
+            if let Some(hint) = performance_hint {
+                w.set_performance_hint(hint);
+            }
+
             // If the window is in exclusive fullscreen, we must restore the desktop
             // video mode (generally this would be done on application exit, but
             // closing the window doesn't necessarily always mean application exit,
@@ -123,7 +162,10 @@ pub struct WindowAttributes {
     pub content_protected: bool,
     pub window_level: WindowLevel,
     pub active: bool,
+    pub focusable: bool,
     pub cursor: Cursor,
+    pub synchronized_resize: bool,
+    pub splash_screen: bool,
     #[cfg(feature = "rwh_06")]
     pub(crate) parent_window: Option<SendSyncRawWindowHandle>,
     pub fullscreen: Option<Fullscreen>,
@@ -158,6 +200,9 @@ impl Default for WindowAttributes {
             #[cfg(feature = "rwh_06")]
             parent_window: None,
             active: true,
+            focusable: true,
+            synchronized_resize: false,
+            splash_screen: false,
             platform_specific: Default::default(),
         }
     }
@@ -448,6 +493,67 @@ impl WindowAttributes {
         self
     }
 
+    /// Whether the window can take the keyboard focus.
+    ///
+    /// Windows which can't take focus don't steal it when they are shown or clicked, which is
+    /// useful for overlays and tool windows. They still receive pointer input.
+    ///
+    /// The default is `true`.
+    ///
+    /// See [`Window::set_focusable`] for details.
+    #[inline]
+    pub fn with_focusable(mut self, focusable: bool) -> Self {
+        self.focusable = focusable;
+        self
+    }
+
+    /// Whether interactive resizes should be synchronized with the presentation of the
+    /// application's content.
+    ///
+    /// When enabled, [`WindowEvent::Resized`] carries a [`ResizeFrame`] which the application
+    /// drops once it presented a frame with the new size. This avoids flickering borders of
+    /// stale content while the user resizes the window.
+    ///
+    /// The default is `false`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / X11 / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    /// [`ResizeFrame`]: crate::event::ResizeFrame
+    #[inline]
+    pub fn with_synchronized_resize(mut self, synchronized_resize: bool) -> Self {
+        self.synchronized_resize = synchronized_resize;
+        self
+    }
+
+    /// Whether the window should keep showing a splash screen until the application presented its
+    /// first frame.
+    ///
+    /// This avoids showing an empty white or black window between launching the application and
+    /// it rendering for the first time. Call [`Window::notify_first_frame_presented()`] to show
+    /// the contents of the window.
+    ///
+    /// The default is `false`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The window is cloaked, i.e. not drawn by the compositor, in the meantime.
+    /// - **macOS:** The window is fully transparent in the meantime.
+    /// - **Web:** The canvas is fully transparent in the meantime, and faded in afterwards. An
+    ///   element to show in its place can be passed with
+    ///   [`WindowAttributesExtWebSys::with_splash_element()`].
+    /// - **Android:** The system splash screen is always shown until the first frame is drawn.
+    /// - **iOS / X11 / Wayland / Orbital:** Unsupported.
+    ///
+    /// [`WindowAttributesExtWebSys::with_splash_element()`]: crate::platform::web::WindowAttributesExtWebSys::with_splash_element
+    #[inline]
+    pub fn with_splash_screen(mut self, splash_screen: bool) -> Self {
+        self.splash_screen = splash_screen;
+        self
+    }
+
     /// Modifies the cursor icon of the window.
     ///
     /// The default is [`CursorIcon::Default`].
@@ -604,6 +710,119 @@ impl Window {
         self.window.maybe_queue_on_main(|w| w.request_redraw())
     }
 
+    /// Applies the changes queued by setters called from the current event handler, of all windows
+    /// and in the order they were called, see the [type-level docs](Self#event-handlers).
+    ///
+    /// Changes queued while the window is applying a change, which is when the handler was called
+    /// for an event reporting that change, are applied right after it instead. Outside of event
+    /// handlers, nothing is queued, so this does nothing.
+    #[inline]
+    pub fn flush(&self) {
+        let _span = tracing::debug_span!("winit::Window::flush",).entered();
+
+        command_queue::flush()
+    }
+
+    /// Starts a dedicated thread drawing the frames of the window with `handler`.
+    ///
+    /// From then on, [`WindowEvent::RedrawRequested`] is delivered to `handler` on that thread
+    /// instead of to the [`ApplicationHandler`], including the redraws requested by the system,
+    /// and [`Window::request_redraw_threaded()`] requests frames without going through the event
+    /// loop. This lets windows draw in parallel, instead of one after the other on the thread of
+    /// the event loop.
+    ///
+    /// While the application handles [`WindowEvent::Resized`] and
+    /// [`WindowEvent::ScaleFactorChanged`], the event loop waits for the current frame to be drawn
+    /// and holds back the next one, so the surface can be resized safely, and each [`RedrawFrame`]
+    /// carries the size the application has seen last. `handler` must thus never wait for the
+    /// event loop.
+    ///
+    /// Starting a thread again replaces the previous one. The thread is stopped by
+    /// [`Window::stop_redraw_thread()`], and when the window is destroyed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Always returns [`RequestError::NotSupported`], since winit can't spawn threads.
+    ///   The canvas can be drawn from a worker with [`transferControlToOffscreen()`] instead.
+    /// - **macOS / iOS / Android / Orbital:** Always returns [`RequestError::NotSupported`].
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`WindowEvent::Resized`]: crate::event::WindowEvent::Resized
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    /// [`ApplicationHandler`]: crate::application::ApplicationHandler
+    /// [`transferControlToOffscreen()`]: https://developer.mozilla.org/en-US/docs/Web/API/HTMLCanvasElement/transferControlToOffscreen
+    pub fn start_redraw_thread(
+        &self,
+        handler: impl FnMut(RedrawFrame) + Send + 'static,
+    ) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!("winit::Window::start_redraw_thread",).entered();
+
+        self.require(
+            WindowCapabilities::REDRAW_THREAD,
+            "the window can't be drawn from another thread",
+        )?;
+        redraw_thread::start(self.id(), self.inner_size(), self.scale_factor(), Box::new(handler));
+        Ok(())
+    }
+
+    /// Stops the thread started by [`Window::start_redraw_thread()`], after the current frame is
+    /// drawn.
+    ///
+    /// [`WindowEvent::RedrawRequested`] is delivered to the [`ApplicationHandler`] again.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`ApplicationHandler`]: crate::application::ApplicationHandler
+    pub fn stop_redraw_thread(&self) {
+        let _span = tracing::debug_span!("winit::Window::stop_redraw_thread",).entered();
+
+        redraw_thread::stop(self.id());
+    }
+
+    /// Requests a frame from the thread started by [`Window::start_redraw_thread()`], from any
+    /// thread and without waking up the event loop.
+    ///
+    /// Unlike [`Window::request_redraw()`], the frame isn't aligned with the drawing loop of the
+    /// windowing system. Falls back to [`Window::request_redraw()`] if no thread was started.
+    #[inline]
+    pub fn request_redraw_threaded(&self) {
+        let _span = tracing::debug_span!("winit::Window::request_redraw_threaded",).entered();
+
+        if !redraw_thread::request(self.id()) {
+            self.request_redraw();
+        }
+    }
+
+    /// Sets the range of frame rates the window wants to be drawn at, `None` leaving the choice
+    /// to the system again.
+    ///
+    /// Displays with variable refresh rates, like ProMotion displays, only run at high rates while
+    /// an application asks for them. Request the high rates while animating, and reset the range
+    /// when the contents are still to save battery.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / macOS:** Runs a `CADisplayLink` with the range as its `preferredFrameRateRange`,
+    ///   which wakes up the event loop on every frame of the display, so
+    ///   [`WindowEvent::RedrawRequested`] is emitted in sync with the display. Only the preferred
+    ///   frame rate is used before iOS 15, and the range is ignored before macOS 14. To get frame
+    ///   rates above 60 Hz on iPhones, the application also has to set
+    ///   `CADisableMinimumFrameDurationOnPhone` in its `Info.plist`.
+    /// - **Web:** Animation frames are skipped to stay below the maximum frame rate, as browsers
+    ///   always run them at the refresh rate of the display.
+    /// - **Android / Windows / Wayland / X11 / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    #[inline]
+    pub fn set_preferred_frame_rate_range(&self, range: Option<FrameRateRange>) {
+        let _span = tracing::debug_span!(
+            "winit::Window::set_preferred_frame_rate_range",
+            range = ?range
+        )
+        .entered();
+
+        self.queue_command(move |w| w.set_preferred_frame_rate_range(range))
+    }
+
     /// Notify the windowing system before presenting to the window.
     ///
     /// You should call this event after your drawing operations, but before you submit
@@ -642,6 +861,98 @@ impl Window {
         self.window.maybe_queue_on_main(|w| w.pre_present_notify());
     }
 
+    /// Presents pixels drawn on the CPU to the window, without a GPU API or swapchain.
+    ///
+    /// `buffer` holds the rows of `size`, from the top-left, with 4 bytes per pixel in RGBA order.
+    /// The alpha channel is ignored, the pixels are opaque. The buffer should be the inner size
+    /// of the window, otherwise it's stretched or cut off depending on the platform.
+    ///
+    /// This is meant for tiny tools and test harnesses. Applications drawing frames regularly are
+    /// better off with a crate like `softbuffer`, which reuses the buffers of the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Sets the contents of the layer of the view to an image of the buffer.
+    /// - **Windows:** Copies the buffer to the window with GDI.
+    /// - **Wayland:** Attaches a `wl_shm` buffer to the surface.
+    /// - **X11:** Copies the buffer to the window with `PutImage`.
+    /// - **Web:** Copies the buffer to the canvas with `putImageData()`, which fails if the canvas
+    ///   already has another kind of context, like WebGL.
+    /// - **Android / iOS / Orbital:** Always returns [`RequestError::NotSupported`].
+    ///
+    /// Returns [`RequestError::BadImage`] if the length of `buffer` isn't
+    /// `4 * size.width * size.height`.
+    pub fn present_rgba(&self, buffer: &[u8], size: PhysicalSize<u32>) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!("winit::Window::present_rgba", ?size).entered();
+
+        let pixel_count = u64::from(size.width) * u64::from(size.height);
+        if pixel_count.checked_mul(4) != Some(buffer.len() as u64) {
+            return Err(RequestError::BadImage(BadImage::BufferSize {
+                width: size.width,
+                height: size.height,
+                byte_count: buffer.len(),
+            }));
+        }
+        self.require(WindowCapabilities::SOFTWARE_PRESENT, "pixels can't be presented")?;
+        self.apply_now(|w| w.present_rgba(buffer, size)).map_err(|error| {
+            RequestError::from_external(error, self.backend, "pixels can't be presented")
+        })
+    }
+
+    /// Reports how long the application took to draw a frame, after handling
+    /// [`WindowEvent::RedrawRequested`].
+    ///
+    /// Winit uses the frame times to leave the application time to draw. While frames are
+    /// reported, [`ControlFlow::WaitUntil`] deadlines wake up earlier by the average frame time,
+    /// so that frames paced with them are done by the deadline, and on Web, polling with
+    /// [`PollStrategy::Scheduler`] falls back to idle callbacks while frames miss their budget.
+    /// Windows that stopped reporting frames for a second are no longer taken into account.
+    ///
+    /// The statistics are available with [`Window::frame_statistics()`].
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`ControlFlow::WaitUntil`]: crate::event_loop::ControlFlow::WaitUntil
+    /// [`PollStrategy::Scheduler`]: crate::platform::web::PollStrategy::Scheduler
+    pub fn report_frame_time(&self, frame_time: Duration) {
+        let _span = tracing::debug_span!("winit::Window::report_frame_time", ?frame_time).entered();
+
+        frame_budget::report(self.id(), frame_time, || {
+            let millihertz = self.current_monitor()?.refresh_rate_millihertz()?;
+            (millihertz != 0).then(|| Duration::from_secs_f64(1000.0 / millihertz as f64))
+        });
+    }
+
+    /// Returns the statistics of the frame times reported with [`Window::report_frame_time()`],
+    /// like the average frame time and the number of frames that missed their budget.
+    ///
+    /// The budget of a frame is one refresh interval of the monitor of the window.
+    #[inline]
+    pub fn frame_statistics(&self) -> FrameStatistics {
+        frame_budget::statistics(self.id())
+    }
+
+    /// Notify winit that the first frame of the window was presented, ending the splash screen
+    /// requested with [`WindowAttributes::with_splash_screen()`].
+    ///
+    /// Call this after submitting the first frame to the windowing system, e.g. after the first
+    /// [`pre_present_notify()`] and swapping the buffers. Calling it again, or for windows
+    /// without a splash screen, does nothing.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Removes the element passed to
+    ///   [`WindowAttributesExtWebSys::with_splash_element()`] from the page.
+    /// - **Android / iOS / X11 / Wayland / Orbital:** Unsupported.
+    ///
+    /// [`pre_present_notify()`]: Self::pre_present_notify
+    /// [`WindowAttributesExtWebSys::with_splash_element()`]: crate::platform::web::WindowAttributesExtWebSys::with_splash_element
+    #[inline]
+    pub fn notify_first_frame_presented(&self) {
+        let _span = tracing::debug_span!("winit::Window::notify_first_frame_presented",).entered();
+
+        self.window.maybe_queue_on_main(|w| w.notify_first_frame_presented());
+    }
+
     /// Reset the dead key state of the keyboard.
     ///
     /// This is useful when a dead key is bound to trigger an action. Then
@@ -659,6 +970,80 @@ impl Window {
 
         self.window.maybe_queue_on_main(|w| w.reset_dead_keys())
     }
+
+    /// Returns which of the fallible operations on the window are supported.
+    ///
+    /// Operations that aren't supported fail with [`RequestError::NotSupported`] instead of doing
+    /// nothing, this allows to detect them up front.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Windows can't be positioned, their level can't be changed, and they can't be
+    ///   made unfocusable. [`WindowCapabilities::FOCUS`] requires the `xdg_activation_v1` protocol.
+    /// - **iOS:** Only [`WindowCapabilities::SET_POSITION`] is supported.
+    /// - **Web:** Only [`WindowCapabilities::SET_POSITION`], [`WindowCapabilities::CURSOR_GRAB`],
+    ///   [`WindowCapabilities::FOCUS`] and [`WindowCapabilities::FOCUSABLE`] are supported.
+    /// - **Orbital:** [`WindowCapabilities::FOCUS`] and [`WindowCapabilities::FOCUSABLE`] aren't
+    ///   supported.
+    /// - **Android:** Nothing is supported.
+    #[inline]
+    pub fn capabilities(&self) -> WindowCapabilities {
+        let _span = tracing::debug_span!("winit::Window::capabilities",).entered();
+
+        self.capabilities
+    }
+
+    /// Wrap a window that was just created, on the main thread.
+    pub(crate) fn new(window: platform_impl::Window) -> Self {
+        let capabilities = window.maybe_wait_on_main(|w| w.capabilities());
+        #[cfg(any(x11_platform, wayland_platform))]
+        let backend = if window.is_wayland() { Backend::Wayland } else { Backend::X11 };
+        #[cfg(not(any(x11_platform, wayland_platform)))]
+        let backend = crate::event_loop::NATIVE_BACKEND;
+        Self { window: Arc::new(window), capabilities, backend }
+    }
+
+    /// Fail with [`RequestError::NotSupported`] unless the window has the `capability`.
+    fn require(
+        &self,
+        capability: WindowCapabilities,
+        reason: &'static str,
+    ) -> Result<(), RequestError> {
+        if self.capabilities().contains(capability) {
+            Ok(())
+        } else {
+            Err(RequestError::NotSupported { platform: self.backend, reason })
+        }
+    }
+
+    /// Run `f` on the main thread and wait for its outcome, after applying the commands queued
+    /// before it, see the [type-level docs](Self#event-handlers).
+    fn apply_now<R: Send>(
+        &self,
+        f: impl FnOnce(&platform_impl::MainThreadWindow) -> R + Send,
+    ) -> R {
+        command_queue::flush();
+        self.window.maybe_wait_on_main(f)
+    }
+
+    /// Run `f` on the main thread, queued while an event handler is running on this thread, see
+    /// the [type-level docs](Self#event-handlers).
+    pub(crate) fn queue_command(
+        &self,
+        f: impl FnOnce(&platform_impl::MainThreadWindow) + Send + 'static,
+    ) {
+        if !command_queue::is_deferring() {
+            return self.window.maybe_queue_on_main(f);
+        }
+
+        // The window may be dropped while the command is queued.
+        let window = Arc::downgrade(&self.window);
+        command_queue::push(Box::new(move || {
+            if let Some(window) = window.upgrade() {
+                window.maybe_queue_on_main(f);
+            }
+        }));
+    }
 }
 
 /// Position and size functions.
@@ -684,6 +1069,29 @@ impl Window {
         self.window.maybe_wait_on_main(|w| w.inner_position())
     }
 
+    /// Returns a [`CoordinateTransform`] to convert between physical coordinates on the surface
+    /// of the window and logical coordinates in the coordinate space of
+    /// [`Window::inner_position`].
+    ///
+    /// The transform is only valid until the window is moved, resized or its scale factor
+    /// changes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Logical coordinates are CSS pixels relative to the viewport, like the coordinates
+    ///   of `getBoundingClientRect()` or `MouseEvent.clientX`. Accounts for CSS [`transform`]s
+    ///   scaling the canvas and for the page zoom, but not for rotation or skewing.
+    /// - **Android / Wayland:** The position of the window is unknown, so logical coordinates are
+    ///   relative to the top-left corner of the window.
+    ///
+    /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
+    #[inline]
+    pub fn coordinate_transform(&self) -> CoordinateTransform {
+        let _span = tracing::debug_span!("winit::Window::coordinate_transform",).entered();
+
+        self.window.maybe_wait_on_main(|w| w.coordinate_transform())
+    }
+
     /// Returns the position of the top-left hand corner of the window relative to the
     /// top-left hand corner of the desktop.
     ///
@@ -717,10 +1125,10 @@ impl Window {
     /// # use winit::window::Window;
     /// # fn scope(window: &Window) {
     /// // Specify the position in logical dimensions like this:
-    /// window.set_outer_position(LogicalPosition::new(400.0, 200.0));
+    /// let _ = window.set_outer_position(LogicalPosition::new(400.0, 200.0));
     ///
     /// // Or specify the position in physical dimensions like this:
-    /// window.set_outer_position(PhysicalPosition::new(400, 200));
+    /// let _ = window.set_outer_position(PhysicalPosition::new(400, 200));
     /// # }
     /// ```
     ///
@@ -730,11 +1138,11 @@ impl Window {
     ///   window in the screen space coordinate system.
     /// - **Web:** Sets the top-left coordinates relative to the viewport. Doesn't account for CSS
     ///   [`transform`].
-    /// - **Android / Wayland:** Unsupported.
+    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`].
     ///
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     #[inline]
-    pub fn set_outer_position<P: Into<Position>>(&self, position: P) {
+    pub fn set_outer_position<P: Into<Position>>(&self, position: P) -> Result<(), RequestError> {
         let position = position.into();
         let _span = tracing::debug_span!(
             "winit::Window::set_outer_position",
@@ -742,7 +1150,137 @@ impl Window {
         )
         .entered();
 
-        self.window.maybe_queue_on_main(move |w| w.set_outer_position(position))
+        self.require(
+            WindowCapabilities::SET_POSITION,
+            "the position of windows is controlled by the system",
+        )?;
+        self.queue_command(move |w| w.set_outer_position(position));
+        Ok(())
+    }
+
+    /// Centers the window inside the [work area] of a monitor.
+    ///
+    /// See [`Window::move_to_monitor`] for details.
+    ///
+    /// [work area]: MonitorHandle::work_area
+    #[inline]
+    pub fn center_on(&self, monitor: MonitorSelector) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!("winit::Window::center_on", monitor = ?monitor).entered();
+
+        let monitor = match monitor {
+            MonitorSelector::Current => self.current_monitor(),
+            MonitorSelector::Primary => self.primary_monitor(),
+            MonitorSelector::Monitor(monitor) => Some(monitor),
+        };
+        let monitor =
+            monitor.or_else(|| self.available_monitors().next()).ok_or(RequestError::Ignored)?;
+        self.move_to_monitor(&monitor, Align::Center)
+    }
+
+    /// Moves the window inside the [work area] of the `monitor`, aligned as given.
+    ///
+    /// Windows larger than the work area are placed at its top-left corner, so the title bar
+    /// stays reachable. Returns [`RequestError::Ignored`] when no monitor can be found.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`], the compositor
+    ///   places windows on its own, usually on the monitor with the pointer.
+    /// - **Web:** Always returns [`RequestError::Ignored`], as browsers don't expose monitors.
+    ///
+    /// [work area]: MonitorHandle::work_area
+    #[inline]
+    pub fn move_to_monitor(
+        &self,
+        monitor: &MonitorHandle,
+        align: Align,
+    ) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!(
+            "winit::Window::move_to_monitor",
+            monitor = ?monitor,
+            align = ?align,
+        )
+        .entered();
+
+        let position = placement::align(monitor.work_area(), self.outer_size(), align);
+        self.set_outer_position(position)
+    }
+
+    /// Moves the window below and to the right of `window`, like new documents are opened in
+    /// many applications.
+    ///
+    /// Starts over at the top-left corner of the [work area] when the window wouldn't fit on the
+    /// monitor of `window` anymore.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`], the compositor
+    ///   places windows on its own.
+    /// - **Web:** Always returns [`RequestError::Ignored`], as browsers don't expose monitors.
+    ///
+    /// [work area]: MonitorHandle::work_area
+    #[inline]
+    pub fn cascade_from(&self, window: &Window) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!("winit::Window::cascade_from").entered();
+
+        self.require(
+            WindowCapabilities::SET_POSITION,
+            "the position of windows is controlled by the system",
+        )?;
+        let from = window.outer_position().map_err(|_| RequestError::Ignored)?;
+        let monitor = window.current_monitor().ok_or(RequestError::Ignored)?;
+        let position = placement::cascade(
+            monitor.work_area(),
+            from,
+            self.outer_size(),
+            monitor.scale_factor(),
+        );
+        self.set_outer_position(position)
+    }
+
+    /// Moves the window to `position` from the top corner of the [work area] of `monitor`, in
+    /// logical pixels of that monitor.
+    ///
+    /// Unlike [`Window::set_outer_position`], the position doesn't depend on where the monitor is
+    /// on the desktop, or on the scale factor of the monitor the window is currently on. In
+    /// [`LayoutDirection::RightToLeft`], the position is from the top-right corner of the work
+    /// area to the top-right corner of the window, so the same position mirrors the placement in
+    /// right-to-left user interfaces. The direction matching the language of the user can be
+    /// found with [`LayoutDirection::of()`].
+    ///
+    /// The window is expected to keep its logical size when moving to a monitor with another
+    /// scale factor, as it does by default when handling [`WindowEvent::ScaleFactorChanged`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`], the compositor
+    ///   places windows on its own.
+    /// - **Web:** Always returns [`RequestError::Ignored`], as browsers don't expose monitors.
+    ///
+    /// [work area]: MonitorHandle::work_area
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    #[inline]
+    pub fn set_outer_position_on(
+        &self,
+        monitor: &MonitorHandle,
+        position: LogicalPosition<f64>,
+        direction: LayoutDirection,
+    ) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!(
+            "winit::Window::set_outer_position_on",
+            monitor = ?monitor,
+            position = ?position,
+            direction = ?direction,
+        )
+        .entered();
+
+        let scale_factor = monitor.scale_factor();
+        // The size the window will have on the monitor.
+        let size =
+            self.outer_size().to_logical::<f64>(self.scale_factor()).to_physical(scale_factor);
+        let position =
+            placement::relative_to(monitor.work_area(), position, size, scale_factor, direction);
+        self.set_outer_position(position)
     }
 
     /// Returns the physical size of the window's client area.
@@ -807,7 +1345,7 @@ impl Window {
             size = ?size
         )
         .entered();
-        self.window.maybe_wait_on_main(|w| w.request_inner_size(size))
+        self.apply_now(|w| w.request_inner_size(size))
     }
 
     /// Returns the physical size of the entire window.
@@ -852,7 +1390,7 @@ impl Window {
             min_size = ?min_size
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.set_min_inner_size(min_size))
+        self.queue_command(move |w| w.set_min_inner_size(min_size))
     }
 
     /// Sets a maximum dimension size for the window.
@@ -880,7 +1418,7 @@ impl Window {
             max_size = ?max_size
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.set_max_inner_size(max_size))
+        self.queue_command(move |w| w.set_max_inner_size(max_size))
     }
 
     /// Returns window resize increments if any were set.
@@ -913,7 +1451,7 @@ impl Window {
             increments = ?increments
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.set_resize_increments(increments))
+        self.queue_command(move |w| w.set_resize_increments(increments))
     }
 }
 
@@ -927,7 +1465,8 @@ impl Window {
     #[inline]
     pub fn set_title(&self, title: &str) {
         let _span = tracing::debug_span!("winit::Window::set_title", title).entered();
-        self.window.maybe_wait_on_main(|w| w.set_title(title))
+        let title = title.to_owned();
+        self.queue_command(move |w| w.set_title(&title))
     }
 
     /// Change the window transparency state.
@@ -949,7 +1488,7 @@ impl Window {
     #[inline]
     pub fn set_transparent(&self, transparent: bool) {
         let _span = tracing::debug_span!("winit::Window::set_transparent", transparent).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_transparent(transparent))
+        self.queue_command(move |w| w.set_transparent(transparent))
     }
 
     /// Change the window blur state.
@@ -963,7 +1502,7 @@ impl Window {
     #[inline]
     pub fn set_blur(&self, blur: bool) {
         let _span = tracing::debug_span!("winit::Window::set_blur", blur).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_blur(blur))
+        self.queue_command(move |w| w.set_blur(blur))
     }
 
     /// Modifies the window's visibility.
@@ -977,7 +1516,7 @@ impl Window {
     #[inline]
     pub fn set_visible(&self, visible: bool) {
         let _span = tracing::debug_span!("winit::Window::set_visible", visible).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_visible(visible))
+        self.queue_command(move |w| w.set_visible(visible))
     }
 
     /// Gets the window's current visibility state.
@@ -995,6 +1534,39 @@ impl Window {
         self.window.maybe_wait_on_main(|w| w.is_visible())
     }
 
+    /// Hides the window, showing an icon for it in the system tray, which shows the window again
+    /// when clicked.
+    ///
+    /// Applications that keep running in the background, like chat clients, can call this on
+    /// [`WindowEvent::CloseRequested`] instead of dropping the window.
+    ///
+    /// [`ApplicationHandler::surface_destroyed()`] is emitted once the window is hidden, so
+    /// renderers can release their swapchains. When the user clicks the icon, the icon is removed,
+    /// the window is shown and focused, and [`ApplicationHandler::surface_created()`] is followed
+    /// by [`ApplicationHandler::tray_activated()`]. Showing the window with
+    /// [`Window::set_visible()`] removes the icon too, without `tray_activated`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The icon is the one set with [`Window::set_window_icon()`], or the default
+    ///   application icon, with the title of the window as its tooltip. Clicking the icon or
+    ///   selecting it with the keyboard shows the window.
+    /// - **macOS:** The icon of the application is shown in the status bar, the area of the menu
+    ///   bar with status items.
+    /// - **Android / iOS / Orbital / Wayland / Web / X11:** Always returns
+    ///   [`RequestError::NotSupported`].
+    ///
+    /// [`WindowEvent::CloseRequested`]: crate::event::WindowEvent::CloseRequested
+    /// [`ApplicationHandler::surface_destroyed()`]: crate::application::ApplicationHandler::surface_destroyed
+    /// [`ApplicationHandler::surface_created()`]: crate::application::ApplicationHandler::surface_created
+    /// [`ApplicationHandler::tray_activated()`]: crate::application::ApplicationHandler::tray_activated
+    pub fn hide_to_tray(&self) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!("winit::Window::hide_to_tray",).entered();
+        self.require(WindowCapabilities::TRAY, "the system has no tray")?;
+        self.queue_command(|w| w.hide_to_tray());
+        Ok(())
+    }
+
     /// Sets whether the window is resizable or not.
     ///
     /// Note that making the window unresizable doesn't exempt you from handling
@@ -1013,7 +1585,7 @@ impl Window {
     #[inline]
     pub fn set_resizable(&self, resizable: bool) {
         let _span = tracing::debug_span!("winit::Window::set_resizable", resizable).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_resizable(resizable))
+        self.queue_command(move |w| w.set_resizable(resizable))
     }
 
     /// Gets the window's current resizable state.
@@ -1040,7 +1612,7 @@ impl Window {
             buttons = ?buttons
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.set_enabled_buttons(buttons))
+        self.queue_command(move |w| w.set_enabled_buttons(buttons))
     }
 
     /// Gets the enabled window buttons.
@@ -1063,7 +1635,7 @@ impl Window {
     #[inline]
     pub fn set_minimized(&self, minimized: bool) {
         let _span = tracing::debug_span!("winit::Window::set_minimized", minimized).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_minimized(minimized))
+        self.queue_command(move |w| w.set_minimized(minimized))
     }
 
     /// Gets the window's current minimized state.
@@ -1092,7 +1664,7 @@ impl Window {
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
         let _span = tracing::debug_span!("winit::Window::set_maximized", maximized).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_maximized(maximized))
+        self.queue_command(move |w| w.set_maximized(maximized))
     }
 
     /// Gets the window's current maximized state.
@@ -1106,6 +1678,31 @@ impl Window {
         self.window.maybe_wait_on_main(|w| w.is_maximized())
     }
 
+    /// Gets how the window is arranged on its monitor, e.g. whether it is snapped to one half of
+    /// the monitor, maximized or fullscreen.
+    ///
+    /// This lets custom title bars show the right glyph for their maximize button. Changes are
+    /// reported with [`WindowEvent::TilingStateChanged`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Snapping is only reported on Windows 11 and recent versions of Windows 10.
+    /// - **macOS:** The tiling of macOS 15 has no API, so windows filling a half or a quarter of
+    ///   the visible frame of their screen are reported as [`Tiled`].
+    /// - **X11:** Never [`Tiled`].
+    /// - **Web:** Only [`Fullscreen`] and [`Floating`] are reported.
+    /// - **iOS / Android / Orbital:** Unsupported, always [`Floating`].
+    ///
+    /// [`WindowEvent::TilingStateChanged`]: crate::event::WindowEvent::TilingStateChanged
+    /// [`Tiled`]: TilingState::Tiled
+    /// [`Fullscreen`]: TilingState::Fullscreen
+    /// [`Floating`]: TilingState::Floating
+    #[inline]
+    pub fn tiling_state(&self) -> TilingState {
+        let _span = tracing::debug_span!("winit::Window::tiling_state",).entered();
+        self.window.maybe_wait_on_main(|w| w.tiling_state())
+    }
+
     /// Sets the window to fullscreen or back.
     ///
     /// ## Platform-specific
@@ -1135,7 +1732,7 @@ impl Window {
             fullscreen = ?fullscreen
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.set_fullscreen(fullscreen.map(|f| f.into())))
+        self.queue_command(move |w| w.set_fullscreen(fullscreen.map(|f| f.into())))
     }
 
     /// Gets the window's current fullscreen state.
@@ -1160,11 +1757,13 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web:** No effect.
+    /// - **iOS / Android / Web:** Always returns [`RequestError::NotSupported`].
     #[inline]
-    pub fn set_decorations(&self, decorations: bool) {
+    pub fn set_decorations(&self, decorations: bool) -> Result<(), RequestError> {
         let _span = tracing::debug_span!("winit::Window::set_decorations", decorations).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_decorations(decorations))
+        self.require(WindowCapabilities::DECORATIONS, "windows never have decorations")?;
+        self.queue_command(move |w| w.set_decorations(decorations));
+        Ok(())
     }
 
     /// Gets the window's current decorations state.
@@ -1186,13 +1785,22 @@ impl Window {
     /// This is just a hint to the OS, and the system could ignore it.
     ///
     /// See [`WindowLevel`] for details.
-    pub fn set_window_level(&self, level: WindowLevel) {
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / Wayland:** Always returns [`RequestError::NotSupported`].
+    pub fn set_window_level(&self, level: WindowLevel) -> Result<(), RequestError> {
         let _span = tracing::debug_span!(
             "winit::Window::set_window_level",
             level = ?level
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.set_window_level(level))
+        self.require(
+            WindowCapabilities::WINDOW_LEVEL,
+            "the stacking order of windows is controlled by the system",
+        )?;
+        self.queue_command(move |w| w.set_window_level(level));
+        Ok(())
     }
 
     /// Sets the window icon.
@@ -1202,7 +1810,11 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Wayland / macOS / Orbital:** Unsupported.
+    /// - **iOS / Android / Web / macOS / Orbital:** Unsupported.
+    ///
+    /// - **Wayland:** Requires the compositor to support the xdg-toplevel-icon protocol. Icons that
+    ///   aren't square are centered in a square. See also
+    ///   `WindowAttributesExtWayland::with_icon_name`.
     ///
     /// - **Windows:** Sets `ICON_SMALL`. The base size for a window icon is 16x16, but it's
     ///   recommended to account for screen scaling and pick a multiple of that, i.e. 32x32.
@@ -1212,7 +1824,7 @@ impl Window {
     #[inline]
     pub fn set_window_icon(&self, window_icon: Option<Icon>) {
         let _span = tracing::debug_span!("winit::Window::set_window_icon",).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_window_icon(window_icon))
+        self.queue_command(move |w| w.set_window_icon(window_icon))
     }
 
     /// Set the IME cursor editing area, where the `position` is the top left corner of that area
@@ -1245,7 +1857,9 @@ impl Window {
     /// ## Platform-specific
     ///
     /// - **X11:** - area is not supported, only position.
-    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    /// - **Web:** The area is relative to the canvas when IME was allowed, and isn't updated when
+    ///   the canvas moves afterwards.
+    /// - **iOS / Android / Orbital:** Unsupported.
     ///
     /// [chinese]: https://support.apple.com/guide/chinese-input-method/use-the-candidate-window-cim12992/104/mac/12.0
     /// [japanese]: https://support.apple.com/guide/japanese-input-method/use-the-candidate-window-jpim10262/6.3/mac/12.0
@@ -1259,7 +1873,7 @@ impl Window {
             size = ?size,
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.set_ime_cursor_area(position, size))
+        self.queue_command(move |w| w.set_ime_cursor_area(position, size))
     }
 
     /// Sets whether the window should get IME events
@@ -1278,22 +1892,33 @@ impl Window {
     ///
     /// - **macOS:** IME must be enabled to receive text-input where dead-key sequences are
     ///   combined.
-    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    /// - **Web:** The keyboard input is received by a hidden text field while IME is allowed, which
+    ///   takes the focus of the canvas. Keys producing text aren't prevented from reaching it,
+    ///   regardless of [`WindowAttributesExtWebSys::with_prevent_default()`].
+    /// - **Android:** Shows the virtual keyboard, which edits the text through the
+    ///   `InputConnection` of the activity. Only supported with `GameActivity`.
+    /// - **iOS / Orbital:** Unsupported.
     /// - **X11**: Enabling IME will disable dead keys reporting during compose.
     ///
     /// [`Ime`]: crate::event::WindowEvent::Ime
     /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
+    /// [`WindowAttributesExtWebSys::with_prevent_default()`]: crate::platform::web::WindowAttributesExtWebSys::with_prevent_default
     #[inline]
     pub fn set_ime_allowed(&self, allowed: bool) {
         let _span = tracing::debug_span!("winit::Window::set_ime_allowed", allowed).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_ime_allowed(allowed))
+        self.queue_command(move |w| w.set_ime_allowed(allowed))
     }
 
     /// Sets the IME purpose for the window using [`ImePurpose`].
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+    /// - **macOS:** [`ImePurpose::Password`] enables secure event input while the window is
+    ///   focused, see `WindowExtMacOS::set_secure_input`.
+    /// - **Windows:** [`ImePurpose::Password`] disables the IME.
+    /// - **Web:** [`ImePurpose::Password`] and [`ImePurpose::Terminal`] turn off autocomplete,
+    ///   autocorrect, autocapitalization and spellchecking on the canvas.
+    /// - **iOS / Android / X11 / Orbital:** Unsupported.
     #[inline]
     pub fn set_ime_purpose(&self, purpose: ImePurpose) {
         let _span = tracing::debug_span!(
@@ -1301,7 +1926,93 @@ impl Window {
             purpose = ?purpose
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.set_ime_purpose(purpose))
+        self.queue_command(move |w| w.set_ime_purpose(purpose))
+    }
+
+    /// Sets which text services of the system act on the text input of the window.
+    ///
+    /// The default is [`TextServicesConfig::default()`], which leaves them to the system.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Controls autocorrection, spellchecking, smart quotes and dashes, and inline
+    ///   predictions. Autocapitalization is ignored.
+    /// - **Windows:** Turning off `suggestions` or `autocorrect` sets the input scope of the window
+    ///   to alphanumeric half-width, which hides the text suggestions of the touch and hardware
+    ///   keyboards. This also makes some IMEs start in alphanumeric mode. The other settings are
+    ///   ignored.
+    /// - **Web:** Sets the `autocomplete`, `autocorrect`, `autocapitalize` and `spellcheck`
+    ///   attributes of the canvas. Smart punctuation is ignored. Turning services off with
+    ///   [`ImePurpose::Password`] or [`ImePurpose::Terminal`] takes precedence.
+    /// - **iOS / Android / Wayland / X11 / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_text_services(&self, config: TextServicesConfig) {
+        let _span = tracing::debug_span!(
+            "winit::Window::set_text_services",
+            config = ?config
+        )
+        .entered();
+        self.queue_command(move |w| w.set_text_services(config))
+    }
+
+    /// Sets the kind of virtual keyboard to show for text input.
+    ///
+    /// The default is [`KeyboardHints::default()`], a text keyboard as chosen by the system.
+    /// Virtual keyboards that are already shown may only pick up the hints the next time they are
+    /// shown.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android:** Sets the input type and the action of the `EditorInfo` of the activity. Only
+    ///   supported with `GameActivity`.
+    /// - **Web:** Sets the `inputmode`, `enterkeyhint` and `autocapitalize` attributes of the
+    ///   canvas and the field receiving IME input. [`EnterKeyLabel::Default`] leaves the label to
+    ///   the browser.
+    /// - **iOS:** Unsupported, as there is no text input on iOS yet.
+    /// - **macOS / Windows / Wayland / X11 / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_keyboard_hints(&self, hints: KeyboardHints) {
+        let _span = tracing::debug_span!(
+            "winit::Window::set_keyboard_hints",
+            hints = ?hints
+        )
+        .entered();
+        self.queue_command(move |w| w.set_keyboard_hints(hints))
+    }
+
+    /// Sets the text around the cursor of the text field receiving IME input.
+    ///
+    /// The `cursor` and the `anchor` of the selection are byte offsets into `text`, and are equal
+    /// if nothing is selected. The text doesn't have to be the whole content of the field, a few
+    /// sentences around the cursor are enough. Input methods use it for reconversion of already
+    /// committed text, and to make suggestions fitting the context. They may then ask to delete
+    /// text with [`Ime::DeleteSurrounding`].
+    ///
+    /// The surrounding text should be updated whenever it changes while IME is allowed, also after
+    /// handling [`Ime::Commit`]. The call is ignored if the `cursor` or the `anchor` isn't at a
+    /// character boundary of `text`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The text is truncated to 4000 bytes around the selection.
+    /// - **Windows:** Used for reconversion and document feed requests of IMM32.
+    /// - **Android:** Sets the text of the `InputConnection`, unless text is being composed. The
+    ///   IME edits the text, so it should contain enough of the field for the IME to delete around
+    ///   the cursor.
+    /// - **iOS / Web / X11 / Orbital:** Unsupported.
+    ///
+    /// [`Ime::DeleteSurrounding`]: crate::event::Ime::DeleteSurrounding
+    /// [`Ime::Commit`]: crate::event::Ime::Commit
+    #[inline]
+    pub fn set_ime_surrounding_text(&self, text: String, cursor: usize, anchor: usize) {
+        let _span =
+            tracing::debug_span!("winit::Window::set_ime_surrounding_text", cursor, anchor,)
+                .entered();
+        let Some(text) = SurroundingText::new(text, cursor, anchor) else {
+            tracing::warn!("the cursor or the anchor of the IME surrounding text is out of bounds");
+            return;
+        };
+        self.queue_command(move |w| w.set_ime_surrounding_text(text))
     }
 
     /// Brings the window to the front and sets input focus. Has no effect if the window is
@@ -1309,7 +2020,7 @@ impl Window {
     ///
     /// This method steals input focus from other applications. Do not use this method unless
     /// you are certain that's what the user wants. Focus stealing can cause an extremely disruptive
-    /// user experience.
+    /// user experience. Use [`Window::focus_window_with`] to let the system decide instead.
     ///
     /// ## Platform-specific
     ///
@@ -1317,7 +2028,51 @@ impl Window {
     #[inline]
     pub fn focus_window(&self) {
         let _span = tracing::debug_span!("winit::Window::focus_window",).entered();
-        self.window.maybe_queue_on_main(|w| w.focus_window())
+        self.queue_command(|w| w.focus_window())
+    }
+
+    /// Brings the window to the front and sets input focus, following the focus stealing
+    /// prevention of the system according to the [`FocusReason`].
+    ///
+    /// When the system doesn't allow the window to take focus, it usually requests the user's
+    /// attention instead, like [`Window::request_user_attention`]. Has no effect if the window is
+    /// already in focus, minimized, or not visible.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Orbital:** Always returns [`RequestError::NotSupported`].
+    /// - **Wayland:** Requires the `xdg_activation_v1` protocol, returns
+    ///   [`RequestError::NotSupported`] otherwise.
+    /// - **Web:** Focuses the canvas within the page.
+    #[inline]
+    pub fn focus_window_with(&self, reason: FocusReason) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!(
+            "winit::Window::focus_window_with",
+            reason = ?reason
+        )
+        .entered();
+        self.require(WindowCapabilities::FOCUS, "windows can't request focus")?;
+        self.queue_command(move |w| w.focus_window_with(reason));
+        Ok(())
+    }
+
+    /// Sets whether the window can take the keyboard focus.
+    ///
+    /// A window which can't take focus isn't focused when it is shown or clicked, but it keeps the
+    /// focus if it has it already. It still receives pointer input.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Clicking the window still activates the application.
+    /// - **X11:** Sets the input hint of `WM_HINTS`, which window managers may ignore.
+    /// - **Web:** Sets the `tabindex` of the canvas, which is needed to receive keyboard events.
+    /// - **iOS / Android / Wayland / Orbital:** Always returns [`RequestError::NotSupported`].
+    #[inline]
+    pub fn set_focusable(&self, focusable: bool) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!("winit::Window::set_focusable", focusable).entered();
+        self.require(WindowCapabilities::FOCUSABLE, "windows can always take focus")?;
+        self.queue_command(move |w| w.set_focusable(focusable));
+        Ok(())
     }
 
     /// Gets whether the window has keyboard focus.
@@ -1331,6 +2086,37 @@ impl Window {
         self.window.maybe_wait_on_main(|w| w.has_focus())
     }
 
+    /// Gets how visible the window is to the user, combining [`is_visible`],
+    /// [`is_minimized`], [`WindowEvent::Occluded`] and [`has_focus`].
+    ///
+    /// Changes are reported with [`WindowEvent::VisibilityChanged`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Never [`Hidden`] or [`Minimized`]. The canvas is [`Occluded`] while the page is
+    ///   hidden or the canvas is scrolled out of view.
+    /// - **Windows:** Never [`Occluded`], as occlusion isn't reported.
+    /// - **Wayland:** Never [`Minimized`], as minimization isn't reported. Occlusion is reported
+    ///   through the `suspended` state of the compositor.
+    /// - **iOS / Android / Orbital:** Only [`Visible`] and [`Focused`] are reported, and
+    ///   [`WindowEvent::VisibilityChanged`] is unsupported.
+    ///
+    /// [`is_visible`]: Self::is_visible
+    /// [`is_minimized`]: Self::is_minimized
+    /// [`has_focus`]: Self::has_focus
+    /// [`WindowEvent::Occluded`]: crate::event::WindowEvent::Occluded
+    /// [`WindowEvent::VisibilityChanged`]: crate::event::WindowEvent::VisibilityChanged
+    /// [`Hidden`]: WindowVisibility::Hidden
+    /// [`Occluded`]: WindowVisibility::Occluded
+    /// [`Minimized`]: WindowVisibility::Minimized
+    /// [`Visible`]: WindowVisibility::Visible
+    /// [`Focused`]: WindowVisibility::Focused
+    #[inline]
+    pub fn visibility(&self) -> WindowVisibility {
+        let _span = tracing::debug_span!("winit::Window::visibility",).entered();
+        self.window.maybe_wait_on_main(|w| w.visibility())
+    }
+
     /// Requests user attention to the window, this has no effect if the application
     /// is already focused. How requesting for user attention manifests is platform dependent,
     /// see [`UserAttentionType`] for details.
@@ -1351,7 +2137,7 @@ impl Window {
             request_type = ?request_type
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.request_user_attention(request_type))
+        self.queue_command(move |w| w.request_user_attention(request_type))
     }
 
     /// Sets the current window theme. Use `None` to fallback to system default.
@@ -1371,7 +2157,7 @@ impl Window {
             theme = ?theme
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.set_theme(theme))
+        self.queue_command(move |w| w.set_theme(theme))
     }
 
     /// Returns the current window theme.
@@ -1398,7 +2184,119 @@ impl Window {
     pub fn set_content_protected(&self, protected: bool) {
         let _span =
             tracing::debug_span!("winit::Window::set_content_protected", protected).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_content_protected(protected))
+        self.queue_command(move |w| w.set_content_protected(protected))
+    }
+
+    /// Sets whether the window shows up in screenshots, screen recordings and screen shares.
+    ///
+    /// Unlike [`Window::set_content_protected`], [`CapturePolicy::ExcludeFromCapture`] hides the
+    /// window from captures without protecting its content, e.g. for the controls of a video
+    /// conference that shouldn't show up in the shared screen.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** [`CapturePolicy::ExcludeFromCapture`] and [`CapturePolicy::Protected`] both use
+    ///   [`NSWindowSharingNone`].
+    /// - **Windows:** Uses `WDA_EXCLUDEFROMCAPTURE` and `WDA_MONITOR` respectively. Excluding the
+    ///   window from captures requires Windows 10 version 2004, and falls back to `WDA_MONITOR`
+    ///   before.
+    /// - **iOS / Android / Wayland / X11 / Web / Orbital:** Always returns
+    ///   [`RequestError::NotSupported`].
+    ///
+    /// [`NSWindowSharingNone`]: https://developer.apple.com/documentation/appkit/nswindowsharingtype/nswindowsharingnone
+    #[inline]
+    pub fn set_capture_policy(&self, policy: CapturePolicy) -> Result<(), RequestError> {
+        let _span =
+            tracing::debug_span!("winit::Window::set_capture_policy", policy = ?policy).entered();
+
+        self.require(
+            WindowCapabilities::CAPTURE_POLICY,
+            "the system doesn't allow excluding windows from captures",
+        )?;
+        self.queue_command(move |w| w.set_capture_policy(policy));
+        Ok(())
+    }
+
+    /// Tells the system how demanding the application is, so it can trade power savings for
+    /// performance.
+    ///
+    /// The hint applies to the whole process, the strongest hint of all windows is used. It is
+    /// withdrawn when the window is dropped.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / X11:** Registers the process with Feral GameMode if `libgamemode.so.0` is
+    ///   installed. [`PerformanceHint::Realtime`] is the same as [`PerformanceHint::Game`].
+    /// - **Windows:** Opts the process out of power throttling. [`PerformanceHint::Realtime`] also
+    ///   keeps the timer resolution requested by the process while its windows are hidden. Game
+    ///   Mode itself can't be requested, Windows turns it on for the games it recognizes.
+    /// - **macOS / iOS:** Begins a user initiated activity, which keeps the system from throttling
+    ///   the process, or putting it into App Nap on macOS. [`PerformanceHint::Realtime`] makes the
+    ///   activity latency critical as well. To get frame rates above 60 Hz on iPhones with
+    ///   ProMotion, the application also has to set `CADisableMinimumFrameDurationOnPhone` in its
+    ///   `Info.plist`.
+    /// - **Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn set_performance_hint(&self, hint: PerformanceHint) {
+        let _span =
+            tracing::debug_span!("winit::Window::set_performance_hint", hint = ?hint).entered();
+        if let Some(hint) = performance_hint::set(self.id(), hint) {
+            self.queue_command(move |w| w.set_performance_hint(hint))
+        }
+    }
+
+    /// Sets how the types of data dragged into the window are negotiated, `None` restoring the
+    /// default.
+    ///
+    /// When a drag enters the window, the callback receives the MIME types offered by the source,
+    /// and returns the types the window accepts, in order of preference. The drag is rejected if
+    /// none of them were offered, unless it contains files. The data is delivered in all the
+    /// accepted types with [`WindowEvent::DroppedData`].
+    ///
+    /// By default, [`DataTransfer::TEXT`], `text/plain` and [`DataTransfer::URI_LIST`] are
+    /// accepted.
+    ///
+    /// The callback is called on the thread of the event loop, while the drag is being
+    /// negotiated, so it must return quickly.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Android / iOS / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::DroppedData`]: crate::event::WindowEvent::DroppedData
+    /// [`DataTransfer::TEXT`]: crate::event::DataTransfer::TEXT
+    /// [`DataTransfer::URI_LIST`]: crate::event::DataTransfer::URI_LIST
+    pub fn set_drop_negotiation(&self, negotiation: Option<Box<DropNegotiation>>) {
+        let _span = tracing::debug_span!("winit::Window::set_drop_negotiation").entered();
+        drop_negotiation::set(self.id(), negotiation.map(Arc::from));
+    }
+
+    /// Shows the share sheet of the system, for the user to send `data` to another application
+    /// or service.
+    ///
+    /// The sheet is shown asynchronously, [`WindowEvent::ShareCompleted`] reports whether the data
+    /// was shared.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** Shows an `NSSharingServicePicker` below the top edge of the window. The title
+    ///   is used as the subject by services which have one, like Mail.
+    /// - **Web:** Calls `navigator.share()`, which is only available in secure contexts, and which
+    ///   browsers only allow while handling user input like a click. Files aren't shared, as pages
+    ///   can't read them from paths. Returns [`RequestError::NotSupported`] if the browser doesn't
+    ///   implement it.
+    /// - **Windows:** Shows the Share UI of `DataTransferManager`, on Windows 10 or later. Files
+    ///   aren't shared. Sharing is only reported as completed on Windows 10 version 1709 or later,
+    ///   and as cancelled on version 2004 or later.
+    /// - **Android / iOS / Orbital / Wayland / X11:** Always returns
+    ///   [`RequestError::NotSupported`].
+    ///
+    /// [`WindowEvent::ShareCompleted`]: crate::event::WindowEvent::ShareCompleted
+    pub fn share(&self, data: ShareData) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!("winit::Window::share", data = ?data).entered();
+        self.require(WindowCapabilities::SHARE, "the system has no share sheet")?;
+        self.queue_command(move |w| w.share(data));
+        Ok(())
     }
 
     /// Gets the current title of the window.
@@ -1426,7 +2324,44 @@ impl Window {
     pub fn set_cursor(&self, cursor: impl Into<Cursor>) {
         let cursor = cursor.into();
         let _span = tracing::debug_span!("winit::Window::set_cursor",).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_cursor(cursor))
+        self.queue_command(move |w| w.set_cursor(cursor))
+    }
+
+    /// Modifies the cursor icon of the window for a single pointer device.
+    ///
+    /// On systems with several pointers, each of them can show its own cursor over the window.
+    /// The `device_id` is the one reported by the pointer events, e.g.
+    /// [`WindowEvent::CursorMoved`](crate::event::WindowEvent::CursorMoved).
+    ///
+    /// Unlike [`Window::set_cursor()`], this doesn't take [`Window::set_cursor_visible()`] into
+    /// account, and the cursor isn't restored when the cursor visibility changes.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sets the cursor of an XInput2 master pointer.
+    /// - **Wayland:** Sets the cursor of the pointers of a seat, which is kept when the pointers
+    ///   leave and enter the window again.
+    /// - **Android / iOS / macOS / Orbital / Web / Windows:** Always returns
+    ///   [`RequestError::NotSupported`].
+    #[inline]
+    pub fn set_cursor_for_device(
+        &self,
+        device_id: DeviceId,
+        cursor: impl Into<Cursor>,
+    ) -> Result<(), RequestError> {
+        let cursor = cursor.into();
+        let _span = tracing::debug_span!(
+            "winit::Window::set_cursor_for_device",
+            device_id = ?device_id
+        )
+        .entered();
+        self.apply_now(|w| w.set_cursor_for_device(device_id, cursor)).map_err(|error| {
+            RequestError::from_external(
+                error,
+                self.backend,
+                "the cursor can't be set per pointer device",
+            )
+        })
     }
 
     /// Deprecated! Use [`Window::set_cursor()`] instead.
@@ -1462,7 +2397,30 @@ impl Window {
             position = ?position
         )
         .entered();
-        self.window.maybe_wait_on_main(|w| w.set_cursor_position(position))
+        self.apply_now(|w| w.set_cursor_position(position))
+    }
+
+    /// Returns the position of the cursor relative to the top-left corner of the window's client
+    /// area.
+    ///
+    /// This allows initializing hover state right after creating the window, without waiting for
+    /// the first [`WindowEvent::CursorMoved`]. The position may be outside of the window.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Only known while the cursor is over the window, returns
+    ///   [`RequestError::Ignored`] otherwise.
+    /// - **Web:** Returns the position of the last mouse event received by the canvas, or
+    ///   [`RequestError::Ignored`] if there was none.
+    /// - **Android / iOS / Orbital:** Always returns [`RequestError::NotSupported`].
+    ///
+    /// [`WindowEvent::CursorMoved`]: crate::event::WindowEvent::CursorMoved
+    #[inline]
+    pub fn cursor_position(&self) -> Result<PhysicalPosition<f64>, RequestError> {
+        let _span = tracing::debug_span!("winit::Window::cursor_position",).entered();
+        self.window.maybe_wait_on_main(|w| w.cursor_position()).map_err(|error| {
+            RequestError::from_external(error, self.backend, "the cursor position is unknown")
+        })
     }
 
     /// Set grabbing [mode][CursorGrabMode] on the cursor preventing it from leaving the window.
@@ -1480,14 +2438,89 @@ impl Window {
     ///     .unwrap();
     /// # }
     /// ```
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android:** Always returns [`RequestError::NotSupported`].
     #[inline]
-    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), ExternalError> {
+    pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
         let _span = tracing::debug_span!(
             "winit::Window::set_cursor_grab",
             mode = ?mode
         )
         .entered();
-        self.window.maybe_wait_on_main(|w| w.set_cursor_grab(mode))
+        self.require(WindowCapabilities::CURSOR_GRAB, "there is no cursor to grab")?;
+        self.apply_now(|w| w.set_cursor_grab(mode)).map_err(|error| {
+            RequestError::from_external(error, self.backend, "the grab mode is not supported")
+        })
+    }
+
+    /// Captures or releases the pointer `device_id`, so the window keeps receiving its events
+    /// while it is outside of the window.
+    ///
+    /// This is useful for sliders and other widgets that are dragged, which would otherwise stop
+    /// receiving [`WindowEvent::CursorMoved`] as soon as the cursor leaves the window. The
+    /// positions of the events are relative to the window and can be outside of it. The capture
+    /// ends when it is released, or when the system takes it away, e.g. because another window is
+    /// activated.
+    ///
+    /// Unlike [`Window::set_cursor_grab()`], the cursor can still move freely.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Uses [`setPointerCapture()`], which only works while the pointer is pressed, and
+    ///   returns [`RequestError::Ignored`] otherwise. Captures end when the pointer is released.
+    /// - **Windows:** Uses `SetCapture()`, `device_id` is ignored since there is only one cursor.
+    /// - **X11:** Grabs the master pointer `device_id` with XInput 2, which fails if another client
+    ///   grabbed it already.
+    /// - **macOS:** Always returns [`RequestError::NotSupported`], but drags that start in the
+    ///   window are always delivered to it until the button is released.
+    /// - **Wayland:** Always returns [`RequestError::NotSupported`], but the compositor delivers
+    ///   drags that start in the window to it until the button is released.
+    /// - **iOS / Android / Orbital:** Always returns [`RequestError::NotSupported`].
+    ///
+    /// [`WindowEvent::CursorMoved`]: crate::event::WindowEvent::CursorMoved
+    /// [`setPointerCapture()`]: https://developer.mozilla.org/en-US/docs/Web/API/Element/setPointerCapture
+    #[inline]
+    pub fn set_pointer_capture(
+        &self,
+        device_id: DeviceId,
+        capture: bool,
+    ) -> Result<(), RequestError> {
+        let _span = tracing::debug_span!(
+            "winit::Window::set_pointer_capture",
+            device_id = ?device_id,
+            capture
+        )
+        .entered();
+        self.require(WindowCapabilities::POINTER_CAPTURE, "pointers can't be captured")?;
+        self.apply_now(|w| w.set_pointer_capture(device_id, capture)).map_err(|error| {
+            RequestError::from_external(error, self.backend, "the pointer can't be captured")
+        })
+    }
+
+    /// Enables or disables detecting long presses of fingers on the window.
+    ///
+    /// A finger that stays down for [`LongPressGesture::duration`] without moving further than
+    /// [`LongPressGesture::slop`] emits [`WindowEvent::LongPress`]. This is implemented by winit
+    /// itself on top of [`WindowEvent::Touch`], so it behaves the same on all platforms instead
+    /// of following the very different thresholds of each system.
+    ///
+    /// Disabled by default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** The system synthesizes right clicks from press and hold on its own, unless
+    ///   touch input isn't promoted to mouse input.
+    /// - **macOS / Orbital / Wayland / X11:** Has no effect, as no [`WindowEvent::Touch`] is
+    ///   emitted.
+    ///
+    /// [`WindowEvent::LongPress`]: crate::event::WindowEvent::LongPress
+    /// [`WindowEvent::Touch`]: crate::event::WindowEvent::Touch
+    pub fn set_long_press(&self, gesture: Option<LongPressGesture>) {
+        let _span =
+            tracing::debug_span!("winit::Window::set_long_press", gesture = ?gesture).entered();
+        long_press::set(self.id(), gesture);
     }
 
     /// Modifies the cursor's visibility.
@@ -1505,7 +2538,7 @@ impl Window {
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         let _span = tracing::debug_span!("winit::Window::set_cursor_visible", visible).entered();
-        self.window.maybe_queue_on_main(move |w| w.set_cursor_visible(visible))
+        self.queue_command(move |w| w.set_cursor_visible(visible))
     }
 
     /// Moves the window with the left mouse button until the button is released.
@@ -1522,7 +2555,7 @@ impl Window {
     #[inline]
     pub fn drag_window(&self) -> Result<(), ExternalError> {
         let _span = tracing::debug_span!("winit::Window::drag_window",).entered();
-        self.window.maybe_wait_on_main(|w| w.drag_window())
+        self.apply_now(|w| w.drag_window())
     }
 
     /// Resizes the window with the left mouse button until the button is released.
@@ -1541,7 +2574,7 @@ impl Window {
             direction = ?direction
         )
         .entered();
-        self.window.maybe_wait_on_main(|w| w.drag_resize_window(direction))
+        self.apply_now(|w| w.drag_resize_window(direction))
     }
 
     /// Show [window menu] at a specified position .
@@ -1560,7 +2593,7 @@ impl Window {
             position = ?position
         )
         .entered();
-        self.window.maybe_queue_on_main(move |w| w.show_window_menu(position))
+        self.queue_command(move |w| w.show_window_menu(position))
     }
 
     /// Modifies whether the window catches cursor events.
@@ -1571,11 +2604,37 @@ impl Window {
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    /// - **iOS / Android / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    /// - **Web:** Sets the `pointer-events` CSS property of the canvas.
     #[inline]
     pub fn set_cursor_hittest(&self, hittest: bool) -> Result<(), ExternalError> {
         let _span = tracing::debug_span!("winit::Window::set_cursor_hittest", hittest).entered();
-        self.window.maybe_wait_on_main(|w| w.set_cursor_hittest(hittest))
+        self.apply_now(|w| w.set_cursor_hittest(hittest))
+    }
+
+    /// Makes only the given regions of the window catch cursor events.
+    ///
+    /// Events outside of the regions are passed through the window like with
+    /// [`Window::set_cursor_hittest`], such that any other window behind it receives them. This
+    /// lets overlays make only their controls interactive. The regions are relative to the
+    /// top-left corner of the surface, an empty list passes all events through.
+    ///
+    /// The regions are removed again by [`Window::set_cursor_hittest`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / Web:** The window switches between catching and passing through cursor
+    ///   events as the cursor moves in and out of the regions, so the first event after entering a
+    ///   region can still go to the window behind.
+    /// - **iOS / Android / Orbital:** Always returns an [`ExternalError::NotSupported`].
+    #[inline]
+    pub fn set_hittest_regions(&self, regions: &[Region]) -> Result<(), ExternalError> {
+        let _span = tracing::debug_span!(
+            "winit::Window::set_hittest_regions",
+            regions = ?regions
+        )
+        .entered();
+        self.apply_now(|w| w.set_hittest_regions(regions))
     }
 }
 
@@ -1762,6 +2821,66 @@ pub enum Theme {
     Dark,
 }
 
+/// Why the application wants to focus a window, see [`Window::focus_window_with`].
+///
+/// Systems prevent applications from stealing the focus from each other, so that the user doesn't
+/// type into the wrong window. The reason tells the system whether focusing the window was asked
+/// for by the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FocusReason {
+    /// The user asked for the window, e.g. by clicking a button in another window of the
+    /// application or pressing a shortcut.
+    ///
+    /// The window is focused even when the application isn't, like with
+    /// [`Window::focus_window`].
+    UserInitiated,
+
+    /// Another application, or another window of this one, handed its focus over with an
+    /// [`ActivationToken`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** The system checks the token, which is obtained with
+    ///   `WindowExtStartupNotify::request_activation_token`.
+    /// - **Windows / macOS / Web:** Handled like [`FocusReason::Programmatic`], applications hand
+    ///   over focus through the system instead.
+    Token(ActivationToken),
+
+    /// The application wants the window focused on its own, e.g. because a long running task
+    /// finished.
+    ///
+    /// The window is only focused when the system allows it, usually when the application is
+    /// focused already.
+    Programmatic,
+}
+
+/// A rectangle of a window, relative to the top-left corner of its surface.
+///
+/// See [`Window::set_hittest_regions`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Region {
+    pub position: Position,
+    pub size: Size,
+}
+
+impl Region {
+    /// Creates a region from its top-left corner and its size.
+    #[inline]
+    pub fn new<P: Into<Position>, S: Into<Size>>(position: P, size: S) -> Self {
+        Self { position: position.into(), size: size.into() }
+    }
+
+    /// Whether the region contains the `point` on the surface.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn contains(&self, point: PhysicalPosition<f64>, scale_factor: f64) -> bool {
+        let position: PhysicalPosition<f64> = self.position.to_physical(scale_factor);
+        let size: PhysicalSize<f64> = self.size.to_physical(scale_factor);
+        (position.x..position.x + size.width).contains(&point.x)
+            && (position.y..position.y + size.height).contains(&point.y)
+    }
+}
+
 /// ## Platform-specific
 ///
 /// - **X11:** Sets the WM's `XUrgencyHint`. No distinction between [`Critical`] and
@@ -1786,6 +2905,164 @@ pub enum UserAttentionType {
     Informational,
 }
 
+/// How visible a window is to the user, see [`Window::visibility`].
+///
+/// The states are ordered from least to most visible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WindowVisibility {
+    /// The window is hidden, see [`Window::set_visible`].
+    Hidden,
+    /// The window is minimized.
+    Minimized,
+    /// The window is shown, but can't be seen by the user, e.g. because it is covered by other
+    /// windows or in a background tab.
+    Occluded,
+    /// The window can be seen by the user, but doesn't have keyboard focus.
+    Visible,
+    /// The window can be seen by the user and has keyboard focus.
+    Focused,
+}
+
+impl WindowVisibility {
+    pub(crate) fn new(visible: bool, minimized: bool, occluded: bool, focused: bool) -> Self {
+        if !visible {
+            Self::Hidden
+        } else if minimized {
+            Self::Minimized
+        } else if occluded {
+            Self::Occluded
+        } else if focused {
+            Self::Focused
+        } else {
+            Self::Visible
+        }
+    }
+}
+
+/// How a window is arranged on its monitor, see [`Window::tiling_state`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TilingState {
+    /// The window can be moved and resized freely.
+    #[default]
+    Floating,
+    /// The window is snapped against the given edges of its monitor, e.g. to its left half or a
+    /// corner.
+    ///
+    /// The edges are empty if the window is tiled without touching an edge of the monitor, like
+    /// in the middle column of a tiling layout.
+    Tiled(TiledEdges),
+    /// The window is maximized, keeping its decorations.
+    Maximized,
+    /// The window is fullscreen, see [`Window::fullscreen`].
+    Fullscreen,
+}
+
+bitflags::bitflags! {
+    /// The edges of its monitor a window is tiled against, see [`TilingState::Tiled`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct TiledEdges: u8 {
+        const LEFT = 1 << 0;
+        const RIGHT = 1 << 1;
+        const TOP = 1 << 2;
+        const BOTTOM = 1 << 3;
+    }
+}
+
+/// Converts between physical coordinates on the surface of a window and logical coordinates in
+/// the coordinate space of [`Window::inner_position`], see [`Window::coordinate_transform`].
+///
+/// Unlike [`LogicalPosition::to_physical`], this takes into account where the window is placed,
+/// and that the window might be scaled differently than its scale factor implies, e.g. by a CSS
+/// [`transform`] on Web.
+///
+/// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinateTransform {
+    origin: LogicalPosition<f64>,
+    scale_x: f64,
+    scale_y: f64,
+}
+
+impl CoordinateTransform {
+    /// `origin` is the logical position of the top-left corner of the surface and the scales are
+    /// the amount of physical pixels per logical pixel.
+    pub(crate) fn new(origin: LogicalPosition<f64>, scale_x: f64, scale_y: f64) -> Self {
+        Self { origin, scale_x, scale_y }
+    }
+
+    /// A transform for windows which are only scaled by their scale factor.
+    #[allow(dead_code)] // Not used on every platform
+    pub(crate) fn from_scale_factor(
+        inner_position: Option<PhysicalPosition<i32>>,
+        scale_factor: f64,
+    ) -> Self {
+        let origin = inner_position.unwrap_or_default().to_logical(scale_factor);
+        Self::new(origin, scale_factor, scale_factor)
+    }
+
+    /// Converts a logical position, e.g. of an external cursor or element, to a physical position
+    /// relative to the top-left corner of the window's surface.
+    pub fn to_physical<P: Pixel, X: Pixel>(
+        &self,
+        position: LogicalPosition<P>,
+    ) -> PhysicalPosition<X> {
+        let position: LogicalPosition<f64> = position.cast();
+        PhysicalPosition::new(
+            (position.x - self.origin.x) * self.scale_x,
+            (position.y - self.origin.y) * self.scale_y,
+        )
+        .cast()
+    }
+
+    /// Converts a physical position relative to the top-left corner of the window's surface, e.g.
+    /// from [`WindowEvent::CursorMoved`], to a logical position.
+    ///
+    /// [`WindowEvent::CursorMoved`]: crate::event::WindowEvent::CursorMoved
+    pub fn to_logical<P: Pixel, X: Pixel>(
+        &self,
+        position: PhysicalPosition<P>,
+    ) -> LogicalPosition<X> {
+        let position: PhysicalPosition<f64> = position.cast();
+        LogicalPosition::new(
+            position.x / self.scale_x + self.origin.x,
+            position.y / self.scale_y + self.origin.y,
+        )
+        .cast()
+    }
+}
+
+bitflags::bitflags! {
+    /// The fallible operations supported by a window, see [`Window::capabilities()`].
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct WindowCapabilities: u32 {
+        /// The window can be moved with [`Window::set_outer_position()`].
+        const SET_POSITION = 1 << 0;
+        /// The window level can be changed with [`Window::set_window_level()`].
+        const WINDOW_LEVEL = 1 << 1;
+        /// Decorations can be toggled with [`Window::set_decorations()`].
+        const DECORATIONS = 1 << 2;
+        /// The cursor can be grabbed with [`Window::set_cursor_grab()`].
+        const CURSOR_GRAB = 1 << 3;
+        /// The window can request focus with [`Window::focus_window_with()`].
+        const FOCUS = 1 << 4;
+        /// Whether the window can take focus can be changed with [`Window::set_focusable()`].
+        const FOCUSABLE = 1 << 5;
+        /// The window can be excluded from captures with [`Window::set_capture_policy()`].
+        const CAPTURE_POLICY = 1 << 6;
+        /// Pointers can be captured with [`Window::set_pointer_capture()`].
+        const POINTER_CAPTURE = 1 << 7;
+        /// The window can be drawn from another thread with [`Window::start_redraw_thread()`].
+        const REDRAW_THREAD = 1 << 8;
+        /// Data can be shared with [`Window::share()`].
+        const SHARE = 1 << 9;
+        /// The window can be hidden to the tray with [`Window::hide_to_tray()`].
+        const TRAY = 1 << 10;
+        /// Pixels can be presented without a GPU with [`Window::present_rgba()`].
+        const SOFTWARE_PRESENT = 1 << 11;
+    }
+}
+
 bitflags::bitflags! {
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
     pub struct WindowButtons: u32 {
@@ -1802,7 +3079,7 @@ bitflags::bitflags! {
 ///
 /// ## Platform-specific
 ///
-/// - **iOS / Android / Web / Wayland:** Unsupported.
+/// - **iOS / Android / Web / Wayland:** Unsupported, see [`WindowCapabilities::WINDOW_LEVEL`].
 #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
 pub enum WindowLevel {
     /// The window will always be below normal windows.
@@ -1818,6 +3095,256 @@ pub enum WindowLevel {
     AlwaysOnTop,
 }
 
+/// Whether a window shows up in captures of the screen, see [`Window::set_capture_policy`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum CapturePolicy {
+    /// The window is captured like any other window.
+    #[default]
+    Allow,
+    /// The window is left out of captures, which show what is behind it instead.
+    ExcludeFromCapture,
+    /// The content of the window is protected from being captured, which shows a black
+    /// rectangle in its place.
+    Protected,
+}
+
+/// Data to share with another application, see [`Window::share`].
+///
+/// Fields which are `None` or empty are left out, at least one of them has to be set.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct ShareData {
+    /// The title of the shared content, like the subject of an email.
+    pub title: Option<String>,
+    /// Text to share.
+    pub text: Option<String>,
+    /// A URL to share.
+    pub url: Option<String>,
+    /// Files to share, like a screenshot.
+    pub files: Vec<PathBuf>,
+}
+
+impl ShareData {
+    /// Set the title of the shared content.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the text to share.
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// Set the URL to share.
+    pub fn with_url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Add a file to share.
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.files.push(path.into());
+        self
+    }
+}
+
+/// Chooses the accepted types of data dragged into a window from the offered MIME types, see
+/// [`Window::set_drop_negotiation`].
+pub type DropNegotiation = dyn Fn(&[String]) -> Vec<String> + Send + Sync;
+
+/// How demanding the application is, see [`Window::set_performance_hint`].
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum PerformanceHint {
+    /// The system balances power and performance as usual.
+    #[default]
+    Default,
+    /// A game, which wants the CPU and GPU clocked up while it is running.
+    Game,
+    /// An application with deadlines, like audio or video production, which in addition wants
+    /// timers and wake ups to be precise.
+    Realtime,
+}
+
+/// A range of frame rates in Hz, see [`Window::set_preferred_frame_rate_range`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameRateRange {
+    /// The lowest frame rate that is acceptable.
+    pub minimum: f32,
+    /// The highest frame rate that is useful.
+    pub maximum: f32,
+    /// The frame rate the window is drawn at ideally, between `minimum` and `maximum`.
+    pub preferred: f32,
+}
+
+impl FrameRateRange {
+    /// Creates a range, with `minimum` and `maximum` swapped if needed and `preferred` clamped
+    /// between them.
+    pub fn new(minimum: f32, maximum: f32, preferred: f32) -> Self {
+        let (minimum, maximum) =
+            if minimum <= maximum { (minimum, maximum) } else { (maximum, minimum) };
+        Self { minimum, maximum, preferred: preferred.clamp(minimum, maximum) }
+    }
+}
+
+/// How the audio of the application mixes with the audio of other applications, set when
+/// creating a window on iOS and Android.
+///
+/// Interruptions of the audio, e.g. by a phone call, are reported with
+/// [`ApplicationHandler::audio_interrupted()`] and [`ApplicationHandler::audio_resumed()`].
+///
+/// [`ApplicationHandler::audio_interrupted()`]: crate::application::ApplicationHandler::audio_interrupted
+/// [`ApplicationHandler::audio_resumed()`]: crate::application::ApplicationHandler::audio_resumed
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum AudioSessionCategory {
+    /// Audio that isn't essential, like the sound effects of a game, which mixes with the audio of
+    /// other applications and is silenced by the silent switch.
+    Ambient,
+    /// Audio that is essential, like music or the soundtrack of a video, which plays while the
+    /// silent switch is on.
+    Playback,
+    /// Recording audio, which silences the playback of the application.
+    Record,
+}
+
+/// How long presses are detected, see [`Window::set_long_press`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LongPressGesture {
+    /// How long the finger has to stay down.
+    pub duration: Duration,
+    /// How far the finger may move in the meantime, in physical pixels.
+    pub slop: f64,
+    /// Whether to emit a right click with [`WindowEvent::MouseInput`] when the finger is lifted
+    /// after a long press, for applications that only handle mouse input.
+    ///
+    /// [`WindowEvent::MouseInput`]: crate::event::WindowEvent::MouseInput
+    pub secondary_click: bool,
+}
+
+impl Default for LongPressGesture {
+    fn default() -> Self {
+        Self { duration: Duration::from_millis(500), slop: 10.0, secondary_click: false }
+    }
+}
+
+/// Which text services of the system act on text input, see [`Window::set_text_services`].
+///
+/// Text editors for code and terminals usually want to turn all of them off, as they receive the
+/// text verbatim through [`Ime`] events and can't undo changes made behind their back.
+///
+/// [`Ime`]: crate::event::Ime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TextServicesConfig {
+    /// Automatically correct misspelled words.
+    pub autocorrect: bool,
+    /// Replace quotes and dashes with their typographic variants.
+    pub smart_punctuation: bool,
+    /// Suggest how to complete or continue the text.
+    pub suggestions: bool,
+    /// Capitalize the first letter of sentences.
+    pub autocapitalize: bool,
+    /// Mark misspelled words.
+    pub spellcheck: bool,
+}
+
+impl TextServicesConfig {
+    /// Turn off all text services.
+    pub const fn disabled() -> Self {
+        Self {
+            autocorrect: false,
+            smart_punctuation: false,
+            suggestions: false,
+            autocapitalize: false,
+            spellcheck: false,
+        }
+    }
+}
+
+impl Default for TextServicesConfig {
+    /// Leave all text services to the system.
+    fn default() -> Self {
+        Self {
+            autocorrect: true,
+            smart_punctuation: true,
+            suggestions: true,
+            autocapitalize: true,
+            spellcheck: true,
+        }
+    }
+}
+
+/// The kind of virtual keyboard to show for text input, see [`Window::set_keyboard_hints`].
+///
+/// Applications drawing their own forms set these for the focused field, so that virtual keyboards
+/// offer the right layout and action key.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyboardHints {
+    /// The layout of the keyboard.
+    pub kind: KeyboardKind,
+    /// The label of the enter key.
+    pub enter_label: EnterKeyLabel,
+    /// What to capitalize automatically, `None` to follow
+    /// [`TextServicesConfig::autocapitalize`].
+    pub autocap: Option<Autocapitalization>,
+}
+
+/// The layout of a virtual keyboard, see [`KeyboardHints`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum KeyboardKind {
+    /// Text in any language.
+    #[default]
+    Text,
+    /// Whole numbers.
+    Numeric,
+    /// Numbers with a fractional part.
+    Decimal,
+    /// Telephone numbers.
+    Phone,
+    /// An email address.
+    Email,
+    /// A URL.
+    Url,
+    /// A search query.
+    Search,
+}
+
+/// The label of the enter key of a virtual keyboard, see [`KeyboardHints`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum EnterKeyLabel {
+    /// Leave the label to the system.
+    #[default]
+    Default,
+    /// Insert a new line.
+    Enter,
+    /// Finish the input and close the keyboard.
+    Done,
+    /// Go to the entered target, like a URL.
+    Go,
+    /// Move to the next field.
+    Next,
+    /// Move to the previous field.
+    Previous,
+    /// Search for the entered text.
+    Search,
+    /// Send the entered text, like a message.
+    Send,
+}
+
+/// What to capitalize automatically, see [`KeyboardHints`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Autocapitalization {
+    /// Nothing.
+    None,
+    /// The first letter of sentences.
+    Sentences,
+    /// The first letter of words.
+    Words,
+    /// All letters.
+    Characters,
+}
+
 /// Generic IME purposes for use in [`Window::set_ime_purpose`].
 ///
 /// The purpose may improve UX by optimizing the IME for the specific use case,
@@ -1825,7 +3352,7 @@ pub enum WindowLevel {
 ///
 /// ## Platform-specific
 ///
-/// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+/// - **iOS / Android / X11 / Orbital:** Unsupported.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 #[non_exhaustive]
 pub enum ImePurpose {
@@ -31,6 +31,12 @@ fn ids_send() {
 
 #[test]
 fn custom_cursor_send() {
+    needs_send::<winit::window::CursorImage>();
     needs_send::<winit::window::CustomCursorSource>();
     needs_send::<winit::window::CustomCursor>();
 }
+
+#[test]
+fn dialog_future_send() {
+    needs_send::<winit::dialog::DialogFuture<winit::dialog::DialogButton>>();
+}
@@ -23,6 +23,7 @@ fn window_builder_sync() {
 
 #[test]
 fn custom_cursor_sync() {
+    needs_sync::<winit::window::CursorImage>();
     needs_sync::<winit::window::CustomCursorSource>();
     needs_sync::<winit::window::CustomCursor>();
 }